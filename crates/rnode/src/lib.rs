@@ -1,11 +1,11 @@
 //! RNode: Node with node id
 
-use std::sync::Arc;
+use std::{ops::ControlFlow, sync::Arc};
 
 pub use rnode_macros::define_rnode;
 use serde::{Deserialize, Serialize};
 use stc_visit::Visitable;
-pub use stc_visit::{Fold, FoldWith, Visit, VisitMut, VisitMutWith, VisitWith};
+pub use stc_visit::{Fold, FoldWith, TryVisit, TryVisitWith, TryVisitor, Visit, VisitMut, VisitMutWith, VisitWith};
 use swc_common::{EqIgnoreSpan, TypeEq};
 
 /// Alternative for span. This is much more reliable than span.
@@ -50,6 +50,13 @@ impl<V: ?Sized> VisitMutWith<V> for NodeId {
     fn visit_mut_children_with(&mut self, _: &mut V) {}
 }
 
+/// Noop.
+impl<V: ?Sized + TryVisitor> TryVisitWith<V> for NodeId {
+    fn try_visit_children_with(&self, _: &mut V) -> ControlFlow<V::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
 /// Noop.
 impl<V: ?Sized> FoldWith<V> for NodeId {
     fn fold_children_with(self, _: &mut V) -> Self {
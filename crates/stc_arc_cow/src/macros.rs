@@ -2,7 +2,7 @@ macro_rules! impl_traits {
     ($Ty:tt, $Raw:ident) => {
         use std::ops::Deref;
 
-        use stc_visit::{FoldWith, VisitMutWith, VisitWith, Visitable};
+        use stc_visit::{FoldWith, TryVisitWith, TryVisitor, VisitMutWith, VisitWith, Visitable};
         use swc_common::{EqIgnoreSpan, Spanned, TypeEq};
 
         impl<T> Spanned for $Ty<T>
@@ -186,6 +186,17 @@ macro_rules! impl_traits {
             }
         }
 
+        impl<T, V> TryVisitWith<V> for $Ty<T>
+        where
+            V: ?Sized + TryVisitor,
+            T: TryVisitWith<V>,
+        {
+            #[inline]
+            fn try_visit_children_with(&self, v: &mut V) -> std::ops::ControlFlow<V::Break> {
+                (**self).try_visit_children_with(v)
+            }
+        }
+
         impl<T, V> VisitMutWith<V> for $Ty<T>
         where
             V: ?Sized,
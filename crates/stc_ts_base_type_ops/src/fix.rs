@@ -1,5 +1,8 @@
 use rnode::{VisitMut, VisitMutWith};
-use stc_ts_types::{Array, Conditional, FnParam, Intersection, KeywordTypeMetadata, Type, TypeOrSpread, TypeParam, Union, Valid};
+use stc_ts_types::{
+    Array, Conditional, FnParam, Intersection, KeywordTypeMetadata, RestType, Tuple, Type, TypeOrSpread, TypeParam, Union, UnionMembers,
+    Valid,
+};
 use swc_common::TypeEq;
 
 pub trait Fix: Sized {
@@ -69,22 +72,15 @@ impl VisitMut<Union> for Fixer {
             ty.assert_valid();
         }
 
-        let mut new: Vec<Type> = Vec::with_capacity(u.types.capacity());
+        let mut new = UnionMembers::with_capacity(u.types.capacity());
         for ty in u.types.drain(..) {
             if ty.is_never() {
                 continue;
             }
 
-            if new.iter().any(|stored| stored.type_eq(&ty)) {
-                continue;
-            }
-
             if ty.is_union_type() {
                 let u = ty.expect_union_type();
                 for ty in u.types {
-                    if new.iter().any(|stored| stored.type_eq(&ty)) {
-                        continue;
-                    }
                     if ty.is_never() {
                         continue;
                     }
@@ -95,7 +91,8 @@ impl VisitMut<Union> for Fixer {
 
             new.push(ty);
         }
-        u.types = new;
+        new.reduce();
+        u.types = new.into_vec();
     }
 }
 
@@ -128,6 +125,40 @@ impl VisitMut<Intersection> for Fixer {
     }
 }
 
+/// Flattens spreads of tuple types, like `[...[string, number], boolean]`,
+/// which are created by instantiating variadic tuple types.
+impl VisitMut<Tuple> for Fixer {
+    fn visit_mut(&mut self, tuple: &mut Tuple) {
+        tuple.visit_mut_children_with(self);
+
+        fn is_tuple_spread(ty: &Type) -> bool {
+            match ty.normalize() {
+                Type::Rest(RestType { ty, .. }) => ty.normalize().is_tuple(),
+                _ => false,
+            }
+        }
+
+        if !tuple.elems.iter().any(|elem| is_tuple_spread(&elem.ty)) {
+            return;
+        }
+
+        let mut elems = Vec::with_capacity(tuple.elems.len());
+        for elem in tuple.elems.drain(..) {
+            if !is_tuple_spread(&elem.ty) {
+                elems.push(elem);
+                continue;
+            }
+
+            if let Type::Rest(rest) = (*elem.ty).foldable() {
+                if let Type::Tuple(inner) = (*rest.ty).foldable() {
+                    elems.extend(inner.elems);
+                }
+            }
+        }
+        tuple.elems = elems;
+    }
+}
+
 impl Fixer {
     fn fix_type(&mut self, ty: &mut Type) {
         if matches!(ty, Type::Arc(..)) {
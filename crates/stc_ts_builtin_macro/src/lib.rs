@@ -165,6 +165,32 @@ pub fn builtin(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 }
             }
         ));
+        let name_expr = syn::ExprMatch {
+            attrs: vec![],
+            match_token: call_site(),
+            expr: q().quote_with(smart_quote!(Vars {}, { self })).parse(),
+            brace_token: call_site(),
+            arms: files
+                .iter()
+                .map(|(_, f)| {
+                    let name = syn::Ident::new(&name_for(f), call_site());
+                    let s = f.replace(".d.ts", "");
+
+                    q!(Vars { name: &name, s: &s }, { Lib::name => s }).parse()
+                })
+                .collect(),
+        };
+        tokens = tokens.quote_with(smart_quote!(Vars { names: &names, name_expr }, {
+            impl Lib {
+                pub const ALL: &'static [Lib] = &[names];
+
+                /// The name of the lib, like `es2015.core`.
+                pub fn name(self) -> &'static str {
+                    name_expr
+                }
+            }
+        }));
+
         let match_expr = syn::ExprMatch {
             attrs: vec![],
             match_token: call_site(),
@@ -7,6 +7,7 @@ use std::{
 
 use fxhash::FxHashMap;
 use once_cell::sync::Lazy;
+#[cfg(not(feature = "no-threading"))]
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use stc_ts_builtin_macro::builtin;
 use swc_atoms::js_word;
@@ -18,6 +19,11 @@ use swc_ecma_parser::{
 };
 use swc_ecma_visit::{span_remover, FoldWith};
 
+mod lib_dir;
+mod lib_index;
+
+pub use self::lib_dir::{parse_lib_files, read_lib_dir, LibFile};
+
 builtin!();
 
 impl Lib {
@@ -30,6 +36,30 @@ impl Lib {
         lib.load_deps()
     }
 
+    /// Returns the libs used by `tsc` if `lib` is not specified, which include
+    /// `dom`.
+    pub fn default_for(target: EsVersion) -> Vec<Self> {
+        let lib = match target {
+            EsVersion::Es3 | EsVersion::Es5 => Lib::Es5Full,
+            EsVersion::Es2015 => Lib::Es2015Full,
+            EsVersion::Es2016 => Lib::Es2016Full,
+            EsVersion::Es2017 => Lib::Es2017Full,
+            EsVersion::Es2018 => Lib::Es2018Full,
+            EsVersion::Es2019 => Lib::Es2019Full,
+            EsVersion::Es2020 => Lib::Es2020Full,
+            EsVersion::Es2021 => Lib::Es2021Full,
+            EsVersion::Es2022 => Lib::Es2022Full,
+            EsVersion::EsNext => Lib::EsnextFull,
+        };
+
+        lib.load_deps()
+    }
+
+    /// The declarations of this lib, which are compiled into this crate.
+    pub fn source(self) -> &'static str {
+        self.content()
+    }
+
     fn body(self) -> &'static TsNamespaceDecl {
         static CACHE: Lazy<RwLock<FxHashMap<Lib, &'static TsNamespaceDecl>>> = Lazy::new(Default::default);
 
@@ -156,10 +186,19 @@ impl Ord for Lib {
 
 /// Merge definitions
 pub fn load(libs: &[Lib]) -> Vec<&'static TsNamespaceDecl> {
-    libs.into_par_iter().map(|lib| lib.body()).collect()
+    #[cfg(feature = "no-threading")]
+    let iter = libs.iter();
+    #[cfg(not(feature = "no-threading"))]
+    let iter = libs.into_par_iter();
+
+    iter.map(|lib| lib.body()).collect()
 }
 
 fn parse(content: &str) -> TsNamespaceDecl {
+    try_parse(content).expect("failed to parse module")
+}
+
+fn try_parse(content: &str) -> Result<TsNamespaceDecl, swc_ecma_parser::error::Error> {
     let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
 
     let fm = cm.new_source_file(FileName::Anon, content.to_string());
@@ -176,9 +215,9 @@ fn parse(content: &str) -> TsNamespaceDecl {
     let mut parser = Parser::new_from(lexer);
 
     // We cannot use parse_module because of `eval`
-    let script = parser.parse_script().expect("failed to parse module");
+    let script = parser.parse_script()?;
 
-    TsNamespaceDecl {
+    Ok(TsNamespaceDecl {
         span: Default::default(),
         declare: true,
         global: true,
@@ -192,5 +231,5 @@ fn parse(content: &str) -> TsNamespaceDecl {
                 .map(ModuleItem::Stmt)
                 .collect(),
         })),
-    }
+    })
 }
@@ -0,0 +1,86 @@
+//! Libs loaded at runtime from a directory, so a project can use the libs of
+//! the version of TypeScript it depends on.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(not(feature = "no-threading"))]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use swc_ecma_ast::TsNamespaceDecl;
+
+use crate::{try_parse, Lib};
+
+#[derive(Debug, Clone)]
+pub struct LibFile {
+    pub lib: Lib,
+    pub path: PathBuf,
+    pub content: String,
+}
+
+impl LibFile {
+    /// The error names [LibFile::path], as the file is not part of stc.
+    pub fn parse(&self) -> io::Result<TsNamespaceDecl> {
+        try_parse(&self.content).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("failed to parse {}: {}", self.path.display(), err.kind().msg()),
+            )
+        })
+    }
+}
+
+/// Reads `libs` from `dir`, instead of using the libs compiled into this
+/// crate.
+///
+/// `dir` can be the `typescript` package or its `lib` directory. Both
+/// `lib.es5.d.ts` and `es5.d.ts` are accepted as the file of `es5`.
+///
+/// Libs which are not in `dir` are skipped, because an old version of
+/// TypeScript does not have libs for newer versions of ECMAScript.
+pub fn read_lib_dir(dir: &Path, libs: &[Lib]) -> io::Result<Vec<LibFile>> {
+    let dir = if dir.join("lib").join("lib.es5.d.ts").is_file() {
+        dir.join("lib")
+    } else {
+        dir.to_path_buf()
+    };
+
+    if find_file(&dir, Lib::Es5).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not contain typescript libs", dir.display()),
+        ));
+    }
+
+    libs.iter()
+        .filter_map(|&lib| find_file(&dir, lib).map(|path| (lib, path)))
+        .map(|(lib, path)| {
+            let content = fs::read_to_string(&path)?;
+            Ok(LibFile { lib, path, content })
+        })
+        .collect()
+}
+
+/// Parses `files` in parallel, unless `no-threading` is enabled.
+pub fn parse_lib_files(files: &[LibFile]) -> io::Result<Vec<TsNamespaceDecl>> {
+    #[cfg(feature = "no-threading")]
+    let iter = files.iter();
+    #[cfg(not(feature = "no-threading"))]
+    let iter = files.par_iter();
+
+    iter.map(LibFile::parse).collect()
+}
+
+fn find_file(dir: &Path, lib: Lib) -> Option<PathBuf> {
+    let name = lib.name();
+    // `dom.generated.d.ts` of this crate is `lib.dom.d.ts` of typescript.
+    let short = name.strip_suffix(".generated");
+
+    [Some(name), short]
+        .into_iter()
+        .flatten()
+        .flat_map(|name| [format!("lib.{}.d.ts", name), format!("{}.d.ts", name)])
+        .map(|file| dir.join(file))
+        .find(|path| path.is_file())
+}
@@ -0,0 +1,107 @@
+//! Index of the libs declaring each builtin member, used to suggest the lib
+//! which should be added when a newer feature is used.
+
+use fxhash::FxHashMap;
+use once_cell::sync::Lazy;
+use swc_atoms::JsWord;
+use swc_ecma_ast::*;
+
+use crate::Lib;
+
+#[derive(Default)]
+struct LibIndex {
+    /// `(interface, member)` to lib.
+    members: FxHashMap<(JsWord, JsWord), Lib>,
+    globals: FxHashMap<JsWord, Lib>,
+}
+
+static INDEX: Lazy<LibIndex> = Lazy::new(|| {
+    let mut libs = Lib::ALL
+        .iter()
+        .copied()
+        .filter(|lib| lib.feature_name().is_some())
+        .collect::<Vec<_>>();
+    // The oldest lib wins.
+    libs.sort();
+
+    let mut index = LibIndex::default();
+
+    for lib in libs {
+        let body = match &*lib.body().body {
+            TsNamespaceBody::TsModuleBlock(TsModuleBlock { body, .. }) => body,
+            TsNamespaceBody::TsNamespaceDecl(_) => unreachable!(),
+        };
+
+        for item in body {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+                _ => continue,
+            };
+
+            match decl {
+                Decl::TsInterface(i) => {
+                    for member in &i.body.body {
+                        let key = match member {
+                            TsTypeElement::TsPropertySignature(TsPropertySignature { key, computed: false, .. })
+                            | TsTypeElement::TsMethodSignature(TsMethodSignature { key, computed: false, .. }) => key,
+                            _ => continue,
+                        };
+
+                        if let Expr::Ident(key) = &**key {
+                            index.members.entry((i.id.sym.clone(), key.sym.clone())).or_insert(lib);
+                        }
+                    }
+
+                    index.globals.entry(i.id.sym.clone()).or_insert(lib);
+                }
+                Decl::Class(c) => {
+                    index.globals.entry(c.ident.sym.clone()).or_insert(lib);
+                }
+                Decl::Fn(f) => {
+                    index.globals.entry(f.ident.sym.clone()).or_insert(lib);
+                }
+                Decl::Var(v) => {
+                    for decl in &v.decls {
+                        if let Pat::Ident(i) = &decl.name {
+                            index.globals.entry(i.id.sym.clone()).or_insert(lib);
+                        }
+                    }
+                }
+                Decl::TsTypeAlias(a) => {
+                    index.globals.entry(a.id.sym.clone()).or_insert(lib);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    index
+});
+
+impl Lib {
+    /// The lib to suggest for this lib, like `es2015` for `es2015.core`.
+    ///
+    /// Returns [None] for libs which are not a part of an ECMAScript version.
+    pub fn feature_name(self) -> Option<&'static str> {
+        let name = self.name();
+        let (version, feature) = name.split_once('.')?;
+
+        if feature == "full" || !(version.starts_with("es20") || version == "esnext") {
+            return None;
+        }
+
+        Some(version)
+    }
+
+    /// Returns the oldest lib declaring `member` in the interface named
+    /// `interface`.
+    pub fn of_member(interface: &str, member: &str) -> Option<Lib> {
+        INDEX.members.get(&(interface.into(), member.into())).copied()
+    }
+
+    /// Returns the oldest lib declaring a global variable or a global type
+    /// named `name`.
+    pub fn of_global(name: &str) -> Option<Lib> {
+        INDEX.globals.get(&JsWord::from(name)).copied()
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::{Path, PathBuf};
+
+use stc_ts_builtin_types::{read_lib_dir, Lib, LibFile};
+
+#[test]
+fn bundled_libs() {
+    let libs = Lib::load("es2015");
+    let files = read_lib_dir(Path::new("lib"), &libs).unwrap();
+
+    assert_eq!(files.len(), libs.len());
+    let es5 = files.iter().find(|file| file.lib == Lib::Es5).unwrap();
+    es5.parse().unwrap();
+}
+
+#[test]
+fn not_lib_dir() {
+    let err = read_lib_dir(Path::new("src"), &Lib::load("es5")).unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn invalid_lib() {
+    let file = LibFile {
+        lib: Lib::Es5,
+        path: PathBuf::from("lib.es5.d.ts"),
+        content: "interface {".into(),
+    };
+
+    let err = file.parse().unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("lib.es5.d.ts"), "{}", err);
+}
@@ -0,0 +1,184 @@
+//! `extends`
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::{compiler_options, json, ConfigError, ConfigErrorKind, FILE_SPECS};
+
+/// A configuration merged with the ones it extends, before interpreting
+/// options.
+#[derive(Debug, Default)]
+pub(crate) struct RawConfig {
+    /// Options with the file declaring them.
+    pub compiler_options: BTreeMap<String, (Value, PathBuf)>,
+
+    /// `files`, `include` and `exclude` with the file declaring them. Each of
+    /// them overrides the one of extended configurations.
+    pub file_specs: BTreeMap<String, (Value, PathBuf)>,
+
+    /// `references` of the loaded file. Extended configurations do not
+    /// contribute to it.
+    pub references: Option<Value>,
+}
+
+impl RawConfig {
+    /// `stack` contains the files extending `path`, to detect cycles.
+    ///
+    /// Problems of extended configurations are reported to `errors`, and
+    /// problems of `path` itself are returned.
+    pub fn load(path: &Path, stack: &mut Vec<PathBuf>, errors: &mut Vec<ConfigError>) -> Result<Self, ConfigErrorKind> {
+        let value = read(path)?;
+
+        let mut config = RawConfig::default();
+
+        stack.push(path.to_path_buf());
+        for specifier in extends(path, &value, errors) {
+            let dir = path.parent().unwrap();
+            let base = match resolve_extends(dir, specifier).and_then(|p| p.canonicalize().ok()) {
+                Some(v) => v,
+                None => {
+                    errors.push(ConfigError {
+                        file: path.to_path_buf(),
+                        kind: ConfigErrorKind::ExtendsNotFound {
+                            specifier: specifier.to_string(),
+                        },
+                    });
+                    continue;
+                }
+            };
+
+            if let Some(idx) = stack.iter().position(|p| *p == base) {
+                let mut chain = stack[idx..].to_vec();
+                chain.push(base);
+                errors.push(ConfigError {
+                    file: path.to_path_buf(),
+                    kind: ConfigErrorKind::CircularExtends { chain },
+                });
+                continue;
+            }
+
+            match RawConfig::load(&base, stack, errors) {
+                Ok(base) => {
+                    config.compiler_options.extend(base.compiler_options);
+                    config.file_specs.extend(base.file_specs);
+                }
+                Err(kind) => errors.push(ConfigError {
+                    file: path.to_path_buf(),
+                    kind,
+                }),
+            }
+        }
+        stack.pop();
+
+        if let Some(options) = compiler_options(path, &value, errors) {
+            for (name, value) in options {
+                config.compiler_options.insert(name.clone(), (value.clone(), path.to_path_buf()));
+            }
+        }
+
+        for name in FILE_SPECS {
+            if let Some(value) = value.get(name) {
+                config.file_specs.insert(name.to_string(), (value.clone(), path.to_path_buf()));
+            }
+        }
+        config.references = value.get("references").cloned();
+
+        Ok(config)
+    }
+}
+
+fn read(path: &Path) -> Result<Value, ConfigErrorKind> {
+    let content = fs::read_to_string(path).map_err(|err| ConfigErrorKind::CannotRead {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })?;
+
+    json::parse(&content).map_err(|err| ConfigErrorKind::InvalidJson { message: err.to_string() })
+}
+
+/// Returns the specifiers in `extends`, which can be an array since
+/// TypeScript 5.0.
+fn extends<'a>(path: &Path, value: &'a Value, errors: &mut Vec<ConfigError>) -> Vec<&'a str> {
+    let invalid = |errors: &mut Vec<ConfigError>| {
+        errors.push(ConfigError {
+            file: path.to_path_buf(),
+            kind: ConfigErrorKind::InvalidOptionType {
+                name: "extends".into(),
+                expected: "string or Array",
+            },
+        })
+    };
+
+    match value.get("extends") {
+        None => vec![],
+        Some(Value::String(s)) => vec![s],
+        Some(Value::Array(specifiers)) => specifiers
+            .iter()
+            .filter_map(|s| match s {
+                Value::String(s) => Some(&**s),
+                _ => {
+                    invalid(errors);
+                    None
+                }
+            })
+            .collect(),
+        Some(..) => {
+            invalid(errors);
+            vec![]
+        }
+    }
+}
+
+/// Relative specifiers are resolved against `dir`, and others are looked up
+/// in `node_modules` like packages.
+fn resolve_extends(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    if specifier.starts_with("./") || specifier.starts_with("../") || Path::new(specifier).is_absolute() {
+        return with_json_extension(dir.join(specifier));
+    }
+
+    dir.ancestors()
+        .find_map(|dir| resolve_in_package(&dir.join("node_modules").join(specifier)))
+}
+
+/// `path` is `node_modules/<specifier>`.
+fn resolve_in_package(path: &Path) -> Option<PathBuf> {
+    if let Some(path) = with_json_extension(path.to_path_buf()) {
+        return Some(path);
+    }
+
+    if !path.is_dir() {
+        return None;
+    }
+
+    // The `tsconfig` field of `package.json`.
+    let tsconfig = fs::read_to_string(path.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|package| package.get("tsconfig")?.as_str().map(|s| path.join(s)))
+        .and_then(with_json_extension);
+    if tsconfig.is_some() {
+        return tsconfig;
+    }
+
+    Some(path.join("tsconfig.json")).filter(|p| p.is_file())
+}
+
+/// `tsc` appends `.json` if the file does not exist.
+fn with_json_extension(path: PathBuf) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path);
+    }
+
+    if path.extension().map_or(false, |ext| ext == "json") {
+        return None;
+    }
+
+    let mut path = path.into_os_string();
+    path.push(".json");
+    Some(PathBuf::from(path)).filter(|p| p.is_file())
+}
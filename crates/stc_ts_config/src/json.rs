@@ -0,0 +1,76 @@
+//! `tsconfig.json` allows comments and trailing commas.
+
+use serde_json::Value;
+
+pub(crate) fn parse(content: &str) -> serde_json::Result<Value> {
+    serde_json::from_str(&strip(content))
+}
+
+/// Removes comments and trailing commas, so `serde_json` can parse `content`.
+///
+/// Removed characters are replaced with spaces to preserve the positions in
+/// error messages.
+fn strip(content: &str) -> String {
+    let mut buf = String::with_capacity(content.len());
+    // The index of the last comma in `buf`, which is removed if it's followed
+    // by `}` or `]`.
+    let mut last_comma = None;
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                last_comma = None;
+                buf.push(c);
+                while let Some(c) = chars.next() {
+                    buf.push(c);
+                    match c {
+                        '\\' => buf.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                buf.push(' ');
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        buf.push(c);
+                        break;
+                    }
+                    buf.push(' ');
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                buf.push_str("  ");
+                let mut prev = None;
+                for c in chars.by_ref() {
+                    buf.push(if c == '\n' { c } else { ' ' });
+                    if prev == Some('*') && c == '/' {
+                        break;
+                    }
+                    prev = Some(c);
+                }
+            }
+            ',' => {
+                last_comma = Some(buf.len());
+                buf.push(c);
+            }
+            '}' | ']' => {
+                if let Some(idx) = last_comma.take() {
+                    buf.replace_range(idx..idx + 1, " ");
+                }
+                buf.push(c);
+            }
+            _ => {
+                if !c.is_whitespace() {
+                    last_comma = None;
+                }
+                buf.push(c);
+            }
+        }
+    }
+
+    buf
+}
@@ -0,0 +1,358 @@
+//! Loading of `tsconfig.json`.
+//!
+//! Configurations are resolved like `tsc`. A configuration is merged with the
+//! ones it `extends`, and path-valued options stay relative to the file which
+//! declared them.
+//!
+//! - https://www.typescriptlang.org/tsconfig
+
+#![deny(warnings)]
+
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Error};
+use serde_json::{Map, Value};
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{ModuleConfig, Rule};
+use swc_common::errors::{DiagnosticId, Handler};
+use swc_ecma_ast::EsVersion;
+
+use crate::{extends::RawConfig, options::OptionsBuilder};
+pub use crate::{
+    program::{Program, ProgramBuilder},
+    references::ProjectGraph,
+};
+
+mod extends;
+mod json;
+mod options;
+mod program;
+mod references;
+
+/// Top-level fields which select the root files of a program.
+const FILE_SPECS: [&str; 3] = ["files", "include", "exclude"];
+
+/// A `tsconfig.json` resolved with the configurations it extends.
+#[derive(Debug, Clone)]
+pub struct TsConfig {
+    /// Path of the loaded `tsconfig.json`.
+    pub path: PathBuf,
+
+    pub rule: Rule,
+
+    /// `target`
+    pub target: EsVersion,
+
+    /// `module`
+    pub module: ModuleConfig,
+
+    /// `lib`. [None] if the option is not set, in which case the libraries
+    /// depend on `target`.
+    pub libs: Option<Vec<Lib>>,
+
+    /// `noLib`. Use [TsConfig::libs_or_default] to get the libraries to use.
+    pub no_lib: bool,
+
+    /// `baseUrl`, resolved against the directory of the file declaring it.
+    pub base_url: Option<PathBuf>,
+
+    /// The directory of the file declaring `paths`.
+    pub paths_dir: PathBuf,
+
+    /// `paths`, in the order of the file.
+    pub paths: Vec<(String, Vec<String>)>,
+
+    /// `typeRoots`, resolved against the directory of the file declaring it.
+    pub type_roots: Option<Vec<PathBuf>>,
+
+    /// `types`
+    pub types: Option<Vec<String>>,
+
+    /// `outDir`, which is excluded from the root files by default.
+    pub out_dir: Option<PathBuf>,
+
+    /// `composite`
+    pub composite: bool,
+
+    /// `incremental`, which defaults to `composite`.
+    pub incremental: bool,
+
+    /// `tsBuildInfoFile`. Use [TsConfig::build_info_path] to get the path
+    /// including the default.
+    pub build_info_file: Option<PathBuf>,
+
+    /// `declaration`, which defaults to `composite`.
+    pub declaration: bool,
+
+    /// `declarationDir`
+    pub declaration_dir: Option<PathBuf>,
+
+    /// `rootDir`
+    pub root_dir: Option<PathBuf>,
+
+    /// Paths to `tsconfig.json` of referenced projects.
+    pub references: Vec<PathBuf>,
+
+    /// `files`, resolved against the directory of the file declaring it.
+    pub files: Option<Vec<PathBuf>>,
+
+    /// Patterns of `include`, resolved against the directory of the file
+    /// declaring it. Use [ProgramBuilder] to expand them.
+    pub include: Option<Vec<String>>,
+
+    /// Patterns of `exclude`, resolved against the directory of the file
+    /// declaring it.
+    pub exclude: Option<Vec<String>>,
+
+    /// Problems found while loading the configuration. They are not fatal, like
+    /// `tsc`.
+    pub errors: Vec<ConfigError>,
+}
+
+impl TsConfig {
+    /// Loads `path` and the configurations it extends.
+    ///
+    /// Fails only if `path` itself cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let path = path.canonicalize().with_context(|| format!("failed to find {}", path.display()))?;
+        let dir = path.parent().unwrap().to_path_buf();
+
+        let mut errors = vec![];
+        let raw = RawConfig::load(&path, &mut vec![], &mut errors).map_err(|kind| ConfigError { file: path.clone(), kind })?;
+
+        let mut builder = OptionsBuilder::new(&dir);
+        for (name, (value, file)) in &raw.compiler_options {
+            builder.apply(file, name, value, &mut errors);
+        }
+        for (name, (value, file)) in &raw.file_specs {
+            builder.apply_file_spec(file, name, value, &mut errors);
+        }
+        if let Some(references) = &raw.references {
+            builder.apply_references(&path, references, &mut errors);
+        }
+
+        Ok(builder.build(path, errors))
+    }
+
+    /// Parses `content` as the content of `path`, without following `extends`.
+    ///
+    /// Useful for configurations which do not exist on the file system.
+    pub fn parse(path: &Path, content: &str) -> Result<Self, Error> {
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let value = json::parse(content).with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let mut errors = vec![];
+        let mut builder = OptionsBuilder::new(&dir);
+        if let Some(options) = compiler_options(path, &value, &mut errors) {
+            for (name, value) in options {
+                builder.apply(path, name, value, &mut errors);
+            }
+        }
+        for name in FILE_SPECS {
+            if let Some(value) = value.get(name) {
+                builder.apply_file_spec(path, name, value, &mut errors);
+            }
+        }
+        if let Some(references) = value.get("references") {
+            builder.apply_references(path, references, &mut errors);
+        }
+
+        Ok(builder.build(path.to_path_buf(), errors))
+    }
+
+    /// Returns the libraries to use, like `tsc`.
+    ///
+    /// If `lib` is not set, the libraries depend on `target`. See
+    /// [Lib::default_for].
+    pub fn libs_or_default(&self) -> Vec<Lib> {
+        if self.no_lib {
+            return vec![];
+        }
+
+        let mut libs = match &self.libs {
+            Some(libs) => libs.clone(),
+            None => Lib::default_for(self.target),
+        };
+        libs.sort();
+        libs.dedup();
+        libs
+    }
+
+    /// Returns the file storing the state of incremental checking.
+    ///
+    /// Defaults to `<name of the config>.stcbuildinfo` in `outDir`, or next to
+    /// the configuration if `outDir` is not set, like `tsc` does for
+    /// `.tsbuildinfo`.
+    pub fn build_info_path(&self) -> PathBuf {
+        if let Some(path) = &self.build_info_file {
+            return path.clone();
+        }
+
+        let dir = self
+            .out_dir
+            .as_deref()
+            .or_else(|| self.path.parent())
+            .unwrap_or_else(|| Path::new(""));
+        let name = self.path.file_stem().map_or_else(|| "tsconfig".into(), |s| s.to_string_lossy());
+
+        dir.join(format!("{}.stcbuildinfo", name))
+    }
+}
+
+/// Returns `compilerOptions` of a configuration file.
+fn compiler_options<'a>(path: &Path, value: &'a Value, errors: &mut Vec<ConfigError>) -> Option<&'a Map<String, Value>> {
+    match value.get("compilerOptions")? {
+        Value::Object(options) => Some(options),
+        _ => {
+            errors.push(ConfigError {
+                file: path.to_path_buf(),
+                kind: ConfigErrorKind::InvalidOptionType {
+                    name: "compilerOptions".into(),
+                    expected: "object",
+                },
+            });
+            None
+        }
+    }
+}
+
+/// A problem in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// The file containing the problem.
+    pub file: PathBuf,
+    pub kind: ConfigErrorKind,
+}
+
+impl ConfigError {
+    /// The `tsc` error code.
+    pub fn code(&self) -> usize {
+        self.kind.code()
+    }
+
+    #[cold]
+    pub fn emit(&self, h: &Handler) {
+        h.struct_err_with_code(&self.to_string(), DiagnosticId::Error(format!("TS{}", self.code())))
+            .emit();
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.file.display(), self.kind)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// TS5083
+    CannotRead { path: PathBuf, message: String },
+
+    /// TS1005
+    InvalidJson { message: String },
+
+    /// TS6053
+    ExtendsNotFound { specifier: String },
+
+    /// TS18000
+    CircularExtends { chain: Vec<PathBuf> },
+
+    /// TS6053
+    FileNotFound { path: PathBuf },
+
+    /// TS18003
+    NoInputs { include: Vec<String>, exclude: Vec<String> },
+
+    /// TS6304
+    CompositeWithoutDeclaration,
+
+    /// TS6306
+    ReferencedProjectNotComposite { path: PathBuf },
+
+    /// TS6202
+    CircularReferences { chain: Vec<PathBuf> },
+
+    /// TS6307
+    FileNotInProject { path: PathBuf },
+
+    /// TS5023
+    UnknownOption { name: String },
+
+    /// TS5024
+    InvalidOptionType { name: String, expected: &'static str },
+
+    /// TS6046
+    InvalidOptionValue { name: String, expected: String },
+}
+
+impl ConfigErrorKind {
+    pub fn code(&self) -> usize {
+        match self {
+            ConfigErrorKind::CannotRead { .. } => 5083,
+            ConfigErrorKind::InvalidJson { .. } => 1005,
+            ConfigErrorKind::ExtendsNotFound { .. } => 6053,
+            ConfigErrorKind::CircularExtends { .. } => 18000,
+            ConfigErrorKind::FileNotFound { .. } => 6053,
+            ConfigErrorKind::NoInputs { .. } => 18003,
+            ConfigErrorKind::CompositeWithoutDeclaration => 6304,
+            ConfigErrorKind::ReferencedProjectNotComposite { .. } => 6306,
+            ConfigErrorKind::CircularReferences { .. } => 6202,
+            ConfigErrorKind::FileNotInProject { .. } => 6307,
+            ConfigErrorKind::UnknownOption { .. } => 5023,
+            ConfigErrorKind::InvalidOptionType { .. } => 5024,
+            ConfigErrorKind::InvalidOptionValue { .. } => 6046,
+        }
+    }
+}
+
+impl Display for ConfigErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigErrorKind::CannotRead { path, message } => {
+                write!(f, "Cannot read file '{}': {}.", path.display(), message)
+            }
+            ConfigErrorKind::InvalidJson { message } => write!(f, "Invalid JSON: {}.", message),
+            ConfigErrorKind::ExtendsNotFound { specifier } => write!(f, "File '{}' not found.", specifier),
+            ConfigErrorKind::CircularExtends { chain } => {
+                let chain = chain.iter().map(|p| p.display().to_string()).collect::<Vec<_>>();
+                write!(f, "Circularity detected while resolving configuration: {}", chain.join(" -> "))
+            }
+            ConfigErrorKind::FileNotFound { path } => write!(f, "File '{}' not found.", path.display()),
+            ConfigErrorKind::NoInputs { include, exclude } => write!(
+                f,
+                "No inputs were found in config file. Specified 'include' paths were '{:?}' and 'exclude' paths were '{:?}'.",
+                include, exclude
+            ),
+            ConfigErrorKind::CompositeWithoutDeclaration => write!(f, "Composite projects may not disable declaration emit."),
+            ConfigErrorKind::ReferencedProjectNotComposite { path } => {
+                write!(f, "Referenced project '{}' must have setting \"composite\": true.", path.display())
+            }
+            ConfigErrorKind::CircularReferences { chain } => {
+                let chain = chain.iter().map(|p| p.display().to_string()).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "Project references may not form a circular graph. Cycle detected: {}",
+                    chain.join(" -> ")
+                )
+            }
+            ConfigErrorKind::FileNotInProject { path } => write!(
+                f,
+                "File '{}' is not listed within the file list of project. Projects must list all files or use an 'include' pattern.",
+                path.display()
+            ),
+            ConfigErrorKind::UnknownOption { name } => write!(f, "Unknown compiler option '{}'.", name),
+            ConfigErrorKind::InvalidOptionType { name, expected } => {
+                write!(f, "Compiler option '{}' requires a value of type {}.", name, expected)
+            }
+            ConfigErrorKind::InvalidOptionValue { name, expected } => {
+                write!(f, "Argument for '--{}' option must be: {}.", name, expected)
+            }
+        }
+    }
+}
@@ -0,0 +1,497 @@
+//! Interpretation of `compilerOptions`.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{JsxMode, ModuleConfig, ModuleKind, ModuleResolution, Rule, RuleBuilder};
+use swc_ecma_ast::EsVersion;
+
+use crate::{ConfigError, ConfigErrorKind, TsConfig};
+
+/// Options which are valid but do not affect type checking.
+const IGNORED_OPTIONS: &[&str] = &[
+    "allowArbitraryExtensions",
+    "allowImportingTsExtensions",
+    "allowJs",
+    "allowUmdGlobalAccess",
+    "assumeChangesOnlyAffectDirectDependencies",
+    "charset",
+    "checkJs",
+    "declarationMap",
+    "diagnostics",
+    "disableReferencedProjectLoad",
+    "disableSizeLimit",
+    "disableSolutionSearching",
+    "disableSourceOfProjectReferenceRedirect",
+    "emitBOM",
+    "emitDeclarationOnly",
+    "emitDecoratorMetadata",
+    "experimentalDecorators",
+    "explainFiles",
+    "extendedDiagnostics",
+    "generateCpuProfile",
+    "generateTrace",
+    "ignoreDeprecations",
+    "importHelpers",
+    "importsNotUsedAsValues",
+    "inlineSourceMap",
+    "inlineSources",
+    "jsxFactory",
+    "jsxFragmentFactory",
+    "jsxImportSource",
+    "keyofStringsOnly",
+    "listEmittedFiles",
+    "listFiles",
+    "locale",
+    "mapRoot",
+    "maxNodeModuleJsDepth",
+    "moduleDetection",
+    "moduleSuffixes",
+    "newLine",
+    "noEmit",
+    "noEmitHelpers",
+    "noEmitOnError",
+    "noErrorTruncation",
+    "noImplicitUseStrict",
+    "noResolve",
+    "out",
+    "outFile",
+    "plugins",
+    "preserveConstEnums",
+    "preserveSymlinks",
+    "preserveValueImports",
+    "preserveWatchOutput",
+    "pretty",
+    "reactNamespace",
+    "removeComments",
+    "resolvePackageJsonExports",
+    "resolvePackageJsonImports",
+    "rootDirs",
+    "skipDefaultLibCheck",
+    "skipLibCheck",
+    "sourceMap",
+    "sourceRoot",
+    "stripInternal",
+    "traceResolution",
+];
+
+/// Options of checks which stc does not implement yet. They are accepted, so
+/// projects using them can be checked.
+const UNIMPLEMENTED_OPTIONS: &[&str] = &["noImplicitOverride", "noPropertyAccessFromIndexSignature"];
+
+pub(crate) struct OptionsBuilder {
+    rule: RuleBuilder,
+    target: EsVersion,
+    module: Option<ModuleKind>,
+    module_resolution: Option<ModuleResolution>,
+    custom_conditions: Vec<String>,
+    libs: Option<Vec<Lib>>,
+    no_lib: bool,
+    base_url: Option<PathBuf>,
+    paths_dir: PathBuf,
+    paths: Vec<(String, Vec<String>)>,
+    type_roots: Option<Vec<PathBuf>>,
+    types: Option<Vec<String>>,
+    out_dir: Option<PathBuf>,
+    composite: bool,
+    incremental: Option<bool>,
+    build_info_file: Option<PathBuf>,
+    declaration: Option<bool>,
+    declaration_dir: Option<PathBuf>,
+    root_dir: Option<PathBuf>,
+    references: Vec<PathBuf>,
+    files: Option<Vec<PathBuf>>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+}
+
+impl OptionsBuilder {
+    /// `dir` is the directory of the loaded configuration.
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            rule: Rule::builder(),
+            // The default of `tsc`.
+            target: EsVersion::Es5,
+            module: None,
+            module_resolution: None,
+            custom_conditions: vec![],
+            libs: None,
+            no_lib: false,
+            base_url: None,
+            paths_dir: dir.to_path_buf(),
+            paths: vec![],
+            type_roots: None,
+            types: None,
+            out_dir: None,
+            composite: false,
+            incremental: None,
+            build_info_file: None,
+            declaration: None,
+            declaration_dir: None,
+            root_dir: None,
+            references: vec![],
+            files: None,
+            include: None,
+            exclude: None,
+        }
+    }
+
+    /// Applies `files`, `include` or `exclude`, which are declared outside of
+    /// `compilerOptions`.
+    pub fn apply_file_spec(&mut self, file: &Path, name: &str, value: &Value, errors: &mut Vec<ConfigError>) {
+        let dir = file.parent().unwrap_or_else(|| Path::new(""));
+
+        if value.is_null() {
+            return;
+        }
+
+        let specs = match string_list(value) {
+            Some(v) => v,
+            None => {
+                errors.push(ConfigError {
+                    file: file.to_path_buf(),
+                    kind: ConfigErrorKind::InvalidOptionType {
+                        name: name.to_string(),
+                        expected: "Array",
+                    },
+                });
+                return;
+            }
+        };
+
+        match name {
+            "files" => self.files = Some(specs.into_iter().map(|spec| dir.join(spec)).collect()),
+            "include" => self.include = Some(specs.into_iter().map(|spec| resolve_pattern(dir, spec)).collect()),
+            "exclude" => self.exclude = Some(specs.into_iter().map(|spec| resolve_pattern(dir, spec)).collect()),
+            _ => unreachable!("{} is not a file spec", name),
+        }
+    }
+
+    /// `file` is the configuration file declaring the option.
+    pub fn apply(&mut self, file: &Path, name: &str, value: &Value, errors: &mut Vec<ConfigError>) {
+        let dir = file.parent().unwrap_or_else(|| Path::new(""));
+        let mut report = |kind| {
+            errors.push(ConfigError {
+                file: file.to_path_buf(),
+                kind,
+            })
+        };
+        let invalid_type = |expected| ConfigErrorKind::InvalidOptionType {
+            name: name.to_string(),
+            expected,
+        };
+
+        // `null` resets an option set by an extended configuration.
+        if value.is_null() {
+            return;
+        }
+
+        if let Some(apply) = bool_option(name) {
+            match value {
+                Value::Bool(v) => self.rule = apply(self.rule, *v),
+                _ => report(invalid_type("boolean")),
+            }
+            return;
+        }
+
+        if IGNORED_OPTIONS.contains(&name) || UNIMPLEMENTED_OPTIONS.contains(&name) {
+            return;
+        }
+
+        match name {
+            "target" => match value.as_str().map(str::to_lowercase) {
+                Some(s) => match parse_target(&s) {
+                    Some(target) => self.target = target,
+                    None => report(ConfigErrorKind::InvalidOptionValue {
+                        name: name.to_string(),
+                        expected: TARGETS.iter().map(|(name, _)| format!("'{}'", name)).collect::<Vec<_>>().join(", "),
+                    }),
+                },
+                None => report(invalid_type("string")),
+            },
+            "module" => match value.as_str().map(str::to_lowercase) {
+                Some(s) => match s.parse() {
+                    Ok(module) => self.module = Some(module),
+                    Err(..) => report(ConfigErrorKind::InvalidOptionValue {
+                        name: name.to_string(),
+                        expected: "'none', 'commonjs', 'amd', 'system', 'umd', 'es6', 'es2015', 'es2020', 'es2022', 'esnext', 'node16', \
+                                   'nodenext'"
+                            .into(),
+                    }),
+                },
+                None => report(invalid_type("string")),
+            },
+            "moduleResolution" => match value.as_str().map(str::to_lowercase) {
+                Some(s) => match s.parse() {
+                    Ok(resolution) => self.module_resolution = Some(resolution),
+                    // The old name of `node10`.
+                    Err(..) if s == "node" => self.module_resolution = Some(ModuleResolution::Node10),
+                    Err(..) => report(ConfigErrorKind::InvalidOptionValue {
+                        name: name.to_string(),
+                        expected: "'classic', 'node', 'node10', 'node16', 'nodenext', 'bundler'".into(),
+                    }),
+                },
+                None => report(invalid_type("string")),
+            },
+            "jsx" => match value.as_str().map(str::to_lowercase) {
+                Some(s) => match s.parse::<JsxMode>() {
+                    Ok(jsx) => self.rule = self.rule.jsx(jsx),
+                    Err(..) => report(ConfigErrorKind::InvalidOptionValue {
+                        name: name.to_string(),
+                        expected: "'preserve', 'react-native', 'react', 'react-jsx', 'react-jsxdev'".into(),
+                    }),
+                },
+                None => report(invalid_type("string")),
+            },
+            "lib" => match string_list(value) {
+                Some(names) => {
+                    let mut libs = vec![];
+                    for lib in names {
+                        let loaded = Lib::load(&normalize_lib_name(lib));
+                        if loaded.is_empty() {
+                            report(ConfigErrorKind::InvalidOptionValue {
+                                name: name.to_string(),
+                                expected: format!("a name of a library, not '{}'", lib),
+                            });
+                        }
+                        libs.extend(loaded);
+                    }
+                    libs.sort();
+                    libs.dedup();
+                    self.libs = Some(libs);
+                }
+                None => report(invalid_type("list")),
+            },
+            "baseUrl" => match value.as_str() {
+                Some(s) => self.base_url = Some(dir.join(s)),
+                None => report(invalid_type("string")),
+            },
+            "paths" => match value.as_object() {
+                Some(paths) => {
+                    self.paths_dir = dir.to_path_buf();
+                    self.paths.clear();
+                    for (pattern, substitutions) in paths {
+                        match string_list(substitutions) {
+                            Some(substitutions) => self
+                                .paths
+                                .push((pattern.clone(), substitutions.into_iter().map(String::from).collect())),
+                            None => report(ConfigErrorKind::InvalidOptionType {
+                                name: format!("paths['{}']", pattern),
+                                expected: "Array",
+                            }),
+                        }
+                    }
+                }
+                None => report(invalid_type("object")),
+            },
+            "typeRoots" => match string_list(value) {
+                Some(roots) => self.type_roots = Some(roots.into_iter().map(|root| dir.join(root)).collect()),
+                None => report(invalid_type("list")),
+            },
+            "types" => match string_list(value) {
+                Some(types) => self.types = Some(types.into_iter().map(String::from).collect()),
+                None => report(invalid_type("list")),
+            },
+            "customConditions" => match string_list(value) {
+                Some(conditions) => self.custom_conditions = conditions.into_iter().map(String::from).collect(),
+                None => report(invalid_type("list")),
+            },
+            "outDir" => match value.as_str() {
+                Some(s) => self.out_dir = Some(dir.join(s)),
+                None => report(invalid_type("string")),
+            },
+            "composite" => match value.as_bool() {
+                Some(v) => self.composite = v,
+                None => report(invalid_type("boolean")),
+            },
+            "noLib" => match value.as_bool() {
+                Some(v) => self.no_lib = v,
+                None => report(invalid_type("boolean")),
+            },
+            "incremental" => match value.as_bool() {
+                Some(v) => self.incremental = Some(v),
+                None => report(invalid_type("boolean")),
+            },
+            "tsBuildInfoFile" => match value.as_str() {
+                Some(s) => self.build_info_file = Some(dir.join(s)),
+                None => report(invalid_type("string")),
+            },
+            "declaration" => match value.as_bool() {
+                Some(v) => self.declaration = Some(v),
+                None => report(invalid_type("boolean")),
+            },
+            "declarationDir" => match value.as_str() {
+                Some(s) => self.declaration_dir = Some(dir.join(s)),
+                None => report(invalid_type("string")),
+            },
+            "rootDir" => match value.as_str() {
+                Some(s) => self.root_dir = Some(dir.join(s)),
+                None => report(invalid_type("string")),
+            },
+            _ => report(ConfigErrorKind::UnknownOption { name: name.to_string() }),
+        }
+    }
+
+    /// Applies `references`, which is not inherited from extended
+    /// configurations.
+    pub fn apply_references(&mut self, file: &Path, value: &Value, errors: &mut Vec<ConfigError>) {
+        let dir = file.parent().unwrap_or_else(|| Path::new(""));
+
+        let references = match value.as_array() {
+            Some(v) => v,
+            None => {
+                errors.push(ConfigError {
+                    file: file.to_path_buf(),
+                    kind: ConfigErrorKind::InvalidOptionType {
+                        name: "references".into(),
+                        expected: "Array",
+                    },
+                });
+                return;
+            }
+        };
+
+        for reference in references {
+            match reference.get("path").and_then(Value::as_str) {
+                Some(path) => {
+                    // A reference to a directory means `tsconfig.json` in it.
+                    let path = dir.join(path);
+                    self.references.push(if path.extension().map_or(false, |ext| ext == "json") {
+                        path
+                    } else {
+                        path.join("tsconfig.json")
+                    });
+                }
+                None => errors.push(ConfigError {
+                    file: file.to_path_buf(),
+                    kind: ConfigErrorKind::InvalidOptionType {
+                        name: "references.path".into(),
+                        expected: "string",
+                    },
+                }),
+            }
+        }
+    }
+
+    pub fn build(self, path: PathBuf, mut errors: Vec<ConfigError>) -> TsConfig {
+        let module = ModuleConfig::new(self.module.unwrap_or(match self.target {
+            EsVersion::Es3 | EsVersion::Es5 => ModuleKind::CommonJs,
+            _ => ModuleKind::Es2015,
+        }));
+        let module = match self.module_resolution {
+            Some(resolution) => module.resolution(resolution),
+            None => module,
+        }
+        .custom_conditions(self.custom_conditions);
+
+        if self.composite && self.declaration == Some(false) {
+            errors.push(ConfigError {
+                file: path.clone(),
+                kind: ConfigErrorKind::CompositeWithoutDeclaration,
+            });
+        }
+
+        TsConfig {
+            path,
+            rule: self.rule.build(),
+            target: self.target,
+            module,
+            libs: self.libs,
+            no_lib: self.no_lib,
+            base_url: self.base_url,
+            paths_dir: self.paths_dir,
+            paths: self.paths,
+            type_roots: self.type_roots,
+            types: self.types,
+            out_dir: self.out_dir,
+            composite: self.composite,
+            incremental: self.incremental.unwrap_or(self.composite),
+            build_info_file: self.build_info_file,
+            declaration: self.declaration.unwrap_or(self.composite),
+            declaration_dir: self.declaration_dir,
+            root_dir: self.root_dir,
+            references: self.references,
+            files: self.files,
+            include: self.include,
+            exclude: self.exclude,
+            errors,
+        }
+    }
+}
+
+/// Returns the setter of a boolean option which maps to a field of [Rule].
+fn bool_option(name: &str) -> Option<fn(RuleBuilder, bool) -> RuleBuilder> {
+    Some(match name {
+        "strict" => RuleBuilder::strict,
+        "noImplicitAny" => RuleBuilder::no_implicit_any,
+        "noImplicitThis" => RuleBuilder::no_implicit_this,
+        "alwaysStrict" => RuleBuilder::always_strict,
+        "strictNullChecks" => RuleBuilder::strict_null_checks,
+        "strictFunctionTypes" => RuleBuilder::strict_function_types,
+        "strictBindCallApply" => RuleBuilder::strict_bind_call_apply,
+        "strictPropertyInitialization" => RuleBuilder::strict_property_initialization,
+        "useUnknownInCatchVariables" => RuleBuilder::use_unknown_in_catch_variables,
+        "allowUnreachableCode" => RuleBuilder::allow_unreachable_code,
+        "allowUnusedLabels" => RuleBuilder::allow_unused_labels,
+        "noFallthroughCasesInSwitch" => RuleBuilder::no_fallthrough_cases_in_switch,
+        "noImplicitReturns" => RuleBuilder::no_implicit_returns,
+        "noUncheckedIndexedAccess" => RuleBuilder::no_unchecked_indexed_access,
+        "exactOptionalPropertyTypes" => RuleBuilder::exact_optional_property_types,
+        "suppressExcessPropertyErrors" => RuleBuilder::suppress_excess_property_errors,
+        "suppressImplicitAnyIndexErrors" => RuleBuilder::suppress_implicit_any_index_errors,
+        "noStrictGenericChecks" => RuleBuilder::no_strict_generic_checks,
+        "noUnusedLocals" => RuleBuilder::no_unused_locals,
+        "noUnusedParameters" => RuleBuilder::no_unused_parameters,
+        "useDefineForClassFields" => RuleBuilder::use_define_property_for_class_fields,
+        "downlevelIteration" => RuleBuilder::downlevel_iteration,
+        "isolatedModules" => RuleBuilder::isolated_modules,
+        "esModuleInterop" => RuleBuilder::es_module_interop,
+        "allowSyntheticDefaultImports" => RuleBuilder::allow_synthetic_default_imports,
+        "verbatimModuleSyntax" => RuleBuilder::verbatim_module_syntax,
+        "resolveJsonModule" => RuleBuilder::resolve_json_module,
+        "forceConsistentCasingInFileNames" => RuleBuilder::force_consistent_casing_in_file_names,
+        _ => return None,
+    })
+}
+
+const TARGETS: &[(&str, EsVersion)] = &[
+    ("es3", EsVersion::Es3),
+    ("es5", EsVersion::Es5),
+    ("es6", EsVersion::Es2015),
+    ("es2015", EsVersion::Es2015),
+    ("es2016", EsVersion::Es2016),
+    ("es2017", EsVersion::Es2017),
+    ("es2018", EsVersion::Es2018),
+    ("es2019", EsVersion::Es2019),
+    ("es2020", EsVersion::Es2020),
+    ("es2021", EsVersion::Es2021),
+    ("es2022", EsVersion::Es2022),
+    ("esnext", EsVersion::EsNext),
+];
+
+fn parse_target(s: &str) -> Option<EsVersion> {
+    TARGETS.iter().find(|(name, _)| *name == s).map(|(_, target)| *target)
+}
+
+/// `lib` accepts aliases like `es6` and is case-insensitive.
+fn normalize_lib_name(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.split_once('.') {
+        Some(("es6", rest)) => format!("es2015.{}", rest),
+        Some(("es7", rest)) => format!("es2016.{}", rest),
+        None if name == "es6" => "es2015".into(),
+        None if name == "es7" => "es2016".into(),
+        _ => name,
+    }
+}
+
+/// Resolves a pattern of `include` or `exclude` against `dir`, using `/` as
+/// the separator.
+fn resolve_pattern(dir: &Path, pattern: &str) -> String {
+    dir.join(pattern).to_string_lossy().replace('\\', "/")
+}
+
+fn string_list(value: &Value) -> Option<Vec<&str>> {
+    value.as_array()?.iter().map(Value::as_str).collect()
+}
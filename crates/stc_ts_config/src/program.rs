@@ -0,0 +1,292 @@
+//! Expansion of `files`, `include` and `exclude` into the root files of a
+//! program.
+//!
+//! Patterns follow `tsc`.
+//!
+//! - `*` matches zero or more characters, excluding `/`.
+//! - `?` matches one character, excluding `/`.
+//! - `**/` matches any directory, nested to any level.
+//! - If the last component of a pattern has no extension nor wildcard, it's a
+//!   directory and every supported file in it is included.
+//!
+//! Wildcards in `include` do not match names starting with `.`, and `**` does
+//! not enter package directories like `node_modules`.
+
+use std::{
+    collections::BTreeSet,
+    ffi::OsStr,
+    path::{Component, Path, PathBuf},
+};
+
+use walkdir::WalkDir;
+
+use crate::{ConfigError, ConfigErrorKind, TsConfig};
+
+/// Excluded if `exclude` is not set.
+const DEFAULT_EXCLUDE: [&str; 3] = ["node_modules", "bower_components", "jspm_packages"];
+
+/// Files with these extensions can be root files.
+const EXTENSIONS: [&str; 4] = ["ts", "tsx", "mts", "cts"];
+
+/// Root files of a program.
+#[derive(Debug, Clone, Default)]
+pub struct Program {
+    /// Files of `files` come first, followed by files matched by `include` in
+    /// the order of patterns.
+    pub root_files: Vec<PathBuf>,
+
+    pub errors: Vec<ConfigError>,
+}
+
+pub struct ProgramBuilder<'a> {
+    config: &'a TsConfig,
+    case_sensitive: bool,
+}
+
+impl<'a> ProgramBuilder<'a> {
+    /// Patterns are case-insensitive on Windows and macOS.
+    pub fn new(config: &'a TsConfig) -> Self {
+        Self {
+            config,
+            case_sensitive: !cfg!(any(windows, target_os = "macos")),
+        }
+    }
+
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    pub fn build(self) -> Program {
+        let config = self.config;
+        let dir = config.path.parent().unwrap_or_else(|| Path::new(""));
+
+        let mut program = Program::default();
+        let mut seen = BTreeSet::new();
+
+        for file in config.files.iter().flatten() {
+            if !file.is_file() {
+                program.errors.push(ConfigError {
+                    file: config.path.clone(),
+                    kind: ConfigErrorKind::FileNotFound { path: file.clone() },
+                });
+                continue;
+            }
+
+            if seen.insert(self.key(file)) {
+                program.root_files.push(components(file).iter().collect());
+            }
+        }
+
+        let include = match (&config.include, &config.files) {
+            (Some(include), _) => include.clone(),
+            (None, Some(..)) => vec![],
+            (None, None) => vec![dir.join("**/*").to_string_lossy().replace('\\', "/")],
+        };
+        let exclude = match &config.exclude {
+            Some(exclude) => exclude.clone(),
+            None => DEFAULT_EXCLUDE
+                .iter()
+                .map(|name| dir.join(name))
+                .chain(config.out_dir.clone())
+                .map(|path| path.to_string_lossy().replace('\\', "/"))
+                .collect(),
+        };
+
+        let include_patterns = include.iter().map(|p| Pattern::new(p, true)).collect::<Vec<_>>();
+        let exclude_patterns = exclude.iter().map(|p| Pattern::new(p, false)).collect::<Vec<_>>();
+        let is_excluded = |path: &Path| {
+            let components = components(path);
+            exclude_patterns.iter().any(|p| p.matches(&components, self.case_sensitive))
+        };
+
+        let mut matched = vec![];
+        for pattern in &include_patterns {
+            let walker = WalkDir::new(pattern.base()).sort_by_file_name().into_iter().filter_entry(|e| {
+                e.depth() == 0 || !e.file_type().is_dir() || (pattern.may_enter(e.file_name()) && !is_excluded(e.path()))
+            });
+
+            for entry in walker.flatten() {
+                let path = entry.path();
+                if !entry.file_type().is_file() || !is_supported(path) {
+                    continue;
+                }
+
+                if pattern.matches(&components(path), self.case_sensitive) && !is_excluded(path) && seen.insert(self.key(path)) {
+                    matched.push(entry.into_path());
+                }
+            }
+        }
+
+        // Declaration files are outputs of source files with the same name.
+        let sources = matched
+            .iter()
+            .filter(|path| !is_declaration(path))
+            .map(|path| self.key(&path.with_extension("")))
+            .collect::<BTreeSet<_>>();
+        program.root_files.extend(
+            matched
+                .into_iter()
+                .filter(|path| !is_declaration(path) || !sources.contains(&self.key(&strip_declaration(path)))),
+        );
+
+        if program.root_files.is_empty() {
+            program.errors.push(ConfigError {
+                file: config.path.clone(),
+                kind: ConfigErrorKind::NoInputs { include, exclude },
+            });
+        }
+
+        program
+    }
+
+    /// The key to deduplicate files.
+    fn key(&self, path: &Path) -> String {
+        let s = components(path).join("/");
+        if self.case_sensitive {
+            s
+        } else {
+            s.to_lowercase()
+        }
+    }
+}
+
+/// A pattern of `include` or `exclude`, split into components.
+struct Pattern {
+    components: Vec<String>,
+    /// Patterns of `exclude` also match the contents of matched directories.
+    is_include: bool,
+}
+
+impl Pattern {
+    fn new(pattern: &str, is_include: bool) -> Self {
+        let mut components = components(Path::new(pattern));
+
+        if is_include {
+            let is_dir = components.last().map_or(true, |last| !last.contains(['*', '?', '.']));
+            if is_dir {
+                components.push("**".into());
+                components.push("*".into());
+            }
+        }
+
+        Self { components, is_include }
+    }
+
+    /// The directory containing all matching files.
+    fn base(&self) -> PathBuf {
+        self.components.iter().take_while(|c| !has_wildcard(c)).collect()
+    }
+
+    /// Returns `false` if no file in a directory named `name` can match.
+    fn may_enter(&self, name: &OsStr) -> bool {
+        let name = name.to_string_lossy();
+        if !is_implicitly_excluded(&name) {
+            return true;
+        }
+
+        // `**` and wildcards do not enter these directories, but literals do.
+        self.components.iter().any(|c| *c == name)
+    }
+
+    fn matches(&self, path: &[String], case_sensitive: bool) -> bool {
+        if self.is_include {
+            match_components(&self.components, path, true, case_sensitive)
+        } else {
+            (1..=path.len()).any(|len| match_components(&self.components, &path[..len], false, case_sensitive))
+        }
+    }
+}
+
+fn match_components(pattern: &[String], path: &[String], is_include: bool, case_sensitive: bool) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            if match_components(rest, path, is_include, case_sensitive) {
+                return true;
+            }
+
+            match path.split_first() {
+                Some((dir, path)) if !(is_include && is_implicitly_excluded(dir)) => {
+                    match_components(pattern, path, is_include, case_sensitive)
+                }
+                _ => false,
+            }
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path)) => {
+                match_component(first, name, is_include, case_sensitive) && match_components(rest, path, is_include, case_sensitive)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Matches `*` and `?` in a component.
+fn match_component(pattern: &str, name: &str, is_include: bool, case_sensitive: bool) -> bool {
+    if is_include && name.starts_with('.') && pattern.starts_with(['*', '?']) {
+        return false;
+    }
+
+    let normalize = |s: &str| -> Vec<char> {
+        if case_sensitive {
+            s.chars().collect()
+        } else {
+            s.to_lowercase().chars().collect()
+        }
+    };
+    let pattern = normalize(pattern);
+    let name = normalize(name);
+
+    fn wildcard(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| wildcard(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && wildcard(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && wildcard(rest, &name[1..]),
+        }
+    }
+
+    wildcard(&pattern, &name)
+}
+
+fn has_wildcard(component: &str) -> bool {
+    component.contains(['*', '?'])
+}
+
+/// Directories which are skipped by wildcards of `include`.
+fn is_implicitly_excluded(name: &str) -> bool {
+    name.starts_with('.') || DEFAULT_EXCLUDE.contains(&name)
+}
+
+/// Splits `path` into normalized components. `.` is removed and `..` removes
+/// the previous component.
+fn components(path: &Path) -> Vec<String> {
+    let mut components = vec![];
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                components.pop();
+            }
+            _ => components.push(c.as_os_str().to_string_lossy().into_owned()),
+        }
+    }
+    components
+}
+
+fn is_supported(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| EXTENSIONS.contains(&ext))
+}
+
+pub(crate) fn is_declaration(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts")
+}
+
+/// `a.d.ts` to `a`.
+fn strip_declaration(path: &Path) -> PathBuf {
+    path.with_extension("").with_extension("")
+}
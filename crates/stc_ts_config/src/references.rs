@@ -0,0 +1,124 @@
+//! Project references.
+//!
+//! - https://www.typescriptlang.org/docs/handbook/project-references.html
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Error;
+
+use crate::{program::is_declaration, ConfigError, ConfigErrorKind, TsConfig};
+
+/// A project and the projects it references, transitively.
+#[derive(Debug, Clone)]
+pub struct ProjectGraph {
+    /// Projects in build order. Referenced projects come before the projects
+    /// referencing them, so the loaded project is the last one.
+    pub projects: Vec<TsConfig>,
+
+    /// Problems of references. Problems of each configuration are stored in
+    /// [TsConfig::errors].
+    pub errors: Vec<ConfigError>,
+}
+
+impl ProjectGraph {
+    /// Fails only if `path` itself cannot be loaded.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let root = TsConfig::load(path)?;
+
+        let mut graph = ProjectGraph {
+            projects: vec![],
+            errors: vec![],
+        };
+        graph.visit(root, &mut vec![]);
+
+        Ok(graph)
+    }
+
+    /// The loaded project.
+    pub fn root(&self) -> &TsConfig {
+        self.projects.last().expect("the root project is always loaded")
+    }
+
+    /// Returns the projects referenced by `config`.
+    pub fn references<'a>(&'a self, config: &'a TsConfig) -> impl 'a + Iterator<Item = &'a TsConfig> {
+        config.references.iter().filter_map(move |reference| {
+            let reference = reference.canonicalize().ok()?;
+            self.projects.iter().find(|p| p.path == reference)
+        })
+    }
+
+    /// `stack` contains the projects referencing `config`, to detect cycles.
+    fn visit(&mut self, config: TsConfig, stack: &mut Vec<PathBuf>) {
+        stack.push(config.path.clone());
+
+        for reference in &config.references {
+            let mut report = |kind| {
+                self.errors.push(ConfigError {
+                    file: config.path.clone(),
+                    kind,
+                })
+            };
+
+            let path = match reference.canonicalize() {
+                Ok(v) => v,
+                Err(..) => {
+                    report(ConfigErrorKind::FileNotFound { path: reference.clone() });
+                    continue;
+                }
+            };
+
+            if let Some(idx) = stack.iter().position(|p| *p == path) {
+                let mut chain = stack[idx..].to_vec();
+                chain.push(path);
+                report(ConfigErrorKind::CircularReferences { chain });
+                continue;
+            }
+
+            if self.projects.iter().any(|p| p.path == path) {
+                continue;
+            }
+
+            match TsConfig::load(&path) {
+                Ok(referenced) => {
+                    if !referenced.composite {
+                        report(ConfigErrorKind::ReferencedProjectNotComposite { path });
+                    }
+                    self.visit(referenced, stack);
+                }
+                Err(err) => report(ConfigErrorKind::CannotRead {
+                    path,
+                    message: err.to_string(),
+                }),
+            }
+        }
+
+        stack.pop();
+        self.projects.push(config);
+    }
+}
+
+impl TsConfig {
+    /// Returns the declaration file emitted for `source`, or [None] if
+    /// `source` does not have one.
+    ///
+    /// Outputs are placed in `declarationDir` or `outDir`, keeping the path
+    /// relative to `rootDir`.
+    pub fn declaration_output(&self, source: &Path) -> Option<PathBuf> {
+        if is_declaration(source) {
+            return None;
+        }
+
+        let ext = match source.extension()?.to_str()? {
+            "ts" | "tsx" => "d.ts",
+            "mts" => "d.mts",
+            "cts" => "d.cts",
+            _ => return None,
+        };
+
+        let root_dir = self.root_dir.as_deref().or_else(|| self.path.parent())?;
+        let relative = source.strip_prefix(root_dir).ok()?;
+        let out_dir = self.declaration_dir.as_deref().or(self.out_dir.as_deref()).unwrap_or(root_dir);
+
+        Some(out_dir.join(relative).with_extension(ext))
+    }
+}
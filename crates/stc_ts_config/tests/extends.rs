@@ -0,0 +1,231 @@
+use std::{fs, path::PathBuf};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::{ConfigErrorKind, TsConfig};
+use stc_ts_env::{ModuleKind, ModuleResolution};
+use swc_ecma_ast::EsVersion;
+
+/// Writes `files` into a temporary directory and loads `entry`.
+fn load(name: &str, entry: &str, files: &[(&str, &str)]) -> (PathBuf, TsConfig) {
+    let dir = std::env::temp_dir().join("stc-config").join(name);
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+
+    let config = TsConfig::load(&dir.join(entry)).unwrap();
+    (dir, config)
+}
+
+#[test]
+fn comments_and_trailing_commas() {
+    let (_, config) = load(
+        "comments",
+        "tsconfig.json",
+        &[(
+            "tsconfig.json",
+            r#"{
+    // Line comment
+    "compilerOptions": {
+        /* Block comment */
+        "strictNullChecks": true,
+        "target": "ES2020",
+        "module": "CommonJS",
+        "types": ["node",],
+    },
+}
+"#,
+        )],
+    );
+
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+    assert!(config.rule.strict_null_checks);
+    assert!(!config.rule.no_implicit_any);
+    assert_eq!(config.target, EsVersion::Es2020);
+    assert!(matches!(config.module.kind, ModuleKind::CommonJs));
+    assert_eq!(config.types, Some(vec!["node".to_string()]));
+}
+
+#[test]
+fn extends_relative_and_package() {
+    let (dir, config) = load(
+        "extends",
+        "app/tsconfig.json",
+        &[
+            (
+                "node_modules/@tsconfig/strictest/tsconfig.json",
+                r#"{ "compilerOptions": { "strict": true, "noUnusedLocals": true, "target": "es2017" } }"#,
+            ),
+            (
+                "base/tsconfig.base.json",
+                r#"{
+    "extends": "@tsconfig/strictest/tsconfig.json",
+    "compilerOptions": { "baseUrl": ".", "paths": { "@lib/*": ["lib/*"] }, "noUnusedLocals": false }
+}"#,
+            ),
+            (
+                "app/tsconfig.json",
+                r#"{ "extends": "../base/tsconfig.base", "compilerOptions": { "noImplicitAny": false } }"#,
+            ),
+        ],
+    );
+
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+    assert!(config.rule.strict_null_checks);
+    assert!(!config.rule.no_implicit_any);
+    assert!(!config.rule.no_unused_locals);
+    assert_eq!(config.target, EsVersion::Es2017);
+    // Paths are relative to the file declaring them.
+    assert_eq!(config.base_url, Some(dir.join("base").join(".")));
+    assert_eq!(config.paths_dir, dir.join("base"));
+    assert_eq!(config.paths, vec![("@lib/*".to_string(), vec!["lib/*".to_string()])]);
+}
+
+#[test]
+fn extends_array() {
+    let (_, config) = load(
+        "extends-array",
+        "tsconfig.json",
+        &[
+            ("a.json", r#"{ "compilerOptions": { "target": "es2015", "strict": true } }"#),
+            ("b.json", r#"{ "compilerOptions": { "target": "es2019" } }"#),
+            ("tsconfig.json", r#"{ "extends": ["./a.json", "./b.json"] }"#),
+        ],
+    );
+
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+    assert!(config.rule.strict_null_checks);
+    assert_eq!(config.target, EsVersion::Es2019);
+}
+
+#[test]
+fn circular_extends() {
+    let (_, config) = load(
+        "circular",
+        "tsconfig.json",
+        &[
+            ("a.json", r#"{ "extends": "./tsconfig.json" }"#),
+            ("tsconfig.json", r#"{ "extends": "./a.json" }"#),
+        ],
+    );
+
+    assert!(
+        config
+            .errors
+            .iter()
+            .any(|err| matches!(err.kind, ConfigErrorKind::CircularExtends { .. })),
+        "{:?}",
+        config.errors
+    );
+}
+
+#[test]
+fn invalid_options() {
+    let (_, config) = load(
+        "invalid",
+        "tsconfig.json",
+        &[(
+            "tsconfig.json",
+            r#"{
+    "extends": "./missing.json",
+    "compilerOptions": {
+        "strictt": true,
+        "strict": "yes",
+        "target": "es2099",
+        "lib": ["es2015", "es2099"],
+        "outDir": "dist"
+    }
+}"#,
+        )],
+    );
+
+    let codes = config.errors.iter().map(|err| err.code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec![6053, 6046, 5024, 5023, 6046], "{:?}", config.errors);
+}
+
+#[test]
+fn build_info_path() {
+    let (dir, config) = load(
+        "build-info",
+        "tsconfig.app.json",
+        &[(
+            "tsconfig.app.json",
+            r#"{ "compilerOptions": { "composite": true, "outDir": "dist" } }"#,
+        )],
+    );
+    assert!(config.incremental);
+    assert_eq!(config.build_info_path(), dir.join("dist/tsconfig.app.stcbuildinfo"));
+
+    let (dir, config) = load(
+        "build-info-file",
+        "tsconfig.json",
+        &[(
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "incremental": true, "tsBuildInfoFile": ".cache/stc" } }"#,
+        )],
+    );
+    assert!(config.incremental);
+    assert_eq!(config.build_info_path(), dir.join(".cache/stc"));
+}
+
+#[test]
+fn default_libs() {
+    let (_, config) = load(
+        "default-libs",
+        "tsconfig.json",
+        &[("tsconfig.json", r#"{ "compilerOptions": { "target": "es2015" } }"#)],
+    );
+    let libs = config.libs_or_default();
+    assert!(libs.contains(&Lib::Es2015Core));
+    assert!(libs.contains(&Lib::Dom));
+    assert!(!libs.contains(&Lib::Es2016));
+
+    let (_, config) = load(
+        "no-lib",
+        "tsconfig.json",
+        &[("tsconfig.json", r#"{ "compilerOptions": { "target": "es2015", "noLib": true } }"#)],
+    );
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+    assert_eq!(config.libs_or_default(), vec![]);
+}
+
+#[test]
+fn module_resolution() {
+    let (_, config) = load(
+        "module-resolution-default",
+        "tsconfig.json",
+        &[("tsconfig.json", r#"{ "compilerOptions": { "module": "nodenext" } }"#)],
+    );
+    assert_eq!(config.module.resolution, ModuleResolution::NodeNext);
+
+    let (_, config) = load(
+        "module-resolution",
+        "tsconfig.json",
+        &[(
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "module": "esnext", "moduleResolution": "Node" } }"#,
+        )],
+    );
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+    assert!(matches!(config.module.kind, ModuleKind::EsNext));
+    assert_eq!(config.module.resolution, ModuleResolution::Node10);
+}
+
+#[test]
+fn custom_conditions() {
+    let (_, config) = load(
+        "custom-conditions",
+        "tsconfig.json",
+        &[
+            ("base.json", r#"{ "compilerOptions": { "customConditions": ["development"] } }"#),
+            (
+                "tsconfig.json",
+                r#"{ "extends": "./base.json", "compilerOptions": { "module": "node16" } }"#,
+            ),
+        ],
+    );
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+    assert_eq!(config.module.custom_conditions, vec!["development".to_string()]);
+}
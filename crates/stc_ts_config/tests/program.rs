@@ -0,0 +1,129 @@
+use std::{fs, path::PathBuf};
+
+use stc_ts_config::{ConfigErrorKind, Program, ProgramBuilder, TsConfig};
+
+/// Writes `files` into a temporary directory, and expands root files of
+/// `tsconfig.json` in it.
+fn build(name: &str, case_sensitive: bool, files: &[(&str, &str)]) -> (PathBuf, Program) {
+    let dir = std::env::temp_dir().join("stc-program").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+
+    let config = TsConfig::load(&dir.join("tsconfig.json")).unwrap();
+    assert!(config.errors.is_empty(), "{:?}", config.errors);
+
+    let program = ProgramBuilder::new(&config).case_sensitive(case_sensitive).build();
+    (dir, program)
+}
+
+fn relative(dir: &PathBuf, program: &Program) -> Vec<String> {
+    program
+        .root_files
+        .iter()
+        .map(|path| path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+#[test]
+fn default_include() {
+    let (dir, program) = build(
+        "default",
+        true,
+        &[
+            ("tsconfig.json", "{}"),
+            ("a.ts", ""),
+            ("src/b.tsx", ""),
+            ("src/c.js", ""),
+            ("src/nested/d.d.ts", ""),
+            (".hidden/e.ts", ""),
+            ("node_modules/pkg/index.d.ts", ""),
+        ],
+    );
+
+    assert!(program.errors.is_empty(), "{:?}", program.errors);
+    assert_eq!(relative(&dir, &program), vec!["a.ts", "src/b.tsx", "src/nested/d.d.ts"]);
+}
+
+#[test]
+fn include_and_exclude() {
+    let (dir, program) = build(
+        "include-exclude",
+        true,
+        &[
+            (
+                "tsconfig.json",
+                r#"{ "include": ["src", "types/*.d.ts"], "exclude": ["src/**/*.test.ts", "src/generated"] }"#,
+            ),
+            ("src/a.ts", ""),
+            ("src/a.test.ts", ""),
+            ("src/deep/b.ts", ""),
+            ("src/deep/b.test.ts", ""),
+            ("src/generated/c.ts", ""),
+            ("types/globals.d.ts", ""),
+            ("types/nested/skipped.d.ts", ""),
+            ("other/d.ts", ""),
+        ],
+    );
+
+    assert!(program.errors.is_empty(), "{:?}", program.errors);
+    assert_eq!(relative(&dir, &program), vec!["src/a.ts", "src/deep/b.ts", "types/globals.d.ts"]);
+}
+
+#[test]
+fn files_are_not_excluded() {
+    let (dir, program) = build(
+        "files",
+        true,
+        &[
+            (
+                "tsconfig.json",
+                r#"{ "files": ["./lib/main.ts", "missing.ts"], "exclude": ["lib"] }"#,
+            ),
+            ("lib/main.ts", ""),
+            ("lib/other.ts", ""),
+        ],
+    );
+
+    assert_eq!(relative(&dir, &program), vec!["lib/main.ts"]);
+    assert!(
+        matches!(&*program.errors, [err] if matches!(err.kind, ConfigErrorKind::FileNotFound { .. })),
+        "{:?}",
+        program.errors
+    );
+}
+
+#[test]
+fn declaration_with_source() {
+    let (dir, program) = build(
+        "declaration",
+        true,
+        &[("tsconfig.json", "{}"), ("a.ts", ""), ("a.d.ts", ""), ("b.d.ts", "")],
+    );
+
+    assert_eq!(relative(&dir, &program), vec!["a.ts", "b.d.ts"]);
+}
+
+#[test]
+fn case_sensitivity() {
+    let files = [
+        ("tsconfig.json", r#"{ "include": ["src/**/*.TS"], "exclude": ["src/IGNORED.ts"] }"#),
+        ("src/a.ts", ""),
+        ("src/ignored.ts", ""),
+    ];
+
+    let (_, program) = build("case-sensitive", true, &files);
+    assert!(
+        matches!(&*program.errors, [err] if matches!(err.kind, ConfigErrorKind::NoInputs { .. })),
+        "{:?}",
+        program.errors
+    );
+
+    let (dir, program) = build("case-insensitive", false, &files);
+    assert!(program.errors.is_empty(), "{:?}", program.errors);
+    assert_eq!(relative(&dir, &program), vec!["src/a.ts"]);
+}
@@ -0,0 +1,127 @@
+use std::{fs, path::PathBuf};
+
+use stc_ts_config::{ConfigErrorKind, ProjectGraph};
+
+/// Writes `files` into a temporary directory and loads the project graph of
+/// `entry`.
+fn load(name: &str, entry: &str, files: &[(&str, &str)]) -> (PathBuf, ProjectGraph) {
+    let dir = std::env::temp_dir().join("stc-references").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+
+    let graph = ProjectGraph::load(&dir.join(entry)).unwrap();
+    (dir, graph)
+}
+
+#[test]
+fn build_order() {
+    let (dir, graph) = load(
+        "order",
+        "tsconfig.json",
+        &[
+            (
+                "tsconfig.json",
+                r#"{ "files": [], "references": [{ "path": "./app" }, { "path": "./core" }] }"#,
+            ),
+            (
+                "app/tsconfig.json",
+                r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../core/tsconfig.json" }] }"#,
+            ),
+            ("core/tsconfig.json", r#"{ "compilerOptions": { "composite": true } }"#),
+        ],
+    );
+
+    assert!(graph.errors.is_empty(), "{:?}", graph.errors);
+    let order = graph.projects.iter().map(|p| p.path.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        order,
+        vec![
+            dir.join("core/tsconfig.json"),
+            dir.join("app/tsconfig.json"),
+            dir.join("tsconfig.json")
+        ]
+    );
+    assert_eq!(graph.references(graph.root()).count(), 2);
+}
+
+#[test]
+fn invalid_references() {
+    let (_, graph) = load(
+        "invalid",
+        "tsconfig.json",
+        &[
+            (
+                "tsconfig.json",
+                r#"{ "references": [{ "path": "./a" }, { "path": "./plain" }, { "path": "./missing" }] }"#,
+            ),
+            (
+                "a/tsconfig.json",
+                r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../b" }] }"#,
+            ),
+            (
+                "b/tsconfig.json",
+                r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../a" }] }"#,
+            ),
+            ("plain/tsconfig.json", "{}"),
+        ],
+    );
+
+    let kinds = graph.errors.iter().map(|err| err.kind.clone()).collect::<Vec<_>>();
+    assert!(
+        matches!(
+            &*kinds,
+            [
+                ConfigErrorKind::CircularReferences { .. },
+                ConfigErrorKind::ReferencedProjectNotComposite { .. },
+                ConfigErrorKind::FileNotFound { .. },
+            ]
+        ),
+        "{:?}",
+        kinds
+    );
+}
+
+#[test]
+fn composite_without_declaration() {
+    let (_, graph) = load(
+        "declaration",
+        "tsconfig.json",
+        &[(
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "composite": true, "declaration": false } }"#,
+        )],
+    );
+
+    assert!(
+        matches!(&*graph.root().errors, [err] if err.kind == ConfigErrorKind::CompositeWithoutDeclaration),
+        "{:?}",
+        graph.root().errors
+    );
+}
+
+#[test]
+fn declaration_output() {
+    let (dir, graph) = load(
+        "output",
+        "tsconfig.json",
+        &[(
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "composite": true, "rootDir": "src", "outDir": "dist" } }"#,
+        )],
+    );
+    let config = graph.root();
+
+    assert!(config.declaration);
+    assert_eq!(
+        config.declaration_output(&dir.join("src/nested/a.ts")),
+        Some(dir.join("dist/nested/a.d.ts"))
+    );
+    assert_eq!(config.declaration_output(&dir.join("src/b.mts")), Some(dir.join("dist/b.d.mts")));
+    assert_eq!(config.declaration_output(&dir.join("src/c.d.ts")), None);
+    assert_eq!(config.declaration_output(&dir.join("other/d.ts")), None);
+}
@@ -15,7 +15,7 @@ use rnode::{NodeIdGenerator, RNode};
 use stc_ts_ast_rnode::RModule;
 use stc_ts_builtin_types::Lib;
 use stc_ts_dts::cleanup_module_for_dts;
-use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_env::{Env, ModuleKind};
 use stc_ts_file_analyzer::{
     analyzer::{Analyzer, NoopLoader},
     env::EnvFactory,
@@ -163,12 +163,7 @@ fn run_bench(b: &mut Bencher, path: PathBuf) {
 
     ::testing::run_test2(false, |cm, _| {
         let fm = cm.load_file(&path).unwrap();
-        let env = Env::simple(
-            Default::default(),
-            EsVersion::latest(),
-            ModuleConfig::None,
-            &Lib::load("es2020.full"),
-        );
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es2020.full"));
         let stable_env = env.shared().clone();
         let generator = module_id::ModuleIdGenerator::default();
         let path = Arc::new(FileName::Real(path.clone()));
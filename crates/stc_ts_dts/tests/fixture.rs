@@ -21,7 +21,7 @@ use stc_testing::get_git_root;
 use stc_ts_ast_rnode::RModule;
 use stc_ts_builtin_types::Lib;
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
-use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_env::{Env, ModuleKind};
 use stc_ts_file_analyzer::{
     analyzer::{Analyzer, NoopLoader},
     env::EnvFactory,
@@ -73,12 +73,7 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
     let res = testing::Tester::new().print_errors(|cm, handler| {
         let handler = Arc::new(handler);
         let fm = cm.load_file(&file_name).unwrap();
-        let env = Env::simple(
-            Default::default(),
-            EsVersion::latest(),
-            ModuleConfig::None,
-            &Lib::load("es2019.full"),
-        );
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es2019.full"));
         let stable_env = env.shared().clone();
         let generator = module_id::ModuleIdGenerator::default();
         let path = Arc::new(FileName::Real(file_name.clone()));
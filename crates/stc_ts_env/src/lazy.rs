@@ -0,0 +1,117 @@
+use std::{fmt, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use stc_ts_types::Type;
+use stc_utils::cache::Freeze;
+use swc_atoms::JsWord;
+
+use crate::{merge_declarations, BuiltIn};
+
+type LoadLib = Box<dyn Fn() -> Arc<BuiltIn> + Send + Sync>;
+
+/// Builtin declarations of multiple libraries, which are loaded on the first
+/// lookup of a name declared by each library.
+///
+/// Analyzing builtin libraries is slow, and most of `dom` is not used by
+/// projects which list it in `lib`.
+#[derive(Default)]
+pub struct LazyBuiltIn {
+    libs: Vec<LazyLib>,
+
+    /// Indices of libs declaring each variable, in the order of libs.
+    vars: FxHashMap<JsWord, Vec<usize>>,
+    /// Indices of libs declaring each type, in the order of libs.
+    types: FxHashMap<JsWord, Vec<usize>>,
+
+    /// Types declared by multiple libs, like `Array`, which are merged.
+    merged_types: Mutex<FxHashMap<JsWord, Option<Type>>>,
+}
+
+struct LazyLib {
+    name: JsWord,
+    builtin: OnceCell<Arc<BuiltIn>>,
+    load: LoadLib,
+}
+
+impl LazyBuiltIn {
+    /// Adds a library declaring `vars` and `types`.
+    ///
+    /// `load` is called on the first lookup of a name in `vars` or `types`.
+    /// Interfaces and namespaces declared by multiple libraries are merged in
+    /// the order of calls to this method.
+    pub fn add_lib<F>(&mut self, name: JsWord, vars: impl IntoIterator<Item = JsWord>, types: impl IntoIterator<Item = JsWord>, load: F)
+    where
+        F: 'static + Send + Sync + Fn() -> Arc<BuiltIn>,
+    {
+        let idx = self.libs.len();
+        self.libs.push(LazyLib {
+            name,
+            builtin: OnceCell::new(),
+            load: Box::new(load),
+        });
+
+        for name in vars {
+            self.vars.entry(name).or_default().push(idx);
+        }
+        for name in types {
+            self.types.entry(name).or_default().push(idx);
+        }
+    }
+
+    fn lib(&self, idx: usize) -> &BuiltIn {
+        let lib = &self.libs[idx];
+        lib.builtin.get_or_init(|| {
+            tracing::debug!("Loading builtin lib `{}` lazily", lib.name);
+            (lib.load)()
+        })
+    }
+
+    /// Names of variables declared by any lib, without loading them.
+    pub fn var_names(&self) -> impl Iterator<Item = &JsWord> {
+        self.vars.keys()
+    }
+
+    pub fn get_var(&self, name: &JsWord) -> Option<Type> {
+        let libs = self.vars.get(name)?;
+
+        libs.iter().find_map(|&idx| self.lib(idx).vars.get(name).cloned())
+    }
+
+    pub fn get_type(&self, name: &JsWord) -> Option<Type> {
+        let libs = self.types.get(name)?;
+        if let [idx] = **libs {
+            return self.lib(idx).types.get(name).cloned();
+        }
+
+        if let Some(ty) = self.merged_types.lock().get(name) {
+            return ty.clone();
+        }
+
+        // Libraries are loaded without holding the lock, as it may take a while.
+        let mut merged: Option<Type> = None;
+        for &idx in libs {
+            let ty = match self.lib(idx).types.get(name) {
+                Some(ty) => ty,
+                None => continue,
+            };
+
+            merged = Some(match merged {
+                Some(prev) => merge_declarations(&prev, ty).unwrap_or(prev),
+                None => ty.clone(),
+            });
+        }
+        let merged = merged.map(Freeze::freezed);
+
+        self.merged_types.lock().entry(name.clone()).or_insert(merged).clone()
+    }
+}
+
+impl fmt::Debug for LazyBuiltIn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyBuiltIn")
+            .field("libs", &self.libs.iter().map(|lib| &lib.name).collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -1,7 +1,7 @@
 use std::{str::FromStr, sync::Arc};
 
 use parking_lot::Mutex;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use stc_ts_errors::{Error, ErrorKind};
 use stc_ts_type_ops::Fix;
@@ -9,11 +9,15 @@ use stc_ts_types::{Id, Type};
 use stc_utils::cache::Freeze;
 use string_enum::StringEnum;
 use swc_atoms::JsWord;
-use swc_common::{Span, Spanned, DUMMY_SP};
+use swc_common::{FileName, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::EsVersion;
 
-pub use self::marks::{MarkExt, Marks};
+pub use self::{
+    lazy::LazyBuiltIn,
+    marks::{MarkExt, Marks},
+};
 
+mod lazy;
 mod marks;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -28,6 +32,80 @@ impl BuiltIn {
     }
 }
 
+/// Merges declarations of a type declared by multiple libraries, like `Array`
+/// of `es5` and `es2015.core`.
+///
+/// Returns [None] if they cannot be merged, in which case the first one is
+/// used.
+fn merge_declarations(prev: &Type, ty: &Type) -> Option<Type> {
+    match (prev.normalize(), ty.normalize()) {
+        (Type::Interface(prev), Type::Interface(ty)) => {
+            let mut merged = prev.clone();
+            merged.body.extend(ty.body.iter().cloned());
+            Some(Type::Interface(merged))
+        }
+        (Type::Module(prev), Type::Module(ty)) => {
+            let mut merged = prev.clone();
+            merged
+                .exports
+                .types
+                .extend(ty.exports.types.iter().map(|(k, v)| (k.clone(), v.clone())));
+            merged
+                .exports
+                .vars
+                .extend(ty.exports.vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+            Some(Type::Module(merged))
+        }
+        _ => None,
+    }
+}
+
+/// Builtin declarations used by an [Env].
+#[derive(Debug, Clone)]
+enum Builtins {
+    Eager(Arc<BuiltIn>),
+    Lazy(Arc<LazyBuiltIn>),
+}
+
+impl Builtins {
+    fn get_var(&self, name: &JsWord) -> Option<Type> {
+        match self {
+            Builtins::Eager(builtin) => builtin.vars.get(name).cloned(),
+            Builtins::Lazy(builtin) => builtin.get_var(name),
+        }
+    }
+
+    fn get_type(&self, name: &JsWord) -> Option<Type> {
+        match self {
+            Builtins::Eager(builtin) => builtin.types.get(name).cloned(),
+            Builtins::Lazy(builtin) => builtin.get_type(name),
+        }
+    }
+
+    fn var_names(&self) -> Vec<JsWord> {
+        match self {
+            Builtins::Eager(builtin) => builtin.vars.keys().cloned().collect(),
+            Builtins::Lazy(builtin) => builtin.var_names().cloned().collect(),
+        }
+    }
+}
+
+/// The kind of a declaration in the global scope shared by script files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalDeclKind {
+    /// `var` and functions, which can be declared by multiple files.
+    Var,
+    /// `let` and `const`.
+    BlockScoped,
+    Class,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ScriptGlobal {
+    kind: GlobalDeclKind,
+    span: Span,
+}
+
 /// Stuffs which can be changed between runs.
 #[derive(Debug, Clone)]
 pub struct Env {
@@ -35,13 +113,30 @@ pub struct Env {
     rule: Rule,
     target: EsVersion,
     module: ModuleConfig,
-    builtin: Arc<BuiltIn>,
+    builtin: Builtins,
     global_types: Arc<Mutex<FxHashMap<JsWord, Type>>>,
     global_vars: Arc<Mutex<FxHashMap<JsWord, Type>>>,
+    /// Names of loaded builtin libraries, like `es2015.promise`.
+    libs: Arc<Mutex<FxHashSet<JsWord>>>,
+    /// Names declared by `export as namespace Foo`.
+    umd_globals: Arc<Mutex<FxHashSet<JsWord>>>,
+    /// Declarations in the global scope of script files, which are files
+    /// without imports and exports, by file.
+    script_globals: Arc<Mutex<FxHashMap<Arc<FileName>, FxHashMap<JsWord, Vec<ScriptGlobal>>>>>,
 }
 
 impl Env {
-    pub fn new(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Arc<BuiltIn>) -> Self {
+    pub fn new(env: StableEnv, rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>, builtin: Arc<BuiltIn>) -> Self {
+        Self::with_builtins(env, rule, target, module.into(), Builtins::Eager(builtin))
+    }
+
+    /// Same as [Env::new], but builtin libraries are loaded on demand. See
+    /// [LazyBuiltIn].
+    pub fn new_lazy(env: StableEnv, rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>, builtin: Arc<LazyBuiltIn>) -> Self {
+        Self::with_builtins(env, rule, target, module.into(), Builtins::Lazy(builtin))
+    }
+
+    fn with_builtins(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Builtins) -> Self {
         Self {
             stable: env,
             builtin,
@@ -49,6 +144,9 @@ impl Env {
             module,
             global_types: Default::default(),
             global_vars: Default::default(),
+            libs: Default::default(),
+            umd_globals: Default::default(),
+            script_globals: Default::default(),
             rule,
         }
     }
@@ -61,14 +159,21 @@ impl Env {
         self.target
     }
 
-    pub const fn module(&self) -> ModuleConfig {
-        self.module
+    pub const fn module(&self) -> &ModuleConfig {
+        &self.module
     }
 
     pub const fn rule(&self) -> Rule {
         self.rule
     }
 
+    /// Replaces the rule used while analyzing a file.
+    ///
+    /// This is used to apply per-file overrides.
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
     pub fn declare_global_var(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
@@ -92,6 +197,122 @@ impl Env {
         }
     }
 
+    /// Replaces a global type with `ty`, which is the result of merging
+    /// declarations of `name`.
+    pub fn set_global_type(&mut self, name: JsWord, ty: Type) {
+        ty.assert_clone_cheap();
+
+        self.global_types.lock().insert(name, ty);
+    }
+
+    /// Declares `Foo` of `export as namespace Foo`, which can be used without
+    /// an import in scripts.
+    ///
+    /// Globals declared by other files take precedence.
+    pub fn declare_umd_global(&mut self, name: JsWord, var: Option<Type>, ty: Option<Type>) {
+        if let Some(var) = var {
+            if self.get_global_var(DUMMY_SP, &name).is_err() {
+                self.declare_global_var(name.clone(), var);
+                self.umd_globals.lock().insert(name.clone());
+            }
+        }
+
+        if let Some(ty) = ty {
+            if self.get_global_type(DUMMY_SP, &name).is_err() {
+                self.declare_global_type(name, ty);
+            }
+        }
+    }
+
+    /// Returns `true` if the global variable `name` is declared by
+    /// `export as namespace`.
+    pub fn is_umd_global(&self, name: &JsWord) -> bool {
+        self.umd_globals.lock().contains(name)
+    }
+
+    /// Records a declaration of `name` in the global scope shared by script
+    /// files, returning declarations of other files which conflict with it.
+    ///
+    /// `var`s and functions can be declared by multiple files, but other
+    /// declarations cannot.
+    pub fn declare_script_global(
+        &self,
+        file: &Arc<FileName>,
+        name: JsWord,
+        kind: GlobalDeclKind,
+        span: Span,
+    ) -> Vec<(GlobalDeclKind, Span)> {
+        let mut lock = self.script_globals.lock();
+
+        // A declaration may be visited multiple times.
+        if let Some(decls) = lock.get(file).and_then(|decls| decls.get(&name)) {
+            if decls.iter().any(|decl| decl.span == span) {
+                return vec![];
+            }
+        }
+
+        let conflicts = lock
+            .iter()
+            .filter(|(other, _)| *other != file)
+            .flat_map(|(_, decls)| decls.get(&name).into_iter().flatten())
+            .filter(|decl| kind != GlobalDeclKind::Var || decl.kind != GlobalDeclKind::Var)
+            .map(|decl| (decl.kind, decl.span))
+            .collect();
+
+        lock.entry(file.clone())
+            .or_default()
+            .entry(name)
+            .or_default()
+            .push(ScriptGlobal { kind, span });
+
+        conflicts
+    }
+
+    /// Removes declarations of `file` from the global scope shared by script
+    /// files, so the file can be checked again.
+    pub fn clear_script_globals(&self, file: &FileName) {
+        self.script_globals.lock().remove(file);
+    }
+
+    /// Marks a builtin library as loaded, returning `false` if it's already
+    /// loaded.
+    pub fn register_lib(&self, name: &str) -> bool {
+        self.libs.lock().insert(name.into())
+    }
+
+    /// Adds declarations of a builtin library referenced by
+    /// `/// <reference lib="..." />`.
+    ///
+    /// Interfaces and namespaces are merged with existing ones, like `Array`
+    /// of `es2015.core`.
+    pub fn add_builtin_lib(&mut self, lib: &BuiltIn) {
+        for (name, ty) in &lib.vars {
+            if self.get_global_var(DUMMY_SP, name).is_err() {
+                self.global_vars.lock().insert(name.clone(), ty.clone());
+            }
+        }
+
+        for (name, ty) in &lib.types {
+            let ty = match self.get_global_type(DUMMY_SP, name) {
+                Ok(prev) => match merge_declarations(&prev, ty) {
+                    Some(merged) => merged.freezed(),
+                    None => continue,
+                },
+                Err(_) => ty.clone(),
+            };
+
+            self.global_types.lock().insert(name.clone(), ty);
+        }
+    }
+
+    /// Names of all global variables, including the ones of builtin libraries
+    /// which are not loaded yet.
+    pub fn global_var_names(&self) -> Vec<JsWord> {
+        let mut names = self.builtin.var_names();
+        names.extend(self.global_vars.lock().keys().cloned());
+        names
+    }
+
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     pub fn get_global_var(&self, span: Span, name: &JsWord) -> Result<Type, Error> {
         if let Some(ty) = self.global_vars.lock().get(name) {
@@ -99,9 +320,9 @@ impl Env {
             return Ok((*ty).clone());
         }
 
-        if let Some(v) = self.builtin.vars.get(name) {
+        if let Some(v) = self.builtin.get_var(name) {
             debug_assert!(v.is_clone_cheap(), "{:?}", v);
-            return Ok(v.clone());
+            return Ok(v);
         }
 
         Err(ErrorKind::NoSuchVar {
@@ -118,9 +339,9 @@ impl Env {
             return Ok((*ty).clone());
         }
 
-        if let Some(ty) = self.builtin.types.get(name) {
+        if let Some(ty) = self.builtin.get_type(name) {
             debug_assert!(ty.is_clone_cheap(), "{:?}", ty);
-            return Ok(ty.clone());
+            return Ok(ty);
         }
 
         Err(ErrorKind::NoSuchType {
@@ -156,8 +377,9 @@ impl Default for StableEnv {
     }
 }
 
-#[derive(Clone, Copy, StringEnum)]
-pub enum ModuleConfig {
+/// `module`
+#[derive(Clone, Copy, PartialEq, Eq, StringEnum)]
+pub enum ModuleKind {
     /// `commonjs`
     CommonJs,
     /// `es6`
@@ -178,30 +400,257 @@ pub enum ModuleConfig {
     System,
     /// `esnext`
     EsNext,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
+}
+
+/// `moduleResolution`
+#[derive(Clone, Copy, PartialEq, Eq, StringEnum)]
+pub enum ModuleResolution {
+    /// `classic`
+    Classic,
+    /// `node10`
+    Node10,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
+    /// `bundler`
+    Bundler,
 }
 
+impl ModuleResolution {
+    /// The default of `tsc` for `module`.
+    pub const fn default_for(kind: ModuleKind) -> Self {
+        match kind {
+            ModuleKind::CommonJs => ModuleResolution::Node10,
+            ModuleKind::Node16 => ModuleResolution::Node16,
+            ModuleKind::NodeNext => ModuleResolution::NodeNext,
+            _ => ModuleResolution::Classic,
+        }
+    }
+
+    /// Returns true if imports are resolved like ES modules and CommonJS
+    /// modules of node, which is the case for `node16` and `nodenext`.
+    pub const fn is_node16(self) -> bool {
+        matches!(self, ModuleResolution::Node16 | ModuleResolution::NodeNext)
+    }
+}
+
+/// Options about modules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleConfig {
+    pub kind: ModuleKind,
+
+    pub resolution: ModuleResolution,
+
+    /// `customConditions`, which are used to resolve `exports` and `imports`
+    /// of `package.json` in addition to the default conditions.
+    pub custom_conditions: Vec<String>,
+}
+
+impl ModuleConfig {
+    /// Uses the default `moduleResolution` of `kind`.
+    pub const fn new(kind: ModuleKind) -> Self {
+        Self {
+            kind,
+            resolution: ModuleResolution::default_for(kind),
+            custom_conditions: Vec::new(),
+        }
+    }
+
+    pub fn resolution(mut self, resolution: ModuleResolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    pub fn custom_conditions(mut self, conditions: Vec<String>) -> Self {
+        self.custom_conditions = conditions;
+        self
+    }
+}
+
+impl From<ModuleKind> for ModuleConfig {
+    fn from(kind: ModuleKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+/// Checking rules. Fields are named after `compilerOptions` of `tsc`.
+///
+/// Use [Rule::builder] to respect the `strict` umbrella.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Rule {
+    /// `noImplicitAny`
     pub no_implicit_any: bool,
+    /// `noImplicitThis`
     pub no_implicit_this: bool,
+    /// `alwaysStrict`
     pub always_strict: bool,
+    /// `strictNullChecks`
     pub strict_null_checks: bool,
+    /// `strictFunctionTypes`
     pub strict_function_types: bool,
+    /// `strictBindCallApply`
+    pub strict_bind_call_apply: bool,
+    /// `strictPropertyInitialization`
+    pub strict_property_initialization: bool,
+    /// `useUnknownInCatchVariables`
+    pub use_unknown_in_catch_variables: bool,
 
     pub allow_unreachable_code: bool,
     pub allow_unused_labels: bool,
     pub no_fallthrough_cases_in_switch: bool,
     pub no_implicit_returns: bool,
+    pub no_unchecked_indexed_access: bool,
+    pub exact_optional_property_types: bool,
     pub suppress_excess_property_errors: bool,
     pub suppress_implicit_any_index_errors: bool,
     pub no_strict_generic_checks: bool,
     pub no_unused_locals: bool,
     pub no_unused_parameters: bool,
     pub use_define_property_for_class_fields: bool,
+    /// `downlevelIteration`
+    pub downlevel_iteration: bool,
+    /// `isolatedModules`
+    pub isolated_modules: bool,
+    /// `esModuleInterop`
+    pub es_module_interop: bool,
+    /// `allowSyntheticDefaultImports`
+    pub allow_synthetic_default_imports: bool,
+    /// `verbatimModuleSyntax`
+    pub verbatim_module_syntax: bool,
+    /// `resolveJsonModule`
+    pub resolve_json_module: bool,
+    /// `forceConsistentCasingInFileNames`
+    pub force_consistent_casing_in_file_names: bool,
+
+    /// Not a `tsc` option. Types `Object.keys`, `Object.entries`,
+    /// `Object.fromEntries`, `Object.assign` and `Object.freeze` using the
+    /// properties of object literal types instead of the declarations of
+    /// `lib.d.ts`.
+    pub precise_object_methods: bool,
+
+    /// Not a `tsc` option. Reports constructs stc cannot check yet, instead
+    /// of accepting them silently.
+    pub strict_parity: bool,
 
     pub jsx: JsxMode,
 }
 
+impl Rule {
+    pub fn builder() -> RuleBuilder {
+        RuleBuilder::default()
+    }
+
+    /// Enables all flags in the `strict` family, like `--strict`.
+    pub fn enable_strict(&mut self) {
+        self.always_strict = true;
+        self.no_implicit_any = true;
+        self.no_implicit_this = true;
+        self.strict_null_checks = true;
+        self.strict_function_types = true;
+        self.strict_bind_call_apply = true;
+        self.strict_property_initialization = true;
+        self.use_unknown_in_catch_variables = true;
+    }
+}
+
+macro_rules! strict_flags {
+    ($($name:ident),* $(,)?) => {
+        /// Builder for [Rule].
+        ///
+        /// Flags in the `strict` family default to the value passed to
+        /// [RuleBuilder::strict], and can be overridden individually regardless
+        /// of the order of calls.
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct RuleBuilder {
+            strict: bool,
+            $($name: Option<bool>,)*
+            rule: Rule,
+        }
+
+        impl RuleBuilder {
+            $(
+                pub fn $name(mut self, value: bool) -> Self {
+                    self.$name = Some(value);
+                    self
+                }
+            )*
+
+            pub fn build(self) -> Rule {
+                Rule {
+                    $($name: self.$name.unwrap_or(self.strict),)*
+                    ..self.rule
+                }
+            }
+        }
+    };
+}
+
+strict_flags!(
+    no_implicit_any,
+    no_implicit_this,
+    always_strict,
+    strict_null_checks,
+    strict_function_types,
+    strict_bind_call_apply,
+    strict_property_initialization,
+    use_unknown_in_catch_variables,
+);
+
+macro_rules! other_flags {
+    ($($name:ident),* $(,)?) => {
+        impl RuleBuilder {
+            $(
+                pub fn $name(mut self, value: bool) -> Self {
+                    self.rule.$name = value;
+                    self
+                }
+            )*
+        }
+    };
+}
+
+other_flags!(
+    allow_unreachable_code,
+    allow_unused_labels,
+    no_fallthrough_cases_in_switch,
+    no_implicit_returns,
+    no_unchecked_indexed_access,
+    exact_optional_property_types,
+    suppress_excess_property_errors,
+    suppress_implicit_any_index_errors,
+    no_strict_generic_checks,
+    no_unused_locals,
+    no_unused_parameters,
+    use_define_property_for_class_fields,
+    downlevel_iteration,
+    isolated_modules,
+    es_module_interop,
+    allow_synthetic_default_imports,
+    verbatim_module_syntax,
+    resolve_json_module,
+    force_consistent_casing_in_file_names,
+    precise_object_methods,
+    strict_parity,
+);
+
+impl RuleBuilder {
+    /// `strict`
+    pub fn strict(mut self, value: bool) -> Self {
+        self.strict = value;
+        self
+    }
+
+    pub fn jsx(mut self, jsx: JsxMode) -> Self {
+        self.rule.jsx = jsx;
+        self
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub enum JsxMode {
     #[default]
@@ -226,3 +675,57 @@ impl FromStr for JsxMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Rule;
+
+    #[test]
+    fn strict_enables_the_family() {
+        let rule = Rule::builder().strict(true).build();
+
+        assert!(rule.no_implicit_any);
+        assert!(rule.no_implicit_this);
+        assert!(rule.always_strict);
+        assert!(rule.strict_null_checks);
+        assert!(rule.strict_function_types);
+        assert!(rule.strict_bind_call_apply);
+        assert!(rule.strict_property_initialization);
+        assert!(rule.use_unknown_in_catch_variables);
+
+        // Flags outside of the family are not affected.
+        assert!(!rule.no_unchecked_indexed_access);
+        assert!(!rule.exact_optional_property_types);
+    }
+
+    #[test]
+    fn overrides_ignore_the_order_of_calls() {
+        let before = Rule::builder().strict_null_checks(false).strict(true).build();
+        let after = Rule::builder().strict(true).strict_null_checks(false).build();
+
+        for rule in [before, after] {
+            assert!(!rule.strict_null_checks);
+            assert!(rule.no_implicit_any);
+        }
+
+        let rule = Rule::builder().no_implicit_any(true).build();
+        assert!(rule.no_implicit_any);
+        assert!(!rule.strict_null_checks);
+    }
+
+    #[test]
+    fn other_flags_are_kept() {
+        let rule = Rule::builder().no_unchecked_indexed_access(true).strict(true).strict(false).build();
+
+        assert!(rule.no_unchecked_indexed_access);
+        assert!(!rule.no_implicit_any);
+    }
+
+    #[test]
+    fn enable_strict_matches_the_builder() {
+        let mut rule = Rule::default();
+        rule.enable_strict();
+
+        assert_eq!(format!("{:?}", rule), format!("{:?}", Rule::builder().strict(true).build()));
+    }
+}
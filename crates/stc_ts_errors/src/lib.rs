@@ -25,9 +25,10 @@ use swc_common::{
 };
 use swc_ecma_ast::{AssignOp, BinaryOp, UpdateOp};
 
-pub use self::result_ext::DebugExt;
+pub use self::{msg::DeferredMsg, result_ext::DebugExt};
 
 pub mod debug;
+mod msg;
 mod result_ext;
 
 /// [ErrorKind] with debug contexts attached.
@@ -73,15 +74,15 @@ impl Error {
         self
     }
 
+    /// Errors internal to stc (code `0`) are emitted without a code.
     #[cold]
     pub fn emit(&self, h: &Handler) {
         let span = self.span();
 
-        let mut err = h.struct_span_err_with_code(
-            span,
-            &format!("{:#?}", self),
-            DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code()))),
-        );
+        let mut err = match self.code() {
+            0 => h.struct_span_err(span, &format!("{:#?}", self)),
+            code => h.struct_span_err_with_code(span, &format!("{:#?}", self), DiagnosticId::Error(format!("TS{}", code))),
+        };
 
         err.emit();
     }
@@ -204,11 +205,74 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2748
+    AmbientConstEnumWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS1205
+    TypeReexportWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS1208
+    GlobalScriptWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS18056
+    EnumMemberWithoutInitializerWithIsolatedModules {
+        span: Span,
+    },
+
     /// TS7027
     UnreachableCode {
         span: Span,
     },
 
+    /// TS7029
+    FallthroughCaseInSwitch {
+        span: Span,
+    },
+
+    /// TS7030
+    NotAllCodePathsReturnValue {
+        span: Span,
+    },
+
+    /// TS6133
+    DeclaredButNeverRead {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6196
+    DeclaredButNeverUsed {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS6192
+    AllImportsUnused {
+        span: Span,
+    },
+
+    /// TS2578
+    UnusedTsExpectErrorDirective {
+        span: Span,
+    },
+
+    /// TS6385
+    ///
+    /// A suggestion. See [ErrorKind::is_suggestion].
+    Deprecated {
+        span: Span,
+        name: JsWord,
+        /// The text after `@deprecated`, if any.
+        message: Option<JsWord>,
+        decl_span: Span,
+    },
+
     /// TS2454
     VarMayNotBeInitialized {
         span: Span,
@@ -228,6 +292,12 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS5023
+    UnknownRuleInPragma {
+        span: Span,
+        name: Box<str>,
+    },
+
     /// TS17009
     ThisUsedBeforeCallingSuper {
         span: Span,
@@ -278,6 +348,22 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS4104
+    ReadonlyArrayToMutable {
+        span: Span,
+    },
+
+    /// TS2542
+    ReadonlyIndexSignature {
+        span: Span,
+    },
+
+    /// TS2380
+    GetterTypeNotAssignableToSetterType {
+        span: Span,
+        cause: Box<Error>,
+    },
+
     /// TS17013
     InvalidUsageOfNewTarget {
         span: Span,
@@ -378,6 +464,122 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2664
+    InvalidModuleNameInAugmentation {
+        span: Span,
+    },
+
+    /// TS1259
+    DefaultImportRequiresEsModuleInterop {
+        span: Span,
+    },
+
+    /// TS1361
+    TypeOnlyImportUsedAsValue {
+        span: Span,
+        name: Id,
+    },
+
+    /// TS1484
+    TypeImportedWithoutTypeOnlyImport {
+        span: Span,
+        name: Id,
+    },
+
+    /// TS2688
+    TypeDefinitionNotFound {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS1149
+    FileNameDiffersOnlyInCasing {
+        span: Span,
+        name: JsWord,
+        included: JsWord,
+    },
+
+    /// TS1261
+    FileNameDiffersFromDiskOnlyInCasing {
+        span: Span,
+        name: JsWord,
+        real: JsWord,
+    },
+
+    /// TS6053
+    ///
+    /// `/// <reference path="..." />` refers to a file which does not exist.
+    ReferencedFileNotFound {
+        span: Span,
+        path: JsWord,
+    },
+
+    /// TS2726
+    LibDefinitionNotFound {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2686
+    ///
+    /// A global declared by `export as namespace` is used in a module.
+    UmdGlobalInModule {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2307
+    ///
+    /// A pattern of `paths` matched, but no file exists for the pattern.
+    PathMappingTargetNotFound {
+        span: Span,
+        pattern: JsWord,
+    },
+
+    /// TS6305
+    OutputFileNotBuilt {
+        span: Span,
+        output: JsWord,
+        source: JsWord,
+    },
+
+    /// TS2834
+    ImportPathWithoutExtension {
+        span: Span,
+    },
+
+    /// TS2835
+    ImportPathWithoutExtensionWithSuggestion {
+        span: Span,
+        suggestion: JsWord,
+    },
+
+    /// TS2732
+    JsonModuleWithoutResolveJsonModule {
+        span: Span,
+    },
+
+    /// TS2308
+    AmbiguousStarExport {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2821
+    ImportAssertionsNotSupported {
+        span: Span,
+    },
+
+    /// TS2822
+    ImportAssertionsWithTypeOnlyImport {
+        span: Span,
+    },
+
+    /// TS2837
+    ImportAssertionValueNotString {
+        span: Span,
+    },
+
     /// TS5061
     TooManyAsterisk {
         span: Span,
@@ -409,6 +611,31 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2802
+    IterationRequiresDownlevelIteration {
+        span: Span,
+    },
+
+    /// TS1103
+    ForAwaitOutsideAsyncFn {
+        span: Span,
+    },
+
+    /// TS1064
+    AsyncFnReturnTypeMustBePromise {
+        span: Span,
+    },
+
+    /// TS1055
+    InvalidAsyncFnReturnTypeForEs5 {
+        span: Span,
+    },
+
+    /// TS1196
+    CatchClauseVarTypeMustBeAnyOrUnknown {
+        span: Span,
+    },
+
     /// TS2494
     ForOfStringUsedInEs3 {
         span: Span,
@@ -512,6 +739,11 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS1061
+    EnumMemberMustHaveInit {
+        span: Span,
+    },
+
     /// TS2364
     InvalidLhsOfAssign {
         span: Span,
@@ -547,6 +779,8 @@ pub enum ErrorKind {
     /// TS2769
     NoMatchingOverload {
         span: Span,
+        /// Why each overload failed, in declaration order.
+        causes: Vec<Error>,
     },
 
     /// TS2427
@@ -931,6 +1165,22 @@ pub enum ErrorKind {
         name: Id,
     },
 
+    /// TS2552
+    NoSuchVarWithSuggestion {
+        span: Span,
+        name: Id,
+        /// A similar name in scope.
+        suggestion: JsWord,
+    },
+
+    /// TS2583
+    NoSuchVarInLib {
+        span: Span,
+        name: Id,
+        /// The lib declaring the variable, like `es2015`.
+        lib: JsWord,
+    },
+
     /// TS2689
     CannotExtendTypeOnlyItem {
         span: Span,
@@ -985,6 +1235,22 @@ pub enum ErrorKind {
         prop: Option<Box<Key>>,
     },
 
+    /// TS2551
+    NoSuchPropertyWithSuggestion {
+        span: Span,
+        prop: Box<Key>,
+        /// A similar property of the object.
+        suggestion: JsWord,
+    },
+
+    /// TS2550
+    NoSuchPropertyInLib {
+        span: Span,
+        prop: Box<Key>,
+        /// The lib declaring the property, like `es2022`.
+        lib: JsWord,
+    },
+
     NoInitAndNoDefault {
         span: Span,
     },
@@ -1044,7 +1310,24 @@ pub enum ErrorKind {
 
     Unimplemented {
         span: Span,
-        msg: String,
+        msg: DeferredMsg,
+    },
+
+    /// Not a TypeScript error.
+    ///
+    /// The analysis is aborted using a cancellation token, and the result is
+    /// incomplete. This is never reported.
+    Cancelled {
+        span: Span,
+    },
+
+    /// Not a TypeScript error.
+    ///
+    /// Reported instead of errors over the error limit of the checker.
+    TooManyErrors {
+        span: Span,
+        /// The number of errors which are not reported.
+        suppressed: usize,
     },
 
     ResolvedFailed {
@@ -1532,6 +1815,11 @@ impl Error {
 }
 
 impl ErrorKind {
+    /// Normalizes similar error codes into one, which is used to compare
+    /// errors with the baselines of `tsc`, because stc may report a similar
+    /// error instead.
+    ///
+    /// Use [ErrorKind::code] for reporting.
     pub fn normalize_error_code(code: usize) -> usize {
         match code {
             // TS2304: Type not found.
@@ -1634,6 +1922,20 @@ impl ErrorKind {
         err.context_impl(loc, context)
     }
 
+    /// Lazy version of [ErrorKind::context]. `context` is not called in
+    /// release builds.
+    #[track_caller]
+    pub fn with_context<F>(self, context: F) -> Error
+    where
+        F: FnOnce() -> String,
+    {
+        if !cfg!(debug_assertions) {
+            return self.into();
+        }
+
+        self.context(context())
+    }
+
     /// Split error into causes.
     pub fn into_causes(self) -> Vec<Error> {
         match self {
@@ -1648,7 +1950,12 @@ impl ErrorKind {
         }
     }
 
-    /// TypeScript error code.
+    /// TypeScript error code, like `2322` of `TS2322`, which is reported to
+    /// users.
+    ///
+    /// This is `0` for errors which are internal to stc or wrap other errors,
+    /// which `tsc` does not report. New variants should be added to this
+    /// method, as it does not have a fallback.
     pub fn code(&self) -> usize {
         match self {
             ErrorKind::TS1016 { .. } => 1016,
@@ -1704,6 +2011,9 @@ impl ErrorKind {
             | ErrorKind::NoSuchPropertyInClass { .. }
             | ErrorKind::NoSuchPropertyInModule { .. } => 2339,
 
+            ErrorKind::NoSuchPropertyInLib { .. } => 2550,
+            ErrorKind::NoSuchPropertyWithSuggestion { .. } => 2551,
+
             ErrorKind::AssignOpCannotBeApplied { .. } => 2365,
             ErrorKind::TypeUsedAsVar { .. } => 2693,
             ErrorKind::CannotExtendTypeOnlyItem { .. } => 2689,
@@ -1735,6 +2045,8 @@ impl ErrorKind {
             ErrorKind::DuplicateName { .. } | ErrorKind::DuplicateNameWithoutName { .. } => 2300,
 
             ErrorKind::NoSuchVar { .. } => 2304,
+            ErrorKind::NoSuchVarWithSuggestion { .. } => 2552,
+            ErrorKind::NoSuchVarInLib { .. } => 2583,
             ErrorKind::NoSuchType { .. } => 2304,
             ErrorKind::NoSuchTypeButVarExists { .. } => 2749,
             ErrorKind::NoSuchVarButThisHasSuchProperty { .. } => 2663,
@@ -1875,6 +2187,8 @@ impl ErrorKind {
 
             ErrorKind::EnumMemberIdCannotBeNumber { .. } => 2452,
 
+            ErrorKind::EnumMemberMustHaveInit { .. } => 1061,
+
             ErrorKind::NamespaceNotFound { .. } => 2503,
 
             ErrorKind::WithStmtNotSupported { .. } => 2410,
@@ -1921,6 +2235,11 @@ impl ErrorKind {
             ErrorKind::NotArrayType { .. } => 2461,
             ErrorKind::NotArrayTypeNorStringType { .. } => 2495,
             ErrorKind::NotArrayTypeNorStringTypeButDownlevelIterationWouldWork { .. } => 2569,
+            ErrorKind::IterationRequiresDownlevelIteration { .. } => 2802,
+            ErrorKind::ForAwaitOutsideAsyncFn { .. } => 1103,
+            ErrorKind::AsyncFnReturnTypeMustBePromise { .. } => 1064,
+            ErrorKind::InvalidAsyncFnReturnTypeForEs5 { .. } => 1055,
+            ErrorKind::CatchClauseVarTypeMustBeAnyOrUnknown { .. } => 1196,
 
             ErrorKind::NoConstructablePropertyWithName { .. } => 2348,
 
@@ -1944,6 +2263,44 @@ impl ErrorKind {
 
             ErrorKind::ModuleNotFound { .. } => 2307,
 
+            ErrorKind::InvalidModuleNameInAugmentation { .. } => 2664,
+
+            ErrorKind::DefaultImportRequiresEsModuleInterop { .. } => 1259,
+
+            ErrorKind::TypeOnlyImportUsedAsValue { .. } => 1361,
+
+            ErrorKind::TypeImportedWithoutTypeOnlyImport { .. } => 1484,
+
+            ErrorKind::JsonModuleWithoutResolveJsonModule { .. } => 2732,
+
+            ErrorKind::PathMappingTargetNotFound { .. } => 2307,
+
+            ErrorKind::OutputFileNotBuilt { .. } => 6305,
+
+            ErrorKind::TypeDefinitionNotFound { .. } => 2688,
+
+            ErrorKind::ReferencedFileNotFound { .. } => 6053,
+
+            ErrorKind::FileNameDiffersOnlyInCasing { .. } => 1149,
+
+            ErrorKind::FileNameDiffersFromDiskOnlyInCasing { .. } => 1261,
+
+            ErrorKind::LibDefinitionNotFound { .. } => 2726,
+
+            ErrorKind::UmdGlobalInModule { .. } => 2686,
+
+            ErrorKind::ImportPathWithoutExtension { .. } => 2834,
+
+            ErrorKind::ImportPathWithoutExtensionWithSuggestion { .. } => 2835,
+
+            ErrorKind::AmbiguousStarExport { .. } => 2308,
+
+            ErrorKind::ImportAssertionsNotSupported { .. } => 2821,
+
+            ErrorKind::ImportAssertionsWithTypeOnlyImport { .. } => 2822,
+
+            ErrorKind::ImportAssertionValueNotString { .. } => 2837,
+
             ErrorKind::DuplicateConstructor { .. } => 2392,
 
             ErrorKind::DuplicateFnImpl { .. } => 2393,
@@ -1971,6 +2328,11 @@ impl ErrorKind {
 
             ErrorKind::AssignFailedBecauseTupleLengthDiffers { .. } => 2322,
 
+            ErrorKind::ReadonlyArrayToMutable { .. } => 4104,
+            ErrorKind::ReadonlyIndexSignature { .. } => 2542,
+
+            ErrorKind::GetterTypeNotAssignableToSetterType { .. } => 2380,
+
             ErrorKind::ClassMemberNotCompatibleWithStringIndexSignature { .. } => 2411,
 
             ErrorKind::ClassMemberNotCompatibleWithNumericIndexSignature { .. } => 2411,
@@ -2003,6 +2365,8 @@ impl ErrorKind {
 
             ErrorKind::OptionInvalidForEs3 { .. } => 5048,
 
+            ErrorKind::UnknownRuleInPragma { .. } => 5023,
+
             ErrorKind::ShouldBeStaticMethod { .. } => 2387,
 
             ErrorKind::ShouldBeInstanceMethod { .. } => 2388,
@@ -2016,11 +2380,27 @@ impl ErrorKind {
             ErrorKind::VarMayNotBeInitialized { .. } => 2454,
 
             ErrorKind::UnreachableCode { .. } => 7027,
+            ErrorKind::FallthroughCaseInSwitch { .. } => 7029,
+            ErrorKind::NotAllCodePathsReturnValue { .. } => 7030,
+
+            ErrorKind::DeclaredButNeverRead { .. } => 6133,
+            ErrorKind::DeclaredButNeverUsed { .. } => 6196,
+            ErrorKind::AllImportsUnused { .. } => 6192,
+            ErrorKind::UnusedTsExpectErrorDirective { .. } => 2578,
+            ErrorKind::Deprecated { .. } => 6385,
 
             ErrorKind::ConstEnumMemberHasInfinityAsInit { .. } => 2477,
 
             ErrorKind::ConstEnumMemberHasNaNAsInit { .. } => 2478,
 
+            ErrorKind::AmbientConstEnumWithIsolatedModules { .. } => 2748,
+
+            ErrorKind::TypeReexportWithIsolatedModules { .. } => 1205,
+
+            ErrorKind::GlobalScriptWithIsolatedModules { .. } => 1208,
+
+            ErrorKind::EnumMemberWithoutInitializerWithIsolatedModules { .. } => 18056,
+
             ErrorKind::OnlyOneEnumCanOmitInit { .. } => 2432,
 
             ErrorKind::CannotUseTypeAsIndexIndex { .. } => 2538,
@@ -2055,7 +2435,45 @@ impl ErrorKind {
 
             ErrorKind::DuplicatePrivateStaticInstance { .. } => 2804,
 
-            _ => 0,
+            ErrorKind::AssignFailedDueToOptionalityDifference { .. }
+            | ErrorKind::PrivatePropertyIsDifferent { .. }
+            | ErrorKind::PrivateMethodIsDifferent { .. } => 2322,
+
+            ErrorKind::UndefinedSymbol { .. } | ErrorKind::ExportFailed { .. } => 2304,
+
+            ErrorKind::ImportFailed { .. } | ErrorKind::NoSuchExport { .. } => 2305,
+
+            ErrorKind::ExportAllFailed { .. } | ErrorKind::ResolvedFailed { .. } | ErrorKind::ModuleLoadFailed { .. } => 2307,
+
+            ErrorKind::NoSuchConstructor { .. } => 2339,
+
+            ErrorKind::CannotAssignToThis { .. } | ErrorKind::InvalidOperatorForLhs { .. } => 2364,
+
+            ErrorKind::RedeclaredVarWithDifferentType { .. } => 2403,
+
+            ErrorKind::InvalidEnumInit { .. } => 2474,
+
+            ErrorKind::TooManyTupleElements { .. } => 2493,
+
+            ErrorKind::MayBeUndefined { .. } => 2532,
+
+            ErrorKind::ParameterCountMismatch { .. } | ErrorKind::WrongParams { .. } => 2554,
+
+            ErrorKind::WrongTypeParams { .. } => 2558,
+
+            ErrorKind::ClassPropertyInitRequired { .. } => 2564,
+
+            // Errors which are internal to stc, or wrap other errors.
+            ErrorKind::StackOverflow { .. }
+            | ErrorKind::DestructuringAssignInAmbientContext { .. }
+            | ErrorKind::ConstructorRequired { .. }
+            | ErrorKind::NotTuple { .. }
+            | ErrorKind::Unimplemented { .. }
+            | ErrorKind::Cancelled { .. }
+            | ErrorKind::TooManyErrors { .. }
+            | ErrorKind::Errors { .. }
+            | ErrorKind::UnionError { .. }
+            | ErrorKind::IntersectionError { .. } => 0,
         }
     }
 
@@ -2066,13 +2484,19 @@ impl ErrorKind {
                 | ErrorKind::NoSuchPropertyInClass { .. }
                 | ErrorKind::NoSuchPropertyInModule { .. }
                 | ErrorKind::NoSuchPropertyInThis { .. }
+                | ErrorKind::NoSuchPropertyInLib { .. }
+                | ErrorKind::NoSuchPropertyWithSuggestion { .. }
         )
     }
 
     pub fn is_var_not_found(&self) -> bool {
         matches!(
             self,
-            Self::NoSuchVar { .. } | Self::NoSuchVarButThisHasSuchProperty { .. } | Self::NoSuchVarForShorthand { .. }
+            Self::NoSuchVar { .. }
+                | Self::NoSuchVarButThisHasSuchProperty { .. }
+                | Self::NoSuchVarForShorthand { .. }
+                | Self::NoSuchVarInLib { .. }
+                | Self::NoSuchVarWithSuggestion { .. }
         )
     }
 
@@ -2084,6 +2508,16 @@ impl ErrorKind {
         self.code() == 2322
     }
 
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled { .. })
+    }
+
+    /// Suggestions are not problems of the code, like usages of deprecated
+    /// declarations. `tsc` does not report them, but editors show them.
+    pub fn is_suggestion(&self) -> bool {
+        matches!(self, Self::Deprecated { .. })
+    }
+
     pub fn is_type_not_found(&self) -> bool {
         matches!(self, Self::NoSuchType { .. } | Self::NoSuchTypeButVarExists { .. })
     }
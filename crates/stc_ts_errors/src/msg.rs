@@ -0,0 +1,73 @@
+use std::{
+    fmt,
+    fmt::{Debug, Display, Formatter},
+    sync::Arc,
+};
+
+use stc_ts_types::Type;
+use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
+
+/// A message of an error, rendered only when the error is reported.
+///
+/// Most errors created while checking are discarded (e.g. while selecting an
+/// overload or by deduplication), so types are stored as frozen handles and
+/// printed only when the message is used.
+#[derive(Clone)]
+pub enum DeferredMsg {
+    Static(&'static str),
+    Lazy(Arc<dyn Fn() -> String + Send + Sync>),
+}
+
+impl DeferredMsg {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Send + Sync + Fn() -> String,
+    {
+        Self::Lazy(Arc::new(f))
+    }
+
+    /// `text` followed by `{:#?}` of `ty`.
+    pub fn with_type(text: &'static str, ty: &Type) -> Self {
+        let ty = ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed();
+
+        Self::new(move || format!("{}{:#?}", text, ty))
+    }
+
+    pub fn render(&self) -> String {
+        match self {
+            DeferredMsg::Static(s) => s.to_string(),
+            DeferredMsg::Lazy(f) => f(),
+        }
+    }
+}
+
+impl From<&'static str> for DeferredMsg {
+    fn from(s: &'static str) -> Self {
+        Self::Static(s)
+    }
+}
+
+impl Debug for DeferredMsg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.render(), f)
+    }
+}
+
+impl Display for DeferredMsg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DeferredMsg::Static(s) => f.write_str(s),
+            DeferredMsg::Lazy(..) => f.write_str(&self.render()),
+        }
+    }
+}
+
+/// Compares the rendered messages.
+impl PartialEq for DeferredMsg {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DeferredMsg::Static(l), DeferredMsg::Static(r)) => l == r,
+            _ => self.render() == other.render(),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Relation rules for arrays and tuples.
+//!
+//! This mirrors the array/tuple part of `structuredTypeRelatedTo` of `tsc`.
+//!
+//!  - Mutable arrays are covariant in their element type.
+//!  - `readonly T[]` is a proper supertype of `T[]`, so a readonly array or
+//!    tuple cannot be assigned to a mutable one.
+//!  - A tuple is assignable to an array if all of its elements are assignable
+//!    to the element type of the array.
+//!  - An array is assignable to a tuple only if the tuple is `[...T[]]`.
+//!  - Tuples are related element-wise, with arity checks which take optional
+//!    and rest elements into account.
+
+use stc_ts_ast_rnode::{RIdent, RTsEntityName};
+use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_types::{Array, Operator, Ref, Tuple, TupleElement, Type};
+use swc_ecma_ast::TsTypeOperatorOp;
+
+use crate::{
+    analyzer::{
+        assign::{AssignData, AssignOpts},
+        Analyzer,
+    },
+    VResult,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct ArrayLike<'a> {
+    readonly: bool,
+    kind: ArrayLikeKind<'a>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ArrayLikeKind<'a> {
+    Array(&'a Type),
+    Tuple(&'a Tuple),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TupleElemKind<'a> {
+    Required(&'a Type),
+    Optional(&'a Type),
+    /// Element type of a rest element.
+    Rest(&'a Type),
+}
+
+impl<'a> TupleElemKind<'a> {
+    fn ty(self) -> &'a Type {
+        match self {
+            TupleElemKind::Required(ty) | TupleElemKind::Optional(ty) | TupleElemKind::Rest(ty) => ty,
+        }
+    }
+}
+
+/// Elements of a tuple, split into fixed elements and the trailing rest
+/// element.
+#[derive(Debug)]
+struct TupleShape<'a> {
+    fixed: Vec<TupleElemKind<'a>>,
+    rest: Option<&'a Type>,
+}
+
+impl TupleShape<'_> {
+    /// The number of elements which should always exist.
+    fn min_len(&self) -> usize {
+        self.fixed.iter().filter(|el| matches!(el, TupleElemKind::Required(..))).count()
+    }
+
+    /// Returns the type of `index`th element, if it can exist.
+    fn elem_at(&self, index: usize) -> Option<&Type> {
+        match self.fixed.get(index) {
+            Some(el) => Some(el.ty()),
+            None => self.rest,
+        }
+    }
+}
+
+fn array_like(ty: &Type) -> Option<ArrayLike> {
+    match ty.normalize() {
+        Type::Array(Array { elem_type, .. }) => Some(ArrayLike {
+            readonly: false,
+            kind: ArrayLikeKind::Array(elem_type),
+        }),
+        Type::Tuple(tuple) => Some(ArrayLike {
+            readonly: false,
+            kind: ArrayLikeKind::Tuple(tuple),
+        }),
+        Type::Operator(Operator {
+            op: TsTypeOperatorOp::ReadOnly,
+            ty,
+            ..
+        }) => {
+            let inner = array_like(ty)?;
+            Some(ArrayLike { readonly: true, ..inner })
+        }
+        Type::Ref(Ref {
+            type_name: RTsEntityName::Ident(RIdent { sym, .. }),
+            type_args: Some(type_args),
+            ..
+        }) if *sym == *"ReadonlyArray" && type_args.params.len() == 1 => Some(ArrayLike {
+            readonly: true,
+            kind: ArrayLikeKind::Array(&type_args.params[0]),
+        }),
+        _ => None,
+    }
+}
+
+/// Returns [None] if the tuple contains an element we can't handle here, like
+/// a variadic element (`...T`) or a rest element which is not the last one.
+fn tuple_shape(tuple: &Tuple) -> Option<TupleShape> {
+    let mut fixed = Vec::with_capacity(tuple.elems.len());
+    let mut rest = None;
+
+    for (idx, TupleElement { ty, .. }) in tuple.elems.iter().enumerate() {
+        match ty.normalize() {
+            Type::Rest(r) => {
+                if idx != tuple.elems.len() - 1 {
+                    return None;
+                }
+
+                match r.ty.normalize() {
+                    Type::Array(Array { elem_type, .. }) => {
+                        rest = Some(&**elem_type);
+                    }
+                    _ => return None,
+                }
+            }
+            Type::Optional(o) => fixed.push(TupleElemKind::Optional(&o.ty)),
+            _ => fixed.push(TupleElemKind::Required(ty)),
+        }
+    }
+
+    Some(TupleShape { fixed, rest })
+}
+
+impl Analyzer<'_, '_> {
+    /// Assigns an array or a tuple to an array or a tuple.
+    ///
+    /// Returns [None] if one of `to` and `rhs` is not an array-like type, or if
+    /// the relation should be checked by the generic code path.
+    pub(super) fn assign_to_array_like(&mut self, data: &mut AssignData, to: &Type, rhs: &Type, opts: AssignOpts) -> Option<VResult<()>> {
+        let l = array_like(to)?;
+        let r = array_like(rhs)?;
+
+        let l_tuple = match l.kind {
+            ArrayLikeKind::Tuple(t) => Some(tuple_shape(t)?),
+            ArrayLikeKind::Array(..) => None,
+        };
+        let r_tuple = match r.kind {
+            ArrayLikeKind::Tuple(t) => Some(tuple_shape(t)?),
+            ArrayLikeKind::Array(..) => None,
+        };
+
+        Some(self.assign_array_like_inner(data, l, l_tuple, r, r_tuple, opts))
+    }
+
+    fn assign_array_like_inner(
+        &mut self,
+        data: &mut AssignData,
+        l: ArrayLike,
+        l_tuple: Option<TupleShape>,
+        r: ArrayLike,
+        r_tuple: Option<TupleShape>,
+        opts: AssignOpts,
+    ) -> VResult<()> {
+        let span = opts.span;
+
+        // `readonly T[]` is a supertype of `T[]`, but not vice versa.
+        if r.readonly && !l.readonly && !opts.for_castablity {
+            return Err(ErrorKind::ReadonlyArrayToMutable { span }.into());
+        }
+
+        let elem_opts = AssignOpts {
+            allow_unknown_rhs: Some(true),
+            ..opts
+        };
+
+        match (l.kind, l_tuple, r.kind, r_tuple) {
+            (ArrayLikeKind::Array(l_elem), _, ArrayLikeKind::Array(r_elem), _) => self
+                .assign_inner(data, l_elem, r_elem, opts)
+                .context("tried to assign an array to an array"),
+
+            (ArrayLikeKind::Array(l_elem), _, ArrayLikeKind::Tuple(..), Some(r_tuple)) => {
+                let mut errors = vec![];
+                for el in r_tuple.fixed.iter().map(|el| el.ty()).chain(r_tuple.rest) {
+                    errors.extend(self.assign_inner(data, l_elem, el, elem_opts).err());
+                }
+
+                if !errors.is_empty() {
+                    return Err(ErrorKind::TupleAssignError { span, errors }.into());
+                }
+
+                Ok(())
+            }
+
+            (ArrayLikeKind::Tuple(..), Some(l_tuple), ArrayLikeKind::Array(r_elem), _) => {
+                // An empty tuple accepts any array. This was allowed by the structural
+                // path and empty array literals rely on it.
+                if l_tuple.fixed.is_empty() && l_tuple.rest.is_none() {
+                    return Ok(());
+                }
+
+                // Only `[...T[]]` can accept an array, because the length of an array is
+                // unknown.
+                match (&*l_tuple.fixed, l_tuple.rest) {
+                    ([], Some(l_rest)) => self
+                        .assign_inner(data, l_rest, r_elem, elem_opts)
+                        .context("tried to assign an array to a tuple with a rest element"),
+                    _ => Err(ErrorKind::AssignFailedBecauseTupleLengthDiffers { span }.into()),
+                }
+            }
+
+            (ArrayLikeKind::Tuple(..), Some(l_tuple), ArrayLikeKind::Tuple(..), Some(r_tuple)) => {
+                if !opts.ignore_tuple_length_difference {
+                    // The source may be shorter than the required part of the target.
+                    if r_tuple.min_len() < l_tuple.min_len() {
+                        return Err(ErrorKind::AssignFailedBecauseTupleLengthDiffers { span }.into());
+                    }
+
+                    // The source may be longer than the target.
+                    if l_tuple.rest.is_none() && (r_tuple.rest.is_some() || r_tuple.fixed.len() > l_tuple.fixed.len()) {
+                        return Err(ErrorKind::AssignFailedBecauseTupleLengthDiffers { span }.into());
+                    }
+                }
+
+                let mut errors = vec![];
+
+                for (index, r_el) in r_tuple.fixed.iter().enumerate() {
+                    let l_el = match l_tuple.elem_at(index) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+
+                    errors.extend(
+                        self.assign_inner(data, l_el, r_el.ty(), elem_opts)
+                            .with_context(|| format!("tried to assign {}th tuple element", index))
+                            .err(),
+                    );
+                }
+
+                if let Some(r_rest) = r_tuple.rest {
+                    // Elements of the rest element of the source can be at any position after
+                    // the fixed elements.
+                    for index in r_tuple.fixed.len()..l_tuple.fixed.len() {
+                        errors.extend(
+                            self.assign_inner(data, l_tuple.fixed[index].ty(), r_rest, elem_opts)
+                                .with_context(|| format!("tried to assign a rest element to {}th tuple element", index))
+                                .err(),
+                        );
+                    }
+
+                    if let Some(l_rest) = l_tuple.rest {
+                        errors.extend(
+                            self.assign_inner(data, l_rest, r_rest, elem_opts)
+                                .context("tried to assign a rest element to a rest element")
+                                .err(),
+                        );
+                    }
+                }
+
+                if !errors.is_empty() {
+                    return Err(ErrorKind::TupleAssignError { span, errors }.into());
+                }
+
+                Ok(())
+            }
+
+            _ => unreachable!("tuple shape should be computed for tuples"),
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use std::borrow::Cow;
 
-use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_errors::{DebugExt, DeferredMsg, ErrorKind};
 use stc_ts_types::{Class, ClassDef, ClassMember, Type, TypeLitMetadata};
-use stc_utils::cache::Freeze;
+use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
 use swc_common::EqIgnoreSpan;
 use swc_ecma_ast::Accessibility;
 
@@ -48,7 +48,7 @@ impl Analyzer<'_, '_> {
                     } else {
                         return Err(ErrorKind::Unimplemented {
                             span: opts.span,
-                            msg: "Failed to collect class members".to_string(),
+                            msg: "Failed to collect class members".into(),
                         }
                         .into());
                     }
@@ -104,7 +104,7 @@ impl Analyzer<'_, '_> {
 
         Err(ErrorKind::Unimplemented {
             span: opts.span,
-            msg: format!("Assignment of non-class object to class definition\n{:#?}", r),
+            msg: DeferredMsg::with_type("Assignment of non-class object to class definition\n", r),
         }
         .into())
     }
@@ -134,7 +134,7 @@ impl Analyzer<'_, '_> {
                     } else {
                         return Err(ErrorKind::Unimplemented {
                             span: opts.span,
-                            msg: "Failed to collect class members".to_string(),
+                            msg: "Failed to collect class members".into(),
                         }
                         .into());
                     }
@@ -228,7 +228,7 @@ impl Analyzer<'_, '_> {
 
         Err(ErrorKind::Unimplemented {
             span: opts.span,
-            msg: format!("Assignment of non-class object to class\n{:#?}", r),
+            msg: DeferredMsg::with_type("Assignment of non-class object to class\n", r),
         }
         .into())
     }
@@ -276,7 +276,10 @@ impl Analyzer<'_, '_> {
                                     rm.type_params.as_ref(),
                                     &rm.params,
                                     Some(&rm.ret_ty),
-                                    opts,
+                                    AssignOpts {
+                                        is_params_of_method_definition: true,
+                                        ..opts
+                                    },
                                 )
                                 .context("tried to assign a class method to another one")?;
 
@@ -347,7 +350,10 @@ impl Analyzer<'_, '_> {
 
         Err(ErrorKind::Unimplemented {
             span: opts.span,
-            msg: format!("fine-grained class assignment to lhs member: {:#?}", l),
+            msg: DeferredMsg::new({
+                let l = ALLOW_DEEP_CLONE.set(&(), || l.clone());
+                move || format!("fine-grained class assignment to lhs member: {:#?}", l)
+            }),
         }
         .into())
     }
@@ -348,6 +348,9 @@ impl Analyzer<'_, '_> {
         };
 
         // TypeScript functions are bivariant if strict_function_types is false.
+        //
+        // Parameters of methods and constructors are always checked bivariantly, and
+        // the variance is determined by the declaration of the target.
         if !self.env.rule().strict_function_types || opts.is_params_of_method_definition {
             if self.assign_params(data, r_params, l_params, opts).is_ok() {
                 return Ok(());
@@ -695,6 +698,12 @@ impl Analyzer<'_, '_> {
         let span = opts.span;
         debug_assert!(!opts.span.is_dummy(), "Cannot assign function parameters with dummy span");
 
+        // Variance of function types in parameters depends on their own declarations.
+        let opts = AssignOpts {
+            is_params_of_method_definition: false,
+            ..opts
+        };
+
         // TODO(kdy1): Change this to extends call.
 
         let res = if self.rule().strict_function_types {
@@ -882,11 +891,13 @@ impl Analyzer<'_, '_> {
                     return Ok(());
                 }
 
-                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context(format!(
-                    "!l_has_rest && l.params.required.len < r.params.required.len\nLeft: {:?}\nRight: {:?}\n",
-                    required_non_void_li.collect_vec(),
-                    required_non_void_ri.collect_vec()
-                )));
+                return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.with_context(|| {
+                    format!(
+                        "!l_has_rest && l.params.required.len < r.params.required.len\nLeft: {:?}\nRight: {:?}\n",
+                        required_non_void_li.collect_vec(),
+                        required_non_void_ri.collect_vec()
+                    )
+                }));
             }
         }
 
@@ -931,11 +942,17 @@ impl Analyzer<'_, '_> {
                     // If r is a tuple, we should assign each element to l.
                     let r_ty = self.normalize(Some(span), Cow::Borrowed(&r.ty), Default::default())?;
                     if let Some(r_tuple) = r_ty.as_tuple() {
-                        let mut ri = r_tuple.elems.iter();
+                        let ri = r_tuple.elems.iter();
+
+                        // Optional elements are passed as the element type.
+                        let mut ri = ri.map(|el| match el.ty.normalize() {
+                            Type::Optional(o) => &*o.ty,
+                            ty => ty,
+                        });
 
                         let re = ri.next();
                         if let Some(ri) = re {
-                            self.assign_param_type(data, &l.ty, &ri.ty, opts).with_context(|| {
+                            self.assign_param_type(data, &l.ty, ri, opts).with_context(|| {
                                 format!(
                                     "tried to assign a rest parameter to parameters; r_ty = {}",
                                     force_dump_type_as_string(&r.ty)
@@ -945,7 +962,7 @@ impl Analyzer<'_, '_> {
                         for l in li {
                             let re = ri.next();
                             if let Some(ri) = re {
-                                self.assign_param_type(data, &l.ty, &ri.ty, opts).with_context(|| {
+                                self.assign_param_type(data, &l.ty, ri, opts).with_context(|| {
                                     format!(
                                         "tried to assign a rest parameter to parameters; r_ty = {} (iter)",
                                         force_dump_type_as_string(&r.ty)
@@ -3,7 +3,7 @@ use std::{borrow::Cow, collections::HashMap};
 use stc_ts_ast_rnode::{RBool, RExpr, RIdent, RLit, RNumber, RStr, RTsEntityName, RTsEnumMemberId, RTsLit};
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt, ErrorKind,
+    DebugExt, DeferredMsg, ErrorKind,
 };
 use stc_ts_file_analyzer_macros::context;
 use stc_ts_types::{
@@ -11,7 +11,10 @@ use stc_ts_types::{
     LitType, Mapped, Operator, PropertySignature, QueryExpr, QueryType, Ref, RestType, StringMapping, ThisType, Tuple, Type, TypeElement,
     TypeLit, TypeParam,
 };
-use stc_utils::{cache::Freeze, stack};
+use stc_utils::{
+    cache::{Freeze, ALLOW_DEEP_CLONE},
+    stack,
+};
 use swc_atoms::js_word;
 use swc_common::{EqIgnoreSpan, Span, Spanned, TypeEq, DUMMY_SP};
 use swc_ecma_ast::{TruePlusMinus::*, *};
@@ -29,6 +32,7 @@ use crate::{
     VResult,
 };
 
+mod array;
 mod builtin;
 mod cast;
 mod class;
@@ -585,7 +589,7 @@ impl Analyzer<'_, '_> {
 
         data.dejavu.push((left.clone(), right.clone()));
 
-        let res = self.assign_without_wrapping(data, left, right, opts).with_context(|| {
+        let res = stack::maybe_grow(|| self.assign_without_wrapping(data, left, right, opts)).with_context(|| {
             //
             let l = force_dump_type_as_string(left);
             let r = force_dump_type_as_string(right);
@@ -649,11 +653,13 @@ impl Analyzer<'_, '_> {
                     right_ident: opts.right_ident_span,
                     cause: vec![],
                 }
-                .context(format!(
-                    "LHS (final): {}\nRHS (final): {}",
-                    force_dump_type_as_string(to),
-                    force_dump_type_as_string(rhs)
-                )));
+                .with_context(|| {
+                    format!(
+                        "LHS (final): {}\nRHS (final): {}",
+                        force_dump_type_as_string(to),
+                        force_dump_type_as_string(rhs)
+                    )
+                }));
             }};
         }
 
@@ -765,6 +771,10 @@ impl Analyzer<'_, '_> {
             return res;
         }
 
+        if let Some(res) = self.assign_to_array_like(data, to, rhs, opts) {
+            return res;
+        }
+
         if rhs.is_kwd(TsKeywordTypeKind::TsNeverKeyword) {
             return Ok(());
         }
@@ -1115,33 +1125,12 @@ impl Analyzer<'_, '_> {
                         fail!()
                     }
 
+                    // Unlike numbers, strings are not assignable to string enums.
                     Type::Lit(LitType { lit: RTsLit::Str(..), .. })
                     | Type::Keyword(KeywordType {
                         kind: TsKeywordTypeKind::TsStringKeyword,
                         ..
-                    }) => {
-                        if opts.do_not_convert_enum_to_string_nor_number {
-                            fail!()
-                        }
-
-                        // TODO(kdy1): Check for value and disallow `string` (keyword type)
-
-                        // validEnumAssignments.ts insists that this is valid.
-                        // but if enum isn't has num, not assignable
-                        let items = self.find_type(enum_name).context("failed to find an enum for assignment")?;
-
-                        if let Some(items) = items {
-                            for t in items {
-                                if let Type::Enum(en) = t.normalize() {
-                                    if en.has_str {
-                                        return Ok(());
-                                    }
-                                }
-                            }
-                        }
-
-                        fail!()
-                    }
+                    }) => fail!(),
 
                     Type::EnumVariant(rhs) => {
                         if rhs.enum_name == *enum_name {
@@ -1189,8 +1178,13 @@ impl Analyzer<'_, '_> {
 
                         fail!()
                     }
+                    // Any number is assignable to a numeric enum member, for backward
+                    // compatibility with bit flags.
                     Type::Lit(LitType {
-                        lit: RTsLit::Number(r_num),
+                        lit: RTsLit::Number(..), ..
+                    })
+                    | Type::Keyword(KeywordType {
+                        kind: TsKeywordTypeKind::TsNumberKeyword,
                         ..
                     }) => {
                         if opts.do_not_convert_enum_to_string_nor_number {
@@ -1206,10 +1200,8 @@ impl Analyzer<'_, '_> {
                                         RTsEnumMemberId::Ident(RIdent { ref sym, .. })
                                         | RTsEnumMemberId::Str(RStr { value: ref sym, .. }) => sym == name,
                                     }) {
-                                        if let RExpr::Lit(RLit::Num(l_num)) = &*v.val {
-                                            if l_num.value == r_num.value {
-                                                return Ok(());
-                                            }
+                                        if !matches!(&*v.val, RExpr::Lit(RLit::Str(..))) {
+                                            return Ok(());
                                         }
                                     }
                                 }
@@ -1371,6 +1363,15 @@ impl Analyzer<'_, '_> {
                         fail!()
                     }
 
+                    // `E.A` is assignable to the literal type of its value.
+                    if let Type::Lit(r) = self.expand_enum_variant(rhs.clone())?.normalize() {
+                        match (&lhs.lit, &r.lit) {
+                            (RTsLit::Number(l), RTsLit::Number(r)) if l.value == r.value => return Ok(()),
+                            (RTsLit::Str(l), RTsLit::Str(r)) if l.value == r.value => return Ok(()),
+                            _ => {}
+                        }
+                    }
+
                     // Single-variant enums seem to be treated like a number.
                     //
                     // See typeArgumentInferenceWithObjectLiteral.ts
@@ -1796,6 +1797,15 @@ impl Analyzer<'_, '_> {
 
             // let a: string | number = 'string';
             Type::Union(lu) => {
+                // A union enum is assignable if all of its members are.
+                if let Type::EnumVariant(e @ EnumVariant { name: None, .. }) = rhs {
+                    if let Some(variants) = self.expand_union_enum(e)? {
+                        return self
+                            .assign_with_opts(data, to, &variants, opts)
+                            .context("tried to assign an enum as a union of its members");
+                    }
+                }
+
                 // true | false = boolean
                 if rhs.is_kwd(TsKeywordTypeKind::TsBooleanKeyword) {
                     if lu.types.iter().any(|ty| {
@@ -2694,6 +2704,9 @@ impl Analyzer<'_, '_> {
             force_dump_type_as_string(to),
             force_dump_type_as_string(rhs)
         );
+        if let Some(err) = self.unimplemented_error(opts.span, || DeferredMsg::with_type("assignment to ", to)) {
+            return Err(err);
+        }
         Ok(())
     }
 
@@ -2707,6 +2720,9 @@ impl Analyzer<'_, '_> {
         }
 
         error!("unimplemented: assign to intrinsic type\n{:?}\n{}", to, dump_type_as_string(r));
+        if let Some(err) = self.unimplemented_error(opts.span, || DeferredMsg::with_type("assignment to an intrinsic type from ", r)) {
+            return Err(err);
+        }
         Ok(())
     }
 
@@ -2759,7 +2775,7 @@ impl Analyzer<'_, '_> {
 
         Err(ErrorKind::Unimplemented {
             span,
-            msg: "Extract keys".to_string(),
+            msg: "Extract keys".into(),
         })?
     }
 
@@ -2845,7 +2861,10 @@ impl Analyzer<'_, '_> {
 
                             _ => Err(ErrorKind::Unimplemented {
                                 span: opts.span,
-                                msg: format!("Assignment to mapped type: type element - {:?}", member),
+                                msg: DeferredMsg::new({
+                                    let member = ALLOW_DEEP_CLONE.set(&(), || member.clone()).freezed();
+                                    move || format!("Assignment to mapped type: type element - {:?}", member)
+                                }),
                             })?,
                         }
                     }
@@ -2904,7 +2923,17 @@ impl Analyzer<'_, '_> {
                             if let Some(r) = &new_r_ty {
                                 Err(ErrorKind::Unimplemented {
                                     span: opts.span,
-                                    msg: format!("Assignment to mapped type\n{}\n{}", dump_type_as_string(l), dump_type_as_string(r),),
+                                    msg: DeferredMsg::new({
+                                        let l = ALLOW_DEEP_CLONE.set(&(), || l.clone()).freezed();
+                                        let r = ALLOW_DEEP_CLONE.set(&(), || r.clone()).freezed();
+                                        move || {
+                                            format!(
+                                                "Assignment to mapped type\n{}\n{}",
+                                                dump_type_as_string(&l),
+                                                dump_type_as_string(&r)
+                                            )
+                                        }
+                                    }),
                                 })?
                             }
                         }
@@ -2915,7 +2944,7 @@ impl Analyzer<'_, '_> {
 
             Err(ErrorKind::Unimplemented {
                 span: opts.span,
-                msg: "Assignment to mapped type".to_string(),
+                msg: "Assignment to mapped type".into(),
             })?
         };
 
@@ -1,5 +1,5 @@
 use stc_ts_errors::{DebugExt, ErrorKind};
-use stc_ts_types::{QueryExpr, QueryType, Type};
+use stc_ts_types::{IdCtx, QueryExpr, QueryType, Type};
 
 use crate::{
     analyzer::{
@@ -29,33 +29,42 @@ impl Analyzer<'_, '_> {
 
                 self.assign_with_opts(data, &to, rhs, opts)
             }
-            QueryExpr::Import(_) => Err(ErrorKind::Unimplemented {
-                span: opts.span,
-                msg: "assignment of query type with import".to_string(),
+            QueryExpr::Import(import) => {
+                let to = self
+                    .resolve_import_type(opts.span, import, IdCtx::Var)
+                    .context("tried to resolve typeof import() for assignment")?;
+
+                self.assign_with_opts(data, &to, rhs, opts)
             }
-            .into()),
         }
     }
 
     pub(super) fn assign_query_type_to_type(&mut self, data: &mut AssignData, to: &Type, rhs: &QueryType, opts: AssignOpts) -> VResult<()> {
         let to = to.normalize();
 
-        if let QueryExpr::TsEntityName(e) = &*rhs.expr {
-            let rhs = self
-                .resolve_typeof(opts.span, e)
-                .context("tried to resolve typeof for assignment")?;
+        match &*rhs.expr {
+            QueryExpr::TsEntityName(e) => {
+                let rhs = self
+                    .resolve_typeof(opts.span, e)
+                    .context("tried to resolve typeof for assignment")?;
 
-            if rhs.is_global_this() {
-                return Err(ErrorKind::SimpleAssignFailed {
-                    span: opts.span,
-                    cause: None,
+                if rhs.is_global_this() {
+                    return Err(ErrorKind::SimpleAssignFailed {
+                        span: opts.span,
+                        cause: None,
+                    }
+                    .context("global this"));
                 }
-                .context("global this"));
+
+                self.assign_with_opts(data, to, &rhs, opts)
             }
+            QueryExpr::Import(import) => {
+                let rhs = self
+                    .resolve_import_type(opts.span, import, IdCtx::Var)
+                    .context("tried to resolve typeof import() for assignment")?;
 
-            self.assign_with_opts(data, to, &rhs, opts)
-        } else {
-            unimplemented!("assignment of query type with import")
+                self.assign_with_opts(data, to, &rhs, opts)
+            }
         }
     }
 }
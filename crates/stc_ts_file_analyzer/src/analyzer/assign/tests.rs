@@ -58,3 +58,39 @@ fn array_filter_2() {
         Default::default(),
     );
 }
+
+#[test]
+fn readonly_array_1() {
+    test_assign("string[]", "readonly string[]", false, Default::default());
+    test_assign("readonly string[]", "string[]", true, Default::default());
+    test_assign("readonly (string | number)[]", "readonly string[]", true, Default::default());
+}
+
+#[test]
+fn readonly_tuple_1() {
+    test_assign("[string, number]", "readonly [string, number]", false, Default::default());
+    test_assign("readonly [string, number]", "[string, number]", true, Default::default());
+    test_assign("string[]", "readonly [string]", false, Default::default());
+}
+
+#[test]
+fn tuple_to_array_1() {
+    test_assign("string[]", "[string, string]", true, Default::default());
+    test_assign("(string | number)[]", "[string, number]", true, Default::default());
+    test_assign("string[]", "[string, number]", false, Default::default());
+}
+
+#[test]
+fn array_to_tuple_1() {
+    test_assign("[string]", "string[]", false, Default::default());
+    test_assign("[...string[]]", "string[]", true, Default::default());
+}
+
+#[test]
+fn tuple_arity_1() {
+    test_assign("[string, number?]", "[string]", true, Default::default());
+    test_assign("[string]", "[string, number?]", false, Default::default());
+    test_assign("[string]", "[string, string]", false, Default::default());
+    test_assign("[string, ...number[]]", "[string, number, number]", true, Default::default());
+    test_assign("[string, ...number[]]", "[string, number, string]", false, Default::default());
+}
@@ -5,7 +5,7 @@ use rnode::NodeId;
 use stc_ts_ast_rnode::{RIdent, RTsEntityName, RTsLit};
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt, ErrorKind, Errors,
+    DebugExt, DeferredMsg, ErrorKind, Errors,
 };
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{
@@ -739,7 +739,7 @@ impl Analyzer<'_, '_> {
                 _ => {
                     return Err(ErrorKind::Unimplemented {
                         span,
-                        msg: format!("assign_to_type_elements - {:#?}", rhs),
+                        msg: DeferredMsg::with_type("assign_to_type_elements - ", rhs),
                     }
                     .into())
                 }
@@ -1128,9 +1128,24 @@ impl Analyzer<'_, '_> {
                                                 }
                                             }
 
+                                            let l_ty = lp
+                                                .type_ann
+                                                .as_deref()
+                                                .cloned()
+                                                .unwrap_or_else(|| Type::any(span, Default::default()));
+                                            // An optional property accepts `undefined` unless
+                                            // `exactOptionalPropertyTypes` is enabled, in which case a missing
+                                            // property and a property with `undefined` are different.
+                                            let l_ty = if lp.optional && !opts.for_castablity && !self.rule().exact_optional_property_types
+                                            {
+                                                Type::new_union(span, vec![l_ty, Type::undefined(span, Default::default())]).freezed()
+                                            } else {
+                                                l_ty
+                                            };
+
                                             self.assign_inner(
                                                 data,
-                                                lp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
+                                                &l_ty,
                                                 rp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
                                                 opts,
                                             )
@@ -1236,7 +1251,10 @@ impl Analyzer<'_, '_> {
                                                     rf.type_params.as_ref(),
                                                     &rf.params,
                                                     Some(&rf.ret_ty),
-                                                    opts,
+                                                    AssignOpts {
+                                                        is_params_of_method_definition: true,
+                                                        ..opts
+                                                    },
                                                 )
                                                 .context("tried to assign a property with callable type to a method property")?;
                                             }
@@ -1439,6 +1457,7 @@ impl Analyzer<'_, '_> {
                                     rc.ret_ty.as_deref(),
                                     AssignOpts {
                                         infer_type_params_of_left: true,
+                                        is_params_of_method_definition: true,
                                         ..opts
                                     },
                                 );
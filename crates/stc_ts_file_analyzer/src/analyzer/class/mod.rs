@@ -7,7 +7,7 @@ use stc_ts_ast_rnode::{
     RFunction, RIdent, RMemberExpr, RParam, RParamOrTsParamProp, RPat, RPrivateMethod, RPrivateProp, RPropName, RStaticBlock, RStmt,
     RTsEntityName, RTsFnParam, RTsParamProp, RTsParamPropParam, RTsTypeAliasDecl, RTsTypeAnn, RVarDecl, RVarDeclarator,
 };
-use stc_ts_env::ModuleConfig;
+use stc_ts_env::ModuleKind;
 use stc_ts_errors::{DebugExt, ErrorKind, Errors};
 use stc_ts_simple_ast_validations::constructor::ConstructorSuperCallFinder;
 use stc_ts_type_ops::generalization::{prevent_generalize, LitGeneralizer};
@@ -72,7 +72,7 @@ impl Analyzer<'_, '_> {
             // Disabled because of false positives when the constructor initializes the
             // field.
             #[allow(clippy::overly_complex_bool_expr)]
-            if false && self.rule().strict_null_checks {
+            if false && self.rule().strict_null_checks && self.rule().strict_property_initialization {
                 if value.is_none() {
                     if let Some(ty) = &ty {
                         if self
@@ -210,7 +210,9 @@ impl Analyzer<'_, '_> {
             is_optional: p.is_optional,
             readonly: p.readonly,
             definite: p.definite,
+            deprecated: self.is_deprecated(p.span),
             accessor: Default::default(),
+            write_type: Default::default(),
         })
     }
 }
@@ -245,7 +247,9 @@ impl Analyzer<'_, '_> {
             is_optional: p.is_optional,
             readonly: p.readonly,
             definite: p.definite,
+            deprecated: false,
             accessor: Default::default(),
+            write_type: Default::default(),
         })
     }
 }
@@ -511,6 +515,12 @@ impl Analyzer<'_, '_> {
 
                 let declared_ret_ty = try_opt!(c.function.return_type.validate_with(child));
 
+                if c.function.is_async && !c.function.is_generator {
+                    if let Some(ty) = &declared_ret_ty {
+                        child.check_return_type_of_async_fn(ty);
+                    }
+                }
+
                 let span = c.function.span;
                 let is_async = c.function.is_async;
                 let is_generator = c.function.is_generator;
@@ -547,6 +557,7 @@ impl Analyzer<'_, '_> {
                 is_static: c.is_static,
                 is_abstract: c.is_abstract,
                 is_optional: c.is_optional,
+                deprecated: false,
             })),
             MethodKind::Getter => Ok(ClassMember::Property(ClassProperty {
                 span: c.span,
@@ -558,10 +569,12 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                deprecated: false,
                 accessor: Accessor {
                     getter: true,
                     setter: false,
                 },
+                write_type: Default::default(),
             })),
             MethodKind::Setter => Ok(ClassMember::Property(ClassProperty {
                 span: c.span,
@@ -573,10 +586,12 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                deprecated: false,
                 accessor: Accessor {
                     getter: false,
                     setter: true,
                 },
+                write_type: Default::default(),
             })),
         }
     }
@@ -693,6 +708,12 @@ impl Analyzer<'_, '_> {
                 let declared_ret_ty = declared_ret_ty.map(|ty| ty.freezed());
                 child.scope.declared_return_type = declared_ret_ty.clone();
 
+                if c.function.is_async && !c.function.is_generator {
+                    if let Some(ty) = &declared_ret_ty {
+                        child.check_return_type_of_async_fn(ty);
+                    }
+                }
+
                 let span = c.function.span;
                 let is_async = c.function.is_async;
                 let is_generator = c.function.is_generator;
@@ -750,6 +771,7 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        let deprecated = self.is_deprecated(c_span);
         match c.kind {
             MethodKind::Method => Ok(ClassMember::Method(Method {
                 span: c_span,
@@ -761,6 +783,7 @@ impl Analyzer<'_, '_> {
                 type_params,
                 params,
                 ret_ty,
+                deprecated,
             })),
             MethodKind::Getter => Ok(ClassMember::Property(ClassProperty {
                 span: c_span,
@@ -772,10 +795,12 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                deprecated,
                 accessor: Accessor {
                     getter: true,
                     setter: false,
                 },
+                write_type: Default::default(),
             })),
             MethodKind::Setter => Ok(ClassMember::Property(ClassProperty {
                 span: c_span,
@@ -792,10 +817,12 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                deprecated,
                 accessor: Accessor {
                     getter: false,
                     setter: true,
                 },
+                write_type: Default::default(),
             })),
         }
     }
@@ -1528,10 +1555,10 @@ impl Analyzer<'_, '_> {
                 "any" | "void" | "never" | "string" | "number" | "boolean" | "null" | "undefined" | "symbol" => {
                     self.storage.report(ErrorKind::InvalidClassName { span: c.span }.into());
                 }
-                "Object" if self.env.target() <= EsVersion::Es5 => match self.env.module() {
-                    ModuleConfig::None if self.ctx.in_declare => {}
+                "Object" if self.env.target() <= EsVersion::Es5 => match self.env.module().kind {
+                    ModuleKind::None if self.ctx.in_declare => {}
 
-                    ModuleConfig::None | ModuleConfig::Umd | ModuleConfig::System | ModuleConfig::Amd | ModuleConfig::CommonJs => {
+                    ModuleKind::None | ModuleKind::Umd | ModuleKind::System | ModuleKind::Amd | ModuleKind::CommonJs => {
                         self.storage
                             .report(ErrorKind::ClassNameCannotBeObjectWhenTargetingEs5WithModule { span: c.span }.into());
                     }
@@ -1872,7 +1899,9 @@ impl Analyzer<'_, '_> {
                                         is_optional: false,
                                         readonly: p.readonly,
                                         definite: false,
+                                        deprecated: child.is_deprecated(p.span),
                                         accessor: Default::default(),
+                                        write_type: Default::default(),
                                     }),
                                 ));
                             }
@@ -2072,6 +2101,11 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     /// This method combines setters and getters, and merge it just like a
     /// normal property.
+    ///
+    /// If the parameter type of the setter differs from the return type of the
+    /// getter, the parameter type is stored as `write_type` of the combined
+    /// property. The type of the getter should be assignable to the type of the
+    /// setter.
     fn combine_class_properties(&mut self, body: Vec<(usize, ClassMember)>) -> Vec<(usize, ClassMember)> {
         let mut getters = vec![];
         let mut setters = vec![];
@@ -2090,20 +2124,23 @@ impl Analyzer<'_, '_> {
 
             if let ClassMember::Property(ClassProperty {
                 key,
+                value,
+                is_static,
                 accessor: Accessor { setter: true, .. },
                 ..
             }) = body
             {
-                setters.push(key.clone());
+                setters.push((key.clone(), *is_static, value.clone()));
             }
 
             if let ClassMember::Property(ClassProperty {
                 key,
+                is_static,
                 accessor: Accessor { getter: true, .. },
                 ..
             }) = body
             {
-                getters.push(key.clone());
+                getters.push((key.clone(), *is_static));
             }
         }
 
@@ -2119,25 +2156,57 @@ impl Analyzer<'_, '_> {
                 match member {
                     ClassMember::Property(ClassProperty {
                         ref key,
+                        ref value,
+                        is_static,
                         accessor:
                             Accessor {
                                 getter: true,
                                 ref mut setter,
                             },
+                        ref mut write_type,
                         ..
                     }) => {
-                        if setters.iter().any(|setter_key| setter_key.type_eq(key)) {
+                        if let Some((_, _, setter_ty)) = setters
+                            .iter()
+                            .find(|(setter_key, setter_is_static, _)| *setter_is_static == is_static && setter_key.type_eq(key))
+                        {
                             *setter = true;
+
+                            if let (Some(getter_ty), Some(setter_ty)) = (value, setter_ty) {
+                                if !getter_ty.type_eq(setter_ty) {
+                                    let span = key.span();
+
+                                    if let Err(err) = self.assign_with_opts(
+                                        &mut Default::default(),
+                                        setter_ty,
+                                        getter_ty,
+                                        AssignOpts {
+                                            span,
+                                            ..Default::default()
+                                        },
+                                    ) {
+                                        self.storage.report(
+                                            ErrorKind::GetterTypeNotAssignableToSetterType { span, cause: box err }.into(),
+                                        );
+                                    }
+
+                                    *write_type = Some(setter_ty.clone());
+                                }
+                            }
                         }
 
                         Some((idx, member))
                     }
                     ClassMember::Property(ClassProperty {
                         ref key,
+                        is_static,
                         accessor: Accessor { setter: true, .. },
                         ..
                     }) => {
-                        if getters.iter().any(|getter_key| getter_key.type_eq(key)) {
+                        if getters
+                            .iter()
+                            .any(|(getter_key, getter_is_static)| *getter_is_static == is_static && getter_key.type_eq(key))
+                        {
                             return None;
                         }
 
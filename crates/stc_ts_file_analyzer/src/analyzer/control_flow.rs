@@ -12,8 +12,9 @@ use stc_ts_ast_rnode::{
     RBinExpr, RBindingIdent, RCondExpr, RExpr, RIdent, RIfStmt, RObjectPatProp, RPat, RPatOrExpr, RStmt, RSwitchCase, RSwitchStmt,
 };
 use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_simple_ast_validations::reachability::fallthrough_cases;
 use stc_ts_type_ops::{generalization::prevent_generalize, Fix};
-use stc_ts_types::{name::Name, Array, ArrayMetadata, Id, Key, KeywordType, KeywordTypeMetadata, Union};
+use stc_ts_types::{name::Name, Array, ArrayMetadata, Id, Key, KeywordType, KeywordTypeMetadata, TypeElement, TypeLit, Union};
 use stc_ts_utils::MapWithMut;
 use stc_utils::{
     cache::Freeze,
@@ -614,6 +615,12 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, stmt: &RSwitchStmt) -> VResult<()> {
         let discriminant_ty = self.report_errors_for_incomparable_switch_cases(stmt).report(&mut self.storage);
 
+        if self.rule().no_fallthrough_cases_in_switch {
+            for span in fallthrough_cases(stmt) {
+                self.storage.report(ErrorKind::FallthroughCaseInSwitch { span }.into());
+            }
+        }
+
         let mut false_facts = CondFacts::default();
         let mut base_true_facts = self.cur_facts.true_facts.take();
         // Declared at here as it's important to know if last one ends with return.
@@ -1275,6 +1282,16 @@ impl Analyzer<'_, '_> {
 
         match prop_res {
             Ok(mut prop_ty) => {
+                // With `exactOptionalPropertyTypes`, `'foo' in obj` means that the property
+                // is present, so it's not optional anymore.
+                if type_facts.is_none() && self.rule().exact_optional_property_types {
+                    if let Some(lit) = self.convert_type_to_type_lit(span, Cow::Borrowed(&*src))? {
+                        if let Some(lit) = make_property_required(&lit, property) {
+                            return Ok(Type::TypeLit(lit).freezed());
+                        }
+                    }
+                }
+
                 // Check if property matches the type fact.
                 if let Some(type_facts) = type_facts {
                     let orig = prop_ty.clone();
@@ -1441,3 +1458,27 @@ impl Facts {
         }
     }
 }
+
+/// Returns a copy of `lit` where the optional property named `property` is
+/// required.
+///
+/// Returns [None] if there's no such optional property.
+fn make_property_required(lit: &TypeLit, property: &JsWord) -> Option<TypeLit> {
+    let mut changed = false;
+    let mut lit = lit.clone();
+
+    for member in lit.members.iter_mut() {
+        if let TypeElement::Property(p) = member {
+            if p.optional && p.key == *property {
+                p.optional = false;
+                changed = true;
+            }
+        }
+    }
+
+    if changed {
+        Some(lit)
+    } else {
+        None
+    }
+}
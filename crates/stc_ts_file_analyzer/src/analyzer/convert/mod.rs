@@ -18,7 +18,7 @@ use stc_ts_types::{
     ConstructorSignature, FnParam, Id, IdCtx, ImportType, IndexSignature, IndexedAccessType, InferType, InferTypeMetadata, Interface,
     IntrinsicKind, Key, KeywordType, KeywordTypeMetadata, LitType, LitTypeMetadata, Mapped, MethodSignature, Operator, OptionalType,
     Predicate, PropertySignature, QueryExpr, QueryType, Ref, RefMetadata, RestType, StringMapping, Symbol, ThisType, TplElem, TplType,
-    TsExpr, Tuple, TupleElement, TupleMetadata, Type, TypeElement, TypeLit, TypeLitMetadata, TypeParam, TypeParamDecl,
+    TsExpr, Tuple, TupleElement, TupleMetadata, Type, TypeElMetadata, TypeElement, TypeLit, TypeLitMetadata, TypeParam, TypeParamDecl,
     TypeParamInstantiation,
 };
 use stc_ts_utils::{find_ids_in_pat, PatExt};
@@ -408,7 +408,10 @@ impl Analyzer<'_, '_> {
                 type_params,
                 params,
                 ret_ty: try_opt!(d.type_ann.validate_with(child)).map(Box::new),
-                metadata: Default::default(),
+                metadata: TypeElMetadata {
+                    deprecated: child.is_deprecated(d.span),
+                    ..Default::default()
+                },
             })
         })
     }
@@ -487,8 +490,12 @@ impl Analyzer<'_, '_> {
             readonly: d.readonly,
             type_ann,
             type_params,
-            metadata: Default::default(),
+            metadata: TypeElMetadata {
+                deprecated: self.is_deprecated(d.span),
+                ..Default::default()
+            },
             accessor: Default::default(),
+            write_type: Default::default(),
         })
     }
 }
@@ -518,11 +525,15 @@ impl Analyzer<'_, '_> {
             readonly: d.readonly,
             type_ann,
             type_params: Default::default(),
-            metadata: Default::default(),
+            metadata: TypeElMetadata {
+                deprecated: self.is_deprecated(d.span),
+                ..Default::default()
+            },
             accessor: Accessor {
                 getter: true,
                 setter: false,
             },
+            write_type: Default::default(),
         })
     }
 }
@@ -541,11 +552,15 @@ impl Analyzer<'_, '_> {
             readonly: d.readonly,
             type_ann: Default::default(),
             type_params: Default::default(),
-            metadata: Default::default(),
+            metadata: TypeElMetadata {
+                deprecated: self.is_deprecated(d.span),
+                ..Default::default()
+            },
             accessor: Accessor {
                 getter: false,
                 setter: true,
             },
+            write_type: Default::default(),
         })
     }
 }
@@ -599,10 +614,30 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RTsTupleElement) -> VResult<TupleElement> {
+        let mut label = node.label.clone();
+        let mut ty = node.ty.validate_with(self)?;
+
+        // `[name?: T]` is stored as `[name: T?]`, so optionality is checked in one
+        // place.
+        if let Some(RPat::Ident(i)) = &mut label {
+            if i.id.optional {
+                i.id.optional = false;
+
+                if !ty.is_optional() {
+                    ty = Type::Optional(OptionalType {
+                        span: node.span,
+                        ty: box ty,
+                        metadata: Default::default(),
+                        tracker: Default::default(),
+                    });
+                }
+            }
+        }
+
         Ok(TupleElement {
             span: node.span,
-            label: node.label.clone(),
-            ty: box node.ty.validate_with(self)?,
+            label,
+            ty: box ty,
             tracker: Default::default(),
         })
     }
@@ -782,6 +817,7 @@ impl Analyzer<'_, '_> {
 
             RTsEntityName::Ident(ref i) => {
                 self.report_error_for_type_param_usages_in_static_members(i);
+                self.report_deprecated_reference(i.span, &i.into());
 
                 if let Some(types) = self.find_type(&i.into())? {
                     let mut found = false;
@@ -1358,6 +1394,7 @@ impl Analyzer<'_, '_> {
                         type_params: None,
                         metadata: Default::default(),
                         accessor: Default::default(),
+                        write_type: Default::default(),
                     }))
                 }
                 RObjectPatProp::Assign(RAssignPatProp { key, value, .. }) => {
@@ -1376,6 +1413,7 @@ impl Analyzer<'_, '_> {
                         type_params: None,
                         metadata: Default::default(),
                         accessor: Default::default(),
+                        write_type: Default::default(),
                     }))
                 }
                 RObjectPatProp::Rest(..) => {}
@@ -2,12 +2,16 @@ use std::borrow::Cow;
 
 use fxhash::FxHashMap;
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt};
-use stc_ts_types::{ClassDef, ClassMember, ClassProperty, Id, Interface, Method, Type, TypeElement, TypeParam};
+use stc_ts_types::{ClassDef, ClassMember, ClassProperty, Id, Interface, Method, Module, Type, TypeElement, TypeParam};
 use stc_utils::cache::Freeze;
+use swc_atoms::JsWord;
 use swc_common::{Span, Spanned};
 use tracing::info;
 
-use crate::{analyzer::Analyzer, VResult};
+use crate::{
+    analyzer::{Analyzer, ResultExt},
+    VResult,
+};
 
 impl Analyzer<'_, '_> {
     fn type_element_to_class_member(&mut self, el: &TypeElement) -> VResult<Option<ClassMember>> {
@@ -24,7 +28,9 @@ impl Analyzer<'_, '_> {
                 is_optional: p.optional,
                 readonly: p.readonly,
                 definite: false,
+                deprecated: p.metadata.deprecated,
                 accessor: p.accessor,
+                write_type: p.write_type.clone(),
             }))),
             TypeElement::Method(m) => Ok(Some(ClassMember::Method(Method {
                 span: m.span,
@@ -36,6 +42,7 @@ impl Analyzer<'_, '_> {
                 type_params: m.type_params.clone(),
                 params: m.params.clone(),
                 ret_ty: m.ret_ty.clone().unwrap_or_else(|| box Type::any(m.span, Default::default())),
+                deprecated: m.metadata.deprecated,
             }))),
             TypeElement::Index(i) => Ok(Some(ClassMember::IndexSignature(i.clone()))),
         }
@@ -131,6 +138,29 @@ impl Analyzer<'_, '_> {
                 }
             }
 
+            // Multiple blocks of a namespace contribute to one namespace.
+            (Type::Module(a), Type::Module(b)) => {
+                let mut exports = (*a.exports).clone();
+
+                for (sym, ty) in &b.exports.vars {
+                    exports.vars.entry(sym.clone()).or_insert_with(|| ty.clone());
+                }
+                for (id, ty) in &b.exports.private_vars {
+                    exports.private_vars.entry(id.clone()).or_insert_with(|| ty.clone());
+                }
+                for (sym, types) in &b.exports.types {
+                    exports.types.entry(sym.clone()).or_default().extend(types.iter().cloned());
+                }
+                for (id, types) in &b.exports.private_types {
+                    exports.private_types.entry(id.clone()).or_default().extend(types.iter().cloned());
+                }
+
+                return Ok(Some(Type::Module(Module {
+                    exports: box exports,
+                    ..a.clone()
+                })));
+            }
+
             _ => {}
         }
 
@@ -138,18 +168,21 @@ impl Analyzer<'_, '_> {
     }
 
     /// Handle declaration merging.
-    fn merge_declaration_types(&mut self, span: Span, orig: Type, new: Type) -> VResult<Type> {
+    ///
+    /// Returns [None] if `orig` and `new` are separate declarations sharing a
+    /// name, like a namespace merged with an interface.
+    fn merge_declaration_types(&mut self, span: Span, orig: Type, new: Type) -> VResult<Option<Type>> {
         debug_assert!(orig.is_clone_cheap());
         debug_assert!(new.is_clone_cheap());
 
         if let Some(new_ty) = self.merge_from_to(span, orig.clone(), new.clone())? {
-            return Ok(new_ty);
+            return Ok(Some(new_ty));
         }
-        if let Some(new_ty) = self.merge_from_to(span, new.clone(), orig)? {
-            return Ok(new_ty);
+        if let Some(new_ty) = self.merge_from_to(span, new, orig)? {
+            return Ok(Some(new_ty));
         }
 
-        Ok(new)
+        Ok(None)
     }
 
     pub(crate) fn merge_decl_with_name(&mut self, name: Id, new: Type) -> VResult<(Type, bool)> {
@@ -161,9 +194,111 @@ impl Analyzer<'_, '_> {
 
         let orig = orig.next().unwrap().into_owned();
 
-        let new = self.merge_declaration_types(new.span(), orig, new)?;
-        info!("Merging declaration {} with type {}", name, dump_type_as_string(&new));
+        match self.merge_declaration_types(new.span(), orig, new.clone())? {
+            Some(new) => {
+                info!("Merging declaration {} with type {}", name, dump_type_as_string(&new));
+
+                Ok((new, true))
+            }
+            None => Ok((new, false)),
+        }
+    }
+
+    /// Declares a global type, merging it with the previous declaration so
+    /// that `declare global` can augment builtin types like `Array<T>`.
+    pub(crate) fn declare_global_type(&mut self, name: JsWord, ty: Type) {
+        if let Ok(prev) = self.env.get_global_type(ty.span(), &name) {
+            match self.merge_declaration_types(ty.span(), prev, ty.clone()) {
+                Ok(Some(merged)) => {
+                    self.env.set_global_type(name, merged.freezed());
+                    return;
+                }
+                Ok(None) => {}
+                Err(err) => self.storage.report(err),
+            }
+        }
+
+        self.env.declare_global_type(name, ty);
+    }
+
+    /// Merges `declare module "foo" {}` in other files into the exports of
+    /// `module`.
+    pub(crate) fn apply_module_augmentations(&mut self, module: Type, augmentations: Vec<Type>) -> Type {
+        if augmentations.is_empty() {
+            return module;
+        }
+
+        let mut module = match module.normalize() {
+            Type::Module(m) => m.clone(),
+            _ => return module,
+        };
+
+        for augmentation in augmentations {
+            let augmentation = match augmentation.normalize() {
+                Type::Module(m) => m,
+                _ => continue,
+            };
 
-        Ok((new, true))
+            for (sym, ty) in &augmentation.exports.vars {
+                module.exports.vars.entry(sym.clone()).or_insert_with(|| ty.clone());
+            }
+
+            for (sym, types) in &augmentation.exports.types {
+                let orig = module.exports.types.entry(sym.clone()).or_default();
+
+                for ty in types {
+                    let merged = match orig.first() {
+                        Some(prev) => self
+                            .merge_declaration_types(ty.span(), prev.clone(), ty.clone())
+                            .report(&mut self.storage)
+                            .flatten(),
+                        None => None,
+                    };
+
+                    match merged {
+                        Some(merged) => orig[0] = merged.freezed(),
+                        None => orig.push(ty.clone()),
+                    }
+                }
+            }
+        }
+
+        Type::Module(module).freezed()
+    }
+
+    /// Finds the namespace named `name`, which may be merged with a class, a
+    /// function, an enum or an interface.
+    pub(crate) fn find_namespace_type(&self, name: &Id) -> VResult<Option<Type>> {
+        if let Some(types) = self.find_type(name)? {
+            for ty in types {
+                match ty.normalize() {
+                    Type::Module(..) | Type::Namespace(..) => return Ok(Some(ty.into_owned())),
+                    Type::Intersection(i) => {
+                        if let Some(ty) = i.types.iter().find(|ty| is_namespace_like(ty)) {
+                            return Ok(Some(ty.clone()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn is_namespace_like(ty: &Type) -> bool {
+    matches!(ty.normalize(), Type::Module(..) | Type::Namespace(..))
+}
+
+/// Removes namespaces from a declaration merged with namespaces.
+///
+/// Returns [None] if `ty` is not such a declaration.
+pub(crate) fn without_namespaces(ty: &Type) -> Option<Type> {
+    match ty.normalize() {
+        Type::Intersection(i) if i.types.iter().any(is_namespace_like) && !i.types.iter().all(is_namespace_like) => {
+            Some(Type::new_intersection(i.span, i.types.iter().filter(|ty| !is_namespace_like(ty)).cloned()).freezed())
+        }
+        _ => None,
     }
 }
@@ -0,0 +1,255 @@
+//! `@deprecated` tags of JSDoc comments.
+//!
+//! Deprecated declarations of a file are collected before analyzing the file,
+//! and each reference to them is reported as a suggestion. The tags are
+//! exported with the declarations, so references from other modules are
+//! reported too. Deprecated members are marked in the types of their classes
+//! and interfaces.
+//!
+//! ```ts
+//! /** @deprecated Use `bar` instead. */
+//! function foo() {}
+//!
+//! foo(); // TS6385
+//! ```
+
+use std::borrow::Cow;
+
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{RDecl, RExportDecl, RIdent, RModule, RStmt};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{ClassMember, Deprecation, Id, Key, ModuleId, Type, TypeElement};
+use stc_ts_utils::{find_ids_in_pat, StcComments};
+use swc_atoms::JsWord;
+use swc_common::{
+    comments::{Comment, CommentKind, Comments},
+    BytePos, Span, Spanned,
+};
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    /// Collects declarations of `module` with `@deprecated`, and stores them
+    /// so they are exported with the declarations.
+    ///
+    /// Should be called before analyzing any item of the module.
+    pub(super) fn collect_deprecations(&mut self, module_id: ModuleId, module: &RModule) {
+        if self.config.is_builtin {
+            return;
+        }
+
+        let mut collector = DeprecationCollector {
+            comments: &self.comments,
+            deprecations: vec![],
+        };
+        module.visit_with(&mut collector);
+
+        for (id, deprecation) in collector.deprecations {
+            self.storage.store_private_deprecation(module_id, id.clone(), deprecation.clone());
+            self.data.deprecations.insert(id, deprecation);
+        }
+    }
+
+    /// Returns `true` if the declaration at `span` has a JSDoc comment with
+    /// `@deprecated`.
+    ///
+    /// Used to mark members of classes and interfaces.
+    pub(crate) fn is_deprecated(&self, span: Span) -> bool {
+        !self.config.is_builtin && find_deprecation(&self.comments, span).is_some()
+    }
+
+    /// Marks `id`, which is imported from another module, as deprecated.
+    ///
+    /// The import itself is reported, like `tsc`.
+    pub(crate) fn import_deprecation(&mut self, span: Span, id: &Id, deprecation: &Deprecation) {
+        self.data.deprecations.insert(id.clone(), deprecation.clone());
+
+        self.report_deprecated_reference(span, id);
+    }
+
+    /// Reports a reference to `id` if it's declared with `@deprecated`.
+    pub(crate) fn report_deprecated_reference(&mut self, span: Span, id: &Id) {
+        let deprecation = match self.data.deprecations.get(id).cloned() {
+            Some(v) => v,
+            None => return,
+        };
+        // The declaration itself.
+        if deprecation.decl_span.contains(span) || !self.data.deprecated_references.insert(span) {
+            return;
+        }
+
+        self.storage.report(
+            ErrorKind::Deprecated {
+                span,
+                name: id.sym().clone(),
+                message: deprecation.message,
+                decl_span: deprecation.decl_span,
+            }
+            .into(),
+        );
+    }
+
+    /// Reports an access to the property `key` of `obj` if the property is
+    /// declared with `@deprecated`.
+    pub(crate) fn report_deprecated_member(&mut self, span: Span, obj: &Type, key: &Key) {
+        if self.config.is_builtin {
+            return;
+        }
+        let sym = match key {
+            Key::Normal { sym, .. } => sym,
+            _ => return,
+        };
+
+        let decl_span = match self.find_deprecated_member(span, obj, sym) {
+            Some(v) => v,
+            None => return,
+        };
+        if decl_span.contains(span) || !self.data.deprecated_references.insert(span) {
+            return;
+        }
+
+        self.storage.report(
+            ErrorKind::Deprecated {
+                span,
+                name: sym.clone(),
+                message: None,
+                decl_span,
+            }
+            .into(),
+        );
+    }
+
+    /// Returns the span of the declaration of `obj[sym]` if it's deprecated.
+    fn find_deprecated_member(&mut self, span: Span, obj: &Type, sym: &JsWord) -> Option<Span> {
+        let obj = self.normalize(Some(span), Cow::Borrowed(obj), Default::default()).ok()?;
+
+        // Members of classes are not converted, because type elements do not have
+        // the marks of class members.
+        match obj.normalize() {
+            Type::Class(c) => return deprecated_class_member(&c.def.body, sym, false),
+            Type::ClassDef(def) => return deprecated_class_member(&def.body, sym, true),
+            _ => {}
+        }
+
+        let lit = self.convert_type_to_type_lit(span, obj).ok()??;
+        lit.members.iter().find_map(|member| match member {
+            TypeElement::Property(p) if p.metadata.deprecated && is_key(&p.key, sym) => Some(p.span),
+            TypeElement::Method(m) if m.metadata.deprecated && is_key(&m.key, sym) => Some(m.span),
+            _ => None,
+        })
+    }
+}
+
+fn deprecated_class_member(body: &[ClassMember], sym: &JsWord, is_static: bool) -> Option<Span> {
+    body.iter().find_map(|member| match member {
+        ClassMember::Property(p) if p.deprecated && p.is_static == is_static && is_key(&p.key, sym) => Some(p.span),
+        ClassMember::Method(m) if m.deprecated && m.is_static == is_static && is_key(&m.key, sym) => Some(m.span),
+        _ => None,
+    })
+}
+
+fn is_key(key: &Key, sym: &JsWord) -> bool {
+    matches!(key, Key::Normal { sym: key, .. } if key == sym)
+}
+
+/// Returns `Some(message)` if the declaration at `span` has `@deprecated`.
+fn find_deprecation(comments: &StcComments, span: Span) -> Option<Option<JsWord>> {
+    if span.lo == BytePos(0) {
+        return None;
+    }
+
+    // Only the last JSDoc comment applies, like `tsc`.
+    comments.with_leading(span.lo, |comments| {
+        comments.iter().rev().find(|c| is_jsdoc(c)).and_then(parse_deprecated)
+    })
+}
+
+struct DeprecationCollector<'a> {
+    comments: &'a StcComments,
+    deprecations: Vec<(Id, Deprecation)>,
+}
+
+impl DeprecationCollector<'_> {
+    fn check(&mut self, span: Span, decl: &RDecl) {
+        let message = match find_deprecation(self.comments, span) {
+            Some(v) => v,
+            None => return,
+        };
+
+        for (id, decl_span) in declared_names(decl) {
+            self.deprecations.push((
+                id,
+                Deprecation {
+                    decl_span,
+                    message: message.clone(),
+                },
+            ));
+        }
+    }
+}
+
+impl Visit<RStmt> for DeprecationCollector<'_> {
+    fn visit(&mut self, stmt: &RStmt) {
+        if let RStmt::Decl(decl) = stmt {
+            self.check(stmt.span(), decl);
+        }
+
+        stmt.visit_children_with(self);
+    }
+}
+
+impl Visit<RExportDecl> for DeprecationCollector<'_> {
+    fn visit(&mut self, export: &RExportDecl) {
+        self.check(export.span, &export.decl);
+
+        export.visit_children_with(self);
+    }
+}
+
+/// Names declared by `decl`, with the spans of their declarations.
+fn declared_names(decl: &RDecl) -> Vec<(Id, Span)> {
+    match decl {
+        RDecl::Class(c) => vec![(c.ident.clone().into(), c.span())],
+        // Deprecating an overload does not deprecate the others.
+        RDecl::Fn(f) if f.function.body.is_none() && !f.declare => vec![],
+        RDecl::Fn(f) => vec![(f.ident.clone().into(), f.span())],
+        RDecl::Var(var) => var
+            .decls
+            .iter()
+            .flat_map(|d| find_ids_in_pat::<_, RIdent>(&d.name).into_iter().map(move |i| (i.into(), d.span)))
+            .collect(),
+        RDecl::TsInterface(i) => vec![(i.id.clone().into(), i.span)],
+        RDecl::TsTypeAlias(a) => vec![(a.id.clone().into(), a.span)],
+        RDecl::TsEnum(e) => vec![(e.id.clone().into(), e.span)],
+        RDecl::TsModule(..) => vec![],
+    }
+}
+
+fn is_jsdoc(c: &Comment) -> bool {
+    c.kind == CommentKind::Block && c.text.starts_with('*')
+}
+
+/// Returns `Some(message)` if `c` has `@deprecated`.
+fn parse_deprecated(c: &Comment) -> Option<Option<JsWord>> {
+    let mut lines = c.text.lines().map(|line| line.trim_start().trim_start_matches('*').trim());
+
+    let first = lines.by_ref().find_map(|line| line.strip_prefix("@deprecated"))?;
+    if first.starts_with(|c: char| !c.is_whitespace()) {
+        // Another tag, like `@deprecatedFoo`.
+        return None;
+    }
+
+    // The message continues until the next tag.
+    let mut message = first.trim().to_string();
+    for line in lines.take_while(|line| !line.starts_with('@')) {
+        if line.is_empty() {
+            continue;
+        }
+        if !message.is_empty() {
+            message.push(' ');
+        }
+        message.push_str(line);
+    }
+
+    Some(if message.is_empty() { None } else { Some(message.into()) })
+}
@@ -1,7 +1,8 @@
 use fxhash::FxHashMap;
 use rnode::{NodeId, Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RBinExpr, RBindingIdent, RExpr, RIdent, RLit, RNumber, RPat, RStr, RTsEnumDecl, RTsEnumMember, RTsEnumMemberId, RTsLit,
+    RBinExpr, RBindingIdent, RComputedPropName, RExpr, RIdent, RLit, RMemberExpr, RMemberProp, RNumber, RPat, RStr, RTsEnumDecl,
+    RTsEnumMember, RTsEnumMemberId, RTsLit,
 };
 use stc_ts_errors::{ErrorKind, Errors};
 use stc_ts_file_analyzer_macros::validator;
@@ -45,7 +46,8 @@ impl Analyzer<'_, '_> {
             self.validate_with(|a| a.validate_enum_member_name(&m.id));
         }
 
-        let mut default = 0.0;
+        // `None` if the previous member is not a numeric constant.
+        let mut default = Some(0.0);
         let mut values = Default::default();
 
         let mut eval = Evaluator {
@@ -61,11 +63,12 @@ impl Analyzer<'_, '_> {
                 .map(|m| -> VResult<_> {
                     let id_span = m.id.span();
                     let val = eval
-                        .compute(self, id_span, Some(default), m.init.as_deref())
+                        .compute(self, id_span, default, m.init.as_deref())
                         .map(|val| {
-                            if let RTsLit::Number(n) = &val {
-                                default = n.value + 1.0;
-                            }
+                            default = match &val {
+                                RTsLit::Number(n) => Some(n.value + 1.0),
+                                _ => None,
+                            };
                             eval.values.insert(
                                 match &m.id {
                                     RTsEnumMemberId::Ident(i) => i.sym.clone(),
@@ -89,6 +92,7 @@ impl Analyzer<'_, '_> {
                         .or_else(|err| match &m.init {
                             None => Err(err),
                             Some(v) => {
+                                default = None;
                                 if e.is_const {
                                     self.storage.report(err);
                                 }
@@ -116,7 +120,7 @@ impl Analyzer<'_, '_> {
                 span: e.span,
                 has_num: members.iter().any(|m| matches!(*m.val, RExpr::Lit(RLit::Num(..)))),
                 has_str,
-                declare: e.declare,
+                declare: e.declare || self.ctx.in_declare,
                 is_const: e.is_const,
                 id: e.id.clone(),
                 members,
@@ -215,7 +219,7 @@ impl Evaluator<'_> {
                     for m in self.e.members.iter() {
                         match m.id {
                             RTsEnumMemberId::Str(RStr { value: ref sym, .. }) | RTsEnumMemberId::Ident(RIdent { ref sym, .. }) => {
-                                if *sym == id.sym {
+                                if *sym == id.sym && m.init.is_some() {
                                     return self.compute(analyzer, span, None, m.init.as_deref());
                                 }
                             }
@@ -239,7 +243,7 @@ impl Evaluator<'_> {
                                             1.0
                                         }
                                     }
-                                    op!("~") => (!(v as i32)) as f64,
+                                    op!("~") => (!to_int32(v)) as f64,
                                     _ => Err(ErrorKind::InvalidEnumInit { span })?,
                                 },
                                 raw: None,
@@ -258,6 +262,52 @@ impl Evaluator<'_> {
                     }
                 }
 
+                RExpr::Tpl(ref t) => {
+                    let mut value = String::new();
+
+                    for (i, quasi) in t.quasis.iter().enumerate() {
+                        match &quasi.cooked {
+                            Some(cooked) => value.push_str(cooked),
+                            None => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                        }
+
+                        if let Some(expr) = t.exprs.get(i) {
+                            match self.compute(analyzer, span, None, Some(expr))? {
+                                RTsLit::Str(s) => value.push_str(&s.value),
+                                RTsLit::Number(n) => value.push_str(&n.value.to_string()),
+                                _ => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                            }
+                        }
+                    }
+
+                    return Ok(RTsLit::Str(RStr {
+                        span,
+                        value: value.into(),
+                        raw: None,
+                    }));
+                }
+
+                // References to members of the enum being declared, like `E.A` or `E["A"]`.
+                RExpr::Member(RMemberExpr {
+                    obj: box RExpr::Ident(obj),
+                    prop,
+                    ..
+                }) if obj.sym == self.e.id.sym && obj.span.ctxt == self.e.id.span.ctxt => {
+                    let name = match prop {
+                        RMemberProp::Ident(i) => &i.sym,
+                        RMemberProp::Computed(RComputedPropName {
+                            expr: box RExpr::Lit(RLit::Str(s)),
+                            ..
+                        }) => &s.value,
+                        _ => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                    };
+
+                    return match self.values.get(name) {
+                        Some(v) => Ok(v.clone()),
+                        None => Err(ErrorKind::InvalidEnumInit { span }.into()),
+                    };
+                }
+
                 _ => {
                     let res = expr.validate_with_default(analyzer)?;
                     let res = analyzer.expand_enum_variant(res)?;
@@ -268,12 +318,15 @@ impl Evaluator<'_> {
                 }
             }
         } else {
-            if let Some(value) = default {
-                return Ok(RTsLit::Number(RNumber {
-                    span,
-                    value: value as _,
-                    raw: None,
-                }));
+            match default {
+                Some(value) => {
+                    return Ok(RTsLit::Number(RNumber {
+                        span,
+                        value: value as _,
+                        raw: None,
+                    }))
+                }
+                None => return Err(ErrorKind::EnumMemberMustHaveInit { span }.into()),
             }
         }
 
@@ -285,30 +338,28 @@ impl Evaluator<'_> {
         let r = self.compute(analyzer, span, None, Some(&expr.right))?;
 
         Ok(match (l, r) {
-            (RTsLit::Number(RNumber { value: l, .. }), RTsLit::Number(RNumber { value: r, .. })) => {
-                RTsLit::Number(RNumber {
-                    span,
-                    value: match expr.op {
-                        op!(bin, "+") => l + r,
-                        op!(bin, "-") => l - r,
-                        op!("*") => l * r,
-                        op!("/") => l / r,
-
-                        // TODO
-                        op!("&") => ((l.round() as i64) & (r.round() as i64)) as _,
-                        op!("|") => ((l.round() as i64) | (r.round() as i64)) as _,
-                        op!("^") => ((l.round() as i64) ^ (r.round() as i64)) as _,
-
-                        op!("<<") => ((l.round() as i64) << (r.round() as i64)) as _,
-                        op!(">>") => ((l.round() as i64) >> (r.round() as i64)) as _,
-                        // TODO(kdy1): Verify this
-                        op!(">>>") => ((l.round() as u64) >> (r.round() as u64)) as _,
-                        _ => Err(ErrorKind::InvalidEnumInit { span })?,
-                    },
-
-                    raw: None,
-                })
-            }
+            (RTsLit::Number(RNumber { value: l, .. }), RTsLit::Number(RNumber { value: r, .. })) => RTsLit::Number(RNumber {
+                span,
+                value: match expr.op {
+                    op!(bin, "+") => l + r,
+                    op!(bin, "-") => l - r,
+                    op!("*") => l * r,
+                    op!("/") => l / r,
+                    op!("%") => l % r,
+                    op!("**") => l.powf(r),
+
+                    op!("&") => (to_int32(l) & to_int32(r)) as _,
+                    op!("|") => (to_int32(l) | to_int32(r)) as _,
+                    op!("^") => (to_int32(l) ^ to_int32(r)) as _,
+
+                    op!("<<") => (to_int32(l) << (to_uint32(r) & 31)) as _,
+                    op!(">>") => (to_int32(l) >> (to_uint32(r) & 31)) as _,
+                    op!(">>>") => (to_uint32(l) >> (to_uint32(r) & 31)) as _,
+                    _ => Err(ErrorKind::InvalidEnumInit { span })?,
+                },
+
+                raw: None,
+            }),
             (RTsLit::Str(l), RTsLit::Str(r)) if expr.op == op!(bin, "+") => RTsLit::Str(RStr {
                 span,
                 value: format!("{}{}", l.value, r.value).into(),
@@ -337,6 +388,20 @@ impl Evaluator<'_> {
     }
 }
 
+/// `ToInt32` of ECMAScript.
+fn to_int32(v: f64) -> i32 {
+    if !v.is_finite() {
+        return 0;
+    }
+
+    (v.trunc() % 4294967296.0) as i64 as u32 as i32
+}
+
+/// `ToUint32` of ECMAScript.
+fn to_uint32(v: f64) -> u32 {
+    to_int32(v) as u32
+}
+
 impl Analyzer<'_, '_> {
     fn validate_enum_member_name(&mut self, e: &RTsEnumMemberId) -> VResult<()> {
         match e {
@@ -400,6 +465,7 @@ impl Analyzer<'_, '_> {
                     getter: true,
                     setter: false,
                 },
+                write_type: Default::default(),
             }))
         }
         {
@@ -569,9 +635,13 @@ impl Analyzer<'_, '_> {
                     metadata: Default::default(),
                     tracker: Default::default(),
                 })),
-                _ => {
-                    unimplemented!("Handle enum with value other than string literal or numeric literals")
-                }
+                // Computed members are numbers.
+                _ => values.push(Type::Keyword(KeywordType {
+                    span: m.span,
+                    kind: TsKeywordTypeKind::TsNumberKeyword,
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                })),
             }
         }
 
@@ -581,6 +651,53 @@ impl Analyzer<'_, '_> {
         Ok(ty)
     }
 
+    /// Converts an enum type `E` to `E.A | E.B` if all members of `E` have
+    /// literal values.
+    ///
+    /// Returns [None] for enums with computed members, as they are not union
+    /// enums.
+    pub(super) fn expand_union_enum(&self, e: &EnumVariant) -> VResult<Option<Type>> {
+        if e.name.is_some() {
+            return Ok(None);
+        }
+
+        if let Some(types) = self.find_type(&e.enum_name)? {
+            for ty in types {
+                if let Type::Enum(def) = ty.normalize() {
+                    if def.members.is_empty()
+                        || !def
+                            .members
+                            .iter()
+                            .all(|m| matches!(*m.val, RExpr::Lit(RLit::Num(..)) | RExpr::Lit(RLit::Str(..))))
+                    {
+                        return Ok(None);
+                    }
+
+                    let variants = def
+                        .members
+                        .iter()
+                        .map(|m| {
+                            Type::EnumVariant(EnumVariant {
+                                span: e.span,
+                                enum_name: e.enum_name.clone(),
+                                name: Some(match &m.id {
+                                    RTsEnumMemberId::Ident(i) => i.sym.clone(),
+                                    RTsEnumMemberId::Str(s) => s.value.clone(),
+                                }),
+                                metadata: Default::default(),
+                                tracker: Default::default(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    return Ok(Some(Type::new_union(e.span, variants).freezed()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Expands an enum variant as a literal.
     pub(super) fn expand_enum_variant(&self, ty: Type) -> VResult<Type> {
         if let Type::EnumVariant(ref ev) = ty.normalize() {
@@ -1,16 +1,18 @@
+use std::mem::take;
+
 use rnode::{NodeId, VisitWith};
 use stc_ts_ast_rnode::{
     RBindingIdent, RDecl, RDefaultDecl, RExportAll, RExportDecl, RExportDefaultDecl, RExportDefaultExpr, RExportNamedSpecifier,
     RExportSpecifier, RExpr, RIdent, RModuleExportName, RNamedExport, RPat, RStmt, RTsExportAssignment, RTsModuleName, RTsTypeAnn,
     RVarDecl, RVarDeclarator,
 };
-use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_errors::{DebugExt, DeferredMsg, ErrorKind};
 use stc_ts_file_analyzer_macros::extra_validator;
-use stc_ts_types::{Id, IdCtx, ModuleId};
+use stc_ts_types::{ExportEquals, Id, IdCtx, ModuleId, ModuleTypeData};
 use stc_ts_utils::find_ids_in_pat;
 use stc_utils::cache::Freeze;
 use swc_atoms::{js_word, JsWord};
-use swc_common::{Span, Spanned, DUMMY_SP};
+use swc_common::{Span, Spanned, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 
 use crate::{
@@ -87,7 +89,7 @@ impl Analyzer<'_, '_> {
                         // module);
                         return Err(ErrorKind::Unimplemented {
                             span,
-                            msg: format!("Exporting module with a wildcard: {:?}", module),
+                            msg: DeferredMsg::with_type("Exporting module with a wildcard: ", &module),
                         }
                         .into());
                     }
@@ -238,14 +240,14 @@ impl Analyzer<'_, '_> {
     }
 
     /// Exports a variable.
-    fn export_expr(&mut self, name: Id, item_node_id: NodeId, e: &RExpr) -> VResult<()> {
+    fn export_expr(&mut self, name: Id, item_node_id: NodeId, e: &RExpr) -> VResult<Type> {
         self.report_errors_for_duplicated_exports_of_var(e.span(), name.sym().clone());
 
         let ty = e.validate_with_default(self)?.freezed();
 
         if *name.sym() == js_word!("default") {
             if let RExpr::Ident(..) = e {
-                return Ok(());
+                return Ok(ty);
             }
             let var = RVarDeclarator {
                 node_id: NodeId::invalid(),
@@ -261,7 +263,7 @@ impl Analyzer<'_, '_> {
                     type_ann: Some(box RTsTypeAnn {
                         node_id: NodeId::invalid(),
                         span: DUMMY_SP,
-                        type_ann: ty.into(),
+                        type_ann: ty.clone().into(),
                     }),
                 }),
                 init: None,
@@ -280,10 +282,30 @@ impl Analyzer<'_, '_> {
                     Some(box RExpr::Ident(RIdent::new("_default".into(), DUMMY_SP)));
             }
 
-            return Ok(());
+            return Ok(ty);
         }
 
-        Ok(())
+        Ok(ty)
+    }
+
+    /// Stores the entity exported by `export = foo`, which is used as is by
+    /// `import foo = require('./foo')`.
+    ///
+    /// `ty` is the type of `e` as a value.
+    fn export_equals(&mut self, e: &RExpr, ty: Option<Type>) {
+        let types = match e {
+            RExpr::Ident(i) => match self.find_type(&i.into()) {
+                Ok(Some(types)) => types.map(|ty| ty.into_owned().freezed()).collect(),
+                Ok(None) => vec![],
+                Err(err) => {
+                    self.storage.report(err);
+                    vec![]
+                }
+            },
+            _ => vec![],
+        };
+
+        self.storage.export_equals(self.ctx.module_id, ExportEquals { var: ty, types });
     }
 }
 
@@ -292,8 +314,12 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RTsExportAssignment) {
         let ctx = Ctx { ..self.ctx };
-        self.with_ctx(ctx)
-            .export_expr(Id::word(js_word!("default")), node.node_id, &node.expr)?;
+        let ty = self
+            .with_ctx(ctx)
+            .export_expr(Id::word(js_word!("default")), node.node_id, &node.expr)
+            .report(&mut self.storage);
+
+        self.export_equals(&node.expr, ty);
 
         Ok(())
     }
@@ -356,17 +382,11 @@ impl Analyzer<'_, '_> {
         if ctxt != dep {
             match data.normalize() {
                 Type::Module(data) => {
-                    for (id, ty) in data.exports.vars.iter() {
-                        self.storage.reexport_var(span, dep, id.clone(), ty.clone());
-                    }
-                    for (id, types) in data.exports.types.iter() {
-                        for ty in types {
-                            self.storage.reexport_type(span, dep, id.clone(), ty.clone());
-                        }
-                    }
+                    self.reexport_all(span, ctxt, dep, &data.exports);
                 }
                 _ => {
-                    unreachable!()
+                    // The dependency is in the same circular group, so it may not be analyzed yet.
+                    self.data.circular_star_exports.push((span, ctxt, dep));
                 }
             }
         }
@@ -387,13 +407,15 @@ impl Analyzer<'_, '_> {
 
         for specifier in &node.specifiers {
             match specifier {
-                RExportSpecifier::Namespace(_) => {
-                    // We need
-                    match &node.src {
-                        Some(src) => {
-                            let (dep, data) = self.get_imported_items(node.span, &src.value);
+                RExportSpecifier::Namespace(ns) => {
+                    if let Some(src) = &node.src {
+                        let (dep, data) = self.get_imported_items(node.span, &src.value);
+
+                        // `data` is `any` if the import failed or the dependency is in the same
+                        // circular group.
+                        if base != dep {
+                            self.storage.reexport_var(span, base, Id::from(&ns.name).sym().clone(), data);
                         }
-                        None => {}
                     }
                 }
                 RExportSpecifier::Default(_) => {}
@@ -408,8 +430,8 @@ impl Analyzer<'_, '_> {
                                 span,
                                 base,
                                 dep,
-                                named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig)),
                                 Id::from(&named.orig),
+                                named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig)),
                             );
                         }
                         None => {
@@ -474,4 +496,90 @@ impl Analyzer<'_, '_> {
             self.storage.report(ErrorKind::ExportFailed { span, orig, id }.into())
         }
     }
+
+    /// Handles `export * from 'dep'`.
+    ///
+    /// Default exports are not re-exported, and local exports take precedence
+    /// over re-exported ones. If two modules re-exported by `export *` export
+    /// different entities with the same name, the name is ambiguous and an
+    /// error is reported.
+    ///
+    /// Returns `true` if a new name is exported from `ctxt`.
+    fn reexport_all(&mut self, span: Span, ctxt: ModuleId, dep: ModuleId, exports: &ModuleTypeData) -> bool {
+        let mut changed = false;
+
+        let names = exports
+            .vars
+            .keys()
+            .chain(exports.types.keys().filter(|name| !exports.vars.contains_key(*name)))
+            .filter(|name| **name != js_word!("default"))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for name in names {
+            match self.data.star_exports.get(&(ctxt, name.clone())).copied() {
+                // Already re-exported, or ambiguous.
+                Some(Some(prev)) if prev == dep => {}
+                Some(None) => {}
+
+                Some(Some(_)) => {
+                    let is_same_var = match (self.storage.get_exported_var(ctxt, &name), exports.vars.get(&name)) {
+                        (Some(prev), Some(ty)) => prev.type_eq(ty),
+                        (prev, ty) => prev.is_none() && ty.is_none(),
+                    };
+                    let is_same_type = match (self.storage.get_exported_types(ctxt, &name), exports.types.get(&name)) {
+                        (Some(prev), Some(types)) => prev.len() == types.len() && prev.iter().zip(types).all(|(a, b)| a.type_eq(b)),
+                        (prev, types) => prev.is_none() && types.is_none(),
+                    };
+
+                    if !is_same_var || !is_same_type {
+                        self.storage
+                            .report(ErrorKind::AmbiguousStarExport { span, name: name.clone() }.into());
+                        self.data.star_exports.insert((ctxt, name), None);
+                    }
+                }
+
+                None => {
+                    if self.storage.get_exported_var(ctxt, &name).is_some() || self.storage.get_exported_types(ctxt, &name).is_some() {
+                        // Explicit exports win.
+                        continue;
+                    }
+
+                    if let Some(ty) = exports.vars.get(&name) {
+                        self.storage.reexport_var(span, ctxt, name.clone(), ty.clone());
+                    }
+                    for ty in exports.types.get(&name).into_iter().flatten() {
+                        self.storage.reexport_type(span, ctxt, name.clone(), ty.clone());
+                    }
+
+                    self.data.star_exports.insert((ctxt, name), Some(dep));
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Resolves `export *` of modules in the same circular group.
+    ///
+    /// Exports are copied until nothing changes, so that a chain of re-exports
+    /// is followed transitively even if it contains a cycle.
+    pub(super) fn resolve_circular_star_exports(&mut self) {
+        let star_exports = take(&mut self.data.circular_star_exports);
+
+        loop {
+            let mut changed = false;
+
+            for &(span, ctxt, dep) in &star_exports {
+                if let Some(exports) = self.storage.get_exports(dep) {
+                    changed |= self.reexport_all(span, ctxt, dep, &exports);
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
 }
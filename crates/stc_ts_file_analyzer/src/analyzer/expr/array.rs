@@ -1,5 +1,6 @@
-use std::{borrow::Cow, time::Instant};
+use std::borrow::Cow;
 
+use instant::Instant;
 use itertools::Itertools;
 use stc_ts_ast_rnode::{RArrayLit, RExpr, RExprOrSpread, RInvalid, RNumber, RTsLit};
 use stc_ts_errors::{
@@ -41,6 +42,13 @@ use crate::{
 pub(crate) struct GetIteratorOpts {
     /// Defaults to `false`.
     pub disallow_str: bool,
+
+    /// `true` if the iterator is used by the emitted code, like `for-of`,
+    /// spread, or array destructuring.
+    ///
+    /// If `true`, iterating a type which is not an array nor a string is an
+    /// error on ES5 targets without `downlevelIteration`.
+    pub check_downlevel_iteration: bool,
 }
 
 #[validator]
@@ -132,7 +140,15 @@ impl Analyzer<'_, '_> {
                         }
                         _ => {
                             let elem_type = self
-                                .get_iterator_element_type(span, Cow::Owned(element_type), false, Default::default())
+                                .get_iterator_element_type(
+                                    span,
+                                    Cow::Owned(element_type),
+                                    false,
+                                    GetIteratorOpts {
+                                        check_downlevel_iteration: true,
+                                        ..Default::default()
+                                    },
+                                )
                                 .context("tried to calculated the element type of a iterable provided to spread")?
                                 .into_owned();
 
@@ -701,41 +717,53 @@ impl Analyzer<'_, '_> {
                 _ => {}
             }
 
-            self.call_property(
-                span,
-                ExtractKind::Call,
-                Default::default(),
-                &ty,
-                &ty,
-                &Key::Computed(ComputedKey {
+            let iterator = self
+                .call_property(
                     span,
-                    expr: box RExpr::Invalid(RInvalid { span }),
-                    ty: box Type::Symbol(Symbol {
+                    ExtractKind::Call,
+                    Default::default(),
+                    &ty,
+                    &ty,
+                    &Key::Computed(ComputedKey {
                         span,
-                        id: SymbolId::iterator(),
-                        metadata: Default::default(),
-                        tracker: Default::default(),
+                        expr: box RExpr::Invalid(RInvalid { span }),
+                        ty: box Type::Symbol(Symbol {
+                            span,
+                            id: SymbolId::iterator(),
+                            metadata: Default::default(),
+                            tracker: Default::default(),
+                        }),
                     }),
-                }),
-                None,
-                &[],
-                &[],
-                &[],
-                None,
-                CallOpts {
-                    disallow_optional_object_property: true,
-                    do_not_use_any_for_computed_key: true,
-                    ..Default::default()
-                },
-            )
-            .convert_err(|err| match err {
-                ErrorKind::NoCallablePropertyWithName { span, .. }
-                | ErrorKind::NoSuchPropertyInClass { span, .. }
-                | ErrorKind::NoSuchProperty { span, .. } => ErrorKind::MustHaveSymbolIteratorThatReturnsIterator { span },
-                _ => err,
-            })
-            .map(Cow::Owned)
-            .context("tried to call `[Symbol.iterator]()`")
+                    None,
+                    &[],
+                    &[],
+                    &[],
+                    None,
+                    CallOpts {
+                        disallow_optional_object_property: true,
+                        do_not_use_any_for_computed_key: true,
+                        ..Default::default()
+                    },
+                )
+                .convert_err(|err| match err {
+                    ErrorKind::NoCallablePropertyWithName { span, .. }
+                    | ErrorKind::NoSuchPropertyInClass { span, .. }
+                    | ErrorKind::NoSuchProperty { span, .. } => ErrorKind::MustHaveSymbolIteratorThatReturnsIterator { span },
+                    _ => err,
+                })
+                .map(Cow::Owned)
+                .context("tried to call `[Symbol.iterator]()`")?;
+
+            if opts.check_downlevel_iteration
+                && !ty.is_any()
+                && self.env.target() < EsVersion::Es2015
+                && !self.env.rule().downlevel_iteration
+            {
+                // The code emitted for ES5 only supports arrays and strings.
+                self.storage.report(ErrorKind::IterationRequiresDownlevelIteration { span }.into());
+            }
+
+            Ok(iterator)
         })();
 
         res.with_context(|| format!("tried to convert a type ({}) to an iterator", ty_str))
@@ -74,13 +74,60 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Thenables nested deeper than this are not unwrapped.
+const MAX_AWAITED_DEPTH: usize = 50;
+
 impl Analyzer<'_, '_> {
+    /// Returns the type of `await ty`, which is `Awaited<T>` of `lib.es5.d.ts`.
+    ///
+    /// - `any`, `unknown`, `never`, `null` and `undefined` are returned as-is.
+    /// - Members of a union are unwrapped separately.
+    /// - Thenables are unwrapped recursively, using the type of the value
+    ///   passed to `onfulfilled` of `then`.
     pub(crate) fn get_awaited_type<'a>(&mut self, span: Span, ty: Cow<'a, Type>) -> VResult<Cow<'a, Type>> {
-        if let Some(arg) = unwrap_ref_with_single_arg(&ty, "Promise") {
-            return self.get_awaited_type(span, Cow::Borrowed(arg)).map(Cow::into_owned).map(Cow::Owned);
+        self.get_awaited_type_inner(span, ty, 0)
+    }
+
+    fn get_awaited_type_inner<'a>(&mut self, span: Span, ty: Cow<'a, Type>, depth: usize) -> VResult<Cow<'a, Type>> {
+        if depth >= MAX_AWAITED_DEPTH {
+            return Ok(ty);
+        }
+
+        if ty.is_any() || ty.is_unknown() || ty.is_never() || ty.is_null_or_undefined() {
+            return Ok(ty);
+        }
+
+        if let Some(arg) = unwrap_ref_with_single_arg(&ty, "Promise").or_else(|| unwrap_ref_with_single_arg(&ty, "PromiseLike")) {
+            return self
+                .get_awaited_type_inner(span, Cow::Borrowed(arg), depth + 1)
+                .map(Cow::into_owned)
+                .map(Cow::Owned);
         }
 
-        Ok(self
+        match ty.normalize() {
+            Type::Union(u) => {
+                let mut changed = false;
+                let mut types = Vec::with_capacity(u.types.len());
+                for member in &u.types {
+                    let awaited = self.get_awaited_type_inner(span, Cow::Borrowed(member), depth + 1)?;
+                    changed |= matches!(awaited, Cow::Owned(..));
+                    types.push(awaited.into_owned());
+                }
+
+                if !changed {
+                    return Ok(ty);
+                }
+
+                return Ok(Cow::Owned(Type::new_union(u.span, types).freezed()));
+            }
+
+            // `Awaited<T>` of a type parameter is resolved on instantiation.
+            Type::Param(..) => return Ok(ty),
+
+            _ => {}
+        }
+
+        let value_ty = self
             .access_property(
                 span,
                 &ty,
@@ -90,20 +137,45 @@ impl Analyzer<'_, '_> {
                 Default::default(),
             )
             .ok()
-            .and_then(|then_ty| {
-                if let Type::Function(f) = then_ty.normalize() {
-                    // Default type of the first type parameter is awaited type.
-                    if let Some(type_params) = &f.type_params {
-                        if let Some(ty) = type_params.params.first() {
-                            if let Some(ty) = &ty.default {
-                                return Some(Cow::Owned(*ty.clone()));
-                            }
-                        }
-                    }
-                }
+            .and_then(|then_ty| fulfilled_value_type(&then_ty));
 
-                None
-            })
-            .unwrap_or(ty))
+        match value_ty {
+            Some(value_ty) => self
+                .get_awaited_type_inner(span, Cow::Owned(value_ty), depth + 1)
+                .map(Cow::into_owned)
+                .map(Cow::Owned),
+            None => Ok(ty),
+        }
     }
 }
+
+/// Returns the type of the value passed to `onfulfilled`, if `then_ty` is the
+/// type of the `then` method of a thenable.
+fn fulfilled_value_type(then_ty: &Type) -> Option<Type> {
+    let f = match then_ty.normalize() {
+        Type::Function(f) => f,
+        _ => return None,
+    };
+
+    let callback_value = |ty: &Type| match ty.normalize() {
+        Type::Function(callback) => callback.params.first().map(|param| (*param.ty).clone()),
+        _ => None,
+    };
+
+    if let Some(onfulfilled) = f.params.first() {
+        let value = match onfulfilled.ty.normalize() {
+            Type::Union(u) => u.types.iter().find_map(callback_value),
+            ty => callback_value(ty),
+        };
+        if value.is_some() {
+            return value.map(Type::freezed);
+        }
+    }
+
+    // Default type of the first type parameter is awaited type.
+    f.type_params
+        .as_ref()
+        .and_then(|type_params| type_params.params.first())
+        .and_then(|param| param.default.as_ref())
+        .map(|ty| (**ty).clone().freezed())
+}
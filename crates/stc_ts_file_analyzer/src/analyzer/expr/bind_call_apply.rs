@@ -0,0 +1,150 @@
+//! Precise types for `bind`, `call` and `apply` of functions.
+//!
+//! With [stc_ts_env::Rule::strict_bind_call_apply], tsc checks those methods
+//! using the overloads of `CallableFunction`, which infer types from the `this`
+//! parameter of the method. Instead of inferring from `this`, we create the
+//! same signatures directly from the type of the function.
+
+use rnode::NodeId;
+use stc_ts_ast_rnode::{RBindingIdent, RIdent, RPat};
+use stc_ts_types::{CallSignature, FnParam, Function, Key, Tuple, TupleElement, Type, TypeElement, TypeLit};
+use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
+use swc_atoms::js_word;
+use swc_common::Span;
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    /// Returns [None] if the declared members of `Function` should be used.
+    pub(super) fn access_bind_call_apply(&mut self, span: Span, f: &Function, prop: &Key) -> Option<Type> {
+        if !self.rule().strict_bind_call_apply {
+            return None;
+        }
+
+        let sym = match prop {
+            Key::Normal { sym, .. } => sym,
+            _ => return None,
+        };
+
+        let ty = ALLOW_DEEP_CLONE.set(&(), || match &**sym {
+            "call" => Some(fn_call(span, f)),
+            "apply" => Some(fn_apply(span, f)),
+            "bind" => Some(fn_bind(span, f)),
+            _ => None,
+        })?;
+
+        Some(ty.freezed())
+    }
+}
+
+/// Splits parameters into the type of `this` and other parameters.
+fn split_this_param(span: Span, f: &Function) -> (Type, Vec<FnParam>) {
+    let mut this_ty = None;
+    let mut params = vec![];
+
+    for param in &f.params {
+        match &param.pat {
+            RPat::Ident(RBindingIdent {
+                id: RIdent { sym: js_word!("this"), .. },
+                ..
+            }) => this_ty = Some((*param.ty).clone()),
+            _ => params.push(param.clone()),
+        }
+    }
+
+    (this_ty.unwrap_or_else(|| Type::any(span, Default::default())), params)
+}
+
+fn param(span: Span, name: &str, ty: Type, required: bool) -> FnParam {
+    FnParam {
+        span,
+        required,
+        pat: RPat::Ident(RBindingIdent {
+            node_id: NodeId::invalid(),
+            id: RIdent::new(name.into(), span),
+            type_ann: None,
+        }),
+        ty: box ty,
+    }
+}
+
+/// `(thisArg: T, ...args: A) => R`
+fn fn_call(span: Span, f: &Function) -> Type {
+    let (this_ty, params) = split_this_param(span, f);
+
+    Type::Function(Function {
+        span,
+        type_params: f.type_params.clone(),
+        params: vec![param(span, "thisArg", this_ty, true)].into_iter().chain(params).collect(),
+        ret_ty: f.ret_ty.clone(),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+/// `(thisArg: T, args: A) => R`
+fn fn_apply(span: Span, f: &Function) -> Type {
+    let (this_ty, params) = split_this_param(span, f);
+
+    let args_required = params.iter().any(|p| p.required && !matches!(p.pat, RPat::Rest(..)));
+    let args = Type::Tuple(Tuple {
+        span,
+        elems: params.iter().map(TupleElement::from_param).collect(),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    });
+
+    Type::Function(Function {
+        span,
+        type_params: f.type_params.clone(),
+        params: vec![param(span, "thisArg", this_ty, true), param(span, "args", args, args_required)],
+        ret_ty: f.ret_ty.clone(),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+/// An overload for each number of bound arguments, like
+/// `(thisArg: T, arg0: A0) => (...args: A) => R`.
+///
+/// Generic functions can only be bound to `this`, as tsc does not
+/// instantiate type parameters for partial application.
+fn fn_bind(span: Span, f: &Function) -> Type {
+    let (this_ty, params) = split_this_param(span, f);
+
+    let max_bound = if f.type_params.is_some() {
+        0
+    } else {
+        params.iter().take_while(|p| !matches!(p.pat, RPat::Rest(..))).count()
+    };
+
+    let members = (0..=max_bound)
+        .map(|bound| {
+            let bound_fn = Type::Function(Function {
+                span,
+                type_params: f.type_params.clone(),
+                params: params[bound..].to_vec(),
+                ret_ty: f.ret_ty.clone(),
+                metadata: Default::default(),
+                tracker: Default::default(),
+            });
+
+            TypeElement::Call(CallSignature {
+                span,
+                params: vec![param(span, "thisArg", this_ty.clone(), true)]
+                    .into_iter()
+                    .chain(params[..bound].iter().cloned())
+                    .collect(),
+                type_params: None,
+                ret_ty: Some(box bound_fn),
+            })
+        })
+        .collect();
+
+    Type::TypeLit(TypeLit {
+        span,
+        members,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
@@ -12,15 +12,15 @@ use stc_ts_ast_rnode::{
 use stc_ts_env::MarkExt;
 use stc_ts_errors::{
     debug::{dump_type_as_string, dump_type_map, force_dump_type_as_string, print_type},
-    DebugExt, ErrorKind,
+    DebugExt, Error, ErrorKind,
 };
 use stc_ts_file_analyzer_macros::extra_validator;
-use stc_ts_generics::type_param::finder::TypeParamUsageFinder;
+use stc_ts_generics::type_param::finder::{uses_type_param, TypeParamUsageFinder};
 use stc_ts_type_ops::{generalization::prevent_generalize, is_str_lit_or_union, Fix};
 use stc_ts_types::{
     type_id::SymbolId, Alias, Array, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, Function, Id, IdCtx,
-    IndexedAccessType, Instance, Interface, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, QueryExpr, QueryType, Ref,
-    StaticThis, Symbol, Union, UnionMetadata,
+    IndexedAccessType, Instance, Interface, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, OptionalType, QueryExpr,
+    QueryType, Ref, RestType, StaticThis, Symbol, Tuple, TupleElement, Union, UnionMetadata,
 };
 use stc_ts_utils::PatExt;
 use stc_utils::{cache::Freeze, ext::TypeVecExt};
@@ -33,8 +33,10 @@ use ty::TypeExt;
 use crate::{
     analyzer::{
         assign::AssignOpts,
-        expr::TypeOfMode,
+        decl_merging::without_namespaces,
+        expr::{GetIteratorOpts, TypeOfMode},
         generic::InferTypeOpts,
+        import::require_specifier,
         types::NormalizeTypeOpts,
         util::{make_instance_type, ResultExt},
         Analyzer, Ctx, ScopeKind,
@@ -113,7 +115,7 @@ impl Analyzer<'_, '_> {
             RCallee::Import(..) => {
                 return Err(ErrorKind::Unimplemented {
                     span: e.span,
-                    msg: "validation of dynamic import".to_string(),
+                    msg: "validation of dynamic import".into(),
                 }
                 .into())
             }
@@ -261,35 +263,14 @@ impl Analyzer<'_, '_> {
 
         match *callee {
             RExpr::Ident(ref i) if i.sym == js_word!("require") => {
-                let id = args
-                    .iter()
-                    .cloned()
-                    .map(|arg| match arg {
-                        RExprOrSpread { spread: None, expr } => match *expr {
-                            RExpr::Lit(RLit::Str(RStr { span, value, .. })) => RIdent::new(value, span).into(),
-                            _ => unimplemented!("dynamic import: require()"),
-                        },
-                        _ => unimplemented!("error reporting: spread element in require()"),
-                    })
-                    .next()
-                    .unwrap();
-                if let Some(dep) = self.find_imported_var(&id)? {
-                    let dep = dep;
-                    unimplemented!("dep: {:#?}", dep);
-                }
-
-                // if let Some(Type::Enum(ref e)) = self.scope.find_type(&i.into()) {
-                //     return Ok(RTsType::TsTypeRef(RTsTypeRef {
-                //         span,
-                //         type_name: RTsEntityName::Ident(i.clone()),
-                //         type_params: None,
-                //     })
-                //     .into());
-                // }
-                Err(ErrorKind::UndefinedSymbol {
-                    sym: i.into(),
-                    span: i.span(),
-                })?
+                if let Some(src) = require_specifier(args) {
+                    if let Some(dep) = self.find_required_module(&src) {
+                        return Ok(dep);
+                    }
+                }
+
+                // Otherwise `require` is called like other functions, which is
+                // declared by `@types/node`.
             }
 
             _ => {}
@@ -378,27 +359,45 @@ impl Analyzer<'_, '_> {
                     _ => obj_type,
                 };
 
+                if args.iter().any(|arg| is_context_sensitive(&arg.expr)) {
+                    if let Ok(callee) = self.access_property(span, &obj_type, &prop, TypeOfMode::RValue, IdCtx::Var, Default::default()) {
+                        self.apply_type_ann_from_callee(span, kind, args, &callee)?;
+                    }
+                }
+
                 let mut arg_types = self.validate_args(args)?;
                 arg_types.freeze();
 
                 let spread_arg_types = self.spread_args(&arg_types).context("tried to handle spreads in arguments")?;
 
-                return self
-                    .call_property(
-                        span,
-                        kind,
-                        expr,
-                        &obj_type,
-                        &obj_type,
-                        &prop,
-                        type_args.as_ref(),
-                        args,
-                        &arg_types,
-                        &spread_arg_types,
-                        type_ann,
-                        Default::default(),
-                    )
-                    .map(|ty| ty.fixed());
+                let ret_ty = self.call_property(
+                    span,
+                    kind,
+                    expr,
+                    &obj_type,
+                    &obj_type,
+                    &prop,
+                    type_args.as_ref(),
+                    args,
+                    &arg_types,
+                    &spread_arg_types,
+                    type_ann,
+                    Default::default(),
+                )?;
+
+                // The call is validated above, so errors for arguments are still reported.
+                if kind == ExtractKind::Call && self.rule().precise_object_methods {
+                    if let RExpr::Ident(RIdent {
+                        sym: js_word!("Object"), ..
+                    }) = &**obj
+                    {
+                        if let Some(ty) = self.call_object_static_method(span, &prop, &arg_types)? {
+                            return Ok(ty.fixed());
+                        }
+                    }
+                }
+
+                return Ok(ret_ty.fixed());
             }
             _ => {}
         }
@@ -1264,11 +1263,9 @@ impl Analyzer<'_, '_> {
                         .context("tried to expand ref to handle a spread argument")?;
                     match arg_ty.normalize() {
                         Type::Tuple(arg_ty) => {
-                            new_arg_types.extend(arg_ty.elems.iter().map(|element| &element.ty).cloned().map(|ty| TypeOrSpread {
-                                span: arg.spread.unwrap(),
-                                spread: None,
-                                ty,
-                            }));
+                            if expand_tuple_spread(arg.spread.unwrap(), arg_ty, &mut new_arg_types) {
+                                self.scope.is_call_arg_count_unknown = true;
+                            }
                         }
 
                         Type::Keyword(KeywordType {
@@ -1293,7 +1290,15 @@ impl Analyzer<'_, '_> {
                             self.scope.is_call_arg_count_unknown = true;
 
                             let elem_type = self
-                                .get_iterator_element_type(arg.span(), arg_ty, false, Default::default())
+                                .get_iterator_element_type(
+                                    arg.span(),
+                                    arg_ty,
+                                    false,
+                                    GetIteratorOpts {
+                                        check_downlevel_iteration: true,
+                                        ..Default::default()
+                                    },
+                                )
                                 .context("tried to get element type of an iterator for spread syntax in arguments")?;
 
                             new_arg_types.push(TypeOrSpread {
@@ -1341,6 +1346,13 @@ impl Analyzer<'_, '_> {
                 return self.extract(span, expr, &ty, kind, args, arg_types, spread_arg_types, type_args, type_ann, opts);
             }
 
+            // Namespaces merged with a function or a class are not callable.
+            Type::Intersection(..) => {
+                if let Some(ty) = without_namespaces(ty) {
+                    return self.extract(span, expr, &ty, kind, args, arg_types, spread_arg_types, type_args, type_ann, opts);
+                }
+            }
+
             _ => {}
         }
 
@@ -1434,6 +1446,7 @@ impl Analyzer<'_, '_> {
                             arg_types,
                             spread_arg_types,
                         )
+                        .0
                     });
 
                     if let Some(constructor) = constructors.first() {
@@ -2256,14 +2269,16 @@ impl Analyzer<'_, '_> {
             match &param.pat {
                 RPat::Rest(..) => match param.ty.normalize_instance() {
                     Type::Tuple(param_ty) => {
+                        // Required elements of `...args: [string, ...number[], boolean]`
+                        let mut required = 0;
                         for elem in &param_ty.elems {
                             match elem.ty.normalize() {
                                 Type::Rest(..) => {
                                     max_param = None;
-                                    break;
                                 }
                                 Type::Optional(..) => {}
                                 _ => {
+                                    required += 1;
                                     if let Some(max) = &mut max_param {
                                         *max += 1;
                                     }
@@ -2273,6 +2288,7 @@ impl Analyzer<'_, '_> {
                         if let Some(max) = &mut max_param {
                             *max -= 1;
                         }
+                        min_param = min_param - count_required_pat(&param.pat) + required;
                         continue;
                     }
                     _ => {
@@ -2320,51 +2336,71 @@ impl Analyzer<'_, '_> {
         }
 
         let has_spread = args.iter().any(|arg| arg.spread.is_some());
-        if has_spread {
-            // TODO
-            Ok(())
-        } else {
-            if min_param <= args.len() {
-                if let Some(max) = max_param {
-                    if args.len() <= max {
+        let arg_count = if has_spread {
+            // `any` can be spread to any parameter.
+            if arg_types.iter().any(|arg| arg.spread.is_some() && arg.ty.is_any()) {
+                return Ok(());
+            }
+
+            // Spread arguments of non-variadic tuple types are expanded by `spread_args`.
+            match spread_arg_types.iter().find(|arg| arg.spread.is_some()) {
+                Some(variadic) => {
+                    // Parameters before a rest parameter are validated by `validate_arg_types`.
+                    if params.iter().any(|param| matches!(param.pat, RPat::Rest(..))) {
                         return Ok(());
                     }
-                } else {
-                    return Ok(());
+
+                    return Err(ErrorKind::SpreadMustBeTupleOrPassedToRest { span: variadic.span }.into());
                 }
+                None => spread_arg_types.len(),
             }
+        } else {
+            args.len()
+        };
 
-            // For iife, not providing some arguments are allowed.
-            if self.ctx.is_calling_iife {
-                if let Some(max) = max_param {
-                    if args.len() <= max {
-                        return Ok(());
-                    }
+        if min_param <= arg_count {
+            if let Some(max) = max_param {
+                if arg_count <= max {
+                    return Ok(());
                 }
+            } else {
+                return Ok(());
             }
+        }
 
-            if max_param.is_none() {
-                return Err(ErrorKind::ExpectedAtLeastNArgsButGotM { span, min: min_param }.into());
+        // For iife, not providing some arguments are allowed.
+        if self.ctx.is_calling_iife {
+            if let Some(max) = max_param {
+                if arg_count <= max {
+                    return Ok(());
+                }
             }
+        }
+
+        if max_param.is_none() {
+            return Err(ErrorKind::ExpectedAtLeastNArgsButGotM { span, min: min_param }.into());
+        }
 
-            // function foo(a) {}
-            // foo(1, 2, 3)
-            //        ^^^^
-            let span = args
-                .get(min_param)
+        // function foo(a) {}
+        // foo(1, 2, 3)
+        //        ^^^^
+        let span = if has_spread {
+            args.last().map(|arg| arg.expr.span()).unwrap_or(span)
+        } else {
+            args.get(min_param)
                 .map(|arg| match args.last() {
                     Some(to) => arg.expr.span().to(to.expr.span()),
                     None => arg.expr.span(),
                 })
-                .unwrap_or(span);
+                .unwrap_or(span)
+        };
 
-            Err(ErrorKind::ExpectedNArgsButGotM {
-                span,
-                min: min_param,
-                max: max_param,
-            }
-            .into())
+        Err(ErrorKind::ExpectedNArgsButGotM {
+            span,
+            min: min_param,
+            max: max_param,
         }
+        .into())
     }
 
     /// Returns [None] if nothing matched.
@@ -2389,10 +2425,10 @@ impl Analyzer<'_, '_> {
 
         let span = span.with_ctxt(SyntaxContext::empty());
 
-        let mut callable = candidates
+        let callable = candidates
             .iter()
             .map(|c| {
-                let res = self.check_call_args(
+                let (res, err) = self.check_call_args(
                     span,
                     c.type_params.as_deref(),
                     &c.params,
@@ -2402,10 +2438,9 @@ impl Analyzer<'_, '_> {
                     spread_arg_types,
                 );
 
-                (c, res)
+                (c, res, err)
             })
             .collect::<Vec<_>>();
-        callable.sort_by_key(|(_, res)| *res);
 
         if candidates.is_empty() {
             return Ok(None);
@@ -2418,12 +2453,22 @@ impl Analyzer<'_, '_> {
             && callable.len() > 1
             && callable
                 .iter()
-                .all(|(_, res)| matches!(res, ArgCheckResult::WrongArgCount | ArgCheckResult::ArgTypeMismatch))
+                .all(|(_, res, _)| matches!(res, ArgCheckResult::WrongArgCount | ArgCheckResult::ArgTypeMismatch))
         {
-            return Err(ErrorKind::NoMatchingOverload { span }.context("tried to select a call candidate"));
+            let causes = callable.into_iter().filter_map(|(.., err)| err).collect();
+
+            return Err(ErrorKind::NoMatchingOverload { span, causes }.context("tried to select a call candidate"));
         }
 
-        let (c, _) = callable.into_iter().next().unwrap();
+        // Like `tsc`, the first applicable signature in declaration order is
+        // selected. If no signature is applicable, the best failing one is used to
+        // report errors.
+        let c = callable
+            .iter()
+            .find(|(_, res, _)| matches!(res, ArgCheckResult::Exact | ArgCheckResult::MayBe))
+            .or_else(|| callable.iter().min_by_key(|(_, res, _)| *res))
+            .map(|(c, ..)| *c)
+            .unwrap();
 
         if candidates.len() == 1 {
             return self
@@ -2459,6 +2504,95 @@ impl Analyzer<'_, '_> {
         .map(Some)
     }
 
+    /// Prepares generic functions passed as arguments for higher-order
+    /// inference.
+    ///
+    /// If the callee returns a non-generic function, type parameters of a
+    /// generic function passed where a non-generic function is expected are
+    /// propagated to the return type instead of being instantiated, so
+    /// `pipe(list, box)` becomes `<T>(a: T) => Box<T[]>`.
+    ///
+    /// Type parameters of later arguments are instantiated using the types
+    /// inferred from the previous arguments, if possible.
+    ///
+    /// Returns the arguments without the propagated type parameter
+    /// declarations, and the propagated type parameters.
+    fn prepare_higher_order_args(
+        &mut self,
+        span: Span,
+        type_params: &[TypeParam],
+        params: &[FnParam],
+        ret_ty: &Type,
+        args: &[TypeOrSpread],
+    ) -> VResult<Option<(Vec<TypeOrSpread>, Vec<TypeParam>)>> {
+        if !matches!(ret_ty.normalize(), Type::Function(Function { type_params: None, .. })) {
+            return Ok(None);
+        }
+
+        let mut new_args = args.to_vec();
+        let mut propagated = vec![];
+        let mut changed = false;
+
+        for idx in 0..new_args.len() {
+            if new_args[idx].spread.is_some() {
+                break;
+            }
+
+            let param_ty = match params.get(idx) {
+                Some(param) if !matches!(param.pat, RPat::Rest(..)) => &param.ty,
+                _ => break,
+            };
+            if !matches!(param_ty.normalize(), Type::Function(Function { type_params: None, .. })) {
+                continue;
+            }
+
+            let (arg_type_params, arg_fn) = match new_args[idx].ty.normalize() {
+                Type::Function(f) => match &f.type_params {
+                    Some(decl) => (
+                        decl.params.clone(),
+                        Type::Function(Function {
+                            type_params: None,
+                            ..f.clone()
+                        }),
+                    ),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let contextual = if idx == 0 {
+                (**param_ty).clone()
+            } else {
+                let partial = self.infer_arg_types(span, None, type_params, params, &new_args[..idx], None, Default::default())?;
+                self.expand_type_params(&partial.types, (**param_ty).clone(), Default::default())?
+            };
+
+            // Instantiations referring to type parameters of the callee are not known yet.
+            let map = self
+                .infer_type_with_types(span, &arg_type_params, &arg_fn, &contextual, Default::default())?
+                .into_iter()
+                .filter(|(_, ty)| !ty.is_unknown() && !uses_type_param(ty, |used| type_params.iter().any(|p| p.name == *used)))
+                .collect::<FxHashMap<_, _>>();
+
+            let arg_fn = self.expand_type_params(&map, arg_fn, Default::default())?.freezed();
+
+            propagated.extend(arg_type_params.into_iter().filter(|p| !map.contains_key(&p.name)));
+            new_args[idx] = TypeOrSpread {
+                ty: box arg_fn,
+                ..new_args[idx].clone()
+            };
+            changed = true;
+        }
+
+        if !changed {
+            return Ok(None);
+        }
+
+        new_args.freeze();
+
+        Ok(Some((new_args, propagated)))
+    }
+
     /// Returns the return type of function. This method should be called only
     /// for final step because it emits errors instead of returning them.
     ///
@@ -2616,13 +2750,19 @@ impl Analyzer<'_, '_> {
                 let _ = spread_arg_types.to_vec();
             }
 
+            let higher_order = if type_args.is_none() {
+                self.prepare_higher_order_args(span, type_params, &params, &ret_ty, spread_arg_types)?
+            } else {
+                None
+            };
+
             debug!("Inferring arg types for a call");
             let mut inferred = self.infer_arg_types(
                 span,
                 type_args,
                 type_params,
                 &params,
-                spread_arg_types,
+                higher_order.as_ref().map_or(spread_arg_types, |(args, _)| &**args),
                 None,
                 InferTypeOpts {
                     is_type_ann: type_ann.is_some(),
@@ -2779,11 +2919,7 @@ impl Analyzer<'_, '_> {
                     if arg.spread.is_some() {
                         match arg.ty.normalize() {
                             Type::Tuple(arg_ty) => {
-                                new_arg_types.extend(arg_ty.elems.iter().map(|element| &element.ty).cloned().map(|ty| TypeOrSpread {
-                                    span: arg.spread.unwrap(),
-                                    spread: None,
-                                    ty,
-                                }));
+                                expand_tuple_spread(arg.spread.unwrap(), arg_ty, &mut new_arg_types);
                             }
                             _ => {
                                 new_arg_types.push(arg.clone());
@@ -2882,6 +3018,24 @@ impl Analyzer<'_, '_> {
 
             self.add_required_type_params(&mut ty);
 
+            if let Some((_, propagated)) = &higher_order {
+                if let Type::Function(Function {
+                    type_params: Some(decl), ..
+                }) = &mut ty
+                {
+                    for param in decl.params.iter_mut() {
+                        if let Some(orig) = propagated.iter().find(|p| p.name == param.name) {
+                            *param = orig.clone();
+                        }
+                    }
+                }
+
+                // Propagated type parameters are not defaulted to `unknown`.
+                for param in propagated {
+                    default_unknown_map.remove(&param.name);
+                }
+            }
+
             print_type("Return, after adding type params", &ty);
 
             if type_ann.is_none() {
@@ -2919,6 +3073,92 @@ impl Analyzer<'_, '_> {
         Ok(ret_ty)
     }
 
+    /// Validates arguments passed to a rest parameter of a tuple type.
+    ///
+    /// Handles
+    ///
+    ///   param: (...x: [boolean, string, ...number[], boolean])
+    ///   arg: (true, 'str', 1, 2, false)
+    ///      or
+    ///   arg: (true, 'str', ...nums, false)
+    fn validate_rest_tuple_args(&mut self, elems: &[TupleElement], args: &[&TypeOrSpread], is_generic: bool) {
+        fn elem_ty(ty: &Type) -> &Type {
+            match ty.normalize() {
+                Type::Optional(OptionalType { ty, .. }) => &**ty,
+                _ => ty,
+            }
+        }
+
+        let rest_idx = elems.iter().position(|elem| matches!(elem.ty.normalize(), Type::Rest(..)));
+        let (head, tail) = match rest_idx {
+            Some(idx) => (&elems[..idx], &elems[idx + 1..]),
+            None => (elems, &[][..]),
+        };
+
+        let mut pairs = vec![];
+        let mut args = args;
+
+        for elem in head {
+            match args.split_first() {
+                Some((arg, rest)) if arg.spread.is_none() => {
+                    pairs.push((elem_ty(&elem.ty), *arg));
+                    args = rest;
+                }
+                // The position of elements after a variadic spread is not known.
+                Some(..) => {
+                    args = &[];
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        if let Some(Type::Rest(RestType { ty: rest_ty, .. })) = rest_idx.map(|idx| elems[idx].ty.normalize()) {
+            let tail_len = tail.len().min(args.len());
+            let (middle, trailing) = args.split_at(args.len() - tail_len);
+
+            for arg in middle {
+                if arg.spread.is_some() {
+                    pairs.push((&**rest_ty, *arg));
+                } else if let Type::Array(arr) = rest_ty.normalize() {
+                    pairs.push((&*arr.elem_type, *arg));
+                }
+            }
+
+            if trailing.iter().all(|arg| arg.spread.is_none()) {
+                for (elem, arg) in tail[tail.len() - tail_len..].iter().zip(trailing) {
+                    pairs.push((elem_ty(&elem.ty), *arg));
+                }
+            }
+        }
+
+        for (param_ty, arg) in pairs {
+            let res = self
+                .assign_with_opts(
+                    &mut Default::default(),
+                    param_ty,
+                    &arg.ty,
+                    AssignOpts {
+                        span: arg.span,
+                        allow_iterable_on_rhs: true,
+                        ..Default::default()
+                    },
+                )
+                .convert_err(|err| ErrorKind::WrongArgType {
+                    span: arg.span,
+                    inner: box err.into(),
+                })
+                .context("tried to assign to element of a tuple type of a parameter");
+
+            if let Err(err) = res {
+                self.storage.report(err);
+                if is_generic {
+                    return;
+                }
+            }
+        }
+    }
+
     fn validate_arg_types(&mut self, params: &[FnParam], spread_arg_types: &[TypeOrSpread], is_generic: bool) {
         info!("[exprs] Validating arguments");
 
@@ -3013,79 +3253,14 @@ impl Analyzer<'_, '_> {
                     //      or
                     //   arg: (true, 'str', 10)
                     if arg.spread.is_none() {
-                        match param_ty.normalize() {
-                            Type::Tuple(param_ty) if !param_ty.elems.is_empty() => {
-                                let res = self
-                                    .assign_with_opts(
-                                        &mut Default::default(),
-                                        &param_ty.elems[0].ty,
-                                        &arg.ty,
-                                        AssignOpts {
-                                            span: arg.span(),
-                                            allow_iterable_on_rhs: true,
-                                            ..Default::default()
-                                        },
-                                    )
-                                    .map_err(|err| {
-                                        ErrorKind::WrongArgType {
-                                            span: arg.span(),
-                                            inner: box err,
-                                        }
-                                        .into()
-                                    })
-                                    .context("tried to assign to first element of a tuple type of a parameter");
-
-                                match res {
-                                    Ok(_) => {}
-                                    Err(err) => {
-                                        report_err!(err);
-                                        continue;
-                                    }
-                                };
-
-                                for param_elem in param_ty.elems.iter().skip(1) {
-                                    let arg = match args_iter.next() {
-                                        Some(v) => v,
-                                        None => {
-                                            // TODO(kdy1): Argument count
-                                            break;
-                                        }
-                                    };
-
-                                    // TODO(kdy1): Check if arg.spread is none.
-                                    // The logic below is correct only if the arg is not
-                                    // spread.
-
-                                    let res = self
-                                        .assign_with_opts(
-                                            &mut Default::default(),
-                                            &param_elem.ty,
-                                            &arg.ty,
-                                            AssignOpts {
-                                                span: arg.span(),
-                                                allow_iterable_on_rhs: true,
-                                                ..Default::default()
-                                            },
-                                        )
-                                        .convert_err(|err| ErrorKind::WrongArgType {
-                                            span: arg.span(),
-                                            inner: box err.into(),
-                                        })
-                                        .context("tried to assign to element of a tuple type of a parameter");
-
-                                    match res {
-                                        Ok(_) => {}
-                                        Err(err) => {
-                                            report_err!(err);
-                                            continue;
-                                        }
-                                    };
-                                }
+                        if let Type::Tuple(param_ty) = param_ty.normalize() {
+                            if !param_ty.elems.is_empty() {
+                                let args = std::iter::once(arg).chain(args_iter.by_ref()).collect_vec();
+                                self.validate_rest_tuple_args(&param_ty.elems, &args, is_generic);
 
                                 // Skip default type checking logic.
                                 continue;
                             }
-                            _ => {}
                         }
                     }
 
@@ -3450,14 +3625,19 @@ impl Analyzer<'_, '_> {
         res.is_ok()
     }
 
-    /// This method return [Err] if call is invalid
+    /// Checks if a signature is applicable to the arguments.
     ///
+    /// Returns the reason of the failure along with the result if the
+    /// signature is not applicable.
     ///
     /// # Implementation notes
     ///
     /// `anyAssignabilityInInheritance.ts` says `any, not a subtype of number so
     /// it skips that overload, is a subtype of itself so it picks second (if
     /// truly ambiguous it would pick first overload)`
+    ///
+    /// Type parameters of generic signatures are inferred for each signature
+    /// separately, so `<T>(a: T, b: T)` does not match `(1, '')`.
     fn check_call_args(
         &mut self,
         span: Span,
@@ -3467,19 +3647,19 @@ impl Analyzer<'_, '_> {
         args: &[RExprOrSpread],
         arg_types: &[TypeOrSpread],
         spread_arg_types: &[TypeOrSpread],
-    ) -> ArgCheckResult {
+    ) -> (ArgCheckResult, Option<Error>) {
         let _tracing = if cfg!(debug_assertions) {
             Some(tracing::span!(tracing::Level::ERROR, "check_call_args").entered())
         } else {
             None
         };
 
-        if self.validate_type_args_count(span, type_params, type_args).is_err() {
-            return ArgCheckResult::WrongArgCount;
+        if let Err(err) = self.validate_type_args_count(span, type_params, type_args) {
+            return (ArgCheckResult::WrongArgCount, Some(err));
         }
 
-        if self.validate_arg_count(span, params, args, arg_types, spread_arg_types).is_err() {
-            return ArgCheckResult::WrongArgCount;
+        if let Err(err) = self.validate_arg_count(span, params, args, arg_types, spread_arg_types) {
+            return (ArgCheckResult::WrongArgCount, Some(err));
         }
 
         self.with_scope_for_type_params(|analyzer: &mut Analyzer| {
@@ -3489,36 +3669,42 @@ impl Analyzer<'_, '_> {
                 }
             }
 
-            let mut exact = true;
+            let params = match type_params {
+                Some(type_params) if !type_params.is_empty() => {
+                    match analyzer.instantiate_params_for_overload(span, type_params, params, type_args, spread_arg_types) {
+                        Ok(Some(params)) => Cow::Owned(params),
+                        Ok(None) => Cow::Borrowed(params),
+                        Err(err) => return (ArgCheckResult::ArgTypeMismatch, Some(err)),
+                    }
+                }
+                _ => Cow::Borrowed(params),
+            };
 
-            for (arg, param) in arg_types.iter().zip(params) {
-                // match arg.ty.normalize() {
-                //     Type::Union(..) => match param.ty.normalize() {
-                //         Type::Keyword(..) => if self.assign(&param.ty, &arg.ty, span).is_ok()
-                // {},         _ => {}
-                //     },
-                //     _ => {}
-                // }
+            let mut exact = true;
 
+            // Positions of arguments after a variadic spread are not known, and
+            // arguments for a rest parameter are checked by `validate_arg_types`.
+            for (arg, param) in spread_arg_types
+                .iter()
+                .zip(params.iter())
+                .take_while(|(arg, param)| arg.spread.is_none() && !matches!(param.pat, RPat::Rest(..)))
+            {
                 match param.ty.normalize() {
                     Type::Param(..) => {}
                     Type::Instance(param) if param.ty.is_type_param() => {}
                     _ => {
-                        if analyzer
-                            .assign_with_opts(
-                                &mut Default::default(),
-                                &param.ty,
-                                &arg.ty,
-                                AssignOpts {
-                                    span,
-                                    allow_unknown_rhs: Some(true),
-                                    allow_assignment_to_param: true,
-                                    ..Default::default()
-                                },
-                            )
-                            .is_err()
-                        {
-                            return ArgCheckResult::ArgTypeMismatch;
+                        if let Err(err) = analyzer.assign_with_opts(
+                            &mut Default::default(),
+                            &param.ty,
+                            &arg.ty,
+                            AssignOpts {
+                                span: arg.span,
+                                allow_unknown_rhs: Some(true),
+                                allow_assignment_to_param: true,
+                                ..Default::default()
+                            },
+                        ) {
+                            return (ArgCheckResult::ArgTypeMismatch, Some(err));
                         }
 
                         if !analyzer.is_subtype_in_fn_call(span, &arg.ty, &param.ty) {
@@ -3529,36 +3715,102 @@ impl Analyzer<'_, '_> {
             }
 
             if analyzer.scope.is_call_arg_count_unknown || !exact {
-                return ArgCheckResult::MayBe;
+                return (ArgCheckResult::MayBe, None);
             }
 
-            ArgCheckResult::Exact
+            (ArgCheckResult::Exact, None)
         })
     }
 
-    fn apply_type_ann_from_callee(&mut self, span: Span, kind: ExtractKind, args: &[RExprOrSpread], callee: &Type) -> VResult<()> {
-        let c = self.extract_callee_candidates(span, kind, callee)?;
+    /// Infers type arguments of a generic overload from the arguments and
+    /// returns the instantiated parameters.
+    ///
+    /// Returns [None] if inference failed, in which case the caller should
+    /// check the arguments against the generic parameters. Returns [Err] if an
+    /// inferred type does not satisfy the constraint of the type parameter.
+    fn instantiate_params_for_overload(
+        &mut self,
+        span: Span,
+        type_params: &[TypeParam],
+        params: &[FnParam],
+        type_args: Option<&TypeParamInstantiation>,
+        spread_arg_types: &[TypeOrSpread],
+    ) -> VResult<Option<Vec<FnParam>>> {
+        let inferred = match self.infer_arg_types(span, type_args, type_params, params, spread_arg_types, None, Default::default()) {
+            Ok(inferred) if inferred.errored.is_empty() => inferred,
+            _ => return Ok(None),
+        };
 
-        if c.len() != 1 {
-            return Ok(());
+        for type_param in type_params {
+            let (constraint, ty) = match (&type_param.constraint, inferred.types.get(&type_param.name)) {
+                (Some(constraint), Some(ty)) => (constraint, ty),
+                _ => continue,
+            };
+            if ty.is_type_param() {
+                continue;
+            }
+
+            let constraint = self.expand_type_params(&inferred.types, *constraint.clone(), Default::default())?;
+
+            if self
+                .assign_with_opts(
+                    &mut Default::default(),
+                    &constraint,
+                    ty,
+                    AssignOpts {
+                        span,
+                        allow_assignment_to_param_constraint: true,
+                        ..Default::default()
+                    },
+                )
+                .is_err()
+            {
+                return Err(ErrorKind::NotSatisfyConstraint {
+                    span,
+                    left: box constraint,
+                    right: box ty.clone(),
+                }
+                .into());
+            }
         }
 
-        let c = c.into_iter().next().unwrap();
+        let params = params
+            .iter()
+            .map(|param| -> VResult<_> {
+                let ty = box self.expand_type_params(&inferred.types, *param.ty.clone(), Default::default())?;
 
-        // TODO(kdy1): Refactor generic inference logic to use this function.
-        // Currently, the reevaluation logic in get_return_type interferes with this
-        // function
-        if c.type_params.is_some() {
+                Ok(FnParam { ty, ..param.clone() })
+            })
+            .collect::<VResult<Vec<_>>>();
+
+        Ok(params.ok())
+    }
+
+    /// Applies the parameter types of the callee to function expressions in
+    /// arguments, so `arr.map(x => x.length)` does not need annotations.
+    ///
+    /// Parameter types which depend on type parameters of the callee are
+    /// handled by the reevaluation logic of [Self::get_return_type], because
+    /// they should be inferred from context-insensitive arguments first.
+    fn apply_type_ann_from_callee(&mut self, span: Span, kind: ExtractKind, args: &[RExprOrSpread], callee: &Type) -> VResult<()> {
+        if !args.iter().any(|arg| is_context_sensitive(&arg.expr)) {
             return Ok(());
         }
 
-        for (arg, param) in args.iter().zip(c.params.iter()) {
-            // TODO(kdy1):  Handle rest
-            if arg.spread.is_some() || matches!(param.pat, RPat::Rest(..)) {
+        let candidates = self.extract_callee_candidates(span, kind, callee)?;
+
+        for (idx, arg) in args.iter().enumerate() {
+            if arg.spread.is_some() {
                 break;
             }
 
-            self.apply_type_ann_to_expr(&arg.expr, &param.ty)?;
+            if !is_context_sensitive(&arg.expr) {
+                continue;
+            }
+
+            if let Some(ty) = contextual_type_of_arg(&candidates, args.len(), idx) {
+                self.apply_type_ann_to_expr(&arg.expr, ty)?;
+            }
         }
 
         Ok(())
@@ -3774,6 +4026,83 @@ impl VisitMut<Type> for ReturnTypeSimplifier<'_, '_, '_> {
     }
 }
 
+/// Returns `true` if `e` is a function expression with a parameter without a
+/// type annotation.
+fn is_context_sensitive(e: &RExpr) -> bool {
+    match e {
+        RExpr::Paren(e) => is_context_sensitive(&e.expr),
+        RExpr::Fn(f) => f.function.params.iter().any(|p| p.pat.get_ty().is_none()),
+        RExpr::Arrow(f) => f.params.iter().any(|p| p.get_ty().is_none()),
+        _ => false,
+    }
+}
+
+/// Returns the contextual type of the argument at `idx`.
+///
+/// If there are multiple candidates, the candidates accepting `arg_count`
+/// arguments should agree on the parameter types of the callback.
+fn contextual_type_of_arg(candidates: &[CallCandidate], arg_count: usize, idx: usize) -> Option<&Type> {
+    let mut types = candidates
+        .iter()
+        .filter(|c| {
+            let has_rest = c.params.iter().any(|p| matches!(p.pat, RPat::Rest(..)));
+            has_rest || c.params.len() >= arg_count
+        })
+        .map(|c| {
+            let ty = param_type_at(&c.params, idx)?;
+
+            if let Some(type_params) = &c.type_params {
+                if uses_type_param(ty, |used| type_params.iter().any(|p| p.name == *used)) {
+                    return None;
+                }
+            }
+
+            Some(ty)
+        });
+
+    let first = types.next()??;
+
+    for ty in types {
+        let ty = ty?;
+        if !ty.type_eq(first) && !have_same_params(ty, first) {
+            return None;
+        }
+    }
+
+    Some(first)
+}
+
+fn param_type_at(params: &[FnParam], idx: usize) -> Option<&Type> {
+    for (i, param) in params.iter().enumerate() {
+        if let RPat::Rest(..) = param.pat {
+            return match param.ty.normalize() {
+                Type::Array(arr) => Some(&*arr.elem_type),
+                _ => None,
+            };
+        }
+
+        if i == idx {
+            return Some(&*param.ty);
+        }
+    }
+
+    None
+}
+
+/// Returns `true` if both of `a` and `b` are functions with the same
+/// parameters.
+fn have_same_params(a: &Type, b: &Type) -> bool {
+    match (a.normalize(), b.normalize()) {
+        (Type::Function(a), Type::Function(b)) => {
+            a.type_params.is_none()
+                && b.type_params.is_none()
+                && a.params.len() == b.params.len()
+                && a.params.iter().zip(b.params.iter()).all(|(a, b)| a.ty.type_eq(&b.ty))
+        }
+        _ => false,
+    }
+}
+
 fn is_fn_expr(callee: &RExpr) -> bool {
     match callee {
         RExpr::Arrow(..) | RExpr::Fn(..) => true,
@@ -3782,6 +4111,42 @@ fn is_fn_expr(callee: &RExpr) -> bool {
     }
 }
 
+/// Expands a spread argument of a tuple type into an argument per element.
+///
+/// Optional elements are passed as the element type, and a rest element is
+/// kept as a spread of its array type.
+///
+/// Returns `true` if the tuple has a rest element, which means the number of
+/// arguments is not known.
+fn expand_tuple_spread(span: Span, tuple: &Tuple, new_args: &mut Vec<TypeOrSpread>) -> bool {
+    let mut is_variadic = false;
+
+    for elem in &tuple.elems {
+        match elem.ty.normalize() {
+            Type::Optional(OptionalType { ty, .. }) => new_args.push(TypeOrSpread {
+                span,
+                spread: None,
+                ty: ty.clone(),
+            }),
+            Type::Rest(RestType { ty, .. }) => {
+                is_variadic = true;
+                new_args.push(TypeOrSpread {
+                    span,
+                    spread: Some(span),
+                    ty: ty.clone(),
+                })
+            }
+            _ => new_args.push(TypeOrSpread {
+                span,
+                spread: None,
+                ty: elem.ty.clone(),
+            }),
+        }
+    }
+
+    is_variadic
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
 enum ArgCheckResult {
     Exact,
@@ -2,7 +2,10 @@ use stc_ts_ast_rnode::RTsConstAssertion;
 use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_file_analyzer_macros::validator;
 use stc_ts_type_ops::{generalization::prevent_generalize, tuple_to_array::prevent_tuple_to_array};
-use stc_ts_types::{Type, TypeParamInstantiation};
+use stc_ts_types::{Operator, Type, TypeElement, TypeParamInstantiation};
+use stc_ts_utils::MapWithMut;
+use swc_common::Spanned;
+use swc_ecma_ast::TsTypeOperatorOp;
 
 use crate::{
     analyzer::{expr::TypeOfMode, Analyzer, Ctx},
@@ -38,14 +41,46 @@ impl Analyzer<'_, '_> {
             prevent_generalize(&mut ty);
             prevent_tuple_to_array(&mut ty);
 
-            Ok(ty)
+            Ok(make_readonly(ty))
         } else {
             Err(ErrorKind::Unimplemented {
                 span,
-                msg: "Proper error reporting for using const assertion expression in left hand side of an assignment expression"
-                    .to_string(),
+                msg: "Proper error reporting for using const assertion expression in left hand side of an assignment expression".into(),
             }
             .into())
         }
     }
 }
+
+/// `as const` makes tuples and properties readonly, including nested ones.
+fn make_readonly(ty: Type) -> Type {
+    match ty.foldable() {
+        Type::Tuple(mut tuple) => {
+            for elem in &mut tuple.elems {
+                elem.ty.map_with_mut(make_readonly);
+            }
+
+            Type::Operator(Operator {
+                span: tuple.span,
+                op: TsTypeOperatorOp::ReadOnly,
+                ty: box Type::Tuple(tuple),
+                metadata: Default::default(),
+                tracker: Default::default(),
+            })
+        }
+        Type::TypeLit(mut lit) => {
+            for member in &mut lit.members {
+                if let TypeElement::Property(p) = member {
+                    p.readonly = true;
+
+                    if let Some(ty) = &mut p.type_ann {
+                        ty.map_with_mut(make_readonly);
+                    }
+                }
+            }
+
+            Type::TypeLit(lit)
+        }
+        ty => ty,
+    }
+}
@@ -1,12 +1,13 @@
 use std::borrow::Cow;
 
-use stc_ts_ast_rnode::{RArrowExpr, RBlockStmtOrExpr, RNumber, RPat};
+use stc_ts_ast_rnode::{RArrowExpr, RBlockStmtOrExpr, RIdent, RNumber, RPat, RTsEntityName};
 use stc_ts_types::{
-    type_id::DestructureId, Class, ClassMetadata, Function, Key, KeywordType, RestType, Tuple, TupleElement, Type, TypeParam, Union,
+    type_id::DestructureId, Class, ClassMetadata, Function, Key, KeywordType, Ref, RestType, Tuple, TupleElement, Type, TypeParam,
+    TypeParamInstantiation, Union,
 };
 use stc_ts_utils::PatExt;
 use stc_utils::cache::Freeze;
-use swc_common::{Span, Spanned};
+use swc_common::{Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::TsKeywordTypeKind;
 
 use super::call_new::ExtractKind;
@@ -26,6 +27,9 @@ impl Analyzer<'_, '_> {
         let type_ann = self.expand_type_ann(f.span, type_ann)?;
 
         self.with_child(ScopeKind::ArrowFn, Default::default(), |child: &mut Analyzer| {
+            child.ctx.in_async = f.is_async;
+            child.ctx.in_generator = false;
+
             let type_params = try_opt!(f.type_params.validate_with(child));
 
             let params = {
@@ -51,6 +55,11 @@ impl Analyzer<'_, '_> {
                 }
                 None => None,
             };
+            if f.is_async {
+                if let Some(ty) = &declared_ret_ty {
+                    child.check_return_type_of_async_fn(ty);
+                }
+            }
             let declared_ret_ty = match declared_ret_ty {
                 Some(ty) => {
                     let span = ty.span();
@@ -75,10 +84,29 @@ impl Analyzer<'_, '_> {
                 match f.body {
                     RBlockStmtOrExpr::Expr(ref e) => Some({
                         let ty = e.validate_with_args(child, (TypeOfMode::RValue, None, declared_ret_ty.as_ref()))?;
-                        if !child.ctx.in_argument && f.return_type.is_none() && type_ann.is_none() && child.may_generalize(&ty) {
+                        let ty = if !child.ctx.in_argument && f.return_type.is_none() && type_ann.is_none() && child.may_generalize(&ty) {
                             ty.generalize_lit()
                         } else {
                             ty
+                        };
+
+                        if f.is_async {
+                            // The body of an async arrow function is the returned value, which is
+                            // awaited.
+                            let ty = child.get_awaited_type(e.span(), Cow::Owned(ty.freezed()))?.into_owned();
+
+                            Type::Ref(Ref {
+                                span: ty.span(),
+                                type_name: RTsEntityName::Ident(RIdent::new("Promise".into(), DUMMY_SP)),
+                                type_args: Some(box TypeParamInstantiation {
+                                    span: ty.span(),
+                                    params: vec![ty],
+                                }),
+                                metadata: Default::default(),
+                                tracker: Default::default(),
+                            })
+                        } else {
+                            ty
                         }
                     }),
                     RBlockStmtOrExpr::BlockStmt(ref s) => child.visit_stmts_for_return(f.span, f.is_async, f.is_generator, &s.stmts)?,
@@ -145,6 +145,7 @@ impl Analyzer<'_, '_> {
                                     type_params: None,
                                     metadata: Default::default(),
                                     accessor: Default::default(),
+                                    write_type: Default::default(),
                                 }),
                             )?;
                         }
@@ -152,7 +153,7 @@ impl Analyzer<'_, '_> {
                     RJSXAttrName::JSXNamespacedName(attr_name) => {
                         return Err(ErrorKind::Unimplemented {
                             span: attr.span,
-                            msg: "namespaced name for an attribute".to_string(),
+                            msg: "namespaced name for an attribute".into(),
                         }
                         .into())
                     }
@@ -324,7 +325,7 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, e: &RJSXNamespacedName) -> VResult<Type> {
         Err(ErrorKind::Unimplemented {
             span: e.span(),
-            msg: "jsx namespaced name".to_string(),
+            msg: "jsx namespaced name".into(),
         }
         .into())
     }
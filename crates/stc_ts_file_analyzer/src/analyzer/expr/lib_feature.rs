@@ -0,0 +1,121 @@
+//! Errors for builtin members and globals declared only in libs which are
+//! not loaded, like `Array.prototype.at` without `es2022`.
+
+use stc_ts_ast_rnode::{RTsEntityName, RTsLit};
+use stc_ts_builtin_types::Lib;
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::{Id, Key, KeywordType, LitType, Ref, Type, TypeElement};
+use swc_atoms::JsWord;
+use swc_common::Span;
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    /// Replaces `err` with an error naming the lib which declares `prop`, if
+    /// `err` is caused by `prop` not being declared by the loaded libs.
+    pub(super) fn suggest_lib_for_property(&mut self, span: Span, obj: &Type, prop: &Key, err: Error) -> Error {
+        if self.config.is_builtin || !err.is_property_not_found() {
+            return err;
+        }
+
+        let sym = match prop {
+            Key::Normal { sym, .. } => sym,
+            _ => return err,
+        };
+
+        let interface = match builtin_interface_name(obj) {
+            Some(v) => v,
+            None => return err,
+        };
+
+        // The loaded libs may declare the interface with the property, and the
+        // error may come from something else.
+        if let Ok(ty) = self.env.get_global_type(span, &interface) {
+            if let Type::Interface(i) = ty.normalize() {
+                if i.body.iter().any(|member| match member {
+                    TypeElement::Property(p) => p.key == *sym,
+                    TypeElement::Method(m) => m.key == *sym,
+                    _ => false,
+                }) {
+                    return err;
+                }
+            }
+        }
+
+        match Lib::of_member(&interface, sym).and_then(Lib::feature_name) {
+            Some(lib) => ErrorKind::NoSuchPropertyInLib {
+                span,
+                prop: box prop.clone(),
+                lib: lib.into(),
+            }
+            .into(),
+            None => err,
+        }
+    }
+
+    /// Replaces `err` with an error naming the lib which declares `name`, if
+    /// `name` is a global declared only by a lib which is not loaded.
+    pub(super) fn suggest_lib_for_var(&mut self, span: Span, name: &Id, err: Error) -> Error {
+        if self.config.is_builtin || !err.is_var_not_found() {
+            return err;
+        }
+
+        match Lib::of_global(name.sym()).and_then(Lib::feature_name) {
+            Some(lib) => ErrorKind::NoSuchVarInLib {
+                span,
+                name: name.clone(),
+                lib: lib.into(),
+            }
+            .into(),
+            None => err,
+        }
+    }
+}
+
+/// Returns the name of the builtin interface which declares the members of
+/// `ty`.
+fn builtin_interface_name(ty: &Type) -> Option<JsWord> {
+    let name = match ty.normalize() {
+        Type::Ref(Ref {
+            type_name: RTsEntityName::Ident(i),
+            ..
+        }) => return Some(i.sym.clone()),
+        Type::Interface(i) => return Some(i.name.sym().clone()),
+
+        Type::Array(..) | Type::Tuple(..) => "Array",
+
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsStringKeyword,
+            ..
+        })
+        | Type::Lit(LitType { lit: RTsLit::Str(..), .. })
+        | Type::Tpl(..) => "String",
+
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsNumberKeyword,
+            ..
+        })
+        | Type::Lit(LitType {
+            lit: RTsLit::Number(..), ..
+        }) => "Number",
+
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsBigIntKeyword,
+            ..
+        })
+        | Type::Lit(LitType {
+            lit: RTsLit::BigInt(..), ..
+        }) => "BigInt",
+
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsSymbolKeyword,
+            ..
+        })
+        | Type::Symbol(..) => "Symbol",
+
+        _ => return None,
+    };
+
+    Some(name.into())
+}
@@ -2,11 +2,12 @@ use std::{
     borrow::Cow,
     collections::HashMap,
     convert::{TryFrom, TryInto},
-    time::{Duration, Instant},
+    time::Duration,
 };
 
+use instant::Instant;
 use optional_chaining::is_obj_opt_chaining;
-use rnode::{NodeId, VisitWith};
+use rnode::NodeId;
 use stc_ts_ast_rnode::{
     RAssignExpr, RBindingIdent, RClassExpr, RExpr, RIdent, RInvalid, RLit, RMemberExpr, RMemberProp, RNull, RNumber, ROptChainBase,
     ROptChainExpr, RParenExpr, RPat, RPatOrExpr, RSeqExpr, RStr, RSuper, RSuperProp, RSuperPropExpr, RThisExpr, RTpl, RTsEntityName,
@@ -15,24 +16,29 @@ use stc_ts_ast_rnode::{
 use stc_ts_base_type_ops::bindings::BindingKind;
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt, ErrorKind, Errors,
+    DebugExt, DeferredMsg, ErrorKind, Errors,
 };
 use stc_ts_generics::ExpandGenericOpts;
-use stc_ts_type_ops::{generalization::prevent_generalize, is_str_lit_or_union, Fix};
+use stc_ts_type_ops::{generalization::prevent_generalize, is_str_lit_or_union, this::contains_this, Fix};
 pub use stc_ts_types::IdCtx;
 use stc_ts_types::{
-    name::Name, Alias, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, ComputedKey, ConstructorSignature, Id, Key,
+    name::Name, Accessor, Alias, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, ComputedKey, ConstructorSignature, Id, Key,
     KeywordType, KeywordTypeMetadata, LitType, LitTypeMetadata, Method, Module, ModuleTypeData, Operator, OptionalType, PropertySignature,
     QueryExpr, QueryType, QueryTypeMetadata, StaticThis, ThisType, TplElem, TplType, TplTypeMetadata, TypeParamInstantiation,
 };
-use stc_utils::{cache::Freeze, debug_ctx, ext::TypeVecExt, stack};
+use stc_utils::{
+    cache::{Freeze, ALLOW_DEEP_CLONE},
+    debug_ctx,
+    ext::TypeVecExt,
+    stack,
+};
 use swc_atoms::js_word;
 use swc_common::{SourceMapper, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::{op, EsVersion, TruePlusMinus, TsKeywordTypeKind, TsTypeOperatorOp, VarDeclKind};
 use tracing::{debug, info, warn, Level};
 
 use self::bin::extract_name_for_assignment;
-pub(crate) use self::{array::GetIteratorOpts, call_new::CallOpts};
+pub(crate) use self::{array::GetIteratorOpts, call_new::CallOpts, object::retain_own_props_of_instance};
 use crate::{
     analyzer::{
         assign::AssignOpts,
@@ -56,15 +62,19 @@ use crate::{
 mod array;
 mod await_expr;
 mod bin;
+mod bind_call_apply;
 mod call_new;
 mod const_assertion;
 mod constraint_reducer;
 mod function;
 mod jsx;
+mod lib_feature;
 mod meta_prop;
 mod misc;
 mod object;
+mod object_methods;
 pub(crate) mod optional_chaining;
+mod spelling;
 mod type_cast;
 mod unary;
 mod update;
@@ -121,7 +131,7 @@ impl Analyzer<'_, '_> {
 
         let previous_unreachable_state = self.ctx.in_unreachable;
 
-        let mut ty = (|| -> VResult<Type> {
+        let mut ty = stack::maybe_grow(|| -> VResult<Type> {
             match e {
                 RExpr::TaggedTpl(e) => e.validate_with(self),
 
@@ -226,11 +236,7 @@ impl Analyzer<'_, '_> {
 
                 RExpr::Object(e) => e.validate_with_args(self, type_ann),
 
-                // https://github.com/Microsoft/TypeScript/issues/26959
-                RExpr::Yield(..) => {
-                    e.visit_children_with(self);
-                    Ok(Type::any(span, Default::default()))
-                }
+                RExpr::Yield(e) => e.validate_with_args(self, type_ann),
 
                 RExpr::Await(e) => e.validate_with_args(self, type_ann),
 
@@ -278,11 +284,14 @@ impl Analyzer<'_, '_> {
 
                 _ => Err(ErrorKind::Unimplemented {
                     span,
-                    msg: format!("validation of ({:?})", e),
+                    msg: DeferredMsg::new({
+                        let e = e.clone();
+                        move || format!("validation of ({:?})", e)
+                    }),
                 }
                 .into()),
             }
-        })()?;
+        })?;
 
         if self.config.is_builtin {
             // `Symbol.iterator` is defined multiple times, and it results in union of
@@ -326,6 +335,10 @@ impl Analyzer<'_, '_> {
             self.dump_type(span, &ty);
         }
 
+        if !span.is_dummy() {
+            self.record_type(span, &ty);
+        }
+
         Ok(ty)
     }
 }
@@ -381,6 +394,7 @@ impl Analyzer<'_, '_> {
                                                     types,
                                                     private_vars,
                                                     vars,
+                                                    ..
                                                 },
                                             ..
                                         }) => {
@@ -551,6 +565,11 @@ pub(crate) struct AccessPropertyOpts {
 
     /// `true` means that the provided [Key] is crated from a computed key.
     pub is_key_computed: bool,
+
+    /// If `true` and `noUncheckedIndexedAccess` is enabled, reading an element
+    /// of an array or a property through an index signature adds `undefined`
+    /// to the result.
+    pub use_undefined_for_unchecked_index: bool,
 }
 
 #[validator]
@@ -695,6 +714,20 @@ impl Analyzer<'_, '_> {
             None
         };
 
+        // `[Symbol.iterator]` only matches `[Symbol.iterator]`, even if the type of the
+        // key is `symbol`.
+        if let (Key::Computed(d), Key::Computed(c)) = (declared, cur) {
+            match (d.well_known_symbol(), c.well_known_symbol()) {
+                (Some(d), Some(c)) => return d == c,
+                (Some(..), None) | (None, Some(..)) => {
+                    if !d.ty.is_kwd(TsKeywordTypeKind::TsSymbolKeyword) && !c.ty.is_kwd(TsKeywordTypeKind::TsSymbolKeyword) {
+                        return false;
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
         match declared {
             Key::Computed(..) => {}
             _ => {
@@ -839,12 +872,42 @@ impl Analyzer<'_, '_> {
                 if self.key_matches(span, key, prop, true) {
                     match el {
                         TypeElement::Property(ref p) => {
+                            // A pair of `get foo()` and `set foo(v)` in an interface is stored as two
+                            // elements. We use the getter for reads and the setter for writes.
+                            match (p.accessor, type_mode) {
+                                (Accessor { getter: true, setter: false }, TypeOfMode::LValue)
+                                    if has_accessor_of_kind(members, key, false) =>
+                                {
+                                    continue
+                                }
+                                (Accessor { getter: false, setter: true }, TypeOfMode::RValue)
+                                    if has_accessor_of_kind(members, key, true) =>
+                                {
+                                    continue
+                                }
+                                _ => {}
+                            }
+
                             if type_mode == TypeOfMode::LValue && p.readonly {
                                 read_only_flag = true;
                             }
 
-                            if let Some(ref type_ann) = p.type_ann {
-                                if p.optional {
+                            let type_ann = match type_mode {
+                                TypeOfMode::LValue => p.write_type.as_ref().or(p.type_ann.as_ref()),
+                                TypeOfMode::RValue => p.type_ann.as_ref(),
+                            };
+                            // Setter signatures store the type in the parameter.
+                            let type_ann = type_ann.or_else(|| match p.accessor {
+                                Accessor { getter: false, setter: true } => p.params.first().map(|param| &param.ty),
+                                _ => None,
+                            });
+
+                            if let Some(type_ann) = type_ann {
+                                // With `exactOptionalPropertyTypes`, `undefined` cannot be written to
+                                // an optional property unless it's declared explicitly.
+                                let is_exact_write = type_mode == TypeOfMode::LValue && self.rule().exact_optional_property_types;
+
+                                if p.optional && !is_exact_write {
                                     let mut types = vec![Type::undefined(span, Default::default()), *type_ann.clone()];
                                     types.dedup_type();
                                     matching_elements.push(Type::union(types));
@@ -984,16 +1047,22 @@ impl Analyzer<'_, '_> {
 
                 if indexed {
                     if let Some(type_ann) = type_ann {
-                        return Ok(Some(*type_ann.clone()));
+                        return Ok(Some(self.add_undefined_for_unchecked_index(
+                            span,
+                            *type_ann.clone(),
+                            type_mode,
+                            opts,
+                        )));
                     }
 
                     return Ok(Some(Type::any(span, Default::default())));
                 }
 
                 if (**index_ty).type_eq(&*prop_ty) {
-                    return Ok(Some(
-                        type_ann.clone().map(|v| *v).unwrap_or_else(|| Type::any(span, Default::default())),
-                    ));
+                    return Ok(Some(match type_ann {
+                        Some(type_ann) => self.add_undefined_for_unchecked_index(span, *type_ann.clone(), type_mode, opts),
+                        None => Type::any(span, Default::default()),
+                    }));
                 }
 
                 if let Type::EnumVariant(..) = prop_ty.normalize() {
@@ -1044,6 +1113,20 @@ impl Analyzer<'_, '_> {
         Ok(Some(result))
     }
 
+    /// Adds `undefined` to the type of an element read through an index
+    /// signature if `noUncheckedIndexedAccess` is enabled.
+    fn add_undefined_for_unchecked_index(&self, span: Span, ty: Type, type_mode: TypeOfMode, opts: AccessPropertyOpts) -> Type {
+        if !opts.use_undefined_for_unchecked_index || type_mode != TypeOfMode::RValue || !self.rule().no_unchecked_indexed_access {
+            return ty;
+        }
+
+        if ty.is_any() || ty.is_unknown() {
+            return ty;
+        }
+
+        Type::new_union(span, vec![ty, Type::undefined(span, Default::default())])
+    }
+
     pub(super) fn access_property(
         &mut self,
         span: Span,
@@ -1211,10 +1294,23 @@ impl Analyzer<'_, '_> {
             );
         }
 
-        let mut ty = res?;
+        let mut ty = res.map_err(|err| {
+            let err = self.suggest_lib_for_property(span, obj, prop, err);
+            self.suggest_spelling_for_property(span, obj, prop, err)
+        })?;
 
         ty.assert_valid();
 
+        // Resolve polymorphic `this` using the type of the object.
+        if matches!(
+            obj.normalize(),
+            Type::Class(..) | Type::Interface(..) | Type::TypeLit(..) | Type::Ref(..) | Type::Intersection(..)
+        ) && contains_this(&ty)
+        {
+            self.replace_this_in_type(&mut ty, obj.clone());
+            ty.freeze();
+        }
+
         let ty_str = dump_type_as_string(&ty);
 
         debug!(
@@ -1397,7 +1493,10 @@ impl Analyzer<'_, '_> {
 
             return Err(ErrorKind::Unimplemented {
                 span,
-                msg: format!("access_property_inner: global_this: {:?}", prop),
+                msg: DeferredMsg::new({
+                    let prop = ALLOW_DEEP_CLONE.set(&(), || prop.clone()).freezed();
+                    move || format!("access_property_inner: global_this: {:?}", prop)
+                }),
             }
             .into());
         }
@@ -1467,7 +1566,11 @@ impl Analyzer<'_, '_> {
 
                                 ClassMember::Property(member @ ClassProperty { is_static, .. }) => {
                                     if !is_static && member.key.type_eq(prop) {
-                                        let ty = *member.value.clone().unwrap_or_else(|| box Type::any(span, Default::default()));
+                                        let value = match type_mode {
+                                            TypeOfMode::LValue => member.write_type.as_ref().or(member.value.as_ref()),
+                                            TypeOfMode::RValue => member.value.as_ref(),
+                                        };
+                                        let ty = value.map(|v| *v.clone()).unwrap_or_else(|| Type::any(span, Default::default()));
 
                                         return Ok(ty);
                                     }
@@ -1716,6 +1819,11 @@ impl Analyzer<'_, '_> {
             }
 
             Type::Enum(ref e) => {
+                // Ambient const enums can't be inlined without type information.
+                if e.is_const && e.declare && type_mode == TypeOfMode::RValue && self.rule().isolated_modules {
+                    self.storage.report(ErrorKind::AmbientConstEnumWithIsolatedModules { span }.into());
+                }
+
                 // TODO(kdy1): Check if variant exists.
 
                 match prop {
@@ -1889,8 +1997,13 @@ impl Analyzer<'_, '_> {
                                     }
                                 }
 
-                                return Ok(match class_prop.value {
-                                    Some(ref ty) => *ty.clone(),
+                                let value = match type_mode {
+                                    TypeOfMode::LValue => class_prop.write_type.as_ref().or(class_prop.value.as_ref()),
+                                    TypeOfMode::RValue => class_prop.value.as_ref(),
+                                };
+
+                                return Ok(match value {
+                                    Some(ty) => *ty.clone(),
                                     None => Type::any(span, Default::default()),
                                 });
                             }
@@ -2192,13 +2305,13 @@ impl Analyzer<'_, '_> {
                         })
                         | Type::Lit(LitType {
                             lit: RTsLit::Number(..), ..
-                        }) => return Ok(*elem_type),
+                        }) => return Ok(self.add_undefined_for_unchecked_index(span, *elem_type, type_mode, opts)),
 
                         _ => {}
                     }
                 }
                 if let Key::Num(n) = prop {
-                    return Ok(*elem_type.clone());
+                    return Ok(self.add_undefined_for_unchecked_index(span, *elem_type.clone(), type_mode, opts));
                 }
 
                 let array_ty = self.env.get_global_type(span, &js_word!("Array"))?;
@@ -2662,7 +2775,12 @@ impl Analyzer<'_, '_> {
                                     return Ok(Type::any(span, Default::default()));
                                 }
 
-                                if let Some(ref ty) = p.value {
+                                let value = match type_mode {
+                                    TypeOfMode::LValue => p.write_type.as_ref().or(p.value.as_ref()),
+                                    TypeOfMode::RValue => p.value.as_ref(),
+                                };
+
+                                if let Some(ty) = value {
                                     return Ok(*ty.clone());
                                 }
 
@@ -2850,7 +2968,7 @@ impl Analyzer<'_, '_> {
                     kind => {
                         return Err(ErrorKind::Unimplemented {
                             span,
-                            msg: format!("access property of this to {:?}", kind),
+                            msg: DeferredMsg::new(move || format!("access property of this to {:?}", kind)),
                         }
                         .into())
                     }
@@ -3060,6 +3178,10 @@ impl Analyzer<'_, '_> {
             }
 
             Type::Function(f) if type_mode == TypeOfMode::RValue => {
+                if let Some(ty) = self.access_bind_call_apply(span, f, prop) {
+                    return Ok(ty);
+                }
+
                 // Use builtin type `Function`
                 let interface = self.env.get_global_type(f.span, &js_word!("Function"))?;
                 return self.access_property(span, &interface, prop, type_mode, id_ctx, opts);
@@ -3128,8 +3250,38 @@ impl Analyzer<'_, '_> {
                 ty,
                 ..
             }) => {
-                if let TypeOfMode::RValue = type_mode {
-                    return self.access_property(span, ty, prop, type_mode, id_ctx, opts);
+                let is_array_like = matches!(ty.normalize(), Type::Array(..) | Type::Tuple(..));
+
+                match type_mode {
+                    TypeOfMode::RValue => {
+                        // Mutating methods like `push` are not declared in `ReadonlyArray`.
+                        if is_array_like && matches!(prop, Key::Normal { .. }) {
+                            if let Ok(readonly_array) = self.env.get_global_type(span, &"ReadonlyArray".into()) {
+                                if self.access_property(span, &readonly_array, prop, type_mode, id_ctx, opts).is_err() {
+                                    return Err(ErrorKind::NoSuchProperty {
+                                        span,
+                                        obj: Some(box obj.clone()),
+                                        prop: Some(box prop.clone()),
+                                    }
+                                    .into());
+                                }
+                            }
+                        }
+
+                        return self.access_property(span, ty, prop, type_mode, id_ctx, opts);
+                    }
+                    TypeOfMode::LValue if is_array_like => {
+                        let is_index = prop.is_num_like() || matches!(prop, Key::Computed(key) if key.ty.is_num_like());
+
+                        // Elements of tuples are properties, but elements of arrays are an index
+                        // signature.
+                        if is_index && ty.normalize().is_array() {
+                            return Err(ErrorKind::ReadonlyIndexSignature { span }.into());
+                        }
+
+                        return Err(ErrorKind::CannotAssignToReadonlyProperty { span }.into());
+                    }
+                    TypeOfMode::LValue => {}
                 }
             }
 
@@ -3146,12 +3298,12 @@ impl Analyzer<'_, '_> {
 
         Err(ErrorKind::Unimplemented {
             span,
-            msg: format!(
-                "access_property(MemberExpr):\nObject: {:?}\nProp: {:?}\nPath: {}",
-                obj,
-                prop,
-                self.storage.path(self.ctx.module_id)
-            ),
+            msg: DeferredMsg::new({
+                let obj = ALLOW_DEEP_CLONE.set(&(), || obj.clone()).freezed();
+                let prop = ALLOW_DEEP_CLONE.set(&(), || prop.clone()).freezed();
+                let path = self.storage.path(self.ctx.module_id);
+                move || format!("access_property(MemberExpr):\nObject: {:?}\nProp: {:?}\nPath: {}", obj, prop, path)
+            }),
         }
         .into())
     }
@@ -3311,6 +3463,8 @@ impl Analyzer<'_, '_> {
         let id: Id = i.into();
         let name: Name = i.into();
 
+        self.report_deprecated_reference(span, &id);
+
         if self.scope.is_declaring_fn(&id) {
             // We will expand this type query to proper type while calculating returns types
             // of a function.
@@ -3323,6 +3477,11 @@ impl Analyzer<'_, '_> {
             }));
         }
 
+        if !self.ctx.in_type_query && !self.ctx.in_declare && self.data.type_only_imports.contains(&id) {
+            self.storage
+                .report(ErrorKind::TypeOnlyImportUsedAsValue { span, name: id.clone() }.into());
+        }
+
         let mut modules = vec![];
         let mut ty = self.type_of_raw_var(i, type_mode)?;
         if type_mode == TypeOfMode::LValue && (ty.is_class_def() || ty.is_enum_type()) && !ty.metadata().resolved_from_var {
@@ -3615,6 +3774,16 @@ impl Analyzer<'_, '_> {
                     self.storage.report(ErrorKind::CannotExportNonLocalVar { span: i.span }.into());
                 }
 
+                if self.data.is_external_module && !self.config.is_dts && self.env.is_umd_global(&i.sym) {
+                    self.storage.report(
+                        ErrorKind::UmdGlobalInModule {
+                            span: i.span,
+                            name: i.sym.clone(),
+                        }
+                        .into(),
+                    );
+                }
+
                 return Ok(ty);
             }
         }
@@ -3797,11 +3966,11 @@ impl Analyzer<'_, '_> {
                     }
                     .into())
                 } else {
-                    Err(ErrorKind::NoSuchVar {
-                        span,
-                        name: i.clone().into(),
-                    }
-                    .into())
+                    let name: Id = i.clone().into();
+                    let err = ErrorKind::NoSuchVar { span, name: name.clone() }.into();
+
+                    let err = self.suggest_lib_for_var(span, &name, err);
+                    Err(self.suggest_spelling_for_var(span, &name, err))
                 }
             }
         }
@@ -4105,11 +4274,16 @@ impl Analyzer<'_, '_> {
                 IdCtx::Var,
                 AccessPropertyOpts {
                     check_for_undefined_or_null: true,
+                    use_undefined_for_unchecked_index: true,
                     ..Default::default()
                 },
             )
             .context("tried to access property of an object to calculate type of a member expression")?;
 
+        if let RMemberProp::Ident(i) = &expr.prop {
+            self.report_deprecated_member(i.span, &obj_ty, &prop);
+        }
+
         if !self.config.is_builtin {
             if let Some(name) = name {
                 debug_assert_ne!(ty.span(), DUMMY_SP);
@@ -4444,6 +4618,15 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Returns `true` if `members` contains a getter (if `getter` is `true`) or a
+/// setter (if `getter` is `false`) for `key`.
+fn has_accessor_of_kind(members: &[TypeElement], key: &Key, getter: bool) -> bool {
+    members.iter().any(|el| match el {
+        TypeElement::Property(p) => (if getter { p.accessor.getter } else { p.accessor.setter }) && p.key.type_eq(key),
+        _ => false,
+    })
+}
+
 fn is_valid_lhs(l: &RPatOrExpr) -> VResult<()> {
     fn is_valid_lhs_expr(e: &RExpr) -> VResult<()> {
         // obj?.a["b"] += 1;
@@ -4506,29 +4689,34 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, e: &RLit) -> VResult<Type> {
+        let metadata = LitTypeMetadata {
+            fresh: true,
+            ..Default::default()
+        };
+
         match e {
             RLit::Bool(v) => Ok(Type::Lit(LitType {
                 span: v.span,
                 lit: RTsLit::Bool(v.clone()),
-                metadata: Default::default(),
+                metadata,
                 tracker: Default::default(),
             })),
             RLit::Str(ref v) => Ok(Type::Lit(LitType {
                 span: v.span,
                 lit: RTsLit::Str(v.clone()),
-                metadata: Default::default(),
+                metadata,
                 tracker: Default::default(),
             })),
             RLit::Num(v) => Ok(Type::Lit(LitType {
                 span: v.span,
                 lit: RTsLit::Number(v.clone()),
-                metadata: Default::default(),
+                metadata,
                 tracker: Default::default(),
             })),
             RLit::BigInt(v) => Ok(Type::Lit(LitType {
                 span: v.span,
                 lit: RTsLit::BigInt(v.clone()),
-                metadata: Default::default(),
+                metadata,
                 tracker: Default::default(),
             })),
             RLit::Null(RNull { span }) => {
@@ -1,18 +1,22 @@
-use std::{borrow::Cow, time::Instant};
+use std::borrow::Cow;
 
+use instant::Instant;
 use rnode::VisitMutWith;
-use stc_ts_ast_rnode::{RObjectLit, RPropOrSpread, RSpreadElement};
-use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_ast_rnode::{RExpr, RIdent, RObjectLit, RPropOrSpread, RSpreadElement, RTsEntityName};
+use stc_ts_errors::{DebugExt, DeferredMsg, ErrorKind};
 use stc_ts_file_analyzer_macros::validator;
 use stc_ts_type_ops::{union_normalization::ObjectUnionNormalizer, Fix};
-use stc_ts_types::{Accessor, Key, MethodSignature, PropertySignature, Type, TypeElement, TypeLit, TypeParam, Union, UnionMetadata};
+use stc_ts_types::{
+    Accessor, Function, Key, MethodSignature, PropertySignature, Ref, Type, TypeElement, TypeLit, TypeParam, Union, UnionMetadata,
+};
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq};
-use swc_ecma_ast::TsKeywordTypeKind;
+use swc_ecma_ast::{Accessibility, TsKeywordTypeKind};
 use tracing::debug;
 
 use crate::{
     analyzer::{Analyzer, NormalizeTypeOpts, ScopeKind},
+    type_facts::TypeFacts,
     validator::ValidateWith,
     VResult,
 };
@@ -25,10 +29,13 @@ pub struct AppendTypeOpts {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RObjectLit, type_ann: Option<&Type>) -> VResult<Type> {
+        let this_ty = type_ann.and_then(|ty| self.this_type_from_marker(node.span, ty, 0));
         let type_ann = self.expand_type_ann(node.span, type_ann)?;
         debug_assert_eq!(node.span.ctxt, SyntaxContext::empty());
 
         self.with_child(ScopeKind::ObjectLit, Default::default(), |a: &mut Analyzer| {
+            a.scope.this = this_ty;
+
             let mut ret = Type::TypeLit(TypeLit {
                 span: node.span,
                 members: vec![],
@@ -49,6 +56,60 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
+    /// Finds `T` of `ThisType<T>` in the contextual type of an object literal.
+    ///
+    /// `ThisType<T>` is a marker interface declared in the lib, and `this` in
+    /// methods of the object literal is typed as `T`.
+    fn this_type_from_marker(&mut self, span: Span, ty: &Type, depth: u8) -> Option<Type> {
+        if depth > 8 {
+            return None;
+        }
+
+        match ty.normalize() {
+            Type::Ref(Ref {
+                type_name: RTsEntityName::Ident(RIdent { sym, .. }),
+                type_args: Some(type_args),
+                ..
+            }) if *sym == *"ThisType" && type_args.params.len() == 1 => return Some(type_args.params[0].clone()),
+
+            Type::Intersection(ty) => {
+                return ty.types.iter().find_map(|ty| self.this_type_from_marker(span, ty, depth + 1));
+            }
+
+            Type::Interface(ty) => {
+                return ty.extends.iter().find_map(|parent| match (&*parent.expr, &parent.type_args) {
+                    (RExpr::Ident(RIdent { sym, .. }), Some(type_args)) if *sym == *"ThisType" && type_args.params.len() == 1 => {
+                        Some(type_args.params[0].clone())
+                    }
+                    _ => None,
+                });
+            }
+
+            Type::Ref(..) | Type::Alias(..) => {}
+
+            _ => return None,
+        }
+
+        // Expand aliases, but keep intersections as the marker is an element of it.
+        let expanded = self
+            .normalize(
+                Some(span),
+                Cow::Borrowed(ty),
+                NormalizeTypeOpts {
+                    preserve_intersection: true,
+                    ..Default::default()
+                },
+            )
+            .ok()?
+            .into_owned();
+
+        if expanded.type_eq(ty) {
+            return None;
+        }
+
+        self.this_type_from_marker(span, &expanded, depth + 1)
+    }
+
     /// Object literals in unions are normalized upon widening.
     ///
     ///```ts
@@ -236,7 +297,16 @@ impl Analyzer<'_, '_> {
         }
 
         match rhs.normalize() {
-            Type::Interface(..) | Type::Class(..) | Type::Intersection(..) | Type::Mapped(..) => {
+            Type::Class(..) => {
+                // Only own properties of an instance are spread.
+                if let Some(rhs) = self.convert_type_to_type_lit(rhs.span(), Cow::Borrowed(&rhs))? {
+                    let mut rhs = rhs.into_owned();
+                    retain_own_props_of_instance(&mut rhs);
+                    return self.append_type(span, to, Type::TypeLit(rhs), opts);
+                }
+            }
+
+            Type::Interface(..) | Type::Intersection(..) | Type::Mapped(..) => {
                 // Append as a type literal.
                 if let Some(rhs) = self.convert_type_to_type_lit(rhs.span(), Cow::Borrowed(&rhs))? {
                     return self.append_type(span, to, Type::TypeLit(rhs.into_owned()), opts);
@@ -264,7 +334,7 @@ impl Analyzer<'_, '_> {
 
                 match rhs {
                     Type::TypeLit(rhs) => {
-                        lit.members.extend(rhs.members);
+                        self.spread_members(span, &mut lit.members, rhs.members);
                         return Ok(to);
                     }
                     Type::Union(rhs) => {
@@ -327,6 +397,67 @@ impl Analyzer<'_, '_> {
         Ok(Type::new_intersection(span, vec![to, rhs]))
     }
 
+    /// Appends the members of a spread object type to `members`, like `{
+    /// ...left, ...right }`.
+    ///
+    /// - A property of `right` overrides the property of `left` with the same
+    ///   key. If the property of `right` is optional, the type of the property
+    ///   becomes a union of both.
+    /// - Methods and getters become properties, and properties are not readonly
+    ///   anymore.
+    /// - Call and construct signatures are dropped.
+    fn spread_members(&mut self, span: Span, members: &mut Vec<TypeElement>, rhs: Vec<TypeElement>) {
+        for el in rhs {
+            let mut prop = match el {
+                TypeElement::Call(..) | TypeElement::Constructor(..) => continue,
+                TypeElement::Index(..) => {
+                    members.push(el);
+                    continue;
+                }
+                TypeElement::Method(m) => method_to_prop(m),
+                TypeElement::Property(p) => p,
+            };
+            prop.readonly = false;
+            prop.accessor = Default::default();
+            prop.write_type = None;
+
+            let prev = if prop.key.is_computed() {
+                None
+            } else {
+                members
+                    .iter()
+                    .position(|m| m.key().map_or(false, |key| key.type_eq(&prop.key)))
+                    .map(|idx| members.remove(idx))
+            };
+
+            if prop.optional {
+                let prev = match prev {
+                    Some(TypeElement::Property(p)) => Some(p),
+                    Some(TypeElement::Method(m)) => Some(method_to_prop(m)),
+                    _ => None,
+                };
+
+                if let Some(prev) = prev {
+                    // `{ ...{ a: string }, ...{ a?: number } }` is `{ a: string | number }`.
+                    let prev_ty = prev
+                        .type_ann
+                        .map(|ty| *ty)
+                        .unwrap_or_else(|| Type::any(prev.span, Default::default()));
+                    let ty = prop
+                        .type_ann
+                        .take()
+                        .map(|ty| self.apply_type_facts_to_type(TypeFacts::NEUndefined, *ty))
+                        .unwrap_or_else(|| Type::any(prop.span, Default::default()));
+
+                    prop.type_ann = Some(box Type::new_union(span, vec![prev_ty, ty]).freezed());
+                    prop.optional = prev.optional;
+                }
+            }
+
+            members.push(TypeElement::Property(prop));
+        }
+    }
+
     pub(crate) fn append_type_element(&mut self, to: Type, rhs: TypeElement) -> VResult<Type> {
         let _tracing = if cfg!(debug_assertions) {
             Some(tracing::span!(tracing::Level::ERROR, "append_type_element").entered())
@@ -390,9 +521,50 @@ impl Analyzer<'_, '_> {
             }
             _ => Err(ErrorKind::Unimplemented {
                 span: to.span(),
-                msg: format!("append_type_element\n{:?}\n{:?}", to, rhs),
+                msg: DeferredMsg::new(move || format!("append_type_element\n{:?}\n{:?}", to, rhs)),
             }
             .into()),
         }
     }
 }
+
+/// Converts a method to a property with a function type, as methods are
+/// plain properties after spreading.
+fn method_to_prop(m: MethodSignature) -> PropertySignature {
+    PropertySignature {
+        span: m.span,
+        accessibility: m.accessibility,
+        readonly: m.readonly,
+        key: m.key,
+        optional: m.optional,
+        params: Default::default(),
+        type_ann: Some(box Type::Function(Function {
+            span: m.span,
+            type_params: m.type_params,
+            params: m.params,
+            ret_ty: m.ret_ty.unwrap_or_else(|| box Type::any(m.span, Default::default())),
+            metadata: Default::default(),
+            tracker: Default::default(),
+        })),
+        type_params: None,
+        metadata: m.metadata,
+        accessor: Default::default(),
+        write_type: None,
+    }
+}
+
+/// Removes members of a class instance which are not copied by spread or rest,
+/// which are methods and accessors declared on the prototype and non-public
+/// properties.
+pub(crate) fn retain_own_props_of_instance(lit: &mut TypeLit) {
+    lit.members.retain(|m| match m {
+        TypeElement::Method(..) => false,
+        TypeElement::Property(p) => {
+            !p.accessor.getter
+                && !p.accessor.setter
+                && !matches!(p.accessibility, Some(Accessibility::Private | Accessibility::Protected))
+                && !matches!(p.key, Key::Private(..))
+        }
+        _ => true,
+    });
+}
@@ -0,0 +1,252 @@
+//! Precise types for static methods of `Object`.
+//!
+//! This is opt-in via [stc_ts_env::Rule::precise_object_methods], because the
+//! results are more precise than the declarations of `lib.d.ts`, and tsc
+//! does not narrow them.
+
+use stc_ts_ast_rnode::{RStr, RTsLit};
+use stc_ts_types::{
+    Array, Key, LitType, Operator, PropertySignature, Tuple, TupleElement, TupleMetadata, Type, TypeElement, TypeLit, TypeOrSpread,
+};
+use stc_utils::cache::ALLOW_DEEP_CLONE;
+use swc_common::{Span, TypeEq};
+use swc_ecma_ast::TsTypeOperatorOp;
+
+use crate::{analyzer::Analyzer, VResult};
+
+impl Analyzer<'_, '_> {
+    /// Returns [None] if the declared signature of `Object[method]` should be
+    /// used.
+    ///
+    /// Only object literal types are handled, as other types may have more
+    /// properties than the declared ones.
+    pub(super) fn call_object_static_method(&mut self, span: Span, method: &Key, args: &[TypeOrSpread]) -> VResult<Option<Type>> {
+        if args.iter().any(|arg| arg.spread.is_some()) {
+            return Ok(None);
+        }
+
+        let sym = match method {
+            Key::Normal { sym, .. } => sym,
+            _ => return Ok(None),
+        };
+
+        let ty = match &**sym {
+            "keys" => args.first().and_then(|arg| object_keys(span, &arg.ty)),
+            "entries" => args.first().and_then(|arg| object_entries(span, &arg.ty)),
+            "fromEntries" => args.first().and_then(|arg| object_from_entries(span, &arg.ty)),
+            "assign" => object_assign(span, args),
+            "freeze" => args.first().and_then(|arg| object_freeze(&arg.ty)),
+            _ => None,
+        };
+
+        Ok(ty)
+    }
+}
+
+/// Returns the properties of an object literal type, or [None] if `ty` has
+/// other members or computed keys.
+fn literal_props(ty: &Type) -> Option<Vec<&PropertySignature>> {
+    let lit = match ty.normalize() {
+        Type::TypeLit(lit) => lit,
+        _ => return None,
+    };
+
+    lit.members
+        .iter()
+        .map(|member| match member {
+            TypeElement::Property(p) if matches!(p.key, Key::Normal { .. } | Key::Num(..)) => Some(p),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Keys of objects are strings at runtime, even if they are declared as
+/// numbers.
+fn key_as_str(span: Span, key: &Key) -> Type {
+    let value = match key {
+        Key::Normal { sym, .. } => sym.clone(),
+        Key::Num(n) => n.value.to_string().into(),
+        _ => unreachable!("literal_props returns only normal or numeric keys"),
+    };
+
+    Type::Lit(LitType {
+        span,
+        lit: RTsLit::Str(RStr { span, value, raw: None }),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn prop_type(span: Span, p: &PropertySignature) -> Type {
+    match &p.type_ann {
+        Some(ty) => ALLOW_DEEP_CLONE.set(&(), || (**ty).clone()),
+        None => Type::any(span, Default::default()),
+    }
+}
+
+fn array_of(span: Span, elem_type: Type) -> Type {
+    Type::Array(Array {
+        span,
+        elem_type: box elem_type,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+/// `Object.keys({ a: 1, b: '' })` is `("a" | "b")[]`.
+fn object_keys(span: Span, ty: &Type) -> Option<Type> {
+    let props = literal_props(ty)?;
+
+    let keys = props.iter().map(|p| key_as_str(span, &p.key)).collect::<Vec<_>>();
+
+    Some(array_of(span, Type::new_union(span, keys)))
+}
+
+/// `Object.entries({ a: 1, b: '' })` is `(["a", number] | ["b", string])[]`.
+fn object_entries(span: Span, ty: &Type) -> Option<Type> {
+    let props = literal_props(ty)?;
+
+    let entries = props
+        .iter()
+        .map(|p| {
+            Type::Tuple(Tuple {
+                span,
+                elems: vec![
+                    TupleElement {
+                        span,
+                        label: None,
+                        ty: box key_as_str(span, &p.key),
+                        tracker: Default::default(),
+                    },
+                    TupleElement {
+                        span,
+                        label: None,
+                        ty: box prop_type(span, p),
+                        tracker: Default::default(),
+                    },
+                ],
+                metadata: TupleMetadata {
+                    prevent_tuple_to_array: true,
+                    ..Default::default()
+                },
+                tracker: Default::default(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Some(array_of(span, Type::new_union(span, entries)))
+}
+
+/// `Object.fromEntries([["a", 1], ["b", ""]] as const)` is `{ a: 1; b: "" }`.
+///
+/// Only tuples of entries with literal keys are handled, as the keys of arrays
+/// are not known.
+fn object_from_entries(span: Span, ty: &Type) -> Option<Type> {
+    let entries = match strip_readonly(ty) {
+        Type::Tuple(tuple) => &tuple.elems,
+        _ => return None,
+    };
+
+    let mut members: Vec<TypeElement> = vec![];
+    for entry in entries {
+        let elems = match strip_readonly(&entry.ty) {
+            Type::Tuple(tuple) if tuple.elems.len() == 2 => &tuple.elems,
+            _ => return None,
+        };
+
+        let key = match elems[0].ty.normalize() {
+            Type::Lit(LitType { lit: RTsLit::Str(s), .. }) => Key::Normal {
+                span,
+                sym: s.value.clone(),
+            },
+            Type::Lit(LitType {
+                lit: RTsLit::Number(n), ..
+            }) => Key::Num(n.clone()),
+            _ => return None,
+        };
+
+        // A later entry replaces an earlier entry with the same key.
+        let key_str = key_as_str(span, &key);
+        members.retain(|m| match m {
+            TypeElement::Property(prev) => !key_as_str(span, &prev.key).type_eq(&key_str),
+            _ => true,
+        });
+        members.push(TypeElement::Property(PropertySignature {
+            span,
+            accessibility: None,
+            readonly: false,
+            key,
+            optional: false,
+            params: Default::default(),
+            type_ann: Some(box ALLOW_DEEP_CLONE.set(&(), || (*elems[1].ty).clone())),
+            type_params: Default::default(),
+            metadata: Default::default(),
+            accessor: Default::default(),
+            write_type: Default::default(),
+        }));
+    }
+
+    Some(Type::TypeLit(TypeLit {
+        span,
+        members,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    }))
+}
+
+/// `as const` makes tuples readonly.
+fn strip_readonly(ty: &Type) -> &Type {
+    match ty.normalize() {
+        Type::Operator(Operator {
+            op: TsTypeOperatorOp::ReadOnly,
+            ty,
+            ..
+        }) => ty.normalize(),
+        ty => ty,
+    }
+}
+
+/// Merges object literal types. A property of a later argument replaces the
+/// property of an earlier argument with the same key.
+fn object_assign(span: Span, args: &[TypeOrSpread]) -> Option<Type> {
+    if args.is_empty() {
+        return None;
+    }
+
+    let mut members: Vec<TypeElement> = vec![];
+    for arg in args {
+        for p in literal_props(&arg.ty)? {
+            let key = key_as_str(span, &p.key);
+            members.retain(|m| match m {
+                TypeElement::Property(prev) => !key_as_str(span, &prev.key).type_eq(&key),
+                _ => true,
+            });
+            members.push(TypeElement::Property(ALLOW_DEEP_CLONE.set(&(), || p.clone())));
+        }
+    }
+
+    Some(Type::TypeLit(TypeLit {
+        span,
+        members,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    }))
+}
+
+/// `Object.freeze({ a: 1 })` is `{ readonly a: number }`.
+fn object_freeze(ty: &Type) -> Option<Type> {
+    literal_props(ty)?;
+
+    let mut lit = match ty.normalize() {
+        Type::TypeLit(lit) => ALLOW_DEEP_CLONE.set(&(), || lit.clone()),
+        _ => unreachable!(),
+    };
+
+    for member in &mut lit.members {
+        if let TypeElement::Property(p) = member {
+            p.readonly = true;
+        }
+    }
+
+    Some(Type::TypeLit(lit))
+}
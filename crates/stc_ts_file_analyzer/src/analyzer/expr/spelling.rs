@@ -0,0 +1,130 @@
+//! `Did you mean 'console'?` for misspelled variables and properties.
+
+use std::borrow::Cow;
+
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::{ClassMember, Id, Key, Type};
+use stc_utils::spelling::spelling_suggestion;
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+use crate::analyzer::{types::NormalizeTypeOpts, Analyzer};
+
+impl Analyzer<'_, '_> {
+    /// Replaces `err` with an error suggesting a variable with a similar
+    /// name, if `err` is caused by `name` not being declared.
+    pub(super) fn suggest_spelling_for_var(&mut self, span: Span, name: &Id, err: Error) -> Error {
+        if self.config.is_builtin || !matches!(&*err, ErrorKind::NoSuchVar { .. }) {
+            return err;
+        }
+
+        let mut candidates = vec![];
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            candidates.extend(s.vars.keys().map(|id| id.sym().clone()));
+            scope = s.parent();
+        }
+        candidates.extend(
+            self.data
+                .bindings
+                .all
+                .keys()
+                .filter(|id| !self.data.bindings.types.contains(id))
+                .map(|id| id.sym().clone()),
+        );
+        candidates.extend(self.env.global_var_names());
+
+        match suggest(name.sym(), candidates) {
+            Some(suggestion) => ErrorKind::NoSuchVarWithSuggestion {
+                span,
+                name: name.clone(),
+                suggestion,
+            }
+            .into(),
+            None => err,
+        }
+    }
+
+    /// Replaces `err` with an error suggesting a property with a similar name,
+    /// if `err` is caused by `prop` not being a member of `obj`.
+    pub(super) fn suggest_spelling_for_property(&mut self, span: Span, obj: &Type, prop: &Key, err: Error) -> Error {
+        if self.config.is_builtin
+            || !matches!(
+                &*err,
+                ErrorKind::NoSuchProperty { .. } | ErrorKind::NoSuchPropertyInClass { .. } | ErrorKind::NoSuchPropertyInThis { .. }
+            )
+        {
+            return err;
+        }
+
+        let sym = match prop {
+            Key::Normal { sym, .. } => sym,
+            _ => return err,
+        };
+
+        let obj = match self.normalize(
+            Some(span),
+            Cow::Borrowed(obj),
+            NormalizeTypeOpts {
+                normalize_keywords: true,
+                ..Default::default()
+            },
+        ) {
+            Ok(obj) => obj,
+            Err(..) => return err,
+        };
+
+        match suggest(sym, member_names(&obj)) {
+            Some(suggestion) => ErrorKind::NoSuchPropertyWithSuggestion {
+                span,
+                prop: box prop.clone(),
+                suggestion,
+            }
+            .into(),
+            None => err,
+        }
+    }
+}
+
+fn suggest(name: &str, mut candidates: Vec<JsWord>) -> Option<JsWord> {
+    // Sorted, so the suggestion does not depend on the order of hash maps.
+    candidates.sort();
+    candidates.dedup();
+
+    spelling_suggestion(name, candidates.iter().map(|s| &**s)).map(JsWord::from)
+}
+
+/// Names of the properties and methods declared by `ty` itself.
+fn member_names(ty: &Type) -> Vec<JsWord> {
+    let class_member_name = |member: &ClassMember| match member.key()?.into_owned() {
+        Key::Normal { sym, .. } => Some(sym),
+        _ => None,
+    };
+
+    match ty.normalize() {
+        Type::Interface(i) => i.body.iter().filter_map(|el| el.non_computed_key().cloned()).collect(),
+        Type::TypeLit(lit) => lit.members.iter().filter_map(|el| el.non_computed_key().cloned()).collect(),
+        Type::Class(c) => c
+            .def
+            .body
+            .iter()
+            .filter(|member| !is_static(member))
+            .filter_map(class_member_name)
+            .collect(),
+        Type::ClassDef(def) => def
+            .body
+            .iter()
+            .filter(|member| is_static(member))
+            .filter_map(class_member_name)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn is_static(member: &ClassMember) -> bool {
+    match member {
+        ClassMember::Method(m) => m.is_static,
+        ClassMember::Property(p) => p.is_static,
+        ClassMember::Constructor(..) | ClassMember::IndexSignature(..) => false,
+    }
+}
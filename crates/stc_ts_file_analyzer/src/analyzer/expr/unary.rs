@@ -117,6 +117,7 @@ impl Analyzer<'_, '_> {
                 if let Some(arg) = &arg_ty {
                     if let Type::Lit(LitType {
                         lit: RTsLit::Number(RNumber { span, value, .. }),
+                        metadata,
                         ..
                     }) = arg.normalize()
                     {
@@ -129,7 +130,7 @@ impl Analyzer<'_, '_> {
                                 value: if *op == op!(unary, "-") { -(*value) } else { *value },
                                 raw: None,
                             }),
-                            metadata: Default::default(),
+                            metadata: *metadata,
                             tracker: Default::default(),
                         }));
                     }
@@ -10,7 +10,7 @@ use stc_ts_types::{
 use stc_ts_utils::find_ids_in_pat;
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned, SyntaxContext};
-use swc_ecma_ast::TsKeywordTypeKind;
+use swc_ecma_ast::{EsVersion, TsKeywordTypeKind};
 use ty::TypeExt;
 
 use crate::{
@@ -117,6 +117,12 @@ impl Analyzer<'_, '_> {
 
             child.scope.declared_return_type = declared_ret_ty.clone();
 
+            if f.is_async && !f.is_generator {
+                if let Some(ty) = &declared_ret_ty {
+                    child.check_return_type_of_async_fn(ty);
+                }
+            }
+
             if let Some(ty) = &mut declared_ret_ty {
                 ty.freeze();
 
@@ -259,6 +265,65 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
+    /// Checks the declared return type of an async function.
+    ///
+    /// It should be the global `Promise<T>` type, or a reference to a type in
+    /// ES5.
+    pub(crate) fn check_return_type_of_async_fn(&mut self, ty: &Type) {
+        if self.config.is_builtin || self.ctx.in_declare {
+            return;
+        }
+
+        if !self.is_valid_return_type_of_async_fn(ty, 0) {
+            let span = ty.span();
+
+            if self.env.target() < EsVersion::Es2015 {
+                self.storage.report(ErrorKind::InvalidAsyncFnReturnTypeForEs5 { span }.into());
+            } else {
+                self.storage.report(ErrorKind::AsyncFnReturnTypeMustBePromise { span }.into());
+            }
+        }
+    }
+
+    fn is_valid_return_type_of_async_fn(&mut self, ty: &Type, depth: usize) -> bool {
+        let r = match ty.normalize() {
+            Type::Ref(r) => r,
+            _ => return false,
+        };
+
+        // Any type reference may refer to a `Promise`-compatible constructor in ES5.
+        if self.env.target() < EsVersion::Es2015 {
+            return true;
+        }
+
+        let name = match &r.type_name {
+            RTsEntityName::Ident(name) => name,
+            _ => return false,
+        };
+        let id = Id::from(name);
+
+        if name.sym == *"Promise" && !self.data.bindings.types.contains(&id) {
+            return true;
+        }
+
+        if depth >= 10 {
+            return false;
+        }
+
+        // `type P = Promise<number>` is a reference to the global `Promise<T>`.
+        let aliased = self.find_type(&id).ok().flatten().and_then(|mut types| {
+            types.find_map(|ty| match ty.normalize() {
+                Type::Alias(Alias { type_params: None, ty, .. }) => Some((**ty).clone()),
+                _ => None,
+            })
+        });
+
+        match aliased {
+            Some(aliased) => self.is_valid_return_type_of_async_fn(&aliased, depth + 1),
+            None => false,
+        }
+    }
+
     pub(crate) fn fn_to_type_element(&mut self, f: &Function) -> VResult<TypeElement> {
         Ok(TypeElement::Call(CallSignature {
             span: f.span.with_ctxt(SyntaxContext::empty()),
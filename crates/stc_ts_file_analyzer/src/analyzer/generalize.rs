@@ -314,6 +314,7 @@ impl Fold<Type> for Simplifier<'_> {
                             type_params: Default::default(),
                             metadata: Default::default(),
                             accessor: Default::default(),
+                            write_type: Default::default(),
                         })
                     })
                     .collect();
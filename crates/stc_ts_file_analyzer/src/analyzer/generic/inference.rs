@@ -786,12 +786,30 @@ impl Analyzer<'_, '_> {
             _ => Cow::Borrowed(arg),
         };
 
+        if inferred.contravariant {
+            return self.upsert_contra_inferred(span, inferred, name, &arg, opts);
+        }
+
         match inferred.type_params.entry(name.clone()) {
             Entry::Occupied(mut e) => {
                 if e.get().is_fixed {
                     return Ok(());
                 }
 
+                // Only contravariant candidates were found so far.
+                if e.get().candidates.is_empty() {
+                    let info = e.get_mut();
+                    if opts.priority <= info.priority {
+                        if opts.priority < info.priority {
+                            info.contra_candidates = Default::default();
+                        }
+                        info.inferred_type = arg.clone().into_owned();
+                        info.candidates = vec![arg.into_owned()];
+                        info.priority = opts.priority;
+                    }
+                    return Ok(());
+                }
+
                 let _tracing = tracing::span!(
                     Level::ERROR,
                     "infer_type: type param",
@@ -801,11 +819,15 @@ impl Analyzer<'_, '_> {
                 )
                 .entered();
 
+                // An inference with higher priority replaces all previous inferences.
                 if opts.priority < e.get().priority {
-                    e.get_mut().candidates = Default::default();
-                    e.get_mut().contra_candidates = Default::default();
-                    e.get_mut().top_level = true;
-                    e.get_mut().priority = opts.priority;
+                    let info = e.get_mut();
+                    info.inferred_type = arg.clone().into_owned();
+                    info.candidates = vec![arg.into_owned()];
+                    info.contra_candidates = Default::default();
+                    info.top_level = true;
+                    info.priority = opts.priority;
+                    return Ok(());
                 }
 
                 if opts.priority == e.get().priority {
@@ -814,6 +836,8 @@ impl Analyzer<'_, '_> {
                         return Ok(());
                     }
 
+                    e.get_mut().candidates.push(arg.clone().into_owned());
+
                     if opts.append_type_as_union
                         || self
                             .assign_with_opts(
@@ -914,13 +938,13 @@ impl Analyzer<'_, '_> {
                 }
             }
             Entry::Vacant(e) => {
-                let arg = arg.clone();
+                let arg = arg.into_owned();
 
                 e.insert(InferenceInfo {
                     type_param: name,
-                    candidates: Default::default(),
+                    candidates: vec![arg.clone()],
                     contra_candidates: Default::default(),
-                    inferred_type: arg.into_owned(),
+                    inferred_type: arg,
                     priority: opts.priority,
                     top_level: true,
                     is_fixed: false,
@@ -1371,12 +1395,122 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 
-    pub(super) fn finalize_inference(&self, span: Span, type_params: &[TypeParam], inferred: InferData) -> InferTypeResult {
+    /// Records an inference from a contravariant position, like a parameter of
+    /// a callback.
+    ///
+    /// Contravariant candidates are combined into an intersection (or their
+    /// common subtype) instead of a union, and they are used only if there's no
+    /// better covariant candidate. See [Self::finalize_inference].
+    fn upsert_contra_inferred(&mut self, span: Span, inferred: &mut InferData, name: Id, arg: &Type, opts: InferTypeOpts) -> VResult<()> {
+        let priority = opts.priority;
+
+        match inferred.type_params.entry(name.clone()) {
+            Entry::Occupied(mut e) => {
+                let info = e.get_mut();
+                if info.is_fixed || priority > info.priority {
+                    return Ok(());
+                }
+
+                if priority < info.priority {
+                    info.candidates = Default::default();
+                    info.contra_candidates = Default::default();
+                    info.priority = priority;
+                }
+
+                if info.contra_candidates.iter().any(|c| c.type_eq(arg)) {
+                    return Ok(());
+                }
+                info.contra_candidates.push(arg.clone());
+
+                if info.candidates.is_empty() {
+                    let candidates = info.contra_candidates.clone();
+                    let priority = info.priority;
+                    let ty = self.get_contravariant_inference(span, candidates, priority);
+                    e.get_mut().inferred_type = ty;
+                }
+            }
+            Entry::Vacant(e) => {
+                e.insert(InferenceInfo {
+                    type_param: name,
+                    candidates: Default::default(),
+                    contra_candidates: vec![arg.clone()],
+                    inferred_type: arg.clone(),
+                    priority,
+                    top_level: true,
+                    is_fixed: false,
+                    implied_arity: Default::default(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ported from `getContravariantInference` of `tsc`.
+    fn get_contravariant_inference(&mut self, span: Span, candidates: Vec<Type>, priority: InferencePriority) -> Type {
+        if candidates.len() == 1 || priority.intersects(InferencePriority::PriorityImpliesCombination) {
+            return Type::new_intersection(span, candidates).freezed();
+        }
+
+        // Common subtype
+        for (i, candidate) in candidates.iter().enumerate() {
+            let is_subtype_of_all = candidates.iter().enumerate().all(|(j, other)| {
+                i == j
+                    || self
+                        .assign_with_opts(
+                            &mut Default::default(),
+                            other,
+                            candidate,
+                            AssignOpts {
+                                span,
+                                ..Default::default()
+                            },
+                        )
+                        .is_ok()
+            });
+
+            if is_subtype_of_all {
+                return candidate.clone();
+            }
+        }
+
+        Type::new_intersection(span, candidates).freezed()
+    }
+
+    /// Selects the covariant inference if it's compatible with contravariant
+    /// candidates, like `getInferredType` of `tsc`.
+    fn select_inferred_type(&mut self, span: Span, info: &mut InferenceInfo) {
+        if info.candidates.is_empty() || info.contra_candidates.is_empty() {
+            return;
+        }
+
+        let prefer_covariant = !info.inferred_type.is_never()
+            && info.contra_candidates.iter().any(|contra| {
+                self.assign_with_opts(
+                    &mut Default::default(),
+                    contra,
+                    &info.inferred_type,
+                    AssignOpts {
+                        span,
+                        ..Default::default()
+                    },
+                )
+                .is_ok()
+            });
+
+        if !prefer_covariant {
+            info.inferred_type = self.get_contravariant_inference(span, info.contra_candidates.clone(), info.priority);
+        }
+    }
+
+    pub(super) fn finalize_inference(&mut self, span: Span, type_params: &[TypeParam], inferred: InferData) -> InferTypeResult {
         let mut map = HashMap::default();
 
         for (k, mut ty) in inferred.type_params {
             let tp = type_params.iter().find(|tp| tp.name == k);
 
+            self.select_inferred_type(span, &mut ty);
+
             self.replace_null_or_undefined_while_defaulting_to_any(&mut ty.inferred_type);
 
             if !ty.top_level {
@@ -1,6 +1,7 @@
-use std::{borrow::Cow, collections::hash_map::Entry, mem::take, time::Instant};
+use std::{borrow::Cow, collections::hash_map::Entry, mem::take};
 
 use fxhash::{FxHashMap, FxHashSet};
+use instant::Instant;
 use itertools::{EitherOrBoth, Itertools};
 use rnode::{Fold, FoldWith, VisitMut, VisitMutWith, VisitWith};
 use stc_ts_ast_rnode::{RBindingIdent, RIdent, RNumber, RPat, RStr, RTsEntityName, RTsLit};
@@ -451,7 +452,7 @@ impl Analyzer<'_, '_> {
 
         debug!("Start");
 
-        let res = self.infer_type_inner(span, inferred, param, arg, opts);
+        let res = stack::maybe_grow(|| self.infer_type_inner(span, inferred, param, arg, opts));
 
         debug!("End");
 
@@ -1641,6 +1642,7 @@ impl Analyzer<'_, '_> {
                                         type_params: Default::default(),
                                         metadata: Default::default(),
                                         accessor: Default::default(),
+                                        write_type: Default::default(),
                                     }));
                                 }
 
@@ -2057,6 +2059,10 @@ impl Analyzer<'_, '_> {
             None
         };
 
+        if self.infer_type_using_variadic_tuple(span, inferred, param, arg, opts)? {
+            return Ok(());
+        }
+
         let len = param.elems.len().max(arg.elems.len());
 
         for index in 0..len {
@@ -2117,6 +2123,79 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 
+    /// Infers types from a tuple to a tuple with one variadic element, like
+    /// `[string, ...T, boolean]`.
+    ///
+    /// Fixed elements before and after the variadic element are matched from
+    /// the start and from the end of `arg` respectively, and the remaining
+    /// elements of `arg` are inferred to the variadic element as a tuple.
+    ///
+    /// Returns `false` if `param` is not a variadic tuple with one variadic
+    /// element.
+    fn infer_type_using_variadic_tuple(
+        &mut self,
+        span: Span,
+        inferred: &mut InferData,
+        param: &Tuple,
+        arg: &Tuple,
+        opts: InferTypeOpts,
+    ) -> VResult<bool> {
+        let mut rest_positions = param
+            .elems
+            .iter()
+            .enumerate()
+            .filter(|(_, elem)| matches!(elem.ty.normalize(), Type::Rest(..)))
+            .map(|(idx, _)| idx);
+        let rest_idx = match (rest_positions.next(), rest_positions.next()) {
+            (Some(idx), None) => idx,
+            _ => return Ok(false),
+        };
+        let rest_ty = match param.elems[rest_idx].ty.normalize() {
+            Type::Rest(rest) => &*rest.ty,
+            _ => unreachable!(),
+        };
+        // `...number[]` is handled by the element-wise inference.
+        if rest_ty.normalize().is_array() {
+            return Ok(false);
+        }
+
+        let head = &param.elems[..rest_idx];
+        let tail = &param.elems[rest_idx + 1..];
+
+        // Fixed elements of `arg` which can be matched from the end.
+        let arg_rest_idx = arg.elems.iter().position(|elem| matches!(elem.ty.normalize(), Type::Rest(..)));
+        let fixed_tail_len = arg.elems.len() - arg_rest_idx.map(|idx| idx + 1).unwrap_or(0);
+        let fixed_head_len = arg_rest_idx.unwrap_or(arg.elems.len());
+
+        if head.len() > fixed_head_len || tail.len() > fixed_tail_len.min(arg.elems.len() - head.len()) {
+            return Ok(false);
+        }
+
+        for (p, a) in head.iter().zip(arg.elems.iter()) {
+            self.infer_type(span, inferred, &p.ty, &a.ty, opts)?;
+        }
+
+        let middle_end = arg.elems.len() - tail.len();
+        for (p, a) in tail.iter().zip(arg.elems[middle_end..].iter()) {
+            self.infer_type(span, inferred, &p.ty, &a.ty, opts)?;
+        }
+
+        let middle = Type::Tuple(Tuple {
+            span: arg.span,
+            elems: ALLOW_DEEP_CLONE.set(&(), || arg.elems[head.len()..middle_end].to_vec()),
+            metadata: TupleMetadata {
+                prevent_tuple_to_array: true,
+                common: arg.metadata.common,
+            },
+            tracker: Default::default(),
+        })
+        .freezed();
+
+        self.infer_type(span, inferred, rest_ty, &middle, opts)?;
+
+        Ok(true)
+    }
+
     fn infer_type_of_fn_param(
         &mut self,
         span: Span,
@@ -2125,7 +2204,14 @@ impl Analyzer<'_, '_> {
         arg: &FnParam,
         opts: InferTypeOpts,
     ) -> VResult<()> {
-        self.infer_type(
+        // Parameters are in contravariant positions, unless parameters are checked
+        // bivariantly.
+        let old = inferred.contravariant;
+        if !opts.for_fn_assignment && self.rule().strict_function_types {
+            inferred.contravariant = !old;
+        }
+
+        let res = self.infer_type(
             span,
             inferred,
             &param.ty,
@@ -2135,7 +2221,11 @@ impl Analyzer<'_, '_> {
 
                 ..opts
             },
-        )
+        );
+
+        inferred.contravariant = old;
+
+        res
     }
 
     fn infer_type_of_fn_params(
@@ -2146,7 +2236,29 @@ impl Analyzer<'_, '_> {
         args: &[FnParam],
         opts: InferTypeOpts,
     ) -> VResult<()> {
-        for (param, arg) in params.iter().zip(args) {
+        for (idx, (param, arg)) in params.iter().zip(args).enumerate() {
+            // `(...args: infer P) => void` captures the remaining parameters as a tuple,
+            // like `[a: string, b?: number]`.
+            if matches!(param.pat, RPat::Rest(..)) && param.ty.normalize().is_type_param() {
+                let tuple = Type::Tuple(Tuple {
+                    span: arg.span,
+                    elems: args[idx..].iter().map(TupleElement::from_param).collect(),
+                    metadata: TupleMetadata {
+                        prevent_tuple_to_array: true,
+                        ..Default::default()
+                    },
+                    tracker: Default::default(),
+                })
+                .freezed();
+
+                let tuple_param = FnParam {
+                    ty: box tuple,
+                    ..param.clone()
+                };
+
+                return self.infer_type_of_fn_param(span, inferred, param, &tuple_param, opts);
+            }
+
             self.infer_type_of_fn_param(span, inferred, param, arg, opts)?
         }
 
@@ -33,6 +33,11 @@ impl Analyzer<'_, '_> {
         }
 
         for idx in order {
+            // Statements which are not analyzed are ignored, as the result is not used.
+            if self.is_cancelled() {
+                break;
+            }
+
             if self.scope.is_root() {
                 let module_id = self.storage.module_id(idx);
                 self.ctx.module_id = module_id;
@@ -1,19 +1,25 @@
+use std::mem::take;
+
 #[cfg(not(feature = "no-threading"))]
 use rayon::prelude::*;
 use rnode::{Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RCallExpr, RCallee, RExportAll, RExpr, RImportDecl, RImportSpecifier, RLit, RModuleItem, RNamedExport, RStr, RTsExternalModuleRef,
+    RCallExpr, RCallee, RExportAll, RExpr, RExprOrSpread, RImportDecl, RImportSpecifier, RKeyValueProp, RLit, RModuleItem, RNamedExport,
+    RObjectLit, RProp, RPropOrSpread, RStr, RTpl, RTsEntityName, RTsExternalModuleRef, RTsImportEqualsDecl, RTsImportType,
 };
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{BuiltIn, ModuleKind};
 use stc_ts_errors::ErrorKind;
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_storage::Storage;
-use stc_ts_types::{Id, ModuleId, Type};
-use stc_ts_utils::imports::find_imports_in_comments;
+use stc_ts_types::{ExportEquals, Id, IdCtx, ImportType, Key, ModuleId, Type};
+use stc_ts_utils::imports::{find_imports_in_comments, ImportRef};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{comments::Comments, Span, Spanned, GLOBALS};
 
 use crate::{
-    analyzer::{scope::VarKind, util::ResultExt, Analyzer},
+    analyzer::{expr::TypeOfMode, scope::VarKind, util::ResultExt, Analyzer},
+    env::BuiltInGen,
     loader::ModuleInfo,
     validator, DepInfo, VResult,
 };
@@ -30,13 +36,22 @@ impl Analyzer<'_, '_> {
         let dep_id = match dep_id {
             Some(v) => v,
             None => {
-                self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                self.storage.report(self.loader.module_not_found_error(span, &base, dst));
 
                 return (ctxt, Type::any(span, Default::default()));
             }
         };
+        if dst.ends_with(".json") && !self.rule().resolve_json_module {
+            self.storage.report(ErrorKind::JsonModuleWithoutResolveJsonModule { span }.into());
+
+            return (ctxt, Type::any(span, Default::default()));
+        }
         let data = match self.imports.get(&(ctxt, dep_id)).cloned() {
             Some(v) => v,
+            None if self.loader.is_in_same_circular_group(&base, dst) => {
+                // Items are resolved lazily, by `handle_circular_import`.
+                return (dep_id, Type::any(span, Default::default()));
+            }
             None => {
                 self.storage.report(ErrorKind::ModuleNotFound { span }.into());
 
@@ -47,6 +62,16 @@ impl Analyzer<'_, '_> {
         (dep_id, data)
     }
 
+    /// Returns the module loaded by `require('foo')`, without reporting an
+    /// error if it's not loaded.
+    pub(super) fn find_required_module(&self, src: &JsWord) -> Option<Type> {
+        let ctxt = self.ctx.module_id;
+        let base = self.storage.path(ctxt);
+        let dep_id = self.loader.module_id(&base, src)?;
+
+        self.imports.get(&(ctxt, dep_id)).cloned()
+    }
+
     pub(super) fn find_imported_var(&self, id: &Id) -> VResult<Option<Type>> {
         if let Some(ModuleInfo { module_id, data }) = self.imports_by_id.get(id) {
             match data.normalize() {
@@ -66,6 +91,88 @@ impl Analyzer<'_, '_> {
         Ok(None)
     }
 
+    /// Resolves a variable imported from a module in the same circular group.
+    ///
+    /// The exports of the module are read at the time of use, instead of the
+    /// time of import, because the module may not be analyzed yet.
+    pub(super) fn find_circular_import_var(&self, id: &Id) -> Option<Type> {
+        let import = self.data.circular_imports.get(id)?;
+
+        match self.storage.get_exported_var(import.module_id, import.orig.sym()) {
+            Some(ty) => Some(ty),
+            // The type of the variable is not known yet.
+            None => Some(Type::any(import.span, Default::default())),
+        }
+    }
+
+    /// Resolves a type imported from a module in the same circular group.
+    ///
+    /// See [Self::find_circular_import_var].
+    pub(super) fn find_circular_import_types(&self, id: &Id) -> Option<Vec<Type>> {
+        let import = self.data.circular_imports.get(id)?;
+
+        self.storage.get_exported_types(import.module_id, import.orig.sym())
+    }
+
+    /// Reports imports from modules in the same circular group which cannot be
+    /// resolved using the final exports of the modules.
+    pub(super) fn report_unresolved_circular_imports(&mut self) {
+        let imports = take(&mut self.data.circular_imports);
+
+        for (id, import) in &imports {
+            if self.storage.get_exported_var(import.module_id, import.orig.sym()).is_some()
+                || self.storage.get_exported_types(import.module_id, import.orig.sym()).is_some()
+            {
+                continue;
+            }
+
+            self.storage.report(
+                ErrorKind::ImportFailed {
+                    span: import.span,
+                    orig: import.orig.clone(),
+                    id: id.clone(),
+                }
+                .into(),
+            );
+        }
+
+        self.data.circular_imports = imports;
+    }
+
+    /// Resolves `import('foo').Bar` in a type, or `typeof import('foo').bar`
+    /// if `id_ctx` is [IdCtx::Var].
+    pub(crate) fn resolve_import_type(&mut self, span: Span, ty: &ImportType, id_ctx: IdCtx) -> VResult<Type> {
+        let (dep, data) = self.get_imported_items(span, &ty.arg.value);
+        if dep == self.ctx.module_id {
+            // Import failed, and the error is already reported.
+            return Ok(Type::any(span, Default::default()));
+        }
+
+        match &ty.qualifier {
+            Some(qualifier) => self.access_import_qualifier(span, &data, qualifier, id_ctx),
+            None => Ok(data),
+        }
+    }
+
+    fn access_import_qualifier(&mut self, span: Span, module: &Type, name: &RTsEntityName, id_ctx: IdCtx) -> VResult<Type> {
+        let (obj, prop) = match name {
+            RTsEntityName::Ident(i) => (module.clone(), i),
+            RTsEntityName::TsQualifiedName(q) => (self.access_import_qualifier(span, module, &q.left, IdCtx::Type)?, &q.right),
+        };
+
+        self.access_property(
+            span,
+            &obj,
+            &Key::Normal {
+                span: prop.span,
+                sym: prop.sym.clone(),
+            },
+            TypeOfMode::RValue,
+            id_ctx,
+            Default::default(),
+        )
+    }
+
     fn insert_import_info(&mut self, ctxt: ModuleId, dep_module_id: ModuleId, ty: Type) -> VResult<()> {
         self.imports.entry((ctxt, dep_module_id)).or_insert(ty);
 
@@ -78,7 +185,28 @@ impl Analyzer<'_, '_> {
             return;
         }
         // We first load non-circular imports.
-        let imports = ImportFinder::find_imports(&self.comments, module_spans, &self.storage, items);
+        let (mut imports, references) = ImportFinder::find_imports(&self.comments, module_spans, &self.storage, items);
+
+        for (ctxt, span, reference) in references {
+            let err = match &reference {
+                ImportRef::Lib(name) => {
+                    self.load_referenced_lib(span, name);
+                    continue;
+                }
+                ImportRef::Path(path) => ErrorKind::ReferencedFileNotFound { span, path: path.clone() },
+                ImportRef::Types(name) => ErrorKind::TypeDefinitionNotFound { span, name: name.clone() },
+                ImportRef::Normal(..) => unreachable!("comments cannot contain normal imports"),
+            };
+            let src = reference.to_path().unwrap();
+
+            let base = self.storage.path(ctxt);
+            if self.loader.module_id(&base, &src).is_none() {
+                self.storage.report(err.into());
+                continue;
+            }
+
+            imports.push((ctxt, DepInfo { span, src }));
+        }
 
         let loader = self.loader;
         let mut normal_imports = vec![];
@@ -95,6 +223,12 @@ impl Analyzer<'_, '_> {
                 }
             };
 
+            if self.rule().force_consistent_casing_in_file_names {
+                if let Some(err) = loader.file_name_casing_error(span, &base, &import.src) {
+                    self.storage.report(err);
+                }
+            }
+
             if loader.is_in_same_circular_group(&base, &import.src) {
                 continue;
             }
@@ -122,6 +256,8 @@ impl Analyzer<'_, '_> {
 
             match res {
                 Ok(info) => {
+                    // Augmentations are recorded while analyzing other imports.
+                    let info = self.apply_module_augmentations(info, loader.module_augmentations(dep_id));
                     self.insert_import_info(ctxt, dep_id, info).report(&mut self.storage);
                 }
                 Err(err) => self.storage.report(err),
@@ -131,14 +267,150 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
+    /// Adds a builtin library referenced by `/// <reference lib="..." />` to
+    /// the environment, which is shared by all files of the program.
+    fn load_referenced_lib(&mut self, span: Span, name: &JsWord) {
+        let libs = Lib::load(name);
+        if libs.is_empty() {
+            self.storage
+                .report(ErrorKind::LibDefinitionNotFound { span, name: name.clone() }.into());
+            return;
+        }
+
+        let libs = libs.into_iter().filter(|lib| self.env.register_lib(lib.name())).collect::<Vec<_>>();
+        if libs.is_empty() {
+            return;
+        }
+
+        let builtin = BuiltIn::from_ts_libs(self.env.shared(), &libs);
+        self.env.add_builtin_lib(&builtin);
+    }
+
+    /// Declares `foo` of `import foo = require('./foo')` as the entity
+    /// exported by `export =` of the module.
+    pub(super) fn import_export_equals(&mut self, node: &RTsImportEqualsDecl, export: &ExportEquals) -> VResult<()> {
+        let id: Id = node.id.clone().into();
+
+        for ty in &export.types {
+            self.register_type(id.clone(), ty.clone());
+            if node.is_export {
+                self.storage
+                    .reexport_type(node.span, self.ctx.module_id, node.id.sym.clone(), ty.clone());
+            }
+        }
+
+        if let Some(ty) = &export.var {
+            self.declare_var(node.span, VarKind::Import, id, Some(ty.clone()), None, true, false, false)?;
+            if node.is_export {
+                self.storage
+                    .reexport_var(node.span, self.ctx.module_id, node.id.sym.clone(), ty.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the module specifier of `require('foo')`, which can be a string
+/// literal or a template literal without substitutions.
+pub(crate) fn require_specifier(args: &[RExprOrSpread]) -> Option<JsWord> {
+    match args.first()? {
+        RExprOrSpread { spread: None, expr } => match &**expr {
+            RExpr::Lit(RLit::Str(RStr { value, .. })) => Some(value.clone()),
+            RExpr::Tpl(RTpl { exprs, quasis, .. }) if exprs.is_empty() => quasis.first()?.cooked.as_ref().map(|v| JsWord::from(&**v)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `name` exported by the module `data` is a type and not
+/// a value.
+pub(super) fn is_type_only_export(data: &Type, name: &JsWord) -> bool {
+    match data.normalize() {
+        Type::Module(data) => {
+            let is_type = match data.exports.types.get(name) {
+                Some(types) => types.iter().all(|ty| !ty.is_module() && !ty.is_namespace()),
+                None => false,
+            };
+
+            is_type && !data.exports.vars.contains_key(name)
+        }
+        _ => false,
+    }
+}
+
+/// An import from a module in the same circular group.
+#[derive(Debug, Clone)]
+pub(super) struct CircularImport {
+    span: Span,
+    module_id: ModuleId,
+    orig: Id,
+}
+
+impl Analyzer<'_, '_> {
+    /// Imported items are not copied, because the module exporting them may
+    /// not be analyzed yet. Instead, they are resolved using the exports of
+    /// the module at the time of use.
+    fn handle_circular_import(&mut self, span: Span, target: ModuleId, orig: Id, id: Id) {
+        self.data.circular_imports.insert(
+            id,
+            CircularImport {
+                span,
+                module_id: target,
+                orig,
+            },
+        );
+    }
+
+    /// `verbatimModuleSyntax` requires `import type` for imports of types.
+    fn report_type_imported_without_type_only_import(&mut self, span: Span, data: &Type, orig: &Id) {
+        if is_type_only_export(data, orig.sym()) {
+            self.storage
+                .report(ErrorKind::TypeImportedWithoutTypeOnlyImport { span, name: orig.clone() }.into());
+        }
+    }
+
+    /// Validates `with { type: "json" }` of an import.
+    fn check_import_attributes(&mut self, type_only: bool, attrs: &RObjectLit) {
+        if type_only {
+            self.storage
+                .report(ErrorKind::ImportAssertionsWithTypeOnlyImport { span: attrs.span }.into());
+            return;
+        }
+
+        if !matches!(self.env.module().kind, ModuleKind::EsNext | ModuleKind::NodeNext) {
+            self.storage
+                .report(ErrorKind::ImportAssertionsNotSupported { span: attrs.span }.into());
+        }
+
+        for prop in &attrs.props {
+            if let RPropOrSpread::Prop(box RProp::KeyValue(RKeyValueProp { value, .. })) = prop {
+                if !matches!(&**value, RExpr::Lit(RLit::Str(..))) {
+                    self.storage
+                        .report(ErrorKind::ImportAssertionValueNotString { span: value.span() }.into());
+                }
+            }
+        }
+    }
+
+    /// `allowSyntheticDefaultImports`, which is implied by `esModuleInterop`.
+    fn allows_synthetic_default_imports(&self) -> bool {
+        self.rule().allow_synthetic_default_imports || self.rule().es_module_interop
+    }
+
     fn handle_import(&mut self, span: Span, ctxt: ModuleId, target: ModuleId, orig: Id, id: Id) {
         let mut found_entry = false;
+        let mut deprecation = None;
+        let allows_synthetic_default_imports = self.allows_synthetic_default_imports();
 
         // Check for entry only if import was successful.
         if ctxt != target {
             if let Some(data) = self.imports.get(&(ctxt, target)) {
                 match data.normalize() {
                     Type::Module(data) => {
+                        deprecation = data.exports.deprecations.get(orig.sym()).cloned();
+
                         for (i, ty) in &data.exports.vars {
                             if orig.sym() == i {
                                 found_entry = true;
@@ -154,6 +426,26 @@ impl Analyzer<'_, '_> {
                                 }
                             }
                         }
+
+                        // A CommonJS module does not have a default export, but it can be synthesized.
+                        if !found_entry && *orig.sym() == js_word!("default") {
+                            if let Some(export) = &data.exports.export_equals {
+                                found_entry = true;
+
+                                if allows_synthetic_default_imports {
+                                    if let Some(ty) = &export.var {
+                                        self.storage.store_private_var(ctxt, id.clone(), ty.clone());
+                                    }
+                                    for ty in &export.types {
+                                        self.storage.store_private_type(ctxt, id.clone(), ty.clone(), false);
+                                    }
+                                } else {
+                                    self.storage
+                                        .store_private_var(ctxt, id.clone(), Type::any(span, Default::default()));
+                                    self.storage.report(ErrorKind::DefaultImportRequiresEsModuleInterop { span }.into());
+                                }
+                            }
+                        }
                     }
                     _ => {
                         unreachable!()
@@ -162,6 +454,10 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        if let Some(deprecation) = &deprecation {
+            self.import_deprecation(span, &id, deprecation);
+        }
+
         if !found_entry {
             self.data.unresolved_imports.insert(id.clone());
 
@@ -193,20 +489,39 @@ impl Analyzer<'_, '_> {
         let span = node.span;
         let base = self.ctx.module_id;
 
+        if let Some(attrs) = &node.asserts {
+            self.check_import_attributes(node.type_only, attrs);
+        }
+
         let (dep, data) = self.get_imported_items(span, &node.src.value);
+        let is_circular = base != dep && !self.imports.contains_key(&(base, dep));
 
         for specifier in &node.specifiers {
+            let (local, is_type_only) = match specifier {
+                RImportSpecifier::Named(named) => (&named.local, node.type_only || named.is_type_only),
+                RImportSpecifier::Default(default) => (&default.local, node.type_only),
+                RImportSpecifier::Namespace(ns) => (&ns.local, node.type_only),
+            };
+            if is_type_only {
+                self.data.type_only_imports.insert(Id::from(local));
+            }
+
             match specifier {
+                RImportSpecifier::Named(named) if is_circular => {
+                    let orig = named.imported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.local));
+                    self.handle_circular_import(named.span, dep, orig, Id::from(&named.local));
+                }
+                RImportSpecifier::Default(default) if is_circular => {
+                    self.handle_circular_import(default.span, dep, Id::word(js_word!("default")), Id::from(&default.local));
+                }
                 RImportSpecifier::Named(named) => {
-                    //
-                    match &named.imported {
-                        Some(imported) => {
-                            self.handle_import(named.span, base, dep, Id::from(imported), Id::from(&named.local));
-                        }
-                        None => {
-                            self.handle_import(named.span, base, dep, Id::from(&named.local), Id::from(&named.local));
-                        }
+                    let orig = named.imported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.local));
+
+                    if !is_type_only && base != dep && self.rule().verbatim_module_syntax {
+                        self.report_type_imported_without_type_only_import(named.span, &data, &orig);
                     }
+
+                    self.handle_import(named.span, base, dep, orig, Id::from(&named.local));
                 }
                 RImportSpecifier::Default(default) => {
                     self.handle_import(default.span, base, dep, Id::word(js_word!("default")), Id::from(&default.local));
@@ -225,11 +540,29 @@ impl Analyzer<'_, '_> {
                             false,
                         )?;
                     } else {
+                        let export_equals = match data.normalize() {
+                            Type::Module(m) => m.exports.export_equals.as_ref(),
+                            _ => None,
+                        };
+
+                        // Without `esModuleInterop`, a namespace import of a CommonJS module is the
+                        // entity exported by `export =`, so it may be callable or constructable.
+                        let ty = match export_equals {
+                            Some(export) if !self.rule().es_module_interop => {
+                                for ty in &export.types {
+                                    self.register_type(ns.local.clone().into(), ty.clone());
+                                }
+
+                                export.var.clone().unwrap_or_else(|| data.clone())
+                            }
+                            _ => data.clone(),
+                        };
+
                         self.declare_var(
                             ns.span,
                             VarKind::Import,
                             ns.local.clone().into(),
-                            Some(data.clone()),
+                            Some(ty),
                             None,
                             true,
                             false,
@@ -251,6 +584,8 @@ where
     storage: &'a Storage<'a>,
     cur_ctxt: ModuleId,
     to: Vec<(ModuleId, DepInfo)>,
+    /// Triple-slash directives.
+    references: Vec<(ModuleId, Span, ImportRef)>,
     comments: C,
 }
 
@@ -266,11 +601,18 @@ where
         let ctxt = self.cur_ctxt;
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.to
-            .extend(deps.into_iter().map(|src| (ctxt, DepInfo { span, src: src.to_path() })));
+        self.references
+            .extend(deps.into_iter().map(|(span, reference)| (ctxt, span, reference)));
     }
 
-    pub fn find_imports<T>(comments: C, module_span: Vec<(ModuleId, Span)>, storage: &'a Storage<'a>, node: &T) -> Vec<(ModuleId, DepInfo)>
+    /// Returns `(imports, triple-slash directives)`.
+    #[allow(clippy::type_complexity)]
+    pub fn find_imports<T>(
+        comments: C,
+        module_span: Vec<(ModuleId, Span)>,
+        storage: &'a Storage<'a>,
+        node: &T,
+    ) -> (Vec<(ModuleId, DepInfo)>, Vec<(ModuleId, Span, ImportRef)>)
     where
         T: for<'any> VisitWith<ImportFinder<'any, C>>,
     {
@@ -278,6 +620,7 @@ where
             comments,
             storage,
             to: Default::default(),
+            references: Default::default(),
             cur_ctxt: ModuleId::builtin(),
         };
 
@@ -290,7 +633,7 @@ where
 
         node.visit_with(&mut v);
 
-        v.to
+        (v.to, v.references)
     }
 }
 
@@ -318,21 +661,16 @@ where
     C: Comments,
 {
     /// Extracts require('foo')
+    ///
+    /// Like tsc, dynamic requires like `require(name)` are ignored.
     fn visit(&mut self, expr: &RCallExpr) {
         let span = expr.span();
 
         match expr.callee {
             RCallee::Expr(box RExpr::Ident(ref i)) if i.sym == js_word!("require") => {
-                let src = expr
-                    .args
-                    .iter()
-                    .map(|v| match *v.expr {
-                        RExpr::Lit(RLit::Str(RStr { ref value, .. })) => value.clone(),
-                        _ => unimplemented!("error reporting for dynamic require"),
-                    })
-                    .next()
-                    .unwrap();
-                self.to.push((self.cur_ctxt, DepInfo { span, src }));
+                if let Some(src) = require_specifier(&expr.args) {
+                    self.to.push((self.cur_ctxt, DepInfo { span, src }));
+                }
             }
             _ => {}
         }
@@ -404,3 +742,21 @@ where
         ));
     }
 }
+
+impl<C> Visit<RTsImportType> for ImportFinder<'_, C>
+where
+    C: Comments,
+{
+    /// Extracts `import('foo')` in types.
+    fn visit(&mut self, import: &RTsImportType) {
+        import.visit_children_with(self);
+
+        self.to.push((
+            self.cur_ctxt,
+            DepInfo {
+                span: import.span,
+                src: import.arg.value.clone(),
+            },
+        ));
+    }
+}
@@ -15,19 +15,20 @@ use stc_ts_ast_rnode::{
 };
 use stc_ts_base_type_ops::bindings::Bindings;
 use stc_ts_dts_mutations::Mutations;
-use stc_ts_env::{Env, Marks, ModuleConfig, Rule, StableEnv};
-use stc_ts_errors::{debug::debugger::Debugger, DebugExt, ErrorKind};
+use stc_ts_env::{Env, Marks, ModuleKind, Rule, StableEnv};
+use stc_ts_errors::{debug::debugger::Debugger, DebugExt, Error, ErrorKind};
 use stc_ts_storage::{Builtin, Info, Storage};
 use stc_ts_type_cache::TypeCache;
-use stc_ts_types::{type_id::DestructureId, Id, IdCtx, ModuleId, ModuleTypeData, Namespace};
+use stc_ts_types::{type_id::DestructureId, Deprecation, Id, IdCtx, ModuleId, ModuleTypeData, Namespace};
 use stc_ts_utils::StcComments;
 use stc_utils::{cache::Freeze, AHashMap, AHashSet};
 use swc_atoms::{js_word, JsWord};
-use swc_common::{FileName, SourceMap, Span, DUMMY_SP, GLOBALS};
+use swc_common::{FileName, SourceMap, Span, Spanned, DUMMY_SP, GLOBALS};
 use swc_ecma_ast::*;
 
 use self::{
     control_flow::{CondFacts, Facts},
+    import::CircularImport,
     pat::PatMode,
     props::ComputedPropMode,
     scope::{Scope, VarKind},
@@ -35,7 +36,9 @@ use self::{
 };
 pub(crate) use self::{scope::ScopeKind, types::NormalizeTypeOpts};
 use crate::{
+    cancellation::CancellationToken,
     loader::{Load, ModuleInfo},
+    recorder::TypeRecorder,
     ty,
     ty::Type,
     validator,
@@ -57,6 +60,7 @@ mod class;
 mod control_flow;
 mod convert;
 mod decl_merging;
+mod deprecation;
 mod enums;
 mod export;
 mod expr;
@@ -66,6 +70,7 @@ mod generic;
 mod hoisting;
 mod import;
 mod pat;
+mod pragma;
 mod props;
 mod relation;
 mod scope;
@@ -181,6 +186,9 @@ pub(crate) struct Ctx {
 
     disallow_suggesting_property_on_no_var: bool,
 
+    /// `true` while resolving `typeof foo` in a type.
+    in_type_query: bool,
+
     /// Should be modified directly instead of using `with_ctx`.
     in_unreachable: bool,
 
@@ -248,6 +256,10 @@ pub struct Analyzer<'scope, 'b> {
 
     debugger: Option<Debugger>,
 
+    type_recorder: Option<Arc<dyn TypeRecorder>>,
+
+    cancellation: Option<CancellationToken>,
+
     data: AnalyzerData,
 
     destructure_count: Rc<Cell<DestructureId>>,
@@ -272,6 +284,10 @@ struct AnalyzerData {
 
     unresolved_imports: AHashSet<Id>,
 
+    /// Imports from modules in the same circular group, which are resolved
+    /// lazily.
+    circular_imports: FxHashMap<Id, CircularImport>,
+
     /// Spans of declared variables.
     var_spans: AHashMap<Id, Vec<(VarKind, Span)>>,
 
@@ -291,6 +307,34 @@ struct AnalyzerData {
     cache: TypeCache,
 
     checked_for_async_iterator: bool,
+
+    /// Bindings of `import type`, which cannot be used as values.
+    type_only_imports: FxHashSet<Id>,
+
+    /// `true` if the file has an import or an export, so that
+    /// `declare module "foo" {}` is an augmentation.
+    is_external_module: bool,
+
+    /// `true` if the file is a script, which shares the global scope with
+    /// other scripts. Unlike `!is_external_module`, this is `false` while
+    /// analyzing builtin libraries or a circular group of modules.
+    is_script: bool,
+
+    /// The module which provided each name re-exported by `export *`.
+    ///
+    /// [None] if the name is ambiguous.
+    star_exports: FxHashMap<(ModuleId, JsWord), Option<ModuleId>>,
+
+    /// `(span, ctxt, dep)` of `export *` from a module in the same circular
+    /// group, which are resolved after all modules are analyzed.
+    circular_star_exports: Vec<(Span, ModuleId, ModuleId)>,
+
+    /// Declarations with `@deprecated`, including imported ones.
+    deprecations: FxHashMap<Id, Deprecation>,
+
+    /// Reported references to deprecated declarations, because an expression
+    /// can be validated multiple times.
+    deprecated_references: FxHashSet<Span>,
 }
 
 /// Configuration for the analyzer.
@@ -350,6 +394,8 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RScript) -> VResult<ty::Module> {
         let span = node.span;
 
+        self.apply_rule_pragmas(span);
+
         let (errors, data) = {
             let mut new = self.new(Scope::root(), Default::default());
             {
@@ -405,13 +451,43 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             Scope::root(),
             false,
             debugger,
+            None,
+            None,
             Default::default(),
         )
     }
 
+    /// Passes the types of expressions to `recorder`.
+    pub fn with_type_recorder(mut self, recorder: Arc<dyn TypeRecorder>) -> Self {
+        self.type_recorder = Some(recorder);
+        self
+    }
+
+    /// Stops analysis when `token` is cancelled. See [crate::cancellation].
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation.as_ref().map_or(false, |token| token.is_cancelled())
+    }
+
+    /// Returns [ErrorKind::Cancelled] if the analysis is cancelled.
+    ///
+    /// This is cheap, and should be called at the boundaries of files and
+    /// statements.
+    pub(crate) fn check_cancelled(&self, span: Span) -> VResult<()> {
+        if self.is_cancelled() {
+            return Err(ErrorKind::Cancelled { span }.into());
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn for_builtin(env: StableEnv, storage: &'b mut Builtin) -> Self {
         Self::new_inner(
-            Env::new(env, Default::default(), EsVersion::latest(), ModuleConfig::None, Default::default()),
+            Env::new(env, Default::default(), EsVersion::latest(), ModuleKind::None, Default::default()),
             Arc::new(SourceMap::default()),
             Default::default(),
             box storage,
@@ -420,6 +496,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             Scope::root(),
             true,
             None,
+            None,
+            None,
             Default::default(),
         )
     }
@@ -436,6 +514,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             scope,
             self.config.is_builtin,
             self.debugger.clone(),
+            self.type_recorder.clone(),
+            self.cancellation.clone(),
             data,
         )
     }
@@ -450,6 +530,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         scope: Scope<'scope>,
         is_builtin: bool,
         debugger: Option<Debugger>,
+        type_recorder: Option<Arc<dyn TypeRecorder>>,
+        cancellation: Option<CancellationToken>,
         data: AnalyzerData,
     ) -> Self {
         let is_dts = storage.is_dts();
@@ -516,6 +598,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 cannot_fallback_to_iterable_iterator: false,
                 allow_new_target: false,
                 disallow_suggesting_property_on_no_var: false,
+                in_type_query: false,
                 in_unreachable: false,
                 is_not_topmost_type: false,
                 is_fn_param: false,
@@ -529,6 +612,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             mapped_type_param_name: vec![],
             imports_by_id: Default::default(),
             debugger,
+            type_recorder,
+            cancellation,
             data,
             destructure_count: Default::default(),
         }
@@ -713,6 +798,14 @@ impl Load for NoopLoader {
         unreachable!()
     }
 
+    fn module_not_found_error(&self, span: Span, base: &Arc<FileName>, src: &str) -> Error {
+        unreachable!()
+    }
+
+    fn file_name_casing_error(&self, span: Span, base: &Arc<FileName>, src: &str) -> Option<Error> {
+        unreachable!()
+    }
+
     fn is_in_same_circular_group(&self, base: &Arc<FileName>, dep: &str) -> bool {
         unreachable!()
     }
@@ -728,15 +821,30 @@ impl Load for NoopLoader {
     fn declare_module(&self, name: &JsWord, module: Type) {
         unreachable!()
     }
+
+    fn augment_module(&self, base: &Arc<FileName>, src: &str, augmentation: Type) {
+        unreachable!()
+    }
+
+    fn module_augmentations(&self, module_id: ModuleId) -> Vec<Type> {
+        unreachable!()
+    }
 }
 
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, modules: &Vec<RModule>) {
+        if let Some(m) = modules.first() {
+            self.check_cancelled(m.span)?;
+        }
+
         self.ctx.in_module = true;
 
         let mut items = vec![];
         for m in modules {
+            let module_id = self.storage.module_id(items.len());
+            self.collect_deprecations(module_id, m);
+
             items.extend(&m.body);
         }
         // TODO: Pass spans.
@@ -746,6 +854,9 @@ impl Analyzer<'_, '_> {
 
         self.validate_stmts_with_hoisting(&items);
 
+        self.resolve_circular_star_exports();
+        self.report_unresolved_circular_imports();
+
         Ok(())
     }
 }
@@ -753,14 +864,22 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, m: &RModule) {
+        self.check_cancelled(m.span)?;
+
         self.ctx.in_module = true;
         let is_dts = self.config.is_dts;
 
+        self.apply_rule_pragmas(m.span);
+        self.collect_deprecations(self.ctx.module_id, m);
+
         debug_assert!(GLOBALS.is_set(), "Analyzer requires swc_common::GLOBALS");
 
         let ctxt = self.storage.module_id(0);
         let path = self.storage.path(ctxt);
 
+        self.data.is_external_module = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
+        self.data.is_script = !self.data.is_external_module && !self.config.is_builtin;
+
         let items_ref = m.body.iter().collect::<Vec<_>>();
         self.load_normal_imports(vec![(ctxt, m.span)], &items_ref);
 
@@ -810,6 +929,9 @@ impl Analyzer<'_, '_> {
             self.validate_stmts_and_collect(&items_ref);
         }
 
+        self.report_unused_decls(m);
+        self.report_isolated_modules_violations(m);
+
         Ok(())
     }
 }
@@ -820,6 +942,8 @@ impl Analyzer<'_, '_> {
         self.fill_known_type_names(&items);
 
         for item in items.iter() {
+            self.check_cancelled(item.span())?;
+
             item.visit_with(self);
         }
 
@@ -874,11 +998,25 @@ impl Analyzer<'_, '_> {
 
                     // Import successful
                     if ctxt != dep {
-                        analyzer
+                        let data = analyzer
                             .imports
                             .get(&(ctxt, dep))
                             .cloned()
-                            .unwrap_or_else(|| Type::any(e.span, Default::default()))
+                            .unwrap_or_else(|| Type::any(e.span, Default::default()));
+
+                        if let Type::Module(ty::Module {
+                            exports:
+                                box ModuleTypeData {
+                                    export_equals: Some(export),
+                                    ..
+                                },
+                            ..
+                        }) = data.normalize()
+                        {
+                            return analyzer.import_export_equals(node, export);
+                        }
+
+                        data
                     } else {
                         Type::any(e.span, Default::default())
                     }
@@ -1049,7 +1187,17 @@ impl Analyzer<'_, '_> {
                         }
                     }
 
-                    self.loader.declare_module(&s.value, ty.clone());
+                    if self.data.is_external_module && !is_builtin {
+                        let base = self.storage.path(ctxt);
+                        if self.loader.module_id(&base, &s.value).is_some() {
+                            self.loader.augment_module(&base, &s.value, ty.clone());
+                        } else {
+                            self.storage
+                                .report(ErrorKind::InvalidModuleNameInAugmentation { span: s.span }.into());
+                        }
+                    } else {
+                        self.loader.declare_module(&s.value, ty.clone());
+                    }
                 }
             }
         }
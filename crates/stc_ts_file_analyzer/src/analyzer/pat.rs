@@ -111,6 +111,7 @@ impl Analyzer<'_, '_> {
                                 type_params: None,
                                 metadata: Default::default(),
                                 accessor: Default::default(),
+                                write_type: Default::default(),
                             }))
                         }
                         RObjectPatProp::Assign(RAssignPatProp { key, .. }) => {
@@ -132,6 +133,7 @@ impl Analyzer<'_, '_> {
                                     ..Default::default()
                                 },
                                 accessor: Default::default(),
+                                write_type: Default::default(),
                             }))
                         }
                         RObjectPatProp::Rest(..) => {}
@@ -273,6 +275,7 @@ impl Analyzer<'_, '_> {
                         DeclareVarsOpts {
                             kind: VarKind::Param,
                             use_iterator_for_array: false,
+                            has_type_ann: false,
                         },
                     ) {
                         Ok(Some(v)) => Some(v),
@@ -0,0 +1,103 @@
+//! Per-file rule overrides.
+//!
+//! A file can opt into stricter checks with a leading line comment.
+//!
+//! ```ts
+//! // @stc-strict
+//! // @stc-strict strictNullChecks noImplicitAny
+//! ```
+//!
+//! Without arguments, all rules enabled by `--strict` are turned on. Pragmas
+//! can only tighten rules. There's no way to disable a rule enabled by the
+//! project.
+
+use stc_ts_env::Rule;
+use stc_ts_errors::{ErrorKind, Errors};
+use swc_common::{
+    comments::{CommentKind, Comments},
+    BytePos, Span,
+};
+
+use crate::analyzer::Analyzer;
+
+const PRAGMA: &str = "@stc-strict";
+
+impl Analyzer<'_, '_> {
+    /// Applies `// @stc-strict` pragmas in the leading comments of a file.
+    ///
+    /// Should be called before analyzing any item of the file.
+    pub(super) fn apply_rule_pragmas(&mut self, file_span: Span) {
+        if file_span.lo == BytePos(0) {
+            return;
+        }
+
+        let mut rule = self.rule();
+        let mut errors = Errors::default();
+
+        self.comments.with_leading(file_span.lo, |comments| {
+            for c in comments {
+                if c.kind != CommentKind::Line {
+                    continue;
+                }
+
+                let args = match c.text.trim().strip_prefix(PRAGMA) {
+                    Some(args) if args.is_empty() || args.starts_with(char::is_whitespace) => args,
+                    _ => continue,
+                };
+
+                let mut names = args.split_whitespace().peekable();
+                if names.peek().is_none() {
+                    rule.enable_strict();
+                    continue;
+                }
+
+                for name in names {
+                    if !enable_rule(&mut rule, name) {
+                        errors.push(
+                            ErrorKind::UnknownRuleInPragma {
+                                span: c.span,
+                                name: name.into(),
+                            }
+                            .into(),
+                        );
+                    }
+                }
+            }
+        });
+
+        self.storage.report_all(errors);
+        self.env.set_rule(rule);
+    }
+}
+
+/// Returns `false` if `name` is not a known rule.
+fn enable_rule(rule: &mut Rule, name: &str) -> bool {
+    match name {
+        "strict" => rule.enable_strict(),
+        "alwaysStrict" => rule.always_strict = true,
+        "noImplicitAny" => rule.no_implicit_any = true,
+        "noImplicitThis" => rule.no_implicit_this = true,
+        "strictNullChecks" => rule.strict_null_checks = true,
+        "strictFunctionTypes" => rule.strict_function_types = true,
+        "strictBindCallApply" => rule.strict_bind_call_apply = true,
+        "strictPropertyInitialization" => rule.strict_property_initialization = true,
+        "useUnknownInCatchVariables" => rule.use_unknown_in_catch_variables = true,
+        "noUncheckedIndexedAccess" => rule.no_unchecked_indexed_access = true,
+        "exactOptionalPropertyTypes" => rule.exact_optional_property_types = true,
+        "noImplicitReturns" => rule.no_implicit_returns = true,
+        "noFallthroughCasesInSwitch" => rule.no_fallthrough_cases_in_switch = true,
+        "noUnusedLocals" => rule.no_unused_locals = true,
+        "noUnusedParameters" => rule.no_unused_parameters = true,
+        "isolatedModules" => rule.isolated_modules = true,
+        "esModuleInterop" => rule.es_module_interop = true,
+        "allowSyntheticDefaultImports" => rule.allow_synthetic_default_imports = true,
+        "verbatimModuleSyntax" => rule.verbatim_module_syntax = true,
+        "resolveJsonModule" => rule.resolve_json_module = true,
+        "forceConsistentCasingInFileNames" => rule.force_consistent_casing_in_file_names = true,
+        "strictParity" => rule.strict_parity = true,
+        "preciseObjectMethods" => rule.precise_object_methods = true,
+        _ => return false,
+    }
+
+    true
+}
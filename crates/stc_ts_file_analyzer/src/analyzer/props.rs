@@ -187,7 +187,12 @@ impl Analyzer<'_, '_> {
             ..self.ctx
         };
 
-        let old_this = self.scope.this.take();
+        // `this` of an object literal scope comes from `ThisType<T>`, and it should be
+        // visible to methods.
+        let old_this = match self.scope.kind() {
+            ScopeKind::ObjectLit => self.scope.this.clone(),
+            _ => self.scope.this.take(),
+        };
         let res = self.with_ctx(ctx).validate_prop_inner(prop, object_type);
         self.scope.this = old_this;
 
@@ -352,6 +357,7 @@ impl Analyzer<'_, '_> {
                     type_params: Default::default(),
                     metadata: Default::default(),
                     accessor: Default::default(),
+                    write_type: Default::default(),
                 }
                 .into()
             }
@@ -378,6 +384,7 @@ impl Analyzer<'_, '_> {
                     type_params: Default::default(),
                     metadata: Default::default(),
                     accessor: Default::default(),
+                    write_type: Default::default(),
                 }
                 .into()
             }
@@ -411,6 +418,7 @@ impl Analyzer<'_, '_> {
                                 getter: false,
                                 setter: true,
                             },
+                            write_type: Default::default(),
                         }
                         .into())
                     }
@@ -449,6 +457,12 @@ impl Analyzer<'_, '_> {
                         let ret_ty = ret_ty.map(|ty| ty.freezed());
                         child.scope.declared_return_type = ret_ty.clone();
 
+                        if p.function.is_async && !p.function.is_generator {
+                            if let Some(ty) = &ret_ty {
+                                child.check_return_type_of_async_fn(ty);
+                            }
+                        }
+
                         let mut inferred = None;
 
                         if let Some(body) = &p.function.body {
@@ -547,6 +561,7 @@ impl Analyzer<'_, '_> {
                 getter: true,
                 setter: false,
             },
+            write_type: Default::default(),
         }
         .into())
     }
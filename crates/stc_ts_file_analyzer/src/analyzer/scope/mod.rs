@@ -5,14 +5,15 @@ use std::{
     iter,
     mem::{replace, take},
     slice,
-    time::Instant,
 };
 
 use fxhash::{FxHashMap, FxHashSet};
+use instant::Instant;
 use iter::once;
 use once_cell::sync::Lazy;
 use rnode::{Fold, FoldWith, VisitMut, VisitMutWith, VisitWith};
 use stc_ts_ast_rnode::{RPat, RTsEntityName, RTsQualifiedName};
+use stc_ts_env::GlobalDeclKind;
 use stc_ts_errors::{
     debug::{dump_type_as_string, print_backtrace},
     DebugExt, ErrorKind,
@@ -39,6 +40,7 @@ use crate::{
         assign::AssignOpts,
         class::ClassState,
         control_flow::CondFacts,
+        decl_merging::without_namespaces,
         expr::{IdCtx, TypeOfMode},
         generic::InferTypeOpts,
         scope::vars::DeclareVarsOpts,
@@ -829,9 +831,9 @@ impl Analyzer<'_, '_> {
             }
         }
 
-        if self.ctx.in_global {
+        if self.ctx.in_global || self.is_in_script_global_scope() {
             if !ty.is_type_param() {
-                self.env.declare_global_type(name.sym().clone(), ty.clone());
+                self.declare_global_type(name.sym().clone(), ty.clone());
             }
         }
 
@@ -858,7 +860,7 @@ impl Analyzer<'_, '_> {
                 });
 
             // Override class definitions.
-            if should_override {
+            if should_override && ty.is_class_def() {
                 if let Some(kind) = self.scope.get_var(&name).map(|v| v.kind) {
                     self.override_var(kind, name.clone(), ty.clone()).report(&mut self.storage);
                 }
@@ -908,6 +910,7 @@ impl Analyzer<'_, '_> {
 
                 let ctx = Ctx {
                     disallow_suggesting_property_on_no_var: true,
+                    in_type_query: true,
                     ..self.ctx
                 };
 
@@ -1021,6 +1024,10 @@ impl Analyzer<'_, '_> {
                         }
                     }
                 }
+
+                if let Some(ty) = self.find_circular_import_var(name) {
+                    return Some(Cow::Owned(ty));
+                }
             }
 
             if let Some(var) = self.find_var(name) {
@@ -1102,6 +1109,10 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        if let Some(types) = self.find_circular_import_types(name) {
+            return Ok(Some(ItemRef::Owned(types.into_iter())));
+        }
+
         if let Ok(ty) = self.env.get_global_type(DUMMY_SP, name.sym()) {
             return Ok(Some(ItemRef::Owned(vec![ty].into_iter())));
         }
@@ -1257,6 +1268,44 @@ impl Analyzer<'_, '_> {
         None
     }
 
+    /// Returns `true` if declarations of the current scope are in the global
+    /// scope shared by script files.
+    fn is_in_script_global_scope(&self) -> bool {
+        self.data.is_script && self.scope.is_root()
+    }
+
+    /// Records a declaration in the global scope shared by script files, and
+    /// reports declarations of other files which conflict with it.
+    ///
+    /// Conflicts in the same file are reported by [Analyzer::declare_var].
+    fn declare_script_global(&mut self, span: Span, kind: VarKind, name: &Id) {
+        let kind = match kind {
+            VarKind::Var(VarDeclKind::Var) | VarKind::Fn => GlobalDeclKind::Var,
+            VarKind::Var(VarDeclKind::Let | VarDeclKind::Const) => GlobalDeclKind::BlockScoped,
+            VarKind::Class => GlobalDeclKind::Class,
+            VarKind::Param | VarKind::Import | VarKind::Enum | VarKind::Error => return,
+        };
+
+        let file = self.storage.path(self.ctx.module_id);
+        let conflicts = self.env.declare_script_global(&file, name.sym().clone(), kind, span);
+
+        for (prev_kind, prev_span) in conflicts {
+            let is_block_scoped = kind == GlobalDeclKind::BlockScoped || prev_kind == GlobalDeclKind::BlockScoped;
+
+            for span in [prev_span, span] {
+                let name = name.clone();
+                self.storage.report(
+                    if is_block_scoped {
+                        ErrorKind::DuplicateVar { name, span }
+                    } else {
+                        ErrorKind::DuplicateName { name, span }
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
     /// If `allow_multiple` is true and `is_override` is false, the value type
     /// is updated only if it's temporary type (like `typeof foo` while
     /// validating `foo`).
@@ -1387,7 +1436,7 @@ impl Analyzer<'_, '_> {
             ty.assert_valid();
         }
 
-        if self.ctx.in_global {
+        if self.ctx.in_global || self.is_in_script_global_scope() {
             match kind {
                 VarKind::Var(_) | VarKind::Class | VarKind::Fn | VarKind::Enum => {
                     // TODO: Default to any?
@@ -1399,6 +1448,10 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        if self.is_in_script_global_scope() && !is_override && !self.ctx.ignore_errors && !self.ctx.reevaluating() {
+            self.declare_script_global(span, kind, &name);
+        }
+
         if self.scope.is_root() || self.scope.is_module() {
             self.storage.store_private_var(
                 self.ctx.module_id,
@@ -1991,6 +2044,12 @@ impl Expander<'_, '_, '_> {
                     let mut stored_ref = None;
 
                     for t in types {
+                        // Namespaces merged with a type are only used by qualified names.
+                        let t = match without_namespaces(&t) {
+                            Some(ty) => Cow::Owned(ty),
+                            None => t,
+                        };
+
                         if !self.expand_union {
                             let mut finder = UnionFinder { found: false };
                             t.visit_with(&mut finder);
@@ -2153,7 +2212,14 @@ impl Expander<'_, '_, '_> {
             //
             //  let a: StringEnum.Foo = x;
             RTsEntityName::TsQualifiedName(box RTsQualifiedName { left, ref right, .. }) => {
-                let left = self.expand_ts_entity_name(span, left, None, was_top_level, trying_primitive_expansion)?;
+                let namespace = match left {
+                    RTsEntityName::Ident(i) => self.analyzer.find_namespace_type(&i.into())?,
+                    _ => None,
+                };
+                let left = match namespace {
+                    Some(namespace) => Some(namespace),
+                    None => self.expand_ts_entity_name(span, left, None, was_top_level, trying_primitive_expansion)?,
+                };
 
                 if let Some(left) = &left {
                     let ty = self
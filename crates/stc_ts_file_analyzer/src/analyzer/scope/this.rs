@@ -82,12 +82,18 @@ impl Analyzer<'_, '_> {
         let this_ty = self.scope.this();
 
         if let Some(this) = this_ty.map(Cow::into_owned) {
-            ty.visit_mut_with(&mut ThisReplacer {
-                this_ty: this,
-                analyzer: self,
-            })
+            self.replace_this_in_type(ty, this)
         }
     }
+
+    /// Replaces polymorphic `this` types in `ty` with `this_ty`.
+    ///
+    /// This is used to resolve the type of a member accessed through an
+    /// object, so `derived.fluent()` is typed as `Derived` even if `fluent`
+    /// is declared in the base class.
+    pub(crate) fn replace_this_in_type(&mut self, ty: &mut Type, this_ty: Type) {
+        ty.visit_mut_with(&mut ThisReplacer { this_ty, analyzer: self })
+    }
 }
 
 struct ThisReplacer<'a, 'b, 'c> {
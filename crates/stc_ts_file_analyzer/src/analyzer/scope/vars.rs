@@ -3,10 +3,7 @@ use std::borrow::Cow;
 use itertools::Itertools;
 use rnode::{FoldWith, NodeId};
 use stc_ts_ast_rnode::{RBindingIdent, RExpr, RIdent, RNumber, RObjectPatProp, RPat, RStr, RTsEntityName, RTsLit};
-use stc_ts_errors::{
-    debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt, ErrorKind,
-};
+use stc_ts_errors::{debug::dump_type_as_string, DebugExt, DeferredMsg, ErrorKind};
 use stc_ts_type_ops::{tuple_to_array::TupleToArray, widen::Widen, Fix};
 use stc_ts_types::{
     type_id::DestructureId, Array, CommonTypeMetadata, Instance, Key, LitType, PropertySignature, Ref, RestType, Tuple, TupleElement,
@@ -21,12 +18,13 @@ use tracing::debug;
 use crate::{
     analyzer::{
         assign::AssignOpts,
-        expr::{AccessPropertyOpts, GetIteratorOpts, IdCtx, TypeOfMode},
+        expr::{retain_own_props_of_instance, AccessPropertyOpts, GetIteratorOpts, IdCtx, TypeOfMode},
         types::NormalizeTypeOpts,
         util::{opt_union, ResultExt},
         Analyzer, Ctx,
     },
     ty::TypeExt,
+    type_facts::TypeFacts,
     validator::ValidateWith,
     VResult,
 };
@@ -50,6 +48,11 @@ pub enum VarKind {
 pub(crate) struct DeclareVarsOpts {
     pub kind: VarKind,
     pub use_iterator_for_array: bool,
+    /// `true` if the type of the pattern comes from a type annotation.
+    ///
+    /// Default values of bindings are checked against the annotated type
+    /// instead of being merged into it.
+    pub has_type_ann: bool,
 }
 
 impl Default for DeclareVarsOpts {
@@ -57,11 +60,29 @@ impl Default for DeclareVarsOpts {
         Self {
             kind: VarKind::Var(VarDeclKind::Var),
             use_iterator_for_array: false,
+            has_type_ann: false,
         }
     }
 }
 
 impl Analyzer<'_, '_> {
+    /// Reports an error if the default value of a binding is not assignable to
+    /// the annotated type of the binding.
+    fn check_default_value_of_binding(&mut self, span: Span, binding_ty: &Type, default_ty: &Type) {
+        self.assign_with_opts(
+            &mut Default::default(),
+            binding_ty,
+            default_ty,
+            AssignOpts {
+                span,
+                allow_assignment_to_param_constraint: false,
+                ..Default::default()
+            },
+        )
+        .context("tried to assign a default value to a binding")
+        .report(&mut self.storage);
+    }
+
     /// TODO(kdy1): Rename to declare_vars
     ///
     /// # Parameters
@@ -160,6 +181,10 @@ impl Analyzer<'_, '_> {
                 let mut type_ann = type_ann.or(default);
                 type_ann.freeze();
 
+                // A default value is used if the value is `undefined`, so the binding itself
+                // cannot be `undefined`.
+                let ty = ty.map(|ty| self.apply_type_facts_to_type(TypeFacts::NEUndefined, ty).freezed());
+
                 let mut right = p
                     .right
                     .validate_with_args(self, (TypeOfMode::RValue, None, type_ann.as_ref().or(ty.as_ref())))
@@ -190,10 +215,16 @@ impl Analyzer<'_, '_> {
                     )
                     .context("tried to assign a value to a variable with an assignment pattern")
                     .report(&mut self.storage);
+                } else if opts.has_type_ann {
+                    if let Some(ty) = &ty {
+                        self.check_default_value_of_binding(p.right.span(), ty, &right);
+                    }
                 }
 
                 let default = if is_typed {
                     type_ann
+                } else if opts.has_type_ann && ty.is_some() {
+                    None
                 } else {
                     opt_union(span, type_ann, Some(right))
                 }
@@ -213,6 +244,11 @@ impl Analyzer<'_, '_> {
             }
 
             RPat::Array(arr) => {
+                let opts = DeclareVarsOpts {
+                    has_type_ann: opts.has_type_ann || arr.type_ann.is_some(),
+                    ..opts
+                };
+
                 if opts.use_iterator_for_array {
                     // Handle tuple
                     //
@@ -226,7 +262,7 @@ impl Analyzer<'_, '_> {
                                 Cow::Owned(ty),
                                 GetIteratorOpts {
                                     disallow_str: true,
-                                    ..Default::default()
+                                    check_downlevel_iteration: true,
                                 },
                             )
                             .context("tried to convert a type to an iterator to assign with an array pattern.")
@@ -527,6 +563,10 @@ impl Analyzer<'_, '_> {
             }
 
             RPat::Object(obj) => {
+                let opts = DeclareVarsOpts {
+                    has_type_ann: opts.has_type_ann || obj.type_ann.is_some(),
+                    ..opts
+                };
                 let normalize_ty = ty.as_ref().map(Type::normalize);
                 let should_use_no_such_property = !matches!(normalize_ty, Some(Type::TypeLit(..)));
                 let destructure_key = self.regist_destructure(span, ty.clone(), None);
@@ -640,6 +680,7 @@ impl Analyzer<'_, '_> {
                                     type_params: None,
                                     metadata: Default::default(),
                                     accessor: Default::default(),
+                                    write_type: Default::default(),
                                 }),
                             )?;
                         }
@@ -721,11 +762,24 @@ impl Analyzer<'_, '_> {
 
                                             default_value_type.freeze();
 
-                                            let mut default = opt_union(span, default_prop_ty, default_value_type).freezed();
-                                            // TODO(kdy1): Pass default when it's possible.
-                                            if prop_ty.is_some() {
-                                                default = None;
-                                            }
+                                            // The default value is used if the property is `undefined`.
+                                            let prop_ty =
+                                                prop_ty.map(|ty| self.apply_type_facts_to_type(TypeFacts::NEUndefined, ty).freezed());
+
+                                            let default = match (&prop_ty, default_value_type) {
+                                                (Some(prop_ty), Some(default_value_type)) => {
+                                                    if opts.has_type_ann {
+                                                        self.check_default_value_of_binding(default.span(), prop_ty, &default_value_type);
+                                                        None
+                                                    } else {
+                                                        Some(default_value_type)
+                                                    }
+                                                }
+                                                (Some(..), None) => None,
+                                                (None, default_value_type) => {
+                                                    opt_union(span, default_prop_ty, default_value_type).freezed()
+                                                }
+                                            };
 
                                             let result = self
                                                 .add_vars(
@@ -818,6 +872,7 @@ impl Analyzer<'_, '_> {
                                     type_params: None,
                                     metadata: Default::default(),
                                     accessor: Default::default(),
+                                    write_type: Default::default(),
                                 }),
                             )?;
                         }
@@ -914,6 +969,9 @@ impl Analyzer<'_, '_> {
                                 }
                             }
 
+                            new_members.push(m.clone());
+                        } else if let TypeElement::Index(..) = m {
+                            // Index signatures are preserved, like `Omit`.
                             new_members.push(m.clone());
                         }
                     }
@@ -941,7 +999,19 @@ impl Analyzer<'_, '_> {
                     }));
                 }
 
-                Type::Intersection(..) | Type::Class(..) | Type::Interface(..) | Type::ClassDef(..) => {
+                Type::Class(..) => {
+                    // Methods and accessors of a class are not own properties of an instance.
+                    let ty = self.convert_type_to_type_lit(ty.span(), Cow::Borrowed(&ty))?.map(|lit| {
+                        let mut lit = lit.into_owned();
+                        retain_own_props_of_instance(&mut lit);
+                        Type::TypeLit(lit)
+                    });
+                    if let Some(ty) = ty {
+                        return self.exclude_props(span, &ty, keys);
+                    }
+                }
+
+                Type::Intersection(..) | Type::Interface(..) | Type::ClassDef(..) => {
                     let ty = self
                         .convert_type_to_type_lit(ty.span(), Cow::Borrowed(&ty))?
                         .map(Cow::into_owned)
@@ -1018,7 +1088,7 @@ impl Analyzer<'_, '_> {
 
             Err(ErrorKind::Unimplemented {
                 span,
-                msg: format!("exclude_props: {}", force_dump_type_as_string(&ty)),
+                msg: DeferredMsg::with_type("exclude_props: ", &ty),
             }
             .into())
         })()?;
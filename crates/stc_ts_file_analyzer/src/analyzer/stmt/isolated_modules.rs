@@ -0,0 +1,194 @@
+use fxhash::FxHashMap;
+use stc_ts_ast_rnode::{
+    RDecl, RExportDecl, RExportSpecifier, RExpr, RImportDecl, RImportSpecifier, RLit, RMemberProp, RModule, RModuleDecl, RModuleExportName,
+    RModuleItem, RNamedExport, RStmt, RTsEnumDecl, RTsEnumMemberId, RTsModuleDecl, RTsNamespaceBody,
+};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::Type;
+use swc_atoms::JsWord;
+
+use crate::analyzer::{import::is_type_only_export, Analyzer};
+
+impl Analyzer<'_, '_> {
+    /// Reports constructs which cannot be transpiled without information
+    /// from other files, for `isolatedModules`.
+    ///
+    /// Accessing ambient const enums is reported while validating member
+    /// expressions, because it requires the type of the object.
+    pub(crate) fn report_isolated_modules_violations(&mut self, m: &RModule) {
+        if self.config.is_builtin || self.config.is_dts || !self.rule().isolated_modules {
+            return;
+        }
+
+        if !self.data.is_external_module && !m.body.is_empty() {
+            self.storage
+                .report(ErrorKind::GlobalScriptWithIsolatedModules { span: m.span }.into());
+        }
+
+        self.report_type_reexports(m);
+
+        for item in &m.body {
+            match item {
+                RModuleItem::Stmt(RStmt::Decl(decl)) | RModuleItem::ModuleDecl(RModuleDecl::ExportDecl(RExportDecl { decl, .. })) => {
+                    self.report_enums_with_isolated_modules(decl)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `export { Foo } from './foo'` and `import { Foo } from './foo'; export {
+    /// Foo };` require `export type` if `Foo` is a type, because the
+    /// transpiler cannot know if `Foo` should be removed.
+    fn report_type_reexports(&mut self, m: &RModule) {
+        // Local name to `(module specifier, imported name)`
+        let mut imported = FxHashMap::default();
+        for item in &m.body {
+            if let RModuleItem::ModuleDecl(RModuleDecl::Import(RImportDecl {
+                specifiers,
+                src,
+                type_only: false,
+                ..
+            })) = item
+            {
+                for specifier in specifiers {
+                    if let RImportSpecifier::Named(named) = specifier {
+                        if named.is_type_only {
+                            continue;
+                        }
+                        let orig = match &named.imported {
+                            Some(name) => export_name(name),
+                            None => named.local.sym.clone(),
+                        };
+                        imported.insert(named.local.sym.clone(), (src.value.clone(), orig));
+                    }
+                }
+            }
+        }
+
+        for item in &m.body {
+            let export = match item {
+                RModuleItem::ModuleDecl(RModuleDecl::ExportNamed(export @ RNamedExport { type_only: false, .. })) => export,
+                _ => continue,
+            };
+
+            for specifier in &export.specifiers {
+                let named = match specifier {
+                    RExportSpecifier::Named(named) if !named.is_type_only => named,
+                    _ => continue,
+                };
+                let name = export_name(&named.orig);
+
+                let (src, orig) = match &export.src {
+                    Some(src) => (src.value.clone(), name),
+                    None => match imported.get(&name) {
+                        Some(v) => v.clone(),
+                        None => continue,
+                    },
+                };
+
+                let data = match self.imported_module_data(&src) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                if is_type_only_export(&data, &orig) {
+                    self.storage
+                        .report(ErrorKind::TypeReexportWithIsolatedModules { span: named.span }.into());
+                }
+            }
+        }
+    }
+
+    /// Returns the exports of an imported module, if the module is already
+    /// analyzed.
+    fn imported_module_data(&self, src: &JsWord) -> Option<Type> {
+        let ctxt = self.ctx.module_id;
+        let base = self.storage.path(ctxt);
+        let dep = self.loader.module_id(&base, src)?;
+
+        self.imports.get(&(ctxt, dep)).cloned()
+    }
+
+    /// The value of an enum member without an initializer depends on the
+    /// previous member, which cannot be computed if the previous member is
+    /// initialized using a value from another file.
+    fn report_enums_with_isolated_modules(&mut self, decl: &RDecl) {
+        match decl {
+            RDecl::TsEnum(e) => self.report_enum_with_isolated_modules(e),
+            RDecl::TsModule(box RTsModuleDecl { body: Some(body), .. }) => {
+                let mut body = body;
+                while let RTsNamespaceBody::TsNamespaceDecl(decl) = body {
+                    body = &*decl.body;
+                }
+
+                if let RTsNamespaceBody::TsModuleBlock(block) = body {
+                    for item in &block.body {
+                        match item {
+                            RModuleItem::Stmt(RStmt::Decl(decl))
+                            | RModuleItem::ModuleDecl(RModuleDecl::ExportDecl(RExportDecl { decl, .. })) => {
+                                self.report_enums_with_isolated_modules(decl)
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn report_enum_with_isolated_modules(&mut self, e: &RTsEnumDecl) {
+        if e.declare {
+            return;
+        }
+
+        let mut members = vec![];
+        let mut prev_is_constant = true;
+        for member in &e.members {
+            match &member.init {
+                Some(init) => {
+                    prev_is_constant = is_constant_enum_init(init, &e.id.sym, &members);
+                }
+                None => {
+                    if !prev_is_constant {
+                        self.storage
+                            .report(ErrorKind::EnumMemberWithoutInitializerWithIsolatedModules { span: member.span }.into());
+                    }
+                    prev_is_constant = true;
+                }
+            }
+
+            members.push(match &member.id {
+                RTsEnumMemberId::Ident(i) => i.sym.clone(),
+                RTsEnumMemberId::Str(s) => s.value.clone(),
+            });
+        }
+    }
+}
+
+fn export_name(name: &RModuleExportName) -> JsWord {
+    match name {
+        RModuleExportName::Ident(i) => i.sym.clone(),
+        RModuleExportName::Str(s) => s.value.clone(),
+    }
+}
+
+/// Returns `true` if the value of `init` can be computed without information
+/// from other files.
+fn is_constant_enum_init(init: &RExpr, enum_name: &JsWord, members: &[JsWord]) -> bool {
+    match init {
+        RExpr::Lit(RLit::Num(..) | RLit::Str(..)) => true,
+        RExpr::Tpl(tpl) => tpl.exprs.is_empty(),
+        RExpr::Paren(e) => is_constant_enum_init(&e.expr, enum_name, members),
+        RExpr::Unary(e) => is_constant_enum_init(&e.arg, enum_name, members),
+        RExpr::Bin(e) => is_constant_enum_init(&e.left, enum_name, members) && is_constant_enum_init(&e.right, enum_name, members),
+        // A member of the same enum.
+        RExpr::Ident(i) => members.contains(&i.sym),
+        RExpr::Member(e) => match (&*e.obj, &e.prop) {
+            (RExpr::Ident(obj), RMemberProp::Ident(prop)) => obj.sym == *enum_name && members.contains(&prop.sym),
+            _ => false,
+        },
+        _ => false,
+    }
+}
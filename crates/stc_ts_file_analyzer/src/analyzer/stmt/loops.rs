@@ -13,7 +13,7 @@ use swc_common::{Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::{EsVersion, TsKeywordTypeKind, TsTypeOperatorOp, VarDeclKind};
 
 use crate::{
-    analyzer::{control_flow::CondFacts, types::NormalizeTypeOpts, util::ResultExt, Analyzer, Ctx, ScopeKind},
+    analyzer::{control_flow::CondFacts, expr::GetIteratorOpts, types::NormalizeTypeOpts, util::ResultExt, Analyzer, Ctx, ScopeKind},
     ty::Type,
     util::is_str_or_union,
     validator,
@@ -344,7 +344,15 @@ impl Analyzer<'_, '_> {
 
             let mut elem_ty = match kind {
                 ForHeadKind::Of { is_awaited: false } => child
-                    .get_iterator_element_type(rhs.span(), Cow::Owned(rty), false, Default::default())
+                    .get_iterator_element_type(
+                        rhs.span(),
+                        Cow::Owned(rty),
+                        false,
+                        GetIteratorOpts {
+                            check_downlevel_iteration: true,
+                            ..Default::default()
+                        },
+                    )
                     .convert_err(|err| match err {
                         ErrorKind::NotArrayType { span } if matches!(rhs, RExpr::Lit(..)) => ErrorKind::NotArrayTypeNorStringType { span },
                         _ => err,
@@ -392,6 +400,25 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, s: &RForOfStmt) {
+        if s.await_token.is_some() && !self.ctx.in_async {
+            // `for await` is allowed at the top level of modules.
+            let in_fn = Some(true)
+                == self.scope.matches(|scope| {
+                    if scope.is_root() {
+                        return Some(false);
+                    }
+
+                    match scope.kind() {
+                        ScopeKind::Fn | ScopeKind::ArrowFn | ScopeKind::Constructor | ScopeKind::Method { .. } => Some(true),
+                        _ => None,
+                    }
+                });
+
+            if in_fn {
+                self.storage.report(ErrorKind::ForAwaitOutsideAsyncFn { span: s.span }.into());
+            }
+        }
+
         self.check_for_of_in_loop(
             s.span,
             &s.left,
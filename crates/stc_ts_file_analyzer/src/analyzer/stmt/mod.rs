@@ -1,5 +1,4 @@
-use std::time::Instant;
-
+use instant::Instant;
 use rnode::VisitWith;
 use stc_ts_ast_rnode::{RBlockStmt, RBool, RExpr, RExprStmt, RForStmt, RModuleItem, RStmt, RTsExprWithTypeArgs, RTsLit, RWithStmt};
 use stc_ts_errors::{DebugExt, ErrorKind};
@@ -17,9 +16,11 @@ use crate::{
 };
 
 mod ambient_decl;
+mod isolated_modules;
 mod loops;
 pub(crate) mod return_type;
 mod try_catch;
+mod unused;
 mod var_decl;
 
 #[validator]
@@ -55,7 +56,7 @@ impl Analyzer<'_, '_> {
             RStmt::If(_) | RStmt::Switch(_) | RStmt::While(..) | RStmt::DoWhile(..) | RStmt::For(..) | RStmt::ForIn(..) | RStmt::ForOf(..)
         );
 
-        s.visit_children_with(self);
+        stack::maybe_grow(|| s.visit_children_with(self));
 
         self.scope.return_values.in_conditional = old_in_conditional;
 
@@ -5,7 +5,10 @@ use std::{borrow::Cow, mem::take, ops::AddAssign};
 use rnode::{Fold, FoldWith, Visit, VisitWith};
 use stc_ts_ast_rnode::{RBreakStmt, RIdent, RReturnStmt, RStmt, RStr, RThrowStmt, RTsEntityName, RTsLit, RYieldExpr};
 use stc_ts_errors::{DebugExt, ErrorKind};
-use stc_ts_simple_ast_validations::yield_check::YieldValueUsageFinder;
+use stc_ts_simple_ast_validations::{
+    reachability::{can_complete_normally, ReturnFinder},
+    yield_check::YieldValueUsageFinder,
+};
 use stc_ts_types::{
     CommonTypeMetadata, IndexedAccessType, Key, KeywordType, KeywordTypeMetadata, LitType, MethodSignature, Operator, PropertySignature,
     Ref, RefMetadata, TypeElement, TypeParamInstantiation,
@@ -39,6 +42,9 @@ pub(in crate::analyzer) struct ReturnValues {
     should_generalize: bool,
     pub return_types: Vec<Type>,
     yield_types: Vec<Type>,
+    /// Contextual types of `yield` expressions, which are the types sent to
+    /// the generator using `next()`.
+    next_types: Vec<Type>,
     /// Are we in if or switch statement?
     pub(super) in_conditional: bool,
 }
@@ -50,6 +56,7 @@ impl AddAssign for ReturnValues {
 
         self.return_types.extend(rhs.return_types);
         self.yield_types.extend(rhs.yield_types);
+        self.next_types.extend(rhs.next_types);
     }
 }
 
@@ -186,7 +193,12 @@ impl Analyzer<'_, '_> {
                 if types.is_empty() {
                     if let Some(declared) = self.scope.declared_return_type().cloned() {
                         // TODO(kdy1): Change this to `get_iterable_element_type`
-                        if let Ok(el_ty) = self.get_iterator_element_type(span, Cow::Owned(declared), true, Default::default()) {
+                        let el_ty = if is_async {
+                            self.get_async_iterator_element_type(span, Cow::Owned(declared))
+                        } else {
+                            self.get_iterator_element_type(span, Cow::Owned(declared), true, Default::default())
+                        };
+                        if let Ok(el_ty) = el_ty {
                             types.push(el_ty.into_owned());
                         }
                     }
@@ -213,6 +225,14 @@ impl Analyzer<'_, '_> {
                     self.simplify(Type::union(actual))
                 };
 
+                // `N` is an intersection, because a value sent by `next()` should be assignable
+                // to all `yield` expressions.
+                let next_ty = if values.next_types.is_empty() {
+                    Type::unknown(span, Default::default())
+                } else {
+                    Type::new_intersection(span, values.next_types)
+                };
+
                 let mut metadata = yield_ty.metadata();
 
                 return Ok(Some(Type::Ref(Ref {
@@ -231,16 +251,7 @@ impl Analyzer<'_, '_> {
                     },
                     type_args: Some(box TypeParamInstantiation {
                         span,
-                        params: vec![
-                            yield_ty,
-                            ret_ty,
-                            Type::Keyword(KeywordType {
-                                span,
-                                kind: TsKeywordTypeKind::TsUnknownKeyword,
-                                metadata: Default::default(),
-                                tracker: Default::default(),
-                            }),
-                        ],
+                        params: vec![yield_ty, ret_ty, next_ty],
                     }),
                     metadata: RefMetadata {
                         common: metadata,
@@ -316,8 +327,55 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        if self.rule().no_implicit_returns && !is_generator {
+            self.check_implicit_returns(span, is_async, stmts);
+        }
+
         Ok(ret_ty)
     }
+
+    /// `noImplicitReturns`: Reports functions which return a value in some
+    /// code paths, but not in others.
+    fn check_implicit_returns(&mut self, span: Span, is_async: bool, stmts: &[RStmt]) {
+        let mut returns = ReturnFinder::default();
+        for stmt in stmts {
+            stmt.visit_with(&mut returns);
+        }
+
+        if returns.with_value.is_empty() {
+            return;
+        }
+
+        if let Some(declared) = self.scope.declared_return_type().cloned() {
+            let declared = if is_async {
+                match self.get_awaited_type(span, Cow::Owned(declared)) {
+                    Ok(ty) => ty.into_owned(),
+                    Err(..) => return,
+                }
+            } else {
+                declared
+            };
+
+            let may_be_void =
+                |ty: &Type| ty.is_any() || ty.is_unknown() || ty.is_kwd(TsKeywordTypeKind::TsVoidKeyword) || ty.is_undefined();
+            let allows_implicit_return = match declared.normalize() {
+                Type::Union(u) => u.types.iter().any(may_be_void),
+                ty => may_be_void(ty),
+            };
+
+            if allows_implicit_return {
+                return;
+            }
+        }
+
+        for span in returns.without_value {
+            self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+        }
+
+        if can_complete_normally(stmts) {
+            self.storage.report(ErrorKind::NotAllCodePathsReturnValue { span }.into());
+        }
+    }
 }
 
 #[validator]
@@ -348,6 +406,16 @@ impl Analyzer<'_, '_> {
         debug_assert_ne!(ty.span(), DUMMY_SP, "{:?}", ty);
         ty.freeze();
 
+        if self.ctx.in_async {
+            // The returned value of an async function is awaited, and like `yield`, so is
+            // the returned value of an async generator.
+            ty = self
+                .get_awaited_type(node.span, Cow::Owned(ty))
+                .context("tried to await the value returned by an async function")?
+                .into_owned()
+                .freezed();
+        }
+
         if let Some(declared) = self.scope.declared_return_type().cloned() {
             match (self.ctx.in_async, self.ctx.in_generator) {
                 // AsyncGenerator
@@ -447,12 +515,21 @@ impl Analyzer<'_, '_> {
 
 #[validator]
 impl Analyzer<'_, '_> {
-    fn validate(&mut self, e: &RYieldExpr) -> VResult<Type> {
+    fn validate(&mut self, e: &RYieldExpr, type_ann: Option<&Type>) -> VResult<Type> {
         let span = e.span;
 
+        let result_ty = self.type_of_yield_expr(span, type_ann);
+
         if let Some(res) = e.arg.validate_with_default(self) {
             let ty = res?;
 
+            // `yield*` evaluates to the return type of the delegated generator.
+            let result_ty = if e.delegate {
+                generator_type_arg(&ty, 1).cloned().unwrap_or(result_ty)
+            } else {
+                result_ty
+            };
+
             let item_ty = if e.delegate {
                 if self.ctx.in_async {
                     self.get_async_iterator_element_type(e.span, Cow::Owned(ty))
@@ -463,6 +540,11 @@ impl Analyzer<'_, '_> {
                         .context("tried to convert argument as an iterator for delegating yield")?
                         .into_owned()
                 }
+            } else if self.ctx.in_async {
+                // Values yielded by async generators are awaited.
+                self.get_awaited_type(e.span, Cow::Owned(ty))
+                    .context("tried to await a value yielded by an async generator")?
+                    .into_owned()
             } else {
                 ty
             }
@@ -494,7 +576,7 @@ impl Analyzer<'_, '_> {
                             Ok(()) => {}
                             Err(err) => {
                                 self.storage.report(err);
-                                return Ok(Type::any(span, Default::default()));
+                                return Ok(result_ty);
                             }
                         }
                     }
@@ -506,12 +588,14 @@ impl Analyzer<'_, '_> {
                             }
                             .into(),
                         );
-                        return Ok(Type::any(span, Default::default()));
+                        return Ok(result_ty);
                     }
                 }
             }
 
             self.scope.return_values.yield_types.push(item_ty);
+
+            Ok(result_ty)
         } else {
             self.scope.return_values.yield_types.push(Type::Keyword(KeywordType {
                 span: e.span,
@@ -519,9 +603,64 @@ impl Analyzer<'_, '_> {
                 metadata: Default::default(),
                 tracker: Default::default(),
             }));
+
+            Ok(result_ty)
+        }
+    }
+}
+
+impl Analyzer<'_, '_> {
+    /// Returns the type of the value sent to the generator by `next()`, which
+    /// is the result of a `yield` expression.
+    ///
+    /// If the return type of the generator is declared, it's `N` of it.
+    /// Otherwise, the contextual type of the expression is used, and it's
+    /// recorded to infer `N`.
+    fn type_of_yield_expr(&mut self, span: Span, type_ann: Option<&Type>) -> Type {
+        if let Some(declared) = self.scope.declared_return_type() {
+            return match generator_type_arg(declared, 2) {
+                Some(ty) => ty.clone(),
+                None => match declared.normalize() {
+                    Type::Ref(Ref {
+                        type_name: RTsEntityName::Ident(name),
+                        ..
+                    }) if matches!(&*name.sym, "Generator" | "AsyncGenerator") => Type::unknown(span, Default::default()),
+                    Type::Ref(Ref {
+                        type_name: RTsEntityName::Ident(name),
+                        ..
+                    }) if matches!(
+                        &*name.sym,
+                        "Iterator" | "AsyncIterator" | "IterableIterator" | "AsyncIterableIterator"
+                    ) =>
+                    {
+                        Type::undefined(span, Default::default())
+                    }
+                    _ => Type::any(span, Default::default()),
+                },
+            };
         }
 
-        Ok(Type::any(e.span, Default::default()))
+        match type_ann {
+            Some(ty) => {
+                let ty = ty.clone().freezed();
+                self.scope.return_values.next_types.push(ty.clone());
+                ty
+            }
+            None => Type::any(span, Default::default()),
+        }
+    }
+}
+
+/// Returns `idx`-th type argument of a generator type, like `R` of
+/// `Generator<T, R, N>` if `idx` is `1`.
+fn generator_type_arg(ty: &Type, idx: usize) -> Option<&Type> {
+    match ty.normalize() {
+        Type::Ref(Ref {
+            type_name: RTsEntityName::Ident(name),
+            type_args: Some(type_args),
+            ..
+        }) if matches!(&*name.sym, "Generator" | "AsyncGenerator" | "Iterator" | "AsyncIterator") => type_args.params.get(idx),
+        _ => None,
     }
 }
 
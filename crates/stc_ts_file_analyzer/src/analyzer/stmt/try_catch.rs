@@ -1,10 +1,22 @@
 use rnode::VisitWith;
-use stc_ts_ast_rnode::RCatchClause;
+use stc_ts_ast_rnode::{RCatchClause, RPat};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::Type;
+use stc_ts_utils::PatExt;
+use stc_utils::cache::Freeze;
+use swc_common::{Spanned, SyntaxContext};
+use swc_ecma_ast::VarDeclKind;
 
 use crate::{
-    analyzer::{pat::PatMode, scope::ScopeKind, Analyzer, Ctx},
+    analyzer::{
+        pat::PatMode,
+        scope::{vars::DeclareVarsOpts, ScopeKind, VarKind},
+        util::ResultExt,
+        Analyzer, Ctx,
+    },
     validator,
     validator::ValidateWith,
+    VResult,
 };
 
 #[validator]
@@ -18,7 +30,7 @@ impl Analyzer<'_, '_> {
             .with_child(ScopeKind::Block, Default::default(), |child: &mut Analyzer| {
                 match &s.param {
                     Some(pat) => {
-                        pat.validate_with(child)?;
+                        child.declare_catch_clause_var(pat)?;
                     }
                     None => {}
                 }
@@ -29,3 +41,47 @@ impl Analyzer<'_, '_> {
             })
     }
 }
+
+impl Analyzer<'_, '_> {
+    /// The type of a catch clause variable is `unknown` if
+    /// `useUnknownInCatchVariables` is enabled and `any` otherwise.
+    ///
+    /// Only `any` and `unknown` are allowed as the type annotation.
+    fn declare_catch_clause_var(&mut self, pat: &RPat) -> VResult<()> {
+        let span = pat.span().with_ctxt(SyntaxContext::empty());
+
+        let ty = match pat.get_ty() {
+            Some(type_ann) => {
+                let ty = type_ann.validate_with(self)?;
+                if !ty.is_any() && !ty.is_unknown() {
+                    self.storage
+                        .report(ErrorKind::CatchClauseVarTypeMustBeAnyOrUnknown { span: type_ann.span() }.into());
+                }
+                ty
+            }
+            None => {
+                if self.rule().use_unknown_in_catch_variables {
+                    Type::unknown(span, Default::default())
+                } else {
+                    Type::any(span, Default::default())
+                }
+            }
+        }
+        .freezed();
+
+        self.add_vars(
+            pat,
+            Some(ty),
+            None,
+            None,
+            DeclareVarsOpts {
+                kind: VarKind::Var(VarDeclKind::Let),
+                use_iterator_for_array: false,
+                has_type_ann: pat.get_ty().is_some(),
+            },
+        )
+        .report(&mut self.storage);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use stc_ts_ast_rnode::RModule;
+use stc_ts_errors::ErrorKind;
+use stc_ts_simple_ast_validations::unused::{find_unused_decls, UnusedKind};
+
+use crate::analyzer::Analyzer;
+
+impl Analyzer<'_, '_> {
+    /// Reports errors for `noUnusedLocals` and `noUnusedParameters`.
+    pub(crate) fn report_unused_decls(&mut self, m: &RModule) {
+        if self.config.is_builtin || self.config.is_dts {
+            return;
+        }
+
+        let no_unused_locals = self.rule().no_unused_locals;
+        let no_unused_parameters = self.rule().no_unused_parameters;
+        if !no_unused_locals && !no_unused_parameters {
+            return;
+        }
+
+        for decl in find_unused_decls(m) {
+            let err = match decl.kind {
+                UnusedKind::Value if no_unused_locals => ErrorKind::DeclaredButNeverRead {
+                    span: decl.span,
+                    name: decl.name,
+                },
+                UnusedKind::Type if no_unused_locals => ErrorKind::DeclaredButNeverUsed {
+                    span: decl.span,
+                    name: decl.name,
+                },
+                UnusedKind::AllImports if no_unused_locals => ErrorKind::AllImportsUnused { span: decl.span },
+                UnusedKind::Param if no_unused_parameters => ErrorKind::DeclaredButNeverRead {
+                    span: decl.span,
+                    name: decl.name,
+                },
+                _ => continue,
+            };
+
+            self.storage.report(err.into());
+        }
+    }
+}
@@ -5,7 +5,10 @@ use stc_ts_ast_rnode::{
     RArrayPat, RCallExpr, RCallee, RExpr, RIdent, RPat, RTsAsExpr, RTsEntityName, RTsTypeAssertion, RVarDecl, RVarDeclarator,
 };
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt, ErrorKind, Errors};
-use stc_ts_type_ops::{generalization::prevent_generalize, Fix};
+use stc_ts_type_ops::{
+    generalization::{prevent_generalize, prevent_generalize_non_fresh},
+    Fix,
+};
 use stc_ts_types::{
     Array, EnumVariant, Id, Instance, InstanceMetadata, KeywordType, KeywordTypeMetadata, Operator, OperatorMetadata, QueryExpr, QueryType,
     Symbol, SymbolMetadata,
@@ -325,6 +328,9 @@ impl Analyzer<'_, '_> {
                         ty.fix();
                         ty.assert_valid();
 
+                        // Only the types of literal expressions are widened.
+                        prevent_generalize_non_fresh(&mut ty);
+
                         #[allow(clippy::nonminimal_bool)]
                         if !(self.ctx.var_kind == VarDeclKind::Const && ty.is_lit()) && !matches!(v.name, RPat::Array(_) | RPat::Object(..))
                         {
@@ -706,6 +712,7 @@ impl Analyzer<'_, '_> {
                             DeclareVarsOpts {
                                 kind: VarKind::Var(kind),
                                 use_iterator_for_array: false,
+                                has_type_ann: false,
                             },
                         ) {
                             Ok(..) => {}
@@ -729,6 +736,7 @@ impl Analyzer<'_, '_> {
                     DeclareVarsOpts {
                         kind: VarKind::Var(kind),
                         use_iterator_for_array: false,
+                        has_type_ann: false,
                     },
                 )
                 .report(&mut self.storage);
@@ -8,7 +8,7 @@ use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_testing::logger;
 use stc_ts_ast_rnode::RModule;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_env::{Env, ModuleKind, Rule};
 use stc_ts_storage::Single;
 use stc_ts_types::{module_id, Id, ModuleId, Type};
 use stc_utils::stack;
@@ -26,7 +26,7 @@ use crate::{
     tests::{GLOBALS, MARKS},
 };
 
-static ENV: Lazy<Env> = Lazy::new(|| Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5")));
+static ENV: Lazy<Env> = Lazy::new(|| Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5")));
 
 /// Single-file tester
 pub struct Tester<'a, 'b> {
@@ -193,7 +193,7 @@ fn get_env() -> Env {
             ..Default::default()
         },
         EsVersion::latest(),
-        ModuleConfig::None,
+        ModuleKind::None,
         &libs,
     )
 }
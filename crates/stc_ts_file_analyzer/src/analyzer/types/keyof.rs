@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use itertools::Itertools;
 use stc_ts_ast_rnode::{RIdent, RNumber, RTsEntityName, RTsLit};
-use stc_ts_errors::{debug::force_dump_type_as_string, DebugExt, ErrorKind};
+use stc_ts_errors::{DebugExt, DeferredMsg, ErrorKind};
 use stc_ts_type_ops::{is_str_lit_or_union, Fix};
 use stc_ts_types::{
     Class, ClassMember, ClassProperty, KeywordType, KeywordTypeMetadata, LitType, Method, MethodSignature, PropertySignature, Ref, Type,
@@ -368,7 +368,7 @@ impl Analyzer<'_, '_> {
 
             Err(ErrorKind::Unimplemented {
                 span,
-                msg: format!("keyof: {}", force_dump_type_as_string(&ty)),
+                msg: DeferredMsg::with_type("keyof: ", &ty),
             }
             .into())
         })()?;
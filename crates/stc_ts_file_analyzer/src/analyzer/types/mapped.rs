@@ -1,14 +1,14 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, ops::ControlFlow};
 
 use itertools::Itertools;
-use rnode::{NodeId, Visit, VisitMut, VisitMutWith, VisitWith};
+use rnode::{NodeId, TryVisit, TryVisitWith, TryVisitor, VisitMut, VisitMutWith};
 use stc_ts_ast_rnode::{RBindingIdent, RIdent, RPat, RTsEnumMemberId, RTsLit};
 use stc_ts_base_type_ops::apply_mapped_flags;
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
-    DebugExt,
+    DebugExt, DeferredMsg,
 };
-use stc_ts_generics::type_param::finder::TypeParamNameUsageFinder;
+use stc_ts_generics::type_param::finder::uses_type_param;
 use stc_ts_types::{
     Array, Conditional, FnParam, Id, IndexSignature, IndexedAccessType, Key, KeywordType, LitType, Mapped, Operator, PropertySignature,
     Type, TypeElement, TypeLit, TypeParam,
@@ -20,6 +20,7 @@ use tracing::{debug, error, instrument};
 
 use crate::{
     analyzer::{types::NormalizeTypeOpts, Analyzer},
+    util::contains_type_param,
     VResult,
 };
 
@@ -108,6 +109,7 @@ impl Analyzer<'_, '_> {
                                     type_params: Default::default(),
                                     metadata: Default::default(),
                                     accessor: Default::default(),
+                                    write_type: Default::default(),
                                 };
                                 let mut el = TypeElement::Property(p);
                                 apply_mapped_flags(&mut el, m.optional, m.readonly);
@@ -203,6 +205,7 @@ impl Analyzer<'_, '_> {
                                 type_params: Default::default(),
                                 metadata: Default::default(),
                                 accessor: Default::default(),
+                                write_type: Default::default(),
                             };
                             let mut el = TypeElement::Property(p);
 
@@ -247,11 +250,7 @@ impl Analyzer<'_, '_> {
         }
 
         if let Some(mapped_ty) = m.ty.as_deref() {
-            let found_type_param_in_keyof_operand = {
-                let mut v = TypeParamNameUsageFinder::default();
-                keyof_operand.visit_with(&mut v);
-                !v.params.is_empty()
-            };
+            let found_type_param_in_keyof_operand = uses_type_param(&keyof_operand, |_| true);
             if !found_type_param_in_keyof_operand {
                 // Check if type in `keyof T` is only used as `T[K]`.
                 // If so, we can just use the type.
@@ -263,11 +262,9 @@ impl Analyzer<'_, '_> {
                 let mut finder = IndexedAccessTypeFinder {
                     obj: &keyof_operand,
                     key: &m.type_param.name,
-                    can_replace_indexed_type: false,
                 };
 
-                mapped_ty.visit_with(&mut finder);
-                if finder.can_replace_indexed_type {
+                if mapped_ty.try_visit_with(&mut finder).is_break() {
                     let mut replacer = IndexedAccessTypeReplacer {
                         obj: &keyof_operand,
                         key: &m.type_param.name,
@@ -287,6 +284,9 @@ impl Analyzer<'_, '_> {
         //     "unimplemented: expand_mapped_type_with_keyof\nkeyof: {}",
         //     force_dump_type_as_string(&keyof_operand)
         // );
+        if !keyof_operand.is_any() && !contains_type_param(&*keyof_operand) {
+            self.report_unimplemented(span, || DeferredMsg::with_type("a mapped type over `keyof` of ", &keyof_operand));
+        }
         Ok(None)
     }
 
@@ -366,9 +366,24 @@ impl Analyzer<'_, '_> {
 
                 if let Some(types) = self.find_type(&e.enum_name)? {
                     for ty in types.into_iter().map(Cow::into_owned).collect_vec() {
-                        if ty.is_enum_type() {
-                            let items = self.convert_type_to_keys(span, &ty)?;
-                            keys.extend(items.into_iter().flatten());
+                        match (ty.normalize(), &e.name) {
+                            // Only the value of the variant.
+                            (Type::Enum(def), Some(name)) => {
+                                let member = def.members.iter().find(|m| match &m.id {
+                                    RTsEnumMemberId::Ident(i) => i.sym == *name,
+                                    RTsEnumMemberId::Str(s) => s.value == *name,
+                                });
+                                if let Some(member) = member {
+                                    if let Ok(key) = self.validate_key(&member.val, false) {
+                                        keys.push(key);
+                                    }
+                                }
+                            }
+                            (Type::Enum(..), None) => {
+                                let items = self.convert_type_to_keys(span, &ty)?;
+                                keys.extend(items.into_iter().flatten());
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -380,6 +395,9 @@ impl Analyzer<'_, '_> {
 
             _ => {
                 error!("unimplemented: convert_type_to_keys: {}", force_dump_type_as_string(ty));
+                if !contains_type_param(ty) {
+                    self.report_unimplemented(span, || DeferredMsg::with_type("keys of a mapped type: ", ty));
+                }
                 Ok(None)
             }
         }
@@ -603,35 +621,37 @@ impl From<Key> for PropertyName {
     }
 }
 
+/// Breaks on the first `obj[key]`.
 #[derive(Debug)]
 struct IndexedAccessTypeFinder<'a> {
     obj: &'a Type,
     key: &'a Id,
+}
 
-    can_replace_indexed_type: bool,
+impl TryVisitor for IndexedAccessTypeFinder<'_> {
+    type Break = ();
 }
 
-impl Visit<Conditional> for IndexedAccessTypeFinder<'_> {
-    fn visit(&mut self, n: &Conditional) {
-        n.check_type.visit_children_with(self);
+impl TryVisit<Conditional> for IndexedAccessTypeFinder<'_> {
+    fn try_visit(&mut self, n: &Conditional) -> ControlFlow<()> {
+        n.check_type.try_visit_children_with(self)?;
 
-        n.extends_type.visit_children_with(self);
+        n.extends_type.try_visit_children_with(self)
     }
 }
 
-impl Visit<IndexedAccessType> for IndexedAccessTypeFinder<'_> {
-    fn visit(&mut self, n: &IndexedAccessType) {
+impl TryVisit<IndexedAccessType> for IndexedAccessTypeFinder<'_> {
+    fn try_visit(&mut self, n: &IndexedAccessType) -> ControlFlow<()> {
         if (*n.obj_type).type_eq(self.obj)
             && match n.index_type.normalize() {
                 Type::Param(index) => *self.key == index.name,
                 _ => false,
             }
         {
-            self.can_replace_indexed_type = true;
-            return;
+            return ControlFlow::Break(());
         }
 
-        n.visit_children_with(self);
+        n.try_visit_children_with(self)
     }
 }
 
@@ -647,27 +667,28 @@ impl VisitMut<Type> for IndexedAccessTypeReplacer<'_> {
             let mut v = IndexedAccessTypeFinder {
                 obj: self.obj,
                 key: self.key,
-                can_replace_indexed_type: false,
             };
 
-            ty.visit_with(&mut v);
-            if !v.can_replace_indexed_type {
+            if ty.try_visit_with(&mut v).is_continue() {
                 return;
             }
         }
 
-        // TODO(kdy1): PERF
-        ty.normalize_mut();
-
-        if let Type::IndexedAccessType(n) = ty {
-            if (*n.obj_type).type_eq(self.obj)
-                && match n.index_type.normalize() {
-                    Type::Param(index) => *self.key == index.name,
-                    _ => false,
-                }
-            {
-                *ty = self.obj.clone();
+        // Check through `normalize` instead of `normalize_mut`, as the latter deep
+        // clones shared types even if they are not replaced.
+        let is_target = match ty.normalize() {
+            Type::IndexedAccessType(n) => {
+                (*n.obj_type).type_eq(self.obj)
+                    && match n.index_type.normalize() {
+                        Type::Param(index) => *self.key == index.name,
+                        _ => false,
+                    }
             }
+            _ => false,
+        };
+
+        if is_target {
+            *ty = self.obj.clone();
         }
     }
 }
@@ -36,7 +36,7 @@ use super::generic::InferTypeOpts;
 use crate::{
     analyzer::{expr::TypeOfMode, generic::ExtendsOpts, scope::ExpandOpts, Analyzer, Ctx},
     type_facts::TypeFacts,
-    util::unwrap_ref_with_single_arg,
+    util::{contains_type_param, unwrap_ref_with_single_arg},
     VResult,
 };
 
@@ -122,7 +122,7 @@ impl Analyzer<'_, '_> {
         #[cfg(debug_assertions)]
         let input = dump_type_as_string(&ty);
 
-        let res = (|| {
+        let res = stack::maybe_grow(|| {
             let _stack = match stack::track(actual_span) {
                 Ok(v) => v,
                 Err(err) => {
@@ -146,7 +146,11 @@ impl Analyzer<'_, '_> {
 
             {
                 match ty.normalize() {
-                    Type::Ref(_) => {
+                    Type::Ref(r) => {
+                        if let Some(awaited) = self.resolve_awaited_ref(actual_span, r)? {
+                            return Ok(Cow::Owned(self.normalize(span, Cow::Owned(awaited), opts)?.into_owned()));
+                        }
+
                         let mut new_ty = self
                             .expand_top_ref(
                                 actual_span,
@@ -598,7 +602,16 @@ impl Analyzer<'_, '_> {
                                         .normalize(span, Cow::Owned(expanded_ty), opts)
                                         .context("tried to normalize the type returned from typeof");
                                 }
-                                QueryExpr::Import(_) => {}
+                                QueryExpr::Import(import) => {
+                                    let ty = self
+                                        .resolve_import_type(actual_span, import, IdCtx::Var)
+                                        .context("tried to resolve typeof import() as a part of normalization")?
+                                        .freezed();
+
+                                    return self
+                                        .normalize(span, Cow::Owned(ty), opts)
+                                        .context("tried to normalize the type returned from typeof import()");
+                                }
                             }
                         }
                         // TODO
@@ -635,7 +648,16 @@ impl Analyzer<'_, '_> {
                         return Ok(Cow::Owned(ty));
                     }
 
-                    Type::Import(_) => {}
+                    Type::Import(import) => {
+                        let ty = self
+                            .resolve_import_type(actual_span, import, IdCtx::Type)
+                            .context("tried to resolve import() as a part of normalization")?
+                            .freezed();
+
+                        return self
+                            .normalize(span, Cow::Owned(ty), opts)
+                            .context("tried to normalize the type returned from import()");
+                    }
 
                     Type::Predicate(_) => {
                         // TODO(kdy1): Add option for this.
@@ -783,7 +805,7 @@ impl Analyzer<'_, '_> {
             }
 
             Ok(ty)
-        })();
+        });
 
         if let Ok(res) = &res {
             #[cfg(debug_assertions)]
@@ -995,12 +1017,14 @@ impl Analyzer<'_, '_> {
             return never!();
         }
 
-        if normalized_types.len() == 2 {
-            let (a, b) = (&normalized_types[0], &normalized_types[1]);
-            if ((a.is_str_lit() && b.is_str_lit()) || (a.is_num_lit() && b.is_num_lit()) || (a.is_bool_lit() && b.is_bool_lit()))
-                && !a.type_eq(b)
-            {
-                return never!();
+        // `"a" & "b"` is never.
+        for (idx, a) in normalized_types.iter().enumerate() {
+            for b in &normalized_types[idx + 1..] {
+                if ((a.is_str_lit() && b.is_str_lit()) || (a.is_num_lit() && b.is_num_lit()) || (a.is_bool_lit() && b.is_bool_lit()))
+                    && !a.type_eq(b)
+                {
+                    return never!();
+                }
             }
         }
 
@@ -1123,7 +1147,13 @@ impl Analyzer<'_, '_> {
                 .into_owned()
                 .freezed();
 
-            if let Type::TypeLit(elem_tl) = elem.normalize_instance() {
+            let elem_tl = match elem.normalize_instance() {
+                Type::TypeLit(elem_tl) => Some(Cow::Borrowed(elem_tl)),
+                Type::Interface(..) => self.convert_type_to_type_lit(span, Cow::Borrowed(&elem)).ok().flatten(),
+                _ => None,
+            };
+
+            if let Some(elem_tl) = elem_tl {
                 // Intersect property types
                 'outer: for e in elem_tl.members.iter() {
                     if let TypeElement::Property(p) = e {
@@ -1141,10 +1171,15 @@ impl Analyzer<'_, '_> {
                                         .map(|v| *v)
                                         .unwrap_or_else(|| Type::any(span, KeywordTypeMetadata { ..Default::default() }));
 
+                                    // Only conflicting discriminants make the intersection never, and
+                                    // `{ a: string } & { a: number }` is `{ a: never }`.
+                                    let is_discriminant =
+                                        !prev.optional && !p.optional && (has_literal_part(&prev_type) || has_literal_part(&other));
+
                                     let new = self.normalize_intersection_types(span, &[prev_type, other], opts)?;
 
                                     if let Some(new) = new {
-                                        if new.is_never() {
+                                        if new.is_never() && is_discriminant {
                                             return never!();
                                         }
                                         prev.type_ann = Some(box new);
@@ -1624,6 +1659,7 @@ impl Analyzer<'_, '_> {
                         type_params: Default::default(),
                         metadata: Default::default(),
                         accessor: Default::default(),
+                        write_type: Default::default(),
                     }))
                 }
                 ClassMember::IndexSignature(_) => {}
@@ -1972,6 +2008,7 @@ impl Analyzer<'_, '_> {
                         type_params: Default::default(),
                         metadata: Default::default(),
                         accessor: Default::default(),
+                        write_type: Default::default(),
                     }));
                 }
 
@@ -2001,6 +2038,7 @@ impl Analyzer<'_, '_> {
                         getter: true,
                         setter: false,
                     },
+                    write_type: Default::default(),
                 }));
 
                 Cow::Owned(TypeLit {
@@ -2119,6 +2157,30 @@ impl Analyzer<'_, '_> {
         .context("tried to merge a type element")
     }
 
+    /// Resolves `Awaited<T>` of the standard library using
+    /// [Self::get_awaited_type], so it's consistent with `await`.
+    ///
+    /// Returns [None] if `T` contains a type parameter, or if `Awaited` is
+    /// declared by the user.
+    fn resolve_awaited_ref(&mut self, span: Span, r: &Ref) -> VResult<Option<Type>> {
+        let arg = match (&r.type_name, &r.type_args) {
+            (RTsEntityName::Ident(name), Some(type_args)) if name.sym == *"Awaited" && type_args.params.len() == 1 => {
+                if self.data.bindings.types.contains(&Id::from(name)) {
+                    return Ok(None);
+                }
+
+                &type_args.params[0]
+            }
+            _ => return Ok(None),
+        };
+
+        if contains_type_param(arg) {
+            return Ok(None);
+        }
+
+        Ok(Some(self.get_awaited_type(span, Cow::Borrowed(arg))?.into_owned().freezed()))
+    }
+
     ///
     /// - `Promise<T>` => `T`
     /// - `T | PromiseLike<T>` => `T`
@@ -2343,6 +2405,7 @@ impl Analyzer<'_, '_> {
         let is_resolved = self.data.bindings.types.contains(&top_id)
             || self.imports_by_id.contains_key(&top_id)
             || self.data.unresolved_imports.contains(&top_id)
+            || self.data.circular_imports.contains_key(&top_id)
             || self.env.get_global_type(l.span, top_id.sym()).is_ok();
 
         if is_resolved {
@@ -2425,6 +2488,7 @@ impl Analyzer<'_, '_> {
                     type_params: None,
                     metadata: Default::default(),
                     accessor: p.accessor,
+                    write_type: p.write_type.clone(),
                 })
             }
             ClassMember::IndexSignature(i) => TypeElement::Index(i.clone()),
@@ -2615,6 +2679,21 @@ pub(crate) fn left_of_expr(t: &RExpr) -> Option<&RIdent> {
     }
 }
 
+/// Returns `true` if `ty` is a unit type, or a union containing a unit type.
+///
+/// A property is a discriminant if its type has a literal part.
+fn has_literal_part(ty: &Type) -> bool {
+    match ty.normalize() {
+        Type::Lit(..) | Type::EnumVariant(..) => true,
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsNullKeyword | TsKeywordTypeKind::TsUndefinedKeyword,
+            ..
+        }) => true,
+        Type::Union(u) => u.types.iter().any(has_literal_part),
+        _ => false,
+    }
+}
+
 fn apply_string_mapping<T: AsRef<str>>(intrinsic: &IntrinsicKind, raw: T) -> Atom {
     let raw = raw.as_ref();
 
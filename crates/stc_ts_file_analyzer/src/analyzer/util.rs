@@ -2,7 +2,7 @@ use std::{borrow::Cow, iter::once};
 
 use rnode::{Fold, FoldWith, Visit};
 use stc_ts_ast_rnode::{RExpr, RIdent, RPropName, RStr, RTsEntityName, RTsLit, RTsType};
-use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_errors::{DeferredMsg, Error, ErrorKind};
 use stc_ts_storage::Storage;
 use stc_ts_type_ops::{is_str_lit_or_union, Fix};
 use stc_ts_types::{
@@ -15,7 +15,7 @@ use swc_ecma_ast::TsKeywordTypeKind;
 use ty::TypeExt;
 
 use crate::{
-    analyzer::{generic::is_literals, scope::ExpandOpts, Analyzer},
+    analyzer::{generic::is_literals, scope::ExpandOpts, Analyzer, Ctx},
     ty,
     ty::Type,
     VResult,
@@ -41,6 +41,27 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Passes the type of an expression to the [crate::recorder::TypeRecorder],
+    /// if any.
+    pub(crate) fn record_type(&mut self, span: Span, ty: &Type) {
+        let recorder = match &self.type_recorder {
+            Some(recorder) => recorder.clone(),
+            None => return,
+        };
+
+        recorder.record_type(span, ty);
+
+        if recorder.needs_members(span) {
+            let ctx = Ctx {
+                ignore_errors: true,
+                ..self.ctx
+            };
+            if let Ok(Some(lit)) = self.with_ctx(ctx).convert_type_to_type_lit(span, Cow::Borrowed(ty)) {
+                recorder.record_members(span, &lit.members);
+            }
+        }
+    }
+
     /// `span` and `callee` is used only for error reporting.
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     fn make_instance_from_type_elements(&mut self, span: Span, callee: &Type, elements: &[TypeElement]) -> VResult<Type> {
@@ -73,6 +94,26 @@ impl Analyzer<'_, '_> {
         .into())
     }
 
+    /// Returns an error for a construct which cannot be checked yet, if
+    /// [stc_ts_env::Rule::strict_parity] is enabled.
+    ///
+    /// Otherwise the construct is accepted silently, and [None] is returned.
+    pub(crate) fn unimplemented_error(&self, span: Span, msg: impl FnOnce() -> DeferredMsg) -> Option<Error> {
+        if !self.rule().strict_parity || self.ctx.ignore_errors || self.config.is_builtin {
+            return None;
+        }
+
+        Some(ErrorKind::Unimplemented { span, msg: msg() }.into())
+    }
+
+    /// Reports a construct which cannot be checked yet, if
+    /// [stc_ts_env::Rule::strict_parity] is enabled.
+    pub(crate) fn report_unimplemented(&mut self, span: Span, msg: impl FnOnce() -> DeferredMsg) {
+        if let Some(err) = self.unimplemented_error(span, msg) {
+            self.storage.report(err);
+        }
+    }
+
     /// Make instance of `ty`. In case of error, error will be reported to user
     /// and `ty` will be returned.
     ///
@@ -0,0 +1,32 @@
+//! Cooperative cancellation of analysis.
+//!
+//! Editors check a file again on every keystroke, so results of the previous
+//! request become useless before analysis finishes. A [CancellationToken]
+//! passed to [crate::analyzer::Analyzer] is checked at the boundaries of files
+//! and statements, and the analysis stops with
+//! [stc_ts_errors::ErrorKind::Cancelled] if it's cancelled.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A flag to abort analysis. Clones share the flag, so one clone can be passed
+/// to the analyzer and the other one can be used to cancel it.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all analysis using this token. This cannot be undone.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -1,20 +1,28 @@
-use std::{collections::hash_map::Entry, error::Error, path::Path, sync::Arc, time::Instant};
+use std::{
+    collections::hash_map::Entry,
+    convert::Infallible,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
 
 use dashmap::DashMap;
+use instant::Instant;
 use once_cell::sync::{Lazy, OnceCell};
 use rnode::{NodeIdGenerator, RNode, VisitWith};
 use rustc_hash::FxHashMap;
 use sha1::{Digest, Sha1};
 use stc_ts_ast_rnode::{RDecl, RIdent, RModule, RModuleItem, RStmt, RTsModuleName, RVarDecl};
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{BuiltIn, Env, ModuleConfig, Rule, StableEnv};
+use stc_ts_env::{BuiltIn, Env, LazyBuiltIn, ModuleConfig, Rule, StableEnv};
 use stc_ts_storage::Builtin;
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{ClassDef, ModuleTypeData, Type};
 use stc_utils::{cache::Freeze, stack};
 use swc_atoms::JsWord;
-use swc_common::DUMMY_SP;
+use swc_common::{sync::Lrc, SourceFile, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
+use swc_ecma_parser::{error::Error as ParseError, lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use tracing::{info, warn};
 
 use crate::{
@@ -22,6 +30,20 @@ use crate::{
     validator::ValidateWith,
 };
 
+/// Builtin caches of other versions of stc are not used.
+const CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// [None] if the cache is disabled.
+///
+/// See [EnvFactory::set_builtin_cache_dir].
+static BUILTIN_CACHE_DIR: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(|| {
+    let dir = match std::env::var_os("STC_BUILTIN_CACHE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(".stc").join(".builtin-cache"),
+    };
+    RwLock::new(Some(dir))
+});
+
 pub trait BuiltInGen: Sized {
     #[allow(clippy::new_ret_no_self)]
     fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> BuiltIn;
@@ -29,20 +51,84 @@ pub trait BuiltInGen: Sized {
     fn from_ts_libs(env: &StableEnv, libs: &[Lib]) -> BuiltIn {
         debug_assert_ne!(libs, &[], "No typescript library file is specified");
 
-        // Loading builtin is very slow, so we cache it to a file using serde_json
+        let key = {
+            let mut hasher = Sha1::new();
+            hasher.update(CACHE_VERSION.as_bytes());
+            for lib in libs {
+                hasher.update(lib.name().as_bytes());
+                hasher.update(lib.source().as_bytes());
+            }
+            let result = hasher.finalize();
+
+            format!("{:x}", result)
+        };
+
+        Self::cached(&key, || {
+            info!("Loading typescript builtin: {:?}", libs);
+
+            Ok::<_, Infallible>(Self::from_namespace_decls(env, stc_ts_builtin_types::load(libs)))
+        })
+        .unwrap_or_else(|err| match err {})
+    }
+
+    /// Loads `libs` from `dir`, which is typically the `lib` directory of the
+    /// `typescript` package.
+    ///
+    /// See [stc_ts_builtin_types::read_lib_dir].
+    fn from_lib_dir(env: &StableEnv, dir: &Path, libs: &[Lib]) -> io::Result<BuiltIn> {
+        debug_assert_ne!(libs, &[], "No typescript library file is specified");
+
+        let files = stc_ts_builtin_types::read_lib_dir(dir, libs)?;
 
         let key = {
             let mut hasher = Sha1::new();
-            hasher.update(format!("{:?}", libs).as_bytes());
+            hasher.update(CACHE_VERSION.as_bytes());
+            for file in &files {
+                hasher.update(file.lib.name().as_bytes());
+                hasher.update(file.content.as_bytes());
+            }
             let result = hasher.finalize();
 
             format!("{:x}", result)
         };
 
-        let cache_path = Path::new(".stc").join(".builtin-cache").join(&format!("{}.rmp", key));
+        Self::cached(&key, || {
+            info!("Loading typescript builtin from {}: {:?}", dir.display(), libs);
+
+            let decls = stc_ts_builtin_types::parse_lib_files(&files)?;
+            Ok(Self::from_namespace_decls(env, &decls))
+        })
+    }
+
+    /// Builtin is not cached if `no-builtin-cache` is enabled, because there's
+    /// no file system to store it, like on `wasm32-unknown-unknown`.
+    #[cfg(feature = "no-builtin-cache")]
+    fn cached<F, E>(_: &str, load: F) -> Result<BuiltIn, E>
+    where
+        F: FnOnce() -> Result<BuiltIn, E>,
+    {
+        load()
+    }
+
+    /// Loading builtin is very slow, so we cache it to a file.
+    ///
+    /// The cache is best-effort. If it cannot be written, like in a read-only
+    /// sandbox, a warning is logged and the builtin is used without caching.
+    ///
+    /// Errors of `load` are returned as-is, and nothing is cached for them.
+    #[cfg(not(feature = "no-builtin-cache"))]
+    fn cached<F, E>(key: &str, load: F) -> Result<BuiltIn, E>
+    where
+        F: FnOnce() -> Result<BuiltIn, E>,
+    {
+        let cache_dir = match BUILTIN_CACHE_DIR.read().expect("no panic is expected").clone() {
+            Some(dir) => dir,
+            None => return load(),
+        };
+        let cache_path = cache_dir.join(&format!("{}.rmp", key));
 
         if cache_path.is_file() {
-            let res = || -> Result<BuiltIn, Box<dyn Error>> {
+            let res = || -> Result<BuiltIn, Box<dyn std::error::Error>> {
                 let data = std::fs::read(&cache_path)?;
 
                 let builtin = rmp_serde::decode::from_slice(&data)?;
@@ -52,7 +138,7 @@ pub trait BuiltInGen: Sized {
 
             match res {
                 Ok(builtin) => {
-                    return builtin;
+                    return Ok(builtin);
                 }
                 Err(err) => {
                     warn!("Failed to load builtin from cache: {:?}", err);
@@ -60,16 +146,25 @@ pub trait BuiltInGen: Sized {
             }
         }
 
-        let _stack = stack::start(300);
+        let builtin = load()?;
 
-        let mut node_id_gen = NodeIdGenerator::default();
+        if let Err(err) = write_builtin_cache(&cache_dir, key, &builtin) {
+            warn!("Failed to write builtin cache to {}: {:?}", cache_dir.display(), err);
+        }
 
-        info!("Loading typescript builtin: {:?}", libs);
+        Ok(builtin)
+    }
 
-        let modules = stc_ts_builtin_types::load(libs);
+    fn from_namespace_decls<'a, I>(env: &StableEnv, decls: I) -> BuiltIn
+    where
+        I: IntoIterator<Item = &'a TsNamespaceDecl>,
+    {
+        let _stack = stack::start(300);
 
-        let iter = modules
-            .iter()
+        let mut node_id_gen = NodeIdGenerator::default();
+
+        let iter = decls
+            .into_iter()
             .flat_map(|module| match &*module.body {
                 TsNamespaceBody::TsModuleBlock(TsModuleBlock { body, .. }) => body,
                 TsNamespaceBody::TsNamespaceDecl(_) => unreachable!(),
@@ -77,17 +172,48 @@ pub trait BuiltInGen: Sized {
             .cloned()
             .map(|orig| RModuleItem::from_orig(&mut node_id_gen, orig));
 
-        let builtin = Self::from_module_items(env, iter);
+        Self::from_module_items(env, iter)
+    }
 
-        let json_data = rmp_serde::encode::to_vec(&builtin).unwrap_or_else(|err| panic!("failed to serialize builtin cache: {:?}", err));
+    /// Analyzes `.d.ts` files declaring globals which are not part of the
+    /// program, like shims of environments or globals of test frameworks.
+    ///
+    /// Only variables, functions, interfaces, type aliases, namespaces and
+    /// classes without `extends` or `implements` are supported. Other
+    /// statements are ignored with a warning.
+    ///
+    /// Use [EnvFactory::add_ambient_files] to add them to an [Env].
+    fn from_ambient_files(env: &StableEnv, files: &[Lrc<SourceFile>]) -> Result<BuiltIn, ParseError> {
+        let _stack = stack::start(300);
 
-        std::fs::create_dir_all(cache_path.parent().unwrap())
-            .unwrap_or_else(|err| panic!("failed to create directory for builtin cache at {:?}: {:?}", cache_path, err));
+        let mut node_id_gen = NodeIdGenerator::default();
+        let mut items = vec![];
+
+        for fm in files {
+            let lexer = Lexer::new(
+                Syntax::Typescript(TsConfig {
+                    dts: true,
+                    ..Default::default()
+                }),
+                Default::default(),
+                StringInput::from(&**fm),
+                None,
+            );
+            let mut parser = Parser::new_from(lexer);
+
+            let script = parser.parse_script()?;
+            if let Some(err) = parser.take_errors().into_iter().next() {
+                return Err(err);
+            }
 
-        std::fs::write(&cache_path, &json_data)
-            .unwrap_or_else(|err| panic!("failed to write builtin cache at {:?}: {:?}", cache_path, err));
+            for stmt in script.body {
+                for decl in ambient_decls(stmt) {
+                    items.push(RModuleItem::from_orig(&mut node_id_gen, ModuleItem::Stmt(Stmt::Decl(decl))));
+                }
+            }
+        }
 
-        builtin
+        Ok(Self::from_module_items(env, items))
     }
 
     fn from_modules(env: &StableEnv, modules: Vec<RModule>) -> BuiltIn {
@@ -196,6 +322,7 @@ pub trait BuiltInGen: Sized {
                                                     vars: data.vars,
                                                     private_types: Default::default(),
                                                     types: data.types,
+                                                    export_equals: None,
                                                 },
                                                 metadata: Default::default(),
                                                 tracker: Default::default(),
@@ -276,40 +403,232 @@ pub trait BuiltInGen: Sized {
     }
 }
 
+#[cfg(not(feature = "no-builtin-cache"))]
+fn write_builtin_cache(cache_dir: &Path, key: &str, builtin: &BuiltIn) -> Result<(), Box<dyn std::error::Error>> {
+    let data = rmp_serde::encode::to_vec(builtin)?;
+
+    std::fs::create_dir_all(cache_dir)?;
+
+    // Other processes may read or write the same file at the same time, so we
+    // write to a temporary file and rename it, which is atomic.
+    let tmp_path = cache_dir.join(&format!("{}.{}.tmp", key, std::process::id()));
+    let res = std::fs::write(&tmp_path, &data).and_then(|_| std::fs::rename(&tmp_path, cache_dir.join(&format!("{}.rmp", key))));
+    if res.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    res?;
+
+    Ok(())
+}
+
+/// Returns declarations of `stmt` which can be handled by
+/// [BuiltInGen::from_module_items].
+fn ambient_decls(stmt: Stmt) -> Vec<Decl> {
+    let decl = match stmt {
+        Stmt::Decl(decl) => decl,
+        Stmt::Empty(..) => return vec![],
+        _ => {
+            warn!("Ignoring a statement of an ambient file: {:?}", stmt.span());
+            return vec![];
+        }
+    };
+
+    match decl {
+        // `declare var a: A, b: B;` is split, as one declarator is expected per
+        // declaration.
+        Decl::Var(v) => v
+            .decls
+            .iter()
+            .map(|decl| {
+                Decl::Var(box VarDecl {
+                    decls: vec![decl.clone()],
+                    ..*v.clone()
+                })
+            })
+            .collect(),
+
+        Decl::Class(c) if c.class.super_class.is_some() || !c.class.implements.is_empty() => {
+            warn!("Ignoring class `{}` of an ambient file, which extends another type", c.ident.sym);
+            vec![]
+        }
+
+        Decl::TsModule(m) if !matches!(m.id, TsModuleName::Ident(..)) => {
+            warn!("Ignoring a module declaration of an ambient file: {:?}", m.span);
+            vec![]
+        }
+
+        Decl::Fn(..) | Decl::Class(..) | Decl::TsInterface(..) | Decl::TsTypeAlias(..) | Decl::TsModule(..) => vec![decl],
+
+        _ => {
+            warn!("Ignoring a declaration of an ambient file: {:?}", decl.span());
+            vec![]
+        }
+    }
+}
+
 impl BuiltInGen for BuiltIn {
     fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> BuiltIn {
         BuiltIn::new(vars, types)
     }
 }
 
+/// Shared by envs created by [EnvFactory], so they can share builtin types.
+static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
+
 pub trait EnvFactory {
     #[allow(clippy::new_ret_no_self)]
-    fn new(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Arc<BuiltIn>) -> Env;
-    fn simple(rule: Rule, target: EsVersion, module: ModuleConfig, libs: &[Lib]) -> Env {
-        static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
-        static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+    fn new(env: StableEnv, rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>, builtin: Arc<BuiltIn>) -> Env;
+
+    /// Sets the directory to cache builtin libraries parsed by
+    /// [EnvFactory::simple] and [EnvFactory::with_lib_dir].
+    ///
+    /// Defaults to `$STC_BUILTIN_CACHE_DIR`, or `.stc/.builtin-cache` in the
+    /// current directory. Libraries already loaded by this process are not
+    /// affected.
+    fn set_builtin_cache_dir(dir: PathBuf) {
+        *BUILTIN_CACHE_DIR.write().expect("no panic is expected") = Some(dir);
+    }
+
+    /// Disables the cache of builtin libraries, so nothing is read from or
+    /// written to the file system. Builtin libraries are analyzed by each
+    /// process.
+    ///
+    /// This can be reverted with [EnvFactory::set_builtin_cache_dir].
+    fn disable_builtin_cache() {
+        *BUILTIN_CACHE_DIR.write().expect("no panic is expected") = None;
+    }
+
+    /// Analyzes `.d.ts` files declaring globals and adds them to the builtin
+    /// declarations of this env, like a builtin library. Interfaces and
+    /// namespaces are merged with the ones of builtin libraries.
+    ///
+    /// This can be called multiple times. See
+    /// [BuiltInGen::from_ambient_files].
+    fn add_ambient_files(&mut self, files: &[Lrc<SourceFile>]) -> Result<(), ParseError>;
+
+    /// Creates an env with `libs`, which are analyzed on the first use of a
+    /// name declared by each lib. See [LazyBuiltIn].
+    fn simple(rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>, libs: &[Lib]) -> Env {
+        static CACHE: Lazy<DashMap<Vec<Lib>, Arc<LazyBuiltIn>, ahash::RandomState>> = Lazy::new(Default::default);
 
         // TODO(kdy1): Include `env` in cache
         let mut libs = libs.to_vec();
         libs.sort();
         libs.dedup();
 
-        let cell = CACHE.entry(libs.clone()).or_default().clone();
+        let builtin = CACHE.entry(libs.clone()).or_insert_with(|| Arc::new(lazy_builtin(&libs))).clone();
+
+        let env = Env::new_lazy(STABLE_ENV.clone(), rule, target, module, builtin);
+        for lib in &libs {
+            env.register_lib(lib.name());
+        }
+        env
+    }
+
+    /// Same as [EnvFactory::simple], but the libs are the ones `tsc` uses if
+    /// `lib` is not specified. See [Lib::default_for].
+    fn for_target(rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>) -> Env {
+        Self::simple(rule, target, module, &Lib::default_for(target))
+    }
+
+    /// Same as [EnvFactory::simple], but `libs` are loaded from `lib_dir`
+    /// instead of the libs compiled into stc.
+    fn with_lib_dir(rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>, libs: &[Lib], lib_dir: &Path) -> io::Result<Env> {
+        // `noLib`
+        if libs.is_empty() {
+            return Ok(Self::simple(rule, target, module, libs));
+        }
+
+        static CACHE: Lazy<DashMap<(PathBuf, Vec<Lib>), Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+
+        let mut libs = libs.to_vec();
+        libs.sort();
+        libs.dedup();
+
+        let cell = CACHE.entry((lib_dir.to_path_buf(), libs.clone())).or_default().clone();
 
         let builtin = {
-            let builtin = cell.get_or_init(|| {
-                let builtin = BuiltIn::from_ts_libs(&STABLE_ENV, &libs);
-                Arc::new(builtin)
-            });
+            let builtin = cell.get_or_try_init(|| -> io::Result<_> {
+                let builtin = BuiltIn::from_lib_dir(&STABLE_ENV, lib_dir, &libs)?;
+                Ok(Arc::new(builtin))
+            })?;
             (*builtin).clone()
         };
 
-        Self::new(STABLE_ENV.clone(), rule, target, module, builtin)
+        let env = Self::new(STABLE_ENV.clone(), rule, target, module, builtin);
+        for lib in &libs {
+            env.register_lib(lib.name());
+        }
+        Ok(env)
+    }
+}
+
+/// Analyzed libs, shared by all [LazyBuiltIn]s.
+static LIBS: Lazy<DashMap<Lib, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+
+fn lazy_builtin(libs: &[Lib]) -> LazyBuiltIn {
+    let mut builtin = LazyBuiltIn::default();
+
+    for (&lib, decl) in libs.iter().zip(stc_ts_builtin_types::load(libs)) {
+        let (vars, types) = declared_names(decl);
+
+        builtin.add_lib(lib.name().into(), vars, types, move || {
+            let cell = LIBS.entry(lib).or_default().clone();
+            cell.get_or_init(|| Arc::new(BuiltIn::from_ts_libs(&STABLE_ENV, &[lib]))).clone()
+        });
+    }
+
+    builtin
+}
+
+/// Returns the names of variables and types declared by a lib, without
+/// analyzing it.
+fn declared_names(decl: &TsNamespaceDecl) -> (Vec<JsWord>, Vec<JsWord>) {
+    let body = match &*decl.body {
+        TsNamespaceBody::TsModuleBlock(TsModuleBlock { body, .. }) => body,
+        TsNamespaceBody::TsNamespaceDecl(_) => unreachable!(),
+    };
+
+    let mut vars = vec![];
+    let mut types = vec![];
+    for item in body {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            _ => continue,
+        };
+
+        match decl {
+            Decl::Var(v) => {
+                for decl in &v.decls {
+                    if let Pat::Ident(i) = &decl.name {
+                        vars.push(i.id.sym.clone());
+                    }
+                }
+            }
+            Decl::Fn(f) => vars.push(f.ident.sym.clone()),
+            Decl::Class(c) => types.push(c.ident.sym.clone()),
+            Decl::TsInterface(i) => types.push(i.id.sym.clone()),
+            Decl::TsTypeAlias(a) => types.push(a.id.sym.clone()),
+            Decl::TsModule(m) => {
+                if let TsModuleName::Ident(i) = &m.id {
+                    types.push(i.sym.clone());
+                }
+            }
+            _ => {}
+        }
     }
+
+    (vars, types)
 }
 
 impl EnvFactory for Env {
-    fn new(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Arc<BuiltIn>) -> Env {
+    fn new(env: StableEnv, rule: Rule, target: EsVersion, module: impl Into<ModuleConfig>, builtin: Arc<BuiltIn>) -> Env {
         Env::new(env, rule, target, module, builtin)
     }
+
+    fn add_ambient_files(&mut self, files: &[Lrc<SourceFile>]) -> Result<(), ParseError> {
+        let builtin = BuiltIn::from_ambient_files(self.shared(), files)?;
+        self.add_builtin_lib(&builtin);
+        Ok(())
+    }
 }
@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
 use auto_impl::auto_impl;
+use stc_ts_errors::Error;
 use stc_ts_types::{ModuleId, ModuleTypeData, Type};
 use swc_atoms::JsWord;
-use swc_common::FileName;
+use swc_common::{FileName, Span};
 
 use crate::VResult;
 
@@ -22,6 +23,18 @@ pub struct ModuleInfo {
 pub trait Load: 'static + Send + Sync {
     fn module_id(&self, base: &Arc<FileName>, src: &str) -> Option<ModuleId>;
 
+    /// Returns the error to report if [Load::module_id] returned [None].
+    ///
+    /// This is [stc_ts_errors::ErrorKind::ModuleNotFound] unless the resolver
+    /// knows the reason.
+    fn module_not_found_error(&self, span: Span, base: &Arc<FileName>, src: &str) -> Error;
+
+    /// Returns an error if `src` refers to a file using a casing different
+    /// from the one used by other imports or the file system.
+    ///
+    /// Used for `forceConsistentCasingInFileNames`.
+    fn file_name_casing_error(&self, span: Span, base: &Arc<FileName>, src: &str) -> Option<Error>;
+
     /// Note: This method called within a thread
     fn is_in_same_circular_group(&self, base: &Arc<FileName>, src: &str) -> bool;
 
@@ -43,4 +56,14 @@ pub trait Load: 'static + Send + Sync {
 
     /// `module` should be [Type::Arc] of [Type::Module].
     fn declare_module(&self, name: &JsWord, module: Type);
+
+    /// Records `declare module "src" {}` in a module, which augments the
+    /// module `src` resolved from `base`.
+    ///
+    /// `augmentation` should be [Type::Arc] of [Type::Module].
+    fn augment_module(&self, base: &Arc<FileName>, src: &str, augmentation: Type);
+
+    /// Returns augmentations of the module, recorded by
+    /// [Load::augment_module].
+    fn module_augmentations(&self, module_id: ModuleId) -> Vec<Type>;
 }
@@ -0,0 +1,53 @@
+//! Types of expressions, for tools like playgrounds and for tests of inferred
+//! types.
+
+use std::sync::Arc;
+
+use rnode::RNode;
+use stc_ts_ast_rnode::RTsType;
+use stc_ts_types::{Type, TypeElement};
+use stc_utils::cache::ALLOW_DEEP_CLONE;
+use swc_common::{SourceMap, Span};
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Node};
+
+/// Receives the types of expressions computed by [crate::analyzer::Analyzer].
+///
+/// Note that an expression can be validated multiple times, for example while
+/// inferring type parameters of a call. The last call for a span is the final
+/// type of the expression.
+pub trait TypeRecorder: Send + Sync {
+    fn record_type(&self, span: Span, ty: &Type);
+
+    /// If this returns `true`, the members of the type of the expression at
+    /// `span` are passed to [TypeRecorder::record_members].
+    ///
+    /// Expanding a type is expensive, so this should return `true` only for
+    /// spans the recorder is interested in.
+    fn needs_members(&self, span: Span) -> bool {
+        false
+    }
+
+    fn record_members(&self, span: Span, members: &[TypeElement]) {}
+}
+
+/// Prints `ty` as a TypeScript type, like `{ foo: string; }`.
+pub fn print_type(cm: &Arc<SourceMap>, ty: &Type) -> String {
+    let ty = RTsType::from(ALLOW_DEEP_CLONE.set(&(), || ty.clone())).into_orig();
+    let mut buf = vec![];
+
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config {
+                minify: false,
+                ..Default::default()
+            },
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None)),
+        };
+
+        ty.emit_with(&mut emitter).unwrap();
+    }
+
+    String::from_utf8_lossy(&buf).into_owned()
+}
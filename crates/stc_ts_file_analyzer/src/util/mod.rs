@@ -45,6 +45,14 @@ where
     TypeFinder::find(n, check)
 }
 
+/// Check if `ty` depends on a type parameter or an infer type.
+pub(crate) fn contains_type_param<T>(n: &T) -> bool
+where
+    T: VisitWith<TypeFinder>,
+{
+    TypeFinder::find(n, |ty| matches!(ty.normalize(), Type::Param(..) | Type::Infer(..)))
+}
+
 pub(crate) fn is_str_or_union(t: &Type) -> bool {
     match t.normalize() {
         Type::Lit(LitType { lit: RTsLit::Str(..), .. }) => true,
@@ -6,7 +6,7 @@ use std::{
     fs::{self, read_to_string},
     path::{Path, PathBuf},
     process::Command,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use itertools::Itertools;
@@ -14,18 +14,22 @@ use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_testing::logger;
 use stc_ts_ast_rnode::RModule;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, JsxMode, ModuleConfig, Rule};
+use stc_ts_env::{Env, JsxMode, ModuleKind, Rule};
 use stc_ts_errors::{debug::debugger::Debugger, ErrorKind};
 use stc_ts_file_analyzer::{
     analyzer::{Analyzer, NoopLoader},
     env::EnvFactory,
+    recorder::{print_type, TypeRecorder},
     validator::ValidateWith,
 };
 use stc_ts_storage::{ErrorStore, Single};
-use stc_ts_testing::tsc::TscError;
-use stc_ts_types::module_id;
+use stc_ts_testing::{
+    expect_type::{check_expectations, parse_expectations, LineCol, RecordedType},
+    tsc::TscError,
+};
+use stc_ts_types::{module_id, Type};
 use stc_ts_utils::StcComments;
-use swc_common::{errors::DiagnosticId, input::SourceFileInput, FileName, SyntaxContext};
+use swc_common::{errors::DiagnosticId, input::SourceFileInput, FileName, SourceMap, Span, SyntaxContext};
 use swc_ecma_ast::EsVersion;
 use swc_ecma_parser::{lexer::Lexer, Parser, Syntax, TsConfig};
 use swc_ecma_transforms::resolver;
@@ -41,7 +45,16 @@ struct StcError {
 
 fn get_env() -> Env {
     let mut libs = vec![];
-    let ls = &["es2017.full", "es2016.full", "es2015.full"];
+    // `es2019.object` declares `Object.fromEntries`, and the `es2018` libs
+    // declare `Symbol.asyncIterator` and `AsyncGenerator`.
+    let ls = &[
+        "es2019.object",
+        "es2018.asyncgenerator",
+        "es2018.asynciterable",
+        "es2017.full",
+        "es2016.full",
+        "es2015.full",
+    ];
     for s in ls {
         libs.extend(Lib::load(s))
     }
@@ -54,7 +67,7 @@ fn get_env() -> Env {
             ..Default::default()
         },
         EsVersion::latest(),
-        ModuleConfig::None,
+        ModuleKind::None,
         &libs,
     )
 }
@@ -355,14 +368,28 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                 no_fallthrough_cases_in_switch: false,
                 no_implicit_returns: false,
                 no_implicit_this: false,
+                no_unchecked_indexed_access: false,
+                exact_optional_property_types: false,
                 no_strict_generic_checks: false,
                 no_unused_locals: false,
                 no_unused_parameters: false,
                 strict_function_types: false,
                 strict_null_checks: false,
+                strict_bind_call_apply: false,
+                strict_property_initialization: false,
+                use_unknown_in_catch_variables: false,
                 suppress_excess_property_errors: false,
                 suppress_implicit_any_index_errors: false,
                 use_define_property_for_class_fields: false,
+                downlevel_iteration: false,
+                isolated_modules: false,
+                es_module_interop: false,
+                allow_synthetic_default_imports: false,
+                verbatim_module_syntax: false,
+                resolve_json_module: false,
+                force_consistent_casing_in_file_names: false,
+                precise_object_methods: false,
+                strict_parity: false,
                 jsx: JsxMode::Preserve,
             };
 
@@ -386,7 +413,7 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                 panic!("Invalid directive: {:?}", line)
             }
 
-            let env = Env::simple(rule, EsVersion::Es2020, ModuleConfig::None, &libs);
+            let env = Env::simple(rule, EsVersion::Es2020, ModuleKind::None, &libs);
             let stable_env = env.shared().clone();
             let generator = module_id::ModuleIdGenerator::default();
             let path = Arc::new(FileName::Real(file_name.clone()));
@@ -488,3 +515,107 @@ fn pass(file_name: PathBuf) {
 
     println!("[SUCCESS]{}", file_name.display())
 }
+
+/// Records the types of expressions for [expect_type].
+struct ExpectTypeRecorder {
+    cm: Arc<SourceMap>,
+    types: Mutex<Vec<RecordedType>>,
+}
+
+impl TypeRecorder for ExpectTypeRecorder {
+    fn record_type(&self, span: Span, ty: &Type) {
+        let lo = self.cm.lookup_char_pos(span.lo);
+        let hi = self.cm.lookup_char_pos(span.hi);
+
+        self.types.lock().unwrap().push(RecordedType {
+            lo: LineCol {
+                line: lo.line,
+                col: lo.col.0,
+            },
+            hi: LineCol {
+                line: hi.line,
+                col: hi.col.0,
+            },
+            len: (span.hi - span.lo).0 as usize,
+            ty: print_type(&self.cm, ty),
+        });
+    }
+}
+
+/// Checks `// ^?` and `// expect-type:` comments against the types of
+/// expressions.
+#[testing::fixture("tests/expect-type/**/*.ts")]
+fn expect_type(input: PathBuf) {
+    let src = read_to_string(&input).unwrap();
+    let expectations = parse_expectations(&src);
+    assert!(!expectations.is_empty(), "{} does not have any type expectation", input.display());
+
+    let recorded = testing::run_test2(false, |cm, _| {
+        cm.new_source_file(FileName::Anon, "".to_string());
+
+        let fm = cm.load_file(&input).unwrap();
+
+        let env = get_env();
+
+        let generator = module_id::ModuleIdGenerator::default();
+        let path = Arc::new(FileName::Real(input.to_path_buf()));
+
+        let (module_id, top_level_mark) = generator.generate(&path);
+
+        let mut node_id_gen = NodeIdGenerator::default();
+        let mut module = {
+            let lexer = Lexer::new(
+                Syntax::Typescript(TsConfig { ..Default::default() }),
+                EsVersion::Es2021,
+                SourceFileInput::from(&*fm),
+                None,
+            );
+            let mut parser = Parser::new_from(lexer);
+
+            parser.parse_module().unwrap()
+        };
+        module = module.fold_with(&mut resolver(env.shared().marks().unresolved_mark(), top_level_mark, true));
+        let module = RModule::from_orig(&mut node_id_gen, module);
+
+        let mut storage = Single {
+            parent: None,
+            id: module_id,
+            top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+            path,
+            is_dts: false,
+            info: Default::default(),
+        };
+
+        let recorder = Arc::new(ExpectTypeRecorder {
+            cm: cm.clone(),
+            types: Default::default(),
+        });
+
+        {
+            let mut analyzer =
+                Analyzer::root(env, cm, Default::default(), box &mut storage, &NoopLoader, None).with_type_recorder(recorder.clone());
+            module.visit_with(&mut analyzer);
+        }
+
+        let types = recorder.types.lock().unwrap().clone();
+        Ok(types)
+    })
+    .unwrap();
+
+    let mismatches = check_expectations(&expectations, &recorded);
+
+    for m in &mismatches {
+        eprintln!(
+            "{}:{}: expected `{}`, got {}",
+            input.display(),
+            m.expectation.comment_line,
+            m.expectation.expected,
+            match &m.actual {
+                Some(actual) => format!("`{}`", actual),
+                None => "no expression".to_string(),
+            }
+        );
+    }
+
+    assert!(mismatches.is_empty(), "{} type expectation(s) failed", mismatches.len());
+}
@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{BuiltIn, Env, ModuleConfig, StableEnv};
-use stc_ts_file_analyzer::env::BuiltInGen;
-use swc_common::DUMMY_SP;
+use stc_ts_env::{BuiltIn, Env, ModuleKind, StableEnv};
+use stc_ts_file_analyzer::env::{BuiltInGen, EnvFactory};
+use swc_common::{FileName, DUMMY_SP};
 
 #[test]
 pub fn builtin() {
@@ -31,7 +31,7 @@ pub fn builtin() {
             shared,
             Default::default(),
             swc_ecma_ast::EsVersion::Es2020,
-            ModuleConfig::None,
+            ModuleKind::None,
             Arc::new(data),
         );
 
@@ -76,7 +76,7 @@ pub fn intl() {
             shared,
             Default::default(),
             swc_ecma_ast::EsVersion::Es2020,
-            ModuleConfig::None,
+            ModuleKind::None,
             Arc::new(data),
         );
 
@@ -95,3 +95,77 @@ pub fn intl() {
     })
     .unwrap();
 }
+
+#[test]
+pub fn lazy_merges_interfaces_of_libs() {
+    testing::run_test2(false, |_, _| {
+        let env = Env::simple(
+            Default::default(),
+            swc_ecma_ast::EsVersion::Es2020,
+            ModuleKind::None,
+            &Lib::load("es2015"),
+        );
+
+        let array = env
+            .get_global_type(DUMMY_SP, &"Array".into())
+            .expect("failed to get global type Array");
+        let keys = array
+            .expect_interface()
+            .body
+            .iter()
+            .filter_map(|el| el.key())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // `map` is declared by `es5`, and `find` is declared by `es2015.core`.
+        for name in ["map", "find"] {
+            assert!(keys.iter().any(|key| *key == *name), "{} is not found in {:?}", name, keys);
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+pub fn ambient_files_are_merged() {
+    testing::run_test2(false, |cm, _| {
+        let mut env = Env::simple(
+            Default::default(),
+            swc_ecma_ast::EsVersion::Es2020,
+            ModuleKind::None,
+            &Lib::load("es5"),
+        );
+
+        let fm = cm.new_source_file(
+            FileName::Custom("globals.d.ts".into()),
+            "declare var __DEV__: boolean, __TEST__: boolean;
+interface Array<T> {
+    shuffle(): T[];
+}"
+            .into(),
+        );
+        env.add_ambient_files(&[fm]).unwrap();
+
+        env.get_global_var(DUMMY_SP, &"__TEST__".into())
+            .expect("failed to get global var __TEST__");
+
+        let array = env
+            .get_global_type(DUMMY_SP, &"Array".into())
+            .expect("failed to get global type Array");
+        let keys = array
+            .expect_interface()
+            .body
+            .iter()
+            .filter_map(|el| el.key())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for name in ["map", "shuffle"] {
+            assert!(keys.iter().any(|key| *key == *name), "{} is not found in {:?}", name, keys);
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,26 @@
+use std::fs;
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{BuiltIn, Env, StableEnv};
+use stc_ts_file_analyzer::env::{BuiltInGen, EnvFactory};
+
+#[test]
+fn cache_is_written_to_configured_dir() {
+    let dir = std::env::temp_dir().join("stc-builtin-cache");
+    let _ = fs::remove_dir_all(&dir);
+    Env::set_builtin_cache_dir(dir.clone());
+
+    testing::run_test2(false, |_, _| {
+        BuiltIn::from_ts_libs(&StableEnv::new(), &Lib::load("es5"));
+
+        Ok(())
+    })
+    .unwrap();
+
+    let files = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(files.len(), 1, "{:?}", files);
+    assert!(files[0].ends_with(".rmp"), "{:?}", files);
+}
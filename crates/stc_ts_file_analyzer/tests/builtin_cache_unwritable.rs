@@ -0,0 +1,24 @@
+//! Separated from `builtin_cache.rs` because the cache directory is global.
+
+use std::fs;
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{BuiltIn, Env, StableEnv};
+use stc_ts_file_analyzer::env::{BuiltInGen, EnvFactory};
+
+#[test]
+fn unwritable_cache_dir_is_ignored() {
+    // A directory cannot be created under a file.
+    let file = std::env::temp_dir().join("stc-builtin-cache-file");
+    fs::write(&file, "").unwrap();
+    Env::set_builtin_cache_dir(file.join("cache"));
+
+    testing::run_test2(false, |_, _| {
+        BuiltIn::from_ts_libs(&StableEnv::new(), &Lib::load("es5"));
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert!(!file.join("cache").exists());
+}
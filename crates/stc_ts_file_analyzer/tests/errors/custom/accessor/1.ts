@@ -0,0 +1,9 @@
+class Thing {
+    get size(): string {
+        return "";
+    }
+
+    set size(value: number) {}
+}
+
+export { Thing };
@@ -0,0 +1,13 @@
+// The return type of an async function or method must be the global Promise<T> type.
+async function f(): number {
+    return 1;
+}
+
+class C {
+    // The return type of an async function or method must be the global Promise<T> type.
+    async m(): string {
+        return "";
+    }
+}
+
+export {};
@@ -0,0 +1,5 @@
+// Catch clause variable type annotation must be 'any' or 'unknown' if specified.
+try {
+} catch (e: string) {}
+
+export {};
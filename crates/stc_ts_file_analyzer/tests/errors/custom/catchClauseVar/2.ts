@@ -0,0 +1,9 @@
+// @stc-strict useUnknownInCatchVariables
+
+try {
+} catch (e) {
+    // `e` is `unknown`.
+    e.message;
+}
+
+export {};
@@ -0,0 +1,7 @@
+// @stc-strict isolatedModules
+
+declare const enum E {
+    A = 1,
+}
+
+export const a = E.A;
@@ -0,0 +1,9 @@
+declare const opt: { x?: number };
+
+const { x = "" }: { x?: number } = opt;
+
+const [first = ""]: [number?] = [];
+
+function f({ a = "" }: { a?: number }) {}
+
+export {};
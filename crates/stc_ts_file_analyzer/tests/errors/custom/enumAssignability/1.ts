@@ -0,0 +1,6 @@
+enum S {
+    A = "a",
+    B = "b",
+}
+
+export const s: S = "a";
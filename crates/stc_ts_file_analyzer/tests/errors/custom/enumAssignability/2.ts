@@ -0,0 +1,13 @@
+enum E {
+    A,
+    B,
+}
+
+enum F {
+    A,
+    B,
+}
+
+declare const f: F;
+
+export const e: E = f;
@@ -0,0 +1,6 @@
+declare function compute(): number;
+
+export enum E {
+    A = compute(),
+    B,
+}
@@ -0,0 +1,11 @@
+// @stc-strict strictNullChecks exactOptionalPropertyTypes
+
+interface Options {
+    a?: string;
+}
+
+// `undefined` is not assignable to an optional property.
+export const o: Options = { a: undefined };
+
+declare const options: Options;
+options.a = undefined;
@@ -0,0 +1,6 @@
+// @stc-strict
+
+// Conflicting properties which are not discriminants do not make the
+// intersection never.
+declare let a: { a: string } & { a: number };
+export const n: never = a;
@@ -0,0 +1,12 @@
+type T = [name: string, age?: number];
+
+// Type '[]' is not assignable to type 'T'.
+const a: T = [];
+
+// Type '[string, number, number]' is not assignable to type 'T'.
+const b: T = ["a", 1, 2];
+
+declare function f(...args: T): void;
+
+// Expected 1-2 arguments, but got 0.
+f();
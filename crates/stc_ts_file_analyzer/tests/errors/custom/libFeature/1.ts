@@ -0,0 +1,7 @@
+// `at` is declared in es2022.array
+const a = [1, 2, 3];
+a.at(0);
+
+"abc".at(0);
+
+export { };
@@ -0,0 +1,4 @@
+// `WeakRef` is declared in es2021.weakref
+const r = new WeakRef({});
+
+export { r };
@@ -0,0 +1,9 @@
+// @stc-strict
+
+declare const a: "a";
+
+// Literal types from type annotations are not widened.
+let b = a;
+b = "b";
+
+export { b };
@@ -0,0 +1,12 @@
+// @stc-strict noFallthroughCasesInSwitch
+
+declare const x: number;
+
+switch (x) {
+    case 0:
+        x.toFixed();
+    case 1:
+        break;
+}
+
+export {};
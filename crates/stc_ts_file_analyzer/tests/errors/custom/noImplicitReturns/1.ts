@@ -0,0 +1,14 @@
+// @stc-strict noImplicitReturns
+
+export function f(x: boolean) {
+    if (x) {
+        return 1;
+    }
+}
+
+export function g(x: boolean): number | undefined {
+    if (x) {
+        return 1;
+    }
+    return;
+}
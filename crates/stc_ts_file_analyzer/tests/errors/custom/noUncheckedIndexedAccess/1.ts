@@ -0,0 +1,7 @@
+// @stc-strict strictNullChecks noUncheckedIndexedAccess
+
+declare const arr: number[];
+declare const dict: { [key: string]: string };
+
+export const n: number = arr[0];
+export const s: string = dict["a"];
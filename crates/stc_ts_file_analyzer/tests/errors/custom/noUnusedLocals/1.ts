@@ -0,0 +1,12 @@
+// @stc-strict noUnusedLocals noUnusedParameters
+
+function unusedFn() {}
+
+interface Unused {}
+
+export function f(x: number, _y: number) {
+    const local = 1;
+    let written;
+    written = 2;
+    return 0;
+}
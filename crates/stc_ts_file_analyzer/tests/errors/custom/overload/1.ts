@@ -0,0 +1,5 @@
+declare function f(a: string): string;
+declare function f(a: number, b: number): number;
+
+// No overload matches this call.
+f(true);
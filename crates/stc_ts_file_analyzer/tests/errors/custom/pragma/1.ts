@@ -0,0 +1,3 @@
+// @stc-strict strictNullChecks
+
+export const a: string = null;
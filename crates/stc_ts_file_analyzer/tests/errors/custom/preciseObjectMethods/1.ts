@@ -0,0 +1,6 @@
+// @stc-strict preciseObjectMethods
+
+// Type '"b"' is not assignable to type '"a"'.
+const keys: "a"[] = Object.keys({ a: 1, b: "" });
+
+export {};
@@ -0,0 +1,6 @@
+// @stc-strict preciseObjectMethods
+
+// Type 'string' is not assignable to type 'number'.
+const entries: [string, number][] = Object.entries({ a: 1, b: "" });
+
+export {};
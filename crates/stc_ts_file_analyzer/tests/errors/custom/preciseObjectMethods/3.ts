@@ -0,0 +1,6 @@
+// @stc-strict preciseObjectMethods
+
+// Type '""' is not assignable to type '1'.
+const obj: { a: 1 } = Object.fromEntries([["a", ""]] as const);
+
+export {};
@@ -0,0 +1,19 @@
+declare const arr: readonly number[];
+declare const tuple: readonly [string, number];
+
+// Property 'push' does not exist on type 'readonly number[]'.
+arr.push(1);
+
+// Index signature in type 'readonly number[]' only permits reading.
+arr[0] = 1;
+
+// Cannot assign to '0' because it is a read-only property.
+tuple[0] = "a";
+
+// The type 'readonly number[]' is 'readonly' and cannot be assigned to the mutable type 'number[]'.
+const mutable: number[] = arr;
+
+const point = [1, 2] as const;
+
+// Property 'pop' does not exist on type 'readonly [1, 2]'.
+point.pop();
@@ -0,0 +1,13 @@
+declare function f(a: string, b: number): void;
+declare function h(...args: [string, ...number[], boolean]): void;
+
+declare const t: [string, number, number];
+declare const nums: number[];
+
+// Expected 2 arguments, but got 3.
+f(...t);
+
+// A spread argument must either have a tuple type or be passed to a rest parameter.
+f("a", ...nums);
+
+h("a", 1, "b", true);
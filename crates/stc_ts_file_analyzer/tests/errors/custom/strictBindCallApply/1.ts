@@ -0,0 +1,9 @@
+// @stc-strict strictBindCallApply
+
+declare function f(a: number, b: string): boolean;
+
+f.call(undefined, "", "");
+f.apply(undefined, [1, 2]);
+f.bind(undefined, "");
+
+export {};
@@ -0,0 +1,17 @@
+// @stc-strict
+
+declare let f1: (x: string | number) => void;
+declare let f2: (x: string) => void;
+
+// Parameters of function types are compared contravariantly.
+f1 = f2;
+
+interface P {
+    m: (x: string | number) => void;
+}
+
+declare let p: P;
+declare let q: { m: (x: string) => void };
+
+// Properties with function types are not methods.
+p = q;
@@ -0,0 +1,10 @@
+// @stc-strict strictParity
+
+// Keys of template literal types are not expanded yet.
+type Keys = `a${string}` | `b${string}`;
+type M = { [K in Keys]: number };
+
+declare const m: M;
+const n: number = m.a1;
+
+export {};
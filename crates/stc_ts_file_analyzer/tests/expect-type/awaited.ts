@@ -0,0 +1,26 @@
+declare const nested: Promise<Promise<number>>;
+declare const union: string | Promise<boolean>;
+declare const thenable: { then(onfulfilled: (value: string) => void): void };
+
+async function main() {
+    const a = await nested;
+    a; // expect-type: number
+
+    const b = await union;
+    b; // expect-type: string | boolean
+
+    const c = await thenable;
+    c; // expect-type: string
+}
+
+async function returnsPromise() {
+    return nested;
+}
+
+returnsPromise(); // expect-type: Promise<number>
+
+const arrow = async () => nested;
+
+arrow(); // expect-type: Promise<number>
+
+export {};
@@ -0,0 +1,12 @@
+declare function id<T>(v: T): T;
+declare const a: { foo: string; bar: number };
+
+id(a.bar); // expect-type: number
+
+const b = id(a);
+//        ^? { foo: string; bar: number }
+
+b.foo;
+// ^? string
+
+export { b };
@@ -0,0 +1,17 @@
+declare const opt: { x?: number; y?: string };
+
+const { x = 1, y = "" } = opt;
+x; // expect-type: number
+y; // expect-type: string
+
+declare const tuple: [number?];
+
+const [first = 0] = tuple;
+first; // expect-type: number
+
+function f({ a = 1, b = "" } = {}) {
+    a; // expect-type: number
+    b; // expect-type: string
+}
+
+export {};
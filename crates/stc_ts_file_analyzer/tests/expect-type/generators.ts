@@ -0,0 +1,18 @@
+function* counter() {
+    const reset: boolean = yield 1;
+    return "done";
+}
+
+counter(); // expect-type: Generator<number, string, boolean>
+
+function* annotated(): Generator<number, void, string> {
+    const name = yield 1;
+    name; // expect-type: string
+}
+
+function* delegating() {
+    const result = yield* counter();
+    result; // expect-type: string
+}
+
+export {};
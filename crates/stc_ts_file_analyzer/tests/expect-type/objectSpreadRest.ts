@@ -0,0 +1,29 @@
+declare const a: { x: number; y: string };
+declare const b: { y?: boolean; z: number };
+declare const withMethod: { m(): number; readonly r: string };
+
+const overridden = { ...a, x: "s" };
+overridden; // expect-type: { y: string; x: string; }
+
+const optional = { ...a, ...b };
+optional; // expect-type: { x: number; y: string | boolean; z: number; }
+
+const methods = { ...withMethod };
+methods; // expect-type: { m: () => number; r: string; }
+
+class C {
+    p = 1;
+    private q = 2;
+    method() {}
+    get g() {
+        return 1;
+    }
+}
+
+const instance = { ...new C() };
+instance; // expect-type: { p: number; }
+
+const { x, ...rest } = a;
+rest; // expect-type: { y: string; }
+
+export {};
@@ -0,0 +1,20 @@
+interface A {
+    a: string;
+}
+interface B {
+    b: string;
+}
+
+declare const a: "a" | string;
+a; // expect-type: string
+
+declare const b: 1 | 2 | number | "x";
+b; // expect-type: number | "x"
+
+declare const c: A | (A & B);
+c; // expect-type: A
+
+declare const d: true | boolean | 1n;
+d; // expect-type: boolean | 1n
+
+export {};
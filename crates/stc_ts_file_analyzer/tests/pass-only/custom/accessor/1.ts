@@ -0,0 +1,18 @@
+class Thing {
+    #size = 0;
+
+    get size(): number {
+        return this.#size;
+    }
+
+    set size(value: string | number | boolean) {
+        this.#size = Number(value);
+    }
+}
+
+export function f(t: Thing) {
+    t.size = "10";
+    t.size = true;
+    const n: number = t.size;
+    return n;
+}
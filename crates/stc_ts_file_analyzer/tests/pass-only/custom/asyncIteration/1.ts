@@ -0,0 +1,25 @@
+async function* numbers() {
+    yield 1;
+    yield Promise.resolve(2);
+    return "done";
+}
+
+async function main() {
+    const gen: AsyncGenerator<number, string, unknown> = numbers();
+
+    for await (const n of numbers()) {
+        const v: number = n;
+    }
+
+    const consume = async () => {
+        for await (const n of gen) {
+            const v: number = n;
+        }
+    };
+}
+
+async function* delegate(): AsyncGenerator<number, void, unknown> {
+    yield* numbers();
+}
+
+export {};
@@ -0,0 +1,22 @@
+declare const p: Promise<Promise<number>>;
+
+async function f(): Promise<number> {
+    return p;
+}
+
+async function g() {
+    return p;
+}
+
+const n: Promise<number> = g();
+
+const arrow = async (): Promise<string> => "a";
+
+// An alias of the global Promise<T> type is allowed.
+type P = Promise<number>;
+
+async function h(): P {
+    return 1;
+}
+
+export {};
@@ -0,0 +1,16 @@
+try {
+} catch (e) {
+    e.message;
+}
+
+try {
+} catch (e: unknown) {
+    if (e instanceof Error) {
+        e.message;
+    }
+}
+
+try {
+} catch {}
+
+export {};
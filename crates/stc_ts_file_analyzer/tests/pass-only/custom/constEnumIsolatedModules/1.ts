@@ -0,0 +1,12 @@
+// @stc-strict isolatedModules
+
+const enum A {
+    X = 1,
+    Y = X << 2,
+}
+
+const enum B {
+    Z = A.Y + 1,
+}
+
+export const z = B.Z;
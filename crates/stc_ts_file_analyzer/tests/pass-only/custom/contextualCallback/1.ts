@@ -0,0 +1,19 @@
+declare const arr: string[];
+
+export const lengths: number[] = arr.map((x) => x.length);
+export const upper: string[] = arr.filter((x) => x.length > 0).map(function (s) {
+    return s.toUpperCase();
+});
+
+declare function each(cb: (value: string, index: number) => void): void;
+
+each((value, index) => {
+    value.charAt(index);
+});
+
+declare function all(...callbacks: ((n: number) => void)[]): void;
+
+all(
+    (a) => a.toFixed(),
+    (b) => b.toPrecision()
+);
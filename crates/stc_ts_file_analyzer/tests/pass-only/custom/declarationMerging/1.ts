@@ -0,0 +1,51 @@
+function f() {
+    return 1;
+}
+namespace f {
+    export const x = 1;
+}
+f();
+f.x;
+
+class C {
+    a = 1;
+}
+namespace C {
+    export const y = "";
+}
+new C().a;
+C.y;
+
+enum E {
+    A,
+    B,
+}
+namespace E {
+    export function parse(s: string): E {
+        return E.A;
+    }
+}
+E.A;
+E.parse("A");
+
+interface I {
+    a: number;
+}
+namespace I {
+    export const z = 1;
+    export type T = string;
+}
+let v: I = { a: 1 };
+I.z;
+let t: I.T = "";
+
+namespace A {
+    export const x = 1;
+}
+namespace A {
+    export const y = 2;
+}
+A.x;
+A.y;
+
+export {};
@@ -0,0 +1,19 @@
+declare global {
+    interface Array<T> {
+        first(): T | undefined;
+    }
+
+    interface GlobalConfig {
+        debug: boolean;
+    }
+
+    var config: GlobalConfig;
+}
+
+const items = [1, 2, 3];
+const first: number | undefined = items.first();
+items.push(4);
+
+const debug: boolean = config.debug;
+
+export {};
@@ -0,0 +1,7 @@
+declare function require(id: string): any;
+declare const name: string;
+
+const a = require(name);
+const b = require(`${name}/index`);
+
+export {};
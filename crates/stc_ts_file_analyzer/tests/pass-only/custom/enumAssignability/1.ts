@@ -0,0 +1,26 @@
+enum E {
+    A,
+    B,
+    C,
+}
+
+enum S {
+    A = "a",
+    B = "b",
+}
+
+declare const n: number;
+declare const e: E;
+declare const s: S;
+
+// Numbers are assignable to numeric enums.
+export const fromNumber: E = n;
+export const fromNumberLit: E.A = 100;
+
+// Union enums are unions of their members.
+export const union: E.A | E.B | E.C = e;
+export const strUnion: S.A | S.B = s;
+
+// Members are assignable to the literal types of their values.
+export const one: 1 = E.B;
+export const a: "a" = S.A;
@@ -0,0 +1,25 @@
+export const enum E {
+    A = 1 << 3,
+    B = A % 3,
+    C = 2 ** 4,
+    D = E.A | 1,
+    E = ~-1,
+    F = -1 >>> 28,
+    S = `${A}px`,
+}
+
+export const a: 8 = E.A;
+export const b: 2 = E.B;
+export const c: 16 = E.C;
+export const d: 9 = E["D"];
+export const e: 0 = E.E;
+export const f: 15 = E.F;
+export const s: "8px" = E.S;
+
+export enum Computed {
+    A = "a".length,
+    B = 2,
+    C,
+}
+
+export const computedC: 3 = Computed.C;
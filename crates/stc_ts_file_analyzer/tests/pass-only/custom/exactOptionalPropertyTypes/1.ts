@@ -0,0 +1,20 @@
+// @stc-strict strictNullChecks exactOptionalPropertyTypes
+
+interface Options {
+    a?: string;
+    b?: string | undefined;
+}
+
+export const o1: Options = {};
+export const o2: Options = { a: "" };
+export const o3: Options = { b: undefined };
+
+declare const options: Options;
+options.b = undefined;
+
+// Reading a missing property still gives `undefined`.
+export const a: string | undefined = options.a;
+
+if ("a" in options) {
+    const s: string = options.a;
+}
@@ -0,0 +1,10 @@
+declare function pipe<A extends any[], B, C>(ab: (...args: A) => B, bc: (b: B) => C): (...args: A) => C;
+
+declare function list<T>(a: T): T[];
+declare function box<V>(x: V): { value: V };
+
+// `<T>(a: T) => { value: T[] }`
+export const listBox = pipe(list, box);
+
+export const a: { value: number[] } = listBox(1);
+export const b: { value: string[] } = listBox("");
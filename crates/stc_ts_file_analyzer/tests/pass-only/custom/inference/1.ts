@@ -0,0 +1,14 @@
+// @stc-strict
+
+declare function take<T>(cb: (a: T) => void, cb2: (b: T) => void): T;
+
+// Contravariant candidates are intersected.
+export const both: { a: number } & { b: string } = take(
+    (x: { a: number }) => {},
+    (y: { b: string }) => {}
+);
+
+declare function choose<T>(value: T, cb: (v: T) => void): T;
+
+// The covariant candidate is preferred if it's compatible.
+export const n: number = choose(1, (v: number) => {});
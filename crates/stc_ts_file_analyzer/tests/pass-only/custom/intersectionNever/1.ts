@@ -0,0 +1,23 @@
+// @stc-strict
+
+declare let a: string & number;
+export const n1: never = a;
+
+declare let b: "a" & "b";
+export const n2: never = b;
+
+declare let c: { kind: "a"; x: number } & { kind: "b" };
+export const n3: never = c;
+
+interface Foo {
+    kind: "foo";
+}
+interface Bar {
+    kind: "bar";
+}
+declare let d: Foo & Bar;
+export const n4: never = d;
+
+// Disjoint members are removed from unions.
+declare let e: (string & number) | "x";
+export const x: "x" = e;
@@ -0,0 +1,24 @@
+class Pair implements Iterable<string | number> {
+    *[Symbol.iterator](): Iterator<string | number> {
+        yield "a";
+        yield 1;
+    }
+}
+
+const pair = new Pair();
+
+for (const v of pair) {
+    const s: string | number = v;
+}
+
+const spread: (string | number)[] = [...pair];
+
+const [first, second] = pair;
+const f: string | number | undefined = first;
+
+declare function take(...args: (string | number)[]): void;
+take(...pair);
+
+const set = new Set([1, 2, 3]);
+const [one, ...rest] = set;
+const r: number[] = rest;
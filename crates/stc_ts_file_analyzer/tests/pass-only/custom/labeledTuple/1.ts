@@ -0,0 +1,14 @@
+type T = [name: string, age?: number];
+
+const a: T = ["a"];
+const b: T = ["a", 1];
+
+declare function f(...args: T): void;
+f("a");
+f("a", 1);
+
+declare function g(name: string, age?: number): void;
+const h: (...args: T) => void = g;
+
+type Params<F> = F extends (...args: infer P) => any ? P : never;
+const p: Params<typeof g> = ["a"];
@@ -0,0 +1,14 @@
+// @stc-strict
+
+// Fresh literal types are widened.
+let s = "x";
+s = "y";
+
+let n = -1;
+n = 2;
+
+const k = "k";
+let m = k;
+m = "other";
+
+export { s, n, m };
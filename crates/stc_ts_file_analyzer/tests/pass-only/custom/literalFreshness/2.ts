@@ -0,0 +1,19 @@
+// @stc-strict
+
+type Kind = "a" | "b";
+
+declare function take(cb: () => Kind): void;
+declare const cond: boolean;
+
+// Contextual types suppress widening.
+take(() => "a");
+take(() => (cond ? "a" : "b"));
+
+const obj: { kind: Kind } = { kind: "a" };
+const arr: Kind[] = ["a", "b"];
+const pair: [Kind, number] = ["b", 1];
+
+let k: Kind = "a";
+k = "b";
+
+export { obj, arr, pair, k };
@@ -0,0 +1,17 @@
+// @stc-strict noFallthroughCasesInSwitch
+
+declare const x: number;
+
+switch (x) {
+    case 0:
+    case 1:
+        x.toFixed();
+        break;
+    case 2: {
+        throw new Error();
+    }
+    default:
+        x.toFixed();
+}
+
+export {};
@@ -0,0 +1,30 @@
+// @stc-strict noImplicitReturns
+
+export function f(x: boolean) {
+    if (x) {
+        return 1;
+    } else {
+        return 2;
+    }
+}
+
+export function g(x: boolean) {
+    if (x) {
+        return 1;
+    }
+    throw new Error();
+}
+
+export function h(x: boolean): void | number {
+    if (x) {
+        return 1;
+    }
+}
+
+export function loop() {
+    while (true) {
+        if (Math.random()) {
+            return 1;
+        }
+    }
+}
@@ -0,0 +1,15 @@
+// @stc-strict strictNullChecks noUncheckedIndexedAccess
+
+declare const arr: number[];
+declare const dict: { [key: string]: string };
+declare const tuple: [number, string];
+
+export const n: number | undefined = arr[0];
+export const s: string | undefined = dict.a;
+
+// Elements of tuples are not affected.
+export const t: number = tuple[0];
+
+// Writes are checked against the declared type.
+arr[0] = 1;
+dict.a = "";
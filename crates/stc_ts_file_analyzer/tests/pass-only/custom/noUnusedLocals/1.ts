@@ -0,0 +1,21 @@
+// @stc-strict noUnusedLocals noUnusedParameters
+
+interface Point {
+    x: number;
+}
+
+export function f<T>(p: Point, _unused: number, value: T): T {
+    const { x, ...rest } = p;
+    for (const _key in rest) {
+    }
+    const [_first, second] = [1, 2];
+    return second > 0 ? value : value;
+}
+
+export class C {
+    constructor(private readonly a: number) {}
+
+    method(this: C) {
+        return this.a;
+    }
+}
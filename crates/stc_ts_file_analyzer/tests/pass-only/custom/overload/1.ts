@@ -0,0 +1,19 @@
+// Type parameters are inferred for each overload, and the generic overload is
+// skipped because `string` does not satisfy the constraint.
+declare function f<T extends number>(a: T): T;
+declare function f(a: string): boolean;
+
+export const a: boolean = f("");
+
+// The first applicable overload wins.
+declare function g(a: any): number;
+declare function g(a: string): string;
+
+export const b: number = g("");
+
+// An applicable overload is selected even if a later one matches exactly.
+declare const x: any;
+declare function h(a: number): number;
+declare function h(a: any): string;
+
+export const c: number = h(x);
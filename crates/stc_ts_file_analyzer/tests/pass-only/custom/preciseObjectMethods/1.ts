@@ -0,0 +1,8 @@
+// @stc-strict preciseObjectMethods
+
+const obj = { a: 1, b: "" };
+
+const keys: ("a" | "b")[] = Object.keys(obj);
+const numericKeys: "0"[] = Object.keys({ 0: true });
+
+export {};
@@ -0,0 +1,12 @@
+// @stc-strict preciseObjectMethods
+
+const obj = { a: 1, b: "" };
+
+const entries: (["a", number] | ["b", string])[] = Object.entries(obj);
+
+for (const [key, value] of Object.entries({ a: 1 })) {
+    const k: "a" = key;
+    const v: number = value;
+}
+
+export {};
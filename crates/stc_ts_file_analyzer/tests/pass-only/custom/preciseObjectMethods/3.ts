@@ -0,0 +1,14 @@
+// @stc-strict preciseObjectMethods
+
+const obj: { a: 1; b: "" } = Object.fromEntries([
+    ["a", 1],
+    ["b", ""],
+] as const);
+
+// A later entry replaces an earlier one.
+const replaced: { a: "" } = Object.fromEntries([
+    ["a", 1],
+    ["a", ""],
+] as const);
+
+export {};
@@ -0,0 +1,11 @@
+declare const arr: readonly number[];
+declare const tuple: readonly [string, number];
+
+const first: number = arr[0];
+const mapped: string[] = arr.map((v) => `${v}`);
+const joined: string = tuple.join(",");
+const copy: readonly number[] = arr.slice();
+
+const point = [1, 2] as const;
+const x: 1 = point[0];
+const readonlyPoint: readonly number[] = point;
@@ -0,0 +1,14 @@
+declare function f(a: string, b: number): void;
+declare function g(a: string, ...rest: [number, boolean]): void;
+declare function h(...args: [string, ...number[], boolean]): void;
+
+declare const t: [string, number];
+declare const u: [string, number?];
+declare const nums: number[];
+
+f(...t);
+f(...u);
+g("a", ...([1, true] as [number, boolean]));
+h("a", true);
+h("a", 1, 2, true);
+h("a", ...nums, true);
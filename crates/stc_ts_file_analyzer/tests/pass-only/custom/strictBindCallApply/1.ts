@@ -0,0 +1,9 @@
+// @stc-strict strictBindCallApply
+
+declare function f(a: number, b: string): boolean;
+
+export const r1: boolean = f.call(undefined, 1, "");
+export const r2: boolean = f.apply(undefined, [1, ""]);
+
+const bound = f.bind(undefined, 1);
+export const r3: boolean = bound("");
@@ -0,0 +1,26 @@
+// @stc-strict
+
+declare let f1: (x: string | number) => void;
+declare let f2: (x: string) => void;
+
+f2 = f1;
+
+interface M {
+    m(x: string | number): void;
+}
+
+declare let m: M;
+declare let n: { m(x: string): void };
+
+// Parameters of methods are compared bivariantly.
+m = n;
+
+class A {
+    m(x: string | number) {}
+}
+
+class B {
+    m(x: string) {}
+}
+
+export const a: A = new B();
@@ -0,0 +1,19 @@
+class Builder {
+    self: this;
+
+    set(v: number): this {
+        return this;
+    }
+}
+
+class DerivedBuilder extends Builder {
+    extra(): this {
+        return this;
+    }
+}
+
+export function f(b: DerivedBuilder) {
+    const a: DerivedBuilder = b.set(1).extra();
+    const c: DerivedBuilder = b.self;
+    return [a, c];
+}
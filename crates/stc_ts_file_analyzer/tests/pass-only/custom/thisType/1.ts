@@ -0,0 +1,26 @@
+type ObjectDescriptor<D, M> = {
+    data?: D;
+    methods?: M & ThisType<D & M>;
+};
+
+declare function makeObject<D, M>(desc: ObjectDescriptor<D, M>): D & M;
+
+export const obj = makeObject({
+    data: { x: 0, y: 0 },
+    methods: {
+        moveBy(dx: number, dy: number) {
+            this.x += dx;
+            this.y += dy;
+        },
+    },
+});
+
+interface Point {
+    x: number;
+}
+
+export const p: { show(): number } & ThisType<Point> = {
+    show() {
+        return this.x;
+    },
+};
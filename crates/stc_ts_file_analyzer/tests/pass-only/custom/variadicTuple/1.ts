@@ -0,0 +1,13 @@
+declare function concat<T extends unknown[], U extends unknown[]>(a: T, b: U): [...T, ...U];
+declare function tail<T extends unknown[]>(t: [unknown, ...T]): T;
+declare function init<T extends unknown[]>(t: [...T, boolean]): T;
+
+declare const sn: [string, number];
+declare const snb: [string, number, boolean];
+
+const c: [string, number, boolean] = concat(sn, [true] as [boolean]);
+const t: [number, boolean] = tail(snb);
+const i: [string, number] = init(snb);
+
+type Concat<A extends unknown[], B extends unknown[]> = [...A, ...B];
+const x: Concat<[1], [2, 3]> = [1, 2, 3];
@@ -0,0 +1,34 @@
+class Range {
+    constructor(private start: number, private end: number) {}
+
+    *[Symbol.iterator](): Iterator<number> {
+        for (let i = this.start; i < this.end; i++) {
+            yield i;
+        }
+    }
+}
+
+const range = new Range(0, 3);
+const iter: Iterator<number> = range[Symbol.iterator]();
+for (const v of range) {
+    const n: number = v;
+}
+const spread: number[] = [...range];
+
+interface Money {
+    [Symbol.toPrimitive](hint: string): number;
+}
+
+declare const money: Money;
+const amount: number = money[Symbol.toPrimitive]("number");
+
+const iterable = {
+    [Symbol.iterator]() {
+        return [1, 2, 3][Symbol.iterator]();
+    },
+};
+for (const v of iterable) {
+    const n: number = v;
+}
+
+const iterableLike: Iterable<number> = iterable;
@@ -0,0 +1,15 @@
+class Stream {
+    async *[Symbol.asyncIterator](): AsyncIterator<string> {
+        yield "a";
+    }
+}
+
+async function main() {
+    const stream = new Stream();
+    const iter: AsyncIterator<string> = stream[Symbol.asyncIterator]();
+    for await (const chunk of stream) {
+        const s: string = chunk;
+    }
+}
+
+export {};
@@ -9,7 +9,7 @@ use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_testing::init_tracing;
 use stc_ts_ast_rnode::RModule;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_env::{Env, ModuleKind};
 use stc_ts_file_analyzer::{
     analyzer::{Analyzer, NoopLoader},
     env::EnvFactory,
@@ -26,7 +26,7 @@ fn profile_file(name: &str, path: &Path) {
     testing::run_test2(false, |cm, _handler| {
         let fm = cm.load_file(path).unwrap();
 
-        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &[Lib::Es5]);
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &[Lib::Es5]);
 
         let mut node_id_gen = NodeIdGenerator::default();
         let mut module = {
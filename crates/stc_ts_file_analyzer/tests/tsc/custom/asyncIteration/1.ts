@@ -0,0 +1,14 @@
+declare const chunks: AsyncIterable<string>;
+
+function notAsync() {
+    // 'for await' loops are only allowed within async functions and at the top levels of modules.
+    for await (const chunk of chunks) {
+    }
+}
+
+async function* numbers(): AsyncGenerator<number> {
+    // Type 'string' is not assignable to type 'number'.
+    yield Promise.resolve("a");
+}
+
+export {};
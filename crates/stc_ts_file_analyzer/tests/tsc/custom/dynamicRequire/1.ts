@@ -0,0 +1,7 @@
+declare function require(id: string): { loaded: true };
+declare const name: string;
+
+const a: string = require(name);
+const b: true = require(`${name}/index`).loaded;
+
+export {};
@@ -1,14 +1,14 @@
-use std::sync::Arc;
+use std::{ops::ControlFlow, sync::Arc};
 
 use fxhash::{FxHashMap, FxHashSet};
-use rnode::{Fold, FoldWith, Visit, VisitWith};
+use rnode::{Fold, FoldWith, TryVisit, TryVisitWith, TryVisitor};
 use stc_ts_ast_rnode::{RExpr, RInvalid, RTsEntityName, RTsLit};
 use stc_ts_base_type_ops::{apply_mapped_flags, fix::Fix};
 use stc_ts_errors::debug::dump_type_as_string;
 use stc_ts_types::{
-    Array, ArrayMetadata, CallSignature, ClassProperty, ComputedKey, ConstructorSignature, Function, Id, IndexSignature, IndexedAccessType,
-    InferType, Key, KeywordType, KeywordTypeMetadata, LitType, Mapped, Method, MethodSignature, Operator, PropertySignature, Ref, Type,
-    TypeElement, TypeLit, TypeParam,
+    Array, ArrayMetadata, CallSignature, ClassProperty, ComputedKey, ConstructorSignature, Freezed, Function, Id, IndexSignature,
+    IndexedAccessType, InferType, Key, KeywordType, KeywordTypeMetadata, LitType, Mapped, Method, MethodSignature, Operator,
+    PropertySignature, Ref, Type, TypeElement, TypeLit, TypeParam,
 };
 use stc_utils::{cache::Freeze, stack};
 use stc_visit::visit_cache;
@@ -17,7 +17,7 @@ use swc_common::{SourceMap, Spanned, DUMMY_SP};
 use swc_ecma_ast::{TsKeywordTypeKind, TsTypeOperatorOp};
 use tracing::{debug, error, info, warn};
 
-use crate::{type_param::finder::TypeParamNameUsageFinder, ExpandGenericOpts};
+use crate::{type_param::finder::uses_type_param, ExpandGenericOpts};
 
 #[derive(Debug)]
 pub struct InferTypeResult {
@@ -172,6 +172,7 @@ impl GenericExpander<'_> {
                                             match member {
                                                 TypeElement::Property(p) => members.push(TypeElement::Property(PropertySignature {
                                                     type_ann: m.ty.clone().fold_with(&mut MappedHandler {
+                                                        shared: Default::default(),
                                                         key: &p.key,
                                                         param_name: &param.name,
                                                         prop_ty: &p
@@ -189,6 +190,7 @@ impl GenericExpander<'_> {
                                                     optional: method.optional,
                                                     params: Default::default(),
                                                     type_ann: m.ty.clone().fold_with(&mut MappedHandler {
+                                                        shared: Default::default(),
                                                         key: &method.key,
                                                         param_name: &param.name,
                                                         prop_ty: &Type::Function(Function {
@@ -206,6 +208,7 @@ impl GenericExpander<'_> {
                                                     type_params: Default::default(),
                                                     metadata: Default::default(),
                                                     accessor: Default::default(),
+                                                    write_type: Default::default(),
                                                 })),
                                                 _ => {}
                                             }
@@ -371,6 +374,7 @@ impl GenericExpander<'_> {
                                                 type_params: None,
                                                 metadata: Default::default(),
                                                 accessor: Default::default(),
+                                                write_type: Default::default(),
                                             }));
                                         }
                                         TypeElement::Property(p) => {
@@ -484,27 +488,21 @@ impl Fold<Type> for GenericExpander<'_> {
 
         {
             // TODO(kdy1): Remove this block, after fixing a regression of a mapped types.
-            let mut v = TypeParamNameUsageFinder::default();
-            ty.visit_with(&mut v);
-            let will_expand = v.params.iter().any(|param| self.params.contains_key(param));
+            let will_expand = uses_type_param(&ty, |param| self.params.contains_key(param));
             if !will_expand {
                 return ty;
             }
         }
 
         {
-            let mut checker = GenericChecker {
-                params: self.params,
-                found: false,
-            };
-            ty.visit_with(&mut checker);
-            if !checker.found {
+            let mut checker = GenericChecker { params: self.params };
+            if ty.try_visit_with(&mut checker).is_continue() {
                 return ty;
             }
         }
 
         let start = dump_type_as_string(&ty);
-        let ty = self.fold_type(ty).fixed();
+        let ty = stack::maybe_grow(|| self.fold_type(ty)).fixed();
         ty.assert_valid();
         let expanded = dump_type_as_string(&ty);
 
@@ -592,40 +590,39 @@ impl Fold<Method> for GenericExpander<'_> {
     }
 }
 
-/// This [Visit] implementation is used to check if one of the type parameters
-/// are used.
+/// This [TryVisit] implementation is used to check if one of the type
+/// parameters are used. It breaks on the first usage.
 struct GenericChecker<'a> {
     params: &'a FxHashMap<Id, Type>,
-    found: bool,
 }
 
-impl Visit<Type> for GenericChecker<'_> {
-    fn visit(&mut self, ty: &Type) {
-        if self.found {
-            return;
-        }
+impl TryVisitor for GenericChecker<'_> {
+    type Break = ();
+}
 
+impl TryVisit<Type> for GenericChecker<'_> {
+    fn try_visit(&mut self, ty: &Type) -> ControlFlow<()> {
         let key = ty as *const Type as *const ();
 
-        if let Some(v) = GENERIC_CACHE.get_copied(key) {
-            self.found |= v;
-            return;
+        if let Some(found) = GENERIC_CACHE.get_copied(key) {
+            return if found { ControlFlow::Break(()) } else { ControlFlow::Continue(()) };
         }
 
-        ty.visit_children_with(self);
+        let res = ty.try_visit_children_with(self);
 
-        GENERIC_CACHE.insert(key, self.found);
+        GENERIC_CACHE.insert(key, res.is_break());
+
+        res
     }
 }
 
-impl Visit<TypeParam> for GenericChecker<'_> {
-    fn visit(&mut self, ty: &TypeParam) {
+impl TryVisit<TypeParam> for GenericChecker<'_> {
+    fn try_visit(&mut self, ty: &TypeParam) -> ControlFlow<()> {
         if self.params.contains_key(&ty.name) {
-            self.found = true;
-            return;
+            return ControlFlow::Break(());
         }
 
-        ty.visit_children_with(self);
+        ty.try_visit_children_with(self)
     }
 }
 
@@ -633,39 +630,108 @@ struct MappedHandler<'d> {
     param_name: &'d Id,
     prop_ty: &'d Type,
     key: &'d Key,
+    /// Whether a shared type contains a target, by the address of the shared
+    /// type.
+    ///
+    /// The shared types are kept alive, so their addresses are not reused
+    /// while folding.
+    shared: FxHashMap<*const Type, (Freezed, bool)>,
+}
+
+/// Returns `true` if `ty` is `T[K]` where `K extends keyof T` and `T` is
+/// `param_name`.
+fn is_mapped_target(param_name: &Id, ty: &IndexedAccessType) -> bool {
+    if let Type::Param(TypeParam { name: obj_param_name, .. }) = ty.obj_type.normalize() {
+        if let Type::Param(TypeParam {
+            constraint: Some(index_type_constraint),
+            ..
+        }) = ty.index_type.normalize()
+        {
+            if let Type::Operator(
+                operator @ Operator {
+                    op: TsTypeOperatorOp::KeyOf,
+                    ..
+                },
+            ) = index_type_constraint.normalize()
+            {
+                if let Type::Param(constraint_param) = operator.ty.normalize() {
+                    return *obj_param_name == constraint_param.name && *param_name == *obj_param_name;
+                }
+            }
+        }
+    }
+
+    false
 }
 
 impl Fold<Type> for MappedHandler<'_> {
     fn fold(&mut self, mut ty: Type) -> Type {
         if let Type::IndexedAccessType(ty) = ty.normalize() {
-            if let Type::Param(TypeParam { name: obj_param_name, .. }) = ty.obj_type.normalize() {
-                if let Type::Param(TypeParam {
-                    name: index_param_name,
-                    constraint: Some(index_type_constraint),
-                    ..
-                }) = ty.index_type.normalize()
-                {
-                    if let Type::Operator(
-                        operator @ Operator {
-                            op: TsTypeOperatorOp::KeyOf,
-                            ..
-                        },
-                    ) = index_type_constraint.normalize()
-                    {
-                        if let Type::Param(constraint_param) = operator.ty.normalize() {
-                            if *obj_param_name == constraint_param.name && *self.param_name == *obj_param_name {
-                                return self.prop_ty.clone();
-                            }
-                        }
-                    }
-                }
+            if is_mapped_target(self.param_name, ty) {
+                return self.prop_ty.clone();
             }
         }
 
-        // TODO(kdy1): PERF
-        ty.normalize_mut();
-        ty = ty.fold_children_with(self);
+        // Shared (frozen) types without a target are returned as-is, and others are
+        // copied on write.
+        if let Type::Arc(..) = ty {
+            let found = ty
+                .try_visit_with(&mut MappedTargetFinder {
+                    param_name: self.param_name,
+                    shared: &mut self.shared,
+                })
+                .is_break();
+            if !found {
+                return ty;
+            }
 
-        ty
+            ty.normalize_mut();
+        }
+
+        ty.fold_children_with(self)
+    }
+}
+
+/// Searches for a target of [MappedHandler], visiting each shared type at most
+/// once per expansion.
+struct MappedTargetFinder<'a> {
+    param_name: &'a Id,
+    shared: &'a mut FxHashMap<*const Type, (Freezed, bool)>,
+}
+
+impl TryVisitor for MappedTargetFinder<'_> {
+    type Break = ();
+}
+
+impl TryVisit<Type> for MappedTargetFinder<'_> {
+    fn try_visit(&mut self, ty: &Type) -> ControlFlow<()> {
+        let freezed = match ty {
+            Type::Arc(freezed) => freezed,
+            _ => return ty.try_visit_children_with(self),
+        };
+
+        let key = ty.normalize() as *const Type;
+        if let Some((_, found)) = self.shared.get(&key) {
+            return if *found {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            };
+        }
+
+        let res = ty.try_visit_children_with(self);
+        self.shared.insert(key, (freezed.clone(), res.is_break()));
+
+        res
+    }
+}
+
+impl TryVisit<IndexedAccessType> for MappedTargetFinder<'_> {
+    fn try_visit(&mut self, ty: &IndexedAccessType) -> ControlFlow<()> {
+        if is_mapped_target(self.param_name, ty) {
+            return ControlFlow::Break(());
+        }
+
+        ty.try_visit_children_with(self)
     }
 }
@@ -1,5 +1,7 @@
+use std::ops::ControlFlow;
+
 use fxhash::FxHashSet;
-use rnode::{Visit, VisitWith};
+use rnode::{TryVisit, TryVisitWith, TryVisitor, Visit, VisitWith};
 use stc_ts_types::{Id, InferType, TypeParam, TypeParamDecl};
 use stc_utils::cache::ALLOW_DEEP_CLONE;
 
@@ -52,6 +54,58 @@ impl Visit<TypeParam> for TypeParamNameUsageFinder {
     }
 }
 
+/// Returns `true` if `node` uses a type parameter for which `pred` returns
+/// `true`.
+///
+/// Unlike [TypeParamNameUsageFinder], this stops at the first match.
+pub fn uses_type_param<N, F>(node: &N, pred: F) -> bool
+where
+    N: TryVisitWith<TypeParamNameUsageChecker<F>>,
+    F: FnMut(&Id) -> bool,
+{
+    let mut v = TypeParamNameUsageChecker { pred };
+    node.try_visit_with(&mut v).is_break()
+}
+
+/// Early-exit version of [TypeParamNameUsageFinder].
+pub struct TypeParamNameUsageChecker<F>
+where
+    F: FnMut(&Id) -> bool,
+{
+    pred: F,
+}
+
+impl<F> TryVisitor for TypeParamNameUsageChecker<F>
+where
+    F: FnMut(&Id) -> bool,
+{
+    type Break = ();
+}
+
+/// Noop as declaration is not usage.
+impl<F> TryVisit<TypeParamDecl> for TypeParamNameUsageChecker<F>
+where
+    F: FnMut(&Id) -> bool,
+{
+    #[inline]
+    fn try_visit(&mut self, _: &TypeParamDecl) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+impl<F> TryVisit<TypeParam> for TypeParamNameUsageChecker<F>
+where
+    F: FnMut(&Id) -> bool,
+{
+    fn try_visit(&mut self, node: &TypeParam) -> ControlFlow<()> {
+        if (self.pred)(&node.name) {
+            return ControlFlow::Break(());
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct TypeParamUsageFinder {
     pub params: Vec<TypeParam>,
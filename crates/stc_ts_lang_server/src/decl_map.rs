@@ -0,0 +1,197 @@
+//! Declaration maps (`.d.ts.map`), which map declarations in a `.d.ts` file
+//! to the `.ts` file it was generated from.
+//!
+//! Like tsserver, locations in `.d.ts` files of dependencies can be mapped to
+//! their original sources. This is opt-in, because the original sources may
+//! not be shipped with the dependency.
+//!
+//! The server does not answer definition requests yet, so there's no flag to
+//! enable this. [DeclarationMaps::map_location] should be applied to the
+//! locations of definitions once it does.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Context, Result};
+use sourcemap::{decode_data_url, DecodedMap, SourceMap};
+use tower_lsp::lsp_types::{Location, Position, Range, Url};
+
+const SOURCE_MAPPING_URL: &str = "//# sourceMappingURL=";
+
+/// A parsed declaration map of a `.d.ts` file.
+pub struct DeclarationMap {
+    map: SourceMap,
+    /// The directory sources are resolved from.
+    dir: PathBuf,
+}
+
+impl DeclarationMap {
+    /// Reads the declaration map of `dts`, referenced by the
+    /// `sourceMappingURL` comment of it or placed next to it.
+    ///
+    /// Returns [None] if `dts` does not have a declaration map.
+    pub fn read(dts: &Path) -> Result<Option<Self>> {
+        let content = fs::read_to_string(dts).with_context(|| format!("failed to read {}", dts.display()))?;
+        let dir = dts.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let url = content
+            .lines()
+            .rev()
+            .find_map(|line| line.trim().strip_prefix(SOURCE_MAPPING_URL))
+            .map(str::trim);
+
+        if let Some(url) = url {
+            if url.starts_with("data:") {
+                return match decode_data_url(url).context("failed to decode an inline declaration map")? {
+                    DecodedMap::Regular(map) => Ok(Some(DeclarationMap { map, dir })),
+                    _ => Err(anyhow!("{}: indexed declaration maps are not supported", dts.display())),
+                };
+            }
+
+            let path = dir.join(url);
+            let map_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            let content = fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+            return Self::from_slice(map_dir, &content).map(Some);
+        }
+
+        let mut path = dts.as_os_str().to_os_string();
+        path.push(".map");
+        let path = PathBuf::from(path);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_slice(dir, &content).map(Some)
+    }
+
+    /// `dir` is the directory containing the declaration map.
+    pub fn from_slice(dir: PathBuf, content: &[u8]) -> Result<Self> {
+        let map = SourceMap::from_slice(content).context("failed to parse a declaration map")?;
+
+        Ok(DeclarationMap { map, dir })
+    }
+
+    /// Returns the path of the source and the position in it.
+    ///
+    /// Positions after the start of a mapping are mapped by their offset from
+    /// the start, because a declaration map only has mappings for the start
+    /// of identifiers.
+    pub fn original_position(&self, pos: Position) -> Option<(PathBuf, Position)> {
+        let token = self.map.lookup_token(pos.line, pos.character)?;
+        if token.get_dst_line() != pos.line {
+            return None;
+        }
+        let source = token.get_source()?;
+
+        let offset = pos.character.saturating_sub(token.get_dst_col());
+
+        Some((
+            normalize_path(&self.dir.join(source)),
+            Position {
+                line: token.get_src_line(),
+                character: token.get_src_col() + offset,
+            },
+        ))
+    }
+
+    /// Returns [None] if `range` cannot be mapped.
+    pub fn original_range(&self, range: Range) -> Option<(PathBuf, Range)> {
+        let (path, start) = self.original_position(range.start)?;
+        let end = match self.original_position(range.end) {
+            Some((end_path, end)) if end_path == path && end >= start => end,
+            _ => start,
+        };
+
+        Some((path, Range { start, end }))
+    }
+}
+
+/// Declaration maps loaded by the language server.
+#[derive(Default)]
+pub struct DeclarationMaps {
+    enabled: bool,
+    cache: Mutex<HashMap<PathBuf, Option<Arc<DeclarationMap>>>>,
+}
+
+impl DeclarationMaps {
+    pub fn new(enabled: bool) -> Self {
+        DeclarationMaps {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the declaration map of `dts`, reading it if it's not cached.
+    ///
+    /// A declaration map which cannot be read is treated as if it does not
+    /// exist.
+    pub fn get(&self, dts: &Path) -> Option<Arc<DeclarationMap>> {
+        if let Some(map) = self.cache.lock().unwrap().get(dts) {
+            return map.clone();
+        }
+
+        let map = match DeclarationMap::read(dts) {
+            Ok(map) => map.map(Arc::new),
+            Err(err) => {
+                tracing::warn!("failed to load the declaration map of {}: {:?}", dts.display(), err);
+                None
+            }
+        };
+
+        self.cache.lock().unwrap().insert(dts.to_path_buf(), map.clone());
+        map
+    }
+
+    /// Maps `loc` to the original source if it's in a `.d.ts` file with a
+    /// declaration map. Otherwise, `loc` is returned as-is.
+    pub fn map_location(&self, loc: Location) -> Location {
+        if !self.enabled {
+            return loc;
+        }
+
+        let path = match loc.uri.to_file_path() {
+            Ok(path) if path.to_string_lossy().ends_with(".d.ts") => path,
+            _ => return loc,
+        };
+
+        let mapped = self.get(&path).and_then(|map| map.original_range(loc.range));
+        match mapped {
+            Some((source, range)) if source.is_file() => match Url::from_file_path(&source) {
+                Ok(uri) => Location { uri, range },
+                Err(()) => loc,
+            },
+            _ => loc,
+        }
+    }
+
+    /// Drops the cached declaration map of `dts`, like when it's modified.
+    pub fn invalidate(&self, dts: &Path) {
+        self.cache.lock().unwrap().remove(dts);
+    }
+}
+
+/// Removes `.` and `..` without accessing the file system.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(c);
+                }
+            }
+            _ => normalized.push(c),
+        }
+    }
+    normalized
+}
@@ -7,6 +7,10 @@ use tower_lsp::{
 };
 use tracing::info;
 
+pub mod decl_map;
+pub mod pos;
+pub mod twoslash;
+
 #[derive(Debug, Args)]
 pub struct LspCommand {}
 
@@ -0,0 +1,33 @@
+//! Conversion between swc positions and LSP positions.
+
+use stc_utils::line_index::{LineCol, PosMapper};
+use swc_common::{BytePos, SourceFile, Span};
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Returns [None] for dummy spans.
+pub fn to_lsp_range(positions: &PosMapper, span: Span) -> Option<Range> {
+    let (_, range) = positions.range(span)?;
+
+    Some(Range {
+        start: to_lsp_position(range.start),
+        end: to_lsp_position(range.end),
+    })
+}
+
+pub fn to_lsp_position(pos: LineCol) -> Position {
+    Position {
+        line: pos.line,
+        character: pos.col,
+    }
+}
+
+/// Returns [None] if the line does not exist in `file`.
+pub fn from_lsp_position(positions: &PosMapper, file: &SourceFile, pos: Position) -> Option<BytePos> {
+    positions.byte_pos(
+        file,
+        LineCol {
+            line: pos.line,
+            col: pos.character,
+        },
+    )
+}
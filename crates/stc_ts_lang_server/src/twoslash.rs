@@ -0,0 +1,381 @@
+//! Twoslash-style annotation of a source string, for documentation tools and
+//! playgrounds.
+//!
+//! ```ts
+//! declare const a: { foo: number };
+//! a.foo;
+//! // ^?
+//! a.f
+//! // ^|
+//! ```
+//!
+//! A `^?` comment queries the type of the expression above the caret, and a
+//! `^|` comment requests completions at the caret. Those comments are removed
+//! from [TwoslashOutput::code], and all positions are relative to it.
+//!
+//! Only member completions (`obj.prefix`) are supported.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, bail, Result};
+use rnode::{NodeIdGenerator, RNode, VisitWith};
+use serde::Serialize;
+use stc_ts_ast_rnode::RModule;
+use stc_ts_env::Env;
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::{
+    analyzer::{Analyzer, NoopLoader},
+    recorder::{print_type, TypeRecorder},
+};
+use stc_ts_storage::{ErrorStore, Single};
+use stc_ts_types::{module_id::ModuleIdGenerator, Key, Type, TypeElement};
+use stc_ts_utils::StcComments;
+use stc_utils::line_index::{LineCol, PosEncoding, PosMapper};
+use swc_common::{input::SourceFileInput, BytePos, FileName, SourceMap, Span, Spanned, SyntaxContext};
+use swc_ecma_ast::{
+    CallExpr, Callee, EsVersion, ExportAll, ImportDecl, NamedExport, TsImportEqualsDecl, TsImportType, TsModuleDecl, TsModuleName,
+    TsModuleRef,
+};
+use swc_ecma_parser::{lexer::Lexer, Parser, Syntax, TsConfig};
+use swc_ecma_transforms::resolver;
+use swc_ecma_visit::{FoldWith, Visit, VisitWith as _};
+use tower_lsp::lsp_types::Position;
+
+use crate::pos::to_lsp_position;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoslashOutput {
+    /// The input without `^?` and `^|` comments.
+    pub code: String,
+    pub queries: Vec<TwoslashQuery>,
+    pub completions: Vec<TwoslashCompletion>,
+    pub errors: Vec<TwoslashError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoslashQuery {
+    pub position: Position,
+    /// The type of the smallest expression at [TwoslashQuery::position], or
+    /// [None] if there's no expression.
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoslashCompletion {
+    pub position: Position,
+    /// The part of the property name before the caret.
+    pub prefix: String,
+    /// Sorted names of the properties starting with
+    /// [TwoslashCompletion::prefix].
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoslashError {
+    pub start: Position,
+    pub end: Position,
+    pub code: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarkerKind {
+    Query,
+    Completion,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Marker {
+    kind: MarkerKind,
+    pos: LineCol,
+}
+
+/// Type checks `src` as a module.
+///
+/// The caller should ensure that [swc_common::GLOBALS] matches the one used
+/// to create `env`. Sources with imports or `declare module "foo"` are
+/// rejected, as other modules can't be loaded.
+pub fn twoslash(env: Env, src: &str) -> Result<TwoslashOutput> {
+    let (code, markers) = extract_markers(src);
+
+    let cm = Arc::new(SourceMap::default());
+    let fm = cm.new_source_file(FileName::Custom("input.ts".into()), code.clone());
+    let positions = PosMapper::new(cm.clone(), PosEncoding::Utf16);
+
+    let mut queries = vec![];
+    let mut completions = vec![];
+    for marker in &markers {
+        let pos = match positions.byte_pos(&fm, marker.pos) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        match marker.kind {
+            MarkerKind::Query => queries.push((marker.pos, pos)),
+            MarkerKind::Completion => {
+                let offset = (pos - fm.start_pos).0 as usize;
+                let before = &code[..offset];
+                let prefix_start = before.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == '$').len();
+                // End of the object of the member expression.
+                let obj_end = if before[..prefix_start].ends_with('.') {
+                    Some(fm.start_pos + BytePos(prefix_start as u32 - 1))
+                } else {
+                    None
+                };
+
+                completions.push((marker.pos, before[prefix_start..].to_string(), obj_end));
+            }
+        }
+    }
+
+    let recorder = Arc::new(Recorder {
+        cm: cm.clone(),
+        queries: queries.iter().map(|(_, pos)| *pos).collect(),
+        member_positions: completions.iter().filter_map(|(_, _, obj_end)| *obj_end).collect(),
+        types: Default::default(),
+        members: Default::default(),
+    });
+
+    let path = Arc::new(fm.name.clone());
+    let (module_id, top_level_mark) = ModuleIdGenerator::default().generate(&path);
+
+    let comments = StcComments::default();
+    let module = {
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig {
+                decorators: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            SourceFileInput::from(&*fm),
+            Some(&comments),
+        );
+        let mut parser = Parser::new_from(lexer);
+        parser
+            .parse_module()
+            .map_err(|err| anyhow!("failed to parse: {}", err.kind().msg()))?
+    };
+
+    let mut finder = ImportFinder::default();
+    module.visit_with(&mut finder);
+    if finder.found {
+        bail!("imports and `declare module` are not supported");
+    }
+
+    let module = module.fold_with(&mut resolver(env.shared().marks().unresolved_mark(), top_level_mark, true));
+    let module = RModule::from_orig(&mut NodeIdGenerator::default(), module);
+
+    let mut storage = Single {
+        parent: None,
+        id: module_id,
+        top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+        path,
+        is_dts: false,
+        info: Default::default(),
+    };
+
+    {
+        let mut analyzer =
+            Analyzer::root(env, cm.clone(), comments, Box::new(&mut storage), &NoopLoader, None).with_type_recorder(recorder.clone());
+        module.visit_with(&mut analyzer);
+    }
+
+    let errors = ErrorKind::flatten(storage.take_errors().into())
+        .into_iter()
+        .filter_map(|err| {
+            let (_, range) = positions.range(err.span())?;
+
+            Some(TwoslashError {
+                start: to_lsp_position(range.start),
+                end: to_lsp_position(range.end),
+                code: err.code(),
+                message: format!("{:?}", *err),
+            })
+        })
+        .collect();
+
+    let types = recorder.types.lock().unwrap();
+    let queries = queries
+        .iter()
+        .enumerate()
+        .map(|(idx, (pos, _))| TwoslashQuery {
+            position: to_lsp_position(*pos),
+            text: types.get(&idx).map(|(_, text)| text.clone()),
+        })
+        .collect();
+
+    let members = recorder.members.lock().unwrap();
+    let completions = completions
+        .into_iter()
+        .map(|(pos, prefix, obj_end)| {
+            let items = obj_end
+                .and_then(|obj_end| members.get(&obj_end))
+                .map(|names| names.iter().filter(|name| name.starts_with(&prefix)).cloned().collect())
+                .unwrap_or_default();
+
+            TwoslashCompletion {
+                position: to_lsp_position(pos),
+                prefix,
+                items,
+            }
+        })
+        .collect();
+
+    Ok(TwoslashOutput {
+        code,
+        queries,
+        completions,
+        errors,
+    })
+}
+
+/// Removes `^?` and `^|` comments from `src`.
+///
+/// A marker points at the last line which is not a marker, and its column is
+/// the column of the caret.
+fn extract_markers(src: &str) -> (String, Vec<Marker>) {
+    let mut lines = vec![];
+    let mut markers = vec![];
+
+    for line in src.lines() {
+        let trimmed = line.trim_start();
+        let marker = trimmed.strip_prefix("//").map(str::trim_start).and_then(|comment| {
+            if comment.starts_with("^?") {
+                Some(MarkerKind::Query)
+            } else if comment.starts_with("^|") {
+                Some(MarkerKind::Completion)
+            } else {
+                None
+            }
+        });
+
+        match marker {
+            Some(kind) if !lines.is_empty() => {
+                let caret = line.find('^').unwrap();
+
+                markers.push(Marker {
+                    kind,
+                    pos: LineCol {
+                        line: lines.len() as u32 - 1,
+                        col: line[..caret].encode_utf16().count() as u32,
+                    },
+                });
+            }
+            _ => lines.push(line),
+        }
+    }
+
+    let mut code = lines.join("\n");
+    if src.ends_with('\n') {
+        code.push('\n');
+    }
+
+    (code, markers)
+}
+
+/// Finds references to other modules, which can't be loaded by
+/// [NoopLoader].
+#[derive(Default)]
+struct ImportFinder {
+    found: bool,
+}
+
+impl Visit for ImportFinder {
+    fn visit_import_decl(&mut self, _: &ImportDecl) {
+        self.found = true;
+    }
+
+    fn visit_export_all(&mut self, _: &ExportAll) {
+        self.found = true;
+    }
+
+    fn visit_named_export(&mut self, e: &NamedExport) {
+        self.found |= e.src.is_some();
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, d: &TsImportEqualsDecl) {
+        self.found |= matches!(d.module_ref, TsModuleRef::TsExternalModuleRef(..));
+    }
+
+    fn visit_ts_import_type(&mut self, _: &TsImportType) {
+        self.found = true;
+    }
+
+    fn visit_ts_module_decl(&mut self, d: &TsModuleDecl) {
+        if matches!(d.id, TsModuleName::Str(..)) {
+            self.found = true;
+            return;
+        }
+
+        d.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, e: &CallExpr) {
+        if let Callee::Import(..) = e.callee {
+            self.found = true;
+            return;
+        }
+
+        e.visit_children_with(self);
+    }
+}
+
+struct Recorder {
+    cm: Arc<SourceMap>,
+    queries: Vec<BytePos>,
+    /// Ends of objects of member expressions with a completion marker.
+    member_positions: Vec<BytePos>,
+    /// Index of query to the length of the expression and the type.
+    types: Mutex<HashMap<usize, (u32, String)>>,
+    members: Mutex<HashMap<BytePos, Vec<String>>>,
+}
+
+impl TypeRecorder for Recorder {
+    fn record_type(&self, span: Span, ty: &Type) {
+        let len = (span.hi - span.lo).0;
+
+        for (idx, pos) in self.queries.iter().enumerate() {
+            if !(span.lo <= *pos && *pos < span.hi) {
+                continue;
+            }
+
+            let mut types = self.types.lock().unwrap();
+            // The last type of the smallest expression wins.
+            if matches!(types.get(&idx), Some((prev, _)) if *prev < len) {
+                continue;
+            }
+            types.insert(idx, (len, print_type(&self.cm, ty)));
+        }
+    }
+
+    fn needs_members(&self, span: Span) -> bool {
+        self.member_positions.contains(&span.hi)
+    }
+
+    fn record_members(&self, span: Span, members: &[TypeElement]) {
+        let mut names = members
+            .iter()
+            .filter_map(|member| match member {
+                TypeElement::Property(p) => Some(&p.key),
+                TypeElement::Method(m) => Some(&m.key),
+                _ => None,
+            })
+            .filter_map(|key| match key {
+                Key::Normal { sym, .. } => Some(sym.to_string()),
+                Key::Num(n) => Some(n.value.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        self.members.lock().unwrap().insert(span.hi, names);
+    }
+}
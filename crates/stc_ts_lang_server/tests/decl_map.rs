@@ -0,0 +1,83 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use stc_ts_lang_server::decl_map::{DeclarationMap, DeclarationMaps};
+use tower_lsp::lsp_types::{Location, Position, Range, Url};
+
+// Maps `foo` of `export declare function foo(): void;` to `foo` of
+// `export function foo() {}`.
+const MAP: &str = r#"{"version":3,"file":"foo.d.ts","sourceRoot":"","sources":["../src/foo.ts"],"names":[],"mappings":"wBAAgB"}"#;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("stc-decl-map-test").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    fs::write(dir.join("src").join("foo.ts"), "export function foo() {}\n").unwrap();
+    dir
+}
+
+fn pos(line: u32, character: u32) -> Position {
+    Position { line, character }
+}
+
+#[test]
+fn original_position() {
+    let map = DeclarationMap::from_slice(Path::new("/project/dist").to_path_buf(), MAP.as_bytes()).unwrap();
+
+    let (path, start) = map.original_position(pos(0, 24)).unwrap();
+    assert_eq!(path, Path::new("/project/src/foo.ts"));
+    assert_eq!(start, pos(0, 16));
+
+    let (_, end) = map.original_position(pos(0, 27)).unwrap();
+    assert_eq!(end, pos(0, 19));
+
+    assert_eq!(map.original_position(pos(1, 0)), None);
+}
+
+#[test]
+fn source_mapping_url() {
+    let dir = temp_dir("source_mapping_url");
+    let dts = dir.join("dist").join("foo.d.ts");
+    fs::write(&dts, "export declare function foo(): void;\n//# sourceMappingURL=foo.d.ts.map\n").unwrap();
+    fs::write(dir.join("dist").join("foo.d.ts.map"), MAP).unwrap();
+
+    let map = DeclarationMap::read(&dts).unwrap().expect("should find the declaration map");
+    let (path, _) = map.original_position(pos(0, 24)).unwrap();
+    assert_eq!(path, dir.join("src").join("foo.ts"));
+}
+
+#[test]
+fn no_declaration_map() {
+    let dir = temp_dir("no_declaration_map");
+    let dts = dir.join("dist").join("foo.d.ts");
+    fs::write(&dts, "export declare function foo(): void;\n").unwrap();
+
+    assert!(DeclarationMap::read(&dts).unwrap().is_none());
+}
+
+#[test]
+fn map_location() {
+    let dir = temp_dir("map_location");
+    let dts = dir.join("dist").join("foo.d.ts");
+    fs::write(&dts, "export declare function foo(): void;\n").unwrap();
+    fs::write(dir.join("dist").join("foo.d.ts.map"), MAP).unwrap();
+
+    let loc = Location {
+        uri: Url::from_file_path(&dts).unwrap(),
+        range: Range {
+            start: pos(0, 24),
+            end: pos(0, 27),
+        },
+    };
+
+    let disabled = DeclarationMaps::new(false).map_location(loc.clone());
+    assert_eq!(disabled, loc);
+
+    let mapped = DeclarationMaps::new(true).map_location(loc);
+    assert_eq!(mapped.uri, Url::from_file_path(dir.join("src").join("foo.ts")).unwrap());
+    assert_eq!(mapped.range.start, pos(0, 16));
+    assert_eq!(mapped.range.end, pos(0, 19));
+}
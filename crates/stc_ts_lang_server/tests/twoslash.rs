@@ -0,0 +1,55 @@
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_lang_server::twoslash::{twoslash, TwoslashOutput};
+use swc_ecma_ast::EsVersion;
+
+fn run(src: &str) -> TwoslashOutput {
+    try_run(src).unwrap()
+}
+
+fn try_run(src: &str) -> anyhow::Result<TwoslashOutput> {
+    testing::run_test(false, |_, _| {
+        let env = Env::simple(Rule::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+
+        Ok(twoslash(env, src))
+    })
+    .unwrap()
+}
+
+#[test]
+fn query() {
+    let output = run("declare const a: { foo: number };\na.foo;\n// ^?\n");
+
+    assert_eq!(output.code, "declare const a: { foo: number };\na.foo;\n");
+    assert_eq!(output.queries.len(), 1);
+    assert_eq!(output.queries[0].position.line, 1);
+    assert_eq!(output.queries[0].position.character, 3);
+    assert_eq!(output.queries[0].text.as_deref(), Some("number"));
+}
+
+#[test]
+fn completion() {
+    let output = run("declare const a: { foo: number; far: string; bar: boolean };\na.f;\n// ^|\n");
+
+    assert_eq!(output.completions.len(), 1);
+    assert_eq!(output.completions[0].prefix, "f");
+    assert_eq!(output.completions[0].items, vec!["far", "foo"]);
+}
+
+#[test]
+fn errors() {
+    let output = run("const a: string = 1;\n");
+
+    assert_eq!(output.errors.len(), 1);
+    assert_eq!(output.errors[0].code, 2322);
+    assert_eq!(output.errors[0].start.line, 0);
+}
+
+#[test]
+fn imports_are_rejected() {
+    assert!(try_run("import { a } from \"./a\";\n").is_err());
+    assert!(try_run("export * from \"./a\";\n").is_err());
+    assert!(try_run("declare module \"a\" {}\n").is_err());
+    assert!(try_run("type A = import(\"./a\").A;\n").is_err());
+}
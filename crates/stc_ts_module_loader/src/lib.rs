@@ -1,3 +1,5 @@
 #![deny(warnings)]
 
+pub mod package_json;
 pub mod resolvers;
+pub mod version;
@@ -0,0 +1,224 @@
+//! Typed view of the fields of `package.json` which affect type resolution.
+
+use std::{collections::BTreeMap, fs::File, io::BufReader, path::Path};
+
+use anyhow::{Context, Error};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::version::TsVersion;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PackageJson {
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// `"module"` or `"commonjs"`.
+    #[serde(default, rename = "type")]
+    pub ty: Option<String>,
+
+    #[serde(default)]
+    pub types: Option<String>,
+
+    /// Legacy alias of `types`.
+    #[serde(default)]
+    pub typings: Option<String>,
+
+    /// Redirections of paths in the package, keyed by ranges of TypeScript
+    /// versions.
+    #[serde(default, rename = "typesVersions")]
+    pub types_versions: Option<JsonValue>,
+
+    #[serde(default)]
+    pub main: Option<String>,
+
+    #[serde(default)]
+    pub exports: Option<JsonValue>,
+
+    /// Subpath imports, like `#internal/*`.
+    #[serde(default)]
+    pub imports: Option<JsonValue>,
+
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+
+    #[serde(default, rename = "devDependencies")]
+    pub dev_dependencies: BTreeMap<String, String>,
+
+    #[serde(default, rename = "peerDependencies")]
+    pub peer_dependencies: BTreeMap<String, String>,
+}
+
+impl PackageJson {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        let reader = BufReader::new(file);
+        serde_json::from_reader(reader).with_context(|| format!("failed to deserialize {}", path.display()))
+    }
+
+    /// `true` if `.js` and `.d.ts` files of the package are ES modules.
+    pub fn is_esm(&self) -> bool {
+        self.ty.as_deref() == Some("module")
+    }
+
+    /// The `types` field, or the `typings` field if `types` is not set.
+    pub fn types(&self) -> Option<&str> {
+        self.types.as_deref().or(self.typings.as_deref())
+    }
+
+    /// Returns the path mapping of the first entry of `typesVersions` whose
+    /// range matches `version`. The mapping is like `paths` of tsconfig.
+    pub fn types_versions_paths(&self, version: TsVersion) -> Option<Vec<(String, Vec<String>)>> {
+        let map = match &self.types_versions {
+            Some(JsonValue::Object(map)) => map,
+            _ => return None,
+        };
+
+        let (_, paths) = map.iter().find(|(range, _)| version.satisfies(range))?;
+
+        let paths = paths
+            .as_object()?
+            .iter()
+            .map(|(pattern, substitutions)| {
+                let substitutions = match substitutions {
+                    JsonValue::Array(substitutions) => substitutions.iter().filter_map(JsonValue::as_str).map(String::from).collect(),
+                    JsonValue::String(substitution) => vec![substitution.clone()],
+                    _ => vec![],
+                };
+
+                (pattern.clone(), substitutions)
+            })
+            .collect();
+
+        Some(paths)
+    }
+
+    /// Names of all packages listed in `dependencies`, `devDependencies` and
+    /// `peerDependencies`.
+    pub fn dependency_names(&self) -> impl Iterator<Item = &str> {
+        self.dependencies
+            .keys()
+            .chain(self.dev_dependencies.keys())
+            .chain(self.peer_dependencies.keys())
+            .map(|name| &**name)
+    }
+
+    /// Flattens the `exports` field into the list of reachable targets.
+    ///
+    /// Entries are returned in the order node (and tsc) would try them.
+    pub fn export_entries(&self) -> Vec<ExportEntry> {
+        let mut entries = vec![];
+
+        let exports = match &self.exports {
+            Some(exports) => exports,
+            None => return entries,
+        };
+
+        match exports {
+            // `{ ".": ..., "./foo": ... }`
+            JsonValue::Object(map) if map.keys().all(|k| k.starts_with('.')) && !map.is_empty() => {
+                for (subpath, value) in map {
+                    flatten(subpath, &mut vec![], value, &mut entries);
+                }
+            }
+            // Sugar for `{ ".": ... }`
+            _ => flatten(".", &mut vec![], exports, &mut entries),
+        }
+
+        entries
+    }
+
+    /// Flattens the `imports` field, like [PackageJson::export_entries].
+    pub fn import_entries(&self) -> Vec<ExportEntry> {
+        let mut entries = vec![];
+
+        if let Some(JsonValue::Object(map)) = &self.imports {
+            for (subpath, value) in map {
+                flatten(subpath, &mut vec![], value, &mut entries);
+            }
+        }
+
+        entries
+    }
+}
+
+/// A target of the `exports` or `imports` field with the conditions required to
+/// reach it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportEntry {
+    /// e.g. `.`, `./utils`, or `#internal` for `imports`
+    pub subpath: String,
+
+    /// Conditions from the outermost to the innermost one.
+    pub conditions: Vec<String>,
+
+    /// `None` for `null` targets, which exclude the subpath.
+    pub target: Option<String>,
+}
+
+impl ExportEntry {
+    pub fn has_condition(&self, name: &str) -> bool {
+        self.conditions.iter().any(|c| c == name)
+    }
+}
+
+/// A JSON value which keeps the order of the keys of objects.
+///
+/// Conditions of `exports` and ranges of `typesVersions` are tried in order.
+/// [serde_json::Value] keeps the order only if the `preserve_order` feature
+/// is enabled, which would affect every crate of the build.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(IndexMap<String, JsonValue>),
+}
+
+impl JsonValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&IndexMap<String, JsonValue>> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+fn flatten(subpath: &str, conditions: &mut Vec<String>, value: &JsonValue, entries: &mut Vec<ExportEntry>) {
+    match value {
+        JsonValue::String(target) => entries.push(ExportEntry {
+            subpath: subpath.to_string(),
+            conditions: conditions.clone(),
+            target: Some(target.clone()),
+        }),
+        JsonValue::Null => entries.push(ExportEntry {
+            subpath: subpath.to_string(),
+            conditions: conditions.clone(),
+            target: None,
+        }),
+        // Fallback arrays. Only the first valid target is used by node.
+        JsonValue::Array(targets) => {
+            if let Some(first) = targets.first() {
+                flatten(subpath, conditions, first, entries);
+            }
+        }
+        JsonValue::Object(map) => {
+            for (condition, value) in map {
+                conditions.push(condition.clone());
+                flatten(subpath, conditions, value, entries);
+                conditions.pop();
+            }
+        }
+        JsonValue::Bool(..) | JsonValue::Number(..) => {}
+    }
+}
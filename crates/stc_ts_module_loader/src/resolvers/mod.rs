@@ -1 +1,131 @@
+use std::fmt;
+
+use anyhow::Error;
+use stc_ts_env::ModuleConfig;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use self::{node::NodeResolver, node16::Node16Resolver};
+
 pub mod node;
+pub mod node16;
+pub mod paths;
+pub mod references;
+
+/// The resolver selected by `moduleResolution`.
+///
+/// `classic` and `bundler` are resolved like `node10`.
+#[derive(Clone)]
+pub enum ModuleResolver {
+    Node(NodeResolver),
+    Node16(Node16Resolver),
+}
+
+impl ModuleResolver {
+    pub fn new(config: &ModuleConfig) -> Self {
+        if config.resolution.is_node16() {
+            ModuleResolver::Node16(Node16Resolver::new().with_custom_conditions(config.custom_conditions.clone()))
+        } else {
+            ModuleResolver::Node(NodeResolver)
+        }
+    }
+}
+
+impl Resolve for ModuleResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        match self {
+            ModuleResolver::Node(r) => r.resolve(base, target),
+            ModuleResolver::Node16(r) => r.resolve(base, target),
+        }
+    }
+}
+
+/// Errors of module resolution which have a dedicated error code in `tsc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionError {
+    /// A relative import in an ES module does not have an extension.
+    ///
+    /// `suggestion` is the specifier with an extension, if there's a file
+    /// which would be imported.
+    ExtensionRequired { suggestion: Option<String> },
+
+    /// A pattern of `paths` matched the specifier, but none of its
+    /// substitutions exist.
+    PathMappingNotFound { pattern: String },
+
+    /// A source file of a referenced project is imported, but its declaration
+    /// output does not exist.
+    OutputNotBuilt { output: String, source: String },
+}
+
+impl fmt::Display for ResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolutionError::ExtensionRequired {
+                suggestion: Some(suggestion),
+            } => {
+                write!(
+                    f,
+                    "relative imports in ES modules require an extension; did you mean `{}`?",
+                    suggestion
+                )
+            }
+            ResolutionError::ExtensionRequired { suggestion: None } => {
+                write!(f, "relative imports in ES modules require an extension")
+            }
+            ResolutionError::PathMappingNotFound { pattern } => {
+                write!(f, "`paths` pattern `{}` matched, but no file was found", pattern)
+            }
+            ResolutionError::OutputNotBuilt { output, source } => {
+                write!(f, "output file `{}` has not been built from source file `{}`", output, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolutionError {}
+
+/// Returns the name of the `@types` package for `name`.
+///
+/// `@scope/pkg` is provided by `@types/scope__pkg`.
+pub fn types_package_name(name: &str) -> String {
+    match name.strip_prefix('@') {
+        Some(scoped) => scoped.replacen('/', "__", 1),
+        None => name.to_string(),
+    }
+}
+
+/// Splits `@scope/pkg/sub` into `@scope/pkg` and `./sub`.
+pub(crate) fn split_package_name(target: &str) -> (&str, String) {
+    let name_len = if target.starts_with('@') { 2 } else { 1 };
+    let end = target
+        .match_indices('/')
+        .nth(name_len - 1)
+        .map(|(idx, _)| idx)
+        .unwrap_or(target.len());
+
+    let (name, rest) = target.split_at(end);
+    (name, format!(".{}", rest))
+}
+
+/// Finds the pattern of `paths` matching `target`, returning the pattern, its
+/// substitutions and the part matched by `*`.
+///
+/// Like `tsc`, an exact match wins, and then the pattern with the longest
+/// prefix.
+pub(crate) fn find_path_pattern<'a, 'b>(paths: &'a [(String, Vec<String>)], target: &'b str) -> Option<(&'a str, &'a [String], &'b str)> {
+    if let Some((pattern, substitutions)) = paths.iter().find(|(pattern, _)| pattern == target) {
+        return Some((pattern, substitutions, ""));
+    }
+
+    paths
+        .iter()
+        .filter_map(|(pattern, substitutions)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            let matched = target.strip_prefix(prefix)?.strip_suffix(suffix)?;
+
+            Some((prefix.len(), (&**pattern, &**substitutions, matched)))
+        })
+        .max_by_key(|(prefix_len, _)| *prefix_len)
+        .map(|(_, found)| found)
+}
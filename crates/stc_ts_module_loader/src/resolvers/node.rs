@@ -1,24 +1,16 @@
-use std::{
-    fs::File,
-    io::BufReader,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Error};
 use path_clean::PathClean;
-use serde::Deserialize;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
-static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
+use super::{find_path_pattern, split_package_name, types_package_name};
+use crate::{package_json::PackageJson, version::TsVersion};
 
-#[derive(Deserialize)]
-struct PackageJson {
-    #[serde(default)]
-    types: Option<String>,
-}
+static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct NodeResolver;
 
 impl NodeResolver {
@@ -70,11 +62,14 @@ impl NodeResolver {
         // TODO: how to not always initialize this here?
         let root = PathBuf::from("/");
         let pkg_dir = pkg_path.parent().unwrap_or(&root);
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson = serde_json::from_reader(reader).context("failed to deserialize package.json")?;
+        let pkg = PackageJson::read(pkg_path)?;
+
+        let entry = pkg.types().unwrap_or("index.d.ts");
+        if let Some(resolved) = self.resolve_types_versions(TsVersion::CURRENT, pkg_dir, &pkg, entry.trim_start_matches("./")) {
+            return Ok(resolved);
+        }
 
-        if let Some(target) = &pkg.types {
+        if let Some(target) = pkg.types() {
             let path = pkg_dir.join(target);
             return self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path));
         }
@@ -101,10 +96,40 @@ impl NodeResolver {
         self.resolve_as_file(pkg_dir).or_else(|_| self.resolve_as_directory(pkg_dir))
     }
 
+    /// Resolves `subpath` of the package at `pkg_dir` using `typesVersions`.
+    ///
+    /// `subpath` is relative to `pkg_dir`, like `index.d.ts` or `utils`.
+    pub(crate) fn resolve_types_versions(&self, version: TsVersion, pkg_dir: &Path, pkg: &PackageJson, subpath: &str) -> Option<PathBuf> {
+        let paths = pkg.types_versions_paths(version)?;
+        let (_, substitutions, matched) = find_path_pattern(&paths, subpath)?;
+
+        substitutions.iter().find_map(|substitution| {
+            let path = pkg_dir.join(substitution.replacen('*', matched, 1));
+
+            self.try_package(&path).ok()
+        })
+    }
+
+    /// Resolves a subpath like `./utils` of a package using `typesVersions`.
+    fn try_package_subpath(&self, pkg_dir: &Path, subpath: &str) -> Option<PathBuf> {
+        let subpath = subpath.strip_prefix("./")?;
+        let pkg = PackageJson::read(&pkg_dir.join("package.json")).ok()?;
+
+        self.resolve_types_versions(TsVersion::CURRENT, pkg_dir, &pkg, subpath)
+    }
+
     /// Resolve by walking up node_modules folders.
     fn resolve_node_modules(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
         let node_modules = base_dir.join("node_modules");
         if node_modules.is_dir() {
+            let (name, subpath) = split_package_name(target);
+            if let Some(resolved) = self.try_package_subpath(&node_modules.join(name), &subpath) {
+                return Ok(resolved);
+            }
+            if let Some(resolved) = self.try_package_subpath(&node_modules.join("@types").join(types_package_name(name)), &subpath) {
+                return Ok(resolved);
+            }
+
             let path = node_modules.join(target);
             let result = self.try_package(&path);
             if result.is_ok() {
@@ -112,7 +137,7 @@ impl NodeResolver {
             }
 
             {
-                let types = node_modules.join("@types").join(target);
+                let types = node_modules.join("@types").join(types_package_target(target));
 
                 if types.is_dir() {
                     let result = self.try_package(&types);
@@ -170,3 +195,11 @@ impl Resolve for NodeResolver {
             .and_then(|p| self.wrap(p))
     }
 }
+
+/// Maps `@scope/pkg/sub` to `scope__pkg/sub`, which is the path in
+/// `node_modules/@types`.
+fn types_package_target(target: &str) -> String {
+    let (name, subpath) = split_package_name(target);
+
+    format!("{}{}", types_package_name(name), &subpath[1..])
+}
@@ -0,0 +1,348 @@
+//! `moduleResolution: node16` and `moduleResolution: nodenext`.
+//!
+//! - https://www.typescriptlang.org/docs/handbook/modules/reference.html#node16-nodenext
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Error};
+use path_clean::PathClean;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::{node::NodeResolver, split_package_name, types_package_name, ResolutionError};
+use crate::{
+    package_json::{ExportEntry, PackageJson},
+    version::TsVersion,
+};
+
+/// Extensions of files which provide types for an import with the extension
+/// of a JavaScript file.
+static EXTENSION_MAP: &[(&str, &[&str])] = &[
+    ("js", &["ts", "tsx", "d.ts"]),
+    ("jsx", &["tsx", "d.ts"]),
+    ("mjs", &["mts", "d.mts"]),
+    ("cjs", &["cts", "d.cts"]),
+];
+
+/// The module format of the importing file, which decides how its imports are
+/// resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionMode {
+    /// `import` of an ES module.
+    Esm,
+    /// `require` of a CommonJS module.
+    CommonJs,
+}
+
+/// A resolver which follows the rules of node for ES modules and CommonJS
+/// modules.
+///
+/// The format of a file is decided by its extension (`.mts`, `.cts`) or by the
+/// `"type"` field of the nearest `package.json`.
+#[derive(Debug, Default, Clone)]
+pub struct Node16Resolver {
+    custom_conditions: Vec<String>,
+    /// Used to select `typesVersions` and `types@<range>` conditions.
+    ts_version: TsVersion,
+}
+
+impl Node16Resolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `customConditions`, which are enabled in addition to the conditions of
+    /// [Node16Resolver::conditions].
+    pub fn with_custom_conditions(mut self, conditions: Vec<String>) -> Self {
+        self.custom_conditions = conditions;
+        self
+    }
+
+    /// Sets the version of TypeScript used to select `typesVersions` and
+    /// `types@<range>` conditions. Defaults to [TsVersion::CURRENT].
+    pub fn with_ts_version(mut self, version: TsVersion) -> Self {
+        self.ts_version = version;
+        self
+    }
+
+    /// Returns the resolution mode of imports in `file`.
+    pub fn mode_of(&self, file: &Path) -> ResolutionMode {
+        let name = file.to_string_lossy();
+        if name.ends_with(".mts") || name.ends_with(".mjs") {
+            return ResolutionMode::Esm;
+        }
+        if name.ends_with(".cts") || name.ends_with(".cjs") {
+            return ResolutionMode::CommonJs;
+        }
+
+        match file.parent().and_then(find_package_json) {
+            Some((_, pkg)) if pkg.is_esm() => ResolutionMode::Esm,
+            _ => ResolutionMode::CommonJs,
+        }
+    }
+
+    /// Conditions of `exports` and `imports` which are enabled in `mode`,
+    /// including custom conditions.
+    pub fn conditions(&self, mode: ResolutionMode) -> Vec<&str> {
+        let kind = match mode {
+            ResolutionMode::Esm => "import",
+            ResolutionMode::CommonJs => "require",
+        };
+
+        let mut conditions = vec!["types", "node", kind, "default"];
+        conditions.extend(self.custom_conditions.iter().map(|c| &**c));
+        conditions
+    }
+
+    /// Returns `true` if `condition` of `exports` or `imports` is enabled in
+    /// `mode`.
+    pub fn is_condition_enabled(&self, mode: ResolutionMode, condition: &str) -> bool {
+        self.conditions(mode).contains(&condition) || self.is_versioned_types_condition(condition)
+    }
+
+    /// `types@>=5.0` is enabled if the version of TypeScript is in the range.
+    fn is_versioned_types_condition(&self, condition: &str) -> bool {
+        match condition.strip_prefix("types@") {
+            Some(range) => self.ts_version.satisfies(range),
+            None => false,
+        }
+    }
+
+    /// Resolves a path which has an extension. `.js` is mapped to `.ts`,
+    /// `.mjs` to `.mts` and so on.
+    fn resolve_with_extension(&self, path: &Path) -> Option<PathBuf> {
+        let name = path.file_name()?.to_str()?;
+
+        for (js_ext, ts_exts) in EXTENSION_MAP {
+            if let Some(stem) = name.strip_suffix(js_ext).and_then(|s| s.strip_suffix('.')) {
+                for ts_ext in *ts_exts {
+                    let ts_path = path.with_file_name(format!("{}.{}", stem, ts_ext));
+                    if ts_path.is_file() {
+                        return Some(ts_path);
+                    }
+                }
+            }
+        }
+
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+
+        None
+    }
+
+    /// Resolves a relative or absolute import.
+    fn resolve_path(&self, mode: ResolutionMode, path: &Path, target: &str) -> Result<PathBuf, Error> {
+        if let Some(resolved) = self.resolve_with_extension(path) {
+            return Ok(resolved);
+        }
+
+        if mode == ResolutionMode::CommonJs {
+            return NodeResolver
+                .resolve_as_file(path)
+                .or_else(|_| NodeResolver.resolve_as_directory(path));
+        }
+
+        // ES modules require full paths, but we suggest one if the file can be found.
+        let suggestion = if let Ok(resolved) = NodeResolver.resolve_as_file(path) {
+            Some(format!("{}{}", target, js_extension(&resolved)))
+        } else if let Ok(resolved) = NodeResolver.resolve_as_file(&path.join("index")) {
+            Some(format!("{}/index{}", target.trim_end_matches('/'), js_extension(&resolved)))
+        } else if NodeResolver.resolve_as_directory(path).is_ok() {
+            None
+        } else {
+            bail!("file not found: {}", path.display())
+        };
+
+        Err(ResolutionError::ExtensionRequired { suggestion }.into())
+    }
+
+    /// Resolves a bare specifier by walking up `node_modules` folders.
+    fn resolve_node_modules(&self, mode: ResolutionMode, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        let (name, subpath) = split_package_name(target);
+
+        let mut cur = Some(base_dir);
+        while let Some(dir) = cur {
+            let node_modules = dir.join("node_modules");
+
+            for pkg_dir in [node_modules.join(name), node_modules.join("@types").join(types_package_name(name))] {
+                if pkg_dir.is_dir() {
+                    if let Ok(resolved) = self.resolve_package(mode, &pkg_dir, &subpath) {
+                        return Ok(resolved);
+                    }
+                }
+            }
+
+            cur = dir.parent();
+        }
+
+        bail!("not found")
+    }
+
+    /// Resolves `subpath` of the package at `pkg_dir`. `subpath` is `.` for the
+    /// main entry.
+    fn resolve_package(&self, mode: ResolutionMode, pkg_dir: &Path, subpath: &str) -> Result<PathBuf, Error> {
+        let pkg_path = pkg_dir.join("package.json");
+        if pkg_path.is_file() {
+            let pkg = PackageJson::read(&pkg_path)?;
+
+            // `exports` hides everything not listed in it.
+            if pkg.exports.is_some() {
+                return self.resolve_exports(mode, pkg_dir, &pkg, subpath);
+            }
+
+            let entry = match subpath.strip_prefix("./") {
+                Some(subpath) => subpath,
+                None => pkg.types().unwrap_or("index.d.ts").trim_start_matches("./"),
+            };
+            if let Some(resolved) = NodeResolver.resolve_types_versions(self.ts_version, pkg_dir, &pkg, entry) {
+                return Ok(resolved);
+            }
+        }
+
+        let path = pkg_dir.join(subpath).clean();
+
+        match self.resolve_with_extension(&path) {
+            Some(resolved) => Ok(resolved),
+            None => NodeResolver
+                .resolve_as_file(&path)
+                .or_else(|_| NodeResolver.resolve_as_directory(&path)),
+        }
+    }
+
+    /// Resolves `subpath` using the `exports` field of `pkg`.
+    fn resolve_exports(&self, mode: ResolutionMode, pkg_dir: &Path, pkg: &PackageJson, subpath: &str) -> Result<PathBuf, Error> {
+        let target = self.select_target(mode, pkg.export_entries(), subpath)?;
+        let path = pkg_dir.join(target).clean();
+
+        self.resolve_with_extension(&path)
+            .with_context(|| format!("failed to resolve `{}`, which is exported by {}", path.display(), pkg_dir.display()))
+    }
+
+    /// Resolves `#private` specifiers using the `imports` field of the nearest
+    /// `package.json`.
+    fn resolve_imports(&self, mode: ResolutionMode, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        let (pkg_dir, pkg) = match find_package_json(base_dir) {
+            Some(v) => v,
+            None => bail!("package.json not found"),
+        };
+
+        let mapped = self.select_target(mode, pkg.import_entries(), target)?;
+
+        // `imports` can map to other packages.
+        if !mapped.starts_with("./") {
+            return self.resolve_node_modules(mode, &pkg_dir, &mapped);
+        }
+
+        let path = pkg_dir.join(mapped).clean();
+
+        self.resolve_with_extension(&path)
+            .with_context(|| format!("failed to resolve `{}`, which is imported as `{}`", path.display(), target))
+    }
+
+    /// Selects the target of `subpath` from entries of `exports` or `imports`.
+    fn select_target(&self, mode: ResolutionMode, entries: Vec<ExportEntry>, subpath: &str) -> Result<String, Error> {
+        match select_entry(&entries, subpath, |c| self.is_condition_enabled(mode, c)) {
+            Some((entry, matched)) => match (&entry.target, matched) {
+                (Some(target), Some(matched)) => Ok(target.replace('*', matched)),
+                (Some(target), None) => Ok(target.clone()),
+                (None, _) => bail!("`{}` is excluded by package.json", subpath),
+            },
+            None => bail!("`{}` is not listed in package.json", subpath),
+        }
+    }
+}
+
+impl Resolve for Node16Resolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let base = match base {
+            FileName::Real(base) => &**base,
+            _ => {
+                unreachable!("base = {:?}; target = {:?}", base, target)
+            }
+        };
+        let mode = self.mode_of(base);
+
+        let cwd = &Path::new(".");
+        let base_dir = base.parent().unwrap_or(cwd);
+
+        let resolved = if target.starts_with('/') || target.starts_with("./") || target.starts_with("../") {
+            let path = base_dir.join(target).clean();
+
+            self.resolve_path(mode, &path, target)
+                .with_context(|| format!("failed to resolve `{}` from `{}`", target, base.display()))?
+        } else if target.starts_with('#') {
+            self.resolve_imports(mode, base_dir, target)
+                .with_context(|| format!("failed to resolve `{}` using the `imports` field from `{}`", target, base.display()))?
+        } else {
+            self.resolve_node_modules(mode, base_dir, target)
+                .with_context(|| format!("failed to resolve `{}` as a node module from `{}`", target, base.display()))?
+        };
+
+        Ok(FileName::Real(resolved.clean()))
+    }
+}
+
+/// Finds the nearest `package.json` from `dir`, returning the directory which
+/// contains it.
+fn find_package_json(dir: &Path) -> Option<(PathBuf, PackageJson)> {
+    let mut cur = Some(dir);
+    while let Some(dir) = cur {
+        let path = dir.join("package.json");
+        if path.is_file() {
+            return PackageJson::read(&path).ok().map(|pkg| (dir.to_path_buf(), pkg));
+        }
+
+        cur = dir.parent();
+    }
+
+    None
+}
+
+/// Returns the entry of `subpath` node selects from entries of `exports` or
+/// `imports`, which is the first entry whose conditions are all enabled.
+///
+/// If the entry is a pattern like `./utils/*`, the part of `subpath` matched
+/// by `*` is returned too. Exact subpaths take precedence over patterns.
+pub fn select_entry<'a, 'b>(
+    entries: &'a [ExportEntry],
+    subpath: &'b str,
+    is_enabled: impl Fn(&str) -> bool,
+) -> Option<(&'a ExportEntry, Option<&'b str>)> {
+    let entries = entries
+        .iter()
+        .filter(|entry| entry.conditions.iter().all(|c| is_enabled(c)))
+        .collect::<Vec<_>>();
+
+    entries
+        .iter()
+        .find(|entry| entry.subpath == subpath)
+        .map(|&entry| (entry, None))
+        .or_else(|| {
+            entries
+                .iter()
+                .find_map(|&entry| match_pattern(&entry.subpath, subpath).map(|matched| (entry, Some(matched))))
+        })
+}
+
+/// Matches `subpath` against a pattern like `./utils/*`, returning the part
+/// matched by `*`.
+fn match_pattern<'a>(pattern: &str, subpath: &'a str) -> Option<&'a str> {
+    let (prefix, suffix) = pattern.split_once('*')?;
+
+    subpath
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)
+        .filter(|matched| !matched.is_empty())
+}
+
+/// The extension of the JavaScript file emitted for `path`.
+fn js_extension(path: &Path) -> &'static str {
+    let name = path.to_string_lossy();
+    if name.ends_with(".mts") {
+        ".mjs"
+    } else if name.ends_with(".cts") {
+        ".cjs"
+    } else {
+        ".js"
+    }
+}
@@ -0,0 +1,86 @@
+//! `baseUrl` and `paths` of `compilerOptions`.
+//!
+//! - https://www.typescriptlang.org/tsconfig#paths
+use std::path::PathBuf;
+
+use anyhow::Error;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::{find_path_pattern, ResolutionError};
+
+/// Maps non-relative specifiers using `paths` and `baseUrl`, and delegates the
+/// actual resolution to another resolver.
+#[derive(Debug, Clone)]
+pub struct PathsResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+    /// `baseUrl`
+    base_url: Option<PathBuf>,
+    /// The directory substitutions of `paths` are relative to.
+    paths_dir: PathBuf,
+    /// Patterns and their substitutions, in the order of the config file.
+    paths: Vec<(String, Vec<String>)>,
+}
+
+impl<R> PathsResolver<R>
+where
+    R: Resolve,
+{
+    /// Substitutions in `paths` are relative to `base_url`, or to `config_dir`
+    /// if `baseUrl` is not set.
+    pub fn new(inner: R, config_dir: PathBuf, base_url: Option<PathBuf>, paths: Vec<(String, Vec<String>)>) -> Self {
+        Self {
+            inner,
+            paths_dir: base_url.clone().unwrap_or(config_dir),
+            base_url,
+            paths,
+        }
+    }
+
+    /// Tries `baseUrl` and then the inner resolver.
+    fn resolve_without_paths(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        if let Some(base_url) = &self.base_url {
+            if let Ok(resolved) = self.inner.resolve(base, &base_url.join(target).to_string_lossy()) {
+                return Ok(resolved);
+            }
+        }
+
+        self.inner.resolve(base, target)
+    }
+}
+
+impl<R> Resolve for PathsResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        if target.starts_with("./") || target.starts_with("../") || target.starts_with('/') {
+            return self.inner.resolve(base, target);
+        }
+
+        let mapped = find_path_pattern(&self.paths, target).map(|(pattern, substitutions, matched)| {
+            // Substitutions are tried in order.
+            let resolved = substitutions.iter().find_map(|substitution| {
+                let path = self.paths_dir.join(substitution.replacen('*', matched, 1));
+
+                self.inner.resolve(base, &path.to_string_lossy()).ok()
+            });
+
+            (pattern, resolved)
+        });
+
+        match mapped {
+            Some((_, Some(resolved))) => Ok(resolved),
+            // Fall back to the default resolution, but report the mapping if it fails too.
+            Some((pattern, None)) => self.resolve_without_paths(base, target).map_err(|_| {
+                Error::from(ResolutionError::PathMappingNotFound {
+                    pattern: pattern.to_string(),
+                })
+            }),
+            None => self.resolve_without_paths(base, target),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Imports into referenced projects.
+//!
+//! - https://www.typescriptlang.org/docs/handbook/project-references.html
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Error;
+use fxhash::FxHashMap;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::ResolutionError;
+
+/// Redirects imports of source files of referenced projects to their
+/// declaration outputs, like `tsc`, so referenced projects are not checked
+/// again as a part of the referencing project.
+#[derive(Debug, Clone)]
+pub struct ProjectReferencesResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+    /// Source files of referenced projects to their declaration outputs.
+    outputs: Arc<FxHashMap<PathBuf, PathBuf>>,
+}
+
+impl<R> ProjectReferencesResolver<R>
+where
+    R: Resolve,
+{
+    pub fn new(inner: R, outputs: FxHashMap<PathBuf, PathBuf>) -> Self {
+        Self {
+            inner,
+            outputs: Arc::new(outputs),
+        }
+    }
+}
+
+impl<R> Resolve for ProjectReferencesResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let resolved = self.inner.resolve(base, target)?;
+
+        if let FileName::Real(source) = &resolved {
+            if let Some(output) = self.outputs.get(source) {
+                if !output.is_file() {
+                    return Err(ResolutionError::OutputNotBuilt {
+                        output: output.display().to_string(),
+                        source: source.display().to_string(),
+                    }
+                    .into());
+                }
+
+                return Ok(FileName::Real(output.clone()));
+            }
+        }
+
+        Ok(resolved)
+    }
+}
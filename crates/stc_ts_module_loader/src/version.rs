@@ -0,0 +1,92 @@
+//! Versions of TypeScript, used to select `typesVersions` of `package.json`
+//! and `types@<range>` conditions of `exports`.
+
+use std::{cmp::Ordering, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TsVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl TsVersion {
+    /// The version of TypeScript stc is compatible with.
+    pub const CURRENT: TsVersion = TsVersion::new(5, 0, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parses `5`, `5.0` or `5.0.2`, returning the version and the number of
+    /// components specified.
+    fn parse_partial(s: &str) -> Option<(Self, usize)> {
+        let mut parts = [0; 3];
+        let mut len = 0;
+
+        for part in s.split('.') {
+            if len == 3 {
+                return None;
+            }
+            // `x` and `*` match anything.
+            if part == "x" || part == "*" {
+                break;
+            }
+            parts[len] = part.parse().ok()?;
+            len += 1;
+        }
+
+        Some((Self::new(parts[0], parts[1], parts[2]), len))
+    }
+
+    /// Compares only the first `len` components.
+    fn cmp_prefix(&self, other: &Self, len: usize) -> Ordering {
+        let lhs = [self.major, self.minor, self.patch];
+        let rhs = [other.major, other.minor, other.patch];
+
+        lhs[..len].cmp(&rhs[..len])
+    }
+
+    /// Returns `true` if `self` satisfies a semver range like `>=4.2`,
+    /// `>=3.1 <4.0`, `~4.1`, `4.x` or `<3.8 || >=4.1`.
+    pub fn satisfies(&self, range: &str) -> bool {
+        range
+            .split("||")
+            .any(|range| range.split_whitespace().all(|comparator| self.satisfies_comparator(comparator)))
+    }
+
+    fn satisfies_comparator(&self, comparator: &str) -> bool {
+        let (op, version) = [">=", "<=", ">", "<", "=", "~", "^"]
+            .iter()
+            .find_map(|op| comparator.strip_prefix(op).map(|version| (*op, version)))
+            .unwrap_or(("=", comparator));
+
+        let (version, len) = match Self::parse_partial(version.trim_start_matches('v')) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        let ordering = self.cmp_prefix(&version, len);
+        match op {
+            ">=" => ordering != Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            "<" => ordering == Ordering::Less,
+            "~" => self.cmp_prefix(&version, len.min(2)) == Ordering::Equal && ordering != Ordering::Less,
+            "^" => self.major == version.major && ordering != Ordering::Less,
+            _ => ordering == Ordering::Equal,
+        }
+    }
+}
+
+impl Default for TsVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl fmt::Display for TsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
@@ -0,0 +1,308 @@
+//! Node.js bindings, so build tools can check a project without spawning
+//! `stc`.
+//!
+//! A [Program] keeps the state of incremental checking in memory, so only
+//! files affected by changes are checked again on the next call of
+//! [Program::check].
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Error};
+use napi::{bindgen_prelude::AsyncTask, Env as NapiEnv, Task};
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use stc_ts_config::{ConfigError, ProgramBuilder, TsConfig};
+use stc_ts_type_checker::{
+    diagnostics::dedup_errors,
+    incremental::{hash_options, BuildInfo},
+    project::ProjectOptions,
+};
+use swc_common::{errors::Handler, FileName, Globals, SourceMap, Span, Spanned, GLOBALS};
+
+/// Shared by all programs, because builtin libraries are cached across
+/// programs.
+static STC_GLOBALS: Lazy<Globals> = Lazy::new(Globals::new);
+
+#[napi(object)]
+pub struct ProgramOptions {
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc, like `node_modules/typescript/lib`.
+    pub lib_dir: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Absolute path of the file. [None] for errors which are not in a file,
+    /// like packages of `types` which are not found.
+    pub file: Option<String>,
+    pub code: u32,
+    pub message: String,
+    pub start: Option<Position>,
+    pub end: Option<Position>,
+    /// The number of errors suppressed because they are caused by the same
+    /// problem.
+    pub suppressed: u32,
+}
+
+/// Zero-based. Columns are counted in characters.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A project described by a `tsconfig.json`.
+///
+/// The configuration and root files are loaded again on each check, so
+/// changes to them are picked up.
+#[napi]
+pub struct Program {
+    state: Arc<Mutex<State>>,
+}
+
+#[napi]
+impl Program {
+    /// `project` is the path to `tsconfig.json`, or a directory containing
+    /// it.
+    #[napi(constructor)]
+    pub fn new(project: String, options: Option<ProgramOptions>) -> napi::Result<Self> {
+        let project = PathBuf::from(project);
+        let path = if project.is_dir() { project.join("tsconfig.json") } else { project };
+        let config_path = path
+            .canonicalize()
+            .with_context(|| format!("failed to find {}", path.display()))
+            .map_err(to_napi_error)?;
+
+        // Continue from the state stored by `stc --incremental`, if any.
+        let build_info = TsConfig::load(&config_path)
+            .ok()
+            .filter(|config| config.incremental)
+            .and_then(|config| BuildInfo::load(&config.build_info_path()));
+
+        Ok(Self {
+            state: Arc::new(Mutex::new(State {
+                config_path,
+                lib_dir: options.and_then(|options| options.lib_dir).map(PathBuf::from),
+                build_info,
+            })),
+        })
+    }
+
+    /// Absolute paths of the root files.
+    #[napi]
+    pub fn root_files(&self) -> napi::Result<Vec<String>> {
+        let config = TsConfig::load(&self.state.lock().config_path).map_err(to_napi_error)?;
+        let program = ProgramBuilder::new(&config).build();
+
+        Ok(program.root_files.iter().map(|file| file.display().to_string()).collect())
+    }
+
+    /// Checks the program, blocking the current thread.
+    #[napi]
+    pub fn check(&self) -> napi::Result<Vec<Diagnostic>> {
+        self.state.lock().check().map_err(to_napi_error)
+    }
+
+    /// Checks the program on the thread pool of Node.js.
+    #[napi]
+    pub fn check_async(&self) -> AsyncTask<CheckTask> {
+        AsyncTask::new(CheckTask { state: self.state.clone() })
+    }
+}
+
+pub struct CheckTask {
+    state: Arc<Mutex<State>>,
+}
+
+#[napi]
+impl Task for CheckTask {
+    type JsValue = Vec<Diagnostic>;
+    type Output = Vec<Diagnostic>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        self.state.lock().check().map_err(to_napi_error)
+    }
+
+    fn resolve(&mut self, _: NapiEnv, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+struct State {
+    config_path: PathBuf,
+    lib_dir: Option<PathBuf>,
+    /// The result of the previous check.
+    build_info: Option<BuildInfo>,
+}
+
+impl State {
+    fn check(&mut self) -> Result<Vec<Diagnostic>, Error> {
+        GLOBALS.set(&STC_GLOBALS, || self.check_inner())
+    }
+
+    fn check_inner(&mut self) -> Result<Vec<Diagnostic>, Error> {
+        let config = TsConfig::load(&self.config_path)?;
+        let program = ProgramBuilder::new(&config).build();
+
+        let mut diagnostics = config
+            .errors
+            .iter()
+            .chain(&program.errors)
+            .map(config_diagnostic)
+            .collect::<Vec<_>>();
+
+        // A new source map is used for each check, so changed files are loaded again.
+        let cm = Arc::new(SourceMap::default());
+        let handler = Arc::new(Handler::with_emitter_writer(Box::new(io::sink()), None));
+
+        let libs = config.libs_or_default();
+        let options = hash_options(&config.rule, config.target, &config.module, &libs, self.lib_dir.as_deref());
+
+        let (mut checker, errors) =
+            ProjectOptions::from_config(&config, &libs, self.lib_dir.as_deref()).checker(cm.clone(), handler, |resolver| resolver)?;
+        for diagnostic in dedup_errors(&cm, errors) {
+            let span = diagnostic.error.span();
+            let file = if span.is_dummy() {
+                None
+            } else {
+                match &cm.lookup_source_file(span.lo).name {
+                    FileName::Real(path) => Some(path.clone()),
+                    _ => None,
+                }
+            };
+
+            diagnostics.push(Diagnostic {
+                code: diagnostic.error.code() as u32,
+                message: format!("{:#?}", diagnostic.error),
+                suppressed: diagnostic.suppressed as u32,
+                ..located(&cm, file.as_deref(), Some(span).filter(|span| !span.is_dummy()))
+            });
+        }
+
+        let result = checker.check_incremental(self.build_info.as_ref(), &options, &program.root_files);
+
+        for (file, state) in &result.build_info.files {
+            for stored in &state.diagnostics {
+                diagnostics.push(Diagnostic {
+                    code: stored.code as u32,
+                    message: stored.message.clone(),
+                    suppressed: stored.suppressed as u32,
+                    ..located(&cm, Some(file), stored.span(&cm, file))
+                });
+            }
+        }
+
+        if config.incremental {
+            result.build_info.save(&config.build_info_path())?;
+        }
+        self.build_info = Some(result.build_info);
+
+        Ok(diagnostics)
+    }
+}
+
+fn config_diagnostic(err: &ConfigError) -> Diagnostic {
+    Diagnostic {
+        file: Some(err.file.display().to_string()),
+        code: err.code() as u32,
+        message: err.kind.to_string(),
+        start: None,
+        end: None,
+        suppressed: 0,
+    }
+}
+
+/// Returns a [Diagnostic] with only the location filled in.
+fn located(cm: &SourceMap, file: Option<&Path>, span: Option<Span>) -> Diagnostic {
+    let position = |pos| {
+        let loc = cm.lookup_char_pos(pos);
+        Position {
+            line: loc.line as u32 - 1,
+            column: loc.col.0 as u32,
+        }
+    };
+
+    Diagnostic {
+        file: file.map(|file| file.display().to_string()),
+        code: 0,
+        message: String::new(),
+        start: span.map(|span| position(span.lo)),
+        end: span.map(|span| position(span.hi)),
+        suppressed: 0,
+    }
+}
+
+fn to_napi_error(err: Error) -> napi::Error {
+    napi::Error::from_reason(format!("{:?}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::Program;
+
+    /// Writes `files` into a temporary directory and returns the directory.
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join("stc-napi").join(name);
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for (file, content) in files {
+            fs::write(root.join(file), content).unwrap();
+        }
+
+        root.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn errors_are_located() {
+        let root = project(
+            "located",
+            &[
+                ("tsconfig.json", r#"{ "files": ["a.ts", "b.ts"] }"#),
+                ("a.ts", "export const a = 1;\n"),
+                ("b.ts", "import { a } from './a';\n\nconst b: string = a;\n"),
+            ],
+        );
+
+        let program = Program::new(root.display().to_string(), None).unwrap();
+        assert_eq!(program.root_files().unwrap().len(), 2);
+
+        let diagnostics = program.check().unwrap();
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].code, 2322);
+        assert_eq!(diagnostics[0].file.as_deref(), Some(&*root.join("b.ts").display().to_string()));
+        assert_eq!(diagnostics[0].start.as_ref().map(|pos| pos.line), Some(2));
+    }
+
+    #[test]
+    fn changes_are_picked_up() {
+        let root = project(
+            "changes",
+            &[
+                ("tsconfig.json", r#"{ "files": ["a.ts", "b.ts"] }"#),
+                ("a.ts", "export const a = 1;\n"),
+                ("b.ts", "import { a } from './a';\n\nconst b: number = a;\n"),
+            ],
+        );
+
+        let program = Program::new(root.display().to_string(), None).unwrap();
+        assert_eq!(program.check().unwrap().len(), 0);
+
+        // `b.ts` is affected by the change of the signature of `a.ts`.
+        fs::write(root.join("a.ts"), "export const a = '';\n").unwrap();
+        let diagnostics = program.check().unwrap();
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].file.as_deref(), Some(&*root.join("b.ts").display().to_string()));
+
+        fs::write(root.join("a.ts"), "export const a = 2;\n").unwrap();
+        assert_eq!(program.check().unwrap().len(), 0);
+    }
+}
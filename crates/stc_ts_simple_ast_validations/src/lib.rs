@@ -9,4 +9,6 @@
 
 pub mod ambient_fn;
 pub mod constructor;
+pub mod reachability;
+pub mod unused;
 pub mod yield_check;
@@ -0,0 +1,165 @@
+//! Syntactic reachability checks used by `noImplicitReturns` and
+//! `noFallthroughCasesInSwitch`.
+
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RBool, RBreakStmt, RClass, RDoWhileStmt, RExpr, RForInStmt, RForOfStmt, RForStmt, RFunction, RLit, RReturnStmt, RStmt,
+    RSwitchStmt, RWhileStmt,
+};
+use swc_common::Span;
+
+/// Returns `true` if the end of `stmts` is reachable.
+pub fn can_complete_normally(stmts: &[RStmt]) -> bool {
+    !stmts.iter().any(always_jumps)
+}
+
+/// Returns `true` if `stmt` always returns, throws, breaks or continues.
+fn always_jumps(stmt: &RStmt) -> bool {
+    match stmt {
+        RStmt::Return(..) | RStmt::Throw(..) | RStmt::Break(..) | RStmt::Continue(..) => true,
+
+        RStmt::Block(b) => !can_complete_normally(&b.stmts),
+
+        RStmt::If(s) => match &s.alt {
+            Some(alt) => always_jumps(&s.cons) && always_jumps(alt),
+            None => false,
+        },
+
+        RStmt::Try(s) => {
+            let finalizer_jumps = s.finalizer.as_ref().map_or(false, |f| !can_complete_normally(&f.stmts));
+            let block_jumps = !can_complete_normally(&s.block.stmts);
+            let handler_jumps = s.handler.as_ref().map_or(true, |h| !can_complete_normally(&h.body.stmts));
+
+            finalizer_jumps || (block_jumps && handler_jumps)
+        }
+
+        RStmt::While(s) => is_true(Some(&*s.test)) && !has_break(&*s.body),
+        RStmt::For(s) => is_true(s.test.as_deref()) && !has_break(&*s.body),
+        RStmt::DoWhile(s) => !has_break(&*s.body) && (is_true(Some(&*s.test)) || always_jumps(&s.body)),
+
+        RStmt::Switch(s) => {
+            let has_default = s.cases.iter().any(|case| case.test.is_none());
+
+            has_default && !has_break(&s.cases) && s.cases.last().map_or(false, |case| !can_complete_normally(&case.cons))
+        }
+
+        RStmt::Labeled(s) => always_jumps(&s.body) && !has_break(&*s.body),
+
+        _ => false,
+    }
+}
+
+/// `true` for a missing test of `for (;;)` and `true`.
+fn is_true(test: Option<&RExpr>) -> bool {
+    match test {
+        None => true,
+        Some(RExpr::Lit(RLit::Bool(RBool { value, .. }))) => *value,
+        Some(RExpr::Paren(p)) => is_true(Some(&*p.expr)),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `node` contains a `break` which exits the enclosing
+/// statement.
+fn has_break<N>(node: &N) -> bool
+where
+    N: VisitWith<BreakFinder>,
+{
+    let mut v = BreakFinder::default();
+    node.visit_with(&mut v);
+    v.found
+}
+
+#[derive(Default)]
+struct BreakFinder {
+    found: bool,
+    /// Depth of nested loops and switches.
+    depth: usize,
+}
+
+impl Visit<RBreakStmt> for BreakFinder {
+    fn visit(&mut self, s: &RBreakStmt) {
+        if s.label.is_some() || self.depth == 0 {
+            self.found = true;
+        }
+    }
+}
+
+macro_rules! nested_breakable {
+    ($T:ty) => {
+        impl Visit<$T> for BreakFinder {
+            fn visit(&mut self, s: &$T) {
+                self.depth += 1;
+                s.visit_children_with(self);
+                self.depth -= 1;
+            }
+        }
+    };
+}
+
+nested_breakable!(RWhileStmt);
+nested_breakable!(RDoWhileStmt);
+nested_breakable!(RForStmt);
+nested_breakable!(RForInStmt);
+nested_breakable!(RForOfStmt);
+nested_breakable!(RSwitchStmt);
+
+/// noop
+impl Visit<RFunction> for BreakFinder {
+    fn visit(&mut self, _: &RFunction) {}
+}
+
+/// noop
+impl Visit<RArrowExpr> for BreakFinder {
+    fn visit(&mut self, _: &RArrowExpr) {}
+}
+
+/// noop
+impl Visit<RClass> for BreakFinder {
+    fn visit(&mut self, _: &RClass) {}
+}
+
+/// Collects `return` statements of a function, excluding ones in nested
+/// functions.
+#[derive(Debug, Default)]
+pub struct ReturnFinder {
+    /// Spans of `return expr;`
+    pub with_value: Vec<Span>,
+    /// Spans of `return;`
+    pub without_value: Vec<Span>,
+}
+
+impl Visit<RReturnStmt> for ReturnFinder {
+    fn visit(&mut self, s: &RReturnStmt) {
+        if s.arg.is_some() {
+            self.with_value.push(s.span);
+        } else {
+            self.without_value.push(s.span);
+        }
+    }
+}
+
+/// noop
+impl Visit<RFunction> for ReturnFinder {
+    fn visit(&mut self, _: &RFunction) {}
+}
+
+/// noop
+impl Visit<RArrowExpr> for ReturnFinder {
+    fn visit(&mut self, _: &RArrowExpr) {}
+}
+
+/// noop
+impl Visit<RClass> for ReturnFinder {
+    fn visit(&mut self, _: &RClass) {}
+}
+
+/// Returns the spans of `case` clauses which can be reached by falling
+/// through a non-empty previous clause.
+pub fn fallthrough_cases(s: &RSwitchStmt) -> Vec<Span> {
+    s.cases
+        .windows(2)
+        .filter(|cases| !cases[0].cons.is_empty() && can_complete_normally(&cases[0].cons))
+        .map(|cases| cases[1].span)
+        .collect()
+}
@@ -0,0 +1,535 @@
+//! Syntactic detection of unused declarations, used by `noUnusedLocals` and
+//! `noUnusedParameters`.
+//!
+//! This relies on the [SyntaxContext] assigned by the resolver, so a
+//! declaration and its references share the same `(sym, ctxt)` pair
+//! regardless of the scope they are in.
+
+use std::mem::replace;
+
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RAssignExpr, RClassDecl, RConstructor, RDecl, RDefaultDecl, RExportDecl, RExportDefaultDecl, RExpr, RFnDecl, RForInStmt,
+    RForOfStmt, RFunction, RIdent, RImportDecl, RImportSpecifier, RModule, RModuleItem, RObjectPatProp, RParamOrTsParamProp, RPat,
+    RPatOrExpr, RSetterProp, RStmt, RTsEnumDecl, RTsInterfaceDecl, RTsModuleDecl, RTsNamespaceDecl, RTsTypeAliasDecl, RTsTypeParamDecl,
+    RVarDecl,
+};
+use stc_ts_utils::find_ids_in_pat;
+use stc_utils::AHashSet;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Span, SyntaxContext};
+use swc_ecma_ast::AssignOp;
+
+type Id = (JsWord, SyntaxContext);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnusedKind {
+    /// Variables, functions and imports. (TS6133)
+    Value,
+    /// Classes, interfaces, type aliases and enums. (TS6196)
+    Type,
+    /// Parameters and type parameters. (TS6133)
+    Param,
+    /// Every specifier of an import declaration with multiple specifiers.
+    /// (TS6192)
+    AllImports,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnusedDecl {
+    pub span: Span,
+    pub name: JsWord,
+    pub kind: UnusedKind,
+}
+
+/// Returns unused declarations of `m`, in the order of declaration.
+///
+/// Exported declarations and top-level declarations of scripts are never
+/// reported, as they can be used by other files.
+pub fn find_unused_decls(m: &RModule) -> Vec<UnusedDecl> {
+    let is_module = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
+
+    let mut v = UnusedFinder::default();
+
+    if !is_module {
+        for item in &m.body {
+            if let RModuleItem::Stmt(RStmt::Decl(decl)) = item {
+                v.mark_exported(decl);
+            }
+        }
+    }
+
+    m.visit_with(&mut v);
+
+    v.into_unused()
+}
+
+#[derive(Debug)]
+struct Decl {
+    id: Id,
+    span: Span,
+    kind: UnusedKind,
+}
+
+#[derive(Debug)]
+struct ImportGroup {
+    span: Span,
+    /// Includes specifiers which are not checked, like `_foo`.
+    len: usize,
+    decls: Vec<Decl>,
+}
+
+#[derive(Debug, Default)]
+struct UnusedFinder {
+    decls: Vec<Decl>,
+    imports: Vec<ImportGroup>,
+    declared: AHashSet<Id>,
+    exported: AHashSet<Id>,
+
+    /// Spans of identifiers which are not a read of a variable, like the name
+    /// of a declaration or the left hand side of `a = b`.
+    non_refs: AHashSet<Span>,
+    used: AHashSet<Id>,
+
+    in_declare: bool,
+    in_for_in_of_head: bool,
+}
+
+impl UnusedFinder {
+    fn into_unused(self) -> Vec<UnusedDecl> {
+        let is_unused = |d: &Decl| !self.used.contains(&d.id) && !self.exported.contains(&d.id);
+        let to_unused = |d: &Decl| UnusedDecl {
+            span: d.span,
+            name: d.id.0.clone(),
+            kind: d.kind,
+        };
+
+        let mut unused = self.decls.iter().filter(|d| is_unused(d)).map(to_unused).collect::<Vec<_>>();
+
+        for group in &self.imports {
+            if group.len > 1 && group.decls.len() == group.len && group.decls.iter().all(is_unused) {
+                unused.push(UnusedDecl {
+                    span: group.span,
+                    name: js_word!(""),
+                    kind: UnusedKind::AllImports,
+                });
+                continue;
+            }
+
+            unused.extend(group.decls.iter().filter(|d| is_unused(d)).map(to_unused));
+        }
+
+        unused.sort_by_key(|d| d.span.lo);
+        unused
+    }
+
+    fn mark_exported(&mut self, decl: &RDecl) {
+        match decl {
+            RDecl::Class(RClassDecl { ident, .. })
+            | RDecl::Fn(RFnDecl { ident, .. })
+            | RDecl::TsInterface(box RTsInterfaceDecl { id: ident, .. })
+            | RDecl::TsTypeAlias(box RTsTypeAliasDecl { id: ident, .. })
+            | RDecl::TsEnum(box RTsEnumDecl { id: ident, .. }) => {
+                self.exported.insert(id_of(ident));
+            }
+            RDecl::Var(var) => {
+                for decl in &var.decls {
+                    let ids: Vec<RIdent> = find_ids_in_pat(&decl.name);
+                    self.exported.extend(ids.iter().map(id_of));
+                }
+            }
+            RDecl::TsModule(..) => {}
+        }
+    }
+
+    /// Returns a [Decl] if `i` should be checked.
+    fn decl(&mut self, i: &RIdent, kind: UnusedKind, allow_underscore: bool) -> Option<Decl> {
+        self.non_refs.insert(i.span);
+
+        if self.in_declare || i.span.ctxt == SyntaxContext::empty() {
+            return None;
+        }
+
+        if (allow_underscore || kind == UnusedKind::Param) && i.sym.starts_with('_') {
+            return None;
+        }
+
+        let id = id_of(i);
+        if !self.declared.insert(id.clone()) {
+            return None;
+        }
+
+        Some(Decl { id, span: i.span, kind })
+    }
+
+    fn declare(&mut self, i: &RIdent, kind: UnusedKind, allow_underscore: bool) {
+        if let Some(decl) = self.decl(i, kind, allow_underscore) {
+            self.decls.push(decl);
+        }
+    }
+
+    fn declare_pat(&mut self, pat: &RPat, kind: UnusedKind, allow_underscore: bool) {
+        match pat {
+            RPat::Ident(i) => self.declare(&i.id, kind, allow_underscore),
+            RPat::Array(arr) => {
+                for elem in arr.elems.iter().flatten() {
+                    self.declare_pat(elem, kind, true);
+                }
+            }
+            RPat::Object(obj) => {
+                // In `{ a, ...rest }`, `a` is used to remove a property from `rest`.
+                let has_rest = obj.props.iter().any(|prop| matches!(prop, RObjectPatProp::Rest(..)));
+
+                for prop in &obj.props {
+                    match prop {
+                        RObjectPatProp::KeyValue(prop) => {
+                            if !has_rest {
+                                self.declare_pat(&prop.value, kind, true);
+                            }
+                        }
+                        RObjectPatProp::Assign(prop) => {
+                            if !has_rest {
+                                self.declare(&prop.key, kind, false);
+                            }
+                        }
+                        RObjectPatProp::Rest(rest) => self.declare_pat(&rest.arg, kind, false),
+                    }
+                }
+            }
+            RPat::Rest(rest) => self.declare_pat(&rest.arg, kind, allow_underscore),
+            RPat::Assign(assign) => self.declare_pat(&assign.left, kind, allow_underscore),
+            RPat::Invalid(..) | RPat::Expr(..) => {}
+        }
+    }
+
+    fn declare_type_params(&mut self, type_params: Option<&RTsTypeParamDecl>) {
+        for param in type_params.into_iter().flat_map(|decl| &decl.params) {
+            self.declare(&param.name, UnusedKind::Param, true);
+        }
+    }
+
+    fn with_declare<F>(&mut self, declare: bool, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let old = replace(&mut self.in_declare, self.in_declare || declare);
+        op(self);
+        self.in_declare = old;
+    }
+}
+
+fn id_of(i: &RIdent) -> Id {
+    (i.sym.clone(), i.span.ctxt)
+}
+
+impl Visit<RIdent> for UnusedFinder {
+    fn visit(&mut self, i: &RIdent) {
+        if !self.non_refs.contains(&i.span) {
+            self.used.insert(id_of(i));
+        }
+    }
+}
+
+impl Visit<RAssignExpr> for UnusedFinder {
+    fn visit(&mut self, e: &RAssignExpr) {
+        // `a = b` does not read `a`.
+        if e.op == AssignOp::Assign {
+            match &e.left {
+                RPatOrExpr::Pat(box RPat::Ident(i)) => {
+                    self.non_refs.insert(i.id.span);
+                }
+                RPatOrExpr::Expr(box RExpr::Ident(i)) | RPatOrExpr::Pat(box RPat::Expr(box RExpr::Ident(i))) => {
+                    self.non_refs.insert(i.span);
+                }
+                _ => {}
+            }
+        }
+
+        e.visit_children_with(self);
+    }
+}
+
+impl Visit<RImportDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RImportDecl) {
+        let mut decls = vec![];
+
+        for specifier in &d.specifiers {
+            let local = match specifier {
+                RImportSpecifier::Named(s) => &s.local,
+                RImportSpecifier::Default(s) => &s.local,
+                RImportSpecifier::Namespace(s) => &s.local,
+            };
+
+            decls.extend(self.decl(local, UnusedKind::Value, true));
+        }
+
+        self.imports.push(ImportGroup {
+            span: d.span,
+            len: d.specifiers.len(),
+            decls,
+        });
+    }
+}
+
+impl Visit<RExportDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RExportDecl) {
+        self.mark_exported(&d.decl);
+
+        d.visit_children_with(self);
+    }
+}
+
+impl Visit<RExportDefaultDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RExportDefaultDecl) {
+        if let RDefaultDecl::TsInterfaceDecl(i) = &d.decl {
+            self.exported.insert(id_of(&i.id));
+        }
+
+        d.visit_children_with(self);
+    }
+}
+
+impl Visit<RVarDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RVarDecl) {
+        let allow_underscore = replace(&mut self.in_for_in_of_head, false);
+
+        self.with_declare(d.declare, |v| {
+            for decl in &d.decls {
+                v.declare_pat(&decl.name, UnusedKind::Value, allow_underscore);
+            }
+
+            d.visit_children_with(v);
+        });
+    }
+}
+
+impl Visit<RForInStmt> for UnusedFinder {
+    fn visit(&mut self, s: &RForInStmt) {
+        let old = replace(&mut self.in_for_in_of_head, true);
+        s.left.visit_with(self);
+        self.in_for_in_of_head = old;
+
+        s.right.visit_with(self);
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<RForOfStmt> for UnusedFinder {
+    fn visit(&mut self, s: &RForOfStmt) {
+        let old = replace(&mut self.in_for_in_of_head, true);
+        s.left.visit_with(self);
+        self.in_for_in_of_head = old;
+
+        s.right.visit_with(self);
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<RFnDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RFnDecl) {
+        self.with_declare(d.declare, |v| {
+            v.declare(&d.ident, UnusedKind::Value, false);
+
+            d.visit_children_with(v);
+        });
+    }
+}
+
+impl Visit<RFunction> for UnusedFinder {
+    fn visit(&mut self, f: &RFunction) {
+        // Parameters of overloads and abstract methods can't be used.
+        if f.body.is_some() {
+            for param in &f.params {
+                match &param.pat {
+                    RPat::Ident(i) if i.id.sym == js_word!("this") => {}
+                    pat => self.declare_pat(pat, UnusedKind::Param, true),
+                }
+            }
+            self.declare_type_params(f.type_params.as_deref());
+        }
+
+        f.visit_children_with(self);
+    }
+}
+
+impl Visit<RArrowExpr> for UnusedFinder {
+    fn visit(&mut self, f: &RArrowExpr) {
+        for pat in &f.params {
+            self.declare_pat(pat, UnusedKind::Param, true);
+        }
+        self.declare_type_params(f.type_params.as_deref());
+
+        f.visit_children_with(self);
+    }
+}
+
+impl Visit<RConstructor> for UnusedFinder {
+    fn visit(&mut self, c: &RConstructor) {
+        if c.body.is_some() {
+            for param in &c.params {
+                // Parameter properties are class members.
+                if let RParamOrTsParamProp::Param(param) = param {
+                    self.declare_pat(&param.pat, UnusedKind::Param, true);
+                }
+            }
+        }
+
+        c.visit_children_with(self);
+    }
+}
+
+impl Visit<RSetterProp> for UnusedFinder {
+    fn visit(&mut self, p: &RSetterProp) {
+        if p.body.is_some() {
+            self.declare_pat(&p.param, UnusedKind::Param, true);
+        }
+
+        p.visit_children_with(self);
+    }
+}
+
+impl Visit<RClassDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RClassDecl) {
+        self.with_declare(d.declare, |v| {
+            v.declare(&d.ident, UnusedKind::Type, false);
+            v.declare_type_params(d.class.type_params.as_deref());
+
+            d.visit_children_with(v);
+        });
+    }
+}
+
+impl Visit<RTsInterfaceDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsInterfaceDecl) {
+        self.with_declare(d.declare, |v| {
+            v.declare(&d.id, UnusedKind::Type, false);
+            v.declare_type_params(d.type_params.as_deref());
+
+            d.visit_children_with(v);
+        });
+    }
+}
+
+impl Visit<RTsTypeAliasDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsTypeAliasDecl) {
+        self.with_declare(d.declare, |v| {
+            v.declare(&d.id, UnusedKind::Type, false);
+            v.declare_type_params(d.type_params.as_deref());
+
+            d.visit_children_with(v);
+        });
+    }
+}
+
+impl Visit<RTsEnumDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsEnumDecl) {
+        self.with_declare(d.declare, |v| {
+            v.declare(&d.id, UnusedKind::Type, false);
+
+            d.visit_children_with(v);
+        });
+    }
+}
+
+/// Members of ambient modules and namespaces are not checked.
+impl Visit<RTsModuleDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsModuleDecl) {
+        self.with_declare(d.declare, |v| d.visit_children_with(v));
+    }
+}
+
+impl Visit<RTsNamespaceDecl> for UnusedFinder {
+    fn visit(&mut self, d: &RTsNamespaceDecl) {
+        self.with_declare(d.declare, |v| d.visit_children_with(v));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rnode::{NodeIdGenerator, RNode};
+    use stc_ts_ast_rnode::RModule;
+    use swc_common::{FileName, Globals, Mark, SourceMap, GLOBALS};
+    use swc_ecma_ast::EsVersion;
+    use swc_ecma_parser::{parse_file_as_module, Syntax};
+    use swc_ecma_transforms::resolver;
+    use swc_ecma_visit::FoldWith;
+
+    use super::{find_unused_decls, UnusedKind};
+
+    fn unused(src: &str) -> Vec<(String, UnusedKind)> {
+        GLOBALS.set(&Globals::new(), || {
+            let cm = SourceMap::default();
+            let fm = cm.new_source_file(FileName::Anon, src.into());
+
+            let module = parse_file_as_module(&fm, Syntax::Typescript(Default::default()), EsVersion::latest(), None, &mut vec![])
+                .expect("failed to parse")
+                .fold_with(&mut resolver(Mark::new(), Mark::new(), true));
+            let module = RModule::from_orig(&mut NodeIdGenerator::invalid(), module);
+
+            find_unused_decls(&module)
+                .into_iter()
+                .map(|d| (d.name.to_string(), d.kind))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn unused_imports() {
+        let src = r#"
+            import { a, b } from "./a";
+            import c from "./c";
+            import { d, e, _f } from "./d";
+            import * as ns from "./ns";
+
+            export const x = d;
+        "#;
+
+        assert_eq!(
+            unused(src),
+            vec![
+                ("".into(), UnusedKind::AllImports),
+                ("c".into(), UnusedKind::Value),
+                ("e".into(), UnusedKind::Value),
+                ("ns".into(), UnusedKind::Value),
+            ]
+        );
+    }
+
+    #[test]
+    fn underscore_params() {
+        let src = "export function f(_a: number, b: number, c: number) { return c; }";
+
+        assert_eq!(unused(src), vec![("b".into(), UnusedKind::Param)]);
+    }
+
+    #[test]
+    fn type_params() {
+        let src = r#"
+            export function f<T, U, _V>(x: T): T { return x; }
+            export type Box<T, U> = { value: T };
+        "#;
+
+        assert_eq!(unused(src), vec![("U".into(), UnusedKind::Param), ("U".into(), UnusedKind::Param)]);
+    }
+
+    #[test]
+    fn destructuring() {
+        let src = r#"
+            export function f(p: { a: number; b: number }) {
+                const { a, ...rest } = p;
+                const { b } = p;
+                const [_x, y] = [1, 2];
+                return rest;
+            }
+        "#;
+
+        assert_eq!(unused(src), vec![("b".into(), UnusedKind::Value), ("y".into(), UnusedKind::Value)]);
+    }
+
+    #[test]
+    fn top_level_of_script() {
+        let src = "function f(a: number) {} interface I {}";
+
+        assert_eq!(unused(src), vec![("a".into(), UnusedKind::Param)]);
+    }
+}
@@ -1,14 +1,20 @@
 #![feature(box_syntax)]
 
-use std::{collections::hash_map::Entry, mem::take, sync::Arc};
+use std::{
+    collections::hash_map::Entry,
+    mem::{discriminant, take},
+    sync::Arc,
+};
 
 use auto_impl::auto_impl;
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use stc_ts_errors::{Error, ErrorKind, Errors};
-use stc_ts_types::{Id, ModuleId, ModuleTypeData, Type};
+use stc_ts_types::{Deprecation, ExportEquals, Id, ModuleId, ModuleTypeData, Type};
 use stc_utils::cache::Freeze;
 use swc_atoms::JsWord;
-use swc_common::{iter::IdentifyLast, FileName, Span, SyntaxContext, TypeEq, DUMMY_SP};
+use swc_common::{iter::IdentifyLast, FileName, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
+
+pub mod limit;
 
 #[derive(Debug, Default)]
 pub struct Info {
@@ -30,8 +36,24 @@ pub trait TypeStore: Send + Sync {
     fn get_local_type(&self, ctxt: ModuleId, id: Id) -> Option<Type>;
     fn get_local_var(&self, ctxt: ModuleId, id: Id) -> Option<Type>;
 
+    /// Returns the types exported from `ctxt` with the name `name` **at the
+    /// moment**.
+    ///
+    /// Used to resolve imports between modules in a circular group lazily.
+    fn get_exported_types(&self, ctxt: ModuleId, name: &JsWord) -> Option<Vec<Type>>;
+    /// Returns the variable exported from `ctxt` with the name `name` **at the
+    /// moment**.
+    fn get_exported_var(&self, ctxt: ModuleId, name: &JsWord) -> Option<Type>;
+    /// Returns all items exported from `ctxt` **at the moment**.
+    ///
+    /// Used to resolve `export *` between modules in a circular group.
+    fn get_exports(&self, ctxt: ModuleId) -> Option<ModuleTypeData>;
+
     fn store_private_type(&mut self, ctxt: ModuleId, id: Id, ty: Type, should_override: bool);
     fn store_private_var(&mut self, ctxt: ModuleId, id: Id, ty: Type);
+    /// Marks a declaration as `@deprecated`. The mark is exported with the
+    /// declaration by [TypeStore::export_var] and [TypeStore::export_type].
+    fn store_private_deprecation(&mut self, ctxt: ModuleId, id: Id, deprecation: Deprecation);
 
     fn export_type(&mut self, span: Span, ctxt: ModuleId, id: Id);
     fn export_var(&mut self, span: Span, ctxt: ModuleId, id: Id, orig_name: Id);
@@ -39,6 +61,9 @@ pub trait TypeStore: Send + Sync {
     fn reexport_type(&mut self, span: Span, ctxt: ModuleId, id: JsWord, ty: Type);
     fn reexport_var(&mut self, span: Span, ctxt: ModuleId, id: JsWord, ty: Type);
 
+    /// Stores the entity exported by `export = foo`.
+    fn export_equals(&mut self, ctxt: ModuleId, export: ExportEquals);
+
     fn take_info(&mut self, ctxt: ModuleId) -> ModuleTypeData;
 }
 
@@ -66,6 +91,30 @@ pub trait Mode: TypeStore + ErrorStore {
     }
 }
 
+/// Flattens `errors`, and removes errors of the same kind with the same code
+/// and position as an earlier error. The order of `errors` is preserved.
+///
+/// The same error can be reported by different inference paths, like
+/// validating an argument with and without a contextual type. Errors without
+/// a position are not removed, because they are not related to each other.
+pub fn dedup_errors(errors: Vec<Error>) -> Vec<Error> {
+    let mut seen = FxHashSet::default();
+
+    ErrorKind::flatten(errors)
+        .into_iter()
+        .filter(|err| {
+            let span = err.span();
+            span.is_dummy() || seen.insert((discriminant(&**err), err.code(), span.lo, span.hi))
+        })
+        .collect()
+}
+
+fn take_deduped(errors: &mut Errors) -> Errors {
+    let mut deduped = Errors::default();
+    deduped.extend(dedup_errors(take(errors).into()));
+    deduped
+}
+
 #[derive(Debug)]
 pub struct Single<'a> {
     pub parent: Option<&'a Single<'a>>,
@@ -86,7 +135,7 @@ impl ErrorStore for Single<'_> {
     }
 
     fn take_errors(&mut self) -> Errors {
-        take(&mut self.info.errors)
+        take_deduped(&mut self.info.errors)
     }
 }
 
@@ -127,9 +176,19 @@ impl TypeStore for Single<'_> {
         }
     }
 
+    fn store_private_deprecation(&mut self, ctxt: ModuleId, id: Id, deprecation: Deprecation) {
+        debug_assert_eq!(ctxt, self.id);
+
+        self.info.exports.private_deprecations.insert(id, deprecation);
+    }
+
     fn export_var(&mut self, span: Span, ctxt: ModuleId, id: Id, orig_name: Id) {
         debug_assert_eq!(ctxt, self.id);
 
+        if let Some(deprecation) = self.info.exports.private_deprecations.get(&orig_name).cloned() {
+            self.info.exports.deprecations.insert(id.sym().clone(), deprecation);
+        }
+
         match self.info.exports.private_vars.get(&orig_name).cloned() {
             Some(ty) => if let Some(..) = self.info.exports.vars.insert(id.sym().clone(), ty) {},
             None => self.report(ErrorKind::NoSuchVar { span, name: id }.into()),
@@ -139,6 +198,10 @@ impl TypeStore for Single<'_> {
     fn export_type(&mut self, span: Span, ctxt: ModuleId, id: Id) {
         debug_assert_eq!(ctxt, self.id);
 
+        if let Some(deprecation) = self.info.exports.private_deprecations.get(&id).cloned() {
+            self.info.exports.deprecations.insert(id.sym().clone(), deprecation);
+        }
+
         match self.info.exports.private_types.get(&id).cloned() {
             Some(ty) => {
                 *self.info.exports.types.entry(id.sym().clone()).or_default() = ty;
@@ -171,6 +234,40 @@ impl TypeStore for Single<'_> {
         }
     }
 
+    fn get_exported_types(&self, ctxt: ModuleId, name: &JsWord) -> Option<Vec<Type>> {
+        if ctxt != self.id {
+            return None;
+        }
+
+        match self.info.exports.types.get(name) {
+            Some(types) => Some(types.clone()),
+            None => self.parent?.get_exported_types(ctxt, name),
+        }
+    }
+
+    fn get_exported_var(&self, ctxt: ModuleId, name: &JsWord) -> Option<Type> {
+        if ctxt != self.id {
+            return None;
+        }
+
+        match self.info.exports.vars.get(name) {
+            Some(v) => Some(v.clone()),
+            None => self.parent?.get_exported_var(ctxt, name),
+        }
+    }
+
+    fn get_exports(&self, ctxt: ModuleId) -> Option<ModuleTypeData> {
+        if ctxt != self.id {
+            return None;
+        }
+
+        Some(ModuleTypeData {
+            vars: self.info.exports.vars.clone(),
+            types: self.info.exports.types.clone(),
+            ..Default::default()
+        })
+    }
+
     fn take_info(&mut self, ctxt: ModuleId) -> ModuleTypeData {
         debug_assert_eq!(ctxt, self.id);
         take(&mut self.info.exports)
@@ -188,6 +285,12 @@ impl TypeStore for Single<'_> {
         // TODO(kdy1): error reporting for duplicate
         self.info.exports.vars.insert(id, ty);
     }
+
+    fn export_equals(&mut self, ctxt: ModuleId, export: ExportEquals) {
+        debug_assert_eq!(ctxt, self.id);
+
+        self.info.exports.export_equals = Some(export);
+    }
 }
 
 impl<'a> Mode for Single<'a> {
@@ -246,7 +349,7 @@ impl ErrorStore for Group<'_> {
     }
 
     fn take_errors(&mut self) -> Errors {
-        take(&mut self.errors)
+        take_deduped(&mut self.errors)
     }
 }
 
@@ -277,8 +380,15 @@ impl TypeStore for Group<'_> {
         }
     }
 
+    fn store_private_deprecation(&mut self, ctxt: ModuleId, id: Id, deprecation: Deprecation) {
+        self.info.entry(ctxt).or_default().private_deprecations.insert(id, deprecation);
+    }
+
     fn export_var(&mut self, span: Span, ctxt: ModuleId, id: Id, orig_name: Id) {
         let e = self.info.entry(ctxt).or_default();
+        if let Some(deprecation) = e.private_deprecations.get(&orig_name).cloned() {
+            e.deprecations.insert(id.sym().clone(), deprecation);
+        }
         match e.private_vars.get(&orig_name) {
             Some(v) => {
                 e.vars.insert(id.sym().clone(), v.clone());
@@ -292,6 +402,9 @@ impl TypeStore for Group<'_> {
 
     fn export_type(&mut self, span: Span, ctxt: ModuleId, id: Id) {
         let e = self.info.entry(ctxt).or_default();
+        if let Some(deprecation) = e.private_deprecations.get(&id).cloned() {
+            e.deprecations.insert(id.sym().clone(), deprecation);
+        }
         match e.private_types.get(&id) {
             Some(v) => {
                 e.types.insert(id.sym().clone(), v.clone());
@@ -320,6 +433,30 @@ impl TypeStore for Group<'_> {
         }
     }
 
+    fn get_exported_types(&self, ctxt: ModuleId, name: &JsWord) -> Option<Vec<Type>> {
+        match self.info.get(&ctxt).and_then(|data| data.types.get(name)) {
+            Some(types) => Some(types.clone()),
+            None => self.parent?.get_exported_types(ctxt, name),
+        }
+    }
+
+    fn get_exported_var(&self, ctxt: ModuleId, name: &JsWord) -> Option<Type> {
+        match self.info.get(&ctxt).and_then(|data| data.vars.get(name)) {
+            Some(ty) => Some(ty.clone()),
+            None => self.parent?.get_exported_var(ctxt, name),
+        }
+    }
+
+    fn get_exports(&self, ctxt: ModuleId) -> Option<ModuleTypeData> {
+        let data = self.info.get(&ctxt)?;
+
+        Some(ModuleTypeData {
+            vars: data.vars.clone(),
+            types: data.types.clone(),
+            ..Default::default()
+        })
+    }
+
     fn take_info(&mut self, ctxt: ModuleId) -> ModuleTypeData {
         self.info.remove(&ctxt).unwrap_or_default()
     }
@@ -332,6 +469,10 @@ impl TypeStore for Group<'_> {
         // TODO(kdy1): Error reporting for duplicates
         self.info.entry(ctxt).or_default().vars.insert(id, ty);
     }
+
+    fn export_equals(&mut self, ctxt: ModuleId, export: ExportEquals) {
+        self.info.entry(ctxt).or_default().export_equals = Some(export);
+    }
 }
 
 impl Mode for Group<'_> {
@@ -431,6 +572,8 @@ impl TypeStore for Builtin {
         }
     }
 
+    fn store_private_deprecation(&mut self, _: ModuleId, _: Id, _: Deprecation) {}
+
     fn export_var(&mut self, _: Span, _: ModuleId, _: Id, _: Id) {}
 
     fn export_type(&mut self, _: Span, _: ModuleId, _: Id) {}
@@ -444,6 +587,18 @@ impl TypeStore for Builtin {
         self.vars.get(id.sym()).cloned()
     }
 
+    fn get_exported_types(&self, _: ModuleId, _: &JsWord) -> Option<Vec<Type>> {
+        None
+    }
+
+    fn get_exported_var(&self, _: ModuleId, _: &JsWord) -> Option<Type> {
+        None
+    }
+
+    fn get_exports(&self, _: ModuleId) -> Option<ModuleTypeData> {
+        None
+    }
+
     fn take_info(&mut self, _ctxt: ModuleId) -> ModuleTypeData {
         unimplemented!("builtin.take_info")
     }
@@ -451,6 +606,8 @@ impl TypeStore for Builtin {
     fn reexport_type(&mut self, _: Span, _: ModuleId, _: JsWord, _: Type) {}
 
     fn reexport_var(&mut self, _: Span, _: ModuleId, _: JsWord, _: Type) {}
+
+    fn export_equals(&mut self, _: ModuleId, _: ExportEquals) {}
 }
 
 impl Mode for Builtin {
@@ -480,6 +637,7 @@ mod tests {
     use std::path::PathBuf;
 
     use stc_ts_types::module_id;
+    use swc_common::{BytePos, Mark};
 
     use super::*;
 
@@ -524,4 +682,63 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn dedup_errors_by_code_and_position() {
+        testing::run_test(false, |_, _| {
+            let span = |lo, hi, ctxt| Span::new(BytePos(lo), BytePos(hi), ctxt);
+            let ctxt = SyntaxContext::empty().apply_mark(Mark::new());
+
+            let errors: Vec<Error> = vec![
+                ErrorKind::ModuleNotFound {
+                    span: span(1, 5, SyntaxContext::empty()),
+                }
+                .into(),
+                ErrorKind::Errors {
+                    span: DUMMY_SP,
+                    errors: vec![
+                        // Same position, with a different context.
+                        ErrorKind::ModuleNotFound { span: span(1, 5, ctxt) }.into(),
+                        ErrorKind::ModuleNotFound {
+                            span: span(6, 9, SyntaxContext::empty()),
+                        }
+                        .into(),
+                    ],
+                }
+                .into(),
+            ];
+
+            let spans = dedup_errors(errors)
+                .iter()
+                .map(|err| (err.span().lo.0, err.span().hi.0))
+                .collect::<Vec<_>>();
+            assert_eq!(spans, vec![(1, 5), (6, 9)]);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn dedup_errors_keeps_different_kinds_and_dummy_spans() {
+        testing::run_test(false, |_, _| {
+            let span = Span::new(BytePos(1), BytePos(5), SyntaxContext::empty());
+            let name = Id::word("foo".into());
+
+            let errors: Vec<Error> = vec![
+                ErrorKind::NoSuchVar { span, name: name.clone() }.into(),
+                // Same code and position, but a different problem.
+                ErrorKind::NoSuchType { span, name: name.clone() }.into(),
+                ErrorKind::NoSuchVar { span, name: name.clone() }.into(),
+                ErrorKind::ModuleNotFound { span: DUMMY_SP }.into(),
+                ErrorKind::ModuleNotFound { span: DUMMY_SP }.into(),
+            ];
+
+            let codes = dedup_errors(errors).iter().map(|err| err.code()).collect::<Vec<_>>();
+            assert_eq!(codes, vec![2304, 2304, 2307, 2307]);
+
+            Ok(())
+        })
+        .unwrap();
+    }
 }
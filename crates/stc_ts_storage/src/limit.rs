@@ -0,0 +1,130 @@
+//! Limits the number of reported errors, and counts them for summaries.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use stc_ts_errors::{Error, ErrorKind};
+use swc_common::Spanned;
+
+/// The maximum number of errors to report. Suggestions are not counted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorLimit {
+    pub per_file: Option<usize>,
+    /// Files are checked in parallel, so errors of the files checked first are
+    /// reported once this is reached.
+    pub total: Option<usize>,
+}
+
+/// Counts checked files and reported errors, and applies an [ErrorLimit].
+///
+/// Shared by all modules of a program, so this can be used from multiple
+/// threads.
+#[derive(Debug, Default)]
+pub struct ErrorCounter {
+    limit: ErrorLimit,
+    files: AtomicUsize,
+    errors: AtomicUsize,
+    suppressed: AtomicUsize,
+}
+
+impl ErrorCounter {
+    pub fn new(limit: ErrorLimit) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    pub fn limit(&self) -> ErrorLimit {
+        self.limit
+    }
+
+    /// Counts sorted errors of a checked file, and drops errors over the
+    /// limit.
+    ///
+    /// Dropped errors are replaced by an [ErrorKind::TooManyErrors] at the
+    /// position of the first dropped error.
+    pub fn on_file_checked(&self, errors: &mut Vec<Error>) {
+        self.files.fetch_add(1, Ordering::Relaxed);
+
+        let count = errors.iter().filter(|err| !err.is_suggestion()).count();
+        let mut allowed = self.limit.per_file.map_or(count, |max| count.min(max));
+
+        let reported = self
+            .errors
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |reported| {
+                Some(reported + self.limit.total.map_or(allowed, |max| allowed.min(max.saturating_sub(reported))))
+            })
+            .unwrap();
+        if let Some(max) = self.limit.total {
+            allowed = allowed.min(max.saturating_sub(reported));
+        }
+
+        if allowed == count {
+            return;
+        }
+
+        let mut kept = 0;
+        let mut first_dropped = None;
+        errors.retain(|err| {
+            if err.is_suggestion() {
+                return true;
+            }
+            if kept < allowed {
+                kept += 1;
+                return true;
+            }
+
+            first_dropped.get_or_insert_with(|| err.span());
+            false
+        });
+
+        let suppressed = count - allowed;
+        self.suppressed.fetch_add(suppressed, Ordering::Relaxed);
+
+        if let Some(span) = first_dropped {
+            errors.push(ErrorKind::TooManyErrors { span, suppressed }.into());
+        }
+    }
+
+    pub fn summary(&self, elapsed: Duration) -> ErrorSummary {
+        ErrorSummary {
+            files: self.files.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            suppressed: self.suppressed.load(Ordering::Relaxed),
+            elapsed,
+        }
+    }
+}
+
+/// Statistics of a checked program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorSummary {
+    pub files: usize,
+    /// The number of reported errors.
+    pub errors: usize,
+    /// The number of errors dropped because of the [ErrorLimit].
+    pub suppressed: usize,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for ErrorSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Found {} errors in {} files in {:.2}s",
+            self.errors,
+            self.files,
+            self.elapsed.as_secs_f64()
+        )?;
+
+        if self.suppressed > 0 {
+            write!(f, " ({} more errors were not reported because of the error limit)", self.suppressed)?;
+        }
+
+        Ok(())
+    }
+}
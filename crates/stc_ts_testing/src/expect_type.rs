@@ -0,0 +1,245 @@
+//! Support for inline type expectations.
+//!
+//! Two kinds of comments are supported.
+//!
+//! ```ts
+//! const a = foo(1);
+//! //        ^? number
+//!
+//! foo(""); // expect-type: string
+//! ```
+//!
+//! A `^?` query (like twoslash) points at the expression above the caret, and
+//! the smallest expression containing the column of the caret is used.
+//!
+//! An `expect-type:` comment applies to the largest expression starting on
+//! the same line.
+
+/// A position in a file. `line` is 1-based and `col` is 0-based, like
+/// [swc_common::Loc].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectationKind {
+    /// `// ^? T`
+    Query { col: usize },
+    /// `// expect-type: T`
+    Line,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    /// Line of the comment.
+    pub comment_line: usize,
+    /// Line of the expression.
+    pub line: usize,
+    pub kind: ExpectationKind,
+    pub expected: String,
+}
+
+/// Type of an expression, computed by the type checker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedType {
+    pub lo: LineCol,
+    pub hi: LineCol,
+    /// Length of the expression in bytes.
+    pub len: usize,
+    pub ty: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub expectation: Expectation,
+    /// [None] if no expression matched the expectation.
+    pub actual: Option<String>,
+}
+
+const QUERY: &str = "^?";
+const EXPECT_TYPE: &str = "expect-type:";
+
+pub fn parse_expectations(src: &str) -> Vec<Expectation> {
+    let mut expectations = vec![];
+    // Line of the last line which is not a query.
+    let mut last_code_line = None;
+
+    for (idx, line) in src.lines().enumerate() {
+        let line_no = idx + 1;
+        let trimmed = line.trim_start();
+
+        if let Some(comment) = trimmed.strip_prefix("//") {
+            if let Some(rest) = comment.trim_start().strip_prefix(QUERY) {
+                let byte_pos = line.find(QUERY).unwrap();
+
+                if let Some(target) = last_code_line {
+                    expectations.push(Expectation {
+                        comment_line: line_no,
+                        line: target,
+                        kind: ExpectationKind::Query {
+                            col: line[..byte_pos].chars().count(),
+                        },
+                        expected: rest.trim().to_string(),
+                    });
+                }
+                continue;
+            }
+        }
+
+        if let Some(pos) = line.find("//") {
+            if let Some(expected) = line[pos + 2..].trim_start().strip_prefix(EXPECT_TYPE) {
+                expectations.push(Expectation {
+                    comment_line: line_no,
+                    line: line_no,
+                    kind: ExpectationKind::Line,
+                    expected: expected.trim().to_string(),
+                });
+            }
+        }
+
+        last_code_line = Some(line_no);
+    }
+
+    expectations
+}
+
+/// Returns the expectations which are not satisfied by `recorded`.
+///
+/// If multiple types are recorded for an expression, the last one is used.
+pub fn check_expectations(expectations: &[Expectation], recorded: &[RecordedType]) -> Vec<Mismatch> {
+    expectations
+        .iter()
+        .filter_map(|expectation| {
+            let actual = find_type(expectation, recorded).map(|r| r.ty.clone());
+
+            match &actual {
+                Some(actual) if normalize(actual) == normalize(&expectation.expected) => None,
+                _ => Some(Mismatch {
+                    expectation: expectation.clone(),
+                    actual,
+                }),
+            }
+        })
+        .collect()
+}
+
+fn find_type<'a>(expectation: &Expectation, recorded: &'a [RecordedType]) -> Option<&'a RecordedType> {
+    let candidates = recorded.iter().enumerate().filter(|(_, r)| match expectation.kind {
+        ExpectationKind::Query { col } => {
+            let pos = LineCol {
+                line: expectation.line,
+                col,
+            };
+            r.lo <= pos && pos < r.hi
+        }
+        ExpectationKind::Line => r.lo.line == expectation.line,
+    });
+
+    match expectation.kind {
+        ExpectationKind::Query { .. } => candidates.min_by_key(|(idx, r)| (r.len, usize::MAX - idx)),
+        ExpectationKind::Line => candidates.max_by_key(|(idx, r)| (r.len, *idx)),
+    }
+    .map(|(_, r)| r)
+}
+
+/// Ignores differences in whitespaces, quotes and separators of members.
+fn normalize(ty: &str) -> String {
+    let ty = ty.replace(';', ",").replace('\'', "\"");
+    let ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    ty.replace(", }", " }").trim_end_matches(',').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ty_at(line: usize, lo: usize, hi: usize, ty: &str) -> RecordedType {
+        RecordedType {
+            lo: LineCol { line, col: lo },
+            hi: LineCol { line, col: hi },
+            len: hi - lo,
+            ty: ty.into(),
+        }
+    }
+
+    #[test]
+    fn parse_query() {
+        let src = "const a = foo(1);\n//        ^? number\n";
+
+        assert_eq!(
+            parse_expectations(src),
+            vec![Expectation {
+                comment_line: 2,
+                line: 1,
+                kind: ExpectationKind::Query { col: 10 },
+                expected: "number".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_stacked_queries() {
+        let src = "foo(a, b);\n//  ^? A\n//     ^? B\n";
+
+        let expectations = parse_expectations(src);
+
+        assert_eq!(expectations.len(), 2);
+        assert!(expectations.iter().all(|e| e.line == 1));
+    }
+
+    #[test]
+    fn parse_line() {
+        let src = "foo(''); // expect-type: string\n";
+
+        assert_eq!(
+            parse_expectations(src),
+            vec![Expectation {
+                comment_line: 1,
+                line: 1,
+                kind: ExpectationKind::Line,
+                expected: "string".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn query_uses_smallest_expr() {
+        let expectations = parse_expectations("foo(bar);\n//  ^? Bar\n");
+        let recorded = vec![ty_at(1, 0, 8, "Foo"), ty_at(1, 4, 7, "Bar")];
+
+        assert_eq!(check_expectations(&expectations, &recorded), vec![]);
+    }
+
+    #[test]
+    fn line_uses_largest_expr() {
+        let expectations = parse_expectations("foo(bar); // expect-type: Foo\n");
+        let recorded = vec![ty_at(1, 4, 7, "Bar"), ty_at(1, 0, 8, "Foo")];
+
+        assert_eq!(check_expectations(&expectations, &recorded), vec![]);
+    }
+
+    #[test]
+    fn mismatch() {
+        let expectations = parse_expectations("foo(bar); // expect-type: { a: 'a' }\n");
+        let recorded = vec![ty_at(1, 0, 8, "{\n    a: \"a\";\n}")];
+
+        assert_eq!(check_expectations(&expectations, &recorded), vec![]);
+
+        let recorded = vec![ty_at(1, 0, 8, "{ a: \"b\" }")];
+        let mismatches = check_expectations(&expectations, &recorded);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].actual.as_deref(), Some("{ a: \"b\" }"));
+    }
+
+    #[test]
+    fn no_expr() {
+        let expectations = parse_expectations("let a;\n//  ^? any\n");
+
+        let mismatches = check_expectations(&expectations, &[]);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].actual, None);
+    }
+}
@@ -12,7 +12,7 @@ use std::{
 };
 
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_env::{Env, ModuleKind};
 use stc_ts_file_analyzer::env::EnvFactory;
 use stc_ts_module_loader::resolvers::node::NodeResolver;
 use stc_ts_type_checker::{loader::ModuleLoader, Checker};
@@ -57,12 +57,7 @@ fn run_bench(b: &mut Bencher, path: &Path) {
 
         let handler = Arc::new(handler);
 
-        let env = Env::simple(
-            Default::default(),
-            EsVersion::latest(),
-            ModuleConfig::None,
-            &Lib::load("es2020.full"),
-        );
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es2020.full"));
         b.iter(|| {
             let mut checker = Checker::new(
                 cm.clone(),
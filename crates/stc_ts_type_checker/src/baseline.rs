@@ -0,0 +1,202 @@
+//! Baselines of existing errors, for adopting stc gradually.
+//!
+//! A [Baseline] records fingerprints of the errors of a program. Errors in the
+//! baseline are not reported on later runs, so only new errors fail the check.
+//! Entries which do not match any error anymore are reported as stale, like
+//! unused `@ts-expect-error` directives, so fixed errors cannot come back
+//! unnoticed.
+//!
+//! A fingerprint is made of the error code and the hash of the source text of
+//! the error, so it does not change if lines are added above the error.
+//! Messages are not used because they contain positions.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Component, Path},
+};
+
+use anyhow::{Context, Error};
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use stc_ts_errors::{Error as TsError, ErrorKind};
+use swc_common::{FileName, SourceMap, Spanned};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Baseline {
+    /// Keyed by paths relative to the base directory, and then by
+    /// fingerprints. Values are the number of errors with the fingerprint.
+    pub files: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+/// The result of [Baseline::apply].
+#[derive(Debug, Default)]
+pub struct BaselineResult {
+    /// Flattened errors which are not in the baseline, in the original order.
+    pub errors: Vec<TsError>,
+
+    /// The number of errors in the baseline.
+    pub matched: usize,
+
+    pub stale: Vec<StaleEntry>,
+}
+
+/// An entry of a [Baseline] without matching errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleEntry {
+    pub file: String,
+    pub fingerprint: String,
+    /// The number of errors in the baseline which are not reported anymore.
+    pub count: usize,
+}
+
+impl Baseline {
+    /// Records `errors`, which are flattened. Paths of files in `base` are
+    /// stored as relative paths, so the baseline can be shared between
+    /// machines.
+    pub fn from_errors(cm: &SourceMap, base: &Path, errors: &[TsError]) -> Self {
+        let mut keys = Keys::new(cm, base);
+        let mut baseline = Self::default();
+
+        for err in ErrorKind::flatten(errors.to_vec()) {
+            let (file, fingerprint) = keys.key(&err);
+            *baseline.files.entry(file).or_default().entry(fingerprint).or_default() += 1;
+        }
+
+        baseline
+    }
+
+    /// Removes errors recorded in `self` from `errors`.
+    ///
+    /// `base` should be the one used to create `self`.
+    pub fn apply(&self, cm: &SourceMap, base: &Path, errors: Vec<TsError>) -> BaselineResult {
+        let mut keys = Keys::new(cm, base);
+        let mut remaining = self.files.clone();
+        let mut result = BaselineResult::default();
+
+        for err in ErrorKind::flatten(errors) {
+            let (file, fingerprint) = keys.key(&err);
+
+            match remaining.get_mut(&file).and_then(|fingerprints| fingerprints.get_mut(&fingerprint)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    result.matched += 1;
+                }
+                _ => result.errors.push(err),
+            }
+        }
+
+        for (file, fingerprints) in remaining {
+            for (fingerprint, count) in fingerprints {
+                if count > 0 {
+                    result.stale.push(StaleEntry {
+                        file: file.clone(),
+                        fingerprint,
+                        count,
+                    });
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The number of recorded errors.
+    pub fn len(&self) -> usize {
+        self.files.values().flat_map(|fingerprints| fingerprints.values()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+        serde_json::from_slice(&content).with_context(|| format!("invalid baseline {}", path.display()))
+    }
+
+    /// Baselines are committed to repositories, so they are pretty-printed to
+    /// make diffs readable.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        let mut content = serde_json::to_string_pretty(self).context("failed to serialize baseline")?;
+        content.push('\n');
+        fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Returns the fingerprint of `err`, like `TS2322-3b1f0e7c9a2d4f60`.
+///
+/// Whitespaces in the source text are normalized, so reformatting code does not
+/// change fingerprints. The text is hashed with SHA-1, so fingerprints are
+/// stable across versions and platforms.
+pub fn fingerprint(cm: &SourceMap, err: &TsError) -> String {
+    let span = err.span();
+    let text = if span.is_dummy() {
+        String::new()
+    } else {
+        cm.span_to_snippet(span).unwrap_or_default()
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let hash = Sha1::digest(text.as_bytes());
+    let hash = hash[..8].iter().map(|b| format!("{:02x}", b)).collect::<String>();
+
+    format!("TS{}-{}", err.code(), hash)
+}
+
+/// Computes keys of errors, caching relative paths of files.
+struct Keys<'a> {
+    cm: &'a SourceMap,
+    base: &'a Path,
+    paths: FxHashMap<String, String>,
+}
+
+impl<'a> Keys<'a> {
+    fn new(cm: &'a SourceMap, base: &'a Path) -> Self {
+        Self {
+            cm,
+            base,
+            paths: Default::default(),
+        }
+    }
+
+    fn key(&mut self, err: &TsError) -> (String, String) {
+        let span = err.span();
+        let file = if span.is_dummy() {
+            String::new()
+        } else {
+            let name = self.cm.lookup_source_file(span.lo).name.clone();
+            let base = self.base;
+
+            self.paths
+                .entry(name.to_string())
+                .or_insert_with(|| match &name {
+                    FileName::Real(path) => {
+                        let path = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                        let path = path.strip_prefix(base).unwrap_or(&path);
+
+                        // `/` is used on all platforms.
+                        path.components()
+                            .filter_map(|c| match c {
+                                Component::RootDir => Some("".into()),
+                                Component::Normal(s) => Some(s.to_string_lossy()),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>()
+                            .join("/")
+                    }
+                    _ => name.to_string(),
+                })
+                .clone()
+        };
+
+        (file, fingerprint(self.cm, err))
+    }
+}
@@ -0,0 +1,410 @@
+//! Integration API for bundlers.
+//!
+//! A bundler can register [BundlerHooks] to [crate::Checker] and receive the
+//! classification of each import and the usage of exports after checking, so
+//! it can elide type-only imports and shake unused exports without parsing
+//! the files again.
+
+use std::sync::Arc;
+
+use fxhash::{FxHashMap, FxHashSet};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// Classification of an import declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportKind {
+    /// All bindings are used only as types. The import can be removed.
+    TypeOnly,
+    /// At least one binding is used as a value.
+    Value,
+    /// `import * as ns from 'foo'` where `ns` is used as a value.
+    Namespace,
+    /// `import 'foo'`
+    SideEffect,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportBinding {
+    /// Name of the export. `default` for default imports.
+    pub imported: JsWord,
+    pub local: JsWord,
+    /// `true` if the binding is used as a value.
+    pub is_value: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportInfo {
+    pub span: Span,
+    pub src: JsWord,
+    /// The resolved module, if the module loader could resolve it.
+    pub resolved: Option<Arc<FileName>>,
+    pub kind: ImportKind,
+    pub bindings: Vec<ImportBinding>,
+}
+
+/// How exports of a module are used by other modules.
+#[derive(Debug, Clone, Default)]
+pub struct ExportUsage {
+    /// Exports used as values.
+    pub values: FxHashSet<JsWord>,
+    /// Exports used only as types.
+    pub types: FxHashSet<JsWord>,
+    /// `true` if the module is imported as a namespace. All exports should be
+    /// considered as used.
+    pub namespace: bool,
+    /// `true` if the module is imported only for side effects at least once.
+    pub side_effect: bool,
+}
+
+impl ExportUsage {
+    pub(crate) fn add(&mut self, import: &ImportInfo) {
+        match import.kind {
+            ImportKind::Namespace => self.namespace = true,
+            ImportKind::SideEffect => self.side_effect = true,
+            ImportKind::TypeOnly | ImportKind::Value => {}
+        }
+
+        for binding in &import.bindings {
+            if binding.is_value {
+                self.types.remove(&binding.imported);
+                self.values.insert(binding.imported.clone());
+            } else if !self.values.contains(&binding.imported) {
+                self.types.insert(binding.imported.clone());
+            }
+        }
+    }
+}
+
+/// Callbacks invoked by [crate::Checker].
+///
+/// Files are identified using [FileName], which is what the bundler passes to
+/// the module loader.
+pub trait BundlerHooks: Send + Sync {
+    /// Called once per module after the module is checked.
+    fn on_imports(&self, file: &Arc<FileName>, imports: &[ImportInfo]);
+
+    /// Called for each imported module after [crate::Checker::check] is
+    /// done.
+    fn on_export_usage(&self, file: &Arc<FileName>, usage: &ExportUsage);
+}
+
+/// Classifies imports of `module`.
+///
+/// `resolve` returns the resolved file and names of the exported values of the
+/// imported module, if known. If exported values are unknown, every used
+/// binding is treated as a value.
+pub(crate) fn classify_imports<F>(module: &Module, mut resolve: F) -> Vec<ImportInfo>
+where
+    F: FnMut(&JsWord) -> (Option<Arc<FileName>>, Option<FxHashSet<JsWord>>),
+{
+    let mut usage = ValueUsageFinder::default();
+    module.visit_with(&mut usage);
+
+    let mut imports = vec![];
+
+    for item in &module.body {
+        let import = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => import,
+            _ => continue,
+        };
+
+        let (resolved, exported_values) = resolve(&import.src.value);
+
+        let bindings = import
+            .specifiers
+            .iter()
+            .map(|s| {
+                let (imported, local, is_type_only) = match s {
+                    ImportSpecifier::Named(s) => (
+                        match &s.imported {
+                            Some(ModuleExportName::Ident(i)) => i.sym.clone(),
+                            Some(ModuleExportName::Str(s)) => s.value.clone(),
+                            None => s.local.sym.clone(),
+                        },
+                        &s.local,
+                        s.is_type_only,
+                    ),
+                    ImportSpecifier::Default(s) => (js_word!("default"), &s.local, false),
+                    ImportSpecifier::Namespace(s) => ("*".into(), &s.local, false),
+                };
+
+                let is_value = !import.type_only
+                    && !is_type_only
+                    && usage.used.contains(&local.to_id())
+                    && match &exported_values {
+                        Some(values) => &*imported == "*" || values.contains(&imported),
+                        None => true,
+                    };
+
+                ImportBinding {
+                    imported,
+                    local: local.sym.clone(),
+                    is_value,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let kind = if import.specifiers.is_empty() {
+            ImportKind::SideEffect
+        } else if !bindings.iter().any(|b| b.is_value) {
+            ImportKind::TypeOnly
+        } else if import.specifiers.iter().any(|s| matches!(s, ImportSpecifier::Namespace(..))) {
+            ImportKind::Namespace
+        } else {
+            ImportKind::Value
+        };
+
+        imports.push(ImportInfo {
+            span: import.span,
+            src: import.src.value.clone(),
+            resolved,
+            kind,
+            bindings,
+        });
+    }
+
+    imports
+}
+
+/// Finds identifiers used in value positions.
+#[derive(Default)]
+struct ValueUsageFinder {
+    used: FxHashSet<Id>,
+}
+
+impl Visit for ValueUsageFinder {
+    fn visit_expr(&mut self, e: &Expr) {
+        e.visit_children_with(self);
+
+        if let Expr::Ident(i) = e {
+            self.used.insert(i.to_id());
+        }
+    }
+
+    fn visit_prop(&mut self, p: &Prop) {
+        p.visit_children_with(self);
+
+        if let Prop::Shorthand(i) = p {
+            self.used.insert(i.to_id());
+        }
+    }
+
+    fn visit_jsx_element_name(&mut self, n: &JSXElementName) {
+        n.visit_children_with(self);
+
+        if let JSXElementName::Ident(i) = n {
+            self.used.insert(i.to_id());
+        }
+    }
+
+    fn visit_jsx_member_expr(&mut self, n: &JSXMemberExpr) {
+        n.visit_children_with(self);
+
+        if let JSXObject::Ident(i) = &n.obj {
+            self.used.insert(i.to_id());
+        }
+    }
+
+    fn visit_export_named_specifier(&mut self, s: &ExportNamedSpecifier) {
+        if s.is_type_only {
+            return;
+        }
+
+        if let ModuleExportName::Ident(i) = &s.orig {
+            self.used.insert(i.to_id());
+        }
+    }
+
+    fn visit_named_export(&mut self, e: &NamedExport) {
+        // Re-exports from another module don't use local bindings.
+        if e.src.is_some() || e.type_only {
+            return;
+        }
+
+        e.visit_children_with(self);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, d: &TsImportEqualsDecl) {
+        if d.is_type_only {
+            return;
+        }
+
+        if let TsModuleRef::TsEntityName(name) = &d.module_ref {
+            let mut name = name;
+            while let TsEntityName::TsQualifiedName(q) = name {
+                name = &q.left;
+            }
+            if let TsEntityName::Ident(i) = name {
+                self.used.insert(i.to_id());
+            }
+        }
+    }
+
+    /// Types are not values.
+    fn visit_ts_type(&mut self, _: &TsType) {}
+
+    /// Types are not values.
+    fn visit_ts_interface_decl(&mut self, _: &TsInterfaceDecl) {}
+
+    /// Types are not values.
+    fn visit_ts_type_alias_decl(&mut self, _: &TsTypeAliasDecl) {}
+}
+
+/// Export usage of modules, keyed by the imported file.
+pub(crate) type ExportUsageMap = FxHashMap<Arc<FileName>, ExportUsage>;
+
+#[cfg(test)]
+mod tests {
+    use swc_common::Mark;
+    use swc_ecma_parser::{parse_file_as_module, Syntax, TsConfig};
+    use swc_ecma_transforms_base::resolver;
+    use swc_ecma_visit::VisitMutWith;
+
+    use super::*;
+
+    /// Classifies imports of `src`. `values` are the exported values of every
+    /// imported module, if known.
+    fn classify(src: &str, values: Option<&[&str]>) -> Vec<(ImportKind, Vec<(String, bool)>)> {
+        testing::run_test(false, |cm, _| {
+            let fm = cm.new_source_file(FileName::Anon, src.into());
+            let mut module =
+                parse_file_as_module(&fm, Syntax::Typescript(TsConfig::default()), EsVersion::latest(), None, &mut vec![]).unwrap();
+            module.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), true));
+
+            let values = values.map(|values| values.iter().map(|&v| JsWord::from(v)).collect::<FxHashSet<_>>());
+            let imports = classify_imports(&module, |_| (None, values.clone()));
+
+            Ok(imports
+                .into_iter()
+                .map(|import| {
+                    let bindings = import.bindings.iter().map(|b| (b.imported.to_string(), b.is_value)).collect();
+                    (import.kind, bindings)
+                })
+                .collect())
+        })
+        .unwrap()
+    }
+
+    fn import(kind: ImportKind, bindings: &[(&str, bool)]) -> (ImportKind, Vec<(String, bool)>) {
+        (
+            kind,
+            bindings.iter().map(|&(name, is_value)| (name.to_string(), is_value)).collect(),
+        )
+    }
+
+    #[test]
+    fn type_only_imports() {
+        let imports = classify(
+            "import { A } from './a';\nimport type { B } from './b';\nimport { type C } from './c';\nlet x: A | B | C;\n",
+            None,
+        );
+
+        assert_eq!(
+            imports,
+            vec![
+                import(ImportKind::TypeOnly, &[("A", false)]),
+                import(ImportKind::TypeOnly, &[("B", false)]),
+                import(ImportKind::TypeOnly, &[("C", false)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_imports() {
+        let imports = classify(
+            "import a, { b as local, C } from './a';\nimport { type d, e } from './d';\na(local);\nlet c: C = { e };\n",
+            None,
+        );
+
+        assert_eq!(
+            imports,
+            vec![
+                import(ImportKind::Value, &[("default", true), ("b", true), ("C", false)]),
+                import(ImportKind::Value, &[("d", false), ("e", true)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn namespace_and_side_effect_imports() {
+        let imports = classify(
+            "import './polyfill';\nimport * as ns from './ns';\nimport * as types from './types';\nns.f();\nlet t: types.T;\n",
+            None,
+        );
+
+        assert_eq!(
+            imports,
+            vec![
+                import(ImportKind::SideEffect, &[]),
+                import(ImportKind::Namespace, &[("*", true)]),
+                import(ImportKind::TypeOnly, &[("*", false)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reexported_imports() {
+        let imports = classify(
+            "import { a } from './a';\nimport { T } from './t';\nexport { a };\nexport type { T };\nexport { b } from './b';\n",
+            None,
+        );
+
+        assert_eq!(
+            imports,
+            vec![
+                import(ImportKind::Value, &[("a", true)]),
+                import(ImportKind::TypeOnly, &[("T", false)])
+            ]
+        );
+    }
+
+    #[test]
+    fn known_exported_values() {
+        // `I` is an interface, so it's not a value although it's used like one.
+        let imports = classify("import { I, v } from './a';\nconsole.log(I, v);\n", Some(&["v"]));
+
+        assert_eq!(imports, vec![import(ImportKind::Value, &[("I", false), ("v", true)])]);
+    }
+
+    fn info(kind: ImportKind, bindings: &[(&str, bool)]) -> ImportInfo {
+        ImportInfo {
+            span: Default::default(),
+            src: "./a".into(),
+            resolved: None,
+            kind,
+            bindings: bindings
+                .iter()
+                .map(|&(name, is_value)| ImportBinding {
+                    imported: name.into(),
+                    local: name.into(),
+                    is_value,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn export_usage() {
+        let mut usage = ExportUsage::default();
+
+        usage.add(&info(ImportKind::Value, &[("a", true), ("b", false)]));
+        usage.add(&info(ImportKind::TypeOnly, &[("a", false), ("c", false)]));
+        // `b` is used as a value by another import.
+        usage.add(&info(ImportKind::Value, &[("b", true)]));
+
+        let mut values = usage.values.iter().map(|v| &**v).collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec!["a", "b"]);
+        assert_eq!(usage.types.iter().map(|v| &**v).collect::<Vec<_>>(), vec!["c"]);
+        assert!(!usage.namespace);
+        assert!(!usage.side_effect);
+
+        usage.add(&info(ImportKind::SideEffect, &[]));
+        usage.add(&info(ImportKind::Namespace, &[("*", true)]));
+        assert!(usage.namespace);
+        assert!(usage.side_effect);
+    }
+}
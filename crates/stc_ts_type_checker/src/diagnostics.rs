@@ -0,0 +1,301 @@
+//! Streaming and deduplication of diagnostics.
+//!
+//! Checking a large project takes a while. A [DiagnosticsSink] registered to
+//! [crate::Checker] receives the errors of each module as soon as the module
+//! is checked, so users can see progress before [crate::Checker::check]
+//! returns. Build tools can implement it to report diagnostics using their own
+//! format.
+//!
+//! [dedup_errors] should be applied before emitting errors, to avoid reporting
+//! the same problem many times.
+//!
+//! [JsonEmitter] prints emitted diagnostics as JSON lines, for editors and CI
+//! annotators.
+
+use std::{io::Write, sync::Arc};
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::Id;
+use swc_common::{
+    errors::{DiagnosticBuilder, DiagnosticId, Emitter, Handler, Level},
+    FileName, SourceMap, Span, Spanned,
+};
+
+/// Severity of a diagnostic, decided by [DiagnosticsSink::severity].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Suggestion,
+}
+
+/// An error passed to [DiagnosticsSink::on_module_checked].
+#[derive(Debug, Clone)]
+pub struct SinkDiagnostic {
+    pub error: Error,
+    pub severity: Severity,
+}
+
+pub trait DiagnosticsSink: Send + Sync {
+    /// Returns `false` to drop `error`.
+    ///
+    /// Dropped errors are not passed to [DiagnosticsSink::on_module_checked],
+    /// and they are not returned by [crate::Checker::take_errors].
+    fn filter(&self, _file: &Arc<FileName>, _error: &Error) -> bool {
+        true
+    }
+
+    /// Defaults to [Severity::Suggestion] for [ErrorKind::is_suggestion] and
+    /// [Severity::Error] for others.
+    fn severity(&self, error: &Error) -> Severity {
+        if error.is_suggestion() {
+            Severity::Suggestion
+        } else {
+            Severity::Error
+        }
+    }
+
+    /// Called once per module after the module is checked, even if there's no
+    /// error. `diagnostics` are sorted by position.
+    ///
+    /// Modules are checked in parallel, so the order of calls is not
+    /// deterministic. Use [crate::Checker::take_errors] after checking for a
+    /// deterministic result.
+    fn on_module_checked(&self, file: &Arc<FileName>, diagnostics: &[SinkDiagnostic]);
+
+    /// If this returns `true`, modules which are not analyzed yet are skipped
+    /// and types of them are `any`.
+    ///
+    /// This is called before analyzing each module, so it should be cheap.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// Sorts errors of a module by position, and then by error code.
+pub(crate) fn sort_errors(errors: &mut [Error]) {
+    errors.sort_by_key(|err| {
+        let span = err.span();
+        (span.lo, span.hi, err.code())
+    });
+}
+
+/// Sorts errors by file name, and then by position in the file.
+///
+/// Positions of different files depend on the order in which files are
+/// loaded, which differs between runs because modules are loaded in parallel.
+/// Errors without a position come first.
+pub fn sort_errors_by_file(cm: &SourceMap, errors: &mut [Error]) {
+    errors.sort_by_cached_key(|err| {
+        let span = err.span();
+        if span.is_dummy() {
+            return (None, 0, 0, err.code());
+        }
+
+        let file = cm.lookup_source_file(span.lo);
+        (
+            Some(file.name.to_string()),
+            (span.lo - file.start_pos).0,
+            (span.hi - file.start_pos).0,
+            err.code(),
+        )
+    });
+}
+
+/// An error to report, with the number of errors suppressed because they are
+/// caused by the same problem.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub error: Error,
+    pub suppressed: usize,
+}
+
+impl Diagnostic {
+    pub fn emit(&self, handler: &Handler) {
+        self.error.emit(handler);
+
+        if self.suppressed > 0 {
+            handler.note_without_error(&format!(
+                "{} more errors caused by the same problem were suppressed",
+                self.suppressed
+            ));
+        }
+    }
+}
+
+/// Problems which cause many errors.
+#[derive(PartialEq, Eq, Hash)]
+enum Cause {
+    /// Every usage of a name which cannot be resolved in a file is an error.
+    UnresolvedName(FileName, Id),
+}
+
+/// Removes duplicate errors using [stc_ts_storage::dedup_errors], and reports
+/// each cascade of errors once.
+///
+/// For a cascade, only the first error is kept and the others are counted in
+/// [Diagnostic::suppressed]. The order of `errors` is preserved.
+pub fn dedup_errors(cm: &SourceMap, errors: Vec<Error>) -> Vec<Diagnostic> {
+    let mut causes = FxHashMap::<Cause, usize>::default();
+    let mut diagnostics = Vec::<Diagnostic>::new();
+
+    for error in stc_ts_storage::dedup_errors(errors) {
+        if let Some(cause) = cause_of(cm, &error) {
+            if let Some(&idx) = causes.get(&cause) {
+                diagnostics[idx].suppressed += 1;
+                continue;
+            }
+            causes.insert(cause, diagnostics.len());
+        }
+
+        diagnostics.push(Diagnostic { error, suppressed: 0 });
+    }
+
+    diagnostics
+}
+
+fn cause_of(cm: &SourceMap, error: &Error) -> Option<Cause> {
+    match &**error {
+        ErrorKind::NoSuchVar { span, name }
+        | ErrorKind::NoSuchType { span, name }
+        | ErrorKind::NoSuchVarInLib { span, name, .. }
+        | ErrorKind::NoSuchVarWithSuggestion { span, name, .. } => {
+            if span.is_dummy() {
+                return None;
+            }
+            let file = cm.lookup_char_pos(span.lo).file.name.clone();
+
+            Some(Cause::UnresolvedName(file, name.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// An [Emitter] which writes each diagnostic as a line of JSON, like
+///
+/// ```json
+/// {"file":"/app/a.ts","start":{"line":1,"column":7},"end":{"line":1,"column":8},"code":2322,"category":"error","message":"...","relatedInformation":[]}
+/// ```
+///
+/// Use it with [Handler::with_emitter]. Each line can be parsed as a
+/// [JsonDiagnostic].
+pub struct JsonEmitter {
+    cm: Arc<SourceMap>,
+    writer: Box<dyn Write + Send>,
+}
+
+impl JsonEmitter {
+    pub fn new(cm: Arc<SourceMap>, writer: Box<dyn Write + Send>) -> Self {
+        Self { cm, writer }
+    }
+
+    pub fn stdout(cm: Arc<SourceMap>) -> Self {
+        Self::new(cm, Box::new(std::io::stdout()))
+    }
+
+    fn location(&self, span: Option<Span>) -> (Option<String>, Option<JsonPosition>, Option<JsonPosition>) {
+        let span = match span {
+            Some(span) if !span.is_dummy() => span,
+            _ => return (None, None, None),
+        };
+
+        let start = self.cm.lookup_char_pos(span.lo);
+        let end = self.cm.lookup_char_pos(span.hi);
+
+        (
+            Some(start.file.name.to_string()),
+            Some(JsonPosition {
+                line: start.line,
+                column: start.col.0 + 1,
+            }),
+            Some(JsonPosition {
+                line: end.line,
+                column: end.col.0 + 1,
+            }),
+        )
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let (file, start, end) = self.location(db.span.primary_span());
+
+        let related_information = db
+            .children
+            .iter()
+            .map(|child| {
+                let (file, start, end) = self.location(child.span.primary_span());
+                JsonRelatedInformation {
+                    file,
+                    start,
+                    end,
+                    message: child.message(),
+                }
+            })
+            .collect();
+
+        let diagnostic = JsonDiagnostic {
+            file,
+            start,
+            end,
+            code: match &db.code {
+                Some(DiagnosticId::Error(code)) => code.trim_start_matches("TS").parse().ok(),
+                _ => None,
+            },
+            category: match db.level {
+                Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => JsonCategory::Error,
+                Level::Warning => JsonCategory::Warning,
+                _ => JsonCategory::Message,
+            },
+            message: db.message(),
+            related_information,
+        };
+
+        // Diagnostics cannot be reported if the output is broken.
+        if let Ok(line) = serde_json::to_string(&diagnostic) {
+            let _ = writeln!(self.writer, "{}", line);
+        }
+    }
+}
+
+/// A line written by [JsonEmitter].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiagnostic {
+    /// [None] if the diagnostic is not about a file, like a summary.
+    pub file: Option<String>,
+    pub start: Option<JsonPosition>,
+    pub end: Option<JsonPosition>,
+    /// `2322` for `TS2322`. [None] for errors which `tsc` does not report and
+    /// for messages.
+    pub code: Option<usize>,
+    pub category: JsonCategory,
+    pub message: String,
+    pub related_information: Vec<JsonRelatedInformation>,
+}
+
+/// One-based, like the output of `tsc`. Columns are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JsonCategory {
+    Error,
+    Warning,
+    Message,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonRelatedInformation {
+    pub file: Option<String>,
+    pub start: Option<JsonPosition>,
+    pub end: Option<JsonPosition>,
+    pub message: String,
+}
@@ -0,0 +1,124 @@
+//! `// @ts-ignore` and `// @ts-expect-error`.
+
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_utils::StcComments;
+use swc_common::{
+    comments::{Comment, CommentKind},
+    FileName, SourceMap, Span, Spanned,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectiveKind {
+    Ignore,
+    ExpectError,
+}
+
+#[derive(Debug)]
+struct Directive {
+    kind: DirectiveKind,
+    span: Span,
+    /// Zero-based index of the line the comment ends on.
+    line: usize,
+    used: bool,
+}
+
+/// Removes errors suppressed by a directive comment of `file`, and reports
+/// `@ts-expect-error` directives which did not suppress any error.
+///
+/// Like `tsc`, a directive applies to the next line which is not empty and is
+/// not a line comment.
+pub(crate) fn apply_directives(cm: &SourceMap, comments: &StcComments, file: &FileName, errors: &mut Vec<Error>) {
+    let mut directives = find_directives(cm, comments, file);
+    if directives.is_empty() {
+        return;
+    }
+    directives.sort_by_key(|directive| directive.line);
+
+    errors.retain(|err| {
+        let span = err.span();
+        if span.is_dummy() || err.is_suggestion() {
+            return true;
+        }
+
+        let loc = cm.lookup_char_pos(span.lo);
+        if loc.file.name != *file {
+            return true;
+        }
+
+        let mut line = loc.line - 1;
+        while line > 0 {
+            line -= 1;
+
+            if let Ok(idx) = directives.binary_search_by_key(&line, |directive| directive.line) {
+                directives[idx].used = true;
+                return false;
+            }
+
+            let text = loc.file.get_line(line).unwrap_or_default();
+            let text = text.trim();
+            if !text.is_empty() && !text.starts_with("//") {
+                break;
+            }
+        }
+
+        true
+    });
+
+    errors.extend(
+        directives
+            .iter()
+            .filter(|directive| directive.kind == DirectiveKind::ExpectError && !directive.used)
+            .map(|directive| Error::from(ErrorKind::UnusedTsExpectErrorDirective { span: directive.span })),
+    );
+}
+
+fn find_directives(cm: &SourceMap, comments: &StcComments, file: &FileName) -> Vec<Directive> {
+    let mut directives = vec![];
+
+    for map in [&comments.leading, &comments.trailing] {
+        for entry in map.iter() {
+            for comment in entry.value() {
+                let kind = match parse_directive(comment) {
+                    Some(kind) => kind,
+                    None => continue,
+                };
+
+                let loc = cm.lookup_char_pos(comment.span.hi);
+                if loc.file.name != *file {
+                    continue;
+                }
+
+                directives.push(Directive {
+                    kind,
+                    span: comment.span,
+                    line: loc.line - 1,
+                    used: false,
+                });
+            }
+        }
+    }
+
+    // A comment can be both a trailing comment and a leading comment.
+    directives.sort_by_key(|directive| directive.span.lo);
+    directives.dedup_by_key(|directive| directive.span);
+
+    directives
+}
+
+fn parse_directive(comment: &Comment) -> Option<DirectiveKind> {
+    let text = match comment.kind {
+        // `/// @ts-ignore` is also a directive.
+        CommentKind::Line => comment.text.trim_start_matches('/'),
+        // Only the last line of a block comment is checked.
+        CommentKind::Block => comment.text.lines().last()?.trim_start().trim_start_matches(['/', '*']),
+    };
+    let text = text.trim_start().strip_prefix('@')?;
+
+    if text.starts_with("ts-expect-error") {
+        Some(DirectiveKind::ExpectError)
+    } else if text.starts_with("ts-ignore") {
+        Some(DirectiveKind::Ignore)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,119 @@
+//! Finalization of the exports of a module.
+//!
+//! Types stored in [ModuleTypeData] are shared with every importer, so they
+//! should not be modified after the module is analyzed.
+//!
+//! Modules in a circular group can export each other with `export *` and
+//! `export * as ns`, so namespaces of other modules are not visited. Their
+//! exports are finalized with their own modules.
+
+use fxhash::FxHashSet;
+use rnode::{Visit, VisitWith};
+use stc_ts_types::{Conditional, ModuleTypeData, Type};
+use stc_utils::cache::Freeze;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned, TypeEq};
+use tracing::warn;
+
+/// Makes `data` safe to share with importers.
+///
+/// - Types are frozen, so importers can clone them cheaply.
+/// - Duplicate declarations of a type are removed.
+/// - Types containing a type which is internal to the analyzer are replaced
+///   with `any`. This is a bug of the analyzer, so it's logged instead of being
+///   reported to users.
+pub(crate) fn finalize_exports(data: &mut ModuleTypeData) {
+    for (name, ty) in data.vars.iter_mut() {
+        finalize_type(name, ty);
+    }
+    for (id, ty) in data.private_vars.iter_mut() {
+        finalize_type(id.sym(), ty);
+    }
+
+    for (name, types) in data.types.iter_mut() {
+        finalize_types(name, types);
+    }
+    for (id, types) in data.private_types.iter_mut() {
+        finalize_types(id.sym(), types);
+    }
+}
+
+fn finalize_types(name: &JsWord, types: &mut Vec<Type>) {
+    let mut new: Vec<Type> = Vec::with_capacity(types.len());
+    for mut ty in types.drain(..) {
+        finalize_type(name, &mut ty);
+
+        if new.iter().any(|prev| prev.type_eq(&ty)) {
+            continue;
+        }
+        new.push(ty);
+    }
+    *types = new;
+}
+
+fn finalize_type(name: &JsWord, ty: &mut Type) {
+    let mut finder = PlaceholderFinder::default();
+    ty.visit_with(&mut finder);
+
+    if let Some(span) = finder.found {
+        warn!("exported type `{}` contains a type internal to the analyzer at {:?}", name, span);
+        *ty = Type::any(ty.span(), Default::default());
+    }
+
+    ty.freeze();
+    ty.assert_clone_cheap();
+}
+
+/// Finds types which should not escape from the analyzer.
+#[derive(Default)]
+struct PlaceholderFinder {
+    /// `true` while visiting the `extends` type of a conditional type, where
+    /// `infer` types are allowed.
+    in_extends: bool,
+    /// Frozen types which are already visited, with `in_extends`. They are
+    /// shared, so a type can be reached from many places.
+    visited: FxHashSet<(*const Type, bool)>,
+    found: Option<Span>,
+}
+
+impl Visit<Conditional> for PlaceholderFinder {
+    fn visit(&mut self, ty: &Conditional) {
+        ty.check_type.visit_with(self);
+
+        let old = self.in_extends;
+        self.in_extends = true;
+        ty.extends_type.visit_with(self);
+        self.in_extends = old;
+
+        ty.true_type.visit_with(self);
+        ty.false_type.visit_with(self);
+    }
+}
+
+impl Visit<Type> for PlaceholderFinder {
+    fn visit(&mut self, ty: &Type) {
+        if self.found.is_some() {
+            return;
+        }
+
+        match ty {
+            // Finalized with the module.
+            Type::Module(..) => return,
+            Type::Arc(..) => {
+                if !self.visited.insert((ty.normalize() as *const Type, self.in_extends)) {
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        if let Type::Infer(..) = ty.normalize() {
+            if !self.in_extends {
+                self.found = Some(ty.span());
+                return;
+            }
+        }
+
+        ty.visit_children_with(self);
+    }
+}
@@ -0,0 +1,221 @@
+//! Renders diagnostics for terminals, with code frames like `tsc --pretty`.
+//!
+//! ```text
+//! /app/a.ts:2:1 - error TS2304: ...
+//!
+//! 1 | let a = 1;
+//! 2 | foo;
+//!   | ^^^
+//! 3 | a;
+//! ```
+
+use std::{io::Write, sync::Arc};
+
+use ansi_term::{Color, Style};
+use swc_common::{
+    errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, Emitter, Level},
+    SourceMap, Span,
+};
+
+/// Source lines are not made narrower than this, even if the terminal is.
+const MIN_SOURCE_WIDTH: usize = 20;
+
+/// An [Emitter] which prints diagnostics with code frames.
+///
+/// Lines wider than the terminal are cut around the span, so underlines stay
+/// below the code they point at.
+pub struct PrettyEmitter {
+    cm: Arc<SourceMap>,
+    writer: Box<dyn Write + Send>,
+    color: bool,
+    width: usize,
+    context_lines: usize,
+}
+
+impl PrettyEmitter {
+    /// Without colors, for `80` columns and with one line of context.
+    pub fn new(cm: Arc<SourceMap>, writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            cm,
+            writer,
+            color: false,
+            width: 80,
+            context_lines: 1,
+        }
+    }
+
+    /// Colors are disabled by `NO_COLOR`, and the width is read from
+    /// `COLUMNS`.
+    pub fn stderr(cm: Arc<SourceMap>) -> Self {
+        let width = std::env::var("COLUMNS").ok().and_then(|s| s.parse().ok()).unwrap_or(80);
+
+        Self::new(cm, Box::new(std::io::stderr()))
+            .with_color(std::env::var_os("NO_COLOR").is_none())
+            .with_width(width)
+    }
+
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The width of the terminal, in characters.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The number of lines printed before and after the span.
+    pub fn with_context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+
+        let (level, style) = level_of(diagnostic.level);
+        let span = diagnostic.span.primary_span().filter(|span| !span.is_dummy());
+
+        if let Some(span) = span {
+            out.push_str(&self.paint(Color::Cyan.normal(), &self.location(span)));
+            out.push_str(" - ");
+        }
+        out.push_str(&self.paint(style, level));
+        if let Some(DiagnosticId::Error(code)) = &diagnostic.code {
+            out.push(' ');
+            out.push_str(&self.paint(Color::Fixed(8).normal(), code));
+        }
+        out.push_str(": ");
+        out.push_str(&diagnostic.message());
+        out.push('\n');
+
+        if let Some(span) = span {
+            out.push('\n');
+            self.render_frame(&mut out, span, style);
+        }
+
+        for child in &diagnostic.children {
+            let (level, style) = level_of(child.level);
+
+            match child.span.primary_span().filter(|span| !span.is_dummy()) {
+                Some(span) => {
+                    out.push('\n');
+                    out.push_str("  ");
+                    out.push_str(&self.paint(Color::Cyan.normal(), &self.location(span)));
+                    out.push_str(" - ");
+                    out.push_str(&child.message());
+                    out.push_str("\n\n");
+                    self.render_frame(&mut out, span, style);
+                }
+                None => {
+                    out.push_str("  = ");
+                    out.push_str(&self.paint(style, level));
+                    out.push_str(": ");
+                    out.push_str(&child.message());
+                    out.push('\n');
+                }
+            }
+        }
+
+        out
+    }
+
+    fn location(&self, span: Span) -> String {
+        let loc = self.cm.lookup_char_pos(span.lo);
+
+        format!("{}:{}:{}", loc.file.name, loc.line, loc.col.0 + 1)
+    }
+
+    fn render_frame(&self, out: &mut String, span: Span, style: Style) {
+        let lo = self.cm.lookup_char_pos(span.lo);
+        let hi = self.cm.lookup_char_pos(span.hi);
+        let file = lo.file;
+
+        // Zero-based indices of lines.
+        let first = (lo.line - 1).saturating_sub(self.context_lines);
+        let last = (hi.line - 1 + self.context_lines).min(file.count_lines().saturating_sub(1));
+
+        let gutter = (last + 1).to_string().len();
+        let avail = self.width.saturating_sub(gutter + 3).max(MIN_SOURCE_WIDTH);
+
+        // All lines are cut at the same column, so multi-line underlines line up.
+        let offset = if lo.col.0 < avail * 2 / 3 { 0 } else { lo.col.0 - avail / 3 };
+
+        for idx in first..=last {
+            let chars = match file.get_line(idx) {
+                Some(line) => line.trim_end_matches(['\r', '\n']).replace('\t', " ").chars().collect::<Vec<_>>(),
+                None => continue,
+            };
+            // The empty line after the last line break.
+            if chars.is_empty() && idx + 1 == file.count_lines() {
+                continue;
+            }
+
+            let mut text = chars.iter().skip(offset).take(avail).collect::<String>();
+            if offset > 0 && !text.is_empty() {
+                text.replace_range(..text.chars().next().unwrap().len_utf8(), "…");
+            }
+            if chars.len() > offset + avail {
+                text.pop();
+                text.push('…');
+            }
+
+            out.push_str(&self.paint(Color::Blue.normal(), &format!("{:>width$} |", idx + 1, width = gutter)));
+            if !text.is_empty() {
+                out.push(' ');
+                out.push_str(&text);
+            }
+            out.push('\n');
+
+            let line = idx + 1;
+            if line < lo.line || hi.line < line {
+                continue;
+            }
+
+            let start = if line == lo.line {
+                lo.col.0
+            } else {
+                chars.iter().take_while(|c| c.is_whitespace()).count()
+            };
+            let end = (if line == hi.line { hi.col.0 } else { chars.len() }).max(start + 1);
+
+            let start = start.max(offset) - offset;
+            let end = end.min(offset + avail).saturating_sub(offset);
+            if end <= start {
+                continue;
+            }
+
+            out.push_str(&self.paint(Color::Blue.normal(), &format!("{:>width$} | ", "", width = gutter)));
+            out.push_str(&" ".repeat(start));
+            out.push_str(&self.paint(style, &"^".repeat(end - start)));
+            out.push('\n');
+        }
+    }
+
+    fn paint(&self, style: Style, s: &str) -> String {
+        if self.color {
+            style.paint(s).to_string()
+        } else {
+            s.to_string()
+        }
+    }
+}
+
+impl Emitter for PrettyEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let rendered = self.render(db);
+
+        // Diagnostics cannot be reported if the output is broken.
+        let _ = writeln!(self.writer, "{}", rendered);
+    }
+}
+
+fn level_of(level: Level) -> (&'static str, Style) {
+    match level {
+        Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => ("error", Color::Red.bold()),
+        Level::Warning => ("warning", Color::Yellow.bold()),
+        Level::Help => ("help", Color::Green.bold()),
+        _ => ("note", Color::Fixed(8).bold()),
+    }
+}
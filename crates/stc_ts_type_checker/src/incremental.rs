@@ -0,0 +1,449 @@
+//! Incremental checking.
+//!
+//! [Checker::check_incremental] records the state of a program in a
+//! [BuildInfo], which is persisted between runs like `.tsbuildinfo` of `tsc`.
+//! On the next run, only files affected by changes are checked again, and the
+//! errors of the other files are taken from the previous state.
+//!
+//! A file is affected if
+//!
+//! - its content changed, or
+//! - the signature of a file it depends on changed, or
+//! - a file it depends on was removed, or
+//! - an import which could not be resolved can be resolved now.
+//!
+//! The signature of a file is the hash of its `.d.ts`, so changing the body of
+//! a function does not affect the files importing it.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Error};
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{ModuleConfig, Rule};
+use stc_ts_types::ModuleId;
+use swc_common::{
+    errors::{DiagnosticId, Handler},
+    BytePos, FileName, SourceMap, Span, Spanned,
+};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+
+use crate::{diagnostics::dedup_errors, loader::LoadModule, Checker};
+
+/// A [BuildInfo] stored by another version of stc is ignored.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Returns the hash of the options affecting the result of checking, which is
+/// passed to [Checker::check_incremental].
+pub fn hash_options(rule: &Rule, target: EsVersion, module: &ModuleConfig, libs: &[Lib], lib_dir: Option<&Path>) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("{:?}", rule).as_bytes());
+    hasher.update(format!("{:?}", target).as_bytes());
+    hasher.update(format!("{:?}", module).as_bytes());
+    for lib in libs {
+        hasher.update(lib.name().as_bytes());
+    }
+    if let Some(dir) = lib_dir {
+        hasher.update(dir.to_string_lossy().as_bytes());
+    }
+
+    format!("{:x}", hasher.finalize())
+}
+
+/// Used for content hashes and signatures. This should be stable between
+/// runs and versions of the compiler, so [std::hash::Hash] is not used.
+pub(crate) fn hash_content(content: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(content);
+
+    format!("{:x}", hasher.finalize())
+}
+
+/// The state of a program, stored between runs of incremental checking.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub version: String,
+
+    /// Hash of the options used for checking. If the options change, all
+    /// files are checked again.
+    pub options: String,
+
+    /// Files of the program, including dependencies of root files.
+    pub files: BTreeMap<PathBuf, FileState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileState {
+    /// Hash of the content.
+    pub hash: String,
+
+    /// Hash of the `.d.ts` of the file.
+    pub signature: Option<String>,
+
+    /// Files imported or referenced by this file.
+    pub deps: Vec<PathBuf>,
+
+    /// Imports and references which could not be resolved. The file is
+    /// checked again if any of them can be resolved.
+    #[serde(default)]
+    pub unresolved: Vec<String>,
+
+    pub diagnostics: Vec<StoredDiagnostic>,
+}
+
+/// An error of a file, which can be reported without checking the file again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredDiagnostic {
+    /// See [stc_ts_errors::ErrorKind::code].
+    pub code: usize,
+
+    pub message: String,
+
+    /// Byte offsets of `(lo, hi)` relative to the start of the file. [None]
+    /// if the error does not have a position.
+    pub range: Option<(u32, u32)>,
+
+    /// The number of errors suppressed because they are caused by the same
+    /// problem. See [crate::diagnostics::Diagnostic].
+    pub suppressed: usize,
+}
+
+impl BuildInfo {
+    /// Returns [None] if `path` does not exist or it's stored by another
+    /// version of stc.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read(path).ok()?;
+
+        let info: Self = match serde_json::from_slice(&content) {
+            Ok(v) => v,
+            Err(err) => {
+                log::warn!("Ignoring invalid build info `{}`: {}", path.display(), err);
+                return None;
+            }
+        };
+        if info.version != VERSION {
+            log::debug!("Ignoring build info `{}` of stc {}", path.display(), info.version);
+            return None;
+        }
+
+        Some(info)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        let content = serde_json::to_vec(self).context("failed to serialize build info")?;
+        fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Emits errors of all files, returning the number of them.
+    pub fn emit_diagnostics(&self, cm: &SourceMap, handler: &Handler) -> usize {
+        let mut count = 0;
+        for (file, state) in &self.files {
+            for diagnostic in &state.diagnostics {
+                diagnostic.emit(cm, handler, file);
+            }
+            count += state.diagnostics.len();
+        }
+        count
+    }
+}
+
+impl StoredDiagnostic {
+    /// Returns the span of the error in `file`, loading `file` into `cm` if
+    /// it's not loaded yet.
+    ///
+    /// Returns [None] if the error does not have a position or `file` cannot
+    /// be read.
+    pub fn span(&self, cm: &SourceMap, file: &Path) -> Option<Span> {
+        let (lo, hi) = self.range?;
+        let fm = cm
+            .get_source_file(&FileName::Real(file.to_path_buf()))
+            .or_else(|| cm.load_file(file).ok())?;
+
+        Some(Span::new(
+            fm.start_pos + BytePos(lo),
+            fm.start_pos + BytePos(hi),
+            Default::default(),
+        ))
+    }
+
+    /// Emits the error like [stc_ts_errors::Error::emit].
+    pub fn emit(&self, cm: &SourceMap, handler: &Handler, file: &Path) {
+        let mut err = match self.span(cm, file) {
+            Some(span) => handler.struct_span_err(span, &self.message),
+            None => handler.struct_err(&self.message),
+        };
+        if self.code != 0 {
+            err.code(DiagnosticId::Error(format!("TS{}", self.code)));
+        }
+        err.emit();
+
+        if self.suppressed > 0 {
+            handler.note_without_error(&format!(
+                "{} more errors caused by the same problem were suppressed",
+                self.suppressed
+            ));
+        }
+    }
+}
+
+/// The result of [Checker::check_incremental].
+#[derive(Debug)]
+pub struct IncrementalResult {
+    pub build_info: BuildInfo,
+
+    /// Files checked again, in the order of checking. Dependencies checked
+    /// while checking them are not included.
+    pub checked: Vec<PathBuf>,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the hash of the `.d.ts` of an analyzed module.
+    ///
+    /// This should be called before [Checker::take_dts].
+    pub fn signature(&self, id: ModuleId) -> Option<String> {
+        let module = self.dts_modules.get(&id)?.clone().into_orig();
+
+        let mut buf = vec![];
+        {
+            let mut emitter = Emitter {
+                cfg: swc_ecma_codegen::Config {
+                    minify: true,
+                    ..Default::default()
+                },
+                cm: self.cm.clone(),
+                comments: None,
+                wr: box JsWriter::new(self.cm.clone(), "\n", &mut buf, None),
+            };
+
+            emitter.emit_module(&module).ok()?;
+        }
+
+        Some(hash_content(&buf))
+    }
+
+    /// Checks `root_files`, skipping files which are not affected by changes
+    /// since `previous` is recorded. See [crate::incremental] for details.
+    ///
+    /// `options` should be computed using [hash_options]. Errors
+    /// are stored in [IncrementalResult::build_info] instead of
+    /// [Checker::take_errors], because errors of skipped files should be
+    /// reported too.
+    pub fn check_incremental(&mut self, previous: Option<&BuildInfo>, options: &str, root_files: &[PathBuf]) -> IncrementalResult {
+        let previous = previous.filter(|previous| previous.version == VERSION && previous.options == options);
+
+        let mut files = BTreeMap::<PathBuf, FileState>::new();
+        let mut checked = vec![];
+        let mut queue = VecDeque::new();
+
+        match previous {
+            Some(previous) => {
+                let reachable = reachable(root_files, |file| previous.files.get(file).map(|state| state.deps.clone()));
+
+                queue.extend(root_files.iter().filter(|file| !previous.files.contains_key(*file)).cloned());
+
+                for (file, state) in &previous.files {
+                    if !reachable.contains(file) {
+                        continue;
+                    }
+
+                    match fs::read(file) {
+                        Ok(content) if hash_content(&content) == state.hash => {
+                            if self.resolves_any(file, &state.unresolved) {
+                                queue.push_back(file.clone());
+                            }
+                        }
+                        Ok(..) => queue.push_back(file.clone()),
+                        Err(..) => queue.extend(importers(previous, file)),
+                    }
+                }
+            }
+            None => queue.extend(root_files.iter().cloned()),
+        }
+
+        // Scripts share the global scope, so they are checked first, and again if any
+        // file is checked. See [Checker::check_scripts].
+        if !queue.is_empty() {
+            let scripts = root_files
+                .iter()
+                .filter(|file| self.is_script(&Arc::new(FileName::Real((*file).clone()))))
+                .cloned()
+                .collect::<Vec<_>>();
+            for script in scripts.into_iter().rev() {
+                queue.push_front(script);
+            }
+        }
+
+        while let Some(file) = queue.pop_front() {
+            // Files analyzed while checking another file are up to date.
+            if files.contains_key(&file) || !file.is_file() {
+                continue;
+            }
+
+            log::debug!("Checking `{}` again", file.display());
+            self.check(Arc::new(FileName::Real(file.clone())));
+            checked.push(file.clone());
+
+            for loaded in self.module_loader.loaded_files() {
+                let path = match &*loaded {
+                    FileName::Real(path) => path,
+                    _ => continue,
+                };
+                if files.contains_key(path) {
+                    continue;
+                }
+
+                let id = match self.module_loader.load_module(&loaded, false) {
+                    Ok(records) => records.entry.id,
+                    Err(..) => continue,
+                };
+                // Loaded, but not analyzed yet.
+                if self.get_types(id).is_none() {
+                    continue;
+                }
+
+                let state = FileState {
+                    hash: self.module_loader.content_hash(&loaded).unwrap_or_default(),
+                    signature: self.signature(id),
+                    deps: self
+                        .module_loader
+                        .dependencies(&loaded)
+                        .iter()
+                        .filter_map(|dep| match &**dep {
+                            FileName::Real(path) => Some(path.clone()),
+                            _ => None,
+                        })
+                        .collect(),
+                    unresolved: self.module_loader.unresolved(&loaded),
+                    diagnostics: vec![],
+                };
+
+                if let Some(previous) = previous {
+                    let changed = previous.files.get(path).map_or(true, |old| old.signature != state.signature);
+                    if changed {
+                        queue.extend(importers(previous, path));
+                    }
+                }
+
+                files.insert(path.clone(), state);
+            }
+
+            let errors = self.take_errors();
+            for diagnostic in dedup_errors(&self.cm, errors) {
+                let error = &diagnostic.error;
+                let span = error.span();
+
+                // Errors without a position in a tracked file are stored as errors of `file`.
+                let mut range = None;
+                let mut owner = file.clone();
+                if !span.is_dummy() {
+                    let fm = self.cm.lookup_source_file(span.lo);
+                    if let FileName::Real(path) = &fm.name {
+                        if files.contains_key(path) {
+                            owner = path.clone();
+                            range = Some(((span.lo - fm.start_pos).0, (span.hi - fm.start_pos).0));
+                        }
+                    }
+                }
+
+                let stored = StoredDiagnostic {
+                    code: error.code(),
+                    message: format!("{:#?}", error),
+                    range,
+                    suppressed: diagnostic.suppressed,
+                };
+                if let Some(state) = files.get_mut(&owner) {
+                    state.diagnostics.push(stored);
+                }
+            }
+        }
+
+        // Files which are not checked again keep their previous state.
+        let reachable = reachable(root_files, |file| {
+            files
+                .get(file)
+                .or_else(|| previous?.files.get(file))
+                .map(|state| state.deps.clone())
+        });
+        if let Some(previous) = previous {
+            for file in &reachable {
+                if files.contains_key(file) || !file.is_file() {
+                    continue;
+                }
+                if let Some(state) = previous.files.get(file) {
+                    files.insert(file.clone(), state.clone());
+                }
+            }
+        }
+        files.retain(|file, _| reachable.contains(file));
+
+        IncrementalResult {
+            build_info: BuildInfo {
+                version: VERSION.to_string(),
+                options: options.to_string(),
+                files,
+            },
+            checked,
+        }
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns `true` if any of `specifiers`, which could not be resolved
+    /// from `file` by the previous run, can be resolved now.
+    fn resolves_any(&self, file: &Path, specifiers: &[String]) -> bool {
+        if specifiers.is_empty() {
+            return false;
+        }
+
+        let base = Arc::new(FileName::Real(file.to_path_buf()));
+        specifiers
+            .iter()
+            .any(|specifier| self.module_loader.resolve_dep(&base, specifier).is_ok())
+    }
+}
+
+/// Returns files depending on `file` in `info`.
+fn importers<'a>(info: &'a BuildInfo, file: &'a Path) -> impl 'a + Iterator<Item = PathBuf> {
+    info.files
+        .iter()
+        .filter(move |(_, state)| state.deps.iter().any(|dep| dep == file))
+        .map(|(path, _)| path.clone())
+}
+
+/// Returns `roots` and the files they depend on, transitively.
+fn reachable(roots: &[PathBuf], deps: impl Fn(&Path) -> Option<Vec<PathBuf>>) -> FxHashSet<PathBuf> {
+    let mut done = FxHashSet::default();
+    let mut stack = roots.to_vec();
+
+    while let Some(file) = stack.pop() {
+        if done.contains(&file) {
+            continue;
+        }
+
+        stack.extend(deps(&file).unwrap_or_default());
+        done.insert(file);
+    }
+
+    done
+}
@@ -1,30 +1,58 @@
 //! Full type checker with dependency support.
 #![feature(box_syntax)]
 
-use std::{mem::take, sync::Arc, time::Instant};
+use std::{mem::take, path::PathBuf, sync::Arc};
 
 use dashmap::{DashMap, DashSet, SharedValue};
 use fxhash::{FxBuildHasher, FxHashMap};
-use loader::LoadModule;
+use instant::Instant;
+use loader::{LoadModule, ModuleRecord};
 use once_cell::sync::OnceCell;
 use parking_lot::{Mutex, RwLock};
 use rnode::{NodeIdGenerator, RNode, VisitWith};
-use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
+use static_assertions::assert_impl_all;
+use stc_ts_ast_rnode::{RIdent, RModule, RStr, RTsModuleName};
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
 use stc_ts_env::Env;
-use stc_ts_errors::{debug::debugger::Debugger, Error};
-use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
-use stc_ts_storage::{ErrorStore, File, Group, Single};
+use stc_ts_errors::{debug::debugger::Debugger, Error, ErrorKind};
+use stc_ts_file_analyzer::{
+    analyzer::Analyzer, cancellation::CancellationToken, loader::Load, validator::ValidateWith, ModuleTypeData, VResult,
+};
+use stc_ts_module_loader::resolvers::{node::NodeResolver, ResolutionError};
+pub use stc_ts_storage::limit::{ErrorLimit, ErrorSummary};
+use stc_ts_storage::{limit::ErrorCounter, ErrorStore, File, Group, Single, Storage};
 use stc_ts_types::{ModuleId, Type};
+use stc_ts_utils::StcComments;
 use stc_utils::{cache::Freeze, early_error};
 use swc_atoms::JsWord;
-use swc_common::{errors::Handler, FileName, SourceMap, Spanned, DUMMY_SP};
-use swc_ecma_ast::Module;
+use swc_common::{errors::Handler, FileName, SourceMap, Span, Spanned, DUMMY_SP};
+use swc_ecma_ast::{Module, ModuleDecl, ModuleItem};
 use tracing::{info, warn};
 
+use crate::{
+    bundler::{BundlerHooks, ExportUsageMap},
+    diagnostics::{sort_errors, sort_errors_by_file, DiagnosticsSink, SinkDiagnostic},
+    directives::apply_directives,
+    exports::finalize_exports,
+};
+
+pub mod baseline;
+pub mod bundler;
+pub mod diagnostics;
+mod directives;
+mod exports;
+pub mod formatter;
+pub mod incremental;
 pub mod loader;
+pub mod project;
+pub mod source;
 mod typings;
 
+// Bindings move these between threads, like the worker threads of Node.js.
+assert_impl_all!(Checker<loader::ModuleLoader<NodeResolver>>: Send, Sync);
+assert_impl_all!(incremental::BuildInfo: Send, Sync);
+assert_impl_all!(source::CheckOptions: Send, Sync);
+
 /// Onc instance per swc::Compiler
 pub struct Checker<L>
 where
@@ -37,6 +65,9 @@ where
 
     declared_modules: RwLock<Vec<(ModuleId, Type)>>,
 
+    /// `declare module "foo" {}` in modules, keyed by the augmented module.
+    augmentations: RwLock<FxHashMap<ModuleId, Vec<Type>>>,
+
     /// Information required to generate `.d.ts` files.
     dts_modules: Arc<DashMap<ModuleId, RModule, FxBuildHasher>>,
 
@@ -50,6 +81,24 @@ where
     env: Env,
 
     debugger: Option<Debugger>,
+
+    bundler_hooks: Option<Arc<dyn BundlerHooks>>,
+
+    /// Used only if `bundler_hooks` is [Some].
+    export_usage: Mutex<ExportUsageMap>,
+
+    diagnostics_sink: Option<Arc<dyn DiagnosticsSink>>,
+
+    cancellation: Option<CancellationToken>,
+
+    /// The first name used to import each file, keyed by canonical names.
+    ///
+    /// Used for `forceConsistentCasingInFileNames`.
+    file_names: DashMap<Arc<FileName>, Arc<FileName>, FxBuildHasher>,
+
+    error_counter: ErrorCounter,
+
+    created_at: Instant,
 }
 
 impl<L> Checker<L>
@@ -67,9 +116,47 @@ where
             errors: Default::default(),
             debugger,
             declared_modules: Default::default(),
+            augmentations: Default::default(),
             module_loader,
+            bundler_hooks: None,
+            export_usage: Default::default(),
+            diagnostics_sink: None,
+            cancellation: None,
+            file_names: Default::default(),
+            error_counter: Default::default(),
+            created_at: Instant::now(),
         }
     }
+
+    /// Registers callbacks for bundlers. See [bundler] for details.
+    pub fn with_bundler_hooks(mut self, hooks: Arc<dyn BundlerHooks>) -> Self {
+        self.bundler_hooks = Some(hooks);
+        self
+    }
+
+    /// Registers a sink which receives errors of each module as soon as the
+    /// module is checked. See [diagnostics] for details.
+    pub fn with_diagnostics_sink(mut self, sink: Arc<dyn DiagnosticsSink>) -> Self {
+        self.diagnostics_sink = Some(sink);
+        self
+    }
+
+    /// Stops checking when `token` is cancelled.
+    ///
+    /// Modules which are not analyzed yet are skipped, and the analysis of
+    /// modules in progress stops at the next statement. Results of a
+    /// cancelled checker are incomplete, so it should be dropped.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Stops reporting errors of a file, or of the program, once `limit` is
+    /// reached. See [ErrorCounter::on_file_checked] for details.
+    pub fn with_error_limit(mut self, limit: ErrorLimit) -> Self {
+        self.error_counter = ErrorCounter::new(limit);
+        self
+    }
 }
 
 impl<L> Checker<L>
@@ -107,11 +194,155 @@ where
         let end = Instant::now();
         log::debug!("Analysis of `{}` and dependencies took {:?}", entry, end - start);
 
+        if let Some(hooks) = &self.bundler_hooks {
+            let usage = take(&mut *self.export_usage.lock());
+            for (file, usage) in usage {
+                hooks.on_export_usage(&file, &usage);
+            }
+        }
+
         modules.entry.id
     }
 
+    /// Checks scripts of `files`, which are files without imports and exports,
+    /// in the order of `files`.
+    ///
+    /// Scripts share the global scope, so this should be called before
+    /// checking files of a program with [Checker::check]. Otherwise, a file
+    /// cannot use globals declared by scripts which are not checked yet.
+    pub fn check_scripts(&self, files: &[PathBuf]) {
+        for file in files {
+            let file = Arc::new(FileName::Real(file.clone()));
+            if self.is_script(&file) {
+                self.check(file);
+            }
+        }
+    }
+
+    fn is_script(&self, file: &Arc<FileName>) -> bool {
+        match self.module_loader.load_module(file, true) {
+            Ok(records) => records.entry.ast.body.iter().all(|item| matches!(item, ModuleItem::Stmt(..))),
+            Err(..) => false,
+        }
+    }
+
+    /// Classifies imports of `record` and passes them to the bundler hooks.
+    ///
+    /// Should be called after the module is analyzed.
+    fn report_imports_to_bundler(&self, record: &ModuleRecord) {
+        let hooks = match &self.bundler_hooks {
+            Some(v) => v,
+            None => return,
+        };
+
+        let imports = bundler::classify_imports(&record.ast, |src| {
+            let dep = match self.module_loader.load_dep(&record.filename, src) {
+                Ok(v) => v.entry,
+                Err(..) => return (None, None),
+            };
+
+            let values = self.get_types(dep.id).and_then(|ty| match ty.normalize() {
+                Type::Module(m) => Some(m.exports.vars.keys().cloned().collect()),
+                _ => None,
+            });
+
+            (Some(dep.filename.clone()), values)
+        });
+
+        {
+            let mut usage = self.export_usage.lock();
+            for import in &imports {
+                if let Some(file) = &import.resolved {
+                    usage.entry(file.clone()).or_default().add(import);
+                }
+            }
+        }
+
+        hooks.on_imports(&record.filename, &imports);
+    }
+
+    /// Returns errors sorted by [sort_errors_by_file], so the order does not
+    /// depend on the order in which modules are checked.
     pub fn take_errors(&mut self) -> Vec<Error> {
-        take(self.errors.get_mut())
+        let mut errors = take(self.errors.get_mut());
+        sort_errors_by_file(&self.cm, &mut errors);
+        errors
+    }
+
+    /// Returns the number of checked files and reported errors, and the time
+    /// elapsed since the checker was created.
+    pub fn summary(&self) -> ErrorSummary {
+        self.error_counter.summary(self.created_at.elapsed())
+    }
+
+    /// Sorts errors of a checked module, applies the error limit and passes
+    /// them to the diagnostics sink.
+    fn on_module_checked(&self, file: &Arc<FileName>, comments: &StcComments, errors: &mut Vec<Error>) {
+        errors.retain(|err| !err.is_cancelled());
+        apply_directives(&self.cm, comments, file, errors);
+        // Suggestions are only for editors, which use a sink.
+        if self.diagnostics_sink.is_none() {
+            errors.retain(|err| !err.is_suggestion());
+        }
+        sort_errors(errors);
+
+        if let Some(sink) = &self.diagnostics_sink {
+            errors.retain(|err| sink.filter(file, err));
+        }
+        self.error_counter.on_file_checked(errors);
+
+        if let Some(sink) = &self.diagnostics_sink {
+            let diagnostics = errors
+                .iter()
+                .map(|err| SinkDiagnostic {
+                    error: err.clone(),
+                    severity: sink.severity(err),
+                })
+                .collect::<Vec<_>>();
+            sink.on_module_checked(file, &diagnostics);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation.as_ref().map_or(false, |token| token.is_cancelled())
+            || self.diagnostics_sink.as_ref().map_or(false, |sink| sink.is_cancelled())
+    }
+
+    fn new_analyzer<'b>(&'b self, comments: StcComments, storage: Storage<'b>) -> Analyzer<'b, 'b> {
+        let analyzer = Analyzer::root(self.env.clone(), self.cm.clone(), comments, storage, self, self.debugger.clone());
+
+        match &self.cancellation {
+            Some(token) => analyzer.with_cancellation_token(token.clone()),
+            None => analyzer,
+        }
+    }
+
+    /// Splits errors of a circular group by file, and reports them using
+    /// [Self::on_module_checked].
+    ///
+    /// Errors without a position are reported as errors of `entry`.
+    fn on_group_checked(&self, entry: &Arc<FileName>, records: &[Arc<ModuleRecord>], comments: &StcComments, errors: &mut Vec<Error>) {
+        let mut by_file = records.iter().map(|record| (record.filename.clone(), vec![])).collect::<Vec<_>>();
+
+        for err in take(errors) {
+            let span = err.span();
+            let idx = if span.is_dummy() {
+                None
+            } else {
+                let file = self.cm.lookup_source_file(span.lo);
+                by_file.iter().position(|(filename, _)| **filename == file.name)
+            };
+            let idx = idx
+                .or_else(|| by_file.iter().position(|(filename, _)| filename == entry))
+                .unwrap_or(0);
+
+            by_file[idx].1.push(err);
+        }
+
+        for (filename, mut file_errors) in by_file {
+            self.on_module_checked(&filename, comments, &mut file_errors);
+            errors.extend(file_errors);
+        }
     }
 
     /// Analyzes one module.
@@ -130,6 +361,11 @@ where
             }
         }
 
+        if self.is_cancelled() {
+            info!("Skipping {} because checking is cancelled", path);
+            return Type::any(DUMMY_SP, Default::default());
+        }
+
         let is_first_run = self.started.insert(id);
 
         if is_first_run && modules_in_group.modules.len() > 1 {
@@ -171,14 +407,12 @@ where
                     .collect::<Vec<_>>();
                 let mut mutations;
                 {
-                    let mut a = Analyzer::root(
-                        self.env.clone(),
-                        self.cm.clone(),
-                        modules_in_group.comments.clone(),
-                        box &mut storage,
-                        self,
-                        self.debugger.clone(),
-                    );
+                    // Declarations of the previous check are replaced.
+                    for record in modules_in_group.modules.iter() {
+                        self.env.clear_script_globals(&record.filename);
+                    }
+
+                    let mut a = self.new_analyzer(modules_in_group.comments.clone(), box &mut storage);
                     let _ = modules.validate_with(&mut a);
                     mutations = a.mutations.unwrap();
                 }
@@ -198,8 +432,19 @@ where
                 }
 
                 {
+                    let mut errors: Vec<Error> = storage.take_errors().into();
+                    for data in storage.info.values_mut() {
+                        finalize_exports(data);
+                    }
+                    for record in modules_in_group.modules.iter() {
+                        if let Some(data) = storage.info.get(&record.id) {
+                            self.declare_umd_global(record, data);
+                        }
+                    }
+                    self.on_group_checked(&path, &modules_in_group.modules, &modules_in_group.comments, &mut errors);
+
                     let mut lock = self.errors.lock();
-                    lock.extend(storage.take_errors());
+                    lock.extend(errors);
                 }
                 {
                     let mut lock = self.module_types.write();
@@ -228,6 +473,10 @@ where
                 }
             }
 
+            for record in modules_in_group.modules.iter() {
+                self.report_imports_to_bundler(record);
+            }
+
             let lock = self.module_types.read();
             return lock.get(&id).and_then(|cell| cell.get().cloned()).unwrap();
         }
@@ -261,6 +510,46 @@ where
         }
     }
 
+    /// Declares `Foo` of `export as namespace Foo` as a global, using the
+    /// exports of the module.
+    fn declare_umd_global(&self, record: &ModuleRecord, exports: &ModuleTypeData) {
+        let name = record.ast.body.iter().find_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::TsNamespaceExport(decl)) => Some(decl.id.clone()),
+            _ => None,
+        });
+        let name = match name {
+            Some(v) => v,
+            None => return,
+        };
+
+        let (var, ty) = match &exports.export_equals {
+            Some(export) => (
+                export.var.clone(),
+                export
+                    .types
+                    .iter()
+                    .find(|ty| ty.is_module() || ty.is_namespace())
+                    .or_else(|| export.types.first())
+                    .cloned(),
+            ),
+            None => {
+                let module = Type::Module(stc_ts_types::Module {
+                    span: name.span,
+                    name: RTsModuleName::Ident(RIdent::new(name.sym.clone(), name.span)),
+                    exports: box exports.clone(),
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                })
+                .freezed();
+
+                (Some(module.clone()), Some(module))
+            }
+        };
+
+        info!("Declaring UMD global `{}` of {}", name.sym, record.filename);
+        self.env.clone().declare_umd_global(name.sym, var, ty);
+    }
+
     fn analyze_non_circular_module(&self, module_id: ModuleId, path: Arc<FileName>) -> Type {
         let start = Instant::now();
 
@@ -292,14 +581,10 @@ where
         let mut mutations;
         {
             let start = Instant::now();
-            let mut a = Analyzer::root(
-                self.env.clone(),
-                self.cm.clone(),
-                records.comments,
-                box &mut storage,
-                self,
-                self.debugger.clone(),
-            );
+            // Declarations of the previous check are replaced.
+            self.env.clear_script_globals(&path);
+
+            let mut a = self.new_analyzer(records.comments.clone(), box &mut storage);
 
             module.visit_with(&mut a);
 
@@ -316,13 +601,17 @@ where
             cleanup_module_for_dts(&mut module.body, &storage.info.exports);
         }
 
+        let mut errors: Vec<Error> = storage.take_errors().into();
+        finalize_exports(&mut storage.info.exports);
+        self.declare_umd_global(&record, &storage.info.exports);
+        self.on_module_checked(&path, &records.comments, &mut errors);
+
         if early_error() {
-            for err in storage.info.errors {
+            for err in errors {
                 self.handler.struct_span_err(err.span(), &format!("{:?}", err)).emit();
             }
         } else {
-            let mut errors = self.errors.lock();
-            errors.extend(storage.info.errors);
+            self.errors.lock().extend(errors);
         }
 
         let type_info = Type::Module(stc_ts_types::Module {
@@ -340,6 +629,8 @@ where
 
         self.dts_modules.insert(module_id, module);
 
+        self.report_imports_to_bundler(&record);
+
         let dur = Instant::now() - start;
         log::trace!("[Timing] Full analysis of {} took {:?}", path, dur);
 
@@ -356,6 +647,65 @@ where
         Some(records.entry.id)
     }
 
+    fn module_not_found_error(&self, span: Span, base: &Arc<FileName>, module_specifier: &str) -> Error {
+        let err = match self.module_loader.load_dep(base, module_specifier) {
+            Ok(..) => return ErrorKind::ModuleNotFound { span }.into(),
+            Err(err) => err,
+        };
+
+        match err.chain().find_map(|err| err.downcast_ref::<ResolutionError>()) {
+            Some(ResolutionError::ExtensionRequired {
+                suggestion: Some(suggestion),
+            }) => ErrorKind::ImportPathWithoutExtensionWithSuggestion {
+                span,
+                suggestion: suggestion.as_str().into(),
+            }
+            .into(),
+            Some(ResolutionError::ExtensionRequired { suggestion: None }) => ErrorKind::ImportPathWithoutExtension { span }.into(),
+            Some(ResolutionError::PathMappingNotFound { pattern }) => ErrorKind::PathMappingTargetNotFound {
+                span,
+                pattern: pattern.as_str().into(),
+            }
+            .into(),
+            Some(ResolutionError::OutputNotBuilt { output, source }) => ErrorKind::OutputFileNotBuilt {
+                span,
+                output: output.as_str().into(),
+                source: source.as_str().into(),
+            }
+            .into(),
+            None => ErrorKind::ModuleNotFound { span }.into(),
+        }
+    }
+
+    fn file_name_casing_error(&self, span: Span, base: &Arc<FileName>, module_specifier: &str) -> Option<Error> {
+        let (name, canonical) = self.module_loader.resolve_dep(base, module_specifier).ok()?;
+
+        let included = self.file_names.entry(canonical.clone()).or_insert_with(|| name.clone()).clone();
+        if included != name {
+            return Some(
+                ErrorKind::FileNameDiffersOnlyInCasing {
+                    span,
+                    name: name.to_string().into(),
+                    included: included.to_string().into(),
+                }
+                .into(),
+            );
+        }
+
+        if name != canonical {
+            return Some(
+                ErrorKind::FileNameDiffersFromDiskOnlyInCasing {
+                    span,
+                    name: name.to_string().into(),
+                    real: canonical.to_string().into(),
+                }
+                .into(),
+            );
+        }
+
+        None
+    }
+
     fn is_in_same_circular_group(&self, base: &Arc<FileName>, module_specifier: &str) -> bool {
         let records = self.module_loader.load_dep(base, module_specifier).ok();
 
@@ -394,4 +744,20 @@ where
         info!("Declaring module with type `{}`", name);
         self.declared_modules.write().push((module_id, module));
     }
+
+    fn augment_module(&self, base: &Arc<FileName>, src: &str, augmentation: Type) {
+        augmentation.assert_clone_cheap();
+
+        let module_id = match self.module_loader.load_dep(base, src) {
+            Ok(records) => records.entry.id,
+            Err(..) => return,
+        };
+
+        info!("Augmenting module `{}` from `{}`", src, base);
+        self.augmentations.write().entry(module_id).or_default().push(augmentation);
+    }
+
+    fn module_augmentations(&self, module_id: ModuleId) -> Vec<Type> {
+        self.augmentations.read().get(&module_id).cloned().unwrap_or_default()
+    }
 }
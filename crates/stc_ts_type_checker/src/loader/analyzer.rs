@@ -38,7 +38,7 @@ where
     fn check_comments(&mut self, span: Span) {
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.references.extend(deps.into_iter().map(|i| i.to_path()));
+        self.references.extend(deps.into_iter().filter_map(|(_, i)| i.to_path()));
     }
 }
 
@@ -76,6 +76,12 @@ where
         self.deps.push(import.expr.value.clone());
     }
 
+    fn visit_ts_import_type(&mut self, import: &TsImportType) {
+        import.visit_children_with(self);
+
+        self.deps.push(import.arg.value.clone());
+    }
+
     fn visit_ts_module_decl(&mut self, n: &TsModuleDecl) {
         n.visit_children_with(self);
 
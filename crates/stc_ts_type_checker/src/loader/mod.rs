@@ -1,22 +1,31 @@
-use std::sync::{Arc, Mutex, RwLock};
+use std::{
+    fs::read_dir,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
 
 use anyhow::{bail, Context, Result};
 use auto_impl::auto_impl;
 use dashmap::{DashMap, DashSet};
 use fxhash::FxBuildHasher;
-use petgraph::algo::kosaraju_scc;
+use petgraph::{algo::kosaraju_scc, EdgeDirection::Outgoing};
+#[cfg(not(feature = "no-threading"))]
 use rayon::prelude::*;
 use stc_ts_env::Env;
 use stc_ts_types::{module_id::ModuleIdGenerator, ModuleId};
 use stc_ts_utils::StcComments;
-use swc_common::{FileName, SourceMap, Span, SyntaxContext, GLOBALS};
-use swc_ecma_ast::{EsVersion, Module};
+use swc_common::{FileName, SourceFile, SourceMap, Span, SyntaxContext, DUMMY_SP, GLOBALS};
+use swc_ecma_ast::{
+    BindingIdent, Decl, EsVersion, Expr, Ident, Module, ModuleDecl, ModuleItem, Pat, Stmt, TsExportAssignment, VarDecl, VarDeclKind,
+    VarDeclarator,
+};
 use swc_ecma_loader::resolve::Resolve;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_visit::VisitMutWith;
 use swc_fast_graph::digraph::FastDiGraphMap;
 
 use self::analyzer::find_modules_and_deps;
+use crate::incremental::hash_content;
 
 mod analyzer;
 pub mod store;
@@ -53,6 +62,24 @@ pub trait LoadModule: 'static + Send + Sync {
 
     /// Same constraints for [`LoadModule::load_module`] applies.
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records>;
+
+    /// Returns `(name, canonical name)` of a dependency.
+    ///
+    /// `name` is the result of the resolver, and the canonical name uses the
+    /// casing of the file system. Modules are identified by canonical names.
+    fn resolve_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<(Arc<FileName>, Arc<FileName>)>;
+
+    /// Returns the hash of the content of a loaded file.
+    ///
+    /// Used to detect changes between runs of incremental checking.
+    fn content_hash(&self, filename: &Arc<FileName>) -> Option<String>;
+
+    /// Returns the files imported or referenced by a loaded file.
+    fn dependencies(&self, filename: &Arc<FileName>) -> Vec<Arc<FileName>>;
+
+    /// Returns the imports and references of a loaded file which could not be
+    /// resolved.
+    fn unresolved(&self, filename: &Arc<FileName>) -> Vec<String>;
 }
 
 /// A simple implementation of [LoadModule].
@@ -73,6 +100,12 @@ where
     ids: ModuleIdGenerator,
     parse_cache: DashMap<Arc<FileName>, (Arc<ModuleRecord>, StcComments), FxBuildHasher>,
     parsing_errors: Mutex<Vec<swc_ecma_parser::error::Error>>,
+    /// Hashes of the content of loaded files.
+    content_hashes: DashMap<Arc<FileName>, String, FxBuildHasher>,
+    /// Module specifiers which could not be resolved, by the importing file.
+    unresolved: DashMap<Arc<FileName>, Vec<String>, FxBuildHasher>,
+    /// Cache for [real_case_path].
+    real_paths: DashMap<PathBuf, PathBuf, FxBuildHasher>,
 }
 
 impl<R> ModuleLoader<R>
@@ -92,9 +125,44 @@ where
             parse_cache: Default::default(),
             ids: Default::default(),
             parsing_errors: Default::default(),
+            content_hashes: Default::default(),
+            unresolved: Default::default(),
+            real_paths: Default::default(),
         }
     }
 
+    /// Returns the files loaded so far, including dependencies of entries.
+    pub fn loaded_files(&self) -> Vec<Arc<FileName>> {
+        self.loading_started.iter().map(|name| name.key().clone()).collect()
+    }
+
+    /// Resolves `module_specifier`, returning `(name, canonical name)`.
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<(Arc<FileName>, Arc<FileName>)> {
+        let name = self
+            .resolver
+            .resolve(base, module_specifier)
+            .with_context(|| format!("failed to resolve `{}` from `{}`", module_specifier, base))?;
+
+        let canonical = match &name {
+            FileName::Real(path) => {
+                let cached = self.real_paths.get(path).map(|real| real.clone());
+                let real = match cached {
+                    Some(real) => real,
+                    None => {
+                        let real = real_case_path(path);
+                        self.real_paths.insert(path.clone(), real.clone());
+                        real
+                    }
+                };
+
+                FileName::Real(real)
+            }
+            _ => name.clone(),
+        };
+
+        Ok((Arc::new(name), Arc::new(canonical)))
+    }
+
     fn load_recursively(&self, filename: &Arc<FileName>, calc_cycles: bool) -> Result<ModuleId> {
         let (id, _) = self.ids.generate(filename);
 
@@ -107,17 +175,23 @@ where
 
         let (_declared_modules, references, deps) = find_modules_and_deps(&comments, &entry.ast);
 
-        let deps = GLOBALS.with(|globals| {
-            (references.par_iter().map(|v| (v, false)))
-                .chain(deps.par_iter().map(|v| (v, true)))
-                .map(|(dep, is_normal_dep)| {
-                    GLOBALS.set(globals, || {
-                        let dep_path = Arc::new(self.resolver.resolve(filename, dep)?);
+        #[cfg(feature = "no-threading")]
+        let iter = (references.iter().map(|v| (v, false))).chain(deps.iter().map(|v| (v, true)));
+        #[cfg(not(feature = "no-threading"))]
+        let iter = (references.par_iter().map(|v| (v, false))).chain(deps.par_iter().map(|v| (v, true)));
 
-                        self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
-                    })
+        let deps = GLOBALS.with(|globals| {
+            iter.map(|(dep, is_normal_dep)| {
+                GLOBALS.set(globals, || {
+                    let (_, dep_path) = self.resolve(filename, dep).map_err(|err| {
+                        self.unresolved.entry(filename.clone()).or_default().push(dep.to_string());
+                        err
+                    })?;
+
+                    self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
                 })
-                .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
         });
 
         {
@@ -169,20 +243,22 @@ where
     fn parse_inner(&self, filename: &Arc<FileName>) -> Result<(Arc<ModuleRecord>, StcComments)> {
         let comments = self.comments.clone();
 
-        let (fm, syntax) = match &**filename {
+        let (fm, syntax, is_json) = match &**filename {
             FileName::Real(path) => {
                 let fm = self
                     .cm
                     .load_file(path)
                     .with_context(|| format!("failed to load module `{}`", path.display()))?;
+                self.content_hashes.insert(filename.clone(), hash_content(fm.src.as_bytes()));
 
                 let syntax = TsConfig {
-                    dts: path.as_os_str().to_string_lossy().ends_with(".d.ts"),
+                    dts: is_dts_path(path),
                     tsx: path.extension().map(|v| v == "tsx").unwrap_or(false),
                     ..Default::default()
                 };
+                let is_json = path.extension().map(|v| v == "json").unwrap_or(false);
 
-                (fm, syntax)
+                (fm, syntax, is_json)
             }
 
             FileName::Custom(..) => {
@@ -219,7 +295,11 @@ where
         );
 
         let mut parser = Parser::new_from(lexer);
-        let result = parser.parse_module();
+        let result = if is_json {
+            parser.parse_expr().map(|expr| json_module(&fm, expr))
+        } else {
+            parser.parse_module()
+        };
 
         let mut ast = match result {
             Ok(v) => v,
@@ -258,6 +338,85 @@ where
     }
 }
 
+/// Returns `path` using the casing of the file system.
+///
+/// On case-insensitive file systems, `path` may refer to a file using a
+/// different casing. Components which do not exist are kept as-is.
+fn real_case_path(path: &Path) -> PathBuf {
+    let mut real = PathBuf::new();
+
+    for component in path.components() {
+        let name = match component {
+            Component::Normal(name) => name,
+            _ => {
+                real.push(component);
+                continue;
+            }
+        };
+
+        let dir = if real.as_os_str().is_empty() { Path::new(".") } else { &real };
+        let found = read_dir(dir).ok().and_then(|entries| {
+            let mut case_insensitive = None;
+            for entry in entries.flatten() {
+                let entry_name = entry.file_name();
+                if entry_name == name {
+                    return Some(entry_name);
+                }
+                if case_insensitive.is_none() && entry_name.to_string_lossy().eq_ignore_ascii_case(&name.to_string_lossy()) {
+                    case_insensitive = Some(entry_name);
+                }
+            }
+            case_insensitive
+        });
+
+        match found {
+            Some(found) => real.push(found),
+            None => real.push(name),
+        }
+    }
+
+    real
+}
+
+/// `.d.ts`, `.d.mts` and `.d.cts`
+pub(crate) fn is_dts_path(path: &Path) -> bool {
+    let name = path.as_os_str().to_string_lossy();
+
+    name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts")
+}
+
+/// Creates a module for a JSON file, which is typed like
+///
+/// ```ts
+/// const _default = { /* content of the JSON file */ };
+/// export = _default;
+/// ```
+fn json_module(fm: &SourceFile, expr: Box<Expr>) -> Module {
+    let id = Ident::new("_default".into(), DUMMY_SP);
+
+    Module {
+        span: Span::new(fm.start_pos, fm.end_pos, Default::default()),
+        body: vec![
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(BindingIdent::from(id.clone())),
+                    init: Some(expr),
+                    definite: false,
+                }],
+            })))),
+            ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(TsExportAssignment {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Ident(id)),
+            })),
+        ],
+        shebang: None,
+    }
+}
+
 impl<R> LoadModule for ModuleLoader<R>
 where
     R: 'static + Sync + Send + Resolve,
@@ -299,11 +458,34 @@ where
     }
 
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records> {
-        let filename = self
-            .resolver
-            .resolve(base, module_specifier)
-            .with_context(|| format!("failed to resolve `{}` from `{}`", module_specifier, base))?;
+        let (_, filename) = self.resolve(base, module_specifier)?;
+
+        self.load_module(&filename, false)
+    }
+
+    fn resolve_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<(Arc<FileName>, Arc<FileName>)> {
+        self.resolve(base, module_specifier)
+    }
+
+    fn content_hash(&self, filename: &Arc<FileName>) -> Option<String> {
+        self.content_hashes.get(filename).map(|hash| hash.clone())
+    }
+
+    fn dependencies(&self, filename: &Arc<FileName>) -> Vec<Arc<FileName>> {
+        let (id, _) = self.ids.generate(filename);
+
+        let g = self.dep_graph.read().unwrap();
+        if !g.contains_node(id) {
+            return vec![];
+        }
+
+        g.neighbors_directed(id, Outgoing).map(|dep| self.ids.path(dep)).collect()
+    }
 
-        self.load_module(&Arc::new(filename), false)
+    fn unresolved(&self, filename: &Arc<FileName>) -> Vec<String> {
+        self.unresolved
+            .get(filename)
+            .map(|specifiers| specifiers.clone())
+            .unwrap_or_default()
     }
 }
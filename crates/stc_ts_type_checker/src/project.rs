@@ -0,0 +1,113 @@
+//! Creating a [Checker] for a project, which is shared by the commands and the
+//! bindings.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Error};
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::TsConfig;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::{paths::PathsResolver, ModuleResolver};
+use swc_common::{errors::Handler, SourceMap};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_loader::resolve::Resolve;
+
+use crate::{loader::ModuleLoader, Checker};
+
+/// Options used to create a [Checker] for a project. See
+/// [ProjectOptions::checker].
+#[derive(Debug, Clone)]
+pub struct ProjectOptions<'a> {
+    pub rule: Rule,
+
+    pub target: EsVersion,
+
+    pub module: ModuleConfig,
+
+    pub libs: &'a [Lib],
+
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc.
+    pub lib_dir: Option<&'a Path>,
+
+    /// The directory `paths` are resolved against if `base_url` is [None].
+    pub paths_dir: PathBuf,
+
+    pub base_url: Option<PathBuf>,
+
+    pub paths: Vec<(String, Vec<String>)>,
+
+    /// Typing libraries are searched from the ancestors of this path. See
+    /// [Checker::load_typings].
+    pub base: &'a Path,
+
+    pub type_roots: Option<&'a [PathBuf]>,
+
+    pub types: Option<&'a [String]>,
+}
+
+impl<'a> ProjectOptions<'a> {
+    /// Uses the options of `config`. `libs` should be
+    /// [TsConfig::libs_or_default].
+    pub fn from_config(config: &'a TsConfig, libs: &'a [Lib], lib_dir: Option<&'a Path>) -> Self {
+        Self {
+            rule: config.rule,
+            target: config.target,
+            module: config.module.clone(),
+            libs,
+            lib_dir,
+            paths_dir: config.paths_dir.clone(),
+            base_url: config.base_url.clone(),
+            paths: config.paths.clone(),
+            base: &config.path,
+            type_roots: config.type_roots.as_deref(),
+            types: config.types.as_deref(),
+        }
+    }
+
+    /// Creates a [Checker] and loads the typing libraries.
+    ///
+    /// `resolver` can wrap the resolver for `paths`, like
+    /// [stc_ts_module_loader::resolvers::references::ProjectReferencesResolver].
+    ///
+    /// Errors in typing libraries are not reported, but missing libraries
+    /// are. They are returned instead of being stored in the checker.
+    pub fn checker<R, F>(
+        self,
+        cm: Arc<SourceMap>,
+        handler: Arc<Handler>,
+        resolver: F,
+    ) -> Result<(Checker<ModuleLoader<R>>, Vec<stc_ts_errors::Error>), Error>
+    where
+        R: 'static + Sync + Send + Resolve,
+        F: FnOnce(PathsResolver<ModuleResolver>) -> R,
+    {
+        // Builtin libraries are cached, so they are parsed only once.
+        let env = match self.lib_dir {
+            Some(dir) => Env::with_lib_dir(self.rule, self.target, self.module, self.libs, dir)
+                .with_context(|| format!("failed to load libs from {}", dir.display()))?,
+            None => Env::simple(self.rule, self.target, self.module, self.libs),
+        };
+
+        let resolver = resolver(PathsResolver::new(
+            ModuleResolver::new(env.module()),
+            self.paths_dir,
+            self.base_url,
+            self.paths,
+        ));
+        let mut checker = Checker::new(cm.clone(), handler, env.clone(), None, ModuleLoader::new(cm, env, resolver));
+
+        checker.load_typings(self.base, self.type_roots, self.types);
+        let errors = ErrorKind::flatten(checker.take_errors())
+            .into_iter()
+            .filter(|err| matches!(&**err, ErrorKind::TypeDefinitionNotFound { .. }))
+            .collect();
+
+        Ok((checker, errors))
+    }
+}
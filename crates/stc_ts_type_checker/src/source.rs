@@ -0,0 +1,197 @@
+//! Checking a single source string, without the file system.
+//!
+//! [check_source] is for playgrounds, fuzzers and tests of tools built on stc,
+//! which have a file in memory and do not want to set up a [Checker] and a
+//! module loader.
+
+use std::{io, path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, bail, Result};
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, ModuleKind, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_storage::limit::ErrorLimit;
+use stc_ts_types::module_id::ModuleIdGenerator;
+use stc_ts_utils::StcComments;
+use swc_common::{errors::Handler, FileName, Globals, SourceMap, Span, SyntaxContext, GLOBALS};
+use swc_ecma_ast::{EsVersion, Module};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_visit::VisitMutWith;
+
+use crate::{
+    diagnostics::{dedup_errors, Diagnostic},
+    incremental::hash_content,
+    loader::{is_dts_path, LoadModule, ModuleRecord, Records},
+    Checker,
+};
+
+/// Options of [check_source].
+#[derive(Debug, Clone)]
+pub struct CheckOptions {
+    pub rule: Rule,
+    pub target: EsVersion,
+    pub module: ModuleConfig,
+    /// Defaults to the libs `tsc` uses for `target`. See [Lib::default_for].
+    pub libs: Vec<Lib>,
+    pub error_limit: ErrorLimit,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        let target = EsVersion::latest();
+
+        Self {
+            rule: Default::default(),
+            target,
+            module: ModuleKind::None.into(),
+            libs: Lib::default_for(target),
+            error_limit: Default::default(),
+        }
+    }
+}
+
+/// Type checks `source` as a file named `filename`, returning deduplicated
+/// errors sorted by position.
+///
+/// `filename` is only used to decide the syntax, like `.tsx` or `.d.ts`.
+/// Imports cannot be resolved, so they are reported as errors. Fails only if
+/// `source` cannot be parsed.
+pub fn check_source(filename: &str, source: &str, options: &CheckOptions) -> Result<Vec<Diagnostic>> {
+    check_source_with_cm(&Arc::new(SourceMap::default()), filename, source, options)
+}
+
+/// [check_source], but the file is added to `cm`, so the spans of the
+/// returned errors can be mapped to lines and columns.
+pub fn check_source_with_cm(cm: &Arc<SourceMap>, filename: &str, source: &str, options: &CheckOptions) -> Result<Vec<Diagnostic>> {
+    if GLOBALS.is_set() {
+        check_source_inner(cm, filename, source, options)
+    } else {
+        GLOBALS.set(&Globals::new(), || check_source_inner(cm, filename, source, options))
+    }
+}
+
+fn check_source_inner(cm: &Arc<SourceMap>, filename: &str, source: &str, options: &CheckOptions) -> Result<Vec<Diagnostic>> {
+    let handler = Arc::new(Handler::with_emitter_writer(Box::new(io::sink()), None));
+    let env = Env::simple(options.rule, options.target, options.module.clone(), &options.libs);
+
+    let path = PathBuf::from(filename);
+    let name = Arc::new(FileName::Real(path.clone()));
+    let fm = cm.new_source_file((*name).clone(), source.to_string());
+
+    let ids = ModuleIdGenerator::default();
+    let (id, top_level_mark) = ids.generate(&name);
+
+    let comments = StcComments::default();
+    let is_dts = is_dts_path(&path);
+    let mut ast = {
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig {
+                dts: is_dts,
+                tsx: path.extension().map_or(false, |ext| ext == "tsx"),
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            StringInput::from(&*fm),
+            Some(&comments),
+        );
+        let mut parser = Parser::new_from(lexer);
+        parser
+            .parse_module()
+            .map_err(|err| anyhow!("failed to parse `{}`: {}", filename, err.kind().msg()))?
+    };
+    ast.visit_mut_with(&mut swc_ecma_transforms_base::resolver(
+        env.shared().marks().unresolved_mark(),
+        top_level_mark,
+        true,
+    ));
+
+    let loader = SourceLoader {
+        cm: cm.clone(),
+        record: Arc::new(ModuleRecord {
+            id,
+            is_dts,
+            filename: name.clone(),
+            top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+            ast,
+        }),
+        comments,
+        hash: hash_content(source.as_bytes()),
+        ids,
+    };
+
+    let mut checker = Checker::new(cm.clone(), handler, env, None, loader).with_error_limit(options.error_limit);
+    checker.check(name);
+
+    Ok(dedup_errors(cm, checker.take_errors()))
+}
+
+/// A [LoadModule] which knows only one file.
+struct SourceLoader {
+    cm: Arc<SourceMap>,
+    record: Arc<ModuleRecord>,
+    comments: StcComments,
+    hash: String,
+    ids: ModuleIdGenerator,
+}
+
+impl LoadModule for SourceLoader {
+    fn load_module(&self, filename: &Arc<FileName>, _is_entry: bool) -> Result<Records> {
+        if *filename == self.record.filename {
+            return Ok(Records {
+                modules: vec![self.record.clone()],
+                entry: self.record.clone(),
+                comments: self.comments.clone(),
+            });
+        }
+
+        match &**filename {
+            // Modules declared using `declare module "foo"`.
+            FileName::Custom(..) => {
+                let fm = self.cm.new_source_file((**filename).clone(), String::new());
+                let (id, top_level_mark) = self.ids.generate(filename);
+                let record = Arc::new(ModuleRecord {
+                    id,
+                    is_dts: false,
+                    filename: filename.clone(),
+                    top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+                    ast: Module {
+                        span: Span::new(fm.start_pos, fm.end_pos, Default::default()),
+                        body: Default::default(),
+                        shebang: Default::default(),
+                    },
+                });
+
+                Ok(Records {
+                    modules: vec![record.clone()],
+                    entry: record,
+                    comments: self.comments.clone(),
+                })
+            }
+            _ => bail!("`{}` is not loaded", filename),
+        }
+    }
+
+    fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records> {
+        bail!("cannot resolve `{}` from `{}` without the file system", module_specifier, base)
+    }
+
+    fn resolve_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<(Arc<FileName>, Arc<FileName>)> {
+        bail!("cannot resolve `{}` from `{}` without the file system", module_specifier, base)
+    }
+
+    fn content_hash(&self, filename: &Arc<FileName>) -> Option<String> {
+        if *filename == self.record.filename {
+            Some(self.hash.clone())
+        } else {
+            None
+        }
+    }
+
+    fn dependencies(&self, _: &Arc<FileName>) -> Vec<Arc<FileName>> {
+        vec![]
+    }
+
+    fn unresolved(&self, _: &Arc<FileName>) -> Vec<String> {
+        vec![]
+    }
+}
@@ -2,12 +2,15 @@ use std::{
     fs::read_dir,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Instant,
 };
 
+use fxhash::FxHashMap;
+use instant::Instant;
+#[cfg(not(feature = "no-threading"))]
 use rayon::prelude::*;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
-use swc_common::FileName;
+use stc_ts_errors::ErrorKind;
+use stc_ts_module_loader::resolvers::{node::NodeResolver, types_package_name};
+use swc_common::{FileName, DUMMY_SP};
 
 use crate::{loader::LoadModule, Checker};
 
@@ -15,63 +18,101 @@ impl<L> Checker<L>
 where
     L: LoadModule,
 {
-    fn try_loading_typing_of_one_package(&self, dir: &Path) {
+    /// Returns `true` if `dir` is a package with typings.
+    fn try_loading_typing_of_one_package(&self, dir: &Path) -> bool {
         if !dir.is_dir() {
-            return;
+            return false;
         }
 
         let result = NodeResolver
             .resolve_as_file(dir)
             .or_else(|_| NodeResolver.resolve_as_directory(dir));
 
-        if let Ok(entry) = result {
-            let entry = Arc::new(FileName::Real(entry));
-            let start = Instant::now();
+        match result {
+            Ok(entry) => {
+                let entry = Arc::new(FileName::Real(entry));
+                let start = Instant::now();
 
-            self.analyze_module(None, entry);
+                self.analyze_module(None, entry);
 
-            let end = Instant::now();
-            log::debug!("Loading typings at `{}` took {:?}", dir.display(), end - start);
-        }
-    }
-
-    fn load_typings_from_dir(&self, dir: &Path, types: Option<&[String]>) {
-        let types_dir = dir.join("node_modules").join("@types");
-
-        if !types_dir.is_dir() {
-            return Default::default();
-        }
-
-        let dirs = types.map(|s| s.iter().map(|s| PathBuf::from(s.clone())).collect()).or_else(|| {
-            let pkgs = read_dir(&types_dir).ok()?;
+                let end = Instant::now();
+                log::debug!("Loading typings at `{}` took {:?}", dir.display(), end - start);
 
-            let f = pkgs.into_iter().filter_map(Result::ok).map(|e| e.path()).collect::<Vec<_>>();
-
-            Some(f)
-        });
-
-        if let Some(dirs) = dirs {
-            dirs.into_par_iter().for_each(|dir| {
-                self.try_loading_typing_of_one_package(&types_dir.join(dir));
-            });
+                true
+            }
+            Err(_) => false,
         }
     }
 
     /// Load typings from node_modules.
     ///
+    /// `type_roots` defaults to `node_modules/@types` of `base` and its
+    /// ancestors. If `types` is [Some], only the listed packages are loaded,
+    /// and an error is reported for packages which are not found.
+    ///
+    /// Global declarations of the packages are added to the environment.
+    ///
     /// - https://www.typescriptlang.org/tsconfig#typeRoots
     /// - https://www.typescriptlang.org/tsconfig#types
-    pub fn load_typings(&self, base: &Path, _type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
-        let mut dirs = vec![];
-
-        let mut cur = Some(base);
-        while let Some(c) = cur {
-            dirs.push(c.to_path_buf());
-            cur = c.parent();
+    pub fn load_typings(&self, base: &Path, type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
+        let type_roots = match type_roots {
+            Some(type_roots) => type_roots.to_vec(),
+            None => base
+                .ancestors()
+                .map(|dir| dir.join("node_modules").join("@types"))
+                .filter(|dir| dir.is_dir())
+                .collect(),
+        };
+
+        match types {
+            Some(types) => {
+                #[cfg(feature = "no-threading")]
+                let iter = types.iter();
+                #[cfg(not(feature = "no-threading"))]
+                let iter = types.par_iter();
+
+                iter.for_each(|name| {
+                    let dir_name = types_package_name(name);
+
+                    // The nearest type root wins.
+                    let found = type_roots
+                        .iter()
+                        .any(|root| self.try_loading_typing_of_one_package(&root.join(&dir_name)));
+
+                    if !found {
+                        self.errors.lock().push(
+                            ErrorKind::TypeDefinitionNotFound {
+                                span: DUMMY_SP,
+                                name: name.as_str().into(),
+                            }
+                            .into(),
+                        );
+                    }
+                });
+            }
+            None => {
+                // The nearest type root wins.
+                let mut packages = FxHashMap::default();
+                for root in &type_roots {
+                    let entries = match read_dir(root) {
+                        Ok(entries) => entries,
+                        Err(_) => continue,
+                    };
+
+                    for entry in entries.filter_map(Result::ok) {
+                        packages.entry(entry.file_name()).or_insert_with(|| entry.path());
+                    }
+                }
+
+                #[cfg(feature = "no-threading")]
+                let iter = packages.into_iter();
+                #[cfg(not(feature = "no-threading"))]
+                let iter = packages.into_par_iter();
+
+                iter.for_each(|(_, dir)| {
+                    self.try_loading_typing_of_one_package(&dir);
+                });
+            }
         }
-
-        dirs.into_par_iter().for_each(|dir| {
-            self.load_typings_from_dir(&dir, types);
-        });
     }
 }
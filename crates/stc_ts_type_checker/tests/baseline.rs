@@ -0,0 +1,68 @@
+use std::{path::Path, sync::Arc};
+
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_type_checker::{
+    baseline::{fingerprint, Baseline},
+    source::{check_source_with_cm, CheckOptions},
+};
+use swc_common::{BytePos, FileName, SourceMap, Span, Spanned, SyntaxContext};
+
+fn check(source: &str) -> (Arc<SourceMap>, Vec<Error>) {
+    let cm = Arc::new(SourceMap::default());
+    let diagnostics = check_source_with_cm(&cm, "input.ts", source, &CheckOptions::default()).unwrap();
+
+    (cm, diagnostics.into_iter().map(|d| d.error).collect())
+}
+
+#[test]
+fn only_new_errors_are_reported() {
+    let base = Path::new("/app");
+
+    let (cm, errors) = check("const a: string = 1;\n");
+    let baseline = Baseline::from_errors(&cm, base, &errors);
+    assert_eq!(baseline.len(), 1);
+
+    // The recorded error is moved to the third line.
+    let (cm, errors) = check("const b: number = '';\n\nconst a: string = 1;\n");
+    let result = baseline.apply(&cm, base, errors);
+
+    let errors = result
+        .errors
+        .iter()
+        .map(|err| (err.code(), cm.lookup_char_pos(err.span().lo).line))
+        .collect::<Vec<_>>();
+    assert_eq!(errors, vec![(2322, 1)]);
+    assert_eq!(result.matched, 1);
+    assert_eq!(result.stale, vec![]);
+}
+
+#[test]
+fn fixed_errors_are_stale() {
+    let base = Path::new("/app");
+
+    let (cm, errors) = check("const a: string = 1;\nconst b: number = '';\n");
+    let baseline = Baseline::from_errors(&cm, base, &errors);
+
+    let (cm, errors) = check("const a: string = 1;\n");
+    let result = baseline.apply(&cm, base, errors);
+
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.matched, 1);
+    assert_eq!(result.stale.len(), 1);
+    assert_eq!(result.stale[0].file, "input.ts");
+    assert_eq!(result.stale[0].count, 1);
+    assert!(result.stale[0].fingerprint.starts_with("TS2322-"));
+}
+
+#[test]
+fn fingerprints_are_stable() {
+    let cm = SourceMap::default();
+    let fm = cm.new_source_file(FileName::Anon, "import 'foo\n   bar';".into());
+    let err: Error = ErrorKind::ModuleNotFound {
+        span: Span::new(fm.start_pos + BytePos(8), fm.start_pos + BytePos(18), SyntaxContext::empty()),
+    }
+    .into();
+
+    // The first 8 bytes of the SHA-1 hash of `foo bar`.
+    assert_eq!(fingerprint(&cm, &err), "TS2307-3773dea651569098");
+}
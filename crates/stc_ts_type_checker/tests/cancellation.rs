@@ -0,0 +1,78 @@
+use std::{fs, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::Error;
+use stc_ts_file_analyzer::{cancellation::CancellationToken, env::EnvFactory};
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    diagnostics::{DiagnosticsSink, SinkDiagnostic},
+    loader::ModuleLoader,
+    Checker,
+};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Cancels the token when `b.ts` is checked, which happens while `a.ts` is
+/// being analyzed.
+struct CancelOnDependency(CancellationToken);
+
+impl DiagnosticsSink for CancelOnDependency {
+    fn on_module_checked(&self, file: &Arc<FileName>, _: &[SinkDiagnostic]) {
+        if file.to_string().ends_with("b.ts") {
+            self.0.cancel();
+        }
+    }
+}
+
+fn check(name: &str, token: CancellationToken, cancel_on_dependency: bool) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-cancellation").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.ts"), "import { x } from './b';\n\nconst a: string = x;\n").unwrap();
+    fs::write(dir.join("b.ts"), "export const x: number = 1;\n").unwrap();
+    let dir = dir.canonicalize().unwrap();
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::CommonJs, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        )
+        .with_cancellation_token(token.clone());
+        if cancel_on_dependency {
+            checker = checker.with_diagnostics_sink(Arc::new(CancelOnDependency(token)));
+        }
+
+        checker.check(Arc::new(FileName::Real(dir.join("a.ts"))));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn not_cancelled() {
+    let errors = check("not-cancelled", CancellationToken::new(), false);
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+}
+
+#[test]
+fn cancelled_before_checking() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let errors = check("before", token, false);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn cancelled_while_analyzing() {
+    let errors = check("while-analyzing", CancellationToken::new(), true);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use stc_ts_errors::ErrorKind;
+use stc_ts_type_checker::{
+    source::{check_source, check_source_with_cm, CheckOptions},
+    ErrorLimit,
+};
+use swc_common::{SourceMap, Spanned};
+
+#[test]
+fn reports_errors() {
+    let diagnostics = check_source(
+        "input.ts",
+        "const a: string = 1;\nconst b: number = '';\n",
+        &CheckOptions::default(),
+    )
+    .unwrap();
+
+    let codes = diagnostics.iter().map(|d| d.error.code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec![2322, 2322]);
+}
+
+#[test]
+fn default_libs_follow_target() {
+    let diagnostics = check_source(
+        "input.ts",
+        "export const p: Promise<number> = Promise.resolve(1);\nexport const d: Document = document;\n",
+        &CheckOptions::default(),
+    )
+    .unwrap();
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn valid_source() {
+    let diagnostics = check_source("input.ts", "export const a: string = 'a';\n", &CheckOptions::default()).unwrap();
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn imports_are_not_resolved() {
+    let diagnostics = check_source("input.ts", "import { a } from './a';\n", &CheckOptions::default()).unwrap();
+
+    assert!(
+        diagnostics.iter().any(|d| matches!(&*d.error, ErrorKind::ModuleNotFound { .. })),
+        "{:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn syntax_error() {
+    assert!(check_source("input.ts", "const = ;", &CheckOptions::default()).is_err());
+}
+
+#[test]
+fn spans_can_be_mapped_to_lines() {
+    let cm = Arc::new(SourceMap::default());
+    let diagnostics = check_source_with_cm(&cm, "input.ts", "\nconst b: number = '';\n", &CheckOptions::default()).unwrap();
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(cm.lookup_char_pos(diagnostics[0].error.span().lo).line, 2);
+}
+
+/// Codes are the ones reported by `tsc` for the same source.
+#[test]
+fn tsc_error_codes() {
+    let cases = [
+        ("const a: string = 1;\n", 2322),
+        ("foo();\n", 2304),
+        ("function f(a: string) {}\nf(1);\n", 2345),
+        ("const a = { b: 1 };\na.c;\n", 2339),
+        ("let a = 1;\nlet a = 2;\n", 2451),
+        ("import { a } from './a';\n", 2307),
+    ];
+
+    for (source, code) in cases {
+        let diagnostics = check_source("input.ts", source, &CheckOptions::default()).unwrap();
+
+        let codes = diagnostics.iter().map(|d| d.error.code()).collect::<Vec<_>>();
+        assert!(codes.contains(&code), "{:?} does not contain {} for `{}`", codes, code, source);
+    }
+}
+
+#[test]
+fn error_limit() {
+    let source = "const a: string = 1;\nconst b: string = 1;\nconst c: string = 1;\n";
+
+    let limits = [
+        (Some(2), None, vec![2322, 2322, 0], 1),
+        (None, Some(1), vec![2322, 0], 2),
+        (Some(5), Some(5), vec![2322, 2322, 2322], 0),
+    ];
+
+    for (per_file, total, expected, suppressed) in limits {
+        let options = CheckOptions {
+            error_limit: ErrorLimit { per_file, total },
+            ..Default::default()
+        };
+        let diagnostics = check_source("input.ts", source, &options).unwrap();
+
+        let codes = diagnostics.iter().map(|d| d.error.code()).collect::<Vec<_>>();
+        assert_eq!(codes, expected);
+
+        let notices = diagnostics
+            .iter()
+            .filter_map(|d| match &*d.error {
+                ErrorKind::TooManyErrors { suppressed, .. } => Some(*suppressed),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(notices.iter().sum::<usize>(), suppressed);
+    }
+}
+
+fn codes(source: &str) -> Vec<(usize, usize)> {
+    let cm = Arc::new(SourceMap::default());
+    let diagnostics = check_source_with_cm(&cm, "input.ts", source, &CheckOptions::default()).unwrap();
+
+    diagnostics
+        .iter()
+        .map(|d| (d.error.code(), cm.lookup_char_pos(d.error.span().lo).line))
+        .collect()
+}
+
+#[test]
+fn ts_ignore() {
+    assert_eq!(
+        codes("// @ts-ignore\nconst a: string = 1;\nconst b: string = 1;\n"),
+        vec![(2322, 3)]
+    );
+}
+
+#[test]
+fn ts_expect_error() {
+    assert_eq!(codes("// @ts-expect-error\n\n// comment\nconst a: string = 1;\n"), vec![]);
+    assert_eq!(codes("/* @ts-expect-error */\nconst a: string = 1;\n"), vec![]);
+}
+
+#[test]
+fn unused_ts_expect_error() {
+    assert_eq!(
+        codes("// @ts-expect-error\nconst a: string = '';\n// @ts-ignore\nconst b: string = '';\n"),
+        vec![(2578, 1)]
+    );
+}
+
+#[test]
+fn directive_applies_to_next_line_only() {
+    assert_eq!(
+        codes("// @ts-expect-error\nconst a = 1;\nconst b: string = 1;\n"),
+        vec![(2578, 1), (2322, 3)]
+    );
+}
+
+#[test]
+fn spelling_suggestions() {
+    let diagnostics = check_source(
+        "input.ts",
+        "const value = 1;\nvalu;\nconst obj = { length: 1 };\nobj.lenght;\n",
+        &CheckOptions::default(),
+    )
+    .unwrap();
+
+    let suggestions = diagnostics
+        .iter()
+        .map(|d| match &*d.error {
+            ErrorKind::NoSuchVarWithSuggestion { suggestion, .. } | ErrorKind::NoSuchPropertyWithSuggestion { suggestion, .. } => {
+                (d.error.code(), &**suggestion)
+            }
+            _ => panic!("unexpected error: {:?}", d.error),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(suggestions, vec![(2552, "value"), (2551, "length")]);
+}
+
+/// Suggestions are not reported without a diagnostics sink.
+#[test]
+fn deprecated_without_sink() {
+    let diagnostics = check_source(
+        "input.ts",
+        "/** @deprecated */\nexport const a = 1;\nexport const b = a;\n",
+        &CheckOptions::default(),
+    )
+    .unwrap();
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
@@ -0,0 +1,81 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-circular").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const A: &str = "import { B, b } from './b';
+
+export type A = { b: B };
+
+export const a: A = { b };
+";
+
+const B: &str = "import { A } from './a';
+
+export type B = { a?: A; value: string };
+
+export const b: B = { value: '' };
+
+export function f(x: A): string {
+    return x.b.value;
+}
+";
+
+#[test]
+fn types_of_circular_imports_do_not_depend_on_order() {
+    let errors_a = check("order-a", "a.ts", &[("a.ts", A), ("b.ts", B)]);
+    let errors_b = check("order-b", "b.ts", &[("a.ts", A), ("b.ts", B)]);
+
+    assert!(errors_a.is_empty(), "{:?}", errors_a);
+    assert!(errors_b.is_empty(), "{:?}", errors_b);
+}
+
+#[test]
+fn missing_circular_import() {
+    let errors = check(
+        "missing",
+        "a.ts",
+        &[
+            ("a.ts", "import { C } from './b';\nexport type A = C;\n"),
+            ("b.ts", "import { A } from './a';\nexport type B = A;\n"),
+        ],
+    );
+
+    assert!(
+        errors.iter().any(|err| matches!(&**err, ErrorKind::ImportFailed { .. })),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,368 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    diagnostics::{
+        dedup_errors, sort_errors_by_file, DiagnosticsSink, JsonCategory, JsonDiagnostic, JsonEmitter, JsonPosition, Severity,
+        SinkDiagnostic,
+    },
+    loader::ModuleLoader,
+    Checker,
+};
+use stc_ts_types::Id;
+use swc_common::{errors::Handler, BytePos, FileName, SourceMap, Span, SyntaxContext};
+use swc_ecma_ast::EsVersion;
+
+fn span(cm: &SourceMap, file: &str, lo: u32, hi: u32) -> Span {
+    let fm = cm
+        .get_source_file(&FileName::Custom(file.into()))
+        .unwrap_or_else(|| cm.new_source_file(FileName::Custom(file.into()), "x".repeat(100)));
+
+    Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi), SyntaxContext::empty())
+}
+
+fn no_such_var(span: Span, name: &str) -> Error {
+    ErrorKind::NoSuchVar {
+        span,
+        name: Id::word(name.into()),
+    }
+    .into()
+}
+
+#[test]
+fn identical_errors() {
+    let cm = SourceMap::default();
+
+    let errors = vec![
+        ErrorKind::ModuleNotFound {
+            span: span(&cm, "a.ts", 0, 5),
+        }
+        .into(),
+        ErrorKind::ModuleNotFound {
+            span: span(&cm, "a.ts", 0, 5),
+        }
+        .into(),
+        ErrorKind::ModuleNotFound {
+            span: span(&cm, "a.ts", 10, 15),
+        }
+        .into(),
+    ];
+
+    let diagnostics = dedup_errors(&cm, errors);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| d.suppressed == 0));
+}
+
+#[test]
+fn errors_are_sorted_by_file() {
+    let cm = SourceMap::default();
+
+    // `b.ts` is loaded first, so its positions are smaller.
+    let mut errors = vec![
+        no_such_var(span(&cm, "b.ts", 10, 13), "b2"),
+        no_such_var(span(&cm, "b.ts", 0, 3), "b1"),
+        no_such_var(span(&cm, "a.ts", 0, 3), "a1"),
+    ];
+
+    sort_errors_by_file(&cm, &mut errors);
+
+    let names = errors
+        .iter()
+        .map(|err| match &**err {
+            ErrorKind::NoSuchVar { name, .. } => name.sym().to_string(),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["a1", "b1", "b2"]);
+}
+
+#[test]
+fn unresolved_name_cascade() {
+    let cm = SourceMap::default();
+
+    let errors = vec![
+        no_such_var(span(&cm, "a.ts", 0, 3), "foo"),
+        no_such_var(span(&cm, "a.ts", 10, 13), "foo"),
+        no_such_var(span(&cm, "a.ts", 20, 23), "bar"),
+        no_such_var(span(&cm, "a.ts", 30, 33), "foo"),
+        no_such_var(span(&cm, "b.ts", 0, 3), "foo"),
+    ];
+
+    let diagnostics = dedup_errors(&cm, errors);
+
+    assert_eq!(
+        diagnostics.iter().map(|d| d.suppressed).collect::<Vec<_>>(),
+        vec![2, 0, 0],
+        "each unresolved name should be reported once per file"
+    );
+}
+
+/// Reports `TS2322` as warnings and drops other errors.
+#[derive(Default)]
+struct RecordingSink {
+    cancelled: bool,
+    received: Mutex<Vec<(Arc<FileName>, Vec<(usize, Severity)>)>>,
+}
+
+impl DiagnosticsSink for RecordingSink {
+    fn filter(&self, _: &Arc<FileName>, error: &Error) -> bool {
+        error.code() == 2322
+    }
+
+    fn severity(&self, _: &Error) -> Severity {
+        Severity::Warning
+    }
+
+    fn on_module_checked(&self, file: &Arc<FileName>, diagnostics: &[SinkDiagnostic]) {
+        let diagnostics = diagnostics.iter().map(|d| (d.error.code(), d.severity)).collect();
+        self.received.lock().unwrap().push((file.clone(), diagnostics));
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+}
+
+fn check_with_sink(name: &str, sink: Arc<RecordingSink>) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-diagnostics-sink").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    let path: PathBuf = dir.join("index.ts");
+    fs::write(&path, "const a: string = 1;\nfoo;\n").unwrap();
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        )
+        .with_diagnostics_sink(sink.clone());
+
+        checker.check(Arc::new(FileName::Real(path.canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn sink_filters_errors() {
+    let sink = Arc::new(RecordingSink::default());
+    let errors = check_with_sink("filter", sink.clone());
+
+    assert!(errors.iter().all(|err| err.code() == 2322));
+    assert_eq!(errors.len(), 1);
+
+    let received = sink.received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].1, vec![(2322, Severity::Warning)]);
+}
+
+#[test]
+fn cancelled_sink_skips_modules() {
+    let sink = Arc::new(RecordingSink {
+        cancelled: true,
+        ..Default::default()
+    });
+    let errors = check_with_sink("cancelled", sink.clone());
+
+    assert!(errors.is_empty());
+    assert!(sink.received.lock().unwrap().is_empty());
+}
+
+/// Uses the default filter and severities.
+#[derive(Default)]
+struct DefaultSink {
+    received: Mutex<Vec<(usize, Severity)>>,
+}
+
+impl DiagnosticsSink for DefaultSink {
+    fn on_module_checked(&self, _: &Arc<FileName>, diagnostics: &[SinkDiagnostic]) {
+        let mut received = self.received.lock().unwrap();
+        received.extend(diagnostics.iter().map(|d| (d.error.code(), d.severity)));
+    }
+}
+
+/// Writes `files` and checks the last one.
+fn check_deprecated(name: &str, files: &[(&str, &str)], sink: Arc<DefaultSink>) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-diagnostics-sink").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path: PathBuf = dir.join(files.last().unwrap().0);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        )
+        .with_diagnostics_sink(sink.clone());
+
+        checker.check(Arc::new(FileName::Real(path.canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+fn deprecated_names(errors: &[Error]) -> Vec<String> {
+    let mut names = errors
+        .iter()
+        .map(|err| match &**err {
+            ErrorKind::Deprecated { name, .. } => name.to_string(),
+            _ => panic!("unexpected error: {:?}", err),
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn deprecated_references_are_suggestions() {
+    let sink = Arc::new(DefaultSink::default());
+    let errors = check_deprecated(
+        "deprecated",
+        &[(
+            "index.ts",
+            "/** @deprecated Use `bar` instead. */\nexport function foo() {}\nexport function bar() {}\nfoo();\nbar();\n",
+        )],
+        sink.clone(),
+    );
+
+    assert_eq!(sink.received.lock().unwrap().clone(), vec![(6385, Severity::Suggestion)]);
+
+    assert_eq!(errors.len(), 1);
+    match &*errors[0] {
+        ErrorKind::Deprecated { name, message, .. } => {
+            assert_eq!(&**name, "foo");
+            assert_eq!(message.as_deref(), Some("Use `bar` instead."));
+        }
+        _ => panic!("unexpected error: {:?}", errors[0]),
+    }
+}
+
+const DEPRECATED_EXPORTS: &str = "/** @deprecated Use `bar` instead. */
+export function foo() {}
+/** @deprecated */
+export interface Foo {}
+export function bar() {}
+";
+
+#[test]
+fn deprecated_imports() {
+    let errors = check_deprecated(
+        "deprecated-imports",
+        &[
+            ("a.ts", DEPRECATED_EXPORTS),
+            ("b.ts", "import { foo, bar, Foo } from './a';\nfoo();\nbar();\nlet f: Foo;\n"),
+        ],
+        Default::default(),
+    );
+
+    // The import specifiers are reported too, like `tsc`.
+    assert_eq!(deprecated_names(&errors), vec!["Foo", "Foo", "foo", "foo"]);
+    for err in &errors {
+        if let ErrorKind::Deprecated { name, message, .. } = &**err {
+            if &**name == "foo" {
+                assert_eq!(message.as_deref(), Some("Use `bar` instead."));
+            }
+        }
+    }
+}
+
+#[test]
+fn deprecated_members() {
+    let errors = check_deprecated(
+        "deprecated-members",
+        &[(
+            "index.ts",
+            "export interface I {
+    /** @deprecated */
+    old: number;
+    new: number;
+}
+declare const i: I;
+i.old;
+i.new;
+
+export class C {
+    /** @deprecated */
+    old() {}
+    new() {}
+
+    /** @deprecated */
+    static s = 1;
+}
+new C().old();
+new C().new();
+C.s;
+",
+        )],
+        Default::default(),
+    );
+
+    assert_eq!(deprecated_names(&errors), vec!["old", "old", "s"]);
+}
+
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn json_lines() {
+    let cm = Arc::new(SourceMap::default());
+    let fm = cm.new_source_file(FileName::Real(PathBuf::from("/app/a.ts")), "let a = 1;\nfoo;\n".into());
+
+    let buf = SharedBuf::default();
+    let handler = Handler::with_emitter(true, false, Box::new(JsonEmitter::new(cm.clone(), Box::new(buf.clone()))));
+
+    let span = Span::new(fm.start_pos + BytePos(11), fm.start_pos + BytePos(14), SyntaxContext::empty());
+    no_such_var(span, "foo").emit(&handler);
+    handler.note_without_error("Found 1 errors");
+
+    let output = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+    let lines = output
+        .lines()
+        .map(|line| serde_json::from_str::<JsonDiagnostic>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+
+    let error = &lines[0];
+    assert_eq!(error.file.as_deref(), Some("/app/a.ts"));
+    assert_eq!(error.code, Some(2304));
+    assert_eq!(error.category, JsonCategory::Error);
+    assert_eq!(error.start, Some(JsonPosition { line: 2, column: 1 }));
+    assert_eq!(error.end, Some(JsonPosition { line: 2, column: 4 }));
+
+    let note = &lines[1];
+    assert_eq!(note.file, None);
+    assert_eq!(note.code, None);
+    assert_eq!(note.category, JsonCategory::Message);
+    assert_eq!(note.message, "Found 1 errors");
+}
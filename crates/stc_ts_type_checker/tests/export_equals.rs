@@ -0,0 +1,126 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-export-equals").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const LIB: &str = "class Foo {
+    value = 1;
+}
+
+namespace Foo {
+    export interface Options {
+        debug: boolean;
+    }
+}
+
+export = Foo;
+";
+
+#[test]
+fn import_equals_uses_export_equals() {
+    let errors = check(
+        "class",
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "import Foo = require('./lib');
+
+const foo: Foo = new Foo();
+const value: number = foo.value;
+const options: Foo.Options = { debug: true };
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn export_equals_mixed_with_other_exports() {
+    let errors = check(
+        "mixed",
+        "main.ts",
+        &[("main.ts", "export const a = 1;\n\nconst b = 2;\n\nexport = b;\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::ExportEqualsMixedWithOtherExports { .. })),
+        "{:?}",
+        errors
+    );
+}
+
+const FN: &str = "function f(): number {
+    return 1;
+}
+
+export = f;
+";
+
+#[test]
+fn default_import_requires_es_module_interop() {
+    let errors = check(
+        "default-import",
+        "main.ts",
+        &[("lib.ts", FN), ("main.ts", "import f from './lib';\n\nf();\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::DefaultImportRequiresEsModuleInterop { .. })),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn namespace_import_of_export_equals_is_callable() {
+    let errors = check(
+        "namespace-import",
+        "main.ts",
+        &[
+            ("lib.ts", FN),
+            ("main.ts", "import * as f from './lib';\n\nconst v: number = f();\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -0,0 +1,116 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind, Rule};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+use swc_ecma_loader::resolve::Resolve;
+
+/// Resolves `./name` to `name.ts` without checking if the file exists, like
+/// resolvers on case-insensitive file systems.
+struct CaseInsensitiveResolver;
+
+impl Resolve for CaseInsensitiveResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, anyhow::Error> {
+        let base = match base {
+            FileName::Real(base) => base,
+            _ => unreachable!(),
+        };
+
+        Ok(FileName::Real(
+            base.parent().unwrap().join(format!("{}.ts", target.trim_start_matches("./"))),
+        ))
+    }
+}
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, force_consistent_casing: bool, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-file-name-casing").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let rule = Rule::builder()
+            .force_consistent_casing_in_file_names(force_consistent_casing)
+            .build();
+        let env = Env::simple(rule, EsVersion::latest(), ModuleKind::EsNext, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, CaseInsensitiveResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const LIB: &str = "export const a: number = 1;\n";
+
+#[test]
+fn imports_with_different_casing() {
+    let errors = check(
+        "different",
+        true,
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "import { a } from './lib';\nimport { a as b } from './Lib';\n\nexport const c: number = a + b;\n",
+            ),
+        ],
+    );
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(&*errors[0], ErrorKind::FileNameDiffersOnlyInCasing { .. }), "{:?}", errors);
+}
+
+#[test]
+fn import_differs_from_disk() {
+    let errors = check(
+        "disk",
+        true,
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            ("main.ts", "import { a } from './LIB';\n\nexport const c: number = a;\n"),
+        ],
+    );
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(
+        matches!(&*errors[0], ErrorKind::FileNameDiffersFromDiskOnlyInCasing { .. }),
+        "{:?}",
+        errors
+    );
+}
+
+/// Without the rule, both imports refer to the same module.
+#[test]
+fn different_casing_without_rule() {
+    let errors = check(
+        "without-rule",
+        false,
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "import { a } from './lib';\nimport { a as b } from './Lib';\n\nexport const c: number = a + b;\n",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -0,0 +1,65 @@
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_type_checker::formatter::PrettyEmitter;
+use swc_common::{
+    errors::{Diagnostic, DiagnosticId, Level},
+    BytePos, FileName, SourceMap, Span, SyntaxContext,
+};
+
+fn render(src: &str, lo: u32, hi: u32, width: usize) -> String {
+    let cm = Arc::new(SourceMap::default());
+    let fm = cm.new_source_file(FileName::Real(PathBuf::from("/app/a.ts")), src.into());
+    let span = Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi), SyntaxContext::empty());
+
+    let mut diagnostic = Diagnostic::new_with_code(Level::Error, Some(DiagnosticId::Error("TS2304".into())), "Cannot find name 'foo'.");
+    diagnostic.set_span(span);
+
+    PrettyEmitter::new(cm, Box::new(std::io::sink()))
+        .with_width(width)
+        .render(&diagnostic)
+}
+
+#[test]
+fn code_frame() {
+    let rendered = render("let a = 1;\nlet b = foo;\na;\nb;\n", 19, 22, 80);
+
+    assert_eq!(
+        rendered,
+        "/app/a.ts:2:9 - error TS2304: Cannot find name 'foo'.
+
+1 | let a = 1;
+2 | let b = foo;
+  |         ^^^
+3 | a;
+"
+    );
+}
+
+#[test]
+fn multi_line_span() {
+    let rendered = render("f(\n  foo,\n  bar);\n", 5, 15, 80);
+
+    assert_eq!(
+        rendered,
+        "/app/a.ts:2:3 - error TS2304: Cannot find name 'foo'.
+
+1 | f(
+2 |   foo,
+  |   ^^^^
+3 |   bar);
+  |   ^^^
+"
+    );
+}
+
+#[test]
+fn long_line_is_cut_around_span() {
+    let src = format!("let a = [{}foo{}];\n", "1, ".repeat(30), ", 2".repeat(30));
+    let lo = src.find("foo").unwrap() as u32;
+    let rendered = render(&src, lo, lo + 3, 40);
+
+    let lines = rendered.lines().collect::<Vec<_>>();
+    assert_eq!(lines[2], "1 | …, 1, 1, 1, foo, 2, 2, 2, 2, 2, 2, …");
+    assert_eq!(lines[3], "  |             ^^^");
+    assert!(lines.iter().all(|line| line.chars().count() <= 40));
+}
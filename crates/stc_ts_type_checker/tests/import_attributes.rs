@@ -0,0 +1,101 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind, Rule};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(rule: Rule, name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-import-attributes").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(rule, EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const DATA: &str = r#"{ "name": "stc", "version": 1 }"#;
+
+#[test]
+fn json_module() {
+    let rule = Rule::builder().resolve_json_module(true).es_module_interop(true).build();
+    let errors = check(
+        rule,
+        "json",
+        "main.ts",
+        &[
+            ("data.json", DATA),
+            (
+                "main.ts",
+                "import data from './data.json';
+
+const name: string = data.name;
+const version: number = data.version;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn json_module_without_resolve_json_module() {
+    let errors = check(
+        Default::default(),
+        "json-without-flag",
+        "main.ts",
+        &[("data.json", DATA), ("main.ts", "import data from './data.json';\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::JsonModuleWithoutResolveJsonModule { .. })),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn import_attributes_require_esnext() {
+    let rule = Rule::builder().resolve_json_module(true).es_module_interop(true).build();
+    let errors = check(
+        rule,
+        "attributes",
+        "main.ts",
+        &[
+            ("data.json", DATA),
+            ("main.ts", "import data from './data.json' assert { type: 'json' };\n"),
+        ],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::ImportAssertionsNotSupported { .. })),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,87 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-import-types").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const LIB: &str = "export interface Options {
+    debug: boolean;
+}
+
+export namespace ns {
+    export type Name = string;
+}
+
+export const version = 1;
+";
+
+#[test]
+fn import_types() {
+    let errors = check(
+        "types",
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "const options: import('./lib').Options = { debug: true };
+const name: import('./lib').ns.Name = '';
+const version: typeof import('./lib').version = 1;
+
+declare const lib: typeof import('./lib');
+const v: number = lib.version;
+
+export {};
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn import_type_of_missing_module() {
+    let errors = check(
+        "missing",
+        "main.ts",
+        &[("main.ts", "declare const lib: typeof import('./missing');\n\nexport {};\n")],
+    );
+
+    assert!(
+        errors.iter().any(|err| matches!(&**err, ErrorKind::ModuleNotFound { .. })),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{
+    incremental::{BuildInfo, IncrementalResult},
+    loader::ModuleLoader,
+    Checker,
+};
+use swc_ecma_ast::EsVersion;
+
+/// Writes `files` into `dir`, and checks `a.ts`, `b.ts` and `c.ts` using the
+/// state of the previous run.
+fn run(dir: &Path, previous: Option<&BuildInfo>, files: &[(&str, &str)]) -> IncrementalResult {
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let roots = ["a.ts", "b.ts", "c.ts"].iter().map(|file| dir.join(file)).collect::<Vec<_>>();
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::CommonJs, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        Ok(checker.check_incremental(previous, "", &roots))
+    })
+    .unwrap()
+}
+
+fn setup(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("stc-incremental").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.canonicalize().unwrap()
+}
+
+const B: &str = "import { f } from './a';\n\nexport const x: string = f();\n";
+
+const C: &str = "export const c = 1;\n";
+
+#[test]
+fn importers_are_checked_only_if_signature_changes() {
+    let dir = setup("signature");
+
+    let first = run(
+        &dir,
+        None,
+        &[("a.ts", "export function f(): number { return 1; }\n"), ("b.ts", B), ("c.ts", C)],
+    );
+    assert_eq!(first.checked.len(), 3);
+    assert_eq!(first.build_info.files[&dir.join("b.ts")].diagnostics.len(), 1);
+
+    // Changing the body of `f` does not affect `b.ts`, and its error is kept.
+    let second = run(
+        &dir,
+        Some(&first.build_info),
+        &[("a.ts", "export function f(): number { return 2; }\n")],
+    );
+    assert_eq!(second.checked, vec![dir.join("a.ts")]);
+    assert_eq!(second.build_info.files[&dir.join("b.ts")].diagnostics.len(), 1);
+
+    let third = run(
+        &dir,
+        Some(&second.build_info),
+        &[("a.ts", "export function f(): string { return ''; }\n")],
+    );
+    assert_eq!(third.checked, vec![dir.join("a.ts"), dir.join("b.ts")]);
+    assert!(third.build_info.files[&dir.join("b.ts")].diagnostics.is_empty());
+}
+
+#[test]
+fn unchanged_program_is_not_checked() {
+    let dir = setup("unchanged");
+
+    let first = run(
+        &dir,
+        None,
+        &[("a.ts", "export function f(): number { return 1; }\n"), ("b.ts", B), ("c.ts", C)],
+    );
+    let second = run(&dir, Some(&first.build_info), &[]);
+
+    assert!(second.checked.is_empty(), "{:?}", second.checked);
+    assert_eq!(second.build_info.files.len(), first.build_info.files.len());
+}
+
+/// `a.ts` is checked again when the module it failed to import is created.
+#[test]
+fn unresolved_imports_are_checked_again() {
+    let dir = setup("unresolved");
+
+    let first = run(
+        &dir,
+        None,
+        &[
+            ("a.ts", "import { x } from './d';\n\nexport const a: string = x;\n"),
+            ("b.ts", C),
+            ("c.ts", C),
+        ],
+    );
+    assert_eq!(first.build_info.files[&dir.join("a.ts")].unresolved, vec!["./d".to_string()]);
+    assert!(!first.build_info.files[&dir.join("a.ts")].diagnostics.is_empty());
+
+    let second = run(&dir, Some(&first.build_info), &[("d.ts", "export const x = '';\n")]);
+    assert_eq!(second.checked, vec![dir.join("a.ts")]);
+    assert!(second.build_info.files[&dir.join("a.ts")].unresolved.is_empty());
+    assert!(second.build_info.files[&dir.join("a.ts")].diagnostics.is_empty());
+}
@@ -0,0 +1,146 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind, Rule};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` with `isolatedModules` after writing `files` into a
+/// temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-isolated-modules").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let rule = Rule::builder().isolated_modules(true).build();
+        let env = Env::simple(rule, EsVersion::latest(), ModuleKind::EsNext, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+fn count(errors: &[Error], f: impl Fn(&ErrorKind) -> bool) -> usize {
+    errors.iter().filter(|err| f(err)).count()
+}
+
+const LIB: &str = "export class Foo {}
+
+export interface Options {
+    debug: boolean;
+}
+";
+
+#[test]
+fn type_reexport() {
+    let errors = check(
+        "type-reexport",
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "import { Options as Opts } from './lib';
+
+export { Foo, Options } from './lib';
+export { Opts };
+",
+            ),
+        ],
+    );
+
+    assert_eq!(
+        count(&errors, |err| matches!(err, ErrorKind::TypeReexportWithIsolatedModules { .. })),
+        2,
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn type_reexport_with_export_type() {
+    let errors = check(
+        "export-type",
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "export type { Options } from './lib';
+export { Foo, type Options as Opts } from './lib';
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn global_script() {
+    let errors = check("global-script", "main.ts", &[("main.ts", "const a = 1;\n")]);
+
+    assert_eq!(
+        count(&errors, |err| matches!(err, ErrorKind::GlobalScriptWithIsolatedModules { .. })),
+        1,
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn enum_member_after_non_constant_member() {
+    let errors = check(
+        "enum",
+        "main.ts",
+        &[
+            ("lib.ts", "export const base = 10;\n"),
+            (
+                "main.ts",
+                "import { base } from './lib';
+
+export enum Ok {
+    A = 1,
+    B,
+    C = A << 2,
+    D,
+    E = Ok.D + 1,
+    F,
+}
+
+export enum Bad {
+    A = base,
+    B,
+}
+",
+            ),
+        ],
+    );
+
+    assert_eq!(
+        count(&errors, |err| matches!(
+            err,
+            ErrorKind::EnumMemberWithoutInitializerWithIsolatedModules { .. }
+        )),
+        1,
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,281 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, ModuleKind, ModuleResolution};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::{node16::Node16Resolver, ModuleResolver};
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+use swc_ecma_loader::resolve::Resolve;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    check_with_resolver(Node16Resolver::new(), name, entry, files)
+}
+
+fn check_with_resolver<R>(resolver: R, name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error>
+where
+    R: 'static + Sync + Send + Clone + Resolve,
+{
+    let dir = std::env::temp_dir().join("stc-node16").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::Node16, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, resolver.clone()),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn esm_import_with_js_extension() {
+    let errors = check(
+        "js-extension",
+        "main.ts",
+        &[
+            ("package.json", r#"{ "type": "module" }"#),
+            ("lib.ts", "export const a = 1;\n"),
+            ("main.ts", "import { a } from './lib.js';\n\nconst n: number = a;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn esm_import_without_extension() {
+    let errors = check(
+        "no-extension",
+        "main.ts",
+        &[
+            ("package.json", r#"{ "type": "module" }"#),
+            ("lib.ts", "export const a = 1;\n"),
+            ("main.ts", "import { a } from './lib';\n"),
+        ],
+    );
+
+    assert!(
+        errors.iter().any(|err| matches!(
+            &**err,
+            ErrorKind::ImportPathWithoutExtensionWithSuggestion { suggestion, .. } if &**suggestion == "./lib.js"
+        )),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn mts_imports_cts() {
+    let errors = check(
+        "mts",
+        "main.mts",
+        &[
+            ("lib.cts", "export const a = 1;\n"),
+            ("util.mts", "export const b = '';\n"),
+            (
+                "main.mts",
+                "import { a } from './lib.cjs';
+import { b } from './util.mjs';
+
+const n: number = a;
+const s: string = b;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn cjs_import_without_extension() {
+    let errors = check(
+        "cjs",
+        "main.ts",
+        &[
+            ("package.json", r#"{ "type": "commonjs" }"#),
+            ("lib.ts", "export const a = 1;\n"),
+            ("main.ts", "import { a } from './lib';\n\nconst n: number = a;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn conditional_exports() {
+    let errors = check(
+        "exports",
+        "main.ts",
+        &[
+            ("package.json", r#"{ "type": "module" }"#),
+            (
+                "node_modules/pkg/package.json",
+                r#"{
+    "name": "pkg",
+    "exports": {
+        ".": {
+            "import": { "types": "./esm/index.d.mts" },
+            "require": { "types": "./cjs/index.d.cts" }
+        },
+        "./utils/*": { "types": "./utils/*.d.ts" },
+        "./internal/*": null
+    }
+}"#,
+            ),
+            ("node_modules/pkg/esm/index.d.mts", "export declare const esm: number;\n"),
+            ("node_modules/pkg/cjs/index.d.cts", "export declare const cjs: number;\n"),
+            ("node_modules/pkg/utils/strings.d.ts", "export declare const s: string;\n"),
+            ("node_modules/pkg/internal/secret.d.ts", "export declare const secret: string;\n"),
+            (
+                "main.ts",
+                "import { esm } from 'pkg';
+import { s } from 'pkg/utils/strings';
+
+const n: number = esm;
+const t: string = s;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn excluded_export() {
+    let errors = check(
+        "excluded",
+        "main.ts",
+        &[
+            (
+                "node_modules/pkg/package.json",
+                r#"{ "name": "pkg", "exports": { ".": "./index.d.ts", "./internal/*": null } }"#,
+            ),
+            ("node_modules/pkg/index.d.ts", "export {};\n"),
+            ("node_modules/pkg/internal/secret.d.ts", "export declare const secret: string;\n"),
+            ("main.ts", "import { secret } from 'pkg/internal/secret';\n"),
+        ],
+    );
+
+    assert!(
+        errors.iter().any(|err| matches!(&**err, ErrorKind::ModuleNotFound { .. })),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn subpath_imports() {
+    let errors = check(
+        "imports",
+        "src/main.ts",
+        &[
+            (
+                "package.json",
+                r##"{
+    "type": "module",
+    "imports": {
+        "#internal/*": "./src/internal/*.js",
+        "#dep": "dep"
+    }
+}"##,
+            ),
+            ("src/internal/util.ts", "export const util = 1;\n"),
+            ("node_modules/dep/package.json", r#"{ "name": "dep", "types": "./index.d.ts" }"#),
+            ("node_modules/dep/index.d.ts", "export declare const dep: string;\n"),
+            (
+                "src/main.ts",
+                "import { util } from '#internal/util';
+import { dep } from '#dep';
+
+const n: number = util;
+const s: string = dep;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn custom_conditions() {
+    let files = [
+        (
+            "node_modules/pkg/package.json",
+            r#"{ "name": "pkg", "exports": { "development": "./dev.d.ts", "default": "./index.d.ts" } }"#,
+        ),
+        ("node_modules/pkg/dev.d.ts", "export declare const dev: boolean;\n"),
+        ("node_modules/pkg/index.d.ts", "export {};\n"),
+        ("main.ts", "import { dev } from 'pkg';\n"),
+    ];
+
+    let errors = check_with_resolver(
+        Node16Resolver::new().with_custom_conditions(vec!["development".into()]),
+        "custom-conditions",
+        "main.ts",
+        &files,
+    );
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let errors = check("no-custom-conditions", "main.ts", &files);
+    assert!(!errors.is_empty(), "{:?}", errors);
+
+    let errors = check_with_resolver(
+        ModuleResolver::new(&ModuleConfig::new(ModuleKind::Node16).custom_conditions(vec!["development".into()])),
+        "configured-custom-conditions",
+        "main.ts",
+        &files,
+    );
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn resolver_of_module_resolution() {
+    let files = [
+        ("package.json", r#"{ "type": "module" }"#),
+        ("lib.ts", "export const a = 1;\n"),
+        ("main.ts", "import { a } from './lib';\n"),
+    ];
+
+    let errors = check_with_resolver(
+        ModuleResolver::new(&ModuleKind::Node16.into()),
+        "resolution-node16",
+        "main.ts",
+        &files,
+    );
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::ImportPathWithoutExtensionWithSuggestion { .. })),
+        "{:?}",
+        errors
+    );
+
+    let errors = check_with_resolver(
+        ModuleResolver::new(&ModuleConfig::new(ModuleKind::Node16).resolution(ModuleResolution::Node10)),
+        "resolution-node10",
+        "main.ts",
+        &files,
+    );
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -0,0 +1,133 @@
+use std::{fs, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::{node::NodeResolver, paths::PathsResolver};
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory, using
+/// `paths` relative to `baseUrl`, which is the temporary directory.
+fn check(name: &str, entry: &str, paths: &[(&str, &[&str])], files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-paths").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+    let path = dir.join(entry);
+
+    let paths = paths
+        .iter()
+        .map(|(pattern, substitutions)| (pattern.to_string(), substitutions.iter().map(|s| s.to_string()).collect()))
+        .collect();
+    let resolver = PathsResolver::new(NodeResolver, dir.clone(), Some(dir), paths);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, resolver.clone()),
+        );
+
+        checker.check(Arc::new(FileName::Real(path.clone())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn wildcard_mapping() {
+    let errors = check(
+        "wildcard",
+        "src/main.ts",
+        &[("@app/*", &["src/app/*"])],
+        &[
+            ("src/app/config.ts", "export const port = 1;\n"),
+            ("src/main.ts", "import { port } from '@app/config';\n\nconst n: number = port;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn fallback_substitutions() {
+    let errors = check(
+        "fallback",
+        "main.ts",
+        &[("*", &["generated/*", "*"])],
+        &[
+            ("generated/schema.ts", "export const schema = '';\n"),
+            ("util.ts", "export const util = 1;\n"),
+            (
+                "main.ts",
+                "import { schema } from 'schema';
+import { util } from 'util';
+
+const s: string = schema;
+const n: number = util;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn longest_prefix_wins() {
+    let errors = check(
+        "longest-prefix",
+        "main.ts",
+        &[("@lib/*", &["wrong/*"]), ("@lib/core/*", &["core/*"])],
+        &[
+            ("core/index.ts", "export const core = 1;\n"),
+            ("main.ts", "import { core } from '@lib/core/index';\n\nconst n: number = core;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn base_url() {
+    let errors = check(
+        "base-url",
+        "src/main.ts",
+        &[],
+        &[
+            ("lib/math.ts", "export const pi = 3.14;\n"),
+            ("src/main.ts", "import { pi } from 'lib/math';\n\nconst n: number = pi;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn mapped_file_not_found() {
+    let errors = check(
+        "not-found",
+        "main.ts",
+        &[("@app/*", &["src/*"])],
+        &[("main.ts", "import { a } from '@app/missing';\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::PathMappingTargetNotFound { pattern, .. } if &**pattern == "@app/*")),
+        "{:?}",
+        errors
+    );
+}
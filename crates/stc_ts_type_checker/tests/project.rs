@@ -0,0 +1,37 @@
+use std::{fs, sync::Arc};
+
+use stc_ts_config::TsConfig;
+use stc_ts_errors::ErrorKind;
+use stc_ts_type_checker::project::ProjectOptions;
+
+/// Only missing typing libraries are reported, not errors in them.
+#[test]
+fn missing_typings_are_reported() {
+    let dir = std::env::temp_dir().join("stc-project").join("typings");
+    fs::create_dir_all(dir.join("node_modules/@types/broken")).unwrap();
+    fs::write(
+        dir.join("node_modules/@types/broken/index.d.ts"),
+        "declare const broken: number;\ndeclare const value: string = broken;\n",
+    )
+    .unwrap();
+    let dir = dir.canonicalize().unwrap();
+
+    let config = TsConfig::parse(
+        &dir.join("tsconfig.json"),
+        r#"{ "compilerOptions": { "types": ["broken", "missing"] } }"#,
+    )
+    .unwrap();
+    let libs = config.libs_or_default();
+
+    let errors = testing::run_test2(false, |cm, handler| {
+        let (_, errors) = ProjectOptions::from_config(&config, &libs, None)
+            .checker(cm, Arc::new(handler), |resolver| resolver)
+            .unwrap();
+
+        Ok(errors)
+    })
+    .unwrap();
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(&*errors[0], ErrorKind::TypeDefinitionNotFound { name, .. } if &**name == "missing"));
+}
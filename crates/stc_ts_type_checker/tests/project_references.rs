@@ -0,0 +1,73 @@
+use std::{fs, sync::Arc};
+
+use fxhash::FxHashMap;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::{node::NodeResolver, references::ProjectReferencesResolver};
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Writes `files` into a temporary directory, and checks `app/main.ts`, which
+/// references the project in `core`.
+fn check(name: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-project-references").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+
+    let mut outputs = FxHashMap::default();
+    outputs.insert(dir.join("core/src/index.ts"), dir.join("core/dist/index.d.ts"));
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::CommonJs, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, ProjectReferencesResolver::new(NodeResolver, outputs)),
+        );
+
+        checker.check(Arc::new(FileName::Real(dir.join("app/main.ts"))));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const SOURCE: &str = "export const value: number = 1;\n";
+
+const MAIN: &str = "import { value } from '../core/src/index';\n\nexport const s: string = value;\n";
+
+#[test]
+fn import_uses_declaration_output() {
+    let errors = check(
+        "built",
+        &[
+            ("core/src/index.ts", SOURCE),
+            // The output differs from the source to tell which one is used.
+            ("core/dist/index.d.ts", "export declare const value: string;\n"),
+            ("app/main.ts", MAIN),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn import_without_declaration_output() {
+    let errors = check("not-built", &[("core/src/index.ts", SOURCE), ("app/main.ts", MAIN)]);
+
+    assert!(
+        errors.iter().any(|err| matches!(&**err, ErrorKind::OutputFileNotBuilt { .. })),
+        "{:?}",
+        errors
+    );
+}
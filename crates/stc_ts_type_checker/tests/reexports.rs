@@ -0,0 +1,204 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-reexports").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn namespace_reexport() {
+    let errors = check(
+        "namespace",
+        "main.ts",
+        &[
+            ("a.ts", "export const a = 1;\nexport interface A {\n    a: number;\n}\n"),
+            ("b.ts", "export * as ns from './a';\n"),
+            (
+                "main.ts",
+                "import { ns } from './b';
+
+const a: number = ns.a;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn deep_reexport_chain() {
+    let errors = check(
+        "chain",
+        "main.ts",
+        &[
+            ("a.ts", "export const a = 1;\nexport default 'a';\n"),
+            ("b.ts", "export * from './a';\nexport const b = '';\n"),
+            ("c.ts", "export * from './b';\nexport { a as renamed } from './a';\n"),
+            (
+                "main.ts",
+                "import { a, b, renamed } from './c';
+
+const x: number = a;
+const y: string = b;
+const z: number = renamed;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn default_is_not_reexported() {
+    let errors = check(
+        "default",
+        "main.ts",
+        &[
+            ("a.ts", "export default 1;\n"),
+            ("b.ts", "export * from './a';\n"),
+            ("main.ts", "import b from './b';\n"),
+        ],
+    );
+
+    assert!(!errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn ambiguous_star_exports() {
+    let errors = check(
+        "ambiguous",
+        "main.ts",
+        &[
+            ("a.ts", "export const x = 1;\n"),
+            ("b.ts", "export const x = '';\n"),
+            ("main.ts", "export * from './a';\nexport * from './b';\n"),
+        ],
+    );
+
+    assert!(
+        errors.iter().any(|err| matches!(&**err, ErrorKind::AmbiguousStarExport { .. })),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn local_export_wins_over_star_export() {
+    let errors = check(
+        "local",
+        "main.ts",
+        &[
+            ("a.ts", "export const x = 1;\n"),
+            ("b.ts", "export * from './a';\nexport const x = '';\n"),
+            ("main.ts", "import { x } from './b';\n\nconst s: string = x;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn circular_star_exports() {
+    let errors = check(
+        "circular",
+        "main.ts",
+        &[
+            ("a.ts", "export * from './b';\nexport const a = 1;\n"),
+            ("b.ts", "export * from './a';\nexport const b = '';\n"),
+            (
+                "main.ts",
+                "import { a, b } from './a';
+
+const x: number = a;
+const y: string = b;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn circular_star_exports_of_types() {
+    let errors = check(
+        "circular-types",
+        "main.ts",
+        &[
+            ("a.ts", "export * from './c';\nexport interface A {\n    b: import('./b').B;\n}\n"),
+            (
+                "b.ts",
+                "export * from './a';\nexport type B = { a: A };\nimport { A } from './a';\n",
+            ),
+            (
+                "c.ts",
+                "export * from './b';\nexport type C<T> = T extends Array<infer U> ? U : never;\n",
+            ),
+            (
+                "main.ts",
+                "import { A, B, C } from './a';
+
+declare const a: A;
+const b: B = a.b;
+const c: C<string[]> = '';
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn circular_namespace_exports() {
+    let errors = check(
+        "circular-namespaces",
+        "main.ts",
+        &[
+            ("a.ts", "export * as b from './b';\nexport const a = 1;\n"),
+            ("b.ts", "export * as a from './a';\nexport const b = '';\n"),
+            (
+                "main.ts",
+                "import { a, b } from './a';
+
+const x: number = a;
+const y: string = b.b;
+",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -0,0 +1,137 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-references").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn reference_path() {
+    let errors = check(
+        "path",
+        "main.ts",
+        &[
+            ("globals.d.ts", "declare var appVersion: string;\n"),
+            (
+                "main.ts",
+                "/// <reference path=\"globals.d.ts\" />\n\nconst version: string = appVersion;\n",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn reference_path_not_found() {
+    let errors = check(
+        "path-not-found",
+        "main.ts",
+        &[("main.ts", "/// <reference path='missing.d.ts'/>\n\nexport {};\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::ReferencedFileNotFound { path, .. } if &**path == "missing.d.ts")),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn reference_types() {
+    let errors = check(
+        "types",
+        "main.ts",
+        &[
+            ("node_modules/@types/env/index.d.ts", "declare var appVersion: string;\n"),
+            (
+                "main.ts",
+                "/// <reference types=\"env\" />\n\nconst version: string = appVersion;\n",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn reference_types_not_found() {
+    let errors = check(
+        "types-not-found",
+        "main.ts",
+        &[("main.ts", "/// <reference types=\"missing\" />\n\nexport {};\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::TypeDefinitionNotFound { name, .. } if &**name == "missing")),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn reference_lib() {
+    let errors = check(
+        "lib",
+        "main.ts",
+        &[(
+            "main.ts",
+            "/// <reference lib=\"es2015.promise\" />\n\nconst p = Promise.resolve(1);\n",
+        )],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn reference_lib_not_found() {
+    let errors = check(
+        "lib-not-found",
+        "main.ts",
+        &[("main.ts", "/// <reference lib=\"es2077\" />\n\nexport {};\n")],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::LibDefinitionNotFound { name, .. } if &**name == "es2077")),
+        "{:?}",
+        errors
+    );
+}
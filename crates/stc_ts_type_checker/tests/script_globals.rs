@@ -0,0 +1,139 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast::EsVersion;
+
+/// Writes `files` into a temporary directory and checks all of them, like
+/// root files of a program.
+fn check(name: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-script-globals").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+    let root_files = files.iter().map(|(file, _)| dir.join(file)).collect::<Vec<PathBuf>>();
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::CommonJs, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check_scripts(&root_files);
+        for file in &root_files {
+            checker.check(Arc::new(FileName::Real(file.clone())));
+        }
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn globals_of_scripts_are_shared() {
+    let errors = check(
+        "shared",
+        &[
+            // The module is checked after the scripts, although it's listed first.
+            (
+                "main.ts",
+                "export const n: number = count + double(1);\nexport const c: Counter = { value: n };\n",
+            ),
+            ("count.ts", "var count = 1;\ninterface Counter {\n    value: number;\n}\n"),
+            ("double.ts", "declare function double(n: number): number;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn var_can_be_declared_by_multiple_scripts() {
+    let errors = check("var", &[("a.ts", "var value = 1;\n"), ("b.ts", "var value = 2;\n")]);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn block_scoped_var_in_multiple_scripts() {
+    let errors = check("let", &[("a.ts", "let value = 1;\n"), ("b.ts", "const value = 2;\n")]);
+
+    let duplicates = errors
+        .iter()
+        .filter(|err| matches!(&***err, ErrorKind::DuplicateVar { name, .. } if &**name.sym() == "value"))
+        .count();
+    assert_eq!(duplicates, 2, "{:?}", errors);
+    assert!(errors.iter().all(|err| err.code() == 2451), "{:?}", errors);
+}
+
+#[test]
+fn modules_do_not_share_globals() {
+    let errors = check(
+        "modules",
+        &[("a.ts", "export let value = 1;\n"), ("b.ts", "export let value = 2;\n")],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+/// Declarations of the previous check are forgotten when a file is checked
+/// again, like in watch mode.
+#[test]
+fn recheck_with_same_env() {
+    let dir = std::env::temp_dir().join("stc-script-globals").join("recheck");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.ts"), "let value = 1;\n").unwrap();
+    fs::write(dir.join("b.ts"), "let other = 2;\n").unwrap();
+    let dir = dir.canonicalize().unwrap();
+    let root_files = vec![dir.join("a.ts"), dir.join("b.ts")];
+
+    testing::run_test2(false, |_, handler| {
+        let handler = Arc::new(handler);
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::CommonJs, &Lib::load("es5"));
+
+        for step in 0..2 {
+            if step == 1 {
+                // `value` is moved from `a.ts` to `b.ts`.
+                fs::write(dir.join("a.ts"), "let renamed = 1;\n").unwrap();
+                fs::write(dir.join("b.ts"), "let value = 2;\n").unwrap();
+            }
+
+            // Files are loaded again, like a new run of watch mode.
+            let cm = Arc::new(SourceMap::default());
+            let mut checker = Checker::new(
+                cm.clone(),
+                handler.clone(),
+                env.clone(),
+                None,
+                ModuleLoader::new(cm.clone(), env.clone(), NodeResolver),
+            );
+            checker.check_scripts(&root_files);
+
+            let errors = checker.take_errors();
+            assert!(errors.is_empty(), "step {}: {:?}", step, errors);
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn scripts_are_checked_once() {
+    // Root files are checked after `check_scripts`, like the `check` command.
+    let errors = check("once", &[("a.ts", "var a: string = 1;\n"), ("b.ts", "export {};\n")]);
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].code(), 2322);
+}
@@ -26,11 +26,12 @@ use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use serde::Deserialize;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_env::{Env, ModuleKind, Rule};
 use stc_ts_file_analyzer::env::EnvFactory;
 use stc_ts_module_loader::resolvers::node::NodeResolver;
 use stc_ts_type_checker::{loader::ModuleLoader, Checker};
 use stc_ts_utils::StcComments;
+use stc_utils::line_index::{PosEncoding, PosMapper};
 use swc_common::{
     errors::{DiagnosticBuilder, DiagnosticId},
     input::SourceFileInput,
@@ -328,7 +329,7 @@ struct TestSpec {
     ts_config: TsConfig,
     target: EsVersion,
     raw_target: String,
-    module_config: ModuleConfig,
+    module_config: ModuleKind,
 }
 
 fn parse_targets(s: &str) -> Vec<(String, EsVersion)> {
@@ -417,7 +418,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
             allow_unreachable_code: false,
             ..Default::default()
         };
-        let mut module_config = ModuleConfig::None;
+        let mut module_config = ModuleKind::None;
         let ts_config = TsConfig::default();
 
         let mut had_comment = false;
@@ -481,11 +482,28 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                 } else if s.starts_with("noEmitHelpers") {
                     // TODO
                 } else if s.starts_with("downlevelIteration:") {
-                    // TODO
+                    let v = s["downlevelIteration:".len()..].trim().parse().unwrap();
+                    rule.downlevel_iteration = v;
                 } else if s.starts_with("sourceMap:") || s.starts_with("sourcemap:") {
                     // TODO
                 } else if s.starts_with("isolatedModules:") {
-                    // TODO
+                    let v = s["isolatedModules:".len()..].trim().parse().unwrap();
+                    rule.isolated_modules = v;
+                } else if s.starts_with("esModuleInterop:") {
+                    let v = s["esModuleInterop:".len()..].trim().parse().unwrap();
+                    rule.es_module_interop = v;
+                } else if s.starts_with("allowSyntheticDefaultImports:") {
+                    let v = s["allowSyntheticDefaultImports:".len()..].trim().parse().unwrap();
+                    rule.allow_synthetic_default_imports = v;
+                } else if s.starts_with("verbatimModuleSyntax:") {
+                    let v = s["verbatimModuleSyntax:".len()..].trim().parse().unwrap();
+                    rule.verbatim_module_syntax = v;
+                } else if s.starts_with("resolveJsonModule:") {
+                    let v = s["resolveJsonModule:".len()..].trim().parse().unwrap();
+                    rule.resolve_json_module = v;
+                } else if s.starts_with("forceConsistentCasingInFileNames:") {
+                    let v = s["forceConsistentCasingInFileNames:".len()..].trim().parse().unwrap();
+                    rule.force_consistent_casing_in_file_names = v;
                 } else if s.starts_with("lib:") {
                     let s = s["lib:".len()..].trim();
                     let mut ls = HashSet::<_>::default();
@@ -687,11 +705,13 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
         // format!("{:?}", time)); }
     }
 
+    // Lines are counted like tsc, which breaks lines at `\r` too.
+    let positions = PosMapper::new(tester.cm.clone(), PosEncoding::Utf16);
     let mut extra_errors = diagnostics
         .iter()
         .map(|d| {
             let span = d.span.primary_span().unwrap();
-            let cp = tester.cm.lookup_char_pos(span.lo());
+            let line = positions.line_col(span.lo()).map(|(_, pos)| pos.line as usize + 1).unwrap_or(0);
             let code = d
                 .code
                 .clone()
@@ -703,7 +723,7 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
                 }
             };
 
-            (cp.line, code)
+            (line, code)
         })
         .collect::<Vec<_>>();
     extra_errors.sort();
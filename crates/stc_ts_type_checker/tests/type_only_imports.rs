@@ -0,0 +1,74 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-type-only-imports").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const LIB: &str = "export class Foo {}
+
+export interface Options {
+    debug: boolean;
+}
+";
+
+#[test]
+fn type_only_import_used_as_value() {
+    let errors = check(
+        "value",
+        "main.ts",
+        &[
+            ("lib.ts", LIB),
+            (
+                "main.ts",
+                "import type { Foo } from './lib';
+import { type Options } from './lib';
+
+const a: Foo = new Foo();
+const b: typeof Foo = Foo;
+const c: Options = { debug: true };
+",
+            ),
+        ],
+    );
+
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|err| matches!(&***err, ErrorKind::TypeOnlyImportUsedAsValue { .. }))
+            .count(),
+        2,
+        "{:?}",
+        errors
+    );
+}
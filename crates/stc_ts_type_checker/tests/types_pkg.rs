@@ -1,7 +1,7 @@
 use std::{env::current_dir, sync::Arc};
 
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_env::{Env, ModuleKind};
 use stc_ts_errors::ErrorKind;
 use stc_ts_file_analyzer::env::EnvFactory;
 use stc_ts_module_loader::resolvers::node::NodeResolver;
@@ -35,7 +35,7 @@ fn run_tests_for_types_pkg(module_specifier: &str) {
             .resolve(&FileName::Real(current_dir().unwrap()), module_specifier)
             .expect("failed to resolve entry");
 
-        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es2020"));
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es2020"));
         let mut checker = Checker::new(
             cm.clone(),
             handler.clone(),
@@ -0,0 +1,174 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::Error;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{
+    resolvers::{node::NodeResolver, node16::Node16Resolver},
+    version::TsVersion,
+};
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+use swc_ecma_loader::resolve::Resolve;
+
+/// Checks `entry` after writing `files` into a temporary directory.
+fn check<R>(resolver: R, module: ModuleKind, name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error>
+where
+    R: 'static + Sync + Send + Resolve,
+{
+    let dir = std::env::temp_dir().join("stc-types-versions").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), module, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, resolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(Path::new(&path).canonicalize().unwrap())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+/// The layout of `@types/node`, which redirects old versions of TypeScript to
+/// `ts4.8`.
+#[test]
+fn types_versions_of_old_compilers_are_ignored() {
+    let errors = check(
+        NodeResolver,
+        ModuleKind::CommonJs,
+        "old-compilers",
+        "main.ts",
+        &[
+            (
+                "node_modules/@types/node/package.json",
+                r#"{ "name": "@types/node", "types": "index.d.ts", "typesVersions": { "<=4.8": { "*": ["ts4.8/*"] } } }"#,
+            ),
+            ("node_modules/@types/node/index.d.ts", "export declare const version: string;\n"),
+            (
+                "node_modules/@types/node/ts4.8/index.d.ts",
+                "export declare const version: number;\n",
+            ),
+            ("main.ts", "import { version } from 'node';\n\nconst v: string = version;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+/// The layout of packages which ship declarations for new versions of
+/// TypeScript in a separate directory.
+#[test]
+fn types_versions_redirects_main_entry() {
+    let errors = check(
+        NodeResolver,
+        ModuleKind::CommonJs,
+        "main-entry",
+        "main.ts",
+        &[
+            (
+                "node_modules/@types/lib/package.json",
+                r#"{ "name": "@types/lib", "types": "index.d.ts", "typesVersions": { ">=4.5": { "*": ["ts4.5/*"] } } }"#,
+            ),
+            ("node_modules/@types/lib/index.d.ts", "export declare const value: number;\n"),
+            ("node_modules/@types/lib/ts4.5/index.d.ts", "export declare const value: string;\n"),
+            ("main.ts", "import { value } from 'lib';\n\nconst v: string = value;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn types_versions_redirects_subpath() {
+    let errors = check(
+        NodeResolver,
+        ModuleKind::CommonJs,
+        "subpath",
+        "main.ts",
+        &[
+            (
+                "node_modules/lib/package.json",
+                r#"{ "name": "lib", "typesVersions": { "*": { "utils": ["types/utils.d.ts"] } } }"#,
+            ),
+            ("node_modules/lib/index.d.ts", "export {};\n"),
+            ("node_modules/lib/types/utils.d.ts", "export declare function noop(): void;\n"),
+            ("main.ts", "import { noop } from 'lib/utils';\n\nnoop();\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn types_versions_with_configured_version() {
+    let files = &[
+        (
+            "node_modules/lib/package.json",
+            r#"{ "name": "lib", "types": "index.d.ts", "typesVersions": { "<4.0": { "*": ["ts3.9/*"] } } }"#,
+        ),
+        ("node_modules/lib/index.d.ts", "export declare const value: string;\n"),
+        ("node_modules/lib/ts3.9/index.d.ts", "export declare const value: number;\n"),
+        ("main.ts", "import { value } from 'lib';\n\nconst v: number = value;\n"),
+    ];
+
+    let errors = check(
+        Node16Resolver::new().with_ts_version(TsVersion::new(3, 9, 0)),
+        ModuleKind::Node16,
+        "configured-version",
+        "main.ts",
+        files,
+    );
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let errors = check(Node16Resolver::new(), ModuleKind::Node16, "configured-version", "main.ts", files);
+    assert!(!errors.is_empty(), "ts3.9/index.d.ts should not be used by default");
+}
+
+#[test]
+fn versioned_types_condition() {
+    let files = &[
+        (
+            "node_modules/lib/package.json",
+            r#"{
+                "name": "lib",
+                "exports": {
+                    ".": {
+                        "types@<4.7": "./ts4.6/index.d.ts",
+                        "types": "./index.d.ts",
+                        "default": "./index.js"
+                    }
+                }
+            }"#,
+        ),
+        ("node_modules/lib/index.d.ts", "export declare const value: string;\n"),
+        ("node_modules/lib/ts4.6/index.d.ts", "export declare const value: number;\n"),
+        ("main.ts", "import { value } from 'lib';\n\nconst v: string = value;\n"),
+    ];
+
+    let errors = check(Node16Resolver::new(), ModuleKind::Node16, "versioned-condition", "main.ts", files);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let errors = check(
+        Node16Resolver::new().with_ts_version(TsVersion::new(4, 6, 0)),
+        ModuleKind::Node16,
+        "versioned-condition",
+        "main.ts",
+        files,
+    );
+    assert!(!errors.is_empty(), "ts4.6/index.d.ts should be used by TypeScript 4.6");
+}
@@ -0,0 +1,112 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Writes `files` into a temporary directory, loads typings like the `types`
+/// and `typeRoots` options, and then checks `entry`.
+fn check(name: &str, entry: &str, type_roots: Option<&[&str]>, types: Option<&[&str]>, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-typings").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+    let path = dir.join(entry);
+
+    let type_roots = type_roots.map(|roots| roots.iter().map(|root| dir.join(root)).collect::<Vec<PathBuf>>());
+    let types = types.map(|types| types.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::None, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.load_typings(Path::new(&dir), type_roots.as_deref(), types.as_deref());
+        checker.check(Arc::new(FileName::Real(path.clone())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn global_declarations_of_types_package() {
+    let errors = check(
+        "globals",
+        "main.ts",
+        None,
+        None,
+        &[
+            (
+                "node_modules/@types/env/index.d.ts",
+                "declare var appVersion: string;\ninterface AppConfig {\n    debug: boolean;\n}\n",
+            ),
+            ("main.ts", "const v: string = appVersion;\nconst c: AppConfig = { debug: true };\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn scoped_types_package() {
+    let errors = check(
+        "scoped",
+        "main.ts",
+        None,
+        Some(&[]),
+        &[
+            ("node_modules/@types/scope__pkg/index.d.ts", "export declare const value: number;\n"),
+            ("main.ts", "import { value } from '@scope/pkg';\n\nconst n: number = value;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn custom_type_roots() {
+    let errors = check(
+        "type-roots",
+        "main.ts",
+        Some(&["typings"]),
+        None,
+        &[
+            ("typings/env/index.d.ts", "declare var appVersion: string;\n"),
+            ("main.ts", "const v: string = appVersion;\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn missing_types_package() {
+    let errors = check("missing", "main.ts", None, Some(&["missing"]), &[("main.ts", "export {};\n")]);
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::TypeDefinitionNotFound { name, .. } if &**name == "missing")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,129 @@
+use std::{fs, path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind};
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+/// Writes `files` into a temporary directory, loads typings in
+/// `node_modules/@types`, and then checks `entry`.
+fn check(name: &str, entry: &str, files: &[(&str, &str)]) -> Vec<Error> {
+    let dir = std::env::temp_dir().join("stc-umd").join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    let dir = dir.canonicalize().unwrap();
+    let path = dir.join(entry);
+
+    testing::run_test2(false, |cm, handler| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleKind::CommonJs, &Lib::load("es5"));
+        let mut checker = Checker::new(
+            cm.clone(),
+            Arc::new(handler),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm, env, NodeResolver),
+        );
+
+        checker.load_typings(Path::new(&dir), None, None);
+        checker.check(Arc::new(FileName::Real(path.clone())));
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+const UMD_LIB: &str =
+    "export declare function greet(): string;\nexport interface Options {\n    loud: boolean;\n}\nexport as namespace Greeter;\n";
+
+const UMD_EXPORT_EQUALS_LIB: &str =
+    "declare namespace Greeter {\n    function greet(): string;\n}\nexport = Greeter;\nexport as namespace Greeter;\n";
+
+#[test]
+fn umd_global_in_script() {
+    let errors = check(
+        "script",
+        "main.ts",
+        &[
+            ("node_modules/@types/greeter/index.d.ts", UMD_LIB),
+            (
+                "main.ts",
+                "const s: string = Greeter.greet();\nconst options: Greeter.Options = { loud: true };\n",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn umd_global_with_export_equals() {
+    let errors = check(
+        "export-equals",
+        "main.ts",
+        &[
+            ("node_modules/@types/greeter/index.d.ts", UMD_EXPORT_EQUALS_LIB),
+            ("main.ts", "const s: string = Greeter.greet();\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn umd_global_in_module() {
+    let errors = check(
+        "module",
+        "main.ts",
+        &[
+            ("node_modules/@types/greeter/index.d.ts", UMD_LIB),
+            ("main.ts", "export const s: string = Greeter.greet();\n"),
+        ],
+    );
+
+    assert!(
+        errors
+            .iter()
+            .any(|err| matches!(&**err, ErrorKind::UmdGlobalInModule { name, .. } if &**name == "Greeter")),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn umd_type_in_module() {
+    let errors = check(
+        "module-type",
+        "main.ts",
+        &[
+            ("node_modules/@types/greeter/index.d.ts", UMD_LIB),
+            ("main.ts", "export const options: Greeter.Options = { loud: true };\n"),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn umd_module_imported() {
+    let errors = check(
+        "imported",
+        "main.ts",
+        &[
+            ("node_modules/@types/greeter/index.d.ts", UMD_LIB),
+            (
+                "main.ts",
+                "import * as Greeter from 'greeter';\n\nexport const s: string = Greeter.greet();\n",
+            ),
+        ],
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
@@ -24,3 +24,24 @@ pub fn prevent_generalize(ty: &mut Type) {
         },
     )
 }
+
+/// Prevents generalization of literal types which are not fresh, like `"a"` of
+/// `declare const a: "a"`.
+///
+/// This should be called before generalizing the type of an expression stored
+/// to a mutable location, as only fresh literal types are widened.
+#[instrument(skip_all)]
+pub fn prevent_generalize_non_fresh(ty: &mut Type) {
+    replace_type(
+        ty,
+        |ty| match ty.normalize() {
+            Type::Lit(LitType { metadata, .. }) => !metadata.fresh && !metadata.common.prevent_generalization,
+            _ => false,
+        },
+        |ty| {
+            let mut ty = ty.take();
+            ty.metadata_mut().prevent_generalization = true;
+            Some(ty)
+        },
+    )
+}
@@ -8,7 +8,7 @@ use stc_ts_types::{
 use stc_utils::ext::TypeVecExt;
 use swc_ecma_ast::TsKeywordTypeKind;
 
-pub use self::metadata::prevent_generalize;
+pub use self::metadata::{prevent_generalize, prevent_generalize_non_fresh};
 
 mod metadata;
 
@@ -368,6 +368,7 @@ impl ObjectUnionNormalizer {
                                 type_params: Default::default(),
                                 metadata: Default::default(),
                                 accessor: Default::default(),
+                                write_type: Default::default(),
                             }))
                         }
                     }
@@ -405,6 +406,7 @@ impl ObjectUnionNormalizer {
                                     type_params: Default::default(),
                                     metadata: Default::default(),
                                     accessor: Default::default(),
+                                    write_type: Default::default(),
                                 }));
                                 idx
                             }
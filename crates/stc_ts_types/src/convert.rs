@@ -269,11 +269,22 @@ impl From<Tuple> for RTsType {
 
 impl From<TupleElement> for RTsTupleElement {
     fn from(e: TupleElement) -> Self {
+        let mut label = e.label;
+        let mut ty = e.ty;
+
+        // `name: T?` is not valid, so it's printed as `name?: T`.
+        if let Some(RPat::Ident(i)) = &mut label {
+            if let Type::Optional(o) = *ty {
+                i.id.optional = true;
+                ty = o.ty;
+            }
+        }
+
         RTsTupleElement {
             node_id: NodeId::invalid(),
             span: e.span,
-            label: e.label,
-            ty: e.ty.into(),
+            label,
+            ty: ty.into(),
         }
     }
 }
@@ -16,21 +16,21 @@ use std::{
     fmt::{Debug, Formatter},
     iter::FusedIterator,
     mem::{replace, transmute},
-    ops::AddAssign,
+    ops::{AddAssign, ControlFlow},
 };
 
 use fxhash::FxHashMap;
 use is_macro::Is;
 use num_bigint::BigInt;
 use num_traits::Zero;
-use rnode::{FoldWith, VisitMut, VisitMutWith, VisitWith};
+use rnode::{FoldWith, TryVisitWith, TryVisitor, VisitMut, VisitMutWith, VisitWith};
 use scoped_tls::scoped_thread_local;
 use serde::{Deserialize, Serialize};
 use static_assertions::assert_eq_size;
 use stc_arc_cow::freeze::Freezer;
 use stc_ts_ast_rnode::{
-    RBigInt, RExpr, RIdent, RNumber, RPat, RPrivateName, RStr, RTplElement, RTsEntityName, RTsEnumMemberId, RTsKeywordType, RTsLit,
-    RTsModuleName, RTsNamespaceDecl, RTsThisType, RTsThisTypeOrIdent,
+    RBigInt, RBindingIdent, RExpr, RIdent, RMemberExpr, RMemberProp, RNumber, RPat, RPrivateName, RStr, RTplElement, RTsEntityName,
+    RTsEnumMemberId, RTsKeywordType, RTsLit, RTsModuleName, RTsNamespaceDecl, RTsThisType, RTsThisTypeOrIdent,
 };
 use stc_utils::{
     cache::{Freeze, ALLOW_DEEP_CLONE},
@@ -57,6 +57,7 @@ pub use self::{
     intrinsic::{IntrinsicKind, StringMapping},
     metadata::*,
     module_id::ModuleId,
+    union::UnionMembers,
 };
 
 mod convert;
@@ -70,6 +71,7 @@ pub mod name;
 pub mod replace;
 mod tracker;
 pub mod type_id;
+mod union;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum IdCtx {
@@ -84,6 +86,30 @@ pub struct ModuleTypeData {
 
     pub private_types: FxHashMap<Id, Vec<Type>>,
     pub types: FxHashMap<JsWord, Vec<Type>>,
+
+    /// `export = foo`
+    pub export_equals: Option<ExportEquals>,
+
+    /// Declarations with `@deprecated`.
+    pub private_deprecations: FxHashMap<Id, Deprecation>,
+    /// Exports declared with `@deprecated`, which are reported when they are
+    /// used by other modules.
+    pub deprecations: FxHashMap<JsWord, Deprecation>,
+}
+
+/// A `@deprecated` tag of a declaration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub decl_span: Span,
+    /// The text after `@deprecated`, if any.
+    pub message: Option<JsWord>,
+}
+
+/// The entity exported by `export = foo`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportEquals {
+    pub var: Option<Type>,
+    pub types: Vec<Type>,
 }
 
 impl Visitable for ModuleTypeData {}
@@ -92,6 +118,12 @@ impl<V: ?Sized> VisitWith<V> for ModuleTypeData {
     fn visit_children_with(&self, _: &mut V) {}
 }
 
+impl<V: ?Sized + TryVisitor> TryVisitWith<V> for ModuleTypeData {
+    fn try_visit_children_with(&self, _: &mut V) -> ControlFlow<V::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
 impl<V: ?Sized> VisitMutWith<V> for ModuleTypeData {
     fn visit_mut_children_with(&mut self, _: &mut V) {}
 }
@@ -123,6 +155,10 @@ impl AddAssign for ModuleTypeData {
 
         self.vars.extend(other.vars);
         self.private_vars.extend(other.private_vars);
+
+        if other.export_equals.is_some() {
+            self.export_equals = other.export_equals;
+        }
     }
 }
 
@@ -388,6 +424,12 @@ impl TypeEq for Key {
 
             (Key::Private(..), _) | (_, Key::Private(..)) => false,
 
+            (Key::Computed(a), Key::Computed(b)) => match (a.well_known_symbol(), b.well_known_symbol()) {
+                (Some(a), Some(b)) => a == b,
+                (Some(..), None) | (None, Some(..)) => false,
+                (None, None) => a.ty.type_eq(&b.ty),
+            },
+
             (Key::Computed(ComputedKey { ty: a, .. }), b) | (b, Key::Computed(ComputedKey { ty: a, .. })) => match b {
                 Key::Computed(b) => a.type_eq(&b.ty),
                 Key::Normal { sym, .. } => match &**a {
@@ -534,9 +576,38 @@ pub struct ComputedKey {
     pub ty: Box<Type>,
 }
 
+impl ComputedKey {
+    /// Returns the well-known symbol used as the key, like `Symbol.iterator`
+    /// of `[Symbol.iterator]`.
+    ///
+    /// The expression is checked if the type of the key is not a symbol,
+    /// because the type of `Symbol.iterator` can be a `unique symbol` or a
+    /// union of them if the libs declare it more than once.
+    pub fn well_known_symbol(&self) -> Option<SymbolId> {
+        if let Type::Symbol(Symbol { id, .. }) = self.ty.normalize() {
+            return id.known_name().map(|_| *id);
+        }
+
+        match &*self.expr {
+            RExpr::Member(RMemberExpr {
+                obj: box RExpr::Ident(RIdent {
+                    sym: js_word!("Symbol"), ..
+                }),
+                prop: RMemberProp::Ident(RIdent { sym, .. }),
+                ..
+            }) => SymbolId::try_known(sym),
+            _ => None,
+        }
+    }
+}
+
 impl TypeEq for ComputedKey {
     fn type_eq(&self, other: &Self) -> bool {
-        self.ty.type_eq(&other.ty)
+        match (self.well_known_symbol(), other.well_known_symbol()) {
+            (Some(l), Some(r)) => l == r,
+            (Some(..), None) | (None, Some(..)) => false,
+            (None, None) => self.ty.type_eq(&other.ty),
+        }
     }
 }
 
@@ -581,7 +652,7 @@ pub struct LitType {
 }
 
 #[cfg(target_pointer_width = "64")]
-assert_eq_size!(LitType, [u8; 96]);
+assert_eq_size!(LitType, [u8; 104]);
 
 #[derive(Debug, Clone, PartialEq, Eq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
 pub struct KeywordType {
@@ -754,6 +825,8 @@ assert_eq_size!(Module, [u8; 72]);
 #[derive(Debug, Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
 pub struct Enum {
     pub span: Span,
+    /// `true` for `declare enum` and enums in ambient contexts, like `.d.ts`
+    /// files.
     pub declare: bool,
     pub is_const: bool,
     #[use_eq_ignore_span]
@@ -843,6 +916,8 @@ pub struct Method {
     pub type_params: Option<TypeParamDecl>,
     pub params: Vec<FnParam>,
     pub ret_ty: Box<Type>,
+    /// Declared with `@deprecated`.
+    pub deprecated: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
@@ -857,8 +932,15 @@ pub struct ClassProperty {
     pub is_optional: bool,
     pub readonly: bool,
     pub definite: bool,
+    /// Declared with `@deprecated`.
+    pub deprecated: bool,
 
     pub accessor: Accessor,
+    /// Type used for writes, if it differs from `value`.
+    ///
+    /// This is [Some] only if the parameter type of the setter is different
+    /// from the return type of the getter.
+    pub write_type: Option<Box<Type>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
@@ -961,6 +1043,63 @@ impl Debug for TupleElement {
     }
 }
 
+impl TupleElement {
+    /// Converts a parameter to an element of the tuple of parameters, like
+    /// `Parameters<typeof f>`.
+    ///
+    /// The name of the parameter becomes the label, and optional or rest
+    /// parameters become optional or rest elements.
+    pub fn from_param(param: &FnParam) -> Self {
+        let (label, ty) = match &param.pat {
+            RPat::Rest(rest) => (
+                rest.arg.clone(),
+                Type::Rest(RestType {
+                    span: param.span,
+                    ty: param.ty.clone(),
+                    metadata: Default::default(),
+                    tracker: Default::default(),
+                }),
+            ),
+            pat => {
+                let ty = if param.required {
+                    (*param.ty).clone()
+                } else {
+                    Type::Optional(OptionalType {
+                        span: param.span,
+                        ty: param.ty.clone(),
+                        metadata: Default::default(),
+                        tracker: Default::default(),
+                    })
+                };
+
+                (box pat.clone(), ty)
+            }
+        };
+
+        // Only identifiers can be labels.
+        let label = match *label {
+            RPat::Ident(i) => Some(RPat::Ident(RBindingIdent {
+                id: RIdent { optional: false, ..i.id },
+                type_ann: None,
+                ..i
+            })),
+            _ => None,
+        };
+
+        TupleElement {
+            span: param.span,
+            label,
+            ty: box ty,
+            tracker: Default::default(),
+        }
+    }
+
+    /// Returns `true` for `T?` and `name?: T`.
+    pub fn is_optional(&self) -> bool {
+        matches!(self.ty.normalize(), Type::Optional(..))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
 pub struct Alias {
     pub span: Span,
@@ -1109,6 +1248,11 @@ pub struct PropertySignature {
     pub metadata: TypeElMetadata,
 
     pub accessor: Accessor,
+    /// Type used for writes, if it differs from `type_ann`.
+    ///
+    /// This is [Some] only if the parameter type of the setter is different
+    /// from the return type of the getter.
+    pub write_type: Option<Box<Type>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
@@ -1445,7 +1589,7 @@ impl Type {
     }
 
     pub fn new_union<I: IntoIterator<Item = Self> + Debug>(span: Span, iter: I) -> Self {
-        let mut elements = vec![];
+        let mut elements = UnionMembers::default();
 
         for ty in iter {
             if ty.is_unknown() {
@@ -1461,18 +1605,14 @@ impl Type {
             if ty.is_union_type() {
                 let types = ty.expect_union_type().types;
                 for new in types {
-                    if elements.iter().any(|prev: &Type| prev.type_eq(&new)) {
-                        continue;
-                    }
-                    elements.push(new)
+                    elements.push(new);
                 }
             } else {
-                if elements.iter().any(|prev: &Type| prev.type_eq(&ty)) {
-                    continue;
-                }
-                elements.push(ty)
+                elements.push(ty);
             }
         }
+        elements.reduce();
+        let mut elements = elements.into_vec();
         // Drop `never`s.
         elements.retain(|ty| !ty.is_never());
 
@@ -1487,7 +1627,7 @@ impl Type {
     pub fn union<I: IntoIterator<Item = Self> + Debug>(iter: I) -> Self {
         let mut span = DUMMY_SP;
 
-        let mut elements = vec![];
+        let mut elements = UnionMembers::default();
 
         for ty in iter {
             let sp = ty.span();
@@ -1502,18 +1642,14 @@ impl Type {
             if ty.is_union_type() {
                 let types = ty.expect_union_type().types;
                 for new in types {
-                    if elements.iter().any(|prev: &Type| prev.type_eq(&new)) {
-                        continue;
-                    }
-                    elements.push(new)
+                    elements.push(new);
                 }
             } else {
-                if elements.iter().any(|prev: &Type| prev.type_eq(&ty)) {
-                    continue;
-                }
-                elements.push(ty)
+                elements.push(ty);
             }
         }
+        elements.reduce();
+        let mut elements = elements.into_vec();
         // Drop `never`s.
         elements.retain(|ty| !ty.is_never());
 
@@ -2776,6 +2912,16 @@ where
     }
 }
 
+impl<V> TryVisitWith<V> for Freezed
+where
+    V: ?Sized + TryVisitor,
+{
+    #[inline]
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.ty.try_visit_with(visitor)
+    }
+}
+
 impl<V> VisitMutWith<V> for Freezed
 where
     V: ?Sized,
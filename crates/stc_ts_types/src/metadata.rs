@@ -3,7 +3,9 @@
 //! All metadata structs should **derive** [Default].
 //! It means, all field should be `false` by default.
 
-use rnode::{FoldWith, VisitMutWith, VisitWith};
+use std::ops::ControlFlow;
+
+use rnode::{FoldWith, TryVisitWith, TryVisitor, VisitMutWith, VisitWith};
 use serde::{Deserialize, Serialize};
 use stc_visit::Visitable;
 use swc_common::{EqIgnoreSpan, TypeEq};
@@ -59,6 +61,14 @@ macro_rules! impl_basic_traits {
             #[inline]
             fn visit_mut_children_with(&mut self, _: &mut F) {}
         }
+
+        /// Noop.
+        impl<F: ?Sized + TryVisitor> TryVisitWith<F> for $T {
+            #[inline]
+            fn try_visit_children_with(&self, _: &mut F) -> ControlFlow<F::Break> {
+                ControlFlow::Continue(())
+            }
+        }
     };
 }
 
@@ -129,6 +139,13 @@ impl_traits!(KeywordTypeMetadata);
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LitTypeMetadata {
     pub common: CommonTypeMetadata,
+
+    /// `true` for the type of a literal expression, like `"a"` in `let a =
+    /// "a"`.
+    ///
+    /// Fresh literal types are widened when stored to a mutable location,
+    /// while literal types from type annotations are preserved.
+    pub fresh: bool,
 }
 
 impl_traits!(LitTypeMetadata);
@@ -402,6 +419,9 @@ pub struct TypeElMetadata {
     /// While assignment, missing property error will not occur by the element
     /// with this flag set to `true`.
     pub has_default: bool,
+
+    /// Declared with `@deprecated`.
+    pub deprecated: bool,
 }
 
 impl_basic_traits!(TypeElMetadata);
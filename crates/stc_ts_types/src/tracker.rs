@@ -1,6 +1,6 @@
-use std::panic::Location;
+use std::{ops::ControlFlow, panic::Location};
 
-use rnode::{FoldWith, VisitMutWith, VisitWith};
+use rnode::{FoldWith, TryVisitWith, TryVisitor, VisitMutWith, VisitWith};
 use serde::{Deserialize, Serialize};
 use stc_visit::Visitable;
 use swc_common::{EqIgnoreSpan, TypeEq};
@@ -49,6 +49,13 @@ impl<const N: &'static str, V: ?Sized> VisitWith<V> for Tracker<N> {
     fn visit_children_with(&self, _: &mut V) {}
 }
 
+impl<const N: &'static str, V: ?Sized + TryVisitor> TryVisitWith<V> for Tracker<N> {
+    #[inline]
+    fn try_visit_children_with(&self, _: &mut V) -> ControlFlow<V::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
 impl<const N: &'static str, V: ?Sized> VisitMutWith<V> for Tracker<N> {
     #[inline]
     fn visit_mut_children_with(&mut self, _: &mut V) {}
@@ -25,6 +25,12 @@ impl SymbolId {
 
         SymbolId(id, static_name)
     }
+
+    /// Returns the name of a well-known symbol, like `iterator` for
+    /// `Symbol.iterator`.
+    pub fn known_name(self) -> Option<&'static str> {
+        self.1
+    }
 }
 
 impl Serialize for SymbolId {
@@ -71,14 +77,22 @@ macro_rules! known {
             )*
 
             pub fn known(s:&str) -> Self {
+                match Self::try_known(s) {
+                    Some(v) => v,
+                    None => {
+                        unreachable!("Unknown builtin symbol {}", s)
+                    }
+                }
+            }
+
+            /// Returns the well-known symbol named `s`, like `iterator`.
+            pub fn try_known(s:&str) -> Option<Self> {
                 match s {
                     $(
-                        $str_name => Self::$name(),
+                        $str_name => Some(Self::$name()),
                     )*
 
-                    _ => {
-                        unreachable!("Unknown builtin symbol {}", s)
-                    }
+                    _ => None,
                 }
             }
         }
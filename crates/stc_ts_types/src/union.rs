@@ -0,0 +1,153 @@
+//! Deduplication and reduction of members of union types.
+
+use fxhash::FxHashSet;
+use num_bigint::BigInt;
+use stc_ts_ast_rnode::{RBigInt, RBool, RNumber, RStr, RTsLit};
+use swc_atoms::JsWord;
+use swc_common::TypeEq;
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::{Intersection, KeywordType, LitType, Type};
+
+/// Members of a union type, without duplicates.
+///
+/// Keywords and literals are compared by hash, so a union of thousands of
+/// literals is built in linear time. Other types are compared using
+/// [TypeEq].
+#[derive(Debug, Default)]
+pub struct UnionMembers {
+    types: Vec<Type>,
+    keys: FxHashSet<MemberKey>,
+    /// Indices of the members without [MemberKey].
+    others: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum MemberKey {
+    Keyword(TsKeywordTypeKind),
+    Str(JsWord),
+    /// Bits of the value, with `-0` normalized to `0`.
+    Num(u64),
+    Bool(bool),
+    BigInt(Box<BigInt>),
+}
+
+impl MemberKey {
+    fn of(ty: &Type) -> Option<Self> {
+        Some(match ty.normalize() {
+            Type::Keyword(KeywordType { kind, .. }) => MemberKey::Keyword(*kind),
+            Type::Lit(LitType { lit, .. }) => match lit {
+                RTsLit::Str(RStr { value, .. }) => MemberKey::Str(value.clone()),
+                RTsLit::Number(RNumber { value, .. }) => MemberKey::Num(if *value == 0.0 { 0 } else { value.to_bits() }),
+                RTsLit::Bool(RBool { value, .. }) => MemberKey::Bool(*value),
+                RTsLit::BigInt(RBigInt { value, .. }) => MemberKey::BigInt(value.clone()),
+                RTsLit::Tpl(..) => return None,
+            },
+            _ => return None,
+        })
+    }
+}
+
+impl UnionMembers {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            types: Vec::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Returns `false` if `ty` is already a member.
+    pub fn push(&mut self, ty: Type) -> bool {
+        match MemberKey::of(&ty) {
+            Some(key) => {
+                if !self.keys.insert(key) {
+                    return false;
+                }
+            }
+            None => {
+                if self.others.iter().any(|&idx| self.types[idx].type_eq(&ty)) {
+                    return false;
+                }
+                self.others.push(self.types.len());
+            }
+        }
+
+        self.types.push(ty);
+        true
+    }
+
+    fn contains(&self, ty: &Type) -> bool {
+        match MemberKey::of(ty) {
+            Some(key) => self.keys.contains(&key),
+            None => self.others.iter().any(|&idx| self.types[idx].type_eq(ty)),
+        }
+    }
+
+    /// Removes members which are subtypes of another member.
+    ///
+    /// Literals are removed if their primitive type is a member, and
+    /// intersections are removed if one of their members is a member of the
+    /// union.
+    ///
+    /// ```text
+    /// "a" | string => string
+    /// A | (A & B) => A
+    /// ```
+    ///
+    /// Other members are kept, even if they are subtypes of another member.
+    pub fn reduce(&mut self) {
+        let has = |kind| self.keys.contains(&MemberKey::Keyword(kind));
+        let (has_str, has_num, has_bool, has_bigint) = (
+            has(TsKeywordTypeKind::TsStringKeyword),
+            has(TsKeywordTypeKind::TsNumberKeyword),
+            has(TsKeywordTypeKind::TsBooleanKeyword),
+            has(TsKeywordTypeKind::TsBigIntKeyword),
+        );
+        let has_intersection = self.others.iter().any(|&idx| self.types[idx].normalize().is_intersection());
+
+        // Fast path.
+        if !has_str && !has_num && !has_bool && !has_bigint && !has_intersection {
+            return;
+        }
+
+        let removed = self
+            .types
+            .iter()
+            .map(|ty| match ty.normalize() {
+                Type::Lit(LitType { lit, .. }) => match lit {
+                    RTsLit::Str(..) | RTsLit::Tpl(..) => has_str,
+                    RTsLit::Number(..) => has_num,
+                    RTsLit::Bool(..) => has_bool,
+                    RTsLit::BigInt(..) => has_bigint,
+                },
+                Type::Tpl(..) => has_str,
+                Type::Intersection(Intersection { types, .. }) => types.iter().any(|ty| self.contains(ty)),
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+
+        if !removed.contains(&true) {
+            return;
+        }
+
+        let types = std::mem::take(&mut self.types);
+        *self = Self::with_capacity(types.len());
+        for (ty, removed) in types.into_iter().zip(removed) {
+            if !removed {
+                self.push(ty);
+            }
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<Type> {
+        self.types
+    }
+}
@@ -8,24 +8,32 @@ use swc_common::{
 pub enum ImportRef {
     /// path="foo"
     Path(JsWord),
-    /// type="foo"
+    /// types="foo"
     Types(JsWord),
+    /// lib="foo"
+    Lib(JsWord),
 
     /// ES6 import.
     Normal(JsWord),
 }
 
 impl ImportRef {
-    pub fn to_path(self) -> JsWord {
+    /// Returns the module specifier to resolve, or [None] for `lib`, which
+    /// refers to a builtin library instead of a file.
+    pub fn to_path(self) -> Option<JsWord> {
         match self {
-            ImportRef::Path(s) => format!("./{}", s).into(),
-            ImportRef::Types(s) => s,
-            ImportRef::Normal(s) => s,
+            ImportRef::Path(s) if s.starts_with("./") || s.starts_with("../") || s.starts_with('/') => Some(s),
+            ImportRef::Path(s) => Some(format!("./{}", s).into()),
+            ImportRef::Types(s) => Some(s),
+            ImportRef::Lib(..) => None,
+            ImportRef::Normal(s) => Some(s),
         }
     }
 }
 
-pub fn find_imports_in_comments<C>(comments: C, span: Span) -> Vec<ImportRef>
+/// Returns triple-slash directives in the leading comments of `span`, with
+/// the spans of the comments.
+pub fn find_imports_in_comments<C>(comments: C, span: Span) -> Vec<(Span, ImportRef)>
 where
     C: Comments,
 {
@@ -43,25 +51,44 @@ where
             if c.kind != CommentKind::Line {
                 continue;
             }
-            if let Some(cmt_text) = c
-                .text
-                .trim()
-                .strip_prefix('/')
-                .map(|s| s.trim())
-                .and_then(|s| s.strip_prefix("<reference"))
-                .and_then(|s| s.strip_suffix("\" />"))
-                .map(|s| s.trim())
-            {
-                if let Some(path) = cmt_text.strip_prefix("path=\"") {
-                    deps.push(ImportRef::Path(path.into()));
-                } else if let Some(path) = cmt_text.strip_prefix("types=\"") {
-                    deps.push(ImportRef::Types(path.into()));
-                } else {
-                    // TODO: Handle lib, types
-                }
+            if let Some(directive) = parse_reference_directive(&c.text) {
+                deps.push((c.span, directive));
             }
         }
     });
 
     deps
 }
+
+/// Parses a triple-slash directive like `/ <reference path="foo.d.ts" />`,
+/// which is the text of a line comment.
+fn parse_reference_directive(text: &str) -> Option<ImportRef> {
+    let attrs = text
+        .strip_prefix('/')?
+        .trim()
+        .strip_prefix("<reference")?
+        .trim_end()
+        .strip_suffix("/>")?;
+
+    if let Some(path) = attr(attrs, "path") {
+        Some(ImportRef::Path(path.into()))
+    } else if let Some(name) = attr(attrs, "types") {
+        Some(ImportRef::Types(name.into()))
+    } else {
+        attr(attrs, "lib").map(|name| ImportRef::Lib(name.to_ascii_lowercase().into()))
+    }
+}
+
+/// Returns the value of `name="value"` or `name='value'` in `attrs`.
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    attrs
+        .match_indices(name)
+        .filter(|(idx, _)| attrs[..*idx].ends_with(char::is_whitespace))
+        .find_map(|(idx, _)| {
+            let value = attrs[idx + name.len()..].trim_start().strip_prefix('=')?.trim_start();
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let value = &value[1..];
+
+            value.find(quote).map(|end| &value[..end])
+        })
+}
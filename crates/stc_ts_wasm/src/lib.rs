@@ -0,0 +1,166 @@
+//! stc for the browser.
+//!
+//! This crate does not access the file system and does not spawn threads, so
+//! it works on `wasm32-unknown-unknown`. Builtin libraries are compiled into
+//! the binary.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::{Context, Error};
+use serde::{Deserialize, Serialize};
+use stc_ts_config::TsConfig;
+use stc_ts_type_checker::source::{check_source_with_cm, CheckOptions};
+use stc_utils::line_index::{LineCol as Pos, PosEncoding, PosMapper};
+use swc_common::{SourceMap, Spanned};
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Options {
+    /// Decides the syntax, like `.tsx` or `.d.ts`. Defaults to `input.ts`.
+    file_name: Option<String>,
+
+    /// Same as `compilerOptions` of `tsconfig.json`.
+    compiler_options: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Diagnostic {
+    code: usize,
+    message: String,
+    /// [None] if the error does not have a position in the file.
+    start: Option<LineCol>,
+    end: Option<LineCol>,
+    /// The number of errors suppressed because they are caused by the same
+    /// problem.
+    suppressed: usize,
+}
+
+/// Zero-based. Columns are counted in UTF-16 code units, like offsets of
+/// JavaScript strings.
+#[derive(Debug, Serialize)]
+struct LineCol {
+    line: usize,
+    column: usize,
+}
+
+/// Type checks `code`, returning an array of diagnostics.
+///
+/// `options` is an object like `{ fileName: "input.tsx", compilerOptions: {
+/// strict: true } }`. Throws if `code` or `options` is invalid.
+#[wasm_bindgen(js_name = checkFile)]
+pub fn check_file(code: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options: Options = if options.is_undefined() || options.is_null() {
+        Default::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)?
+    };
+
+    let diagnostics = check(code, &options).map_err(|err| JsValue::from_str(&format!("{:?}", err)))?;
+
+    Ok(serde_wasm_bindgen::to_value(&diagnostics)?)
+}
+
+fn check(code: &str, options: &Options) -> Result<Vec<Diagnostic>, Error> {
+    let filename = options.file_name.as_deref().unwrap_or("input.ts");
+
+    // `tsconfig.json` does not exist, but it's used to parse options.
+    let config = {
+        let compiler_options = options.compiler_options.clone().unwrap_or_else(|| serde_json::json!({}));
+        let content = serde_json::json!({ "compilerOptions": compiler_options }).to_string();
+        TsConfig::parse(Path::new("/tsconfig.json"), &content).context("invalid compilerOptions")?
+    };
+
+    let mut diagnostics = config
+        .errors
+        .iter()
+        .map(|err| Diagnostic {
+            code: err.code(),
+            message: err.kind.to_string(),
+            start: None,
+            end: None,
+            suppressed: 0,
+        })
+        .collect::<Vec<_>>();
+
+    let check_options = CheckOptions {
+        rule: config.rule,
+        target: config.target,
+        module: config.module.clone(),
+        libs: config.libs_or_default(),
+        error_limit: Default::default(),
+    };
+    let cm = Arc::new(SourceMap::default());
+    let errors = check_source_with_cm(&cm, filename, code, &check_options)?;
+
+    let positions = PosMapper::new(cm, PosEncoding::Utf16);
+    let pos = |pos: Pos| LineCol {
+        line: pos.line as usize,
+        column: pos.col as usize,
+    };
+    diagnostics.extend(errors.into_iter().map(|diagnostic| {
+        let (start, end) = match positions.range(diagnostic.error.span()) {
+            Some((_, range)) => (Some(pos(range.start)), Some(pos(range.end))),
+            None => (None, None),
+        };
+
+        Diagnostic {
+            code: diagnostic.error.code(),
+            message: format!("{:#?}", diagnostic.error),
+            start,
+            end,
+            suppressed: diagnostic.suppressed,
+        }
+    }));
+
+    Ok(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, Options};
+
+    fn check_code(code: &str) -> Vec<super::Diagnostic> {
+        swc_common::GLOBALS.set(&Default::default(), || check(code, &Options::default()).unwrap())
+    }
+
+    #[test]
+    fn no_error() {
+        assert!(check_code("const a: number = 1;\n").is_empty());
+    }
+
+    #[test]
+    fn type_error() {
+        let diagnostics = check_code("const a: number = 'a';\n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, 2322);
+        assert_eq!(diagnostics[0].start.as_ref().map(|pos| pos.line), Some(0));
+    }
+
+    /// An emoji is two UTF-16 code units, like `xx`.
+    #[test]
+    fn utf16_columns() {
+        let ascii = check_code("const a: string = 'xx'; const b: number = a;\n");
+        let emoji = check_code("const a: string = '😀'; const b: number = a;\n");
+
+        assert_eq!(ascii.len(), 1);
+        assert_eq!(emoji.len(), 1);
+
+        let column = |d: &super::Diagnostic| d.start.as_ref().map(|pos| pos.column);
+        assert_eq!(column(&emoji[0]), column(&ascii[0]));
+    }
+
+    #[test]
+    fn invalid_compiler_options() {
+        let options = Options {
+            file_name: None,
+            compiler_options: Some(serde_json::json!({ "target": "es1" })),
+        };
+
+        let diagnostics = swc_common::GLOBALS.set(&Default::default(), || check("", &options).unwrap());
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].start.is_none());
+    }
+}
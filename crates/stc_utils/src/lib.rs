@@ -15,7 +15,9 @@ use swc_common::SyntaxContext;
 pub mod cache;
 pub mod error;
 pub mod ext;
+pub mod line_index;
 pub mod panic_context;
+pub mod spelling;
 pub mod stack;
 
 pub type ABuilderHasher = ahash::RandomState;
@@ -0,0 +1,232 @@
+//! Conversion between byte positions and line/column positions.
+//!
+//! [SourceMap::lookup_char_pos] counts columns in chars and only breaks lines
+//! at `\n`, but tsc breaks lines at `\r\n`, `\r`, `\n`, U+2028 and U+2029, and
+//! LSP clients count columns in UTF-16 code units by default. Use
+//! [PosMapper] instead when a position is shown to the user.
+
+use std::sync::{Arc, Mutex};
+
+use rustc_hash::FxHashMap;
+use swc_common::{BytePos, SourceFile, SourceMap, Span};
+
+#[cfg(test)]
+mod tests;
+
+/// Unit of columns.
+///
+/// See `PositionEncodingKind` of the LSP specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PosEncoding {
+    Utf8,
+    /// Used by tsc and the default of LSP.
+    #[default]
+    Utf16,
+    Utf32,
+}
+
+impl PosEncoding {
+    /// The number of code units required to encode a char whose UTF-8
+    /// length is `len_utf8`.
+    fn units(self, len_utf8: u32) -> u32 {
+        match self {
+            PosEncoding::Utf8 => len_utf8,
+            PosEncoding::Utf16 => {
+                if len_utf8 == 4 {
+                    2
+                } else {
+                    1
+                }
+            }
+            PosEncoding::Utf32 => 1,
+        }
+    }
+}
+
+/// Zero-based line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    start: u32,
+    /// Excludes the line terminator.
+    end: u32,
+}
+
+/// Line table of a file.
+///
+/// Offsets are relative to the start of the file, excluding the byte order
+/// mark, which matches the way [SourceMap] stores files.
+#[derive(Debug)]
+pub struct LineIndex {
+    lines: Vec<Line>,
+    /// Offsets and UTF-8 lengths of non-ASCII chars, in ascending order.
+    wide_chars: Vec<(u32, u32)>,
+}
+
+impl LineIndex {
+    pub fn new(src: &str) -> Self {
+        let src = src.strip_prefix('\u{feff}').unwrap_or(src);
+
+        let mut lines = vec![];
+        let mut wide_chars = vec![];
+        let mut start = 0;
+
+        let mut iter = src.char_indices().peekable();
+        while let Some((idx, c)) = iter.next() {
+            let idx = idx as u32;
+            let next = match c {
+                '\r' => match iter.peek() {
+                    Some((_, '\n')) => {
+                        iter.next();
+                        idx + 2
+                    }
+                    _ => idx + 1,
+                },
+                '\n' => idx + 1,
+                '\u{2028}' | '\u{2029}' => idx + 3,
+                _ => {
+                    if !c.is_ascii() {
+                        wide_chars.push((idx, c.len_utf8() as u32));
+                    }
+                    continue;
+                }
+            };
+
+            lines.push(Line { start, end: idx });
+            start = next;
+        }
+        lines.push(Line {
+            start,
+            end: src.len() as u32,
+        });
+
+        LineIndex { lines, wide_chars }
+    }
+
+    /// Returns the position of `offset`. Offsets past the end of the file are
+    /// clamped.
+    pub fn line_col(&self, offset: u32, encoding: PosEncoding) -> LineCol {
+        let line = self.lines.partition_point(|l| l.start <= offset).saturating_sub(1);
+        let Line { start, end } = self.lines[line];
+        let offset = offset.min(end);
+
+        let lo = self.wide_chars.partition_point(|&(pos, _)| pos < start);
+        let hi = self.wide_chars.partition_point(|&(pos, _)| pos < offset);
+
+        let mut col = offset - start;
+        for &(_, len) in &self.wide_chars[lo..hi] {
+            col = col - len + encoding.units(len);
+        }
+
+        LineCol { line: line as _, col }
+    }
+
+    /// Returns the offset of `pos`, or [None] if the line does not exist.
+    ///
+    /// Columns past the end of the line are clamped to the end of the line,
+    /// and columns in the middle of a char are moved to the start of the char.
+    pub fn offset(&self, pos: LineCol, encoding: PosEncoding) -> Option<u32> {
+        let Line { start, end } = *self.lines.get(pos.line as usize)?;
+
+        let lo = self.wide_chars.partition_point(|&(p, _)| p < start);
+
+        let mut offset = start;
+        let mut col = 0;
+        for &(char_pos, len) in &self.wide_chars[lo..] {
+            if char_pos >= end {
+                break;
+            }
+
+            let ascii_len = char_pos - offset;
+            if col + ascii_len >= pos.col {
+                return Some(offset + (pos.col - col));
+            }
+            col += ascii_len;
+            offset = char_pos;
+
+            let units = encoding.units(len);
+            if col + units > pos.col {
+                return Some(offset);
+            }
+            col += units;
+            offset += len;
+        }
+
+        Some((offset + (pos.col - col)).min(end))
+    }
+}
+
+/// Zero-based range of a span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineColRange {
+    pub start: LineCol,
+    pub end: LineCol,
+}
+
+/// Maps positions in a [SourceMap] to line/column positions.
+///
+/// This is shared by the diagnostic output and the language server, so both
+/// agree on the position of an error.
+pub struct PosMapper {
+    cm: Arc<SourceMap>,
+    encoding: PosEncoding,
+    /// Keyed by [SourceFile::start_pos].
+    cache: Mutex<FxHashMap<BytePos, Arc<LineIndex>>>,
+}
+
+impl PosMapper {
+    pub fn new(cm: Arc<SourceMap>, encoding: PosEncoding) -> Self {
+        Self {
+            cm,
+            encoding,
+            cache: Default::default(),
+        }
+    }
+
+    pub fn encoding(&self) -> PosEncoding {
+        self.encoding
+    }
+
+    fn line_index(&self, file: &SourceFile) -> Arc<LineIndex> {
+        self.cache
+            .lock()
+            .unwrap()
+            .entry(file.start_pos)
+            .or_insert_with(|| Arc::new(LineIndex::new(&file.src)))
+            .clone()
+    }
+
+    /// Returns [None] for dummy positions.
+    pub fn line_col(&self, pos: BytePos) -> Option<(Arc<SourceFile>, LineCol)> {
+        if pos.is_dummy() {
+            return None;
+        }
+
+        let file = self.cm.lookup_source_file(pos);
+        let index = self.line_index(&file);
+        let line_col = index.line_col((pos - file.start_pos).0, self.encoding);
+
+        Some((file, line_col))
+    }
+
+    /// Returns [None] for dummy spans.
+    pub fn range(&self, span: Span) -> Option<(Arc<SourceFile>, LineColRange)> {
+        let (file, start) = self.line_col(span.lo)?;
+        let index = self.line_index(&file);
+        let end = index.line_col((span.hi.max(span.lo) - file.start_pos).0, self.encoding);
+
+        Some((file, LineColRange { start, end }))
+    }
+
+    /// Returns [None] if the line does not exist in `file`.
+    pub fn byte_pos(&self, file: &SourceFile, pos: LineCol) -> Option<BytePos> {
+        let offset = self.line_index(file).offset(pos, self.encoding)?;
+
+        Some(file.start_pos + BytePos(offset))
+    }
+}
@@ -0,0 +1,54 @@
+use super::{LineCol, LineIndex, PosEncoding};
+
+fn lc(line: u32, col: u32) -> LineCol {
+    LineCol { line, col }
+}
+
+#[test]
+fn line_breaks() {
+    let index = LineIndex::new("a\r\nb\rc\nd\u{2028}e");
+
+    assert_eq!(index.line_col(0, PosEncoding::Utf16), lc(0, 0));
+    assert_eq!(index.line_col(3, PosEncoding::Utf16), lc(1, 0));
+    assert_eq!(index.line_col(5, PosEncoding::Utf16), lc(2, 0));
+    assert_eq!(index.line_col(7, PosEncoding::Utf16), lc(3, 0));
+    assert_eq!(index.line_col(11, PosEncoding::Utf16), lc(4, 0));
+
+    // Inside of `\r\n`
+    assert_eq!(index.line_col(2, PosEncoding::Utf16), lc(0, 1));
+}
+
+#[test]
+fn multi_byte() {
+    // `é` is 2 bytes, `😀` is 4 bytes and 2 UTF-16 code units.
+    let src = "let é = '😀'; x";
+    let index = LineIndex::new(src);
+    let x = src.find('x').unwrap() as u32;
+
+    assert_eq!(index.line_col(x, PosEncoding::Utf8), lc(0, x));
+    assert_eq!(index.line_col(x, PosEncoding::Utf16), lc(0, 14));
+    assert_eq!(index.line_col(x, PosEncoding::Utf32), lc(0, 13));
+
+    for encoding in [PosEncoding::Utf8, PosEncoding::Utf16, PosEncoding::Utf32] {
+        let pos = index.line_col(x, encoding);
+        assert_eq!(index.offset(pos, encoding), Some(x));
+    }
+}
+
+#[test]
+fn bom() {
+    let index = LineIndex::new("\u{feff}ab\ncd");
+
+    assert_eq!(index.line_col(0, PosEncoding::Utf16), lc(0, 0));
+    assert_eq!(index.line_col(3, PosEncoding::Utf16), lc(1, 0));
+    assert_eq!(index.offset(lc(1, 1), PosEncoding::Utf16), Some(4));
+}
+
+#[test]
+fn offset_is_clamped() {
+    let index = LineIndex::new("ab\r\ncd");
+
+    assert_eq!(index.offset(lc(0, 10), PosEncoding::Utf16), Some(2));
+    assert_eq!(index.offset(lc(1, 10), PosEncoding::Utf16), Some(6));
+    assert_eq!(index.offset(lc(2, 0), PosEncoding::Utf16), None);
+}
@@ -0,0 +1,88 @@
+//! Suggestions for misspelled names, like `Did you mean 'console'?` for
+//! `consle`.
+//!
+//! The heuristics are the ones of `getSpellingSuggestion` of tsc, so the same
+//! names are suggested.
+
+#[cfg(test)]
+mod tests;
+
+/// Returns the candidate most similar to `name`, or [None] if no candidate is
+/// similar enough.
+///
+/// If multiple candidates are equally similar, the first one is returned, so
+/// `candidates` should be in a stable order.
+pub fn spelling_suggestion<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let len = name.chars().count();
+    let max_len_diff = 2.max(len * 34 / 100);
+    let mut best_distance = (len * 4 / 10) as f64 + 1.0;
+    let mut best = None;
+
+    for candidate in candidates {
+        if candidate == name {
+            continue;
+        }
+
+        let candidate_len = candidate.chars().count();
+        if candidate_len.abs_diff(len) > max_len_diff {
+            continue;
+        }
+        // Short names are noticed by users, unless only the case differs.
+        if candidate_len < 3 && candidate.to_lowercase() != name.to_lowercase() {
+            continue;
+        }
+
+        if let Some(distance) = levenshtein_with_max(name, candidate, best_distance - 0.1) {
+            best_distance = distance;
+            best = Some(candidate);
+        }
+    }
+
+    best
+}
+
+/// Levenshtein distance where changing only the case of a char costs `0.1`
+/// and other substitutions cost `2`.
+///
+/// Returns [None] if the distance is greater than `max`.
+fn levenshtein_with_max(s1: &str, s2: &str, max: f64) -> Option<f64> {
+    let s1 = s1.chars().collect::<Vec<_>>();
+    let s2 = s2.chars().collect::<Vec<_>>();
+
+    let mut previous = (0..=s2.len()).map(|j| j as f64).collect::<Vec<_>>();
+    let mut current = vec![0.0; s2.len() + 1];
+
+    for (i, &c1) in s1.iter().enumerate() {
+        current[0] = (i + 1) as f64;
+        let mut row_min = current[0];
+
+        for (j, &c2) in s2.iter().enumerate() {
+            let distance = if c1 == c2 {
+                previous[j]
+            } else {
+                let substitution = if c1.to_lowercase().eq(c2.to_lowercase()) { 0.1 } else { 2.0 };
+
+                (previous[j + 1] + 1.0).min(current[j] + 1.0).min(previous[j] + substitution)
+            };
+
+            current[j + 1] = distance;
+            row_min = row_min.min(distance);
+        }
+
+        if row_min > max {
+            return None;
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[s2.len()];
+    if distance > max {
+        None
+    } else {
+        Some(distance)
+    }
+}
@@ -0,0 +1,30 @@
+use super::spelling_suggestion;
+
+fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    spelling_suggestion(name, candidates.iter().copied())
+}
+
+#[test]
+fn typos() {
+    assert_eq!(suggest("consle", &["console", "constructor", "Math"]), Some("console"));
+    assert_eq!(suggest("lenght", &["length", "charAt"]), Some("length"));
+    assert_eq!(suggest("documnet", &["document", "doc"]), Some("document"));
+}
+
+#[test]
+fn case_difference_is_preferred() {
+    assert_eq!(suggest("Foo", &["foa", "foo"]), Some("foo"));
+    assert_eq!(suggest("ab", &["AB", "ac"]), Some("AB"));
+}
+
+#[test]
+fn dissimilar_names_are_not_suggested() {
+    assert_eq!(suggest("foo", &["bar", "foobarbaz"]), None);
+    assert_eq!(suggest("a", &["b"]), None);
+    assert_eq!(suggest("foo", &["foo"]), None);
+}
+
+#[test]
+fn first_of_equally_similar_candidates() {
+    assert_eq!(suggest("foobar", &["foobaz", "foobay"]), Some("foobaz"));
+}
@@ -49,6 +49,27 @@ pub fn track(span: Span) -> Result<TrackGuard, StackOverflowError> {
     })
 }
 
+/// If the remaining stack space is less than this, [maybe_grow] allocates a
+/// new segment.
+const RED_ZONE: usize = 128 * 1024;
+
+/// Size of a stack segment allocated by [maybe_grow].
+const STACK_PER_RECURSION: usize = 2 * 1024 * 1024;
+
+/// Runs `f` on a newly allocated stack segment if the current stack is about to
+/// overflow.
+///
+/// Recursive operations on types (normalization, assignment, inference and
+/// expansion) should call this on each level, so deeply nested but valid
+/// inputs do not depend on the size of the thread stack.
+///
+/// This is orthogonal to [track], which limits the depth of recursion to
+/// detect infinite recursion.
+#[inline]
+pub fn maybe_grow<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_PER_RECURSION, f)
+}
+
 /// closure argument: Stack left
 fn with_ctx<T>(f: impl FnOnce(&mut usize) -> T) -> T {
     thread_local! {
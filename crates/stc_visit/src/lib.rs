@@ -11,12 +11,14 @@ use swc_common::SyntaxContext;
 
 pub use self::{
     fold::{Fold, FoldWith},
+    try_visit::{TryVisit, TryVisitWith, TryVisitor},
     visit::{Visit, VisitWith},
     visit_mut::{VisitMut, VisitMutWith},
     visitable::Visitable,
 };
 
 mod fold;
+mod try_visit;
 mod visit;
 pub mod visit_cache;
 mod visit_mut;
@@ -45,6 +47,14 @@ macro_rules! noop {
             #[inline]
             fn visit_children_with(&self, _: &mut V) {}
         }
+
+        impl<V: ?Sized + TryVisitor> TryVisitWith<V> for $T {
+            ///  Noop
+            #[inline]
+            fn try_visit_children_with(&self, _: &mut V) -> std::ops::ControlFlow<V::Break> {
+                std::ops::ControlFlow::Continue(())
+            }
+        }
     };
 }
 macro_rules! primitives {
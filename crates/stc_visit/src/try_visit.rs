@@ -0,0 +1,150 @@
+use std::{cell::RefCell, ops::ControlFlow, rc::Rc, sync::Arc};
+
+use swc_common::Span;
+
+use crate::Visitable;
+
+/// Common part of [TryVisit] implementations.
+///
+/// For fallible traversals, use the error type as [TryVisitor::Break].
+pub trait TryVisitor {
+    /// Value returned from the traversal when it stops early.
+    type Break;
+}
+
+/// Visitor which can stop the traversal by returning [ControlFlow::Break].
+pub trait TryVisit<T: ?Sized + Visitable>: TryVisitor {
+    fn try_visit(&mut self, value: &T) -> ControlFlow<Self::Break>;
+}
+
+pub trait TryVisitWith<V: ?Sized + TryVisitor>: Visitable {
+    fn try_visit_with(&self, visitor: &mut V) -> ControlFlow<V::Break>
+    where
+        V: TryVisit<Self>,
+    {
+        visitor.try_visit(self)
+    }
+
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break>;
+}
+
+impl<T, V> TryVisit<T> for V
+where
+    V: ?Sized + TryVisitor,
+    T: ?Sized + TryVisitWith<Self>,
+{
+    default fn try_visit(&mut self, val: &T) -> ControlFlow<V::Break> {
+        val.try_visit_children_with(self)
+    }
+}
+
+impl<T, V> TryVisitWith<V> for Box<T>
+where
+    T: ?Sized + Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, v: &mut V) -> ControlFlow<V::Break> {
+        v.try_visit(&**self)
+    }
+}
+
+impl<T, V> TryVisitWith<V> for [T]
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.iter().try_for_each(|value| visitor.try_visit(value))
+    }
+}
+
+impl<T, V> TryVisitWith<V> for RefCell<T>
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, v: &mut V) -> ControlFlow<V::Break> {
+        v.try_visit(&*self.borrow())
+    }
+}
+
+impl<V> TryVisitor for &'_ mut V
+where
+    V: ?Sized + TryVisitor,
+{
+    type Break = V::Break;
+}
+
+impl<T, V> TryVisit<T> for &'_ mut V
+where
+    T: TryVisitWith<Self>,
+    V: TryVisit<T>,
+{
+    fn try_visit(&mut self, value: &T) -> ControlFlow<V::Break> {
+        (**self).try_visit(value)
+    }
+}
+
+impl<V> TryVisitWith<V> for Span
+where
+    V: ?Sized + TryVisitor,
+{
+    /// Noop
+    #[inline]
+    fn try_visit_children_with(&self, _: &mut V) -> ControlFlow<V::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T, V> TryVisitWith<V> for Rc<T>
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        visitor.try_visit(&**self)
+    }
+}
+
+impl<T, V> TryVisitWith<V> for Arc<T>
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        visitor.try_visit(&**self)
+    }
+}
+
+impl<T, V> TryVisitWith<V> for Vec<T>
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.iter().try_for_each(|value| visitor.try_visit(value))
+    }
+}
+
+impl<T, V> TryVisitWith<V> for Option<T>
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        match self {
+            Some(value) => visitor.try_visit(value),
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl<T, V> TryVisitWith<V> for &'_ T
+where
+    T: Visitable,
+    V: ?Sized + TryVisit<T>,
+{
+    fn try_visit_children_with(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        visitor.try_visit(&**self)
+    }
+}
@@ -0,0 +1,70 @@
+#![allow(incomplete_features)]
+#![feature(specialization)]
+
+use std::ops::ControlFlow;
+
+use stc_visit::{TryVisit, TryVisitWith, TryVisitor, Visit};
+
+#[derive(Debug, Visit)]
+struct Node {
+    value: u32,
+    children: Vec<Node>,
+}
+
+/// Breaks with the first value greater than `limit`.
+struct Finder {
+    limit: u32,
+    visited: usize,
+}
+
+impl TryVisitor for Finder {
+    type Break = u32;
+}
+
+impl TryVisit<Node> for Finder {
+    fn try_visit(&mut self, n: &Node) -> ControlFlow<u32> {
+        self.visited += 1;
+
+        if n.value > self.limit {
+            return ControlFlow::Break(n.value);
+        }
+
+        n.try_visit_children_with(self)
+    }
+}
+
+fn leaf(value: u32) -> Node {
+    Node { value, children: vec![] }
+}
+
+#[test]
+fn stops_at_first_match() {
+    let tree = Node {
+        value: 0,
+        children: vec![leaf(1), leaf(5), leaf(7), leaf(2)],
+    };
+
+    let mut v = Finder { limit: 4, visited: 0 };
+
+    assert_eq!(tree.try_visit_with(&mut v), ControlFlow::Break(5));
+    assert_eq!(v.visited, 3);
+}
+
+#[test]
+fn continues_without_match() {
+    let tree = Node {
+        value: 0,
+        children: vec![
+            leaf(1),
+            Node {
+                value: 2,
+                children: vec![leaf(3)],
+            },
+        ],
+    };
+
+    let mut v = Finder { limit: 10, visited: 0 };
+
+    assert_eq!(tree.try_visit_with(&mut v), ControlFlow::Continue(()));
+    assert_eq!(v.visited, 4);
+}
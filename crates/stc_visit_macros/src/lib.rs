@@ -4,8 +4,8 @@ use syn::{
     FieldValue, Fields, Ident, Index, Item, Member, Pat, PatPath, PatStruct, Path, Stmt, Token,
 };
 
-/// Note: This generates `FoldWith`, `VisitWith` and `VisitMutWith` although
-/// it's `#[derive(Visit)]`
+/// Note: This generates `FoldWith`, `VisitWith`, `VisitMutWith` and
+/// `TryVisitWith` although it's `#[derive(Visit)]`
 #[proc_macro_derive(Visit, attributes(visit))]
 pub fn derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse::<DeriveInput>(item).unwrap();
@@ -16,6 +16,7 @@ pub fn derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     tts.push_tokens(&items.fold);
     tts.push_tokens(&items.visit_mut);
     tts.push_tokens(&items.visit);
+    tts.push_tokens(&items.try_visit);
 
     tts.push_tokens(&q!(Vars { Type: &input.ident }, {
         #[automatically_derived]
@@ -83,6 +84,24 @@ fn make_item(input: &DeriveInput) -> Set<Item> {
             }
         )
         .parse(),
+        try_visit: q!(
+            Vars {
+                Type: &input.ident,
+                body: &body.try_visit
+            },
+            {
+                #[automatically_derived]
+                impl<V> stc_visit::TryVisitWith<V> for Type
+                where
+                    V: ?Sized + stc_visit::TryVisitor,
+                {
+                    fn try_visit_children_with(&self, _visitor: &mut V) -> ::std::ops::ControlFlow<V::Break> {
+                        body
+                    }
+                }
+            }
+        )
+        .parse(),
     }
 }
 
@@ -118,6 +137,7 @@ fn make_body(input: &DeriveInput) -> Set<Expr> {
         fold: make_body(arms.fold),
         visit: make_body(arms.visit),
         visit_mut: make_body(arms.visit_mut),
+        try_visit: make_body(arms.try_visit),
     }
 }
 
@@ -175,9 +195,15 @@ fn make_arm(path: &Path, fields: &Fields) -> Set<Arm> {
             .parse(),
             visit: Stmt::Semi(exprs.visit, val.span().as_token()),
             visit_mut: Stmt::Semi(exprs.visit_mut, val.span().as_token()),
+            try_visit: Stmt::Semi(exprs.try_visit, val.span().as_token()),
         });
     }
 
+    stmts.try_visit.push(Stmt::Semi(
+        q!(Vars {}, { return ::std::ops::ControlFlow::Continue(()) }).parse(),
+        path.span().as_token(),
+    ));
+
     stmts.fold.push(Stmt::Semi(
         q!(
             Vars {
@@ -216,6 +242,7 @@ fn make_arm(path: &Path, fields: &Fields) -> Set<Arm> {
         fold: make(stmts.fold),
         visit: make(stmts.visit),
         visit_mut: make(stmts.visit_mut),
+        try_visit: make(stmts.try_visit),
     }
 }
 
@@ -224,6 +251,7 @@ fn make_visit_expr(val: &Ident, _field: &Field) -> Set<Expr> {
         fold: q!(Vars { val }, { val.fold_with(_visitor) }).parse(),
         visit: q!(Vars { val }, { val.visit_with(_visitor) }).parse(),
         visit_mut: q!(Vars { val }, { val.visit_mut_with(_visitor) }).parse(),
+        try_visit: q!(Vars { val }, { val.try_visit_with(_visitor)? }).parse(),
     }
 }
 
@@ -232,6 +260,7 @@ struct Set<T> {
     fold: T,
     visit: T,
     visit_mut: T,
+    try_visit: T,
 }
 
 impl<T> Set<Vec<T>> {
@@ -239,5 +268,6 @@ impl<T> Set<Vec<T>> {
         self.fold.push(v.fold);
         self.visit.push(v.visit);
         self.visit_mut.push(v.visit_mut);
+        self.try_visit.push(v.try_visit);
     }
 }
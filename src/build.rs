@@ -0,0 +1,194 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::{Context, Error};
+use clap::Args;
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_config::{ConfigError, ConfigErrorKind, ProgramBuilder, ProjectGraph, TsConfig};
+use stc_ts_module_loader::resolvers::references::ProjectReferencesResolver;
+use stc_ts_type_checker::{diagnostics::dedup_errors, project::ProjectOptions};
+use swc_common::{errors::Handler, FileName, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+
+/// Type check a project and the projects it references, like `tsc --build`.
+///
+/// Referenced projects are checked first. Declarations of each project are
+/// emitted to its output directory, and projects referencing it import the
+/// declarations instead of checking its source files again.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct BuildCommand {
+    /// Path to `tsconfig.json`, or a directory containing it.
+    #[clap(name = "project", default_value = ".")]
+    pub project: PathBuf,
+
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc, like `node_modules/typescript/lib`.
+    #[clap(long)]
+    pub lib_dir: Option<PathBuf>,
+
+    /// Check projects without emitting declarations.
+    #[clap(long)]
+    pub no_emit: bool,
+
+    /// Print diagnostics to stdout as JSON lines instead of printing
+    /// human-readable messages to stderr.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Print diagnostics with code frames, like `tsc --pretty`.
+    #[clap(long, conflicts_with = "json")]
+    pub pretty: bool,
+}
+
+impl BuildCommand {
+    pub fn run(&self, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<(), Error> {
+        let path = if self.project.is_dir() {
+            self.project.join("tsconfig.json")
+        } else {
+            self.project.clone()
+        };
+
+        let graph = ProjectGraph::load(&path)?;
+        for err in &graph.errors {
+            err.emit(&handler);
+        }
+
+        let mut error_count = graph.errors.len();
+        for project in &graph.projects {
+            for err in &project.errors {
+                err.emit(&handler);
+            }
+            error_count += project.errors.len();
+
+            error_count += self.build_project(&cm, &handler, &graph, project)?;
+        }
+
+        log::info!("Found {} errors in {} projects", error_count, graph.projects.len());
+
+        Ok(())
+    }
+
+    /// Returns the number of errors.
+    fn build_project(&self, cm: &Arc<SourceMap>, handler: &Arc<Handler>, graph: &ProjectGraph, project: &TsConfig) -> Result<usize, Error> {
+        let start = Instant::now();
+
+        let program = ProgramBuilder::new(project).build();
+        for err in &program.errors {
+            err.emit(handler);
+        }
+        let mut error_count = program.errors.len();
+
+        let mut outputs = FxHashMap::default();
+        for referenced in graph.references(project) {
+            for file in ProgramBuilder::new(referenced).build().root_files {
+                if let Some(output) = referenced.declaration_output(&file) {
+                    outputs.insert(file, output);
+                }
+            }
+        }
+        let referenced_outputs = outputs.values().cloned().collect::<FxHashSet<_>>();
+
+        let libs = project.libs_or_default();
+        let (mut checker, mut errors) =
+            ProjectOptions::from_config(project, &libs, self.lib_dir.as_deref()).checker(cm.clone(), handler.clone(), |resolver| {
+                ProjectReferencesResolver::new(resolver, outputs)
+            })?;
+
+        checker.check_scripts(&program.root_files);
+        let ids = program
+            .root_files
+            .iter()
+            .map(|file| (file, checker.check(Arc::new(FileName::Real(file.clone())))))
+            .collect::<Vec<_>>();
+        errors.extend(checker.take_errors());
+
+        let diagnostics = dedup_errors(cm, errors);
+        for diagnostic in &diagnostics {
+            diagnostic.emit(handler);
+        }
+        error_count += diagnostics.len();
+
+        // Composite projects must list all of their files, so `tsc` can tell which
+        // project owns a file.
+        if project.composite {
+            let root_files = program.root_files.iter().collect::<FxHashSet<_>>();
+            for file in checker.module_loader().loaded_files() {
+                let path = match &*file {
+                    FileName::Real(path) => path,
+                    _ => continue,
+                };
+
+                if root_files.contains(path) || referenced_outputs.contains(path) || is_in_node_modules(path) {
+                    continue;
+                }
+
+                ConfigError {
+                    file: project.path.clone(),
+                    kind: ConfigErrorKind::FileNotInProject { path: path.clone() },
+                }
+                .emit(handler);
+                error_count += 1;
+            }
+        }
+
+        if project.declaration && !self.no_emit {
+            for (file, id) in ids {
+                let output = match project.declaration_output(file) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                if let Some(module) = checker.take_dts(id) {
+                    emit_declaration(cm, &module, &output)?;
+                }
+            }
+        }
+
+        let end = Instant::now();
+
+        log::info!(
+            "Built {} ({} files, {} errors) in {:?}",
+            project.path.display(),
+            program.root_files.len(),
+            error_count,
+            end - start
+        );
+
+        Ok(error_count)
+    }
+}
+
+fn is_in_node_modules(path: &Path) -> bool {
+    path.components().any(|c| c == Component::Normal("node_modules".as_ref()))
+}
+
+fn emit_declaration(cm: &Arc<SourceMap>, module: &Module, output: &Path) -> Result<(), Error> {
+    let mut buf = vec![];
+
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config {
+                minify: false,
+                ..Default::default()
+            },
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None)),
+        };
+
+        emitter
+            .emit_module(module)
+            .with_context(|| format!("failed to print {}", output.display()))?;
+    }
+
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    fs::write(output, buf).with_context(|| format!("failed to write {}", output.display()))
+}
@@ -1,18 +1,171 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Error};
 use clap::Args;
+use stc_ts_type_checker::{baseline::Baseline, ErrorLimit};
+use swc_common::{errors::Handler, SourceMap};
 
 /// Perform type checking, but this command is not public api and is only used
 /// for testing.
 #[derive(Debug, Args)]
 #[clap(rename_all = "camel-case")]
 pub struct TestCommand {
+    /// The file to check. Defaults to the root files of `--project`.
     #[clap(name = "file")]
-    pub file: String,
+    pub file: Option<String>,
+
+    /// Path to `tsconfig.json`. Options passed as flags override the ones of
+    /// the configuration.
+    #[clap(long, short)]
+    pub project: Option<PathBuf>,
 
     /// The builtin libraries to load. Defaults to `es5`.
     #[clap(long)]
     pub libs: Option<Vec<String>>,
 
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc, like `node_modules/typescript/lib`.
+    #[clap(long)]
+    pub lib_dir: Option<PathBuf>,
+
     /// Directory name of typings to load.
     #[clap(long)]
     pub types: Option<Vec<String>>,
+
+    /// Directories to load typings from. Defaults to `node_modules/@types` of
+    /// the directory of the file and its ancestors.
+    #[clap(long)]
+    pub type_roots: Option<Vec<PathBuf>>,
+
+    /// `baseUrl` of `compilerOptions`.
+    #[clap(long)]
+    pub base_url: Option<PathBuf>,
+
+    /// A pattern of `paths` of `compilerOptions` with its substitutions, like
+    /// `@app/*=src/*,lib/*`. Substitutions are tried in order.
+    #[clap(long)]
+    pub paths: Vec<String>,
+
+    /// Enable all strict type checking options.
+    #[clap(long)]
+    pub strict: bool,
+
+    /// Report constructs stc cannot check yet as errors, instead of accepting
+    /// them silently.
+    #[clap(long)]
+    pub strict_parity: bool,
+
+    /// Emit errors of each module as soon as the module is checked, and print
+    /// a summary at the end.
+    #[clap(long)]
+    pub stream: bool,
+
+    /// Check only files affected by changes since the last run. Enabled by
+    /// `incremental` or `composite` of `--project`.
+    #[clap(long)]
+    pub incremental: bool,
+
+    /// The file storing the state of `--incremental`. Defaults to the one of
+    /// `--project`.
+    #[clap(long)]
+    pub build_info_file: Option<PathBuf>,
+
+    /// Print diagnostics to stdout as JSON lines instead of printing
+    /// human-readable messages to stderr.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Print diagnostics with code frames, like `tsc --pretty`.
+    #[clap(long, conflicts_with = "json")]
+    pub pretty: bool,
+
+    /// Stop reporting errors once this many errors are reported.
+    #[clap(long)]
+    pub max_errors: Option<usize>,
+
+    /// Stop reporting errors of a file once this many errors are reported for
+    /// the file.
+    #[clap(long)]
+    pub max_errors_per_file: Option<usize>,
+
+    /// Report only errors which are not recorded in this file. Paths in the
+    /// file are relative to its directory.
+    #[clap(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Record all errors in `--baseline` instead of reporting them.
+    #[clap(long, requires = "baseline")]
+    pub update_baseline: bool,
+}
+
+impl TestCommand {
+    /// Parses `--max-errors` and `--max-errors-per-file`.
+    pub fn error_limit(&self) -> ErrorLimit {
+        ErrorLimit {
+            per_file: self.max_errors_per_file,
+            total: self.max_errors,
+        }
+    }
+
+    /// Removes errors recorded in `--baseline` from `errors`, or records them
+    /// if `--update-baseline` is passed.
+    ///
+    /// Entries of the baseline which do not match any error are reported as
+    /// warnings, because the baseline should be updated to prevent the errors
+    /// from coming back.
+    pub fn apply_baseline(
+        &self,
+        cm: &SourceMap,
+        handler: &Handler,
+        errors: Vec<stc_ts_errors::Error>,
+    ) -> Result<Vec<stc_ts_errors::Error>, Error> {
+        let path = match &self.baseline {
+            Some(path) => path,
+            None => return Ok(errors),
+        };
+        let base = baseline_dir(path);
+
+        if self.update_baseline {
+            let baseline = Baseline::from_errors(cm, &base, &errors);
+            baseline.save(path)?;
+
+            handler.note_without_error(&format!("Recorded {} errors in {}", baseline.len(), path.display()));
+            return Ok(vec![]);
+        }
+
+        let result = Baseline::load(path)?.apply(cm, &base, errors);
+        for entry in &result.stale {
+            handler.warn(&format!(
+                "{}: {} errors recorded as `{}` are not reported anymore; run with `--update-baseline` to remove them from the baseline",
+                entry.file, entry.count, entry.fingerprint
+            ));
+        }
+        log::info!("{} errors are in the baseline", result.matched);
+
+        Ok(result.errors)
+    }
+
+    /// Parses `--paths`.
+    pub fn paths(&self) -> Result<Vec<(String, Vec<String>)>, Error> {
+        self.paths
+            .iter()
+            .map(|s| match s.split_once('=') {
+                Some((pattern, substitutions)) => Ok((pattern.to_string(), substitutions.split(',').map(String::from).collect())),
+                None => bail!("invalid `--paths` value `{}`: expected `pattern=substitution,...`", s),
+            })
+            .collect()
+    }
+}
+
+/// The directory paths in the baseline `path` are relative to.
+fn baseline_dir(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
 }
@@ -0,0 +1,595 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
+use clap::Args;
+use stc_ts_module_loader::{
+    package_json::{ExportEntry, PackageJson},
+    resolvers::node16::{select_entry, Node16Resolver, ResolutionMode},
+};
+use swc_common::{errors::Handler, SourceMap};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+
+/// Validate the type declarations a package exposes through `package.json`.
+///
+/// Checks that every target of `exports` (or `types`) resolves to an existing
+/// declaration file, that the module format of declarations matches the
+/// javascript files, and that the ESM and CJS declarations of an entry point
+/// export the same names.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct CheckExportsCommand {
+    /// Directory containing `package.json`.
+    #[clap(name = "dir", default_value = ".")]
+    pub dir: PathBuf,
+}
+
+impl CheckExportsCommand {
+    pub fn run(&self, cm: Arc<SourceMap>, handler: &Handler) -> Result<(), Error> {
+        let pkg = PackageJson::read(&self.dir.join("package.json"))?;
+
+        let problems = PackageChecker {
+            cm,
+            dir: &self.dir,
+            pkg: &pkg,
+            resolver: Node16Resolver::new(),
+        }
+        .check()?;
+
+        for problem in &problems {
+            handler.err(&problem.to_string());
+        }
+
+        if !problems.is_empty() {
+            bail!("found {} problem(s) in {}", problems.len(), self.dir.display())
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Import,
+    Require,
+}
+
+impl Mode {
+    fn resolution_mode(self) -> ResolutionMode {
+        match self {
+            Mode::Import => ResolutionMode::Esm,
+            Mode::Require => ResolutionMode::CommonJs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Esm,
+    Cjs,
+}
+
+#[derive(Debug)]
+enum Problem {
+    /// A target of `types` or of a `types` condition does not exist.
+    NoResolution { subpath: String, target: String },
+
+    /// A javascript target does not have a declaration file next to it.
+    Untyped { subpath: String, mode: Mode, target: String },
+
+    /// A `types` condition comes after another condition of the same object,
+    /// so it's unreachable for some resolution modes.
+    MisplacedTypes { subpath: String, conditions: Vec<String> },
+
+    /// The declaration file is an ES module but the javascript file is a
+    /// CommonJS module.
+    FalseEsm { subpath: String, mode: Mode, types: PathBuf },
+
+    /// The declaration file is a CommonJS module but the javascript file is
+    /// an ES module.
+    FalseCjs { subpath: String, mode: Mode, types: PathBuf },
+
+    /// ESM and CJS declarations of an entry point export different names.
+    SurfaceMismatch {
+        subpath: String,
+        only_in_esm: Vec<String>,
+        only_in_cjs: Vec<String>,
+    },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::NoResolution { subpath, target } => {
+                write!(f, "`{}`: declaration file `{}` does not exist", subpath, target)
+            }
+            Problem::Untyped { subpath, mode, target } => {
+                write!(f, "`{}` ({:?}): no declaration file found for `{}`", subpath, mode, target)
+            }
+            Problem::MisplacedTypes { subpath, conditions } => write!(
+                f,
+                "`{}`: the `types` condition at `{}` should be the first condition of its object",
+                subpath,
+                conditions.join(".")
+            ),
+            Problem::FalseEsm { subpath, mode, types } => write!(
+                f,
+                "`{}` ({:?}): `{}` is an ES module but the javascript file is a CommonJS module",
+                subpath,
+                mode,
+                types.display()
+            ),
+            Problem::FalseCjs { subpath, mode, types } => write!(
+                f,
+                "`{}` ({:?}): `{}` is a CommonJS module but the javascript file is an ES module",
+                subpath,
+                mode,
+                types.display()
+            ),
+            Problem::SurfaceMismatch {
+                subpath,
+                only_in_esm,
+                only_in_cjs,
+            } => {
+                write!(f, "`{}`: ESM and CJS declarations export different names", subpath)?;
+                if !only_in_esm.is_empty() {
+                    write!(f, "; only in ESM: {}", only_in_esm.join(", "))?;
+                }
+                if !only_in_cjs.is_empty() {
+                    write!(f, "; only in CJS: {}", only_in_cjs.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+struct PackageChecker<'a> {
+    cm: Arc<SourceMap>,
+    dir: &'a Path,
+    pkg: &'a PackageJson,
+    /// Selects entries like `tsc` with `moduleResolution: node16`.
+    resolver: Node16Resolver,
+}
+
+impl PackageChecker<'_> {
+    fn check(&self) -> Result<Vec<Problem>, Error> {
+        let mut problems = vec![];
+
+        let entries = self.pkg.export_entries();
+
+        if entries.is_empty() {
+            self.check_legacy_entry(&mut problems);
+            return Ok(problems);
+        }
+
+        let mut seen = HashSet::new();
+        let subpaths = entries
+            .iter()
+            .map(|e| &*e.subpath)
+            .filter(|subpath| seen.insert(*subpath))
+            .collect::<Vec<_>>();
+
+        for subpath in subpaths {
+            let subpath_entries = entries.iter().filter(|e| e.subpath == subpath).collect::<Vec<_>>();
+
+            check_types_condition_order(subpath, &subpath_entries, &mut problems);
+
+            let mut resolved = vec![];
+
+            for mode in [Mode::Import, Mode::Require] {
+                if let Some(types) = self.check_mode(subpath, &entries, mode, &mut problems) {
+                    resolved.push(types);
+                }
+            }
+
+            if let [esm, cjs] = &*resolved {
+                if esm != cjs {
+                    self.compare_surface(subpath, esm, cjs, &mut problems)?;
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Packages without `exports` are resolved using `types` and `main`.
+    fn check_legacy_entry(&self, problems: &mut Vec<Problem>) {
+        if let Some(types) = self.pkg.types() {
+            if !self.dir.join(types).is_file() {
+                problems.push(Problem::NoResolution {
+                    subpath: ".".into(),
+                    target: types.into(),
+                });
+            }
+            return;
+        }
+
+        let main = self.pkg.main.as_deref().unwrap_or("./index.js");
+        if !self.dir.join(declaration_path(main)).is_file() {
+            problems.push(Problem::Untyped {
+                subpath: ".".into(),
+                mode: Mode::Require,
+                target: main.into(),
+            });
+        }
+    }
+
+    /// Returns the declaration file for `mode`, if it exists.
+    fn check_mode(&self, subpath: &str, entries: &[ExportEntry], mode: Mode, problems: &mut Vec<Problem>) -> Option<PathBuf> {
+        let resolution_mode = mode.resolution_mode();
+
+        let (types_entry, _) = select_entry(entries, subpath, |c| self.resolver.is_condition_enabled(resolution_mode, c))?;
+        let types_target = types_entry.target.as_deref()?;
+
+        let types = self.dir.join(declaration_path(types_target));
+        if !types.is_file() {
+            if types_entry.has_condition("types") {
+                problems.push(Problem::NoResolution {
+                    subpath: subpath.into(),
+                    target: types_target.into(),
+                });
+            } else {
+                problems.push(Problem::Untyped {
+                    subpath: subpath.into(),
+                    mode,
+                    target: types_target.into(),
+                });
+            }
+            return None;
+        }
+
+        let js_format = select_entry(entries, subpath, |c| {
+            !is_types_condition(c) && self.resolver.is_condition_enabled(resolution_mode, c)
+        })
+        .and_then(|(e, _)| e.target.as_deref())
+        .and_then(|target| self.format_of(target));
+
+        match (self.format_of(types_target), js_format) {
+            (Some(Format::Esm), Some(Format::Cjs)) => problems.push(Problem::FalseEsm {
+                subpath: subpath.into(),
+                mode,
+                types: types.clone(),
+            }),
+            (Some(Format::Cjs), Some(Format::Esm)) => problems.push(Problem::FalseCjs {
+                subpath: subpath.into(),
+                mode,
+                types: types.clone(),
+            }),
+            _ => {}
+        }
+
+        Some(types)
+    }
+
+    fn format_of(&self, target: &str) -> Option<Format> {
+        if target.ends_with(".mjs") || target.ends_with(".mts") {
+            return Some(Format::Esm);
+        }
+        if target.ends_with(".cjs") || target.ends_with(".cts") {
+            return Some(Format::Cjs);
+        }
+        if target.ends_with(".js") || target.ends_with(".ts") {
+            return Some(if self.pkg.is_esm() { Format::Esm } else { Format::Cjs });
+        }
+
+        None
+    }
+
+    fn compare_surface(&self, subpath: &str, esm: &Path, cjs: &Path, problems: &mut Vec<Problem>) -> Result<(), Error> {
+        let (esm_names, cjs_names) = match (self.exported_names(esm)?, self.exported_names(cjs)?) {
+            (Some(esm), Some(cjs)) => (esm, cjs),
+            // We can't know the surface without resolving other modules.
+            _ => return Ok(()),
+        };
+
+        let only_in_esm = esm_names.difference(&cjs_names).cloned().collect::<Vec<_>>();
+        let only_in_cjs = cjs_names.difference(&esm_names).cloned().collect::<Vec<_>>();
+
+        if !only_in_esm.is_empty() || !only_in_cjs.is_empty() {
+            problems.push(Problem::SurfaceMismatch {
+                subpath: subpath.into(),
+                only_in_esm,
+                only_in_cjs,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns names exported by a declaration file.
+    ///
+    /// Returns [None] if the file contains `export *` or `export =`, as the
+    /// exported names depend on other modules.
+    fn exported_names(&self, path: &Path) -> Result<Option<BTreeSet<String>>, Error> {
+        let fm = self
+            .cm
+            .load_file(path)
+            .with_context(|| format!("failed to load {}", path.display()))?;
+
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig {
+                dts: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            StringInput::from(&*fm),
+            None,
+        );
+        let module = Parser::new_from(lexer)
+            .parse_module()
+            .map_err(|err| anyhow::anyhow!("failed to parse {}: {:?}", path.display(), err.kind()))?;
+
+        let mut names = BTreeSet::new();
+
+        for item in &module.body {
+            let decl = match item {
+                ModuleItem::ModuleDecl(decl) => decl,
+                ModuleItem::Stmt(..) => continue,
+            };
+
+            match decl {
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+                    Decl::Class(c) => {
+                        names.insert(c.ident.sym.to_string());
+                    }
+                    Decl::Fn(f) => {
+                        names.insert(f.ident.sym.to_string());
+                    }
+                    Decl::Var(v) => {
+                        for d in &v.decls {
+                            if let Pat::Ident(i) = &d.name {
+                                names.insert(i.id.sym.to_string());
+                            }
+                        }
+                    }
+                    Decl::TsInterface(i) => {
+                        names.insert(i.id.sym.to_string());
+                    }
+                    Decl::TsTypeAlias(a) => {
+                        names.insert(a.id.sym.to_string());
+                    }
+                    Decl::TsEnum(e) => {
+                        names.insert(e.id.sym.to_string());
+                    }
+                    Decl::TsModule(m) => {
+                        if let TsModuleName::Ident(i) = &m.id {
+                            names.insert(i.sym.to_string());
+                        }
+                    }
+                },
+                ModuleDecl::ExportNamed(export) => {
+                    for s in &export.specifiers {
+                        let name = match s {
+                            ExportSpecifier::Named(s) => s.exported.as_ref().unwrap_or(&s.orig),
+                            ExportSpecifier::Namespace(s) => &s.name,
+                            ExportSpecifier::Default(s) => {
+                                names.insert(s.exported.sym.to_string());
+                                continue;
+                            }
+                        };
+                        names.insert(match name {
+                            ModuleExportName::Ident(i) => i.sym.to_string(),
+                            ModuleExportName::Str(s) => s.value.to_string(),
+                        });
+                    }
+                }
+                ModuleDecl::ExportDefaultDecl(..) | ModuleDecl::ExportDefaultExpr(..) => {
+                    names.insert("default".into());
+                }
+                ModuleDecl::ExportAll(..) | ModuleDecl::TsExportAssignment(..) => return Ok(None),
+                ModuleDecl::Import(..) | ModuleDecl::TsImportEquals(..) | ModuleDecl::TsNamespaceExport(..) => {}
+            }
+        }
+
+        Ok(Some(names))
+    }
+}
+
+/// `types` and `types@<range>`, which node ignores.
+fn is_types_condition(condition: &str) -> bool {
+    condition == "types" || condition.starts_with("types@")
+}
+
+/// Reports `types` conditions preceded by a sibling condition.
+fn check_types_condition_order(subpath: &str, entries: &[&ExportEntry], problems: &mut Vec<Problem>) {
+    for (idx, entry) in entries.iter().enumerate() {
+        let (last, parent) = match entry.conditions.split_last() {
+            Some(v) => v,
+            None => continue,
+        };
+        if last != "types" {
+            continue;
+        }
+
+        let has_preceding_sibling = entries[..idx]
+            .iter()
+            .any(|prev| prev.conditions.len() > parent.len() && prev.conditions.starts_with(parent));
+
+        if has_preceding_sibling {
+            problems.push(Problem::MisplacedTypes {
+                subpath: subpath.into(),
+                conditions: entry.conditions.clone(),
+            });
+        }
+    }
+}
+
+/// Returns the path of the declaration file tsc looks for when `target` is
+/// imported.
+fn declaration_path(target: &str) -> String {
+    for (js, dts) in [(".mjs", ".d.mts"), (".cjs", ".d.cts"), (".js", ".d.ts")] {
+        if let Some(stem) = target.strip_suffix(js) {
+            return format!("{}{}", stem, dts);
+        }
+    }
+
+    target.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc};
+
+    use stc_ts_module_loader::{package_json::PackageJson, resolvers::node16::Node16Resolver};
+    use swc_common::SourceMap;
+
+    use super::{PackageChecker, Problem};
+
+    /// Checks a package after writing `files` into a temporary directory.
+    fn check(name: &str, files: &[(&str, &str)]) -> Vec<Problem> {
+        let dir = std::env::temp_dir().join("stc-check-exports").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+
+        let pkg = PackageJson::read(&dir.join("package.json")).unwrap();
+        PackageChecker {
+            cm: Arc::new(SourceMap::default()),
+            dir: &dir,
+            pkg: &pkg,
+            resolver: Node16Resolver::new(),
+        }
+        .check()
+        .unwrap()
+    }
+
+    #[test]
+    fn valid_dual_package() {
+        let problems = check(
+            "valid",
+            &[
+                (
+                    "package.json",
+                    r#"{
+                        "exports": {
+                            ".": {
+                                "import": { "types": "./index.d.mts", "default": "./index.mjs" },
+                                "require": { "types": "./index.d.ts", "default": "./index.js" }
+                            }
+                        }
+                    }"#,
+                ),
+                ("index.d.mts", "export declare const a: number;\n"),
+                ("index.d.ts", "export declare const a: number;\n"),
+            ],
+        );
+
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[test]
+    fn missing_types() {
+        let problems = check(
+            "missing",
+            &[(
+                "package.json",
+                r#"{ "exports": { ".": { "types": "./index.d.ts", "default": "./index.js" } } }"#,
+            )],
+        );
+
+        assert!(
+            matches!(&*problems, [Problem::NoResolution { .. }, Problem::NoResolution { .. }]),
+            "{:?}",
+            problems
+        );
+    }
+
+    #[test]
+    fn misplaced_types_condition() {
+        let problems = check(
+            "misplaced",
+            &[
+                (
+                    "package.json",
+                    r#"{ "exports": { ".": { "default": "./index.js", "types": "./index.d.ts" } } }"#,
+                ),
+                ("index.d.ts", "export declare const a: number;\n"),
+            ],
+        );
+
+        assert!(
+            problems.iter().any(|p| matches!(p, Problem::MisplacedTypes { .. })),
+            "{:?}",
+            problems
+        );
+    }
+
+    #[test]
+    fn false_esm() {
+        let problems = check(
+            "false-esm",
+            &[
+                (
+                    "package.json",
+                    r#"{ "exports": { ".": { "types": "./index.d.mts", "require": "./index.js" } } }"#,
+                ),
+                ("index.d.mts", "export declare const a: number;\n"),
+            ],
+        );
+
+        assert!(problems.iter().any(|p| matches!(p, Problem::FalseEsm { .. })), "{:?}", problems);
+    }
+
+    #[test]
+    fn surface_mismatch() {
+        let problems = check(
+            "surface",
+            &[
+                (
+                    "package.json",
+                    r#"{
+                        "exports": {
+                            "import": { "types": "./index.d.mts", "default": "./index.mjs" },
+                            "require": { "types": "./index.d.ts", "default": "./index.js" }
+                        }
+                    }"#,
+                ),
+                ("index.d.mts", "export declare const a: number;\nexport declare const b: number;\n"),
+                ("index.d.ts", "export declare const a: number;\nexport declare const c: number;\n"),
+            ],
+        );
+
+        match &*problems {
+            [Problem::SurfaceMismatch {
+                only_in_esm, only_in_cjs, ..
+            }] => {
+                assert_eq!(only_in_esm, &["b"]);
+                assert_eq!(only_in_cjs, &["c"]);
+            }
+            _ => panic!("unexpected problems: {:?}", problems),
+        }
+    }
+
+    #[test]
+    fn subpaths_are_checked_once() {
+        let problems = check(
+            "subpaths",
+            &[(
+                "package.json",
+                r#"{
+                    "exports": {
+                        ".": { "types": "./index.d.ts", "default": "./index.js" },
+                        "./utils": { "types": "./utils.d.ts", "default": "./utils.js" }
+                    }
+                }"#,
+            )],
+        );
+
+        let subpaths = problems
+            .iter()
+            .map(|p| match p {
+                Problem::NoResolution { subpath, .. } => &**subpath,
+                _ => panic!("unexpected problem: {:?}", p),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(subpaths, vec![".", ".", "./utils", "./utils"]);
+    }
+}
@@ -2,32 +2,74 @@ extern crate swc_node_base;
 
 use std::{path::PathBuf, sync::Arc, time::Instant};
 
-use anyhow::Error;
+use anyhow::{bail, Context, Error};
 use clap::Parser;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
-use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_config::{ProgramBuilder, TsConfig};
+use stc_ts_env::{ModuleKind, Rule};
 use stc_ts_lang_server::LspCommand;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
-use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use stc_ts_type_checker::{
+    diagnostics::{dedup_errors, JsonEmitter},
+    formatter::PrettyEmitter,
+    incremental::{hash_options, BuildInfo},
+    project::ProjectOptions,
+    ErrorSummary,
+};
 use swc_common::{
-    errors::{ColorConfig, EmitterWriter, Handler},
+    errors::{ColorConfig, Emitter, EmitterWriter, Handler},
     FileName, SourceMap,
 };
 use swc_ecma_ast::EsVersion;
 use tracing_subscriber::EnvFilter;
 
-use crate::check::TestCommand;
+use crate::{
+    build::BuildCommand, check::TestCommand, check_exports::CheckExportsCommand, stream::StreamingEmitter,
+    test_fixtures::TestFixturesCommand, watch::WatchCommand, workspace::CheckWorkspaceCommand,
+};
 
+mod build;
 mod check;
+mod check_exports;
+mod stream;
+mod test_fixtures;
+mod watch;
+mod workspace;
 
 #[derive(Debug, Parser)]
 #[command(name = "stc", about = "Super fast type checker for typescript", author, rename_all = "camel")]
 enum Command {
     Test(TestCommand),
+    Build(BuildCommand),
+    Watch(WatchCommand),
+    #[command(name = "check-exports")]
+    CheckExports(CheckExportsCommand),
+    #[command(name = "check-workspace")]
+    CheckWorkspace(CheckWorkspaceCommand),
+    #[command(name = "test-fixtures")]
+    TestFixtures(TestFixturesCommand),
     Lsp(LspCommand),
 }
 
+impl Command {
+    /// Whether diagnostics should be printed as JSON lines.
+    fn json(&self) -> bool {
+        match self {
+            Command::Test(cmd) => cmd.json,
+            Command::Build(cmd) => cmd.json,
+            _ => false,
+        }
+    }
+
+    /// Whether diagnostics should be printed with code frames.
+    fn pretty(&self) -> bool {
+        match self {
+            Command::Test(cmd) => cmd.pretty,
+            Command::Build(cmd) => cmd.pretty,
+            _ => false,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let start = Instant::now();
@@ -48,7 +90,13 @@ async fn main() -> Result<(), Error> {
 
     let cm = Arc::new(SourceMap::default());
     let handler = {
-        let emitter = Box::new(EmitterWriter::stderr(ColorConfig::Always, Some(cm.clone()), false, false));
+        let emitter: Box<dyn Emitter> = if command.json() {
+            Box::new(JsonEmitter::stdout(cm.clone()))
+        } else if command.pretty() {
+            Box::new(PrettyEmitter::stderr(cm.clone()))
+        } else {
+            Box::new(EmitterWriter::stderr(ColorConfig::Always, Some(cm.clone()), false, false))
+        };
         Arc::new(Handler::with_emitter(true, false, emitter))
     };
 
@@ -62,12 +110,40 @@ async fn main() -> Result<(), Error> {
 
     match command {
         Command::Test(cmd) => {
+            let config = match &cmd.project {
+                Some(path) => {
+                    let config = TsConfig::load(path)?;
+                    for err in &config.errors {
+                        err.emit(&handler);
+                    }
+                    Some(config)
+                }
+                None => None,
+            };
+
+            let (rule, target, module) = match &config {
+                Some(config) => {
+                    let mut rule = config.rule;
+                    if cmd.strict {
+                        rule.enable_strict();
+                    }
+                    rule.strict_parity |= cmd.strict_parity;
+                    (rule, config.target, config.module.clone())
+                }
+                None => (
+                    Rule::builder().strict(cmd.strict).strict_parity(cmd.strict_parity).build(),
+                    EsVersion::latest(),
+                    ModuleKind::None.into(),
+                ),
+            };
+
             let libs = {
                 let start = Instant::now();
 
-                let mut libs = match cmd.libs {
-                    Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
-                    None => Lib::load("es5"),
+                let mut libs = match (&cmd.libs, &config) {
+                    (Some(libs), _) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+                    (None, Some(config)) => config.libs_or_default(),
+                    (None, None) => Lib::default_for(target),
                 };
                 libs.sort();
                 libs.dedup();
@@ -79,43 +155,119 @@ async fn main() -> Result<(), Error> {
                 libs
             };
 
-            let env = Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs);
+            let build_info_path = if cmd.incremental || config.as_ref().map_or(false, |c| c.incremental) {
+                match (&cmd.build_info_file, &config) {
+                    (Some(path), _) => Some(path.clone()),
+                    (None, Some(config)) => Some(config.build_info_path()),
+                    (None, None) => bail!("`--incremental` requires `--project` or `--build-info-file`"),
+                }
+            } else {
+                None
+            };
+            if build_info_path.is_some() && cmd.stream {
+                bail!("`--stream` cannot be used with `--incremental`");
+            }
+            if cmd.baseline.is_some() && (build_info_path.is_some() || cmd.stream) {
+                bail!("`--baseline` cannot be used with `--incremental` or `--stream`");
+            }
+            let options = hash_options(&rule, target, &module, &libs, cmd.lib_dir.as_deref());
+
+            let files = match (&cmd.file, &config) {
+                (Some(file), _) => vec![PathBuf::from(file)],
+                (None, Some(config)) => {
+                    let program = ProgramBuilder::new(config).build();
+                    for err in &program.errors {
+                        err.emit(&handler);
+                    }
+                    program.root_files
+                }
+                (None, None) => bail!("either a file or `--project` is required"),
+            };
+            // Typings are looked up from the project if there's one.
+            let base = match &config {
+                Some(config) => config.path.clone(),
+                None => files[0].clone(),
+            };
 
-            let path = PathBuf::from(cmd.file);
+            let (paths_dir, base_url, paths) = match &config {
+                Some(config) if cmd.paths.is_empty() => (
+                    config.paths_dir.clone(),
+                    cmd.base_url.clone().or_else(|| config.base_url.clone()),
+                    config.paths.clone(),
+                ),
+                _ => (
+                    std::env::current_dir().context("failed to get the current directory")?,
+                    cmd.base_url.clone().or_else(|| config.as_ref().and_then(|c| c.base_url.clone())),
+                    cmd.paths()?,
+                ),
+            };
 
-            {
+            let (checker, mut errors) = {
                 let start = Instant::now();
 
-                let checker = Checker::new(
-                    cm.clone(),
-                    handler.clone(),
-                    env.clone(),
-                    None,
-                    ModuleLoader::new(cm.clone(), env.clone(), NodeResolver),
-                );
-
-                checker.load_typings(&path, None, cmd.types.as_deref());
+                let project = ProjectOptions {
+                    rule,
+                    target,
+                    module,
+                    libs: &libs,
+                    lib_dir: cmd.lib_dir.as_deref(),
+                    paths_dir,
+                    base_url,
+                    paths,
+                    base: &base,
+                    type_roots: cmd.type_roots.as_deref().or_else(|| config.as_ref()?.type_roots.as_deref()),
+                    types: cmd.types.as_deref().or_else(|| config.as_ref()?.types.as_deref()),
+                };
+                let result = project.checker(cm.clone(), handler.clone(), |resolver| resolver)?;
 
                 let end = Instant::now();
 
                 log::info!("Loading typing libraries took {:?}", end - start);
-            }
 
-            let mut errors = vec![];
+                result
+            };
+
+            let stream = if cmd.stream {
+                Some(Arc::new(StreamingEmitter::new(cm.clone(), handler.clone())))
+            } else {
+                None
+            };
+
+            let mut build_info = None;
+            let summary;
 
             let start = Instant::now();
             {
-                let mut checker = Checker::new(
-                    cm.clone(),
-                    handler.clone(),
-                    env.clone(),
-                    None,
-                    ModuleLoader::new(cm, env, NodeResolver),
-                );
+                let mut checker = checker.with_error_limit(cmd.error_limit());
+                if let Some(stream) = &stream {
+                    checker = checker.with_diagnostics_sink(stream.clone());
+                }
 
-                checker.check(Arc::new(FileName::Real(path)));
+                match &build_info_path {
+                    Some(path) => {
+                        let previous = BuildInfo::load(path);
+                        let result = checker.check_incremental(previous.as_ref(), &options, &files);
+
+                        log::info!(
+                            "Checked {} files again ({} files in the program)",
+                            result.checked.len(),
+                            result.build_info.files.len()
+                        );
+
+                        result.build_info.save(path)?;
+                        build_info = Some(result.build_info);
+                    }
+                    None => {
+                        checker.check_scripts(&files);
+                        for file in files {
+                            checker.check(Arc::new(FileName::Real(file)));
+                        }
+
+                        errors.extend(checker.take_errors());
+                    }
+                }
 
-                errors.extend(checker.take_errors());
+                summary = checker.summary();
             }
             let end = Instant::now();
 
@@ -123,17 +275,50 @@ async fn main() -> Result<(), Error> {
 
             {
                 let start = Instant::now();
-                for err in &errors {
-                    err.emit(&handler);
-                }
+                let error_count = match &stream {
+                    Some(stream) => stream.print_summary(),
+                    None => {
+                        let errors = cmd.apply_baseline(&cm, &handler, errors)?;
+                        let diagnostics = dedup_errors(&cm, errors);
+                        for diagnostic in &diagnostics {
+                            diagnostic.emit(&handler);
+                        }
+                        let mut error_count = diagnostics.len();
+                        if let Some(build_info) = &build_info {
+                            error_count += build_info.emit_diagnostics(&cm, &handler);
+                        }
+                        error_count
+                    }
+                };
 
-                let end = Instant::now();
+                // Errors caused by the same problem are emitted once, so the count of the
+                // checker is not used.
+                let summary = ErrorSummary {
+                    errors: error_count,
+                    ..summary
+                };
+                handler.note_without_error(&summary.to_string());
 
-                log::info!("Found {} errors", errors.len());
+                let end = Instant::now();
 
                 log::info!("Error reporting took {:?}", end - start);
             }
         }
+        Command::Build(cmd) => {
+            cmd.run(cm, handler)?;
+        }
+        Command::Watch(cmd) => {
+            cmd.run()?;
+        }
+        Command::CheckExports(cmd) => {
+            cmd.run(cm, &handler)?;
+        }
+        Command::CheckWorkspace(cmd) => {
+            cmd.run(cm, handler)?;
+        }
+        Command::TestFixtures(cmd) => {
+            cmd.run(cm, handler)?;
+        }
         Command::Lsp(cmd) => {
             cmd.run().await?;
         }
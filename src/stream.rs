@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use stc_ts_errors::Error;
+use stc_ts_type_checker::diagnostics::{dedup_errors, DiagnosticsSink, SinkDiagnostic};
+use swc_common::{errors::Handler, FileName, SourceMap};
+
+/// Emits errors of each module as soon as the module is checked, for
+/// `--stream`.
+pub struct StreamingEmitter {
+    cm: Arc<SourceMap>,
+    handler: Arc<Handler>,
+    /// Number of errors per file.
+    counts: Mutex<Vec<(Arc<FileName>, usize)>>,
+}
+
+impl StreamingEmitter {
+    pub fn new(cm: Arc<SourceMap>, handler: Arc<Handler>) -> Self {
+        Self {
+            cm,
+            handler,
+            counts: Default::default(),
+        }
+    }
+
+    /// Prints the number of errors per file, sorted by file name, and returns
+    /// the total.
+    ///
+    /// Errors are emitted in the order modules are checked, which differs
+    /// between runs, so this is the deterministic part of the output.
+    pub fn print_summary(&self) -> usize {
+        let mut counts = self.counts.lock().clone();
+        counts.retain(|(_, count)| *count > 0);
+        counts.sort_by_key(|(file, _)| file.to_string());
+
+        for (file, count) in &counts {
+            self.handler.note_without_error(&format!("{}: {} errors", file, count));
+        }
+
+        counts.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl DiagnosticsSink for StreamingEmitter {
+    fn filter(&self, _: &Arc<FileName>, error: &Error) -> bool {
+        !error.is_suggestion()
+    }
+
+    fn on_module_checked(&self, file: &Arc<FileName>, diagnostics: &[SinkDiagnostic]) {
+        let errors = diagnostics.iter().map(|diagnostic| diagnostic.error.clone()).collect();
+        let diagnostics = dedup_errors(&self.cm, errors);
+        for diagnostic in &diagnostics {
+            diagnostic.emit(&self.handler);
+        }
+
+        self.counts.lock().push((file.clone(), diagnostics.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Write},
+        path::PathBuf,
+        sync::Arc,
+    };
+
+    use parking_lot::Mutex;
+    use stc_ts_errors::{Error, ErrorKind};
+    use stc_ts_type_checker::diagnostics::{DiagnosticsSink, JsonDiagnostic, JsonEmitter, Severity, SinkDiagnostic};
+    use swc_common::{errors::Handler, BytePos, FileName, SourceMap, Span, SyntaxContext};
+
+    use super::StreamingEmitter;
+
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuf {
+        /// Takes the emitted diagnostics.
+        fn take(&self) -> Vec<JsonDiagnostic> {
+            let output = String::from_utf8(std::mem::take(&mut *self.0.lock())).unwrap();
+            output.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+        }
+    }
+
+    fn emitter() -> (Arc<SourceMap>, StreamingEmitter, SharedBuf) {
+        let cm = Arc::new(SourceMap::default());
+        let buf = SharedBuf::default();
+        let handler = Handler::with_emitter(true, false, Box::new(JsonEmitter::new(cm.clone(), Box::new(buf.clone()))));
+
+        (cm.clone(), StreamingEmitter::new(cm, Arc::new(handler)), buf)
+    }
+
+    /// Returns the file and a function creating an error at `lo` of the file.
+    fn file(cm: &SourceMap, name: &str) -> (Arc<FileName>, impl Fn(u32) -> SinkDiagnostic) {
+        let file = FileName::Real(PathBuf::from(name));
+        let fm = cm.new_source_file(file.clone(), "x".repeat(100));
+
+        let error = move |lo: u32| SinkDiagnostic {
+            error: ErrorKind::ModuleNotFound {
+                span: Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(lo + 1), SyntaxContext::empty()),
+            }
+            .into(),
+            severity: Severity::Error,
+        };
+
+        (Arc::new(file), error)
+    }
+
+    #[test]
+    fn errors_are_emitted_per_module() {
+        let (cm, emitter, buf) = emitter();
+        let (b, b_error) = file(&cm, "/app/b.ts");
+        let (a, a_error) = file(&cm, "/app/a.ts");
+        let (c, _) = file(&cm, "/app/c.ts");
+
+        // Duplicates are emitted once.
+        emitter.on_module_checked(&b, &[b_error(0), b_error(0), b_error(10)]);
+        let emitted = buf.take();
+        assert_eq!(emitted.len(), 2);
+        assert!(emitted
+            .iter()
+            .all(|d| d.file.as_deref() == Some("/app/b.ts") && d.code == Some(2307)));
+
+        emitter.on_module_checked(&a, &[a_error(5)]);
+        emitter.on_module_checked(&c, &[]);
+        assert_eq!(buf.take().len(), 1);
+
+        // Files without errors are omitted.
+        assert_eq!(emitter.print_summary(), 3);
+        let summary = buf.take().into_iter().map(|d| d.message).collect::<Vec<_>>();
+        assert_eq!(summary, vec!["/app/a.ts: 1 errors", "/app/b.ts: 2 errors"]);
+    }
+
+    #[test]
+    fn suggestions_are_not_streamed() {
+        let (cm, emitter, _) = emitter();
+        let (a, a_error) = file(&cm, "/app/a.ts");
+
+        let deprecated: Error = ErrorKind::Deprecated {
+            span: Default::default(),
+            name: "foo".into(),
+            message: None,
+            decl_span: Default::default(),
+        }
+        .into();
+
+        assert!(!emitter.filter(&a, &deprecated));
+        assert!(emitter.filter(&a, &a_error(0).error));
+    }
+}
@@ -0,0 +1,254 @@
+//! Snapshot tests of type checking.
+//!
+//! A fixture is a `.ts` or `.tsx` file (not a `.d.ts` file) in the fixture
+//! directory or its subdirectories. Errors expected for `foo.ts` are stored
+//! in `foo.errors.json` next to it, and a fixture without the file should
+//! not have any error.
+//!
+//! ```json
+//! [
+//!   { "line": 3, "column": 7, "endLine": 3, "endColumn": 8, "code": "TS2322" },
+//!   { "file": "../index.d.ts", "line": 1, "column": 1, "endLine": 1, "endColumn": 4, "code": "TS2304" }
+//! ]
+//! ```
+//!
+//! Lines and columns are 1-based, and the end is exclusive. `file` is the
+//! path relative to the fixture, and it's omitted for errors in the fixture
+//! itself.
+//!
+//! Errors suppressed by `// @ts-ignore` or `// @ts-expect-error` are not
+//! recorded, and an unused `// @ts-expect-error` comment is recorded as
+//! `TS2578` by the checker.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind, Rule};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::{errors::Handler, FileName, SourceMap, Spanned};
+use swc_ecma_ast::EsVersion;
+use walkdir::WalkDir;
+
+/// Type check fixtures and compare errors with the expected errors.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct TestFixturesCommand {
+    /// The directory containing fixtures.
+    #[clap(name = "dir")]
+    pub dir: PathBuf,
+
+    /// Update the expected errors instead of comparing them.
+    #[clap(long)]
+    pub bless: bool,
+
+    /// The builtin libraries to load. Defaults to `es5`.
+    #[clap(long)]
+    pub libs: Option<Vec<String>>,
+
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc, like `node_modules/typescript/lib`.
+    #[clap(long)]
+    pub lib_dir: Option<PathBuf>,
+
+    /// Enable all strict type checking options.
+    #[clap(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExpectedError {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    code: String,
+}
+
+impl ExpectedError {
+    fn describe(&self) -> String {
+        format!(
+            "{} at {}{}:{}-{}:{}",
+            self.code,
+            self.file.as_ref().map(|f| format!("{} ", f)).unwrap_or_default(),
+            self.line,
+            self.column,
+            self.end_line,
+            self.end_column
+        )
+    }
+}
+
+impl TestFixturesCommand {
+    pub fn run(&self, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<(), Error> {
+        let mut libs = match &self.libs {
+            Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+            None => Lib::load("es5"),
+        };
+        libs.sort();
+        libs.dedup();
+
+        let rule = Rule::builder().strict(self.strict).build();
+        let env = match &self.lib_dir {
+            Some(dir) => Env::with_lib_dir(rule, EsVersion::latest(), ModuleKind::None, &libs, dir)
+                .with_context(|| format!("failed to load libs from {}", dir.display()))?,
+            None => Env::simple(rule, EsVersion::latest(), ModuleKind::None, &libs),
+        };
+
+        let fixtures = find_fixtures(&self.dir);
+        if fixtures.is_empty() {
+            bail!("no fixture found in {}", self.dir.display())
+        }
+
+        let mut failed = 0;
+        for fixture in &fixtures {
+            let actual = check_fixture(&cm, &handler, &env, fixture)?;
+            let expected_path = fixture.with_extension("errors.json");
+
+            if self.bless {
+                if actual.is_empty() {
+                    if expected_path.exists() {
+                        fs::remove_file(&expected_path).with_context(|| format!("failed to remove {}", expected_path.display()))?;
+                    }
+                } else {
+                    let json = serde_json::to_string_pretty(&actual)?;
+                    fs::write(&expected_path, json + "\n").with_context(|| format!("failed to write {}", expected_path.display()))?;
+                }
+                continue;
+            }
+
+            let expected: Vec<ExpectedError> = if expected_path.exists() {
+                let content = fs::read_to_string(&expected_path).with_context(|| format!("failed to read {}", expected_path.display()))?;
+                let mut expected: Vec<ExpectedError> =
+                    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", expected_path.display()))?;
+                expected.sort();
+                expected
+            } else {
+                vec![]
+            };
+
+            if expected == actual {
+                continue;
+            }
+            failed += 1;
+
+            let mut msg = format!("{}: errors differ", fixture.display());
+            for err in expected.iter().filter(|err| !actual.contains(err)) {
+                msg.push_str(&format!("\n  missing: {}", err.describe()));
+            }
+            for err in actual.iter().filter(|err| !expected.contains(err)) {
+                msg.push_str(&format!("\n  unexpected: {}", err.describe()));
+            }
+            handler.err(&msg);
+        }
+
+        if self.bless {
+            log::info!("Updated {} fixtures", fixtures.len());
+            return Ok(());
+        }
+
+        if failed > 0 {
+            bail!(
+                "{} of {} fixtures failed (run with `--bless` to update them)",
+                failed,
+                fixtures.len()
+            )
+        }
+
+        log::info!("{} fixtures passed", fixtures.len());
+
+        Ok(())
+    }
+}
+
+fn find_fixtures(dir: &Path) -> Vec<PathBuf> {
+    let mut fixtures = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            let name = path.to_string_lossy();
+            (name.ends_with(".ts") || name.ends_with(".tsx")) && !name.ends_with(".d.ts")
+        })
+        .filter(|path| !path.components().any(|c| c.as_os_str() == "node_modules"))
+        .collect::<Vec<_>>();
+    fixtures.sort();
+    fixtures
+}
+
+/// Returns sorted errors of `fixture`.
+fn check_fixture(cm: &Arc<SourceMap>, handler: &Arc<Handler>, env: &Env, fixture: &Path) -> Result<Vec<ExpectedError>, Error> {
+    let fixture_dir = fixture.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut checker = Checker::new(
+        cm.clone(),
+        handler.clone(),
+        env.clone(),
+        None,
+        ModuleLoader::new(cm.clone(), env.clone(), NodeResolver),
+    );
+    let fixture = fixture
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", fixture.display()))?;
+    checker.check(Arc::new(FileName::Real(fixture.clone())));
+
+    let mut errors = vec![];
+    for err in ErrorKind::flatten(checker.take_errors()) {
+        let span = err.span();
+        if span.is_dummy() {
+            continue;
+        }
+        let lo = cm.lookup_char_pos(span.lo);
+        let hi = cm.lookup_char_pos(span.hi);
+
+        let file = match &lo.file.name {
+            FileName::Real(path) if *path == fixture => None,
+            FileName::Real(path) => Some(relative_path(fixture_dir, path)),
+            name => Some(name.to_string()),
+        };
+
+        errors.push(ExpectedError {
+            file,
+            line: lo.line,
+            column: lo.col.0 + 1,
+            end_line: hi.line,
+            end_column: hi.col.0 + 1,
+            code: format!("TS{}", err.code()),
+        });
+    }
+
+    errors.sort();
+    errors.dedup();
+
+    Ok(errors)
+}
+
+fn relative_path(base: &Path, path: &Path) -> String {
+    let base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+    let base = base.components().collect::<Vec<_>>();
+    let target = path.components().collect::<Vec<_>>();
+
+    let common = base.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    let mut rel = PathBuf::new();
+    for _ in common..base.len() {
+        rel.push("..");
+    }
+    for c in &target[common..] {
+        rel.push(c);
+    }
+
+    rel.to_string_lossy().replace('\\', "/")
+}
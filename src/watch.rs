@@ -0,0 +1,266 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver, RecvTimeoutError},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Error};
+use clap::Args;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use stc_ts_config::{ProgramBuilder, TsConfig};
+use stc_ts_type_checker::{
+    diagnostics::dedup_errors,
+    incremental::{hash_options, BuildInfo},
+    project::ProjectOptions,
+};
+use swc_common::{
+    errors::{ColorConfig, EmitterWriter, Handler},
+    SourceMap,
+};
+
+/// Type check a project, and check it again whenever a file changes.
+///
+/// Only files affected by the changes are checked again. Errors of the other
+/// files are reported using the results of the previous run.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct WatchCommand {
+    /// Path to `tsconfig.json`, or a directory containing it.
+    #[clap(name = "project", default_value = ".")]
+    pub project: PathBuf,
+
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc, like `node_modules/typescript/lib`.
+    #[clap(long)]
+    pub lib_dir: Option<PathBuf>,
+
+    /// Milliseconds to wait for more changes before checking again, as
+    /// editors often write a file multiple times on save.
+    #[clap(long, default_value = "50")]
+    pub debounce: u64,
+}
+
+impl WatchCommand {
+    pub fn run(&self) -> Result<(), Error> {
+        let path = if self.project.is_dir() {
+            self.project.join("tsconfig.json")
+        } else {
+            self.project.clone()
+        };
+        let path = path.canonicalize().with_context(|| format!("failed to find {}", path.display()))?;
+        let dir = path.parent().context("the configuration should be in a directory")?.to_path_buf();
+
+        let (tx, rx) = channel();
+        let mut watcher = RecommendedWatcher::new(tx, Default::default()).context("failed to create a file watcher")?;
+        watcher
+            .watch(&dir, RecursiveMode::Recursive)
+            .with_context(|| format!("failed to watch {}", dir.display()))?;
+
+        let mut build_info = None;
+        let mut config = None;
+        loop {
+            // The last valid configuration is used to filter changes.
+            if let Some(loaded) = self.check_once(&path, &mut build_info) {
+                config = Some(loaded);
+            }
+
+            log::info!("Watching for file changes");
+            self.wait_for_changes(&rx, config.as_ref())?;
+        }
+    }
+
+    /// Loads the configuration and checks the project, updating `build_info`.
+    ///
+    /// Errors are reported instead of being returned, so the watcher keeps
+    /// running until they are fixed. [None] is returned if the configuration
+    /// cannot be loaded.
+    fn check_once(&self, path: &Path, build_info: &mut Option<BuildInfo>) -> Option<TsConfig> {
+        let config = match TsConfig::load(path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!("Failed to load {}: {:?}", path.display(), err);
+                return None;
+            }
+        };
+
+        match self.check(&config, build_info.as_ref()) {
+            Ok(info) => *build_info = Some(info),
+            // The previous state is kept, so only changed files are checked again.
+            Err(err) => log::error!("Failed to check {}: {:?}", path.display(), err),
+        }
+
+        Some(config)
+    }
+
+    /// Returns the state to use for the next run.
+    fn check(&self, config: &TsConfig, previous: Option<&BuildInfo>) -> Result<BuildInfo, Error> {
+        let start = Instant::now();
+
+        // A new source map is used for each run, so changed files are loaded again.
+        let cm = Arc::new(SourceMap::default());
+        let handler = {
+            let emitter = Box::new(EmitterWriter::stderr(ColorConfig::Always, Some(cm.clone()), false, false));
+            Arc::new(Handler::with_emitter(true, false, emitter))
+        };
+
+        for err in &config.errors {
+            err.emit(&handler);
+        }
+        let program = ProgramBuilder::new(config).build();
+        for err in &program.errors {
+            err.emit(&handler);
+        }
+
+        let libs = config.libs_or_default();
+        let options = hash_options(&config.rule, config.target, &config.module, &libs, self.lib_dir.as_deref());
+
+        let (mut checker, errors) =
+            ProjectOptions::from_config(config, &libs, self.lib_dir.as_deref())
+                .checker(cm.clone(), handler.clone(), |resolver| resolver)?;
+
+        let result = checker.check_incremental(previous, &options, &program.root_files);
+
+        let diagnostics = dedup_errors(&cm, errors);
+        for diagnostic in &diagnostics {
+            diagnostic.emit(&handler);
+        }
+        let error_count =
+            config.errors.len() + program.errors.len() + diagnostics.len() + result.build_info.emit_diagnostics(&cm, &handler);
+
+        if config.incremental {
+            result.build_info.save(&config.build_info_path())?;
+        }
+
+        let end = Instant::now();
+
+        log::info!(
+            "Found {} errors. Checked {} of {} files in {:?}",
+            error_count,
+            result.checked.len(),
+            result.build_info.files.len(),
+            end - start
+        );
+
+        Ok(result.build_info)
+    }
+
+    /// Blocks until a file which may affect the result changes.
+    fn wait_for_changes(&self, rx: &Receiver<notify::Result<Event>>, config: Option<&TsConfig>) -> Result<(), Error> {
+        let mut changed = false;
+        loop {
+            // Wait for the first change, and then until there's no more change.
+            let event = if changed {
+                match rx.recv_timeout(Duration::from_millis(self.debounce)) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => return Ok(()),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            } else {
+                match rx.recv() {
+                    Ok(event) => event,
+                    Err(..) => break,
+                }
+            };
+
+            let event = match event {
+                Ok(v) => v,
+                Err(err) => {
+                    log::warn!("Failed to watch files: {}", err);
+                    continue;
+                }
+            };
+            if event.kind.is_access() {
+                continue;
+            }
+
+            if let Some(path) = event.paths.iter().find(|path| is_relevant(path, config)) {
+                log::debug!("{} changed", path.display());
+                changed = true;
+            }
+        }
+
+        Err(Error::msg("the file watcher stopped unexpectedly"))
+    }
+}
+
+/// Returns `true` if a change of `path` may affect the result.
+///
+/// Outputs are ignored, unless `config` is [None] because the configuration
+/// is invalid.
+fn is_relevant(path: &Path, config: Option<&TsConfig>) -> bool {
+    if let Some(config) = config {
+        if path == config.build_info_path() || config.out_dir.as_deref().map_or(false, |dir| path.starts_with(dir)) {
+            return false;
+        }
+    }
+    if path.components().any(|c| c == Component::Normal(".git".as_ref())) {
+        return false;
+    }
+
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("ts" | "tsx" | "mts" | "cts" | "json")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use stc_ts_config::TsConfig;
+
+    use super::{is_relevant, WatchCommand};
+
+    fn command() -> WatchCommand {
+        WatchCommand {
+            project: ".".into(),
+            lib_dir: None,
+            debounce: 50,
+        }
+    }
+
+    #[test]
+    fn relevant_paths() {
+        assert!(is_relevant(Path::new("/project/src/a.ts"), None));
+        assert!(is_relevant(Path::new("/project/tsconfig.json"), None));
+        assert!(!is_relevant(Path::new("/project/src/a.js"), None));
+        assert!(!is_relevant(Path::new("/project/.git/index.ts"), None));
+    }
+
+    #[test]
+    fn outputs_are_not_relevant() {
+        let config = TsConfig::parse(
+            Path::new("/project/tsconfig.json"),
+            r#"{ "compilerOptions": { "outDir": "dist" } }"#,
+        )
+        .unwrap();
+
+        assert!(is_relevant(Path::new("/project/src/a.ts"), Some(&config)));
+        assert!(!is_relevant(Path::new("/project/dist/a.d.ts"), Some(&config)));
+        assert!(!is_relevant(&config.build_info_path(), Some(&config)));
+    }
+
+    /// The watcher keeps running while the configuration is invalid, and
+    /// checks the project again once it's fixed.
+    #[test]
+    fn invalid_config_is_reported() {
+        let dir = std::env::temp_dir().join("stc-watch").join("invalid-config");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tsconfig.json");
+        fs::write(dir.join("a.ts"), "export const a: number = 1;\n").unwrap();
+
+        let command = command();
+        let mut build_info = None;
+
+        fs::write(&path, "{").unwrap();
+        assert!(command.check_once(&path, &mut build_info).is_none());
+        assert!(build_info.is_none());
+
+        fs::write(&path, r#"{ "files": ["a.ts"] }"#).unwrap();
+        assert!(command.check_once(&path, &mut build_info).is_some());
+        assert!(build_info.is_some());
+    }
+}
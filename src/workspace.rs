@@ -0,0 +1,390 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::{bail, Context, Error};
+use clap::Args;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleKind, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{package_json::PackageJson, resolvers::ModuleResolver};
+use stc_ts_type_checker::{diagnostics::dedup_errors, loader::ModuleLoader, Checker};
+use swc_common::{errors::Handler, FileName, SourceMap};
+use swc_ecma_ast::EsVersion;
+use walkdir::{DirEntry, WalkDir};
+
+use crate::stream::StreamingEmitter;
+
+/// Type check every TypeScript project of a monorepo.
+///
+/// Each directory containing `tsconfig.json` is a project. A project depends
+/// on another one if its `package.json` lists the package of the other
+/// project as a dependency, and projects are checked in that order. All
+/// projects share the builtin libraries, the source map and parsed modules.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct CheckWorkspaceCommand {
+    /// The root directory of the workspace.
+    #[clap(name = "root", default_value = ".")]
+    pub root: PathBuf,
+
+    /// The builtin libraries to load. Defaults to the libraries `tsc` uses for
+    /// the latest target.
+    #[clap(long)]
+    pub libs: Option<Vec<String>>,
+
+    /// Load the builtin libraries from this directory instead of the ones
+    /// bundled with stc, like `node_modules/typescript/lib`.
+    #[clap(long)]
+    pub lib_dir: Option<PathBuf>,
+
+    /// Enable all strict type checking options.
+    #[clap(long)]
+    pub strict: bool,
+
+    /// Report constructs stc cannot check yet as errors, instead of accepting
+    /// them silently.
+    #[clap(long)]
+    pub strict_parity: bool,
+
+    /// Emit errors of each module as soon as the module is checked, and print
+    /// a summary at the end.
+    #[clap(long)]
+    pub stream: bool,
+}
+
+impl CheckWorkspaceCommand {
+    pub fn run(&self, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<(), Error> {
+        let projects = discover_projects(&self.root)?;
+        if projects.is_empty() {
+            bail!("no `tsconfig.json` found in {}", self.root.display())
+        }
+
+        let (order, cycle) = sort_projects(&projects);
+        if !cycle.is_empty() {
+            let names = cycle.iter().map(|&idx| projects[idx].display_name()).collect::<Vec<_>>();
+            handler.warn(&format!("circular dependency between projects: {}", names.join(", ")));
+        }
+
+        let mut libs = match &self.libs {
+            Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+            None => Lib::default_for(EsVersion::latest()),
+        };
+        libs.sort();
+        libs.dedup();
+
+        let rule = Rule::builder().strict(self.strict).strict_parity(self.strict_parity).build();
+        let env = match &self.lib_dir {
+            Some(dir) => Env::with_lib_dir(rule, EsVersion::latest(), ModuleKind::None, &libs, dir)
+                .with_context(|| format!("failed to load libs from {}", dir.display()))?,
+            None => Env::simple(rule, EsVersion::latest(), ModuleKind::None, &libs),
+        };
+        // Projects are analyzed separately, because scripts of a project should not
+        // see globals of other projects. Modules are parsed only once, though.
+        let loader = Arc::new(ModuleLoader::new(cm.clone(), env.clone(), ModuleResolver::new(env.module())));
+
+        let stream = if self.stream {
+            Some(Arc::new(StreamingEmitter::new(cm.clone(), handler.clone())))
+        } else {
+            None
+        };
+
+        let mut error_count = 0;
+        for idx in order.into_iter().chain(cycle) {
+            let project = &projects[idx];
+            let start = Instant::now();
+
+            let mut checker = Checker::new(cm.clone(), handler.clone(), env.clone(), None, loader.clone());
+            if let Some(stream) = &stream {
+                checker = checker.with_diagnostics_sink(stream.clone());
+            }
+
+            checker.check_scripts(&project.files);
+            for file in &project.files {
+                checker.check(Arc::new(FileName::Real(file.clone())));
+            }
+
+            let diagnostics = dedup_errors(&cm, checker.take_errors());
+            if stream.is_none() {
+                for diagnostic in &diagnostics {
+                    diagnostic.emit(&handler);
+                }
+            }
+            error_count += diagnostics.len();
+
+            let end = Instant::now();
+
+            log::info!(
+                "Checked {} ({} files, {} errors) in {:?}",
+                project.display_name(),
+                project.files.len(),
+                diagnostics.len(),
+                end - start
+            );
+        }
+
+        if let Some(stream) = &stream {
+            let total = stream.print_summary();
+            handler.note_without_error(&format!("Found {} errors", total));
+        }
+
+        log::info!("Found {} errors in {} projects", error_count, projects.len());
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Project {
+    /// Directory containing `tsconfig.json`.
+    dir: PathBuf,
+
+    /// Directory of the package containing the project.
+    pkg_dir: Option<PathBuf>,
+
+    /// Name of the package containing the project.
+    name: Option<String>,
+
+    /// Packages the project depends on.
+    deps: BTreeSet<String>,
+
+    /// Source files which are not declaration files. Declaration files are
+    /// loaded if a source file imports them.
+    files: Vec<PathBuf>,
+}
+
+impl Project {
+    fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self.dir.display().to_string(),
+        }
+    }
+}
+
+fn is_ignored_dir(entry: &DirEntry) -> bool {
+    if entry.depth() == 0 || !entry.file_type().is_dir() {
+        return false;
+    }
+
+    let name = entry.file_name().to_string_lossy();
+    name == "node_modules" || name.starts_with('.')
+}
+
+fn is_source_file(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts") {
+        return false;
+    }
+
+    matches!(path.extension().and_then(|ext| ext.to_str()), Some("ts" | "tsx" | "mts" | "cts"))
+}
+
+fn discover_projects(root: &Path) -> Result<Vec<Project>, Error> {
+    let mut dirs = BTreeSet::new();
+    for entry in WalkDir::new(root).into_iter().filter_entry(|e| !is_ignored_dir(e)) {
+        let entry = entry?;
+        if entry.file_type().is_file() && entry.file_name() == "tsconfig.json" {
+            if let Some(dir) = entry.path().parent() {
+                dirs.insert(dir.to_path_buf());
+            }
+        }
+    }
+
+    let mut projects = vec![];
+    for dir in &dirs {
+        let pkg_dir = dir
+            .ancestors()
+            .take_while(|d| d.starts_with(root))
+            .find(|d| d.join("package.json").is_file());
+        let pkg = match pkg_dir {
+            Some(pkg_dir) => Some(PackageJson::read(&pkg_dir.join("package.json"))?),
+            None => None,
+        };
+
+        // Files of nested projects belong to the nested project.
+        let mut files = vec![];
+        for entry in WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|e| !is_ignored_dir(e) && (e.depth() == 0 || !dirs.contains(e.path())))
+        {
+            let entry = entry?;
+            if entry.file_type().is_file() && is_source_file(entry.path()) {
+                files.push(entry.into_path());
+            }
+        }
+        files.sort();
+
+        projects.push(Project {
+            dir: dir.clone(),
+            pkg_dir: pkg_dir.map(Path::to_path_buf),
+            name: pkg.as_ref().and_then(|pkg| pkg.name.clone()),
+            deps: pkg
+                .as_ref()
+                .map(|pkg| pkg.dependency_names().map(String::from).collect())
+                .unwrap_or_default(),
+            files,
+        });
+    }
+
+    Ok(projects)
+}
+
+/// Returns the indices of projects in dependency order, and the projects which
+/// are part of a dependency cycle.
+fn sort_projects(projects: &[Project]) -> (Vec<usize>, Vec<usize>) {
+    // A package can contain several projects, like one for tests.
+    let mut by_pkg_dir = BTreeMap::<&Path, Vec<usize>>::new();
+    let mut pkg_dirs = BTreeMap::<&str, &Path>::new();
+    for (idx, p) in projects.iter().enumerate() {
+        if let Some(pkg_dir) = &p.pkg_dir {
+            by_pkg_dir.entry(pkg_dir).or_default().push(idx);
+            if let Some(name) = &p.name {
+                pkg_dirs.entry(name).or_insert(pkg_dir);
+            }
+        }
+    }
+
+    let deps = projects
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            p.deps
+                .iter()
+                .filter_map(|name| pkg_dirs.get(&**name))
+                .flat_map(|pkg_dir| by_pkg_dir[pkg_dir].iter().copied())
+                .filter(|&dep| dep != idx)
+                .collect::<BTreeSet<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut order = vec![];
+    let mut done = vec![false; projects.len()];
+
+    // Projects are visited in path order, so the result is deterministic.
+    loop {
+        let ready = (0..projects.len())
+            .filter(|&idx| !done[idx] && deps[idx].iter().all(|&dep| done[dep]))
+            .collect::<Vec<_>>();
+        if ready.is_empty() {
+            break;
+        }
+
+        for idx in ready {
+            done[idx] = true;
+            order.push(idx);
+        }
+    }
+
+    let cycle = (0..projects.len()).filter(|&idx| !done[idx]).collect();
+
+    (order, cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{discover_projects, sort_projects};
+
+    /// Writes `files` into a temporary directory, and returns the names of
+    /// projects in the order they are checked.
+    fn check_order(name: &str, files: &[(&str, &str)]) -> (Vec<String>, Vec<String>) {
+        let root = std::env::temp_dir().join("stc-workspace").join(name);
+        let _ = fs::remove_dir_all(&root);
+        for (file, content) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let projects = discover_projects(&root).unwrap();
+        let (order, cycle) = sort_projects(&projects);
+        let names = |indices: Vec<usize>| {
+            indices
+                .into_iter()
+                .map(|idx| projects[idx].dir.strip_prefix(&root).unwrap().display().to_string())
+                .collect()
+        };
+
+        (names(order), names(cycle))
+    }
+
+    #[test]
+    fn dependencies_come_first() {
+        let (order, cycle) = check_order(
+            "order",
+            &[
+                ("app/package.json", r#"{ "name": "app", "dependencies": { "lib": "*" } }"#),
+                ("app/tsconfig.json", "{}"),
+                ("lib/package.json", r#"{ "name": "lib" }"#),
+                ("lib/tsconfig.json", "{}"),
+            ],
+        );
+
+        assert_eq!(order, vec!["lib", "app"]);
+        assert!(cycle.is_empty());
+    }
+
+    #[test]
+    fn every_project_of_a_package_is_a_dependency() {
+        let (order, _) = check_order(
+            "package",
+            &[
+                ("a/package.json", r#"{ "name": "app", "dependencies": { "lib": "*" } }"#),
+                ("a/tsconfig.json", "{}"),
+                ("lib/package.json", r#"{ "name": "lib" }"#),
+                ("lib/tsconfig.json", "{}"),
+                ("lib/test/tsconfig.json", "{}"),
+            ],
+        );
+
+        assert_eq!(order, vec!["lib", "lib/test", "a"]);
+    }
+
+    #[test]
+    fn circular_dependencies() {
+        let (order, cycle) = check_order(
+            "cycle",
+            &[
+                ("a/package.json", r#"{ "name": "a", "dependencies": { "b": "*" } }"#),
+                ("a/tsconfig.json", "{}"),
+                ("b/package.json", r#"{ "name": "b", "devDependencies": { "a": "*" } }"#),
+                ("b/tsconfig.json", "{}"),
+                ("c/tsconfig.json", "{}"),
+            ],
+        );
+
+        assert_eq!(order, vec!["c"]);
+        assert_eq!(cycle, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn files_of_nested_projects() {
+        let root = std::env::temp_dir().join("stc-workspace").join("nested");
+        let _ = fs::remove_dir_all(&root);
+        for file in [
+            "tsconfig.json",
+            "a.ts",
+            "b.d.ts",
+            "inner/tsconfig.json",
+            "inner/c.ts",
+            "node_modules/d.ts",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let projects = discover_projects(&root).unwrap();
+        let files = projects
+            .iter()
+            .map(|p| p.files.iter().map(|f| f.strip_prefix(&root).unwrap()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(files, vec![vec![Path::new("a.ts")], vec![Path::new("inner/c.ts")]]);
+    }
+}